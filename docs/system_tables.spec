@@ -13,6 +13,9 @@
 * `engine`: (_string_)
 * `owner`: (_unsigned_)
 * `description`: (_string_)
+* `defaults`: (_array_)
+* `partition`: (_map_)
+* `constraints`: (_array_)
 
 Индексы:
 
@@ -60,6 +63,67 @@
 * `_pico_routine_name` (unique), parts: `[name]`
 * `_pico_routine_owner_id` (non-unique), parts: `[owner]`
 
+### _pico_sequence
+
+Поля:
+
+* `id`: (_unsigned_)
+* `name`: (_string_)
+* `start`: (_integer_)
+* `increment`: (_integer_)
+* `allocated`: (_unsigned_)
+* `operable`: (_boolean_)
+* `schema_version`: (_unsigned_)
+* `owner`: (_unsigned_)
+
+Индексы:
+
+* `_pico_sequence_id` (unique), parts: `[id]`
+* `_pico_sequence_name` (unique), parts: `[name]`
+
+### _pico_view
+
+Поля:
+
+* `id`: (_unsigned_)
+* `name`: (_string_)
+* `query`: (_string_)
+* `operable`: (_boolean_)
+* `schema_version`: (_unsigned_)
+* `owner`: (_unsigned_)
+
+Индексы:
+
+* `_pico_view_id` (unique), parts: `[id]`
+* `_pico_view_name` (unique), parts: `[name]`
+
+### _pico_schema
+
+Поля:
+
+* `id`: (_unsigned_)
+* `name`: (_string_)
+* `operable`: (_boolean_)
+* `schema_version`: (_unsigned_)
+* `owner`: (_unsigned_)
+
+Индексы:
+
+* `_pico_schema_id` (unique), parts: `[id]`
+* `_pico_schema_name` (unique), parts: `[name]`
+
+### _pico_table_stats
+
+Поля:
+
+* `table_id`: (_unsigned_)
+* `rows_number`: (_unsigned_)
+* `columns`: (_map_)
+
+Индексы:
+
+* `_pico_table_stats_table_id` (unique), parts: `[table_id]`
+
 ### _pico_property
 
 Поля:
@@ -305,8 +305,8 @@ impl Router for RouterRuntime {
 impl Statistics for RouterRuntime {
     #[allow(unused_variables)]
     fn get_table_stats(&self, table_name: &str) -> Result<Option<Rc<TableStats>>, SbroadError> {
-        // Will be added later.
-        todo!()
+        // Statistics are not gathered in cartridge.
+        Ok(None)
     }
 
     #[allow(unused_variables)]
@@ -314,8 +314,8 @@ impl Statistics for RouterRuntime {
         &self,
         table_column_pair: &TableColumnPair,
     ) -> Result<Option<Rc<Box<dyn Any>>>, SbroadError> {
-        // Will be added later.
-        todo!()
+        // Statistics are not gathered in cartridge.
+        Ok(None)
     }
 
     #[allow(unused_variables)]
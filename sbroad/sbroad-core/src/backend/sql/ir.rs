@@ -21,7 +21,7 @@ use tarantool::tuple::{FunctionArgs, Tuple};
 use crate::errors::{Action, Entity, SbroadError};
 use crate::executor::engine::helpers::table_name;
 use crate::executor::ir::ExecutionPlan;
-use crate::ir::operator::OrderByType;
+use crate::ir::operator::{JoinKind, OrderByType};
 use crate::ir::value::Value;
 
 use super::space::{create_table, TableGuard};
@@ -125,6 +125,15 @@ impl ExecutionPlan {
     /// # Errors
     /// - IR plan is invalid
     pub fn to_params(&self) -> Result<Vec<Value>, SbroadError> {
+        let top_id = self.get_ir_plan().get_top()?;
+        self.subtree_params(top_id)
+    }
+
+    /// Collect parameters of the plan subtree (pointed by top).
+    ///
+    /// # Errors
+    /// - IR plan is invalid
+    pub fn subtree_params(&self, top_id: NodeId) -> Result<Vec<Value>, SbroadError> {
         let plan = self.get_ir_plan();
         let capacity = plan.nodes.len();
         let mut was: AHashSet<NodeId> = AHashSet::with_capacity(plan.constants.len());
@@ -145,7 +154,6 @@ impl ExecutionPlan {
             capacity,
             Box::new(filter),
         );
-        let top_id = plan.get_top()?;
         tree.populate_nodes(top_id);
         let nodes = tree.take_nodes();
         let mut params: Vec<Value> = Vec::with_capacity(nodes.len());
@@ -341,6 +349,7 @@ impl ExecutionPlan {
                 SyntaxData::From => sql.push_str("FROM"),
                 SyntaxData::Leading => sql.push_str("LEADING"),
                 SyntaxData::Limit(limit) => sql.push_str(&format_smolstr!("LIMIT {limit}")),
+                SyntaxData::Offset(offset) => sql.push_str(&format_smolstr!("OFFSET {offset}")),
                 SyntaxData::Both => sql.push_str("BOTH"),
                 SyntaxData::Trailing => sql.push_str("TRAILING"),
                 SyntaxData::Operator(s) => sql.push_str(s.as_str()),
@@ -413,9 +422,18 @@ impl ExecutionPlan {
                                 sql.push_str("DELETE FROM ");
                                 push_identifier(&mut sql, relation)
                             }
-                            Relational::Join(Join { kind, .. }) => sql.push_str(
-                                format!("{} JOIN", kind.to_string().to_uppercase()).as_str(),
-                            ),
+                            Relational::Join(Join { kind, .. }) => match kind {
+                                JoinKind::Inner => sql.push_str("INNER JOIN"),
+                                // Local SQL engine supports only inner and left joins:
+                                // children of the right join are swapped in the syntax
+                                // tree, while full join is executed as a left one and
+                                // the missing inner rows are added on the router.
+                                JoinKind::LeftOuter
+                                | JoinKind::RightOuter
+                                | JoinKind::FullOuter => {
+                                    sql.push_str("LEFT JOIN");
+                                }
+                            },
                             Relational::Projection { .. }
                             | Relational::SelectWithoutScan { .. } => sql.push_str("SELECT"),
                             Relational::ScanRelation(ScanRelation { relation, .. }) => {
@@ -35,8 +35,11 @@ fn check_sql_with_snapshot(
     assert_eq!(expected, sql,);
 }
 
+mod cte;
 mod except;
 mod inner_join;
+mod limit;
+mod outer_join;
 mod projection;
 mod selection;
 mod sub_query;
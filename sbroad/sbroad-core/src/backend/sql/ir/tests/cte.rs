@@ -0,0 +1,53 @@
+use super::*;
+use crate::ir::tree::Snapshot;
+use crate::ir::value::Value;
+
+#[test]
+fn recursive_cte_latest() {
+    let query = r#"WITH RECURSIVE cte (a) AS (
+            SELECT "a" FROM "global_t"
+            UNION ALL
+            SELECT "a" + 1 FROM cte WHERE "a" < 10
+        )
+        SELECT * FROM cte"#;
+
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {} {} {} {} {}",
+            r#"SELECT "cte"."a" FROM (WITH RECURSIVE "cte" AS"#,
+            r#"(SELECT "global_t"."a" FROM "global_t""#,
+            r#"UNION ALL"#,
+            r#"SELECT ("cte"."a") + (?) as "col_1" FROM "cte" as "cte""#,
+            r#"WHERE ("cte"."a") < (?) LIMIT 5001)"#,
+            r#"SELECT * FROM "cte" WHERE CASE WHEN (SELECT COUNT(*) FROM "cte") > 5000"#,
+            r#"THEN ".proc_recursion_row_max_exceeded"(5000) ELSE TRUE END) as "cte""#,
+        ),
+        vec![Value::from(1_u64), Value::from(10_u64)],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Latest);
+}
+
+#[test]
+fn recursive_cte_without_row_max() {
+    let query = r#"WITH RECURSIVE cte (a) AS (
+            SELECT "a" FROM "global_t"
+            UNION ALL
+            SELECT "a" + 1 FROM cte WHERE "a" < 10
+        )
+        SELECT * FROM cte
+        OPTION (sql_recursion_row_max = 0)"#;
+
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {} {} {} {}",
+            r#"SELECT "cte"."a" FROM (WITH RECURSIVE "cte" AS"#,
+            r#"(SELECT "global_t"."a" FROM "global_t""#,
+            r#"UNION ALL"#,
+            r#"SELECT ("cte"."a") + (?) as "col_1" FROM "cte" as "cte""#,
+            r#"WHERE ("cte"."a") < (?) LIMIT 9223372036854775807)"#,
+            r#"SELECT * FROM "cte") as "cte""#,
+        ),
+        vec![Value::from(1_u64), Value::from(10_u64)],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Latest);
+}
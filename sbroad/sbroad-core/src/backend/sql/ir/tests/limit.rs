@@ -0,0 +1,35 @@
+use super::*;
+use crate::ir::tree::Snapshot;
+
+#[test]
+fn limit_offset_latest() {
+    let query = r#"SELECT "product_code" FROM "hash_testing" LIMIT 10 OFFSET 5"#;
+
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {}",
+            r#"SELECT "hash_testing"."product_code""#,
+            r#"FROM "hash_testing""#,
+            r#"LIMIT 10 OFFSET 5"#,
+        ),
+        vec![],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Latest);
+}
+
+#[test]
+fn offset_latest() {
+    let query = r#"SELECT "product_code" FROM "hash_testing" OFFSET 5"#;
+
+    // Local SQL doesn't support OFFSET without LIMIT.
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {}",
+            r#"SELECT "hash_testing"."product_code""#,
+            r#"FROM "hash_testing""#,
+            r#"LIMIT 9223372036854775807 OFFSET 5"#,
+        ),
+        vec![],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Latest);
+}
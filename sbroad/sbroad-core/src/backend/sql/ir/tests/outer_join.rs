@@ -0,0 +1,49 @@
+use super::*;
+use crate::ir::tree::Snapshot;
+use crate::ir::value::Value;
+
+#[test]
+fn right_join_latest() {
+    let query = r#"SELECT "product_code" FROM "hash_testing" right join "history"
+        on "hash_testing"."identification_number" = "history"."id"
+        WHERE "product_code" = 'a'"#;
+
+    // Right join is generated as a left join with swapped children.
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {} {} {} {} {} {}",
+            r#"SELECT "hash_testing"."product_code""#,
+            r#"FROM (SELECT "history"."id" FROM "history") as "history""#,
+            r#"LEFT JOIN (SELECT "hash_testing"."identification_number","#,
+            r#""hash_testing"."product_code","#,
+            r#""hash_testing"."product_units","#,
+            r#""hash_testing"."sys_op" FROM "hash_testing") as "hash_testing""#,
+            r#"ON ("hash_testing"."identification_number") = ("history"."id")"#,
+            r#"WHERE ("hash_testing"."product_code") = (?)"#,
+        ),
+        vec![Value::from("a")],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Latest);
+}
+
+#[test]
+fn full_join_latest() {
+    let query = r#"SELECT "product_code" FROM "hash_testing" full join "history"
+        on "hash_testing"."identification_number" = "history"."id""#;
+
+    // Missing inner rows of the full join are added on the router.
+    let expected = PatternWithParams::new(
+        format!(
+            "{} {} {} {} {} {} {}",
+            r#"SELECT "hash_testing"."product_code""#,
+            r#"FROM (SELECT "hash_testing"."identification_number","#,
+            r#""hash_testing"."product_code","#,
+            r#""hash_testing"."product_units","#,
+            r#""hash_testing"."sys_op" FROM "hash_testing") as "hash_testing""#,
+            r#"LEFT JOIN (SELECT "history"."id" FROM "history") as "history""#,
+            r#"ON ("hash_testing"."identification_number") = ("history"."id")"#,
+        ),
+        vec![],
+    );
+    check_sql_with_snapshot(query, vec![], expected, Snapshot::Latest);
+}
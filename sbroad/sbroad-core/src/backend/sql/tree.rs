@@ -1,16 +1,17 @@
 use crate::errors::{Entity, SbroadError};
 use crate::executor::ir::ExecutionPlan;
+use crate::frontend::sql::RECURSION_ROW_MAX_EXCEEDED_FUNCTION;
 use crate::ir::expression::{FunctionFeature, TrimKind};
 use crate::ir::node::expression::Expression;
 use crate::ir::node::relational::Relational;
 use crate::ir::node::{
     Alias, ArithmeticExpr, BoolExpr, Bound, BoundType, Case, Cast, Concat, Except, FrameType,
     GroupBy, Having, Intersect, Join, Like, Limit, Motion, NamedWindows, Node, NodeId, OrderBy,
-    Over, Projection, Reference, ReferenceAsteriskSource, Row, ScalarFunction, ScanCte,
-    ScanRelation, ScanSubQuery, SelectWithoutScan, Selection, Trim, UnaryExpr, Union, UnionAll,
-    Values, ValuesRow, Window,
+    Over, Projection, RecursiveCte, Reference, ReferenceAsteriskSource, Row, ScalarFunction,
+    ScanCte, ScanRelation, ScanSubQuery, SelectWithoutScan, Selection, Trim, UnaryExpr, Union,
+    UnionAll, Values, ValuesRow, Window,
 };
-use crate::ir::operator::{OrderByElement, OrderByEntity, OrderByType, Unary};
+use crate::ir::operator::{JoinKind, OrderByElement, OrderByEntity, OrderByType, Unary};
 use crate::ir::transformation::redistribution::{MotionOpcode, MotionPolicy};
 use crate::ir::tree::traversal::{LevelNode, PostOrder};
 use crate::ir::tree::Snapshot;
@@ -96,6 +97,8 @@ pub enum SyntaxData {
     Leading,
     /// "limit"
     Limit(u64),
+    /// "offset"
+    Offset(u64),
     /// "both"
     Both,
     /// "trailing"
@@ -421,6 +424,14 @@ impl SyntaxNode {
         }
     }
 
+    fn new_offset(offset: u64) -> Self {
+        SyntaxNode {
+            data: SyntaxData::Offset(offset),
+            left: None,
+            right: Vec::new(),
+        }
+    }
+
     fn new_both() -> Self {
         SyntaxNode {
             data: SyntaxData::Both,
@@ -1087,19 +1098,76 @@ impl<'p> SyntaxPlan<'p> {
     }
 
     fn add_cte(&mut self, id: NodeId) {
-        let (_, cte) = self.prologue_rel(id);
-        let Relational::ScanCte(ScanCte { alias, child, .. }) = cte else {
+        let (plan, cte) = self.prologue_rel(id);
+        let Relational::ScanCte(ScanCte {
+            alias,
+            child,
+            recursive,
+            ..
+        }) = cte
+        else {
             panic!("expected CTE node");
         };
-        let (child, alias) = (*child, alias.clone());
+        let (child, alias, recursive) = (*child, alias.clone(), recursive.clone());
+        let limit = match plan.get_relation_node(child) {
+            Ok(Relational::Limit(Limit { limit, .. })) => *limit,
+            _ => None,
+        };
         let child_sn_id = self.pop_from_stack(child, id);
         let arena = &mut self.nodes;
-        let children: Vec<usize> = vec![
-            arena.push_sn_non_plan(SyntaxNode::new_open()),
-            child_sn_id,
-            arena.push_sn_non_plan(SyntaxNode::new_close()),
-            arena.push_sn_non_plan(SyntaxNode::new_alias(alias)),
-        ];
+        let children: Vec<usize> = match recursive {
+            None => vec![
+                arena.push_sn_non_plan(SyntaxNode::new_open()),
+                child_sn_id,
+                arena.push_sn_non_plan(SyntaxNode::new_close()),
+                arena.push_sn_non_plan(SyntaxNode::new_alias(alias)),
+            ],
+            Some(RecursiveCte {
+                name,
+                is_self_reference: false,
+            }) => {
+                // Recursive CTE is inlined with its own WITH clause:
+                // (WITH RECURSIVE "name" AS (anchor UNION ALL recursive LIMIT n + 1)
+                // SELECT * FROM "name" WHERE <no more than n rows>) as "alias"
+                // The local SQL can't raise errors, so the exceeded row max
+                // is reported by the function called in the filter.
+                let select = match limit {
+                    Some(limit) => {
+                        let row_max = limit - 1;
+                        format_smolstr!(
+                            "SELECT * FROM \"{name}\" WHERE CASE WHEN \
+                            (SELECT COUNT(*) FROM \"{name}\") > {row_max} \
+                            THEN \".{RECURSION_ROW_MAX_EXCEEDED_FUNCTION}\"({row_max}) \
+                            ELSE TRUE END"
+                        )
+                    }
+                    None => format_smolstr!("SELECT * FROM \"{name}\""),
+                };
+                vec![
+                    arena.push_sn_non_plan(SyntaxNode::new_open()),
+                    arena.push_sn_non_plan(SyntaxNode::new_inline(&format_smolstr!(
+                        "WITH RECURSIVE \"{name}\" AS"
+                    ))),
+                    arena.push_sn_non_plan(SyntaxNode::new_open()),
+                    child_sn_id,
+                    arena.push_sn_non_plan(SyntaxNode::new_close()),
+                    arena.push_sn_non_plan(SyntaxNode::new_inline(&select)),
+                    arena.push_sn_non_plan(SyntaxNode::new_close()),
+                    arena.push_sn_non_plan(SyntaxNode::new_alias(alias)),
+                ]
+            }
+            Some(RecursiveCte {
+                name,
+                is_self_reference: true,
+            }) => {
+                // The anchor subtree only describes the output tuple of the
+                // self-reference, so its syntax nodes are not used.
+                vec![
+                    arena.push_sn_non_plan(SyntaxNode::new_inline(&format_smolstr!("\"{name}\""))),
+                    arena.push_sn_non_plan(SyntaxNode::new_alias(alias)),
+                ]
+            }
+        };
         let sn = SyntaxNode::new_pointer(id, None, children);
         arena.push_sn_plan(sn);
     }
@@ -1201,6 +1269,7 @@ impl<'p> SyntaxPlan<'p> {
         let Relational::Join(Join {
             children,
             condition,
+            kind,
             ..
         }) = join
         else {
@@ -1212,9 +1281,15 @@ impl<'p> SyntaxPlan<'p> {
         };
         let inner_plan_id = *children.get(1).expect("JOIN inner child");
         let outer_plan_id = *children.first().expect("JOIN outer child");
+        let is_right_join = matches!(kind, JoinKind::RightOuter);
         let cond_sn_id = self.pop_from_stack(cond_plan_id, id);
-        let inner_sn_id = self.pop_from_stack(inner_plan_id, id);
-        let outer_sn_id = self.pop_from_stack(outer_plan_id, id);
+        let mut inner_sn_id = self.pop_from_stack(inner_plan_id, id);
+        let mut outer_sn_id = self.pop_from_stack(outer_plan_id, id);
+        if is_right_join {
+            // Local SQL engine doesn't support right joins,
+            // so we generate a left join with swapped children.
+            std::mem::swap(&mut inner_sn_id, &mut outer_sn_id);
+        }
         let arena = &mut self.nodes;
         let sn = SyntaxNode::new_pointer(
             id,
@@ -1577,16 +1652,28 @@ impl<'p> SyntaxPlan<'p> {
 
     fn add_limit(&mut self, id: NodeId) {
         let (_, limit) = self.prologue_rel(id);
-        let Relational::Limit(Limit { limit, child, .. }) = limit else {
+        let Relational::Limit(Limit {
+            limit,
+            offset,
+            child,
+            ..
+        }) = limit
+        else {
             panic!("expected LIMIT node");
         };
-        let (limit, child) = (*limit, *child);
+        let (limit, offset, child) = (*limit, *offset, *child);
         let child_sn_id = self.pop_from_stack(child, id);
         let arena = &mut self.nodes;
-        let children: Vec<usize> = vec![
+        // Local SQL engine doesn't support OFFSET without LIMIT,
+        // so we use the maximum possible limit in this case.
+        let limit = limit.unwrap_or(i64::MAX as u64);
+        let mut children: Vec<usize> = vec![
             child_sn_id,
             arena.push_sn_non_plan(SyntaxNode::new_limit(limit)),
         ];
+        if offset > 0 {
+            children.push(arena.push_sn_non_plan(SyntaxNode::new_offset(offset)));
+        }
         let sn = SyntaxNode::new_pointer(id, None, children);
         arena.push_sn_plan(sn);
     }
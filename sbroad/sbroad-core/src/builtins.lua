@@ -1,5 +1,7 @@
+local decimal = require('decimal')
 local dt = require('datetime')
 local helper = require('sbroad.helper')
+local json = require('json')
 
 -- Builtin sbroad funcs implemented in LUA
 local builtins = {}
@@ -40,6 +42,790 @@ builtins.SUBSTRING_TO_REGEXP = function(string, pattern, expr)
 end
 
 
+-- Aggregates implemented in lua.
+--
+-- They are registered as SQL aggregate functions: the aggregate
+-- receives the state as the last argument and returns the new one.
+-- Two stage aggregation computes the states on the storages and
+-- merges them on the router with `*_merge` aggregates, then
+-- `*_finalize` functions convert the state into the result.
+
+local function is_null(v)
+  return v == nil or v == box.NULL
+end
+
+local function to_double(v)
+  if decimal.is_decimal(v) then
+    return tonumber(tostring(v))
+  end
+  return tonumber(v)
+end
+
+-- NULL keys are the largest ones, like in ORDER BY.
+local function key_less(a, b, is_desc)
+  if is_null(a) then
+    return is_desc and not is_null(b)
+  end
+  if is_null(b) then
+    return not is_desc
+  end
+  if is_desc then
+    return a > b
+  end
+  return a < b
+end
+
+local function sort_items(items, is_desc)
+  table.sort(items, function(a, b)
+    return key_less(a.key, b.key, is_desc)
+  end)
+end
+
+-- The state of ordered aggregates is an array of {key, value}
+-- pairs sorted by key. The pair is inserted after the ones with
+-- equal keys, so the order of the input is kept for them.
+local function insert_sorted(items, key, value, is_desc)
+  items = items or {}
+  local lo, hi = 1, #items + 1
+  while lo < hi do
+    local mid = math.floor((lo + hi) / 2)
+    if key_less(key, items[mid][1], is_desc) then
+      hi = mid
+    else
+      lo = mid + 1
+    end
+  end
+  table.insert(items, lo, { key, value })
+  return items
+end
+
+builtins.ARRAY_AGG = function(value, state)
+  state = state or {}
+  table.insert(state, is_null(value) and box.NULL or value)
+  return state
+end
+
+builtins.ARRAY_AGG_MERGE = function(values, state)
+  if is_null(values) then
+    return state
+  end
+  state = state or {}
+  for _, value in ipairs(values) do
+    table.insert(state, value)
+  end
+  return state
+end
+
+builtins.ORDERED_ARRAY_AGG = function(value, key, is_desc, state)
+  return insert_sorted(state, key, is_null(value) and box.NULL or value, is_desc)
+end
+
+builtins.ORDERED_STRING_AGG = function(value, _, key, is_desc, state)
+  if is_null(value) then
+    return state
+  end
+  return insert_sorted(state, key, value, is_desc)
+end
+
+builtins.ORDERED_AGG_MERGE = function(items, is_desc, state)
+  if is_null(items) then
+    return state
+  end
+  if state == nil then
+    return items
+  end
+  local res = {}
+  local i, j = 1, 1
+  while i <= #state or j <= #items do
+    -- Pairs of the state go first for equal keys.
+    if j > #items or (i <= #state and not key_less(items[j][1], state[i][1], is_desc)) then
+      table.insert(res, state[i])
+      i = i + 1
+    else
+      table.insert(res, items[j])
+      j = j + 1
+    end
+  end
+  return res
+end
+
+builtins.ORDERED_ARRAY_AGG_FINALIZE = function(items)
+  if is_null(items) then
+    return nil
+  end
+  local res = {}
+  for i, item in ipairs(items) do
+    res[i] = item[2]
+  end
+  return res
+end
+
+builtins.ORDERED_STRING_AGG_FINALIZE = function(items, sep)
+  if is_null(items) then
+    return nil
+  end
+  local values = {}
+  for i, item in ipairs(items) do
+    values[i] = item[2]
+  end
+  return table.concat(values, is_null(sep) and '' or sep)
+end
+
+local function percentile_append(fraction, key, is_desc, state)
+  if is_null(fraction) then
+    return state
+  end
+  if fraction < 0 or fraction > 1 then
+    error(string.format('percentile value %s is not between 0 and 1', fraction))
+  end
+  state = state or { items = {} }
+  state.fraction = tonumber(fraction)
+  state.is_desc = is_desc
+  -- NULL values are ignored.
+  if not is_null(key) then
+    table.insert(state.items, { key = key })
+  end
+  return state
+end
+
+builtins.PERCENTILE_CONT = percentile_append
+builtins.PERCENTILE_DISC = percentile_append
+
+builtins.PERCENTILE_CONT_FINALIZE = function(state)
+  if is_null(state) or #state.items == 0 then
+    return nil
+  end
+  sort_items(state.items, state.is_desc)
+  local pos = 1 + state.fraction * (#state.items - 1)
+  local lower = math.floor(pos)
+  local upper = math.ceil(pos)
+  local lower_value = to_double(state.items[lower].key)
+  local upper_value = to_double(state.items[upper].key)
+  return lower_value + (upper_value - lower_value) * (pos - lower)
+end
+
+builtins.PERCENTILE_DISC_FINALIZE = function(state)
+  if is_null(state) or #state.items == 0 then
+    return nil
+  end
+  sort_items(state.items, state.is_desc)
+  local pos = math.max(1, math.ceil(state.fraction * #state.items))
+  return state.items[pos].key
+end
+
+-- The state of var_samp, var_pop, stddev_samp and stddev_pop aggregates
+-- is {count, mean, M2}, where M2 is the sum of squared differences from
+-- the mean. It is updated with Welford's algorithm and the states of
+-- different storages are merged with Chan's formula, so the result
+-- doesn't suffer from the cancellation of the sum of squares.
+builtins.VARIANCE_STATE = function(value, state)
+  if is_null(value) then
+    return state
+  end
+  local x = to_double(value)
+  local n, mean, m2 = 0, 0, 0
+  if not is_null(state) then
+    n, mean, m2 = state[1], state[2], state[3]
+  end
+  n = n + 1
+  local delta = x - mean
+  mean = mean + delta / n
+  m2 = m2 + delta * (x - mean)
+  return { n, mean, m2 }
+end
+
+builtins.VARIANCE_MERGE = function(part, state)
+  if is_null(part) then
+    return state
+  end
+  if is_null(state) then
+    return part
+  end
+  local na, ma, m2a = state[1], state[2], state[3]
+  local nb, mb, m2b = part[1], part[2], part[3]
+  local n = na + nb
+  local delta = mb - ma
+  local mean = ma + delta * nb / n
+  local m2 = m2a + m2b + delta * delta * na * nb / n
+  return { n, mean, m2 }
+end
+
+builtins.VARIANCE_FINALIZE = function(state, is_sample)
+  if is_null(state) then
+    return nil
+  end
+  local n, m2 = state[1], state[3]
+  if n == 0 or (is_sample and n == 1) then
+    return nil
+  end
+  if is_sample then
+    return m2 / (n - 1)
+  end
+  return m2 / n
+end
+
+builtins.STDDEV_FINALIZE = function(state, is_sample)
+  local variance = builtins.VARIANCE_FINALIZE(state, is_sample)
+  if variance == nil then
+    return nil
+  end
+  return math.sqrt(variance)
+end
+
+-- JSON functions.
+--
+-- JSON documents are either maps and arrays or their text representation,
+-- which is decoded on the fly. Objects and arrays are told apart by the
+-- serialization hint set by msgpack decoder or by sequential keys.
+
+local function json_value(v)
+  if type(v) == 'string' then
+    local ok, decoded = pcall(json.decode, v)
+    if ok then
+      return decoded
+    end
+  end
+  return v
+end
+
+local function is_array(t)
+  local mt = getmetatable(t)
+  if mt ~= nil and mt.__serialize ~= nil then
+    local hint = mt.__serialize
+    return hint == 'seq' or hint == 'sequence' or hint == 'array'
+  end
+  return #t > 0
+end
+
+local function json_text(v)
+  if is_null(v) then
+    return nil
+  end
+  if type(v) == 'string' then
+    return v
+  end
+  return json.encode(v)
+end
+
+-- Get an object field by name or an array element by index. Like in
+-- PostgreSQL, array indexes start from zero and negative ones count
+-- from the end of the array.
+local function json_step(doc, key)
+  if type(doc) ~= 'table' or is_null(key) then
+    return nil
+  end
+  if not is_array(doc) then
+    if type(key) ~= 'string' then
+      return nil
+    end
+    return doc[key]
+  end
+  local idx = tonumber(key)
+  if idx == nil or idx ~= math.floor(idx) then
+    return nil
+  end
+  if idx < 0 then
+    idx = #doc + idx
+  end
+  return doc[idx + 1]
+end
+
+-- Path is a text array literal: '{a,b,0}'.
+local function json_path(path)
+  local elems = {}
+  path = string.gsub(path, '^%s*{(.*)}%s*$', '%1')
+  for elem in string.gmatch(path, '[^,]+') do
+    table.insert(elems, (string.gsub(elem, '^%s*(.-)%s*$', '%1')))
+  end
+  return elems
+end
+
+local function json_contains(a, b)
+  if type(a) ~= 'table' or type(b) ~= 'table' then
+    if type(a) == 'table' or type(b) == 'table' then
+      return false
+    end
+    return a == b
+  end
+  if is_array(b) then
+    if not is_array(a) then
+      return false
+    end
+    for _, bv in ipairs(b) do
+      local found = false
+      for _, av in ipairs(a) do
+        if json_contains(av, bv) then
+          found = true
+          break
+        end
+      end
+      if not found then
+        return false
+      end
+    end
+    return true
+  end
+  if is_array(a) then
+    return false
+  end
+  for k, bv in pairs(b) do
+    if type(a[k]) == 'nil' or not json_contains(a[k], bv) then
+      return false
+    end
+  end
+  return true
+end
+
+builtins.JSON_GET = function(doc, key)
+  return json_step(json_value(doc), key)
+end
+
+builtins.JSON_GET_TEXT = function(doc, key)
+  return json_text(json_step(json_value(doc), key))
+end
+
+builtins.JSON_GET_PATH = function(doc, path)
+  if is_null(path) then
+    return nil
+  end
+  doc = json_value(doc)
+  for _, elem in ipairs(json_path(path)) do
+    doc = json_step(doc, elem)
+  end
+  return doc
+end
+
+builtins.JSON_GET_PATH_TEXT = function(doc, path)
+  return json_text(builtins.JSON_GET_PATH(doc, path))
+end
+
+builtins.JSON_CONTAINS = function(a, b)
+  if is_null(a) or is_null(b) then
+    return nil
+  end
+  return json_contains(json_value(a), json_value(b))
+end
+
+builtins.JSON_EXTRACT_PATH = function(doc, elem)
+  return json_step(json_value(doc), elem)
+end
+
+builtins.JSON_EXTRACT_PATH_TEXT = function(doc, elem)
+  return json_text(json_step(json_value(doc), elem))
+end
+
+builtins.JSON_ARRAY_LENGTH = function(doc)
+  if is_null(doc) then
+    return nil
+  end
+  doc = json_value(doc)
+  if type(doc) ~= 'table' or not is_array(doc) then
+    error('cannot get array length of a non-array')
+  end
+  return #doc
+end
+
+builtins.JSON_TYPEOF = function(doc)
+  if is_null(doc) then
+    return nil
+  end
+  doc = json_value(doc)
+  if is_null(doc) then
+    return 'null'
+  end
+  if type(doc) == 'table' then
+    return is_array(doc) and 'array' or 'object'
+  end
+  if type(doc) == 'string' or type(doc) == 'boolean' then
+    return type(doc)
+  end
+  return 'number'
+end
+
+-- Steps of json_build_object and json_build_array: the document
+-- is built by a chain of calls starting from NULL.
+builtins.JSON_OBJECT_SET = function(obj, key, value)
+  if is_null(key) then
+    error('null value cannot be used as an object key')
+  end
+  if is_null(obj) then
+    obj = setmetatable({}, { __serialize = 'map' })
+  end
+  obj[key] = is_null(value) and box.NULL or value
+  return obj
+end
+
+builtins.JSON_ARRAY_APPEND = function(arr, value)
+  if is_null(arr) then
+    arr = setmetatable({}, { __serialize = 'seq' })
+  end
+  table.insert(arr, is_null(value) and box.NULL or value)
+  return arr
+end
+
+-- json_build_object and json_build_array without arguments.
+builtins.JSON_OBJECT_NEW = function()
+  return setmetatable({}, { __serialize = 'map' })
+end
+
+builtins.JSON_ARRAY_NEW = function()
+  return setmetatable({}, { __serialize = 'seq' })
+end
+
+-- json_array_elements returns the whole array, it is expanded into rows
+-- by the router. Nested objects and arrays are returned as JSON text,
+-- as maps can't be passed between the instances in virtual tables.
+builtins.JSON_ARRAY_ELEMENTS = function(doc)
+  if is_null(doc) then
+    return nil
+  end
+  doc = json_value(doc)
+  if type(doc) ~= 'table' or not is_array(doc) then
+    error('cannot extract elements from a non-array')
+  end
+  local elems = setmetatable({}, { __serialize = 'seq' })
+  for i, v in ipairs(doc) do
+    if type(v) == 'table' then
+      v = json.encode(v)
+    end
+    elems[i] = v
+  end
+  return elems
+end
+
+-- Date and time functions.
+--
+-- Fields of datetime values are taken in the time zone of the value.
+-- Intervals are decomposed into months, days and nanoseconds, like in
+-- PostgreSQL, so that `date_part` returns the same results for them.
+
+local NSEC_PER_SEC = 1e9
+
+-- Integer division rounding toward zero.
+local function div(a, b)
+  local q = a / b
+  if q < 0 then
+    return math.ceil(q)
+  end
+  return math.floor(q)
+end
+
+local function rem(a, b)
+  return a - div(a, b) * b
+end
+
+local function is_leap(year)
+  return year % 4 == 0 and (year % 100 ~= 0 or year % 400 == 0)
+end
+
+local function days_in_month(year, month)
+  local days = { 31, 28, 31, 30, 31, 30, 31, 31, 30, 31, 30, 31 }
+  if month == 2 and is_leap(year) then
+    return 29
+  end
+  return days[month]
+end
+
+-- Day of the week: 0 is Sunday, 6 is Saturday.
+local function day_of_week(d)
+  local days = math.floor((d.epoch + d.tzoffset * 60) / 86400)
+  -- 1970-01-01 was Thursday.
+  return (days + 4) % 7
+end
+
+local function iso_weeks_in_year(year)
+  local function p(y)
+    return (y + math.floor(y / 4) - math.floor(y / 100) + math.floor(y / 400)) % 7
+  end
+  if p(year) == 4 or p(year - 1) == 3 then
+    return 53
+  end
+  return 52
+end
+
+local function iso_week(d)
+  local isodow = day_of_week(d)
+  if isodow == 0 then
+    isodow = 7
+  end
+  local week = math.floor((d.yday - isodow + 10) / 7)
+  if week < 1 then
+    return iso_weeks_in_year(d.year - 1)
+  end
+  if week > iso_weeks_in_year(d.year) then
+    return 1
+  end
+  return week
+end
+
+local function datetime_part(field, d)
+  local dow = day_of_week(d)
+  if field == 'year' or field == 'years' then
+    return d.year
+  elseif field == 'month' or field == 'months' then
+    return d.month
+  elseif field == 'day' or field == 'days' then
+    return d.day
+  elseif field == 'hour' or field == 'hours' then
+    return d.hour
+  elseif field == 'minute' or field == 'minutes' then
+    return d.min
+  elseif field == 'second' or field == 'seconds' then
+    return d.sec + d.nsec / NSEC_PER_SEC
+  elseif field == 'millisecond' or field == 'milliseconds' then
+    return d.sec * 1e3 + d.nsec / 1e6
+  elseif field == 'microsecond' or field == 'microseconds' then
+    return d.sec * 1e6 + d.nsec / 1e3
+  elseif field == 'dow' then
+    return dow
+  elseif field == 'isodow' then
+    return dow == 0 and 7 or dow
+  elseif field == 'doy' then
+    return d.yday
+  elseif field == 'week' then
+    return iso_week(d)
+  elseif field == 'quarter' then
+    return math.floor((d.month - 1) / 3) + 1
+  elseif field == 'decade' then
+    return math.floor(d.year / 10)
+  elseif field == 'century' then
+    return math.ceil(d.year / 100)
+  elseif field == 'millennium' then
+    return math.ceil(d.year / 1000)
+  elseif field == 'epoch' then
+    return d.epoch + d.nsec / NSEC_PER_SEC
+  elseif field == 'timezone' then
+    return d.tzoffset * 60
+  end
+  error(string.format('unit "%s" not recognized for type datetime', field))
+end
+
+local function interval_part(field, iv)
+  local months = iv.year * 12 + iv.month
+  local days = iv.week * 7 + iv.day
+  local nsec = ((iv.hour * 60 + iv.min) * 60 + iv.sec) * NSEC_PER_SEC + iv.nsec
+  local year = div(months, 12)
+  if field == 'year' or field == 'years' then
+    return year
+  elseif field == 'month' or field == 'months' then
+    return rem(months, 12)
+  elseif field == 'day' or field == 'days' then
+    return days
+  elseif field == 'hour' or field == 'hours' then
+    return div(nsec, 3600 * NSEC_PER_SEC)
+  elseif field == 'minute' or field == 'minutes' then
+    return rem(div(nsec, 60 * NSEC_PER_SEC), 60)
+  elseif field == 'second' or field == 'seconds' then
+    return rem(nsec, 60 * NSEC_PER_SEC) / NSEC_PER_SEC
+  elseif field == 'millisecond' or field == 'milliseconds' then
+    return rem(nsec, 60 * NSEC_PER_SEC) / 1e6
+  elseif field == 'microsecond' or field == 'microseconds' then
+    return rem(nsec, 60 * NSEC_PER_SEC) / 1e3
+  elseif field == 'quarter' then
+    return div(rem(months, 12), 3) + 1
+  elseif field == 'decade' then
+    return div(year, 10)
+  elseif field == 'century' then
+    return div(year, 100)
+  elseif field == 'millennium' then
+    return div(year, 1000)
+  elseif field == 'epoch' then
+    -- A year is 365.25 days and a month is 30 days.
+    return year * 365.25 * 86400 + rem(months, 12) * 30 * 86400
+      + days * 86400 + nsec / NSEC_PER_SEC
+  end
+  error(string.format('unit "%s" not recognized for type interval', field))
+end
+
+builtins.DATE_PART = function(field, v)
+  if is_null(field) or is_null(v) then
+    return nil
+  end
+  field = string.lower(field)
+  if dt.is_interval(v) then
+    return interval_part(field, v)
+  end
+  return datetime_part(field, v)
+end
+
+builtins.DATE_TRUNC = function(field, d)
+  if is_null(field) or is_null(d) then
+    return nil
+  end
+  field = string.lower(field)
+  local t = {
+    year = d.year, month = d.month, day = d.day,
+    hour = d.hour, min = d.min, sec = d.sec, nsec = d.nsec,
+    tzoffset = d.tzoffset,
+  }
+  local function reset(...)
+    for _, name in ipairs({...}) do
+      t[name] = (name == 'month' or name == 'day') and 1 or 0
+    end
+  end
+  if field == 'microseconds' then
+    t.nsec = t.nsec - t.nsec % 1e3
+  elseif field == 'milliseconds' then
+    t.nsec = t.nsec - t.nsec % 1e6
+  elseif field == 'second' then
+    reset('nsec')
+  elseif field == 'minute' then
+    reset('sec', 'nsec')
+  elseif field == 'hour' then
+    reset('min', 'sec', 'nsec')
+  elseif field == 'day' or field == 'week' then
+    reset('hour', 'min', 'sec', 'nsec')
+  elseif field == 'month' then
+    reset('day', 'hour', 'min', 'sec', 'nsec')
+  elseif field == 'quarter' then
+    t.month = t.month - (t.month - 1) % 3
+    reset('day', 'hour', 'min', 'sec', 'nsec')
+  elseif field == 'year' then
+    reset('month', 'day', 'hour', 'min', 'sec', 'nsec')
+  elseif field == 'decade' then
+    t.year = t.year - t.year % 10
+    reset('month', 'day', 'hour', 'min', 'sec', 'nsec')
+  elseif field == 'century' then
+    t.year = t.year - (t.year - 1) % 100
+    reset('month', 'day', 'hour', 'min', 'sec', 'nsec')
+  elseif field == 'millennium' then
+    t.year = t.year - (t.year - 1) % 1000
+    reset('month', 'day', 'hour', 'min', 'sec', 'nsec')
+  else
+    error(string.format('unit "%s" not recognized for type datetime', field))
+  end
+  local res = dt.new(t)
+  if field == 'week' then
+    -- Weeks start on Monday.
+    local dow = day_of_week(res)
+    res:sub({ day = dow == 0 and 6 or dow - 1 })
+  end
+  return res
+end
+
+-- Symbolic difference of two datetimes in years, months and days,
+-- borrowing from the larger units like PostgreSQL does.
+builtins.AGE = function(a, b)
+  if is_null(a) or is_null(b) then
+    return nil
+  end
+  if a.tzoffset ~= b.tzoffset then
+    b = dt.new({ timestamp = b.epoch, nsec = b.nsec, tzoffset = a.tzoffset })
+  end
+  local sign = 1
+  if a < b then
+    a, b = b, a
+    sign = -1
+  end
+  local nsec = a.nsec - b.nsec
+  local sec = a.sec - b.sec
+  local min = a.min - b.min
+  local hour = a.hour - b.hour
+  local day = a.day - b.day
+  local month = a.month - b.month
+  local year = a.year - b.year
+  if nsec < 0 then
+    nsec = nsec + NSEC_PER_SEC
+    sec = sec - 1
+  end
+  if sec < 0 then
+    sec = sec + 60
+    min = min - 1
+  end
+  if min < 0 then
+    min = min + 60
+    hour = hour - 1
+  end
+  if hour < 0 then
+    hour = hour + 24
+    day = day - 1
+  end
+  if day < 0 then
+    day = day + days_in_month(b.year, b.month)
+    month = month - 1
+  end
+  if month < 0 then
+    month = month + 12
+    year = year - 1
+  end
+  return dt.interval.new({
+    year = sign * year, month = sign * month, day = sign * day,
+    hour = sign * hour, min = sign * min, sec = sign * sec,
+    nsec = sign * nsec,
+  })
+end
+
+local datetime_functions = {
+  { name = 'date_part', impl = 'DATE_PART', returns = 'double',
+    param_list = {'string', 'any'} },
+  { name = 'date_trunc', impl = 'DATE_TRUNC', returns = 'datetime',
+    param_list = {'string', 'datetime'} },
+  { name = 'age', impl = 'AGE', returns = 'interval',
+    param_list = {'datetime', 'datetime'} },
+}
+
+local json_functions = {
+  { name = 'json_get', impl = 'JSON_GET', returns = 'any',
+    param_list = {'any', 'any'} },
+  { name = 'json_get_text', impl = 'JSON_GET_TEXT', returns = 'string',
+    param_list = {'any', 'any'} },
+  { name = 'json_get_path', impl = 'JSON_GET_PATH', returns = 'any',
+    param_list = {'any', 'string'} },
+  { name = 'json_get_path_text', impl = 'JSON_GET_PATH_TEXT', returns = 'string',
+    param_list = {'any', 'string'} },
+  { name = 'json_contains', impl = 'JSON_CONTAINS', returns = 'boolean',
+    param_list = {'any', 'any'} },
+  { name = 'json_extract_path', impl = 'JSON_EXTRACT_PATH', returns = 'any',
+    param_list = {'any', 'string'} },
+  { name = 'json_extract_path_text', impl = 'JSON_EXTRACT_PATH_TEXT', returns = 'string',
+    param_list = {'any', 'string'} },
+  { name = 'json_array_length', impl = 'JSON_ARRAY_LENGTH', returns = 'integer',
+    param_list = {'any'} },
+  { name = 'json_typeof', impl = 'JSON_TYPEOF', returns = 'string',
+    param_list = {'any'} },
+  { name = 'json_object_set', impl = 'JSON_OBJECT_SET', returns = 'map',
+    param_list = {'any', 'string', 'any'} },
+  { name = 'json_array_append', impl = 'JSON_ARRAY_APPEND', returns = 'array',
+    param_list = {'any', 'any'} },
+  { name = 'json_object_new', impl = 'JSON_OBJECT_NEW', returns = 'map',
+    param_list = {} },
+  { name = 'json_array_new', impl = 'JSON_ARRAY_NEW', returns = 'array',
+    param_list = {} },
+  { name = 'json_array_elements', impl = 'JSON_ARRAY_ELEMENTS', returns = 'array',
+    param_list = {'any'} },
+}
+
+local aggregates = {
+  { name = 'array_agg', impl = 'ARRAY_AGG', param_list = {'any'} },
+  { name = 'array_agg_merge', impl = 'ARRAY_AGG_MERGE', param_list = {'any'} },
+  { name = 'ordered_array_agg', impl = 'ORDERED_ARRAY_AGG',
+    param_list = {'any', 'any', 'boolean'} },
+  { name = 'ordered_string_agg', impl = 'ORDERED_STRING_AGG',
+    param_list = {'string', 'string', 'any', 'boolean'} },
+  { name = 'ordered_agg_merge', impl = 'ORDERED_AGG_MERGE',
+    param_list = {'any', 'boolean'} },
+  { name = 'percentile_cont', impl = 'PERCENTILE_CONT',
+    param_list = {'number', 'number', 'boolean'} },
+  { name = 'percentile_disc', impl = 'PERCENTILE_DISC',
+    param_list = {'number', 'any', 'boolean'} },
+  { name = 'variance_state', impl = 'VARIANCE_STATE', param_list = {'number'} },
+  { name = 'variance_merge', impl = 'VARIANCE_MERGE', param_list = {'any'} },
+}
+
+local aggregate_finalizers = {
+  { name = 'ordered_array_agg_finalize', impl = 'ORDERED_ARRAY_AGG_FINALIZE',
+    returns = 'array', param_list = {'any'} },
+  { name = 'ordered_string_agg_finalize', impl = 'ORDERED_STRING_AGG_FINALIZE',
+    returns = 'string', param_list = {'any', 'string'} },
+  { name = 'percentile_cont_finalize', impl = 'PERCENTILE_CONT_FINALIZE',
+    returns = 'double', param_list = {'any'} },
+  { name = 'percentile_disc_finalize', impl = 'PERCENTILE_DISC_FINALIZE',
+    returns = 'any', param_list = {'any'} },
+  { name = 'variance_finalize', impl = 'VARIANCE_FINALIZE',
+    returns = 'double', param_list = {'any', 'boolean'} },
+  { name = 'stddev_finalize', impl = 'STDDEV_FINALIZE',
+    returns = 'double', param_list = {'any', 'boolean'} },
+}
 
 local function init()
   -- cartridge
@@ -106,6 +892,37 @@ local function init()
       is_deterministic = true,
       if_not_exists=true
   })
+
+  for _, functions in ipairs({json_functions, datetime_functions, aggregate_finalizers}) do
+    for _, func in ipairs(functions) do
+      body = string.format("function(...) return %s.builtins.%s(...) end",
+      module, func.impl)
+      box.schema.func.create(func.name, {
+          language = 'LUA',
+          returns = func.returns,
+          body = body,
+          param_list = func.param_list,
+          exports = {'SQL'},
+          is_deterministic = true,
+          if_not_exists=true
+      })
+    end
+  end
+
+  for _, aggr in ipairs(aggregates) do
+    body = string.format("function(...) return %s.builtins.%s(...) end",
+    module, aggr.impl)
+    box.schema.func.create(aggr.name, {
+        language = 'LUA',
+        returns = 'any',
+        body = body,
+        param_list = aggr.param_list,
+        aggregate = 'group',
+        exports = {'SQL'},
+        is_deterministic = true,
+        if_not_exists=true
+    })
+  end
 end
 
 return {
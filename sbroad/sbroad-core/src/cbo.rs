@@ -11,8 +11,11 @@ use smol_str::SmolStr;
 
 use crate::cbo::histogram::{merge_histograms, Histogram, HistogramRowsNumberPair, Scalar};
 use crate::errors::{Entity, SbroadError};
+use crate::ir::node::NodeId;
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
+use tarantool::decimal::Decimal;
 
 /// Struct representing statistics for the whole table.
 #[derive(Debug, Clone, PartialEq)]
@@ -26,8 +29,20 @@ impl TableStats {
     pub fn new(rows_number: u64) -> Self {
         Self { rows_number }
     }
+
+    #[must_use]
+    pub fn rows_number(&self) -> u64 {
+        self.rows_number
+    }
 }
 
+/// Number of rows in the tables by their names.
+pub type TableRowsMap = HashMap<SmolStr, u64>;
+
+/// Selectivity of the `Selection` filters and `Join` conditions
+/// by the ids of the relational nodes.
+pub type SelectivityMap = HashMap<NodeId, Decimal>;
+
 /// Struct representing statistics for concrete column.
 ///
 /// The reason some values are stored in that structure and not in `Histogram`: some values may be
@@ -82,6 +97,18 @@ impl TableColumnPair {
     pub(crate) fn new(table_name: SmolStr, column_id: usize) -> Self {
         Self(table_name, column_id)
     }
+
+    /// Name of the table.
+    #[must_use]
+    pub fn table_name(&self) -> &str {
+        &self.0
+    }
+
+    /// Position of the column in the table.
+    #[must_use]
+    pub fn column_id(&self) -> usize {
+        self.1
+    }
 }
 
 /// Helper struct representing pair of (`TableStats`, `ColumnStats` (upcasted)).
@@ -185,6 +212,8 @@ pub fn merge_stats<T: Scalar>(
     ))
 }
 
+pub mod analyze;
+pub mod cardinality;
 pub mod helpers;
 pub mod histogram;
 pub mod selectivity;
@@ -0,0 +1,437 @@
+//! Statistics construction from the table samples gathered by `ANALYZE`.
+//!
+//! Every replicaset sends a random sample of its table rows along with the
+//! number of rows it stores. Each sampled row is weighted by the number of rows
+//! it stands for, so the most common values and the histogram describe the whole
+//! table even if the data is distributed unevenly between the replicasets.
+//!
+//! The logic follows `compute_scalar_stats` from `PostgreSQL` (`analyze.c`).
+
+use crate::cbo::histogram::normalization::DEFAULT_HISTOGRAM_BUCKETS_NUMBER;
+use crate::cbo::histogram::{Histogram, HistogramBuckets, Mcv, McvSet, Scalar};
+use crate::cbo::ColumnStats;
+use crate::errors::{Entity, SbroadError};
+use crate::ir::relation::Type;
+use crate::ir::value::double::Double;
+use crate::ir::value::Value;
+use serde::{Deserialize, Serialize};
+use smol_str::format_smolstr;
+use std::any::Any;
+use std::collections::HashSet;
+use std::rc::Rc;
+use tarantool::decimal::Decimal;
+
+/// Maximum number of rows sampled on a single replicaset.
+/// `PostgreSQL` samples `300 * default_statistics_target` rows.
+pub const DEFAULT_SAMPLE_ROWS_NUMBER: usize = 30_000;
+
+/// Maximum number of the most common values kept for a column.
+/// The same as `default_statistics_target` in `PostgreSQL`.
+pub const DEFAULT_MCV_NUMBER: usize = 100;
+
+/// Random sample of the table rows gathered on a single replicaset.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TableSample {
+    /// Number of rows in the table on the replicaset.
+    pub rows_number: u64,
+    /// Sampled rows with all the table columns in the order of the table format.
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Column statistics in a type-erased form suitable for storing in a system table.
+/// Converted into `ColumnStats` of the column type with `decode_column_stats`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct EncodedColumnStats {
+    /// Column MIN value.
+    pub min_value: Value,
+    /// Column MAX value.
+    pub max_value: Value,
+    /// Average size of column value in bytes.
+    pub avg_size: u64,
+    /// Fraction of NULL values among all column rows.
+    pub null_fraction: f64,
+    /// Number of distinct values divided by the number of non-NULL rows.
+    pub distinct_values_fraction: f64,
+    /// Most common values and their frequencies (fractions of all column rows).
+    pub most_common_values: Vec<(Value, f64)>,
+    /// Boundaries of the histogram buckets. The most common values
+    /// are not included in the histogram.
+    pub histogram_boundaries: Vec<Value>,
+}
+
+/// Scalar types that can be built from the column values.
+pub trait ScalarValue: Scalar + Sized {
+    /// Returns `None` if the value can't be represented by the type.
+    fn from_value(value: &Value) -> Option<Self>;
+
+    fn into_value(self) -> Value;
+}
+
+impl ScalarValue for bool {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::Boolean(self)
+    }
+}
+
+impl ScalarValue for i64 {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(i) => Some(*i),
+            Value::Unsigned(u) => i64::try_from(*u).ok(),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::Integer(self)
+    }
+}
+
+impl ScalarValue for u64 {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Unsigned(u) => Some(*u),
+            Value::Integer(i) => u64::try_from(*i).ok(),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::Unsigned(self)
+    }
+}
+
+impl ScalarValue for Decimal {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Decimal(d) => Some(*d),
+            Value::Integer(i) => Some(Decimal::from(*i)),
+            Value::Unsigned(u) => Some(Decimal::from(*u)),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::Decimal(self)
+    }
+}
+
+impl ScalarValue for Double {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Double(d) => Some(d.clone()),
+            Value::Integer(i) => Some(Double::from(*i)),
+            Value::Unsigned(u) => Some(Double::from(*u)),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::Double(self)
+    }
+}
+
+impl ScalarValue for String {
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            _ => None,
+        }
+    }
+
+    fn into_value(self) -> Value {
+        Value::String(self)
+    }
+}
+
+/// Approximate size of the value in bytes.
+fn value_size(value: &Value) -> u64 {
+    match value {
+        Value::Null | Value::Tuple(_) => 0,
+        Value::Boolean(_) => 1,
+        Value::Integer(_) | Value::Unsigned(_) | Value::Double(_) => 8,
+        Value::Decimal(_) | Value::Datetime(_) | Value::Interval(_) | Value::Uuid(_) => 16,
+        Value::String(s) => s.len() as u64,
+        Value::Varbinary(v) => v.len() as u64,
+    }
+}
+
+fn decimal_from_f64(value: f64) -> Result<Decimal, SbroadError> {
+    Decimal::try_from(value).map_err(|_| {
+        SbroadError::Invalid(
+            Entity::Statistics,
+            Some(format_smolstr!("Unable to cast {value} to decimal")),
+        )
+    })
+}
+
+/// Build statistics of the table column from the replicaset samples.
+///
+/// Returns `None` for the column types we don't gather statistics for
+/// and for the columns containing only NULLs.
+///
+/// # Errors
+/// - Sampled rows don't contain the column.
+/// - Sampled values don't match the column type.
+pub fn build_column_stats(
+    samples: &[TableSample],
+    column: usize,
+    column_type: &Type,
+) -> Result<Option<EncodedColumnStats>, SbroadError> {
+    match column_type {
+        Type::Boolean => build_typed_column_stats::<bool>(samples, column),
+        Type::Decimal => build_typed_column_stats::<Decimal>(samples, column),
+        Type::Double => build_typed_column_stats::<Double>(samples, column),
+        Type::Integer => build_typed_column_stats::<i64>(samples, column),
+        Type::String => build_typed_column_stats::<String>(samples, column),
+        Type::Unsigned => build_typed_column_stats::<u64>(samples, column),
+        Type::Any
+        | Type::Array
+        | Type::Datetime
+        | Type::Interval
+        | Type::Map
+        | Type::Uuid
+        | Type::Varbinary => Ok(None),
+    }
+}
+
+/// Column value met in the samples.
+struct SampledValue<T> {
+    value: T,
+    /// Number of table rows with this value.
+    weight: f64,
+    /// Number of sampled rows with this value.
+    count: usize,
+}
+
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+#[allow(clippy::cast_sign_loss, clippy::too_many_lines)]
+fn build_typed_column_stats<T: ScalarValue>(
+    samples: &[TableSample],
+    column: usize,
+) -> Result<Option<EncodedColumnStats>, SbroadError> {
+    let rows_number: u64 = samples.iter().map(|sample| sample.rows_number).sum();
+    if rows_number == 0 {
+        return Ok(None);
+    }
+
+    let mut values: Vec<(T, f64)> = Vec::new();
+    let mut total_size = 0.0;
+    for sample in samples {
+        if sample.rows.is_empty() {
+            continue;
+        }
+        // Every sampled row stands for `weight` rows of the replicaset.
+        let weight = sample.rows_number as f64 / sample.rows.len() as f64;
+        for row in &sample.rows {
+            let value = row.get(column).ok_or_else(|| {
+                SbroadError::Invalid(
+                    Entity::Statistics,
+                    Some(format_smolstr!("sampled row has no column {column}")),
+                )
+            })?;
+            if let Value::Null = value {
+                continue;
+            }
+            let scalar = T::from_value(value).ok_or_else(|| {
+                SbroadError::Invalid(
+                    Entity::Statistics,
+                    Some(format_smolstr!(
+                        "unexpected value {value} in the sample of column {column}"
+                    )),
+                )
+            })?;
+            total_size += weight * value_size(value) as f64;
+            values.push((scalar, weight));
+        }
+    }
+    if values.is_empty() {
+        return Ok(None);
+    }
+    let sampled_number = values.len();
+    let non_null_rows: f64 = values.iter().map(|(_, weight)| weight).sum();
+    let all_rows = rows_number as f64;
+
+    values.sort_by(|(left, _), (right, _)| left.cmp(right));
+    let mut sampled: Vec<SampledValue<T>> = Vec::new();
+    for (value, weight) in values {
+        match sampled.last_mut() {
+            Some(last) if last.value == value => {
+                last.weight += weight;
+                last.count += 1;
+            }
+            _ => sampled.push(SampledValue {
+                value,
+                weight,
+                count: 1,
+            }),
+        }
+    }
+    let (Some(first), Some(last)) = (sampled.first(), sampled.last()) else {
+        return Ok(None);
+    };
+    let min_value = first.value.clone().into_value();
+    let max_value = last.value.clone().into_value();
+
+    // Haas-Stokes estimator of the number of distinct values (the same as in `PostgreSQL`).
+    let distinct_in_sample = sampled.len() as f64;
+    let met_once = sampled.iter().filter(|v| v.count == 1).count() as f64;
+    let sampled_number = sampled_number as f64;
+    let distinct = if met_once >= sampled_number {
+        // All the sampled values are unique, suppose the column is unique.
+        non_null_rows
+    } else {
+        sampled_number * distinct_in_sample
+            / (sampled_number - met_once + met_once * sampled_number / non_null_rows)
+    };
+    let distinct = distinct.min(non_null_rows).max(distinct_in_sample);
+
+    // If all the distinct values fit into the list, there is no need
+    // in the histogram. Otherwise only the values met more than once and
+    // more often than an average one are treated as the most common.
+    let most_common: HashSet<usize> = if sampled.len() <= DEFAULT_MCV_NUMBER {
+        (0..sampled.len()).collect()
+    } else {
+        let avg_weight = non_null_rows / distinct_in_sample;
+        let mut candidates: Vec<usize> = (0..sampled.len())
+            .filter(|i| sampled[*i].count > 1 && sampled[*i].weight > avg_weight)
+            .collect();
+        candidates.sort_by(|l, r| sampled[*r].weight.total_cmp(&sampled[*l].weight));
+        candidates.truncate(DEFAULT_MCV_NUMBER);
+        candidates.into_iter().collect()
+    };
+
+    let mut most_common_values = Vec::with_capacity(most_common.len());
+    let mut rest = Vec::with_capacity(sampled.len() - most_common.len());
+    for (i, sampled_value) in sampled.into_iter().enumerate() {
+        if most_common.contains(&i) {
+            let frequency = sampled_value.weight / all_rows;
+            most_common_values.push((sampled_value.value.into_value(), frequency));
+        } else {
+            rest.push(sampled_value);
+        }
+    }
+
+    Ok(Some(EncodedColumnStats {
+        min_value,
+        max_value,
+        avg_size: (total_size / non_null_rows).round() as u64,
+        null_fraction: (all_rows - non_null_rows).max(0.0) / all_rows,
+        distinct_values_fraction: distinct / non_null_rows,
+        most_common_values,
+        histogram_boundaries: histogram_boundaries(rest),
+    }))
+}
+
+/// Split the sorted distinct values into buckets holding
+/// the equal number of rows and return the buckets boundaries.
+#[allow(clippy::cast_precision_loss)]
+fn histogram_boundaries<T: ScalarValue>(sampled: Vec<SampledValue<T>>) -> Vec<Value> {
+    if sampled.len() < 2 {
+        return Vec::new();
+    }
+    let buckets_number = DEFAULT_HISTOGRAM_BUCKETS_NUMBER.min(sampled.len() - 1);
+    let rows: f64 = sampled.iter().map(|v| v.weight).sum();
+    let bucket_rows = rows / buckets_number as f64;
+
+    let last = sampled.len() - 1;
+    let mut boundaries = Vec::with_capacity(buckets_number + 1);
+    let mut accumulated = 0.0;
+    let mut bucket = 1;
+    for (i, sampled_value) in sampled.into_iter().enumerate() {
+        accumulated += sampled_value.weight;
+        if i == 0 || i == last {
+            boundaries.push(sampled_value.value.into_value());
+            continue;
+        }
+        if bucket < buckets_number && accumulated >= bucket_rows * bucket as f64 {
+            boundaries.push(sampled_value.value.into_value());
+            while bucket < buckets_number && accumulated >= bucket_rows * bucket as f64 {
+                bucket += 1;
+            }
+        }
+    }
+    boundaries
+}
+
+/// Convert the stored statistics into `ColumnStats` of the type
+/// corresponding to the column type (see `selectivity` module).
+///
+/// Returns `None` if statistics are not gathered for the column type
+/// or if the stored values don't match it (i.e. the column type has
+/// been changed since the statistics were gathered).
+///
+/// # Errors
+/// - Invalid frequencies or histogram boundaries.
+pub fn decode_column_stats(
+    stats: &EncodedColumnStats,
+    column_type: &Type,
+) -> Result<Option<Rc<Box<dyn Any>>>, SbroadError> {
+    match column_type {
+        Type::Boolean => decode_typed_column_stats::<bool>(stats),
+        Type::Decimal => decode_typed_column_stats::<Decimal>(stats),
+        Type::Double => decode_typed_column_stats::<Double>(stats),
+        Type::Integer => decode_typed_column_stats::<i64>(stats),
+        Type::String => decode_typed_column_stats::<String>(stats),
+        Type::Unsigned => decode_typed_column_stats::<u64>(stats),
+        Type::Any
+        | Type::Array
+        | Type::Datetime
+        | Type::Interval
+        | Type::Map
+        | Type::Uuid
+        | Type::Varbinary => Ok(None),
+    }
+}
+
+fn decode_typed_column_stats<T: ScalarValue>(
+    stats: &EncodedColumnStats,
+) -> Result<Option<Rc<Box<dyn Any>>>, SbroadError> {
+    let (Some(min_value), Some(max_value)) = (
+        T::from_value(&stats.min_value),
+        T::from_value(&stats.max_value),
+    ) else {
+        return Ok(None);
+    };
+
+    let mut most_common_values = McvSet::with_capacity(stats.most_common_values.len());
+    for (value, frequency) in &stats.most_common_values {
+        let Some(value) = T::from_value(value) else {
+            return Ok(None);
+        };
+        most_common_values.insert(Mcv::new(value, decimal_from_f64(*frequency)?));
+    }
+
+    let Some(mut boundaries) = stats
+        .histogram_boundaries
+        .iter()
+        .map(T::from_value)
+        .collect::<Option<Vec<T>>>()
+    else {
+        return Ok(None);
+    };
+    let buckets = if boundaries.len() < 2 {
+        HistogramBuckets::default()
+    } else {
+        HistogramBuckets::try_from(&mut boundaries)?
+    };
+
+    let histogram = Histogram::new(
+        most_common_values,
+        buckets,
+        decimal_from_f64(stats.null_fraction)?,
+        decimal_from_f64(stats.distinct_values_fraction)?,
+    );
+    let column_stats = ColumnStats::new(min_value, max_value, stats.avg_size, Some(histogram));
+    Ok(Some(Rc::new(Box::new(column_stats))))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,123 @@
+use crate::cbo::analyze::{
+    build_column_stats, decode_column_stats, EncodedColumnStats, TableSample,
+};
+use crate::cbo::histogram::normalization::DEFAULT_HISTOGRAM_BUCKETS_NUMBER;
+use crate::cbo::ColumnStats;
+use crate::ir::relation::Type;
+use crate::ir::value::Value;
+use pretty_assertions::assert_eq;
+
+fn sample(rows_number: u64, values: Vec<Value>) -> TableSample {
+    TableSample {
+        rows_number,
+        rows: values
+            .into_iter()
+            .map(|v| vec![Value::Unsigned(1), v])
+            .collect(),
+    }
+}
+
+#[test]
+fn most_common_values_only() {
+    let samples = vec![
+        sample(
+            4,
+            vec![
+                Value::Integer(1),
+                Value::Integer(1),
+                Value::Integer(2),
+                Value::Null,
+            ],
+        ),
+        sample(4, vec![Value::Integer(2), Value::Integer(2)]),
+    ];
+    let stats = build_column_stats(&samples, 1, &Type::Integer)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        stats,
+        EncodedColumnStats {
+            min_value: Value::Integer(1),
+            max_value: Value::Integer(2),
+            avg_size: 8,
+            null_fraction: 0.125,
+            distinct_values_fraction: 2.0 / 7.0,
+            most_common_values: vec![(Value::Integer(1), 0.25), (Value::Integer(2), 0.625)],
+            histogram_boundaries: vec![],
+        }
+    );
+}
+
+#[test]
+fn samples_are_weighted() {
+    let samples = vec![
+        sample(1000, vec![Value::from("a"); 10]),
+        sample(100, vec![Value::from("bb"); 10]),
+    ];
+    let stats = build_column_stats(&samples, 1, &Type::String)
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        stats.most_common_values,
+        vec![
+            (Value::from("a"), 1000.0 / 1100.0),
+            (Value::from("bb"), 100.0 / 1100.0),
+        ]
+    );
+    assert_eq!(stats.avg_size, 1);
+}
+
+#[test]
+fn histogram_of_unique_values() {
+    let values: Vec<Value> = (0..1000_u64).rev().map(Value::Unsigned).collect();
+    let stats = build_column_stats(&[sample(1000, values)], 1, &Type::Unsigned)
+        .unwrap()
+        .unwrap();
+    assert!(stats.most_common_values.is_empty());
+    assert_eq!(stats.distinct_values_fraction, 1.0);
+    assert_eq!(stats.null_fraction, 0.0);
+    let boundaries = &stats.histogram_boundaries;
+    assert_eq!(boundaries.len(), DEFAULT_HISTOGRAM_BUCKETS_NUMBER + 1);
+    assert_eq!(boundaries.first(), Some(&Value::Unsigned(0)));
+    assert_eq!(boundaries.last(), Some(&Value::Unsigned(999)));
+    assert!(boundaries.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[test]
+fn unsupported_and_null_columns() {
+    let samples = vec![sample(2, vec![Value::Null, Value::Null])];
+    assert_eq!(
+        build_column_stats(&samples, 1, &Type::Integer).unwrap(),
+        None
+    );
+    assert_eq!(build_column_stats(&samples, 1, &Type::Map).unwrap(), None);
+
+    let samples = vec![sample(1, vec![Value::from("a")])];
+    let err = build_column_stats(&samples, 1, &Type::Integer).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid statistics: unexpected value 'a' in the sample of column 1"
+    );
+}
+
+#[test]
+fn decode_stats() {
+    let values: Vec<Value> = (0..1000_i64).map(|i| Value::Integer(i % 500)).collect();
+    let stats = build_column_stats(&[sample(1000, values)], 1, &Type::Integer)
+        .unwrap()
+        .unwrap();
+
+    let decoded = decode_column_stats(&stats, &Type::Integer)
+        .unwrap()
+        .unwrap();
+    let column_stats = decoded.downcast_ref::<ColumnStats<i64>>().unwrap();
+    assert_eq!(column_stats.min_value, 0);
+    assert_eq!(column_stats.max_value, 499);
+    let histogram = column_stats.histogram.as_ref().unwrap();
+    assert_eq!(histogram.buckets_number(), DEFAULT_HISTOGRAM_BUCKETS_NUMBER);
+
+    // The column type has been changed since the statistics were gathered.
+    assert!(decode_column_stats(&stats, &Type::String)
+        .unwrap()
+        .is_none());
+}
@@ -0,0 +1,304 @@
+//! Estimation of the number of rows produced by the relational operators.
+//!
+//! The estimation starts from the table sizes and applies the selectivity
+//! of the filters (`column op constant`) and join conditions (`column op column`)
+//! estimated with the column statistics (see `selectivity` module). Parts
+//! of the filters that can't be estimated (e.g. `OR` or expressions over
+//! the columns) are ignored, so the estimation is an upper bound of the number
+//! of rows. It is enough to compare the sizes of the join children.
+
+use crate::cbo::selectivity::{calculate_condition_selectivity, calculate_filter_selectivity};
+use crate::cbo::{SelectivityMap, TableColumnPair, TableRowsMap};
+use crate::errors::SbroadError;
+use crate::executor::engine::Router;
+use crate::ir::node::expression::Expression;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::{
+    BoolExpr, Constant, Join, Limit, Motion, NodeId, Reference, ScanRelation, Selection, UnionAll,
+    Values,
+};
+use crate::ir::operator::{Bool, JoinKind};
+use crate::ir::transformation::partition::flip;
+use crate::ir::tree::traversal::{LevelNode, PostOrder, REL_CAPACITY};
+use crate::ir::value::Value;
+use crate::ir::Plan;
+use tarantool::decimal::Decimal;
+
+impl Plan {
+    /// Fill the number of rows in the tables referenced by the plan
+    /// from the table statistics and estimate the selectivity of the
+    /// filters and join conditions over these tables. Tables without
+    /// statistics are skipped.
+    ///
+    /// # Errors
+    /// - Failed to get table statistics.
+    pub fn set_statistics(&mut self, runtime: &impl Router) -> Result<(), SbroadError> {
+        let mut table_rows = TableRowsMap::with_capacity(self.relations.tables.len());
+        for (name, table) in &self.relations.tables {
+            if table.is_system() {
+                continue;
+            }
+            if let Some(stats) = runtime.get_table_stats(name)? {
+                table_rows.insert(name.clone(), stats.rows_number());
+            }
+        }
+        self.table_rows = table_rows;
+
+        let mut selectivity = SelectivityMap::new();
+        if !self.table_rows.is_empty() {
+            let top_id = self.get_top()?;
+            let mut post_tree =
+                PostOrder::with_capacity(|node| self.nodes.rel_iter(node), REL_CAPACITY);
+            post_tree.populate_nodes(top_id);
+            let nodes = post_tree.take_nodes();
+            drop(post_tree);
+
+            for LevelNode(_, rel_id) in nodes {
+                let value = match self.get_relation_node(rel_id)? {
+                    Relational::Selection(Selection { filter, .. }) => {
+                        self.estimate_filter_selectivity(runtime, rel_id, *filter)?
+                    }
+                    Relational::Join(Join { condition, .. }) => {
+                        self.estimate_condition_selectivity(runtime, rel_id, *condition)?
+                    }
+                    _ => None,
+                };
+                if let Some(value) = value {
+                    selectivity.insert(rel_id, value);
+                }
+            }
+        }
+        self.selectivity = selectivity;
+        Ok(())
+    }
+
+    /// Estimate the number of rows produced by the relational node.
+    /// Returns `None` if some of the scanned tables have no statistics
+    /// or the node is not supported by the estimation.
+    ///
+    /// # Errors
+    /// - Node is not relational.
+    pub fn estimate_rows(&self, rel_id: NodeId) -> Result<Option<u64>, SbroadError> {
+        let rows = match self.get_relation_node(rel_id)? {
+            Relational::ScanRelation(ScanRelation { relation, .. }) => {
+                self.table_rows.get(relation).copied()
+            }
+            Relational::Values(Values { children, .. }) => Some(children.len() as u64),
+            Relational::Limit(Limit { limit, child, .. }) => {
+                let rows = self.estimate_rows(*child)?;
+                match limit {
+                    Some(limit) => Some(rows.map_or(*limit, |rows| rows.min(*limit))),
+                    None => rows,
+                }
+            }
+            Relational::UnionAll(UnionAll { left, right, .. }) => {
+                match (self.estimate_rows(*left)?, self.estimate_rows(*right)?) {
+                    (Some(left), Some(right)) => Some(left.saturating_add(right)),
+                    _ => None,
+                }
+            }
+            Relational::Selection(_) => {
+                let rows = self.estimate_rows(self.get_relational_child(rel_id, 0)?)?;
+                match self.selectivity.get(&rel_id) {
+                    Some(selectivity) => rows.map(|rows| apply_selectivity(rows, *selectivity)),
+                    None => rows,
+                }
+            }
+            Relational::Join(Join { kind, .. }) => {
+                let (Some(outer_rows), Some(inner_rows)) = (
+                    self.estimate_rows(self.get_relational_child(rel_id, 0)?)?,
+                    self.estimate_rows(self.get_relational_child(rel_id, 1)?)?,
+                ) else {
+                    return Ok(None);
+                };
+                let Some(selectivity) = self.selectivity.get(&rel_id) else {
+                    return Ok(None);
+                };
+                let rows = apply_selectivity(outer_rows.saturating_mul(inner_rows), *selectivity);
+                // Outer joins keep the unmatched rows of the outer sides.
+                Some(match kind {
+                    JoinKind::Inner => rows,
+                    JoinKind::LeftOuter => rows.max(outer_rows),
+                    JoinKind::RightOuter => rows.max(inner_rows),
+                    JoinKind::FullOuter => rows.max(outer_rows.saturating_add(inner_rows)),
+                })
+            }
+            Relational::Motion(Motion { child: None, .. }) => None,
+            Relational::Motion(_)
+            | Relational::Projection(_)
+            | Relational::Having(_)
+            | Relational::GroupBy(_)
+            | Relational::OrderBy(_)
+            | Relational::NamedWindows(_)
+            | Relational::ScanSubQuery(_)
+            | Relational::ScanCte(_) => {
+                self.estimate_rows(self.get_relational_child(rel_id, 0)?)?
+            }
+            Relational::Except(_)
+            | Relational::Intersect(_)
+            | Relational::Union(_)
+            | Relational::SelectWithoutScan(_)
+            | Relational::ValuesRow(_)
+            | Relational::Insert(_)
+            | Relational::Update(_)
+            | Relational::Delete(_) => None,
+        };
+        Ok(rows)
+    }
+
+    /// Selectivity of the selection filter as a product of the selectivities
+    /// of its `column op constant` conjuncts. Other conjuncts are ignored.
+    /// Returns `None` if there are no such conjuncts.
+    fn estimate_filter_selectivity(
+        &self,
+        runtime: &impl Router,
+        selection_id: NodeId,
+        filter_id: NodeId,
+    ) -> Result<Option<Decimal>, SbroadError> {
+        let mut selectivity: Option<Decimal> = None;
+        for conjunct in self.comparison_conjuncts(filter_id)? {
+            let Some((left, op, right)) = conjunct else {
+                continue;
+            };
+            let (column, op, value_id) = match (
+                self.get_scan_column(selection_id, left)?,
+                self.get_scan_column(selection_id, right)?,
+            ) {
+                (Some(column), None) => (column, op, right),
+                (None, Some(column)) => (column, flip(op), left),
+                _ => continue,
+            };
+            let value_id = self.unwrap_single_column(value_id)?;
+            let Expression::Constant(Constant { value }) = self.get_expression_node(value_id)?
+            else {
+                continue;
+            };
+            if matches!(value, Value::Null) {
+                continue;
+            }
+            // Constants that can't be compared with the column statistics
+            // (e.g. a negative number for an unsigned column) are ignored.
+            let Ok(value) = calculate_filter_selectivity(runtime, &column, value, &op) else {
+                continue;
+            };
+            selectivity = Some(selectivity.map_or(value, |s| s * value));
+        }
+        Ok(selectivity)
+    }
+
+    /// Selectivity of the join condition as a product of the selectivities
+    /// of its `column op column` conjuncts comparing the columns of the
+    /// outer and inner children. Returns `None` if the condition contains
+    /// anything else (except for `true`), as the join size can't be estimated
+    /// without it.
+    fn estimate_condition_selectivity(
+        &self,
+        runtime: &impl Router,
+        join_id: NodeId,
+        condition_id: NodeId,
+    ) -> Result<Option<Decimal>, SbroadError> {
+        let condition_id = self.unwrap_single_column(condition_id)?;
+        if let Expression::Constant(Constant {
+            value: Value::Boolean(true),
+        }) = self.get_expression_node(condition_id)?
+        {
+            return Ok(Some(Decimal::from(1)));
+        }
+
+        let mut selectivity = Decimal::from(1);
+        for conjunct in self.comparison_conjuncts(condition_id)? {
+            let Some((left, op, right)) = conjunct else {
+                return Ok(None);
+            };
+            let (Some(left), Some(right)) = (
+                self.get_scan_column(join_id, left)?,
+                self.get_scan_column(join_id, right)?,
+            ) else {
+                return Ok(None);
+            };
+            // Statistics that don't match the column types are ignored.
+            let Ok(value) = calculate_condition_selectivity(runtime, &left, &right, &op) else {
+                return Ok(None);
+            };
+            selectivity *= value;
+        }
+        Ok(Some(selectivity))
+    }
+
+    /// Split the expression by `AND` into comparisons (`=`, `!=`, `<`, `<=`, `>`, `>=`).
+    /// Other conjuncts are returned as `None`.
+    fn comparison_conjuncts(
+        &self,
+        expr_id: NodeId,
+    ) -> Result<Vec<Option<(NodeId, Bool, NodeId)>>, SbroadError> {
+        let mut conjuncts = Vec::new();
+        let mut stack = vec![expr_id];
+        while let Some(expr_id) = stack.pop() {
+            let expr_id = self.unwrap_single_column(expr_id)?;
+            let Expression::Bool(BoolExpr { left, op, right }) =
+                self.get_expression_node(expr_id)?
+            else {
+                conjuncts.push(None);
+                continue;
+            };
+            match op {
+                Bool::And => {
+                    stack.push(*right);
+                    stack.push(*left);
+                }
+                Bool::Eq | Bool::NotEq | Bool::Lt | Bool::LtEq | Bool::Gt | Bool::GtEq => {
+                    conjuncts.push(Some((*left, *op, *right)));
+                }
+                _ => conjuncts.push(None),
+            }
+        }
+        Ok(conjuncts)
+    }
+
+    /// Table column referenced by the expression, if it is a reference
+    /// of the relational node to a column of a table scan among its children.
+    fn get_scan_column(
+        &self,
+        rel_id: NodeId,
+        expr_id: NodeId,
+    ) -> Result<Option<TableColumnPair>, SbroadError> {
+        let expr_id = self.unwrap_single_column(expr_id)?;
+        let Expression::Reference(Reference {
+            parent: Some(parent),
+            targets: Some(targets),
+            position,
+            ..
+        }) = self.get_expression_node(expr_id)?
+        else {
+            return Ok(None);
+        };
+        let [target] = targets.as_slice() else {
+            return Ok(None);
+        };
+        if *parent != rel_id {
+            return Ok(None);
+        }
+        let Some(child_id) = self.get_relational_children(rel_id)?.get(*target) else {
+            return Ok(None);
+        };
+        match self.get_relation_node(*child_id)? {
+            Relational::ScanRelation(ScanRelation { relation, .. }) => {
+                Ok(Some(TableColumnPair::new(relation.clone(), *position)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Apply selectivity to the number of rows. The result is not less than one
+/// row if there are any rows, as the statistics may be outdated.
+fn apply_selectivity(rows: u64, selectivity: Decimal) -> u64 {
+    if rows == 0 {
+        return 0;
+    }
+    (Decimal::from(rows) * selectivity)
+        .floor()
+        .to_u64()
+        .unwrap_or(rows)
+        .clamp(1, rows)
+}
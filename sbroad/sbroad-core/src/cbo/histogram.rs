@@ -346,7 +346,6 @@ impl<T: Scalar> Bucket<T> {
     /// Checks that passed constant value falls into bucket boundaries.
     /// `from_boundary` is taken as a `to` boundary of preceding bucket or as self `from` boundary
     /// value, in case we deal with the first bucket.
-    #[allow(dead_code)]
     fn constant_falls_into_bucket(
         &self,
         previous_bucket: Option<&Bucket<T>>,
@@ -372,7 +371,6 @@ impl<T: Scalar> Bucket<T> {
     }
 
     /// Helper function to get `from` boundary in case we are dealing with a first bucket.
-    #[allow(dead_code)]
     fn get_from_boundary(&self) -> Result<T, SbroadError> {
         match &self.bucket_type {
             BucketType::First { from_boundary } => Ok(from_boundary.clone()),
@@ -413,6 +411,39 @@ impl<T: Scalar> HistogramBuckets<T> {
         self.inner.is_empty()
     }
 
+    /// Fraction of the buckets values that are less than the passed one.
+    /// Values are considered to be uniformly distributed inside a bucket.
+    ///
+    /// # Errors
+    /// - Unable to calculate the fraction of the bucket the value covers.
+    pub fn fraction_less_than(&self, value: &T) -> Result<Decimal, SbroadError>
+    where
+        T: Scalar<Other = T>,
+    {
+        if self.is_empty() {
+            return Ok(Decimal::from(0));
+        }
+        let mut covered = Decimal::from(0);
+        let mut previous_bucket: Option<&Bucket<T>> = None;
+        for bucket in &self.inner {
+            if *value >= bucket.to_boundary {
+                covered += Decimal::from(1);
+            } else {
+                if bucket.constant_falls_into_bucket(previous_bucket, value)? {
+                    let from_boundary = match previous_bucket {
+                        Some(previous_bucket) => previous_bucket.to_boundary.clone(),
+                        None => bucket.get_from_boundary()?,
+                    };
+                    covered +=
+                        value.boundaries_occupied_fraction(&from_boundary, &bucket.to_boundary)?;
+                }
+                break;
+            }
+            previous_bucket = Some(bucket);
+        }
+        Ok(covered / Decimal::from(self.len()))
+    }
+
     /// Helper function for statistics normalization (merge) purposes.
     /// Get pairs of (bucket's boundary, its frequency).
     /// Note that for the purposes of algorithm `to` boundary of the last bucket is stored with
@@ -1,9 +1,12 @@
 //! Selection and Join operators selectivity estimation logic.
-//! TODO: move the logic of selectivity calculation from prototype.
-//! TODO: add docs.
-//! TODO: add adequate default values.
+//!
+//! Selectivity is a fraction of rows that satisfy a filter (`column op constant`)
+//! or a join condition (`column op column`). It is estimated with the column
+//! statistics gathered by `ANALYZE`: the most common values, the histogram
+//! buckets and the column MIN and MAX values. When there are no statistics,
+//! the default selectivities are used.
 
-use crate::cbo::histogram::Scalar;
+use crate::cbo::histogram::{Mcv, Scalar};
 use crate::cbo::{ColumnStats, TableColumnPair, TableStats};
 use crate::errors::{Entity, SbroadError};
 use crate::executor::engine::helpers::to_user;
@@ -19,27 +22,50 @@ use std::fmt::Display;
 use std::num::TryFromIntError;
 use std::rc::Rc;
 use std::str::FromStr;
-use tarantool::decimal;
 use tarantool::decimal::{Decimal, DecimalToIntError};
 
-#[allow(dead_code)]
-const DEFAULT_FILTER_EQ_SELECTIVITY: f64 = 0.0;
-#[allow(dead_code)]
-const DEFAULT_FILTER_RANGE_SELECTIVITY: f64 = 0.0;
-#[allow(dead_code)]
-const DEFAULT_CONDITION_EQ_SELECTIVITY: f64 = 0.0;
-#[allow(dead_code)]
-const DEFAULT_CONDITION_RANGE_SELECTIVITY: f64 = 0.0;
+/// Selectivity of `column = constant` filter used when there are no statistics
+/// to estimate it (the same as `DEFAULT_EQ_SEL` in `PostgreSQL`).
+const DEFAULT_FILTER_EQ_SELECTIVITY: f64 = 0.005;
+/// Selectivity of `column < constant` (and other range) filters used when there
+/// are no statistics to estimate it (the same as `DEFAULT_INEQ_SEL` in `PostgreSQL`).
+const DEFAULT_FILTER_RANGE_SELECTIVITY: f64 = 0.333_333;
+/// Selectivity of `column = column` join condition used when there are no
+/// statistics to estimate it.
+const DEFAULT_CONDITION_EQ_SELECTIVITY: f64 = 0.005;
+/// Selectivity of `column < column` (and other range) join conditions.
+/// Histograms don't help to estimate it, so it is always used.
+const DEFAULT_CONDITION_RANGE_SELECTIVITY: f64 = 0.333_333;
 
-impl<T: Scalar> ColumnStats<T> {
-    #[allow(clippy::unused_self)]
+fn default_filter_selectivity(operator: &Bool) -> Result<Decimal, SbroadError> {
+    match operator {
+        Bool::Eq => decimal_from_str(&DEFAULT_FILTER_EQ_SELECTIVITY),
+        Bool::NotEq => Ok(Decimal::from(1) - decimal_from_str(&DEFAULT_FILTER_EQ_SELECTIVITY)?),
+        _ => decimal_from_str(&DEFAULT_FILTER_RANGE_SELECTIVITY),
+    }
+}
+
+fn default_condition_selectivity(operator: &Bool) -> Result<Decimal, SbroadError> {
+    match operator {
+        Bool::Eq => decimal_from_str(&DEFAULT_CONDITION_EQ_SELECTIVITY),
+        Bool::NotEq => Ok(Decimal::from(1) - decimal_from_str(&DEFAULT_CONDITION_EQ_SELECTIVITY)?),
+        _ => decimal_from_str(&DEFAULT_CONDITION_RANGE_SELECTIVITY),
+    }
+}
+
+/// Bound selectivity to the [0; 1] range.
+fn clamp_selectivity(selectivity: Decimal) -> Decimal {
+    selectivity.max(Decimal::from(0)).min(Decimal::from(1))
+}
+
+impl<T: Scalar<Other = T>> ColumnStats<T> {
     fn filter_selectivity(
         &self,
         table_stats: &Rc<TableStats>,
         constant: &T,
         operator: &Bool,
     ) -> Result<Decimal, SbroadError> {
-        match operator {
+        let selectivity = match operator {
             Bool::Eq => self.filter_eq_selectivity(constant, table_stats),
             Bool::NotEq => self.filter_neq_selectivity(constant, table_stats),
             Bool::Gt | Bool::GtEq | Bool::Lt | Bool::LtEq => {
@@ -47,45 +73,131 @@ impl<T: Scalar> ColumnStats<T> {
             }
             _ => Err(SbroadError::Invalid(
                 Entity::Statistics,
-                Some(SmolStr::from(
+                Some(format_smolstr!(
                     "Unexpected boolean operator met for selectivity estimation: {operator:?}",
                 )),
             )),
+        }?;
+        Ok(clamp_selectivity(selectivity))
+    }
+
+    /// Fraction of NULL values in the column.
+    fn null_fraction(&self) -> Decimal {
+        self.histogram
+            .as_ref()
+            .map_or(Decimal::from(0), |histogram| histogram.null_fraction)
+    }
+
+    /// Absolute number of distinct non-NULL values in the column.
+    /// Returns `None` if the histogram is not gathered.
+    fn distinct_values_number(&self, table_stats: &Rc<TableStats>) -> Option<Decimal> {
+        let histogram = self.histogram.as_ref()?;
+        let distinct = Decimal::from(table_stats.rows_number())
+            * (Decimal::from(1) - histogram.null_fraction)
+            * histogram.distinct_values_fraction;
+        if distinct < Decimal::from(1) {
+            return None;
         }
+        Some(distinct)
     }
 
-    #[allow(clippy::unused_self)]
-    #[allow(clippy::unnecessary_wraps)]
+    /// PostgreSQL lines: `var_eq_const` (selfuncs.c).
+    ///
+    /// The most common values give the frequency of the constant. Other values
+    /// are considered to be equally frequent: the rows not covered by the most
+    /// common values are evenly spread over the rest distinct values.
     fn filter_eq_selectivity(
         &self,
-        _constant: &T,
-        _table_stats: &Rc<TableStats>,
+        constant: &T,
+        table_stats: &Rc<TableStats>,
     ) -> Result<Decimal, SbroadError> {
-        Ok(decimal!(1.0))
+        if constant < &self.min_value || constant > &self.max_value {
+            return Ok(Decimal::from(0));
+        }
+        let Some(histogram) = &self.histogram else {
+            return decimal_from_str(&DEFAULT_FILTER_EQ_SELECTIVITY);
+        };
+        let most_common_values = &histogram.most_common_values;
+        if let Some(mcv) = most_common_values.get(&Mcv::new(constant.clone(), Decimal::from(0))) {
+            return Ok(mcv.frequency);
+        }
+        let Some(distinct) = self.distinct_values_number(table_stats) else {
+            return decimal_from_str(&DEFAULT_FILTER_EQ_SELECTIVITY);
+        };
+        let rest_distinct =
+            (distinct - Decimal::from(most_common_values.len())).max(Decimal::from(1));
+        let rest_fraction =
+            Decimal::from(1) - histogram.null_fraction - most_common_values.frequencies_sum();
+        Ok(rest_fraction / rest_distinct)
     }
 
-    #[allow(clippy::unused_self)]
-    #[allow(clippy::unnecessary_wraps)]
     fn filter_neq_selectivity(
         &self,
         constant: &T,
         table_stats: &Rc<TableStats>,
     ) -> Result<Decimal, SbroadError> {
-        Ok(Decimal::from(1) - self.filter_eq_selectivity(constant, table_stats)?)
+        Ok(Decimal::from(1)
+            - self.null_fraction()
+            - self.filter_eq_selectivity(constant, table_stats)?)
     }
 
-    #[allow(clippy::unused_self)]
-    #[allow(clippy::unnecessary_wraps)]
+    /// PostgreSQL lines: `scalarineqsel` (selfuncs.c).
     fn filter_range_selectivity(
         &self,
-        _constant: &T,
+        constant: &T,
         _table_stats: &Rc<TableStats>,
-        _operator: &Bool,
+        operator: &Bool,
     ) -> Result<Decimal, SbroadError> {
-        Ok(decimal!(1.0))
+        let Some(less_than) = self.fraction_less_than(constant)? else {
+            return decimal_from_str(&DEFAULT_FILTER_RANGE_SELECTIVITY);
+        };
+        match operator {
+            Bool::Lt | Bool::LtEq => Ok(less_than),
+            _ => Ok(Decimal::from(1) - self.null_fraction() - less_than),
+        }
+    }
+
+    /// Fraction of the column rows with values less than the constant.
+    ///
+    /// The most common values are checked one by one, the rest values
+    /// are estimated with the histogram buckets. When there are no buckets,
+    /// the values are considered to be uniformly distributed between the
+    /// column MIN and MAX values.
+    ///
+    /// Returns `None` if the fraction can't be estimated (e.g. for boolean columns).
+    fn fraction_less_than(&self, constant: &T) -> Result<Option<Decimal>, SbroadError> {
+        let not_null = Decimal::from(1) - self.null_fraction();
+        if constant <= &self.min_value {
+            return Ok(Some(Decimal::from(0)));
+        }
+        if constant > &self.max_value {
+            return Ok(Some(not_null));
+        }
+
+        let (mcv_fraction, rest_fraction, buckets) = match &self.histogram {
+            Some(histogram) => {
+                let mcv_fraction = histogram
+                    .most_common_values
+                    .inner
+                    .iter()
+                    .filter(|mcv| &mcv.value < constant)
+                    .fold(Decimal::from(0), |sum, mcv| sum + mcv.frequency);
+                let rest_fraction = not_null - histogram.most_common_values.frequencies_sum();
+                (mcv_fraction, rest_fraction, Some(&histogram.buckets))
+            }
+            None => (Decimal::from(0), not_null, None),
+        };
+
+        let rest_less_than = match buckets {
+            Some(buckets) if !buckets.is_empty() => buckets.fraction_less_than(constant)?,
+            _ => match constant.boundaries_occupied_fraction(&self.min_value, &self.max_value) {
+                Ok(fraction) => fraction,
+                Err(_) => return Ok(None),
+            },
+        };
+        Ok(Some(mcv_fraction + rest_fraction * rest_less_than))
     }
 
-    #[allow(clippy::unused_self)]
     fn condition_selectivity(
         &self,
         self_table_stats: &Rc<TableStats>,
@@ -93,7 +205,7 @@ impl<T: Scalar> ColumnStats<T> {
         other_table_stats: &Rc<TableStats>,
         operator: &Bool,
     ) -> Result<Decimal, SbroadError> {
-        match operator {
+        let selectivity = match operator {
             Bool::Eq => self.condition_eq_selectivity(self_table_stats, other, other_table_stats),
             Bool::NotEq => {
                 self.condition_neq_selectivity(self_table_stats, other, other_table_stats)
@@ -106,48 +218,57 @@ impl<T: Scalar> ColumnStats<T> {
             ),
             _ => Err(SbroadError::Invalid(
                 Entity::Statistics,
-                Some(SmolStr::from(
+                Some(format_smolstr!(
                     "Unexpected boolean operator met for selectivity estimation: {operator:?}",
                 )),
             )),
-        }
+        }?;
+        Ok(clamp_selectivity(selectivity))
     }
 
-    #[allow(clippy::unused_self)]
-    #[allow(clippy::unnecessary_wraps)]
-    #[allow(dead_code)]
+    /// PostgreSQL lines: `eqjoinsel_inner` (selfuncs.c).
+    ///
+    /// Every value of the column with fewer distinct values is supposed
+    /// to match some value of the other column.
     fn condition_eq_selectivity(
         &self,
-        _self_table_stats: &Rc<TableStats>,
-        _other: &ColumnStats<T>,
-        _other_table_stats: &Rc<TableStats>,
+        self_table_stats: &Rc<TableStats>,
+        other: &ColumnStats<T>,
+        other_table_stats: &Rc<TableStats>,
     ) -> Result<Decimal, SbroadError> {
-        Ok(decimal!(1.0))
+        let (Some(self_distinct), Some(other_distinct)) = (
+            self.distinct_values_number(self_table_stats),
+            other.distinct_values_number(other_table_stats),
+        ) else {
+            return decimal_from_str(&DEFAULT_CONDITION_EQ_SELECTIVITY);
+        };
+        Ok(
+            (Decimal::from(1) - self.null_fraction()) * (Decimal::from(1) - other.null_fraction())
+                / self_distinct.max(other_distinct),
+        )
     }
 
-    #[allow(clippy::unused_self)]
-    #[allow(clippy::unnecessary_wraps)]
     fn condition_neq_selectivity(
         &self,
         self_table_stats: &Rc<TableStats>,
         other: &ColumnStats<T>,
         other_table_stats: &Rc<TableStats>,
     ) -> Result<Decimal, SbroadError> {
-        Ok(Decimal::from(1)
-            - self.condition_eq_selectivity(self_table_stats, other, other_table_stats)?)
+        Ok(
+            (Decimal::from(1) - self.null_fraction()) * (Decimal::from(1) - other.null_fraction())
+                - self.condition_eq_selectivity(self_table_stats, other, other_table_stats)?,
+        )
     }
 
     #[allow(clippy::unused_self)]
-    #[allow(clippy::unnecessary_wraps)]
-    #[allow(dead_code)]
     fn condition_range_selectivity(
         &self,
         _self_table_stats: &Rc<TableStats>,
         _other: &ColumnStats<T>,
         _other_table_stats: &Rc<TableStats>,
-        _operator: &Bool,
+        operator: &Bool,
     ) -> Result<Decimal, SbroadError> {
-        Ok(decimal!(1.0))
+        default_condition_selectivity(operator)
     }
 }
 
@@ -226,9 +347,6 @@ impl From<TryFromIntError> for SbroadError {
 /// - Unable to get metadata.
 /// - Types mismatch.
 /// - Selectivity calculation error.
-///
-/// # Panics
-/// - Unimplemented logic.
 #[allow(clippy::module_name_repetitions)]
 #[allow(clippy::too_many_lines)]
 pub fn calculate_filter_selectivity(
@@ -242,7 +360,7 @@ pub fn calculate_filter_selectivity(
     let column_stats = statistics.get_column_stats(table_column_pair)?;
 
     let (Some(table_stats), Some(column_stats)) = (table_stats, column_stats) else {
-        return decimal_from_str(&DEFAULT_FILTER_EQ_SELECTIVITY);
+        return default_filter_selectivity(operator);
     };
 
     let table = statistics.metadata().lock().table(table_name.as_str())?;
@@ -334,18 +452,67 @@ pub fn calculate_filter_selectivity(
             }
             _ => types_mismatch_error,
         },
-        Type::Array | Type::Any | Type::Map | Type::Datetime => Err(SbroadError::Invalid(
+        Type::Array
+        | Type::Any
+        | Type::Map
+        | Type::Datetime
+        | Type::Interval
+        | Type::Varbinary
+        | Type::Uuid => Err(SbroadError::Invalid(
             Entity::Statistics,
-            Some(SmolStr::from(
-                "Unable to calculate selectivity for array type column",
+            Some(format_smolstr!(
+                "Unable to calculate selectivity for {column_type:?} type column",
             )),
         )),
-        Type::Uuid => {
-            todo!("Don't know what to do here")
-        }
     }
 }
 
+/// Helper function to get the statistics of the column along with its metadata.
+fn get_column_stats_with_metadata(
+    statistics: &(impl Statistics + Router),
+    table_column_pair: &TableColumnPair,
+) -> Result<Option<(Rc<TableStats>, Rc<Box<dyn Any>>, Column)>, SbroadError> {
+    let TableColumnPair(table_name, colum_index) = table_column_pair;
+    let table_stats = statistics.get_table_stats(table_name)?;
+    let column_stats = statistics.get_column_stats(table_column_pair)?;
+    let (Some(table_stats), Some(column_stats)) = (table_stats, column_stats) else {
+        return Ok(None);
+    };
+    let table = statistics.metadata().lock().table(table_name.as_str())?;
+    let column = table.columns.get(*colum_index).ok_or_else(|| {
+        SbroadError::Invalid(
+            Entity::Statistics,
+            Some(format_smolstr!(
+                "Column with name {} is not found in metadata",
+                to_user(table_name)
+            )),
+        )
+    })?;
+    Ok(Some((table_stats, column_stats, column.clone())))
+}
+
+/// Table statistics, column statistics and column metadata.
+type ColumnStatsWithMetadata<'stats> = (
+    &'stats Rc<TableStats>,
+    &'stats Rc<Box<dyn Any>>,
+    &'stats Column,
+);
+
+fn typed_condition_selectivity<T: Scalar<Other = T>>(
+    (left_table_stats, left_column_stats, left_column): ColumnStatsWithMetadata,
+    (right_table_stats, right_column_stats, right_column): ColumnStatsWithMetadata,
+    operator: &Bool,
+) -> Result<Decimal, SbroadError> {
+    let left_downcasted_stats = downcast_column_stats::<T>(left_column_stats, left_column)?;
+    let right_downcasted_stats = downcast_column_stats::<T>(right_column_stats, right_column)?;
+    left_downcasted_stats.condition_selectivity(
+        left_table_stats,
+        right_downcasted_stats,
+        right_table_stats,
+        operator,
+    )
+}
+
 /// Generic function for `ON` clause selectivity estimation.
 ///
 /// # Errors
@@ -358,75 +525,40 @@ pub fn calculate_condition_selectivity(
     right_table_column_pair: &TableColumnPair,
     operator: &Bool,
 ) -> Result<Decimal, SbroadError> {
-    let TableColumnPair(left_table_name, left_colum_index) = left_table_column_pair;
-    let left_table_stats = statistics.get_table_stats(left_table_name)?;
-    let left_column_stats = statistics.get_column_stats(left_table_column_pair)?;
-    let (Some(left_table_stats), Some(left_column_stats)) = (left_table_stats, left_column_stats)
-    else {
-        return decimal_from_str(&DEFAULT_FILTER_EQ_SELECTIVITY);
-    };
-    let left_table = statistics
-        .metadata()
-        .lock()
-        .table(left_table_name.as_str())?;
-    let left_column = left_table.columns.get(*left_colum_index).ok_or_else(|| {
-        SbroadError::Invalid(
-            Entity::Statistics,
-            Some(format_smolstr!(
-                "Column with name {} is not found in metadata",
-                to_user(left_table_name)
-            )),
-        )
-    })?;
-    let left_column_type = &left_column.r#type;
-
-    let TableColumnPair(right_table_name, right_colum_index) = right_table_column_pair;
-    let right_table_stats = statistics.get_table_stats(right_table_name)?;
-    let right_column_stats = statistics.get_column_stats(right_table_column_pair)?;
-    let (Some(right_table_stats), Some(right_column_stats)) =
-        (right_table_stats, right_column_stats)
+    let (
+        Some((left_table_stats, left_column_stats, left_column)),
+        Some((right_table_stats, right_column_stats, right_column)),
+    ) = (
+        get_column_stats_with_metadata(statistics, left_table_column_pair)?,
+        get_column_stats_with_metadata(statistics, right_table_column_pair)?,
+    )
     else {
-        return decimal_from_str(&DEFAULT_FILTER_EQ_SELECTIVITY);
+        return default_condition_selectivity(operator);
     };
-    let right_table = statistics
-        .metadata()
-        .lock()
-        .table(left_table_name.as_str())?;
-    let right_column = right_table.columns.get(*right_colum_index).ok_or_else(|| {
-        SbroadError::Invalid(
-            Entity::Statistics,
-            Some(format_smolstr!(
-                "Column with name {} is not found in metadata",
-                to_user(right_table_name),
-            )),
-        )
-    })?;
-    let right_column_type = &right_column.r#type;
+    let left = (&left_table_stats, &left_column_stats, &left_column);
+    let right = (&right_table_stats, &right_column_stats, &right_column);
 
-    let types_mismatch_error = Err(SbroadError::Invalid(
-        Entity::Statistics,
-        Some(format_smolstr!(
-            "Column types {left_column_type:?} and {right_column_type:?} doesn't correspond."
-        )),
-    ));
-
-    match (left_column_type.get(), right_column_type.get()) {
+    match (left_column.r#type.get(), right_column.r#type.get()) {
         (Some(Type::Boolean), Some(Type::Boolean)) => {
-            let left_downcasted_stats =
-                downcast_column_stats::<bool>(&left_column_stats, left_column)?;
-            let right_downcasted_stats =
-                downcast_column_stats::<bool>(&right_column_stats, right_column)?;
-            left_downcasted_stats.condition_selectivity(
-                &left_table_stats,
-                right_downcasted_stats,
-                &right_table_stats,
-                operator,
-            )
+            typed_condition_selectivity::<bool>(left, right, operator)
+        }
+        (Some(Type::Decimal), Some(Type::Decimal)) => {
+            typed_condition_selectivity::<Decimal>(left, right, operator)
+        }
+        (Some(Type::Double), Some(Type::Double)) => {
+            typed_condition_selectivity::<Double>(left, right, operator)
+        }
+        (Some(Type::Integer), Some(Type::Integer)) => {
+            typed_condition_selectivity::<i64>(left, right, operator)
+        }
+        (Some(Type::String), Some(Type::String)) => {
+            typed_condition_selectivity::<String>(left, right, operator)
         }
-        _ => {
-            // TODO: Fill remaining pairs logic, throw error otherwise (or implement logic of column stats cast).
-            types_mismatch_error
+        (Some(Type::Unsigned), Some(Type::Unsigned)) => {
+            typed_condition_selectivity::<u64>(left, right, operator)
         }
+        // Statistics of the columns of different types can't be compared.
+        _ => default_condition_selectivity(operator),
     }
 }
 
@@ -1,25 +1,75 @@
-use crate::cbo::selectivity::calculate_filter_selectivity;
+use crate::cbo::selectivity::{calculate_condition_selectivity, calculate_filter_selectivity};
 use crate::cbo::TableColumnPair;
 use crate::executor::engine::mock::RouterRuntimeMock;
 use crate::ir::operator::Bool;
 use crate::ir::value::Value;
 use smol_str::SmolStr;
 use tarantool::decimal;
+use tarantool::decimal::Decimal;
+
+fn test_space_filter_selectivity(constant: u64, operator: &Bool) -> Decimal {
+    let runtime = RouterRuntimeMock::new();
+    calculate_filter_selectivity(
+        &runtime,
+        &TableColumnPair(SmolStr::from("test_space"), 0),
+        &Value::from(constant),
+        operator,
+    )
+    .unwrap()
+}
 
-/// This test is here just to show that the code compiles.
 #[test]
 fn numeric_filter_eq_selectivity() {
-    let runtime = RouterRuntimeMock::new();
-    let table_name = SmolStr::from("test_space");
-    let column_index = 0;
+    // Most common value.
+    assert_eq!(test_space_filter_selectivity(4, &Bool::Eq), decimal!(0.02));
+    // (1 - 0.2 (nulls) - 0.2 (most common values)) / (104 - 4) distinct values.
+    assert_eq!(
+        test_space_filter_selectivity(100, &Bool::Eq),
+        decimal!(0.006)
+    );
+    // Greater than the column MAX value.
+    assert_eq!(test_space_filter_selectivity(5000, &Bool::Eq), decimal!(0));
+    assert_eq!(
+        test_space_filter_selectivity(4, &Bool::NotEq),
+        decimal!(0.78)
+    );
+}
+
+#[test]
+fn numeric_filter_range_selectivity() {
+    // 0.2 (all most common values) + 0.6 (buckets) * 0.5 (two of four buckets).
+    assert_eq!(test_space_filter_selectivity(200, &Bool::Lt), decimal!(0.5));
+    assert_eq!(test_space_filter_selectivity(200, &Bool::Gt), decimal!(0.3));
+    assert_eq!(test_space_filter_selectivity(0, &Bool::Lt), decimal!(0));
+    assert_eq!(
+        test_space_filter_selectivity(5000, &Bool::Lt),
+        decimal!(0.8)
+    );
+}
 
-    let constant = Value::from(4u64);
+#[test]
+fn filter_selectivity_without_statistics() {
+    let runtime = RouterRuntimeMock::new();
     let selectivity = calculate_filter_selectivity(
         &runtime,
-        &TableColumnPair(table_name, column_index),
-        &constant,
+        &TableColumnPair(SmolStr::from("test_space"), 3),
+        &Value::from(1u64),
+        &Bool::Eq,
+    )
+    .unwrap();
+    assert_eq!(selectivity, decimal!(0.005));
+}
+
+#[test]
+fn numeric_condition_eq_selectivity() {
+    let runtime = RouterRuntimeMock::new();
+    // 0.9 (not nulls) * 0.9 (not nulls) / (10000 * 0.9 * 0.001666) distinct values.
+    let selectivity = calculate_condition_selectivity(
+        &runtime,
+        &TableColumnPair(SmolStr::from("hash_testing"), 0),
+        &TableColumnPair(SmolStr::from("hash_testing"), 0),
         &Bool::Eq,
     )
     .unwrap();
-    assert_eq!(selectivity, decimal!(1.0))
+    assert_eq!(selectivity, decimal!(0.81) / decimal!(14.994));
 }
@@ -67,7 +67,7 @@ local function future_wait(cond, timeout)
         if timeout and timeout <= 0 then
             return nil, lerror.make("dql timeout exceeded")
         end
-        local res, err = cond:wait_result(timeout)
+        local res, err = helper.wait_result(cond, timeout)
         if err then
             return nil, lerror.make(err)
         end
@@ -380,13 +380,17 @@ _G.dispatch_dml = function(uuid_to_args, waiting_timeout, tier_name)
         table.insert(futures, future)
     end
 
+    -- Unlike reading, the cancelled DML is waited for: storages roll back
+    -- the changes of the cancelled query, and the client must not get
+    -- the cancellation error before that.
     for _, future in ipairs(futures) do
         local res, err = future:wait_result(waiting_timeout)
         if err ~= nil then
             error(err)
         end
 
-        -- proc_sql_execute returns [{rows_count = ..}] tuple.
+        -- proc_sql_execute returns [{rows_count = .., rows = ..}] tuple,
+        -- where rows are present only for DML with RETURNING clause.
         -- But this rust function is wrapped with proc macros that
         -- add an additional layer of array (see ReturnMsgpack).
         local next_result = helper.unwrap_execute_result(res[1][1])
@@ -394,6 +398,12 @@ _G.dispatch_dml = function(uuid_to_args, waiting_timeout, tier_name)
             result = next_result
         else
             result.row_count = result.row_count + next_result.row_count
+            if next_result.rows ~= nil then
+                result.rows = result.rows or {}
+                for _, row in ipairs(next_result.rows) do
+                    table.insert(result.rows, row)
+                end
+            end
         end
     end
 
@@ -472,7 +482,7 @@ _G.dispatch_dql = function(uuid_to_args, waiting_timeout, row_count, sql_motion_
         if err ~= nil then
             error(err)
         end
-        _, err = future:wait_result(waiting_timeout)
+        _, err = helper.wait_result(future, waiting_timeout)
         if err ~= nil then
             error(err)
         end
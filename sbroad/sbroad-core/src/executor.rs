@@ -23,22 +23,31 @@
 //! 5. Repeats step 3 till we are done with motion layers.
 //! 6. Executes the final IR top subtree and returns the final result to the user.
 use self::engine::query_id;
-use crate::errors::{Entity, SbroadError};
+use crate::errors::{Action, Entity, SbroadError};
 use crate::executor::bucket::Buckets;
-use crate::executor::engine::{Router, TableVersionMap, Vshard};
+use crate::executor::engine::helpers::{
+    materialize_returning, output_metadata, plan_cache_text, replace_metadata_in_dql_result,
+};
+use crate::executor::engine::{Metadata, Router, TableVersionMap, Vshard};
 use crate::executor::ir::ExecutionPlan;
 use crate::executor::lru::Cache;
+use crate::executor::result::{ConsumerResult, ProducerResult};
+use crate::executor::vtable::{calculate_unified_types, VTableTuple, VirtualTable};
 use crate::frontend::Ast;
+use crate::ir::explain::execution_info::{RuntimeStats, RuntimeStatsRef};
 use crate::ir::node::relational::Relational;
 use crate::ir::node::{Motion, NodeId};
+use crate::ir::relation::Column;
 use crate::ir::transformation::redistribution::MotionPolicy;
 use crate::ir::value::Value;
 use crate::ir::{Options, Plan, Slices};
 use crate::utils::MutexLike;
-use smol_str::SmolStr;
+use smol_str::{format_smolstr, SmolStr};
 use std::any::Any;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::Instant;
+use tarantool::tuple::Tuple;
 
 pub mod bucket;
 pub mod engine;
@@ -57,6 +66,7 @@ impl Plan {
     pub fn optimize(&mut self) -> Result<(), SbroadError> {
         self.update_local_timestamps()?;
         self.cast_constants()?;
+        self.expand_partitions()?;
         self.replace_in_operator()?;
         self.push_down_not()?;
         self.split_columns()?;
@@ -69,6 +79,43 @@ impl Plan {
     }
 }
 
+/// Number of the rows in the dispatch result: the returned rows
+/// for DQL and the affected rows for DML.
+fn result_row_count(result: &dyn Any) -> Result<u64, SbroadError> {
+    if let Some(res) = result.downcast_ref::<ProducerResult>() {
+        return Ok(res.rows.len() as u64);
+    }
+    if let Some(res) = result.downcast_ref::<ConsumerResult>() {
+        return Ok(res.row_count);
+    }
+    let Some(tuple) = result.downcast_ref::<Tuple>() else {
+        return Ok(0);
+    };
+    let value = rmpv::decode::read_value(&mut tuple.data()).map_err(|e| {
+        SbroadError::FailedTo(
+            Action::Decode,
+            Some(Entity::Tuple),
+            format_smolstr!("dispatch result: {e:?}"),
+        )
+    })?;
+    // The result is a tuple of a single map:
+    // {"metadata": [..], "rows": [..]} or {"row_count": n}.
+    let rmpv::Value::Array(items) = value else {
+        return Ok(0);
+    };
+    let Some(rmpv::Value::Map(fields)) = items.first() else {
+        return Ok(0);
+    };
+    for (key, value) in fields {
+        match (key.as_str(), value) {
+            (Some("rows"), rmpv::Value::Array(rows)) => return Ok(rows.len() as u64),
+            (Some("row_count"), count) => return Ok(count.as_u64().unwrap_or_default()),
+            _ => {}
+        }
+    }
+    Ok(0)
+}
+
 /// Query to execute.
 #[derive(Debug)]
 pub struct Query<'a, C>
@@ -121,7 +168,8 @@ where
         C::Cache: Cache<SmolStr, Plan>,
         C::ParseTree: Ast,
     {
-        let key = query_id(sql);
+        let search_path = coordinator.metadata().lock().search_path();
+        let key = query_id(&plan_cache_text(sql, &search_path));
         let mut cache = coordinator.cache().lock();
 
         let mut plan = Plan::new();
@@ -160,14 +208,16 @@ where
 
         if plan.is_block()? {
             plan.bind_params(params)?;
-        } else if !plan.is_ddl()?
-            && !plan.is_acl()?
+        } else if plan.is_ddl()? {
+            plan.bind_ddl_params(params)?;
+        } else if !plan.is_acl()?
             && !plan.is_plugin()?
             && !plan.is_deallocate()?
             && !plan.is_tcl()?
         {
             plan.bind_params(params)?;
             plan.apply_options()?;
+            plan.set_statistics(coordinator)?;
             plan.optimize()?;
         }
         let query = Query {
@@ -248,9 +298,11 @@ where
                                 .get_relation_node(motion_child_id)?;
 
                             if matches!(motion_child, Relational::Values { .. }) {
+                                let started = Instant::now();
                                 let virtual_table = self
                                     .coordinator
                                     .materialize_values(&mut self.exec_plan, motion_child_id)?;
+                                self.record_motion_stats(*motion_id, &virtual_table, started);
                                 self.exec_plan.set_motion_vtable(
                                     motion_id,
                                     virtual_table,
@@ -278,11 +330,13 @@ where
                 let top_id = self.exec_plan.get_motion_subtree_root(*motion_id)?;
 
                 let buckets = self.bucket_discovery(top_id)?;
+                let started = Instant::now();
                 let virtual_table = self.coordinator.materialize_motion(
                     &mut self.exec_plan,
                     motion_id,
                     &buckets,
                 )?;
+                self.record_motion_stats(*motion_id, &virtual_table, started);
                 self.exec_plan
                     .set_motion_vtable(motion_id, virtual_table, &vshard)?;
             }
@@ -291,6 +345,71 @@ where
         Ok(())
     }
 
+    /// Replace the VALUES source of an `INSERT` query with already prepared rows.
+    ///
+    /// The rows are put into the virtual table of the motion under the insert node
+    /// (and resharded by its policy), so bulk loading (e.g. `COPY FROM`) doesn't have
+    /// to build a statement with all the rows inlined into the VALUES clause.
+    ///
+    /// # Errors
+    /// - The query is not an `INSERT ... VALUES` one.
+    /// - Failed to cast the values to the unified column types.
+    /// - Failed to reshard the rows.
+    pub fn set_insert_values(&mut self, rows: Vec<VTableTuple>) -> Result<(), SbroadError> {
+        let ir = self.exec_plan.get_ir_plan();
+        let top_id = ir.get_top()?;
+        if !matches!(ir.get_relation_node(top_id)?, Relational::Insert(_)) {
+            return Err(SbroadError::Invalid(
+                Entity::Plan,
+                Some("expected INSERT query to set values for".into()),
+            ));
+        }
+        let motion_id = ir.get_relational_child(top_id, 0)?;
+        let values_id = self.exec_plan.get_motion_child(motion_id)?;
+        let values = self.exec_plan.get_ir_plan().get_relation_node(values_id)?;
+        if !matches!(values, Relational::Values(_)) {
+            return Err(SbroadError::Invalid(
+                Entity::Plan,
+                Some(format_smolstr!(
+                    "expected VALUES under INSERT motion, got {values:?}"
+                )),
+            ));
+        }
+        let columns_len = self
+            .exec_plan
+            .get_ir_plan()
+            .get_row_list(values.output())?
+            .len();
+
+        let mut vtable = VirtualTable::new();
+        vtable
+            .get_mut_columns()
+            .resize_with(columns_len, Column::default);
+        vtable.get_mut_tuples().reserve(rows.len());
+        for row in rows {
+            if row.len() != columns_len {
+                return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                    "expected {columns_len} values in a row, got {}",
+                    row.len()
+                )));
+            }
+            vtable.add_tuple(row);
+        }
+        let unified_types = calculate_unified_types(&vtable.get_types())?;
+        vtable.cast_values(&unified_types)?;
+
+        let _ = self
+            .exec_plan
+            .get_mut_ir_plan()
+            .replace_with_stub(values_id);
+        let tier = self.exec_plan.get_ir_plan().tier.as_ref();
+        let vshard = self.coordinator.get_vshard_object_by_tier(tier)?;
+        self.exec_plan
+            .set_motion_vtable(&motion_id, vtable, &vshard)?;
+        self.exec_plan.unlink_motion_subtree(motion_id)?;
+        Ok(())
+    }
+
     /// Builds explain from current query
     ///
     /// # Errors
@@ -310,6 +429,36 @@ where
         if self.is_explain() {
             return self.produce_explain();
         }
+        self.execute()
+    }
+
+    /// Execute the query gathering the runtime statistics for `EXPLAIN ANALYZE`.
+    /// The result of the query is discarded.
+    ///
+    /// # Errors
+    /// - Failed to execute the query.
+    pub(crate) fn execute_with_stats(&mut self) -> Result<RuntimeStats, SbroadError> {
+        let stats = RuntimeStatsRef::default();
+        self.exec_plan.set_stats(Some(stats.clone()));
+        let started = Instant::now();
+        let result = self.execute();
+        self.exec_plan.set_stats(None);
+        let rows = result_row_count(result?.as_ref())?;
+        let mut stats = stats.take();
+        stats.finish(rows, started.elapsed());
+        Ok(stats)
+    }
+
+    fn record_motion_stats(&self, motion_id: NodeId, vtable: &VirtualTable, started: Instant) {
+        if let Some(stats) = self.exec_plan.get_stats() {
+            let rows = vtable.get_tuples().len() as u64;
+            stats
+                .borrow_mut()
+                .finish_motion(motion_id, rows, started.elapsed());
+        }
+    }
+
+    fn execute(&mut self) -> Result<Box<dyn Any>, SbroadError> {
         self.get_mut_exec_plan()
             .get_mut_ir_plan()
             .restore_constants()?;
@@ -333,13 +482,65 @@ where
             };
             return v.to_output(&motion_aliases);
         }
+        // `RETURNING` projection is detached from the DML subtree, so we
+        // have to get it before the subtree is taken for dispatching.
+        let returning_id = if ir_plan.get_relation_node(top_id)?.is_dml() {
+            ir_plan.dml_returning(top_id)?
+        } else {
+            None
+        };
         let buckets = self.bucket_discovery(top_id)?;
-        self.coordinator.dispatch(
+        let started = Instant::now();
+        let result = self.coordinator.dispatch(
             &mut self.exec_plan,
             top_id,
             &buckets,
             engine::DispatchReturnFormat::Tuple,
-        )
+        )?;
+        if let Some(stats) = self.exec_plan.get_stats() {
+            let rows = result_row_count(result.as_ref())?;
+            stats.borrow_mut().finish_top(rows, started.elapsed());
+        }
+        match returning_id {
+            Some(proj_id) => self.dispatch_returning(proj_id, result),
+            None => Ok(result),
+        }
+    }
+
+    /// Evaluate the `RETURNING` projection of the DML on the router
+    /// over the tuples affected on storages.
+    ///
+    /// # Errors
+    /// - Failed to materialize affected tuples or to evaluate the projection.
+    fn dispatch_returning(
+        &mut self,
+        proj_id: NodeId,
+        dml_result: Box<dyn Any>,
+    ) -> Result<Box<dyn Any>, SbroadError> {
+        let ir = self.exec_plan.get_ir_plan();
+        let motion_id = ir.get_relational_child(proj_id, 0)?;
+        // The projection is consumed on dispatch, so metadata has to be built beforehand.
+        let metadata = output_metadata(ir, proj_id)?;
+        let vtable = materialize_returning(&mut self.exec_plan, motion_id, dml_result)?;
+        let tier = self.exec_plan.get_ir_plan().tier.as_ref();
+        let vshard = self.coordinator.get_vshard_object_by_tier(tier)?;
+        self.exec_plan
+            .set_motion_vtable(&motion_id, vtable, &vshard)?;
+        let mut result = self.coordinator.dispatch(
+            &mut self.exec_plan,
+            proj_id,
+            &Buckets::Any,
+            engine::DispatchReturnFormat::Tuple,
+        )?;
+        // Replace metadata of the local SQL execution with the one from the plan.
+        let Some(tuple) = result.downcast_mut::<Tuple>() else {
+            return Err(SbroadError::Invalid(
+                Entity::Tuple,
+                Some("expected tuple as a result of RETURNING clause".into()),
+            ));
+        };
+        let tuple = replace_metadata_in_dql_result(tuple, &metadata)?;
+        Ok(Box::new(tuple) as Box<dyn Any>)
     }
 
     /// Query explain
@@ -606,7 +606,9 @@ where
                                     .disjunct(&outer_buckets)?
                                     .conjuct(&filter_buckets)?
                             }
-                            JoinKind::LeftOuter => inner_buckets.disjunct(&outer_buckets)?,
+                            JoinKind::LeftOuter | JoinKind::RightOuter | JoinKind::FullOuter => {
+                                inner_buckets.disjunct(&outer_buckets)?
+                            }
                         };
                         self.bucket_map.insert(output_id, join_buckets);
                     } else {
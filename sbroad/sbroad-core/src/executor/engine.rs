@@ -54,6 +54,20 @@ pub trait Metadata: Sized {
     /// - Failed to get function by name from the metadata.
     fn function(&self, fn_name: &str) -> Result<&Function, SbroadError>;
 
+    /// Get the query of a view by normalized name.
+    /// Returns `None` if there is no such view.
+    ///
+    /// # Errors
+    /// - Failed to get view from the metadata.
+    fn view(&self, _view_name: &str) -> Result<Option<SmolStr>, SbroadError> {
+        Ok(None)
+    }
+
+    /// Get the schemas to look up unqualified table and view names in.
+    fn search_path(&self) -> Vec<SmolStr> {
+        vec![SmolStr::from(helpers::DEFAULT_SCHEMA)]
+    }
+
     /// Get the wait timeout for the query execution.
     fn waiting_timeout(&self) -> u64;
 
@@ -84,8 +98,36 @@ pub fn get_builtin_functions() -> &'static [Function] {
             Function::new_stable("to_date".into(), DerivedType::new(Type::Datetime), false),
             Function::new_stable("to_char".into(), DerivedType::new(Type::String), false),
             Function::new_stable("substring".into(), DerivedType::new(Type::String), false),
+            Function::new_stable("date_trunc".into(), DerivedType::new(Type::Datetime), false),
+            Function::new_stable("date_part".into(), DerivedType::new(Type::Double), false),
+            Function::new_stable("age".into(), DerivedType::new(Type::Interval), false),
+            // JSON functions, also used to execute JSON operators
+            Function::new_stable("json_get".into(), DerivedType::new(Type::Any), false),
+            Function::new_stable(
+                "json_get_text".into(),
+                DerivedType::new(Type::String),
+                false,
+            ),
+            Function::new_stable("json_get_path".into(), DerivedType::new(Type::Any), false),
+            Function::new_stable(
+                "json_get_path_text".into(),
+                DerivedType::new(Type::String),
+                false,
+            ),
+            Function::new_stable(
+                "json_contains".into(),
+                DerivedType::new(Type::Boolean),
+                false,
+            ),
+            Function::new_stable(
+                "json_array_length".into(),
+                DerivedType::new(Type::Integer),
+                false,
+            ),
+            Function::new_stable("json_typeof".into(), DerivedType::new(Type::String), false),
             // stable system functions
             Function::new_stable("substr".into(), DerivedType::new(Type::String), true),
+            Function::new_stable("length".into(), DerivedType::new(Type::Integer), true),
             Function::new_stable("lower".into(), DerivedType::new(Type::String), true),
             Function::new_stable("upper".into(), DerivedType::new(Type::String), true),
             Function::new_stable("coalesce".into(), DerivedType::new(Type::Any), true),
@@ -97,6 +139,13 @@ pub fn get_builtin_functions() -> &'static [Function] {
                 DerivedType::new(Type::String),
                 false,
             ),
+            Function::new_volatile(
+                get_real_function_name("nextval")
+                    .expect("shouldn't fail")
+                    .into(),
+                DerivedType::new(Type::Integer),
+                false,
+            ),
         ]
     })
 }
@@ -259,7 +308,7 @@ impl ConvertToDispatchResult for Tuple {
 }
 
 /// A router trait.
-pub trait Router: QueryCache {
+pub trait Router: QueryCache + Statistics {
     type ParseTree;
     type MetadataProvider: Metadata;
     type VshardImplementor: Vshard;
@@ -452,4 +501,14 @@ pub trait Vshard {
     /// - Internal error. Under normal conditions we should always return
     ///   bucket id successfully.
     fn determine_bucket_id(&self, s: &[&Value]) -> Result<u64, SbroadError>;
+
+    /// Check whether the execution of the current request should be stopped
+    /// (e.g. the query was cancelled or its statement timeout has expired).
+    /// Storages call it while applying DML, so an error rolls back the changes.
+    ///
+    /// # Errors
+    /// - The request was interrupted.
+    fn check_interrupted(&self) -> Result<(), SbroadError> {
+        Ok(())
+    }
 }
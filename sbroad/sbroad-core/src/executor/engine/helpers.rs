@@ -6,9 +6,9 @@ use crate::{
     ir::{
         node::{
             expression::Expression, relational::Relational, Alias, Constant, Limit, Motion, NodeId,
-            Update, Values, ValuesRow,
+            ScanRelation, Update, Values, ValuesRow,
         },
-        relation::DerivedType,
+        relation::{DerivedType, Partitioning, Table},
     },
     utils::MutexLike,
 };
@@ -16,12 +16,13 @@ use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use std::{
     any::Any,
     cmp::Ordering,
-    collections::HashMap,
+    collections::{hash_map::Entry, HashMap, HashSet},
     rc::Rc,
     str::{from_utf8, FromStr},
     sync::OnceLock,
+    time::Instant,
 };
-use tarantool::transaction::transaction;
+use tarantool::transaction::{is_in_transaction, transaction};
 use tarantool::{
     error::{Error, TarantoolErrorCode},
     msgpack,
@@ -32,7 +33,7 @@ use tarantool::{space::Space, tuple::TupleBuffer};
 use crate::backend::sql::space::{TableGuard, ADMIN_ID};
 use crate::executor::engine::helpers::storage::{execute_prepared, execute_unprepared, prepare};
 use crate::executor::engine::{QueryCache, StorageCache};
-use crate::executor::protocol::{EncodedTables, SchemaInfo};
+use crate::executor::protocol::{EncodedTables, SchemaInfo, VTablesMeta};
 use crate::ir::node::Node;
 use crate::ir::operator::ConflictStrategy;
 use crate::ir::value::{EncodedValue, MsgPackValue};
@@ -48,7 +49,7 @@ use crate::{
         bucket::Buckets,
         ir::{ExecutionPlan, QueryType},
         protocol::{Binary, EncodedOptionalData, OptionalData, RequiredData},
-        result::{ConsumerResult, MetadataColumn, ProducerResult},
+        result::{ConsumerResult, ExecutorTuple, MetadataColumn, ProducerResult, ReturningResult},
         vtable::{calculate_unified_types, VTableTuple, VirtualTable},
     },
     ir::{
@@ -62,7 +63,7 @@ use crate::{
 use serde::Serialize;
 use tarantool::msgpack::rmp::{self, decode::RmpRead};
 use tarantool::session::with_su;
-use tarantool::tuple::Tuple;
+use tarantool::tuple::{ToTupleBuffer, Tuple};
 
 use self::{
     storage::{dql_cache_miss_result, StorageReturnFormat},
@@ -75,6 +76,37 @@ pub mod proxy;
 pub mod storage;
 pub mod vshard;
 
+/// Schema of the objects created without specifying a schema.
+pub const DEFAULT_SCHEMA: &str = "public";
+
+/// Name of the session parameter with the schemas to look up
+/// unqualified table names in.
+pub const SEARCH_PATH_PARAM: &str = "search_path";
+
+/// Name of the session parameter limiting the execution time of a statement.
+pub const STATEMENT_TIMEOUT_PARAM: &str = "statement_timeout";
+
+/// Name under which a table or a view of the schema is stored in the metadata.
+/// Objects of the default schema keep their own names: `public.t` is `t`,
+/// while `s.t` is `s.t`.
+#[must_use]
+pub fn qualified_name(schema: &str, name: &str) -> SmolStr {
+    if schema == DEFAULT_SCHEMA {
+        return SmolStr::from(name);
+    }
+    format_smolstr!("{schema}.{name}")
+}
+
+/// Text identifying the query in the plan caches. The same query may
+/// reference different tables depending on the search path.
+#[must_use]
+pub fn plan_cache_text(query: &str, search_path: &[SmolStr]) -> SmolStr {
+    if matches!(search_path, [schema] if schema == DEFAULT_SCHEMA) {
+        return SmolStr::from(query);
+    }
+    format_smolstr!("/* search_path: {} */ {query}", search_path.join(", "))
+}
+
 /// Transform:
 ///
 /// ```text
@@ -171,14 +203,23 @@ pub fn build_required_binary(exec_plan: &mut ExecutionPlan) -> Result<Binary, Sb
     let tables = exec_plan.encode_vtables();
     let router_version_map = std::mem::take(&mut exec_plan.get_mut_ir_plan().version_map);
     let schema_info = SchemaInfo::new(router_version_map);
-    let required = RequiredData::new(
+    let mut required = RequiredData::new(
         sub_plan_id,
         params,
         query_type,
         exec_plan.get_ir_plan().options.clone(),
         schema_info,
         tables,
+        exec_plan.get_ir_plan().txn_id.clone(),
     );
+    required.collect_stats = exec_plan.get_stats().is_some();
+    required
+        .request_id
+        .clone_from(&exec_plan.get_ir_plan().request_id);
+    required.statement_timeout = exec_plan
+        .get_ir_plan()
+        .statement_deadline
+        .map(|deadline| deadline.saturating_duration_since(Instant::now()));
     let required_as_tuple = required.to_tuple()?;
     Ok(required_as_tuple.into())
 }
@@ -830,7 +871,7 @@ fn has_zero_limit_clause(plan: &ExecutionPlan) -> Result<bool, SbroadError> {
     let ir = plan.get_ir_plan();
     let top_id = ir.get_top()?;
     if let Relational::Limit(Limit { limit, .. }) = ir.get_relation_node(top_id)? {
-        return Ok(*limit == 0);
+        return Ok(*limit == Some(0));
     }
     Ok(false)
 }
@@ -1110,6 +1151,88 @@ pub fn materialize_motion(
     Ok(vtable)
 }
 
+/// Materialize the motion under the `RETURNING` projection of DML
+/// into a virtual table with the tuples affected on storages.
+///
+/// # Errors
+/// - DML result is not a tuple with affected rows.
+/// - Failed to cast affected rows to the table column types.
+pub fn materialize_returning(
+    plan: &mut ExecutionPlan,
+    motion_id: NodeId,
+    dml_result: Box<dyn Any>,
+) -> Result<VirtualTable, SbroadError> {
+    let tuple = dml_result.downcast::<Tuple>().map_err(|e| {
+        SbroadError::Invalid(
+            Entity::Tuple,
+            Some(format_smolstr!("expected DML result tuple: {e:?}")),
+        )
+    })?;
+    let result: ReturningResult = tuple
+        .field(0)
+        .map_err(|e| {
+            SbroadError::FailedTo(
+                Action::Decode,
+                Some(Entity::Tuple),
+                format_smolstr!("DML result: {e}"),
+            )
+        })?
+        .ok_or_else(|| SbroadError::NotFound(Entity::Tuple, "in DML result".into()))?;
+
+    // Storages send back the whole tuples.
+    table_tuples_as_vtable(plan, motion_id, result.rows)
+}
+
+/// Build a virtual table for the motion over the table scan from the whole
+/// table tuples. The motion contains only the user columns of the table
+/// (without "bucket_id"), so it is removed from the tuples.
+///
+/// # Errors
+/// - Motion subtree doesn't end with a table scan.
+/// - Failed to cast tuples to the table column types.
+fn table_tuples_as_vtable(
+    plan: &mut ExecutionPlan,
+    motion_id: NodeId,
+    mut rows: Vec<ExecutorTuple>,
+) -> Result<VirtualTable, SbroadError> {
+    let ir = plan.get_ir_plan();
+    let alias = if let Relational::Motion(Motion { alias, .. }) = ir.get_relation_node(motion_id)? {
+        alias.clone()
+    } else {
+        panic!("Expected motion node with id {motion_id:?}");
+    };
+    let mut rel_id = ir.get_relational_child(motion_id, 0)?;
+    let relation = loop {
+        if let Relational::ScanRelation(ScanRelation { relation, .. }) =
+            ir.get_relation_node(rel_id)?
+        {
+            break relation;
+        }
+        rel_id = ir.get_relational_child(rel_id, 0)?;
+    };
+    if let Some(pos) = ir
+        .get_relation_or_error(relation)?
+        .get_bucket_id_position()?
+    {
+        for row in rows.iter_mut().filter(|row| pos < row.len()) {
+            row.remove(pos);
+        }
+    }
+    let columns = vtable_columns(ir, motion_id)?;
+    plan.unlink_motion_subtree(motion_id)?;
+
+    let mut producer = ProducerResult {
+        rows,
+        ..Default::default()
+    };
+    let mut vtable = producer.as_virtual_table(columns)?;
+    if let Some(name) = alias {
+        vtable.set_alias(name.as_str());
+    }
+
+    Ok(vtable)
+}
+
 /// Function that is called from `exec_ir_on_some_buckets`.
 /// Its purpose is to iterate through every vtable presented in `plan` subtree and
 /// to replace them by new vtables. New vtables indices (map bucket id -> tuples) will contain
@@ -1552,7 +1675,7 @@ pub fn execute_dml_on_storage<R: Vshard + QueryCache>(
     runtime: &R,
     raw_optional: &mut Vec<u8>,
     required: &mut RequiredData,
-) -> Result<ConsumerResult, SbroadError>
+) -> Result<ReturningResult, SbroadError>
 where
     R::Cache: StorageCache,
 {
@@ -1619,20 +1742,122 @@ where
     Ok(())
 }
 
+/// Space of the DML target table. The tuples of a partitioned table
+/// are stored in the spaces of its partitions.
+struct TargetSpace {
+    space: Space,
+    partitions: Option<TargetPartitions>,
+}
+
+struct TargetPartitions {
+    partitioning: Partitioning,
+    /// Position of the partition key in the primary key.
+    pk_position: usize,
+    spaces: HashMap<SmolStr, Space>,
+}
+
+fn find_space(name: &str) -> Result<Space, SbroadError> {
+    Space::find(name).ok_or_else(|| {
+        SbroadError::Invalid(
+            Entity::Space,
+            Some(format_smolstr!("space {name} not found")),
+        )
+    })
+}
+
+impl TargetSpace {
+    fn new(table: &Table) -> Result<Self, SbroadError> {
+        let space = find_space(&table.name)?;
+        let Some(partitioning) = &table.partitioning else {
+            return Ok(TargetSpace {
+                space,
+                partitions: None,
+            });
+        };
+        let pk_position = table
+            .primary_key
+            .positions
+            .iter()
+            .position(|pos| *pos == partitioning.column)
+            .ok_or_else(|| {
+                SbroadError::Invalid(
+                    Entity::PrimaryKey,
+                    Some(format_smolstr!(
+                        "primary key of {} doesn't contain the partition key",
+                        table.name
+                    )),
+                )
+            })?;
+        Ok(TargetSpace {
+            space,
+            partitions: Some(TargetPartitions {
+                partitioning: partitioning.clone(),
+                pk_position,
+                spaces: HashMap::new(),
+            }),
+        })
+    }
+
+    /// Space to store the table tuple.
+    fn for_tuple(&mut self, tuple: &[EncodedValue]) -> Result<&Space, SbroadError> {
+        match &mut self.partitions {
+            None => Ok(&self.space),
+            Some(partitions) => {
+                let position = partitions.partitioning.column;
+                partitions.space(tuple, position)
+            }
+        }
+    }
+
+    /// Space storing the tuple with the primary key.
+    fn for_pk(&mut self, pk: &[EncodedValue]) -> Result<&Space, SbroadError> {
+        match &mut self.partitions {
+            None => Ok(&self.space),
+            Some(partitions) => {
+                let position = partitions.pk_position;
+                partitions.space(pk, position)
+            }
+        }
+    }
+}
+
+impl TargetPartitions {
+    fn space(&mut self, values: &[EncodedValue], position: usize) -> Result<&Space, SbroadError> {
+        let key = match values.get(position) {
+            Some(EncodedValue::Ref(value)) => Value::from(EncodedValue::Ref(value.clone())),
+            Some(EncodedValue::Owned(value)) => value.clone(),
+            None => {
+                return Err(SbroadError::Invalid(
+                    Entity::Tuple,
+                    Some(format_smolstr!(
+                        "partition key not found at position {position}"
+                    )),
+                ))
+            }
+        };
+        let partition = self.partitioning.partition_for(&key)?;
+        match self.spaces.entry(partition.name.clone()) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => Ok(entry.insert(find_space(&partition.name)?)),
+        }
+    }
+}
+
 #[allow(clippy::too_many_lines)]
 fn execute_update_on_storage<R: Vshard + QueryCache>(
     runtime: &R,
     optional: &mut OptionalData,
     required: &mut RequiredData,
-) -> Result<ConsumerResult, SbroadError>
+) -> Result<ReturningResult, SbroadError>
 where
     R::Cache: StorageCache,
 {
     let plan = optional.exec_plan.get_ir_plan();
     let update_id = plan.get_top()?;
     let update_child_id = plan.dml_child_id(update_id)?;
-    let space_name = plan.dml_node_table(update_id)?.name().clone();
-    let mut result = ConsumerResult::default();
+    let mut space = TargetSpace::new(plan.dml_node_table(update_id)?)?;
+    let returning = plan.dml_returning(update_id)?.is_some();
+    let mut result = ReturningResult::default();
     let is_sharded = plan.is_sharded_update(update_id)?;
     let build_vtable_locally = !optional
         .exec_plan
@@ -1648,21 +1873,30 @@ where
         materialize_vtable_locally(runtime, optional, required, update_child_id)?;
     }
     let vtable = optional.exec_plan.get_motion_vtable(update_child_id)?;
-    let space = Space::find(&space_name).ok_or_else(|| {
-        SbroadError::Invalid(
-            Entity::Space,
-            Some(format_smolstr!("space {space_name} not found")),
-        )
-    })?;
-    transaction(|| -> Result<(), SbroadError> {
+    dml_transaction(runtime, || -> Result<(), SbroadError> {
         let plan = optional.exec_plan.get_ir_plan();
         if is_sharded {
             let delete_tuple_len = plan.get_update_delete_tuple_len(update_id)?;
             let builder = init_sharded_update_tuple_builder(plan, &vtable, update_id)?;
-            execute_sharded_update(&mut result, &vtable, &space, &builder, delete_tuple_len)?;
+            execute_sharded_update(
+                runtime,
+                &mut result,
+                &vtable,
+                &mut space,
+                &builder,
+                delete_tuple_len,
+                returning,
+            )?;
         } else {
             let builder = init_local_update_tuple_builder(plan, &vtable, update_id)?;
-            execute_local_update(&mut result, &builder, &vtable, &space)?;
+            execute_local_update(
+                runtime,
+                &mut result,
+                &builder,
+                &vtable,
+                &mut space,
+                returning,
+            )?;
         }
         Ok(())
     })?;
@@ -1672,12 +1906,14 @@ where
 
 /// A working horse for `execute_update_on_storage` in case we're dealing with
 /// sharded update.
-fn execute_sharded_update(
-    result: &mut ConsumerResult,
+fn execute_sharded_update<R: Vshard>(
+    runtime: &R,
+    result: &mut ReturningResult,
     vtable: &VirtualTable,
-    space: &Space,
+    space: &mut TargetSpace,
     builder: &TupleBuilderPattern,
     delete_tuple_len: usize,
+    returning: bool,
 ) -> Result<(), SbroadError> {
     for tuple in vtable.get_tuples() {
         if tuple.len() == delete_tuple_len {
@@ -1685,13 +1921,14 @@ fn execute_sharded_update(
                 .iter()
                 .map(|val| EncodedValue::Ref(MsgPackValue::from(val)))
                 .collect();
-            if let Err(Error::Tarantool(tnt_err)) = space.delete(&pk) {
+            if let Err(Error::Tarantool(tnt_err)) = space.for_pk(&pk)?.delete(&pk) {
                 return Err(SbroadError::FailedTo(
                     Action::Delete,
                     Some(Entity::Tuple),
                     format_smolstr!("{tnt_err:?}"),
                 ));
             }
+            runtime.check_interrupted()?;
         }
     }
     for (bucket_id, positions) in vtable.get_bucket_index() {
@@ -1742,14 +1979,21 @@ fn execute_sharded_update(
                 }
                 // We can have multiple rows with the same primary key,
                 // so replace is used.
-                if let Err(e) = space.replace(&insert_tuple) {
-                    return Err(SbroadError::FailedTo(
-                        Action::Insert,
-                        Some(Entity::Tuple),
-                        format_smolstr!("{e:?}"),
-                    ));
+                let tuple = space
+                    .for_tuple(&insert_tuple)?
+                    .replace(&insert_tuple)
+                    .map_err(|e| {
+                        SbroadError::FailedTo(
+                            Action::Insert,
+                            Some(Entity::Tuple),
+                            format_smolstr!("{e:?}"),
+                        )
+                    })?;
+                if returning {
+                    push_returning_row(result, &tuple)?;
                 }
                 result.row_count += 1;
+                runtime.check_interrupted()?;
             }
         }
     }
@@ -1850,19 +2094,27 @@ pub fn build_update_args<'t>(
 
 /// A working horse for `execute_update_on_storage` in case we're dealing with
 /// nonsharded update.
-fn execute_local_update(
-    result: &mut ConsumerResult,
+fn execute_local_update<R: Vshard>(
+    runtime: &R,
+    result: &mut ReturningResult,
     builder: &TupleBuilderPattern,
     vtable: &VirtualTable,
-    space: &Space,
+    space: &mut TargetSpace,
+    returning: bool,
 ) -> Result<(), SbroadError> {
     for vt_tuple in vtable.get_tuples() {
         let args = build_update_args(vt_tuple, builder)?;
-        let update_res = space.update(&args.key_tuple, &args.ops);
-        update_res.map_err(|e| {
+        let update_res = space
+            .for_pk(&args.key_tuple)?
+            .update(&args.key_tuple, &args.ops);
+        let tuple = update_res.map_err(|e| {
             SbroadError::FailedTo(Action::Update, Some(Entity::Space), format_smolstr!("{e}"))
         })?;
+        if let Some(tuple) = tuple.filter(|_| returning) {
+            push_returning_row(result, &tuple)?;
+        }
         result.row_count += 1;
+        runtime.check_interrupted()?;
     }
     Ok(())
 }
@@ -1905,7 +2157,7 @@ fn execute_delete_on_storage<R: Vshard + QueryCache>(
     runtime: &R,
     optional: &mut OptionalData,
     required: &mut RequiredData,
-) -> Result<ConsumerResult, SbroadError>
+) -> Result<ReturningResult, SbroadError>
 where
     R::Cache: StorageCache,
 {
@@ -1917,6 +2169,9 @@ where
         // We have a deal with a DELETE without WHERE filter
         // and want to execute local SQL instead of space api.
 
+        // The statement is prepared in a separate fiber, so it can't
+        // be executed in a transaction and is not interrupted midway.
+        runtime.check_interrupted()?;
         let mut info = QueryInfo::new(optional, required);
         let mut locked_cache = runtime.cache().lock();
         let res = read_or_prepare::<R, R::Mutex>(
@@ -1939,13 +2194,17 @@ where
                 )
             })?;
         let res = data.remove(0);
-        return Ok(res);
+        return Ok(ReturningResult {
+            row_count: res.row_count,
+            rows: Vec::new(),
+        });
     }
 
     let delete_child_id = delete_childen[0];
     let builder = init_delete_tuple_builder(plan, delete_id)?;
-    let space_name = plan.dml_node_table(delete_id)?.name().clone();
-    let mut result = ConsumerResult::default();
+    let mut space = TargetSpace::new(plan.dml_node_table(delete_id)?)?;
+    let returning = plan.dml_returning(delete_id)?.is_some();
+    let mut result = ReturningResult::default();
     let build_vtable_locally = !optional
         .exec_plan
         .contains_vtable_for_motion(delete_child_id);
@@ -1953,23 +2212,22 @@ where
         materialize_vtable_locally(runtime, optional, required, delete_child_id)?;
     }
     let vtable = optional.exec_plan.get_motion_vtable(delete_child_id)?;
-    let space = Space::find(&space_name).ok_or_else(|| {
-        SbroadError::Invalid(
-            Entity::Space,
-            Some(format_smolstr!("space {space_name} not found")),
-        )
-    })?;
-    transaction(|| -> Result<(), SbroadError> {
+    dml_transaction(runtime, || -> Result<(), SbroadError> {
         for vt_tuple in vtable.get_tuples() {
             let delete_tuple = build_delete_args(vt_tuple, &builder)?;
-            if let Err(Error::Tarantool(tnt_err)) = space.delete(&delete_tuple) {
-                return Err(SbroadError::FailedTo(
-                    Action::Delete,
-                    Some(Entity::Tuple),
-                    format_smolstr!("{tnt_err:?}"),
-                ));
+            match space.for_pk(&delete_tuple)?.delete(&delete_tuple) {
+                Err(Error::Tarantool(tnt_err)) => {
+                    return Err(SbroadError::FailedTo(
+                        Action::Delete,
+                        Some(Entity::Tuple),
+                        format_smolstr!("{tnt_err:?}"),
+                    ));
+                }
+                Ok(Some(tuple)) if returning => push_returning_row(&mut result, &tuple)?,
+                _ => {}
             }
             result.row_count += 1;
+            runtime.check_interrupted()?;
         }
         Ok(())
     })?;
@@ -1982,7 +2240,7 @@ fn execute_insert_on_storage<R: Vshard + QueryCache>(
     runtime: &R,
     optional: &mut OptionalData,
     required: &mut RequiredData,
-) -> Result<ConsumerResult, SbroadError>
+) -> Result<ReturningResult, SbroadError>
 where
     R::Cache: StorageCache,
 {
@@ -1992,8 +2250,9 @@ where
     let plan = optional.exec_plan.get_ir_plan();
     let insert_id = plan.get_top()?;
     let insert_child_id = plan.dml_child_id(insert_id)?;
-    let space_name = plan.dml_node_table(insert_id)?.name().clone();
-    let mut result = ConsumerResult::default();
+    let mut space = TargetSpace::new(plan.dml_node_table(insert_id)?)?;
+    let returning = plan.dml_returning(insert_id)?.is_some();
+    let mut result = ReturningResult::default();
 
     // There are two ways to execute an `INSERT` query:
     // 1. Execute SQL subtree under the `INSERT` node (`INSERT .. SELECT ..`)
@@ -2012,20 +2271,38 @@ where
 
     // Check if the virtual table have been dispatched (case 2) or built locally (case 1).
     let vtable = optional.exec_plan.get_motion_vtable(insert_child_id)?;
-    let space = Space::find(&space_name).ok_or_else(|| {
-        SbroadError::Invalid(
-            Entity::Space,
-            Some(format_smolstr!("space {space_name} not found")),
-        )
-    })?;
     let plan = optional.exec_plan.get_ir_plan();
     // let builder = add_casts_to_builder(builder, &tuple_pos_to_type, vtable.as_ref())?;
     let builder = init_insert_tuple_builder(plan, vtable.as_ref(), insert_id)?;
-    let conflict_strategy = optional
-        .exec_plan
-        .get_ir_plan()
-        .insert_conflict_strategy(insert_id)?;
-    transaction(|| -> Result<(), SbroadError> {
+    let conflict_strategy = plan.insert_conflict_strategy(insert_id)?.clone();
+    let conflict_update = plan.insert_conflict_update(insert_id)?;
+    let pk_positions = plan
+        .dml_node_table(insert_id)?
+        .primary_key
+        .positions
+        .clone();
+
+    // The conflicting tuples of `ON CONFLICT DO UPDATE` are found and
+    // updated by the local SQL before the transaction is opened. Preparing
+    // and executing the SQL may yield, and a yield aborts the transaction
+    // without memtx MVCC (`instance.memtx.use_mvcc_engine`).
+    let conflict_update = match conflict_update {
+        Some(update_id) => {
+            let mut conflicts =
+                find_insert_conflicts(runtime, &vtable, &builder, &mut space, &pk_positions)?;
+            if conflicts.tuples.is_empty() {
+                None
+            } else {
+                let excluded = std::mem::take(&mut conflicts.excluded);
+                let (update_vtable, update_builder) =
+                    materialize_conflict_update(runtime, optional, required, update_id, excluded)?;
+                Some((conflicts, update_vtable, update_builder))
+            }
+        }
+        None => None,
+    };
+
+    dml_transaction(runtime, || -> Result<(), SbroadError> {
         for (bucket_id, positions) in vtable.get_bucket_index() {
             for pos in positions {
                 let vt_tuple = vtable.get_tuples().get(*pos).ok_or_else(|| {
@@ -2037,6 +2314,13 @@ where
                     )
                 })?;
                 let insert_tuple = build_insert_args(vt_tuple, &builder, Some(bucket_id))?;
+                if let Some((conflicts, ..)) = &conflict_update {
+                    let key = encode_key(&insert_tuple, &pk_positions)?;
+                    if conflicts.tuples.contains_key(&key) {
+                        continue;
+                    }
+                }
+                let space = space.for_tuple(&insert_tuple)?;
                 let insert_result = space.insert(&insert_tuple);
                 if let Err(Error::Tarantool(tnt_err)) = &insert_result {
                     if tnt_err.error_code() == TarantoolErrorCode::TupleFound as u32 {
@@ -2062,15 +2346,23 @@ where
                                         "Trying to replace according to conflict strategy"
                                     )
                                 );
-                                space.replace(&insert_tuple).map_err(|e| {
+                                let tuple = space.replace(&insert_tuple).map_err(|e| {
                                     SbroadError::FailedTo(
                                         Action::ReplaceOnConflict,
                                         Some(Entity::Space),
                                         format_smolstr!("{e}"),
                                     )
                                 })?;
+                                if returning {
+                                    push_returning_row(&mut result, &tuple)?;
+                                }
                                 result.row_count += 1;
                             }
+                            ConflictStrategy::DoUpdate => {
+                                // The tuple was inserted by a concurrent request
+                                // after the conflicts had been found.
+                                return Err(conflict_concurrent_change_error());
+                            }
                             ConflictStrategy::DoFail => {
                                 return Err(SbroadError::FailedTo(
                                     Action::Insert,
@@ -2083,25 +2375,279 @@ where
                         // jump to next tuple iteration. Otherwise
                         // the error is not DuplicateKey, and we
                         // should throw it back to user.
+                        runtime.check_interrupted()?;
                         continue;
                     };
                 }
-                insert_result.map_err(|e| {
+                let tuple = insert_result.map_err(|e| {
                     SbroadError::FailedTo(
                         Action::Insert,
                         Some(Entity::Space),
                         format_smolstr!("{e}"),
                     )
                 })?;
+                if returning {
+                    push_returning_row(&mut result, &tuple)?;
+                }
                 result.row_count += 1;
+                runtime.check_interrupted()?;
             }
         }
+        if let Some((conflicts, update_vtable, update_builder)) = &conflict_update {
+            conflicts.check_unchanged()?;
+            execute_local_update(
+                runtime,
+                &mut result,
+                update_builder,
+                update_vtable,
+                &mut space,
+                returning,
+            )?;
+        }
         Ok(())
     })?;
 
     Ok(result)
 }
 
+/// Encode the values of the key columns of the table tuple,
+/// so that the keys could be compared.
+fn encode_key(tuple: &[EncodedValue], positions: &[usize]) -> Result<Vec<u8>, SbroadError> {
+    let key = key_values(tuple, positions)?;
+    rmp_serde::to_vec(&key).map_err(|e| {
+        SbroadError::FailedTo(
+            Action::Serialize,
+            Some(Entity::Tuple),
+            format_smolstr!("{e}"),
+        )
+    })
+}
+
+/// Values of the key columns of the table tuple.
+fn key_values<'v, 't>(
+    tuple: &'v [EncodedValue<'t>],
+    positions: &[usize],
+) -> Result<Vec<&'v EncodedValue<'t>>, SbroadError> {
+    positions
+        .iter()
+        .map(|pos| {
+            tuple.get(*pos).ok_or_else(|| {
+                SbroadError::Invalid(
+                    Entity::Tuple,
+                    Some(format_smolstr!("key column at position {pos} not found")),
+                )
+            })
+        })
+        .collect()
+}
+
+fn conflict_update_twice_error() -> SbroadError {
+    SbroadError::Other("ON CONFLICT DO UPDATE command cannot affect row a second time".into())
+}
+
+fn conflict_concurrent_change_error() -> SbroadError {
+    SbroadError::Other(
+        "ON CONFLICT DO UPDATE command conflicts with a concurrent change of the row, retry the command"
+            .into(),
+    )
+}
+
+/// Table tuples conflicting by the primary key with the tuples
+/// inserted by `INSERT .. ON CONFLICT DO UPDATE`.
+struct InsertConflicts {
+    /// The conflicting table tuples by the encoded primary key.
+    tuples: HashMap<Vec<u8>, ConflictingTuple>,
+    /// Tuples rejected by insertion, they form the `excluded` table.
+    excluded: Vec<ExecutorTuple>,
+}
+
+struct ConflictingTuple {
+    space: Space,
+    key: Tuple,
+    tuple: Tuple,
+}
+
+impl InsertConflicts {
+    /// Check that the conflicting table tuples haven't been changed
+    /// since they were found, as the update was calculated over them.
+    fn check_unchanged(&self) -> Result<(), SbroadError> {
+        for conflict in self.tuples.values() {
+            let current = conflict.space.get(&conflict.key).map_err(|e| {
+                SbroadError::FailedTo(Action::Get, Some(Entity::Tuple), format_smolstr!("{e}"))
+            })?;
+            if !matches!(current, Some(tuple) if tuple.data() == conflict.tuple.data()) {
+                return Err(conflict_concurrent_change_error());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find the table tuples conflicting with the inserted ones for
+/// `ON CONFLICT DO UPDATE`. As in PostgreSQL, a table tuple can't be
+/// affected by the command twice, so the inserted tuples must have
+/// distinct primary keys.
+fn find_insert_conflicts<R: Vshard>(
+    runtime: &R,
+    vtable: &VirtualTable,
+    builder: &TupleBuilderPattern,
+    space: &mut TargetSpace,
+    pk_positions: &[usize],
+) -> Result<InsertConflicts, SbroadError> {
+    let mut conflicts = InsertConflicts {
+        tuples: HashMap::new(),
+        excluded: Vec::new(),
+    };
+    let mut affected_keys: HashSet<Vec<u8>> = HashSet::new();
+    for (bucket_id, positions) in vtable.get_bucket_index() {
+        for pos in positions {
+            let vt_tuple = vtable.get_tuples().get(*pos).ok_or_else(|| {
+                SbroadError::Invalid(
+                    Entity::VirtualTable,
+                    Some(format_smolstr!(
+                        "tuple at position {pos} not found in virtual table"
+                    )),
+                )
+            })?;
+            let insert_tuple = build_insert_args(vt_tuple, builder, Some(bucket_id))?;
+            let key = encode_key(&insert_tuple, pk_positions)?;
+            if !affected_keys.insert(key.clone()) {
+                return Err(conflict_update_twice_error());
+            }
+            let key_tuple = new_tuple(&key_values(&insert_tuple, pk_positions)?)?;
+            let space = space.for_tuple(&insert_tuple)?;
+            let found = space.get(&key_tuple).map_err(|e| {
+                SbroadError::FailedTo(Action::Get, Some(Entity::Tuple), format_smolstr!("{e}"))
+            })?;
+            if let Some(tuple) = found {
+                conflicts
+                    .excluded
+                    .push(decode_table_tuple(&new_tuple(&insert_tuple)?)?);
+                conflicts.tuples.insert(
+                    key,
+                    ConflictingTuple {
+                        space: space.clone(),
+                        key: key_tuple,
+                        tuple,
+                    },
+                );
+            }
+            runtime.check_interrupted()?;
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Calculate the local `Update` of `ON CONFLICT DO UPDATE` clause for
+/// the tuples rejected by insertion. These tuples are materialized into
+/// the `excluded` virtual table and the update expressions are calculated
+/// by the local SQL. Returns the updated tuples and their builder.
+///
+/// The rejected tuples have distinct primary keys, so every table tuple
+/// is updated at most once.
+fn materialize_conflict_update<R: Vshard + QueryCache>(
+    runtime: &R,
+    optional: &mut OptionalData,
+    required: &RequiredData,
+    update_id: NodeId,
+    excluded: Vec<ExecutorTuple>,
+) -> Result<(Rc<VirtualTable>, TupleBuilderPattern), SbroadError>
+where
+    R::Cache: StorageCache,
+{
+    let plan = optional.exec_plan.get_ir_plan();
+    let excluded_id = plan.conflict_excluded_motion(update_id)?;
+    let update_child_id = plan.dml_child_id(update_id)?;
+    let vtable = table_tuples_as_vtable(&mut optional.exec_plan, excluded_id, excluded)?;
+    let mut vtables_meta = VTablesMeta::with_capacity(1);
+    vtables_meta.insert(excluded_id, vtable.metadata());
+    // Inserted tuples are kept by the caller, they are not needed in the plan.
+    if let Some(vtables) = optional.exec_plan.get_mut_vtables() {
+        vtables.clear();
+    }
+    optional
+        .exec_plan
+        .set_motion_vtable(&excluded_id, vtable, runtime)?;
+
+    // The local SQL of the update differs from the one under the `Insert`,
+    // so it gets its own syntax tree, parameters and cache key (calculated
+    // over the stashed constants the same way as on the router).
+    optional.exec_plan.get_mut_ir_plan().stash_constants()?;
+    let subtree_id = optional
+        .exec_plan
+        .get_motion_subtree_root(update_child_id)?;
+    let sp = SyntaxPlan::new(&optional.exec_plan, subtree_id, Snapshot::Oldest)?;
+    optional.ordered = OrderedSyntaxNodes::try_from(sp)?;
+    optional.vtables_meta = vtables_meta;
+    let mut update_required = RequiredData::new(
+        optional.exec_plan.get_ir_plan().pattern_id(subtree_id)?,
+        optional.exec_plan.subtree_params(subtree_id)?,
+        required.query_type.clone(),
+        required.options.clone(),
+        required.schema_info.clone(),
+        optional.exec_plan.encode_vtables(),
+        required.txn_id.clone(),
+    );
+    update_required.request_id.clone_from(&required.request_id);
+    update_required.statement_timeout = required.statement_timeout;
+    materialize_vtable_locally(runtime, optional, &mut update_required, update_child_id)?;
+
+    let vtable = optional.exec_plan.get_motion_vtable(update_child_id)?;
+    let builder =
+        init_local_update_tuple_builder(optional.exec_plan.get_ir_plan(), &vtable, update_id)?;
+    Ok((vtable, builder))
+}
+
+fn new_tuple<T: ToTupleBuffer + ?Sized>(values: &T) -> Result<Tuple, SbroadError> {
+    Tuple::new(values).map_err(|e| {
+        SbroadError::FailedTo(Action::Create, Some(Entity::Tuple), format_smolstr!("{e}"))
+    })
+}
+
+/// Decode the table tuple from the storage.
+fn decode_table_tuple(tuple: &Tuple) -> Result<ExecutorTuple, SbroadError> {
+    msgpack::decode(tuple.data()).map_err(|e| {
+        SbroadError::FailedTo(
+            Action::Decode,
+            Some(Entity::Tuple),
+            format_smolstr!("table tuple: {e}"),
+        )
+    })
+}
+
+/// Save the tuple affected by DML with `RETURNING` clause
+/// to send it back to the router.
+fn push_returning_row(result: &mut ReturningResult, tuple: &Tuple) -> Result<(), SbroadError> {
+    result.rows.push(decode_table_tuple(tuple)?);
+    Ok(())
+}
+
+/// Apply DML changes atomically. Tarantool doesn't support nested
+/// transactions, so when the request is a part of an interactive
+/// transaction the changes join the already opened one.
+///
+/// The changes are not committed if the request was interrupted
+/// while they were applied.
+///
+/// # Errors
+/// - Failed to apply changes or to commit the transaction.
+/// - The request was interrupted.
+fn dml_transaction<R, T, F>(runtime: &R, f: F) -> Result<T, SbroadError>
+where
+    R: Vshard,
+    F: FnOnce() -> Result<T, SbroadError>,
+{
+    let apply = || -> Result<T, SbroadError> {
+        let res = f()?;
+        runtime.check_interrupted()?;
+        Ok(res)
+    };
+    if is_in_transaction() {
+        return apply();
+    }
+    Ok(transaction(apply)?)
+}
+
 /// Execute DML query locally
 ///
 /// # Errors
@@ -2270,8 +2816,17 @@ pub fn try_get_metadata_from_plan(
     // Get metadata (column types) from the top node's output tuple.
     let ir = plan.get_ir_plan();
     let top_id = ir.get_top()?;
-    let top_output_id = ir.get_relation_node(top_id)?.output();
-    let columns = ir.get_row_list(top_output_id)?;
+    Ok(Some(output_metadata(ir, top_id)?))
+}
+
+/// Get result metadata (column names and types) from the output
+/// tuple of the relational node.
+///
+/// # Errors
+/// - Output columns are not aliases.
+pub fn output_metadata(ir: &Plan, rel_id: NodeId) -> Result<Vec<MetadataColumn>, SbroadError> {
+    let output_id = ir.get_relation_node(rel_id)?.output();
+    let columns = ir.get_row_list(output_id)?;
     let mut metadata = Vec::with_capacity(columns.len());
     for col_id in columns {
         let column = ir.get_expression_node(*col_id)?;
@@ -2286,7 +2841,7 @@ pub fn try_get_metadata_from_plan(
         };
         metadata.push(MetadataColumn::new(column_name, column_type));
     }
-    Ok(Some(metadata))
+    Ok(metadata)
 }
 
 fn parse_rows(tuple: &Tuple) -> Result<&[u8], SbroadError> {
@@ -11,9 +11,10 @@ use tarantool::tuple::{Tuple, TupleBuffer};
 
 use crate::backend::sql::space::ADMIN_ID;
 use crate::error;
-use crate::errors::SbroadError;
+use crate::errors::{Action, Entity, SbroadError};
 use crate::executor::engine::helpers::proxy::SQL_CACHE_PROXY;
 use crate::executor::engine::helpers::table_name;
+use crate::ir::explain::execution_info::StorageStats;
 use crate::ir::node::NodeId;
 use crate::ir::value::{EncodedValue, Value};
 use crate::utils::ByteCounter;
@@ -33,6 +34,10 @@ const IPROTO_META: u8 = 0x32;
 /// Format:
 /// * Raw -> vector of bytes
 /// * Vshard -> Tuple(row count, cache miss flag, producer result)
+///
+/// For `EXPLAIN ANALYZE` the cache miss flag of the `Vshard` format
+/// is replaced with the statistics of the local execution
+/// (see [`attach_storage_stats`]).
 #[derive(Debug)]
 pub enum StorageReturnFormat {
     // Return result as a vector of bytes, no need
@@ -297,3 +302,71 @@ pub fn execute_unprepared(
     })??;
     result_to_tuple(&mut stream, max_rows, format)
 }
+
+/// Count opcodes of the VDBE program compiled for the query.
+///
+/// # Errors
+/// - Failed to explain the query.
+pub fn count_vdbe_opcodes(
+    query: &str,
+    params: &[Value],
+    sql_vdbe_opcode_max: u64,
+) -> Result<u64, SbroadError> {
+    let encoded_params = encoded_params(params);
+    let explain = format!("EXPLAIN {query}");
+    let mut stream = with_su(ADMIN_ID, || {
+        prepare_and_execute_raw(&explain, &encoded_params, sql_vdbe_opcode_max)
+    })??;
+    let (_, data) = parse_dql_result(&mut stream);
+    let rmpv::Value::Array(opcodes) = data else {
+        return Err(SbroadError::Invalid(
+            Entity::MsgPack,
+            Some(format_smolstr!(
+                "expected array of VDBE opcodes, got: {data:?}"
+            )),
+        ));
+    };
+    Ok(opcodes.len() as u64)
+}
+
+/// Replace the cache miss flag in the DQL result of `DqlVshard` format
+/// with the statistics of the local execution.
+///
+/// # Errors
+/// - The tuple is not a DQL result of `DqlVshard` format.
+pub fn attach_storage_stats(tuple: &Tuple, stats: &StorageStats) -> Result<Tuple, SbroadError> {
+    let decode_err = |e: &dyn std::fmt::Debug| {
+        SbroadError::FailedTo(
+            Action::Decode,
+            Some(Entity::MsgPack),
+            format_smolstr!("DQL result: {e:?}"),
+        )
+    };
+    let data = tuple.data();
+    let mut stream = data;
+    let len = rmp::decode::read_array_len(&mut stream).map_err(|e| decode_err(&e))?;
+    if len != 3 {
+        return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+            "expected DQL result of 3 elements, got {len}"
+        )));
+    }
+    let row_count: u64 = rmp_serde::decode::from_read(&mut stream).map_err(|e| decode_err(&e))?;
+    let _cache_miss: bool =
+        rmp_serde::decode::from_read(&mut stream).map_err(|e| decode_err(&e))?;
+
+    let mut buf = Vec::with_capacity(data.len() + 64);
+    write_array_len(&mut buf, 3).expect("failed to write array length");
+    rmp::encode::write_uint(&mut buf, row_count).expect("failed to write rows count");
+    rmp_serde::encode::write_named(&mut buf, stats).map_err(|e| {
+        SbroadError::FailedTo(
+            Action::Serialize,
+            Some(Entity::MsgPack),
+            format_smolstr!("storage statistics: {e:?}"),
+        )
+    })?;
+    buf.extend_from_slice(stream);
+    let tup_buf = TupleBuffer::try_from_vec(buf).map_err(|e| {
+        SbroadError::FailedTo(Action::Create, Some(Entity::Tuple), format_smolstr!("{e}"))
+    })?;
+    Ok(Tuple::from(&tup_buf))
+}
@@ -13,6 +13,7 @@ use tarantool::{
 };
 
 use crate::ir::{
+    explain::execution_info::{ReplicasetStats, RuntimeStatsRef, StorageStats},
     helpers::RepeatableState,
     transformation::redistribution::{MotionOpcode, MotionPolicy},
     tree::{
@@ -34,7 +35,8 @@ use crate::{
 };
 use ahash::AHashMap;
 use rand::{thread_rng, Rng};
-use smol_str::{format_smolstr, SmolStr};
+use serde::Deserialize;
+use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use tarantool::tlua::{CDataOnStack, LuaState, LuaTable, PushGuard, PushInto};
 use tarantool::{tlua::LuaFunction, tuple::Tuple};
 
@@ -265,6 +267,31 @@ pub fn exec_cacheable_dql_with_custom_plans<'a>(
 ) -> Result<DqlResult<'a>, SbroadError> {
     let deadline = fiber::clock().saturating_add(Duration::from_secs(waiting_timeout));
     let rs_count = rs_to_plan.len();
+    if let Some(stats) = rs_to_plan
+        .values()
+        .find_map(|plan| plan.get_stats().cloned())
+    {
+        let mut rs_to_full_args = HashMap::with_capacity(rs_count);
+        for (rs, mut exec_plan) in rs_to_plan {
+            let required_binary = build_required_binary(&mut exec_plan)?;
+            let optional_binary = build_optional_binary(exec_plan)?;
+            rs_to_full_args.insert(rs, FullMessage::new(required_binary, optional_binary));
+        }
+        let lua_table = transform_lua_res(
+            "cacheable with custom plans and statistics".into(),
+            lua_dispatch_dql(
+                lua,
+                rs_to_full_args,
+                0,
+                sql_motion_row_max,
+                waiting_timeout,
+                tier_name,
+            ),
+        )?;
+        let rs_to_res = convert_lua_table_to_rust(&lua_table, rs_count)?;
+        record_replicaset_stats(&stats, &rs_to_res);
+        return Ok(rs_to_res);
+    }
     let mut cache_required_args = HashMap::with_capacity(rs_to_plan.len());
     for (rs, exec_plan) in &mut rs_to_plan {
         let required_binary = build_required_binary(exec_plan)?;
@@ -331,7 +358,7 @@ pub struct OneRSResult<'lua> {
     // ensures ibuf_ptr is valid.
     _source: Rc<LuaTable<LuaStackGuard<'lua>>>,
     // Storage returns result as msgpack array of three elements:
-    // [row count, cache miss flag, [producer result]]
+    // [row count, cache miss flag or statistics, [producer result]]
     // This offset points to the start of producer result.
     read_offset: usize,
     // Number of rows returned
@@ -340,6 +367,31 @@ pub struct OneRSResult<'lua> {
     // only cache required data and we missed
     // the cache on the storage.
     pub cache_miss: bool,
+    // Size of the whole response in bytes.
+    pub bytes: u64,
+    // Statistics of the local execution, reported
+    // by the storage for `EXPLAIN ANALYZE`.
+    pub storage_stats: Option<StorageStats>,
+}
+
+/// The second element of the storage response.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StorageExecutionInfo {
+    CacheMiss(bool),
+    Stats(StorageStats),
+}
+
+fn record_replicaset_stats(stats: &RuntimeStatsRef, rs_to_res: &RSResultMap<'_>) {
+    let mut stats = stats.borrow_mut();
+    for (rs, result) in rs_to_res {
+        stats.add_replicaset(ReplicasetStats {
+            replicaset: rs.to_smolstr(),
+            rows: result.row_cnt,
+            bytes: result.bytes,
+            storage: result.storage_stats.clone(),
+        });
+    }
 }
 
 impl OneRSResult<'_> {
@@ -443,14 +495,19 @@ fn convert_lua_table_to_rust<'lua>(
                 let s = std::slice::from_raw_parts(ibuf.rpos, sz);
                 (s, ibuf)
             };
+            let bytes = s.len() as u64;
             let row_cnt: u64 = rmp_serde::decode::from_read(&mut s).map_err(|e| {
                 SbroadError::Other(format_smolstr!("failed to read row cnt from ibuf: {e:?}"))
             })?;
-            let cache_miss: bool = rmp_serde::decode::from_read(&mut s).map_err(|e| {
+            let info: StorageExecutionInfo = rmp_serde::decode::from_read(&mut s).map_err(|e| {
                 SbroadError::Other(format_smolstr!(
-                    "failed to read cache hit flag from ibuf: {e:?}"
+                    "failed to read cache hit flag or statistics from ibuf: {e:?}"
                 ))
             })?;
+            let (cache_miss, storage_stats) = match info {
+                StorageExecutionInfo::CacheMiss(cache_miss) => (cache_miss, None),
+                StorageExecutionInfo::Stats(stats) => (false, Some(stats)),
+            };
 
             // ptr.offset_from(ptr) requires two pointers to point to
             // the same allocated object.
@@ -464,6 +521,8 @@ fn convert_lua_table_to_rust<'lua>(
                 read_offset: usize::try_from(read_offset).unwrap(),
                 row_cnt,
                 cache_miss,
+                bytes,
+                storage_stats,
             }
         };
         rs_to_res.insert((*key).to_string(), result);
@@ -526,6 +585,30 @@ pub fn exec_cacheable_dql_with_single_plan<'lua>(
     tier_name: Option<&SmolStr>,
 ) -> Result<DqlResult<'lua>, SbroadError> {
     let deadline = fiber::clock().saturating_add(Duration::from_secs(waiting_timeout));
+    if let Some(stats) = exec_plan.get_stats().cloned() {
+        // The storage needs the whole plan to count VDBE opcodes, so
+        // the statistics are gathered in a single round.
+        let full_message = {
+            let required_binary = build_required_binary(&mut exec_plan)?;
+            let optional_binary = build_optional_binary(exec_plan)?;
+            FullMessage::new(required_binary, optional_binary)
+        };
+        let lua_table = transform_lua_res(
+            "cacheable dql with single plan and statistics".into(),
+            lua_dql_single_plan(
+                lua,
+                full_message,
+                replicasets,
+                0,
+                sql_motion_row_max,
+                waiting_timeout,
+                tier_name,
+            ),
+        )?;
+        let rs_to_res = convert_lua_table_to_rust(&lua_table, replicasets.len())?;
+        record_replicaset_stats(&stats, &rs_to_res);
+        return Ok(rs_to_res);
+    }
     let required_message = {
         let required_binary = build_required_binary(&mut exec_plan)?;
         RequiredMessage::from(required_binary)
@@ -30,17 +30,18 @@ use crate::executor::result::ProducerResult;
 use crate::executor::vtable::VirtualTable;
 use crate::executor::Cache;
 use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::ir::ddl::{PartitionBound, PartitionStrategy};
 use crate::ir::function::Function;
 use crate::ir::node::NodeId;
 use crate::ir::relation::DerivedType;
-use crate::ir::relation::{Column, ColumnRole, SpaceEngine, Table, Type};
+use crate::ir::relation::{Column, ColumnRole, Partition, Partitioning, SpaceEngine, Table, Type};
 use crate::ir::tree::Snapshot;
 use crate::ir::value::Value;
 use crate::ir::Plan;
 use crate::utils::MutexLike;
 
 use super::helpers::vshard::{prepare_rs_to_ir_map, GroupedBuckets};
-use super::helpers::{dispatch_by_buckets, normalize_name_from_sql};
+use super::helpers::{dispatch_by_buckets, normalize_name_from_sql, DEFAULT_SCHEMA};
 use super::{get_builtin_functions, DispatchReturnFormat, Metadata, QueryCache};
 
 pub const TEMPLATE: &str = "test";
@@ -50,6 +51,8 @@ pub const TEMPLATE: &str = "test";
 pub struct RouterConfigurationMock {
     functions: HashMap<SmolStr, Function>,
     tables: HashMap<SmolStr, Table>,
+    views: HashMap<SmolStr, SmolStr>,
+    search_path: Vec<SmolStr>,
     bucket_count: u64,
     sharding_column: SmolStr,
 }
@@ -73,6 +76,14 @@ impl Metadata for RouterConfigurationMock {
         }
     }
 
+    fn view(&self, view_name: &str) -> Result<Option<SmolStr>, SbroadError> {
+        Ok(self.views.get(view_name).cloned())
+    }
+
+    fn search_path(&self) -> Vec<SmolStr> {
+        self.search_path.clone()
+    }
+
     fn waiting_timeout(&self) -> u64 {
         0
     }
@@ -99,6 +110,10 @@ impl Default for RouterConfigurationMock {
 }
 
 impl RouterConfigurationMock {
+    pub fn set_search_path(&mut self, search_path: &[&str]) {
+        self.search_path = search_path.iter().map(|s| s.to_smolstr()).collect();
+    }
+
     /// Mock engine constructor.
     ///
     /// # Panics
@@ -629,6 +644,112 @@ impl RouterConfigurationMock {
                 .unwrap(),
         );
 
+        let columns = vec![
+            Column::new(
+                "id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new("doc", DerivedType::new(Type::Map), ColumnRole::User, true),
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+        ];
+
+        let sharding_key: &[&str] = &["id"];
+        let primary_key: &[&str] = &["id"];
+        tables.insert(
+            "docs".to_smolstr(),
+            Table::new_sharded(
+                "docs",
+                columns,
+                sharding_key,
+                primary_key,
+                SpaceEngine::Memtx,
+            )
+            .unwrap(),
+        );
+
+        let columns = vec![
+            Column::new(
+                "id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "data",
+                DerivedType::new(Type::Varbinary),
+                ColumnRole::User,
+                true,
+            ),
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+        ];
+
+        let sharding_key: &[&str] = &["id"];
+        let primary_key: &[&str] = &["id"];
+        tables.insert(
+            "bins".to_smolstr(),
+            Table::new_sharded(
+                "bins",
+                columns,
+                sharding_key,
+                primary_key,
+                SpaceEngine::Memtx,
+            )
+            .unwrap(),
+        );
+
+        let columns = vec![
+            Column::new(
+                "id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "ts",
+                DerivedType::new(Type::Datetime),
+                ColumnRole::User,
+                true,
+            ),
+            Column::new(
+                "duration",
+                DerivedType::new(Type::Interval),
+                ColumnRole::User,
+                true,
+            ),
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+        ];
+
+        let sharding_key: &[&str] = &["id"];
+        let primary_key: &[&str] = &["id"];
+        tables.insert(
+            "events".to_smolstr(),
+            Table::new_sharded(
+                "events",
+                columns,
+                sharding_key,
+                primary_key,
+                SpaceEngine::Memtx,
+            )
+            .unwrap(),
+        );
+
         let columns = vec![
             Column::new(
                 "a",
@@ -650,6 +771,80 @@ impl RouterConfigurationMock {
             Table::new_global("global_t", columns, primary_key).unwrap(),
         );
 
+        let columns = vec![
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+            Column::new(
+                "a",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "b",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            )
+            .with_default(Some("42".to_smolstr())),
+            Column::new("c", DerivedType::new(Type::String), ColumnRole::User, true)
+                .with_default(Some("'x' || 'y'".to_smolstr())),
+        ];
+
+        let sharding_key: &[&str] = &["a"];
+        let primary_key: &[&str] = &["a"];
+        tables.insert(
+            "t_def".to_smolstr(),
+            Table::new_sharded(
+                "t_def",
+                columns,
+                sharding_key,
+                primary_key,
+                SpaceEngine::Memtx,
+            )
+            .unwrap(),
+        );
+
+        let columns = vec![
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+            Column::new(
+                "a",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new("b", DerivedType::new(Type::Integer), ColumnRole::User, true),
+            Column::new("c", DerivedType::new(Type::Integer), ColumnRole::User, true),
+            Column {
+                generated_always: true,
+                ..Column::new("d", DerivedType::new(Type::Integer), ColumnRole::User, true)
+                    .with_default(Some(r#""b" * 2"#.to_smolstr()))
+            },
+        ];
+
+        let sharding_key: &[&str] = &["a"];
+        let primary_key: &[&str] = &["a"];
+        tables.insert(
+            "t_gen".to_smolstr(),
+            Table::new_sharded(
+                "t_gen",
+                columns,
+                sharding_key,
+                primary_key,
+                SpaceEngine::Memtx,
+            )
+            .unwrap(),
+        );
+
         // Table for sbroad-benches
         let columns = vec![
             Column::new(
@@ -1302,9 +1497,210 @@ impl RouterConfigurationMock {
             .unwrap(),
         );
 
+        let columns = vec![
+            Column::new(
+                "id",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "d",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+        ];
+        let sharding_key: &[&str] = &["id"];
+        let primary_key: &[&str] = &["id", "d"];
+        let partitions = [
+            (
+                "events_old",
+                PartitionBound::Range {
+                    from: None,
+                    to: Some(Value::Integer(10)),
+                },
+            ),
+            (
+                "events_new",
+                PartitionBound::Range {
+                    from: Some(Value::Integer(10)),
+                    to: Some(Value::Integer(20)),
+                },
+            ),
+            ("events_default", PartitionBound::Default),
+        ];
+        let mut events = Table::new_sharded(
+            "events",
+            columns.clone(),
+            sharding_key,
+            primary_key,
+            SpaceEngine::Memtx,
+        )
+        .unwrap();
+        for (name, _) in &partitions {
+            let mut partition = events.clone();
+            partition.name = (*name).to_smolstr();
+            tables.insert((*name).to_smolstr(), partition);
+        }
+        events.partitioning = Some(Partitioning {
+            strategy: PartitionStrategy::Range,
+            column: 1,
+            partitions: partitions
+                .into_iter()
+                .map(|(name, bound)| Partition {
+                    name: name.to_smolstr(),
+                    bound,
+                })
+                .collect(),
+        });
+        tables.insert("events".to_smolstr(), events);
+
+        let columns = vec![
+            Column::new(
+                "a",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new("b", DerivedType::new(Type::String), ColumnRole::User, false),
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+        ];
+        let sharding_key: &[&str] = &["a"];
+        let primary_key: &[&str] = &["a", "b"];
+        let partitions = [
+            (
+                "lt_xy",
+                PartitionBound::List {
+                    values: vec![Value::from("x"), Value::from("y")],
+                },
+            ),
+            (
+                "lt_z",
+                PartitionBound::List {
+                    values: vec![Value::from("z")],
+                },
+            ),
+        ];
+        let mut lt =
+            Table::new_sharded("lt", columns, sharding_key, primary_key, SpaceEngine::Memtx)
+                .unwrap();
+        for (name, _) in &partitions {
+            let mut partition = lt.clone();
+            partition.name = (*name).to_smolstr();
+            tables.insert((*name).to_smolstr(), partition);
+        }
+        lt.partitioning = Some(Partitioning {
+            strategy: PartitionStrategy::List,
+            column: 1,
+            partitions: partitions
+                .into_iter()
+                .map(|(name, bound)| Partition {
+                    name: name.to_smolstr(),
+                    bound,
+                })
+                .collect(),
+        });
+        tables.insert("lt".to_smolstr(), lt);
+
+        let columns = vec![
+            Column::new(
+                "a",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "b",
+                DerivedType::new(Type::Integer),
+                ColumnRole::User,
+                false,
+            ),
+            Column::new(
+                "bucket_id",
+                DerivedType::new(Type::Unsigned),
+                ColumnRole::Sharding,
+                true,
+            ),
+        ];
+        let sharding_key: &[&str] = &["a"];
+        let primary_key: &[&str] = &["a", "b"];
+        let partitions = [
+            (
+                "ht_0",
+                PartitionBound::Hash {
+                    modulus: 2,
+                    remainder: 0,
+                },
+            ),
+            (
+                "ht_1",
+                PartitionBound::Hash {
+                    modulus: 2,
+                    remainder: 1,
+                },
+            ),
+        ];
+        let mut ht =
+            Table::new_sharded("ht", columns, sharding_key, primary_key, SpaceEngine::Memtx)
+                .unwrap();
+        for (name, _) in &partitions {
+            let mut partition = ht.clone();
+            partition.name = (*name).to_smolstr();
+            tables.insert((*name).to_smolstr(), partition);
+        }
+        ht.partitioning = Some(Partitioning {
+            strategy: PartitionStrategy::Hash,
+            column: 1,
+            partitions: partitions
+                .into_iter()
+                .map(|(name, bound)| Partition {
+                    name: name.to_smolstr(),
+                    bound,
+                })
+                .collect(),
+        });
+        tables.insert("ht".to_smolstr(), ht);
+
+        // Tables of schema `s` are stored under qualified names.
+        let mut s_t = tables["t"].clone();
+        s_t.name = "s.t".to_smolstr();
+        tables.insert("s.t".to_smolstr(), s_t);
+        let mut s_t1 = tables["t1"].clone();
+        s_t1.name = "s.t1".to_smolstr();
+        tables.insert("s.t1".to_smolstr(), s_t1);
+
+        let mut views = HashMap::new();
+        views.insert(
+            "v_t".to_smolstr(),
+            "select a, b from t where c > 1".to_smolstr(),
+        );
+        views.insert("v_v".to_smolstr(), "select a from v_t".to_smolstr());
+        views.insert(
+            "v_recursive".to_smolstr(),
+            "select * from v_recursive".to_smolstr(),
+        );
+        views.insert(
+            "s.v_t".to_smolstr(),
+            r#"select a, b from "s.t" as "t""#.to_smolstr(),
+        );
+
         RouterConfigurationMock {
             functions,
             tables,
+            views,
+            search_path: vec![DEFAULT_SCHEMA.to_smolstr()],
             bucket_count: 10000,
             sharding_column: "bucket_id".into(),
         }
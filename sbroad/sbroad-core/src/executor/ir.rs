@@ -8,6 +8,7 @@ use smol_str::{format_smolstr, ToSmolStr};
 use crate::errors::{Action, Entity, SbroadError};
 use crate::executor::engine::Vshard;
 use crate::executor::vtable::{VirtualTable, VirtualTableMap};
+use crate::ir::explain::execution_info::RuntimeStatsRef;
 use crate::ir::node::expression::ExprOwned;
 use crate::ir::node::expression::{Expression, MutExpression};
 use crate::ir::node::relational::{MutRelational, RelOwned, Relational};
@@ -56,6 +57,10 @@ pub struct ExecutionPlan {
     /// Virtual tables for `Motion` nodes.
     /// Map of { `Motion` node_id -> it's corresponding data }
     pub vtables: Option<VirtualTableMap>,
+    /// Runtime statistics filled by dispatches of the plan
+    /// (and its subtrees) for `EXPLAIN ANALYZE`.
+    #[serde(skip)]
+    stats: Option<RuntimeStatsRef>,
 }
 
 impl From<Plan> for ExecutionPlan {
@@ -63,6 +68,7 @@ impl From<Plan> for ExecutionPlan {
         ExecutionPlan {
             plan,
             vtables: None,
+            stats: None,
         }
     }
 }
@@ -101,6 +107,16 @@ impl ExecutionPlan {
         &self.plan
     }
 
+    /// Runtime statistics collected for `EXPLAIN ANALYZE`, if any.
+    #[must_use]
+    pub fn get_stats(&self) -> Option<&RuntimeStatsRef> {
+        self.stats.as_ref()
+    }
+
+    pub fn set_stats(&mut self, stats: Option<RuntimeStatsRef>) {
+        self.stats = stats;
+    }
+
     #[must_use]
     pub fn get_sql_motion_row_max(&self) -> u64 {
         self.plan.options.sql_motion_row_max
@@ -168,6 +184,9 @@ impl ExecutionPlan {
                 MotionOpcode::RemoveDuplicates => {
                     vtable.remove_duplicates();
                 }
+                MotionOpcode::UnnestArrays(positions) => {
+                    vtable.unnest_arrays(positions)?;
+                }
                 MotionOpcode::ReshardIfNeeded => {
                     // Resharding must be done before applying projection
                     // to the virtual table. Otherwise projection can
@@ -213,6 +232,20 @@ impl ExecutionPlan {
                     };
                     vtable.add_missing_rows(from_vtable)?;
                 }
+                MotionOpcode::AddMissingRowsForFullJoin { motion_id } => {
+                    let motion_id = *motion_id;
+                    let Some(vtables) = &mut self.vtables else {
+                        return Err(SbroadError::UnexpectedNumberOfValues(
+                            "expected at least one virtual table".into(),
+                        ));
+                    };
+                    let Some(from_vtable) = vtables.map().get(&motion_id) else {
+                        return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                            "expected virtual table for motion {motion_id:?}"
+                        )));
+                    };
+                    vtable.add_missing_inner_rows(from_vtable)?;
+                }
                 MotionOpcode::SerializeAsEmptyTable(_) => {}
             }
         }
@@ -378,7 +411,24 @@ impl ExecutionPlan {
         let mut subtree =
             PostOrder::with_capacity(|node| plan.exec_plan_subtree_iter(node), plan.nodes.len());
         subtree.populate_nodes(top_id);
-        let nodes = subtree.take_nodes();
+        let mut nodes = subtree.take_nodes();
+
+        // `ON CONFLICT DO UPDATE` subtree is not a child of the `Insert`,
+        // but it is executed on the storage as well.
+        if let Ok(Node::Relational(Relational::Insert(Insert {
+            conflict_update: Some(update_id),
+            ..
+        }))) = plan.get_node(top_id)
+        {
+            let mut conflict_subtree = PostOrder::with_capacity(
+                |node| plan.exec_plan_subtree_iter(node),
+                plan.nodes.len(),
+            );
+            conflict_subtree.populate_nodes(*update_id);
+            let mut conflict_nodes = conflict_subtree.take_nodes();
+            conflict_nodes.append(&mut nodes);
+            nodes = conflict_nodes;
+        }
 
         // We can't replace CTE subtree as it can be reused in other slices of the plan.
         // So, collect all CTE nodes and their subtree nodes (relational and expression)
@@ -492,6 +542,13 @@ impl ExecutionPlan {
             let ir_plan = self.get_ir_plan();
             match node {
                 NodeOwned::Relational(ref mut rel) => {
+                    if let RelOwned::Insert(Insert {
+                        conflict_update: Some(update_id),
+                        ..
+                    }) = rel
+                    {
+                        *update_id = subtree_map.get_id(*update_id);
+                    }
                     match rel {
                         RelOwned::NamedWindows(NamedWindows { windows, .. }) => {
                             for window in windows {
@@ -895,6 +952,11 @@ impl ExecutionPlan {
         new_plan.stash_constants()?;
         new_plan.options = self.get_ir_plan().options.clone();
         new_plan.tier.clone_from(&self.get_ir_plan().tier);
+        new_plan.txn_id.clone_from(&self.get_ir_plan().txn_id);
+        new_plan
+            .request_id
+            .clone_from(&self.get_ir_plan().request_id);
+        new_plan.statement_deadline = self.get_ir_plan().statement_deadline;
 
         let vtables = if new_vtables.is_empty() {
             None
@@ -904,6 +966,7 @@ impl ExecutionPlan {
         let new_exec_plan = ExecutionPlan {
             plan: new_plan,
             vtables,
+            stats: self.stats.clone(),
         };
         Ok(new_exec_plan)
     }
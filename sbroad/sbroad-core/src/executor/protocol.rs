@@ -2,6 +2,7 @@ use rmp::decode::{read_array_len, Bytes, RmpRead};
 use serde::{Deserialize, Serialize};
 use smol_str::{format_smolstr, SmolStr};
 use std::collections::HashMap;
+use std::time::Duration;
 use tarantool::tlua::{self, AsLua, Push, PushGuard, PushInto, PushOne, PushOneInto, Void};
 use tarantool::tuple::{Tuple, TupleBuilder};
 
@@ -115,6 +116,14 @@ impl From<Vec<u8>> for Binary {
     }
 }
 
+impl Binary {
+    /// Raw msgpack of the wrapped tuple, i.e. an array of one binary string.
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        self.0.data()
+    }
+}
+
 impl From<Tuple> for Binary {
     #[inline(always)]
     fn from(tuple: tarantool::tuple::Tuple) -> Self {
@@ -275,6 +284,18 @@ pub struct RequiredData {
     pub options: Options,
     pub schema_info: SchemaInfo,
     pub tables: EncodedTables,
+    /// Interactive transaction the request belongs to.
+    /// Option::None means the request is executed in autocommit mode.
+    pub txn_id: Option<SmolStr>,
+    /// Query execution the request belongs to, storages reject
+    /// the requests of the cancelled queries.
+    pub request_id: Option<SmolStr>,
+    /// Time left until the statement timeout of the query expires,
+    /// storages reject the requests received after that.
+    pub statement_timeout: Option<Duration>,
+    /// Storage reports statistics of the local execution along
+    /// with the result (`EXPLAIN ANALYZE`).
+    pub collect_stats: bool,
 }
 
 impl Default for RequiredData {
@@ -286,6 +307,10 @@ impl Default for RequiredData {
             options: Options::default(),
             schema_info: SchemaInfo::default(),
             tables: EncodedTables::default(),
+            txn_id: None,
+            request_id: None,
+            statement_timeout: None,
+            collect_stats: false,
         }
     }
 }
@@ -337,6 +362,7 @@ impl RequiredData {
         options: Options,
         schema_info: SchemaInfo,
         tables: EncodedTables,
+        txn_id: Option<SmolStr>,
     ) -> Self {
         RequiredData {
             plan_id,
@@ -345,6 +371,10 @@ impl RequiredData {
             options,
             schema_info,
             tables,
+            txn_id,
+            request_id: None,
+            statement_timeout: None,
+            collect_stats: false,
         }
     }
 
@@ -8,6 +8,9 @@
 //!
 //! When executing DML (INSERT) we will get `ConsumerResult`, which fields are:
 //! * `row_count` (u64): the number of tuples inserted (that may be equal to 0)
+//!
+//! DML with `RETURNING` clause produces `ReturningResult`, which also contains
+//! * `rows` (Vec of `ExecutorTuple`): the affected tuples
 
 use core::fmt::Debug;
 use serde::ser::{Serialize, SerializeMap, Serializer};
@@ -236,6 +239,40 @@ impl Serialize for ConsumerResult {
 /// This impl allows to convert `ConsumerResult` into `Tuple`, using `Tuple::new` method.
 impl Encode for ConsumerResult {}
 
+/// Results of query execution for `INSERT`, `UPDATE` and `DELETE`
+/// with `RETURNING` clause: besides the number of affected tuples
+/// storages send back the tuples themselves.
+///
+/// Rows are omitted in serialization when empty, so a result without
+/// them has the same format as `ConsumerResult`.
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+pub struct ReturningResult {
+    pub row_count: u64,
+    #[serde(default)]
+    pub rows: Vec<ExecutorTuple>,
+}
+
+impl Serialize for ReturningResult {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.rows.is_empty() {
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry("row_count", &self.row_count)?;
+            return map.end();
+        }
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("row_count", &self.row_count)?;
+        map.serialize_entry("rows", &self.rows)?;
+        map.end()
+    }
+}
+
+/// This impl allows to convert `ReturningResult` into `Tuple`, using `Tuple::new` method.
+impl Encode for ReturningResult {}
+
 impl Plan {
     /// Checks if the plan contains a `Values` node.
     ///
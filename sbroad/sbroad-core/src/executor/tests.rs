@@ -908,6 +908,47 @@ fn groupby_linker_test() {
     assert_eq!(expected, result);
 }
 
+#[test]
+fn insert_values_from_rows() {
+    let sql = r#"INSERT INTO "t1" VALUES (NULL, NULL)"#;
+
+    let coordinator = RouterRuntimeMock::new();
+
+    let mut query = Query::new(&coordinator, sql, vec![]).unwrap();
+    let rows: Vec<Vec<Value>> = vec![
+        vec![Value::from("a"), Value::from(1_i64)],
+        vec![Value::from("b"), Value::from(2_i64)],
+    ];
+    query.set_insert_values(rows.clone()).unwrap();
+
+    let plan = query.get_exec_plan();
+    let top_id = plan.get_ir_plan().get_top().unwrap();
+    let motion_id = plan.get_ir_plan().get_relational_child(top_id, 0).unwrap();
+    let vtable = plan.get_motion_vtable(motion_id).unwrap();
+    assert_eq!(vtable.get_tuples(), rows.as_slice());
+    for (pos, row) in rows.iter().enumerate() {
+        let bucket_id = coordinator
+            .determine_bucket_id(&row.iter().collect::<Vec<_>>())
+            .unwrap();
+        assert!(vtable.get_bucket_index()[&bucket_id].contains(&pos));
+    }
+    // The VALUES subtree is not dispatched anymore.
+    assert!(plan
+        .get_ir_plan()
+        .get_relational_children(motion_id)
+        .unwrap()
+        .is_empty());
+
+    let err = Query::new(&coordinator, sql, vec![])
+        .unwrap()
+        .set_insert_values(vec![vec![Value::from("a")]])
+        .unwrap_err();
+    assert_eq!(
+        "unexpected number of values: expected 2 values in a row, got 1",
+        err.to_string()
+    );
+}
+
 mod between;
 mod bucket_id;
 mod cast;
@@ -170,6 +170,8 @@ impl VirtualTable {
             r#type: vtable_col.r#type,
             role: vtable_col.role,
             is_nullable: vtable_col.is_nullable,
+            default: None,
+            generated_always: false,
         };
         self.columns.push(col);
     }
@@ -557,6 +559,40 @@ impl VirtualTable {
     /// # Errors
     /// - invalid arguments
     pub fn add_missing_rows(&mut self, from_vtable: &Rc<VirtualTable>) -> Result<(), SbroadError> {
+        self.add_missing_rows_at(from_vtable, 0, None)
+    }
+
+    /// Adds rows that are not present in `Self`
+    /// from another virtual table.
+    ///
+    /// Assumptions:
+    /// 1. All columns from `from_vtable` are located in
+    ///    a row at the end of the current vtable's columns,
+    ///    followed by a marker column.
+    /// 2. The marker is NULL only in the rows where the columns
+    ///    of `from_vtable` are padded with NULLs.
+    ///
+    /// # Errors
+    /// - invalid arguments
+    pub fn add_missing_inner_rows(
+        &mut self,
+        from_vtable: &Rc<VirtualTable>,
+    ) -> Result<(), SbroadError> {
+        let Some(marker) = self.columns.len().checked_sub(1) else {
+            return Err(SbroadError::UnexpectedNumberOfValues(
+                "self vtable must have a marker column!".into(),
+            ));
+        };
+        let offset = marker.saturating_sub(from_vtable.columns.len());
+        self.add_missing_rows_at(from_vtable, offset, Some(marker))
+    }
+
+    fn add_missing_rows_at(
+        &mut self,
+        from_vtable: &Rc<VirtualTable>,
+        offset: usize,
+        marker: Option<usize>,
+    ) -> Result<(), SbroadError> {
         if from_vtable.columns.len() >= self.columns.len() {
             return Err(SbroadError::UnexpectedNumberOfValues(
                 "from vtable must have less columns then self vtable!".into(),
@@ -566,7 +602,13 @@ impl VirtualTable {
 
         let key_tuple_len = from_vtable.columns.len();
         for tuple in &self.tuples {
-            let key_tuple = &tuple[..key_tuple_len];
+            if let Some(marker) = marker {
+                // Skip the padding of the rows without a match.
+                if matches!(tuple[marker], Value::Null) {
+                    continue;
+                }
+            }
+            let key_tuple = &tuple[offset..offset + key_tuple_len];
             current_tuples.insert(key_tuple);
         }
         current_tuples.shrink_to_fit();
@@ -595,7 +637,7 @@ impl VirtualTable {
         self.tuples.reserve(missing_tuples_cnt);
         for (key_tuple, count) in missing_tuples {
             let mut joined_tuple = vec![Value::Null; self.columns.len()];
-            move_to_slice(&mut joined_tuple[0..key_tuple_len], key_tuple);
+            move_to_slice(&mut joined_tuple[offset..offset + key_tuple_len], key_tuple);
             for _ in 0..count - 1 {
                 self.tuples.push(joined_tuple.clone());
             }
@@ -626,6 +668,45 @@ impl VirtualTable {
         self.tuples.truncate(unique_cnt);
     }
 
+    /// Expand the arrays in the given columns into rows. Like in PostgreSQL,
+    /// several arrays of the same row are iterated in parallel: the row is
+    /// replaced with as many rows as there are elements in the longest array,
+    /// the shorter arrays are padded with NULLs. Rows where all the arrays
+    /// are empty or NULL are removed.
+    ///
+    /// # Errors
+    /// - the value in a column is neither an array nor NULL
+    pub fn unnest_arrays(&mut self, positions: &[ColumnPosition]) -> Result<(), SbroadError> {
+        let mut tuples = Vec::with_capacity(self.tuples.len());
+        for tuple in std::mem::take(&mut self.tuples) {
+            let mut arrays: Vec<&[Value]> = Vec::with_capacity(positions.len());
+            for pos in positions {
+                match tuple.get(*pos) {
+                    Some(Value::Tuple(array)) => arrays.push(&array.0),
+                    Some(Value::Null) => arrays.push(&[]),
+                    value => {
+                        return Err(SbroadError::Invalid(
+                            Entity::VirtualTable,
+                            Some(format_smolstr!(
+                                "expected an array in column {pos}, got {value:?}"
+                            )),
+                        ))
+                    }
+                }
+            }
+            let rows = arrays.iter().map(|array| array.len()).max().unwrap_or(0);
+            for idx in 0..rows {
+                let mut new_tuple = tuple.clone();
+                for (pos, array) in positions.iter().zip(&arrays) {
+                    new_tuple[*pos] = array.get(idx).cloned().unwrap_or(Value::Null);
+                }
+                tuples.push(new_tuple);
+            }
+        }
+        self.tuples = tuples;
+        Ok(())
+    }
+
     /// Convert vtable to output tuple for returning
     /// result from stored procedure.
     ///
@@ -774,7 +855,9 @@ pub fn calculate_unified_types(
             | Type::Boolean
             | Type::String
             | Type::Unsigned
-            | Type::Datetime => &[],
+            | Type::Datetime
+            | Type::Interval => &[],
+            Type::Varbinary => &[Type::String],
             Type::Uuid => &[Type::String],
             Type::Integer => &[Type::Unsigned],
             Type::Double => &[Type::Unsigned, Type::Integer],
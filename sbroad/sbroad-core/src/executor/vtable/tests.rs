@@ -242,6 +242,110 @@ fn vtable_add_missing_from2() {
     assert_eq!(expected, vtable);
 }
 
+#[test]
+fn vtable_add_missing_inner_rows() {
+    let mut vtable = VirtualTable::new();
+
+    // t: a (pk), s: b, marker
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.set_alias("t");
+    vtable.add_tuple(vec![
+        Value::from(3_u64),
+        Value::from(1_u64),
+        Value::Boolean(true),
+    ]);
+    vtable.add_tuple(vec![Value::from(4_u64), Value::Null, Value::Null]);
+
+    vtable.set_primary_key(&[0]).unwrap();
+
+    let mut from_vtable = VirtualTable::new();
+    from_vtable.add_column(vcolumn_integer_user_non_null());
+    from_vtable.set_alias("s");
+    from_vtable.add_tuple(vec![Value::from(1_u64)]);
+    from_vtable.add_tuple(vec![Value::from(2_u64)]);
+    from_vtable.add_tuple(vec![Value::from(2_u64)]);
+
+    vtable
+        .add_missing_inner_rows(&Rc::new(from_vtable))
+        .unwrap();
+
+    let expected_index = VTableIndex::new();
+
+    let expected = VirtualTable {
+        columns: vec![
+            column_integer_user_non_null(SmolStr::from("COL_1")),
+            column_integer_user_non_null(SmolStr::from("COL_2")),
+            column_integer_user_non_null(SmolStr::from("COL_3")),
+        ],
+        tuples: vec![
+            vec![Value::from(3_u64), Value::from(1_u64), Value::Boolean(true)],
+            vec![Value::from(4_u64), Value::Null, Value::Null],
+            vec![Value::Null, Value::from(2_u64), Value::Null],
+            vec![Value::Null, Value::from(2_u64), Value::Null],
+        ],
+        name: Some(SmolStr::from("t")),
+        primary_key: Some(vec![0]),
+        bucket_index: expected_index,
+    };
+
+    assert_eq!(expected, vtable);
+}
+
+/// Inner rows of NULLs are not confused with the padding
+/// of the outer rows without a match.
+#[test]
+fn vtable_add_missing_inner_null_rows() {
+    let mut vtable = VirtualTable::new();
+
+    // t: a (pk), s: b, marker
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.set_alias("t");
+    vtable.add_tuple(vec![Value::from(4_u64), Value::Null, Value::Null]);
+
+    let mut from_vtable = VirtualTable::new();
+    from_vtable.add_column(vcolumn_integer_user_non_null());
+    from_vtable.set_alias("s");
+    from_vtable.add_tuple(vec![Value::Null]);
+
+    vtable
+        .add_missing_inner_rows(&Rc::new(from_vtable))
+        .unwrap();
+
+    assert_eq!(
+        vtable.get_tuples(),
+        &[
+            vec![Value::from(4_u64), Value::Null, Value::Null],
+            vec![Value::Null, Value::Null, Value::Null],
+        ]
+    );
+
+    // The same row of NULLs matched by the join is not added again.
+    let mut vtable = VirtualTable::new();
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.set_alias("t");
+    vtable.add_tuple(vec![Value::from(4_u64), Value::Null, Value::Boolean(true)]);
+
+    let mut from_vtable = VirtualTable::new();
+    from_vtable.add_column(vcolumn_integer_user_non_null());
+    from_vtable.set_alias("s");
+    from_vtable.add_tuple(vec![Value::Null]);
+
+    vtable
+        .add_missing_inner_rows(&Rc::new(from_vtable))
+        .unwrap();
+
+    assert_eq!(
+        vtable.get_tuples(),
+        &[vec![Value::from(4_u64), Value::Null, Value::Boolean(true)]]
+    );
+}
+
 #[test]
 fn vtable_remove_duplicates1() {
     let mut vtable = VirtualTable::new();
@@ -337,6 +441,50 @@ fn vtable_remove_duplicates3() {
     assert_eq!(expected, vtable);
 }
 
+#[test]
+fn vtable_unnest_arrays() {
+    let array = |values: Vec<Value>| Value::Tuple(crate::ir::value::Tuple::from(values));
+    let mut vtable = VirtualTable::new();
+
+    // t: a, b, c
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.add_column(vcolumn_integer_user_non_null());
+    vtable.set_alias("t");
+    vtable.add_tuple(vec![
+        Value::from(1_u64),
+        array(vec![Value::from(10_u64), Value::from(20_u64)]),
+        array(vec![Value::from(100_u64)]),
+    ]);
+    vtable.add_tuple(vec![Value::from(2_u64), array(vec![]), Value::Null]);
+    vtable.add_tuple(vec![
+        Value::from(3_u64),
+        Value::Null,
+        array(vec![Value::from(300_u64)]),
+    ]);
+
+    vtable.unnest_arrays(&[1, 2]).unwrap();
+
+    assert_eq!(
+        vtable.get_tuples(),
+        &[
+            vec![
+                Value::from(1_u64),
+                Value::from(10_u64),
+                Value::from(100_u64)
+            ],
+            vec![Value::from(1_u64), Value::from(20_u64), Value::Null],
+            vec![Value::from(3_u64), Value::Null, Value::from(300_u64)],
+        ]
+    );
+
+    vtable.add_tuple(vec![Value::from(4_u64), Value::from(1_u64), Value::Null]);
+    assert_eq!(
+        "invalid virtual table: expected an array in column 1, got Some(Unsigned(1))",
+        vtable.unnest_arrays(&[1, 2]).unwrap_err().to_string()
+    );
+}
+
 #[test]
 fn vtable_values_types_casting_single_tuple() {
     let mut actual_vtable = VirtualTable::new();
@@ -3,7 +3,6 @@
 //! Parses an SQL statement to the abstract syntax tree (AST)
 //! and builds the intermediate representation (IR).
 
-use crate::ir::node::ddl::DdlOwned;
 use crate::ir::node::deallocate::Deallocate;
 use crate::ir::node::tcl::Tcl;
 use crate::ir::node::{
@@ -30,7 +29,9 @@ use time::{OffsetDateTime, Time};
 
 use crate::errors::Entity::AST;
 use crate::errors::{Action, Entity, SbroadError};
-use crate::executor::engine::helpers::{normalize_name_from_sql, to_user};
+use crate::executor::engine::helpers::{
+    normalize_name_from_sql, to_user, DEFAULT_SCHEMA, SEARCH_PATH_PARAM, STATEMENT_TIMEOUT_PARAM,
+};
 use crate::executor::engine::Metadata;
 use crate::frontend::sql::ast::{
     AbstractSyntaxTree, ParseNode, ParseNodes, ParseTree, Rule, StackParseNode,
@@ -38,17 +39,22 @@ use crate::frontend::sql::ast::{
 use crate::frontend::sql::ir::SubtreeCloner;
 use crate::frontend::sql::ir::Translation;
 use crate::frontend::Ast;
-use crate::ir::acl::AlterOption;
+use crate::ir::acl::{AlterOption, AuthMethod};
 use crate::ir::acl::{GrantRevokeType, Privilege};
-use crate::ir::aggregates::AggregateKind;
-use crate::ir::ddl::{AlterSystemType, ColumnDef, SetParamScopeType, SetParamValue};
+use crate::ir::aggregates::{AggregateKind, AggregateOrderBy};
+use crate::ir::ddl::{
+    AlterSystemType, ColumnDef, PartitionBound, PartitionBy, PartitionStrategy, SetParamScopeType,
+    SetParamValue, TableConstraint,
+};
 use crate::ir::ddl::{Language, ParamDef};
+use crate::ir::explain::{ExplainFormat, ExplainOptions};
 use crate::ir::expression::cast::Type as CastType;
 use crate::ir::expression::{
     ColumnPositionMap, ColumnWithScan, ColumnsRetrievalSpec, ExpressionId, FunctionFeature,
     Position, TrimKind, VolatilityType,
 };
 use crate::ir::expression::{NewColumnsSource, Substring};
+use crate::ir::function::{Function, JSON_ARRAY_ELEMENTS};
 use crate::ir::helpers::RepeatableState;
 use crate::ir::node::expression::{Expression, MutExpression};
 use crate::ir::node::plugin::{
@@ -57,31 +63,29 @@ use crate::ir::node::plugin::{
 };
 use crate::ir::node::relational::{MutRelational, Relational};
 use crate::ir::node::{
-    AlterSystem, AlterUser, BoolExpr, Constant, CountAsterisk, CreateIndex, CreateProc, CreateRole,
-    CreateTable, CreateUser, DropIndex, DropProc, DropRole, DropTable, DropUser, GrantPrivilege,
-    Node, NodeId, Procedure, RenameRoutine, RevokePrivilege, ScanCte, ScanRelation, SetParam,
-    SetTransaction, Trim,
+    AlterSystem, AlterUser, Analyze, BoolExpr, Constant, CountAsterisk, CreateIndex,
+    CreatePartition, CreateProc, CreateRole, CreateSchema, CreateSequence, CreateTable,
+    CreateTableAs, CreateUser, CreateView, DropIndex, DropProc, DropRole, DropSchema, DropSequence,
+    DropTable, DropUser, DropView, GrantPrivilege, Node, NodeId, Procedure, RenameRoutine,
+    RevokePrivilege, ScanCte, ScanRelation, SetParam, SetTransaction, Trim,
 };
 use crate::ir::operator::{
     Arithmetic, Bool, ConflictStrategy, JoinKind, OrderByElement, OrderByEntity, OrderByType, Unary,
 };
-use crate::ir::relation::{Column, ColumnRole, TableKind, Type as RelationType};
-use crate::ir::transformation::redistribution::ColumnPosition;
+use crate::ir::relation::{Column, ColumnRole, DerivedType, TableKind, Type as RelationType};
+use crate::ir::transformation::redistribution::{ColumnPosition, MotionPolicy, Program};
 use crate::ir::tree::traversal::{
     LevelNode, PostOrder, PostOrderWithFilter, EXPR_CAPACITY, REL_CAPACITY,
 };
 use crate::ir::value::Value;
 use crate::ir::{node::plugin, OptionKind, OptionParamValue, OptionSpec, Plan};
 use crate::warn;
-use tarantool::auth::AuthMethod;
 use tarantool::decimal::Decimal;
 use tarantool::space::SpaceEngineType;
 use type_system::TypeAnalyzer;
 
 // DDL timeout in seconds (1 day).
 const DEFAULT_TIMEOUT_F64: f64 = 24.0 * 60.0 * 60.0;
-// TODO: implement and use `AuthMethod::DEFAULT`
-// when tarantool-module gets Md5 as default one
 const DEFAULT_AUTH_METHOD: AuthMethod = AuthMethod::Md5;
 
 const DEFAULT_IF_EXISTS: bool = false;
@@ -89,11 +93,14 @@ const DEFAULT_IF_NOT_EXISTS: bool = false;
 
 const DEFAULT_WAIT_APPLIED_GLOBALLY: bool = true;
 
+// Name of the tuples proposed for insertion in `ON CONFLICT DO UPDATE` clause.
+const EXCLUDED_SCAN_NAME: &str = "excluded";
+
 fn get_default_timeout() -> Decimal {
     Decimal::from_str(&format!("{DEFAULT_TIMEOUT_F64}")).expect("default timeout casting failed")
 }
 
-/// Matches appropriate [`tarantool::auth::AuthMethod`] with passed `Rule`.
+/// Matches appropriate [`AuthMethod`] with passed `Rule`.
 /// Panics as unreachable code if no appropriate method was found.
 #[inline(always)]
 fn auth_method_from_auth_rule(auth_rule: Rule) -> AuthMethod {
@@ -101,30 +108,39 @@ fn auth_method_from_auth_rule(auth_rule: Rule) -> AuthMethod {
         Rule::ChapSha1 => AuthMethod::ChapSha1,
         Rule::Ldap => AuthMethod::Ldap,
         Rule::Md5 => AuthMethod::Md5,
+        Rule::ScramSha256 => AuthMethod::ScramSha256,
         _ => unreachable!("got a non-auth parsing rule"),
     }
 }
 
-const NUM_OF_VOLATILE_FUNCTIONS: usize = 1;
+const NUM_OF_VOLATILE_FUNCTIONS: usize = 2;
 
 /// Names used by the user in SQL.
-static USER_FACING_NAMES_OF_FUNCTIONS: [&str; NUM_OF_VOLATILE_FUNCTIONS] = ["instance_uuid"];
+static USER_FACING_NAMES_OF_FUNCTIONS: [&str; NUM_OF_VOLATILE_FUNCTIONS] =
+    ["instance_uuid", "nextval"];
 
 /// Names of functions in picodata .rs files, which annotated with `#[tarantool::proc]` and exposed to SQL.
 pub static NAMES_OF_FUNCTIONS_IN_SOURCES: [&str; NUM_OF_VOLATILE_FUNCTIONS] =
-    ["proc_instance_uuid"];
+    ["proc_instance_uuid", "proc_sequence_nextval"];
 
 /// '.' + name in sources. Plain name in sources (which is exported by picodata) doesn't work, because
 /// when tarantool executing box.func['proc_name']:call(..) (box.execute("select proc_name()")
 /// it try to find dynlib under the 'libproc_name' name with defined `proc_name` in it.
 /// With . as first symbol tarantool try to find proc symbol in current executable.
-static NAMES_OF_FUNCTIONS_IN_TARANTOOL: [&str; NUM_OF_VOLATILE_FUNCTIONS] = [".proc_instance_uuid"];
+static NAMES_OF_FUNCTIONS_IN_TARANTOOL: [&str; NUM_OF_VOLATILE_FUNCTIONS] =
+    [".proc_instance_uuid", ".proc_sequence_nextval"];
+
+/// Name of the function in sources raising an error when a recursive CTE produces
+/// more rows than `sql_recursion_row_max` allows. The local SQL can't raise errors
+/// by itself, so the generated query of the CTE calls it (as `'.' + name`).
+pub static RECURSION_ROW_MAX_EXCEEDED_FUNCTION: &str = "proc_recursion_row_max_exceeded";
 
 // Kind of map from `user-facing names` to names in sources and names
 // in `_func` space.
 #[rustfmt::skip]
 static VOLATILE_FUNCTIONS_NAMINGS: [(&str, &str, &str); NUM_OF_VOLATILE_FUNCTIONS] = [
     (USER_FACING_NAMES_OF_FUNCTIONS[0], NAMES_OF_FUNCTIONS_IN_SOURCES[0], NAMES_OF_FUNCTIONS_IN_TARANTOOL[0]),
+    (USER_FACING_NAMES_OF_FUNCTIONS[1], NAMES_OF_FUNCTIONS_IN_SOURCES[1], NAMES_OF_FUNCTIONS_IN_TARANTOOL[1]),
 ];
 
 /// Maps (maybe quoted or uppercased) name from user to real procedure name in tarantool.
@@ -139,6 +155,15 @@ pub fn get_real_function_name(name_from_sql: &str) -> Option<&'static str> {
 
 // Helper map to store CTE node ids by their names.
 type CTEs = AHashMap<SmolStr, NodeId>;
+/// CTEs declared in `WITH RECURSIVE` clauses (name -> declaration).
+type RecursiveCTEs = AHashMap<SmolStr, RecursiveCteDecl>;
+
+struct RecursiveCteDecl {
+    /// AST id of the CTE node.
+    ast_id: usize,
+    /// Whether the CTE refers to itself in its recursive part.
+    is_self_referenced: bool,
+}
 
 #[allow(clippy::uninlined_format_args)]
 fn get_timeout(ast: &AbstractSyntaxTree, node_id: usize) -> Result<Decimal, SbroadError> {
@@ -229,9 +254,11 @@ fn parse_proc_params(
             Rule::TypeDecimal => RelationType::Decimal,
             Rule::TypeDouble => RelationType::Double,
             Rule::TypeInt => RelationType::Integer,
+            Rule::TypeInterval => RelationType::Interval,
             Rule::TypeString | Rule::TypeText | Rule::TypeVarchar => RelationType::String,
             Rule::TypeUuid => RelationType::Uuid,
             Rule::TypeUnsigned => RelationType::Unsigned,
+            Rule::TypeVarbinary => RelationType::Varbinary,
             _ => unreachable!("Unexpected node: {type_node:?}"),
         };
         params.push(ParamDef { data_type });
@@ -348,6 +375,15 @@ fn parse_create_proc(
                 // We don't need to parse language node, because we support only SQL.
             }
             Rule::ProcBody => {
+                let dml_node = ast.nodes.get_node(child_node.first_child())?;
+                if let Some(last_id) = dml_node.children.last() {
+                    if ast.nodes.get_node(*last_id)?.rule == Rule::Returning {
+                        return Err(SbroadError::Unsupported(
+                            Entity::Query,
+                            Some("RETURNING clause in procedure body".into()),
+                        ));
+                    }
+                }
                 body = child_node
                     .value
                     .as_ref()
@@ -694,6 +730,283 @@ fn parse_drop_index(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<DropIn
     })
 }
 
+fn parse_create_sequence(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<CreateSequence, SbroadError> {
+    assert_eq!(node.rule, Rule::CreateSequence);
+    let mut name = SmolStr::default();
+    let mut start = 1;
+    let mut increment = 1;
+    let mut timeout = get_default_timeout();
+    let mut if_not_exists = DEFAULT_IF_NOT_EXISTS;
+    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::Identifier => name = parse_identifier(ast, *child_id)?,
+            Rule::SequenceStart => start = parse_sequence_option(ast, child_node)?,
+            Rule::SequenceIncrement => increment = parse_sequence_option(ast, child_node)?,
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            Rule::IfNotExists => if_not_exists = true,
+            Rule::WaitAppliedGlobally => wait_applied_globally = true,
+            Rule::WaitAppliedLocally => wait_applied_globally = false,
+            _ => panic!("Unexpected create sequence node: {child_node:?}"),
+        }
+    }
+    if increment == 0 {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some("sequence INCREMENT must not be zero".into()),
+        ));
+    }
+    Ok(CreateSequence {
+        name,
+        start,
+        increment,
+        if_not_exists,
+        wait_applied_globally,
+        timeout,
+    })
+}
+
+fn parse_sequence_option(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<i64, SbroadError> {
+    let value_node = ast.nodes.get_node(node.first_child())?;
+    assert_eq!(value_node.rule, Rule::Integer);
+    let Some(str_value) = value_node.value.as_ref() else {
+        return Err(SbroadError::Invalid(
+            AST,
+            Some("Integer node has value".into()),
+        ));
+    };
+    str_value.parse::<i64>().map_err(|_| {
+        SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!(
+                "sequence option value is out of range: {str_value}"
+            )),
+        )
+    })
+}
+
+fn parse_drop_sequence(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<DropSequence, SbroadError> {
+    assert_eq!(node.rule, Rule::DropSequence);
+    let mut name = SmolStr::default();
+    let mut timeout = get_default_timeout();
+    let mut if_exists = DEFAULT_IF_EXISTS;
+    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::Identifier => name = parse_identifier(ast, *child_id)?,
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            Rule::IfExists => if_exists = true,
+            Rule::WaitAppliedGlobally => wait_applied_globally = true,
+            Rule::WaitAppliedLocally => wait_applied_globally = false,
+            _ => panic!("Unexpected drop sequence node: {child_node:?}"),
+        }
+    }
+    Ok(DropSequence {
+        name,
+        if_exists,
+        timeout,
+        wait_applied_globally,
+    })
+}
+
+fn parse_create_view(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<CreateView, SbroadError> {
+    assert_eq!(node.rule, Rule::CreateView);
+    let mut name = SmolStr::default();
+    let mut query = SmolStr::default();
+    let mut or_replace = false;
+    let mut timeout = get_default_timeout();
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::NewTable => name = parse_identifier(ast, *child_id)?,
+            Rule::OrReplace => or_replace = true,
+            Rule::ViewBody => {
+                query = child_node
+                    .value
+                    .as_ref()
+                    .expect("view query must not be empty")
+                    .clone();
+            }
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            _ => panic!("Unexpected create view node: {child_node:?}"),
+        }
+    }
+    Ok(CreateView {
+        name,
+        query,
+        or_replace,
+        wait_applied_globally: DEFAULT_WAIT_APPLIED_GLOBALLY,
+        timeout,
+    })
+}
+
+fn parse_drop_view(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<DropView, SbroadError> {
+    assert_eq!(node.rule, Rule::DropView);
+    let mut name = SmolStr::default();
+    let mut timeout = get_default_timeout();
+    let mut if_exists = DEFAULT_IF_EXISTS;
+    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::TableNameIdentifier => name = parse_identifier(ast, *child_id)?,
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            Rule::IfExists => if_exists = true,
+            Rule::WaitAppliedGlobally => wait_applied_globally = true,
+            Rule::WaitAppliedLocally => wait_applied_globally = false,
+            _ => panic!("Unexpected drop view node: {child_node:?}"),
+        }
+    }
+    Ok(DropView {
+        name,
+        if_exists,
+        timeout,
+        wait_applied_globally,
+    })
+}
+
+fn parse_create_schema(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<CreateSchema, SbroadError> {
+    assert_eq!(node.rule, Rule::CreateSchema);
+    let mut name = SmolStr::default();
+    let mut timeout = get_default_timeout();
+    let mut if_not_exists = DEFAULT_IF_NOT_EXISTS;
+    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::Identifier => name = parse_identifier(ast, *child_id)?,
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            Rule::IfNotExists => if_not_exists = true,
+            Rule::WaitAppliedGlobally => wait_applied_globally = true,
+            Rule::WaitAppliedLocally => wait_applied_globally = false,
+            _ => panic!("Unexpected create schema node: {child_node:?}"),
+        }
+    }
+    if name == DEFAULT_SCHEMA {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!("schema {DEFAULT_SCHEMA} already exists")),
+        ));
+    }
+    Ok(CreateSchema {
+        name,
+        if_not_exists,
+        wait_applied_globally,
+        timeout,
+    })
+}
+
+fn parse_drop_schema(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<DropSchema, SbroadError> {
+    assert_eq!(node.rule, Rule::DropSchema);
+    let mut name = SmolStr::default();
+    let mut timeout = get_default_timeout();
+    let mut if_exists = DEFAULT_IF_EXISTS;
+    let mut cascade = false;
+    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::Identifier => name = parse_identifier(ast, *child_id)?,
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            Rule::IfExists => if_exists = true,
+            Rule::Cascade => cascade = true,
+            Rule::WaitAppliedGlobally => wait_applied_globally = true,
+            Rule::WaitAppliedLocally => wait_applied_globally = false,
+            _ => panic!("Unexpected drop schema node: {child_node:?}"),
+        }
+    }
+    if name == DEFAULT_SCHEMA {
+        return Err(SbroadError::Unsupported(
+            Entity::Query,
+            Some(format_smolstr!("dropping schema {DEFAULT_SCHEMA}")),
+        ));
+    }
+    Ok(DropSchema {
+        name,
+        if_exists,
+        cascade,
+        wait_applied_globally,
+        timeout,
+    })
+}
+
+fn parse_analyze(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<Analyze, SbroadError> {
+    assert_eq!(node.rule, Rule::Analyze);
+    let mut table = None;
+    let mut timeout = get_default_timeout();
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::Table => table = Some(parse_identifier(ast, *child_id)?),
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            _ => panic!("Unexpected analyze node: {child_node:?}"),
+        }
+    }
+    Ok(Analyze { table, timeout })
+}
+
+fn parse_explain_options(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<ExplainOptions, SbroadError> {
+    assert_eq!(node.rule, Rule::Explain);
+    let mut options = ExplainOptions::default();
+    // The last child is the explained query.
+    let (_, option_ids) = node
+        .children
+        .split_last()
+        .expect("Explain has no children.");
+    for option_id in option_ids {
+        let option_node = ast.nodes.get_node(*option_id)?;
+        match option_node.rule {
+            Rule::ExplainAnalyze => options.analyze = true,
+            Rule::ExplainOptions => {
+                for param_id in &option_node.children {
+                    let param_node = ast.nodes.get_node(*param_id)?;
+                    match param_node.rule {
+                        Rule::ExplainAnalyzeOption => {
+                            options.analyze = match param_node.children.first() {
+                                Some(flag_id) => ast.nodes.get_node(*flag_id)?.rule == Rule::True,
+                                None => true,
+                            };
+                        }
+                        Rule::ExplainFormat => {
+                            let format_id = param_node
+                                .children
+                                .first()
+                                .expect("Format expected under ExplainFormat");
+                            options.format = match ast.nodes.get_node(*format_id)?.rule {
+                                Rule::ExplainFormatJson => ExplainFormat::Json,
+                                _ => ExplainFormat::Text,
+                            };
+                        }
+                        _ => panic!("Unexpected explain option: {param_node:?}"),
+                    }
+                }
+            }
+            _ => panic!("Unexpected explain node: {option_node:?}"),
+        }
+    }
+    Ok(options)
+}
+
 fn parse_column_def_type(node: &ParseNode) -> Result<RelationType, SbroadError> {
     let data_type = match node.rule {
         Rule::TypeBool => RelationType::Boolean,
@@ -701,10 +1014,12 @@ fn parse_column_def_type(node: &ParseNode) -> Result<RelationType, SbroadError>
         Rule::TypeDecimal => RelationType::Decimal,
         Rule::TypeDouble => RelationType::Double,
         Rule::TypeInt => RelationType::Integer,
+        Rule::TypeInterval => RelationType::Interval,
         Rule::TypeString | Rule::TypeText | Rule::TypeVarchar => RelationType::String,
         Rule::TypeUnsigned => RelationType::Unsigned,
         Rule::TypeJSON => RelationType::Map,
         Rule::TypeUuid => RelationType::Uuid,
+        Rule::TypeVarbinary => RelationType::Varbinary,
         _ => {
             panic!("Met unexpected rule under ColumnDef: {:?}.", node.rule);
         }
@@ -712,70 +1027,369 @@ fn parse_column_def_type(node: &ParseNode) -> Result<RelationType, SbroadError>
     Ok(data_type)
 }
 
-#[allow(clippy::too_many_lines)]
-#[allow(clippy::uninlined_format_args)]
-fn parse_create_table(
-    ast: &AbstractSyntaxTree,
-    node: &ParseNode,
-) -> Result<CreateTable, SbroadError> {
-    assert_eq!(
-        node.rule,
-        Rule::CreateTable,
-        "Expected rule CreateTable, got {:?}.",
-        node.rule
-    );
-    let mut table_name = SmolStr::default();
-    let mut columns: Vec<ColumnDef> = Vec::new();
-    let mut pk_keys: Vec<SmolStr> = Vec::new();
-    let mut shard_key: Vec<SmolStr> = Vec::new();
-    let mut engine_type: SpaceEngineType = SpaceEngineType::default();
-    let mut explicit_null_columns: AHashSet<SmolStr> = AHashSet::new();
-    let mut timeout = get_default_timeout();
-    let mut tier = None;
-    let mut is_global = false;
-    let mut if_not_exists = DEFAULT_IF_NOT_EXISTS;
-    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+/// Name of the sequence implicitly created for a `SERIAL` or an identity
+/// column, the same as in PostgreSQL.
+fn implicit_sequence_name(table: &str, column: &str) -> SmolStr {
+    format_smolstr!("{table}_{column}_seq")
+}
 
-    let nullable_primary_key_column_error = Err(SbroadError::Invalid(
-        Entity::Column,
-        Some(SmolStr::from(
-            "Primary key mustn't contain nullable columns.",
-        )),
-    ));
-    let primary_key_already_declared_error = Err(SbroadError::Invalid(
-        Entity::Node,
-        Some(format_smolstr!("Primary key has been already declared.",)),
-    ));
+/// Error on an explicit value of a `GENERATED ALWAYS` column.
+fn generated_always_column_error(column: &str, action: Action) -> SbroadError {
+    SbroadError::FailedTo(
+        action,
+        Some(Entity::Column),
+        format_smolstr!(
+            "column {} is GENERATED ALWAYS, its value can't be specified explicitly",
+            to_user(column)
+        ),
+    )
+}
 
-    for child_id in &node.children {
-        let child_node = ast.nodes.get_node(*child_id)?;
-        match child_node.rule {
-            Rule::IfNotExists => if_not_exists = true,
-            Rule::NewTable => {
-                table_name = parse_identifier(ast, *child_id)?;
+/// `DEFAULT` expression of a column backed by a sequence. The name is quoted,
+/// so that `nextval` doesn't lowercase it.
+fn nextval_default(sequence: &str) -> SmolStr {
+    let escaped = sequence.replace('\'', "''");
+    format_smolstr!("nextval('\"{escaped}\"')")
+}
+
+/// Parse `DEFAULT` expression of the column definition. The expression is
+/// stored as SQL text and is calculated by the router on insertion, so it
+/// can't depend on the inserted tuple or on the query parameters.
+fn parse_column_default(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<SmolStr, SbroadError> {
+    debug_assert_eq!(node.rule, Rule::ColumnDefault);
+    let expr_node = ast.nodes.get_node(node.first_child())?;
+    let mut stack = expr_node.children.clone();
+    while let Some(id) = stack.pop() {
+        let child = ast.nodes.get_node(id)?;
+        let forbidden = match child.rule {
+            Rule::Parameter => Some("parameters"),
+            Rule::SubQuery => Some("subqueries"),
+            Rule::Over => Some("window functions"),
+            Rule::IdentifierWithOptionalContinuation => {
+                let is_function = match child.children.get(1) {
+                    Some(id) => {
+                        ast.nodes.get_node(*id)?.rule == Rule::FunctionInvocationContinuation
+                    }
+                    None => false,
+                };
+                (!is_function).then_some("column references")
             }
-            Rule::Columns => {
-                let columns_node = ast.nodes.get_node(*child_id)?;
-                for col_id in &columns_node.children {
-                    let column_def_node = ast.nodes.get_node(*col_id)?;
-                    let column_def_children = &column_def_node.children;
+            _ => None,
+        };
+        if let Some(forbidden) = forbidden {
+            return Err(SbroadError::Unsupported(
+                Entity::Expression,
+                Some(format_smolstr!("{forbidden} in column DEFAULT expression")),
+            ));
+        }
+        stack.extend(child.children.iter().copied());
+    }
+    let value = expr_node
+        .value
+        .clone()
+        .expect("column default must not be empty");
+    Ok(value)
+}
 
-                    let name_node_id = column_def_children
-                        .first()
-                        .expect("ColumnDef should have a name child node");
-                    let name = parse_identifier(ast, *name_node_id)?;
+/// SQL text of the `CHECK` expression with the column names quoted,
+/// so that the storages resolve them the same way as the router.
+fn check_expr_text(pair: &Pair<'_, Rule>) -> SmolStr {
+    let text = pair.as_str();
+    let start = pair.as_span().start();
+    let mut result = String::with_capacity(text.len());
+    let mut pos = 0;
+    for inner in pair.clone().into_inner().flatten() {
+        if inner.as_rule() != Rule::IdentifierWithOptionalContinuation {
+            continue;
+        }
+        let mut parts = inner.into_inner();
+        let identifier = parts.next().expect("identifier expected");
+        // Function calls are left as is, qualified references are
+        // rejected on the CREATE TABLE parsing.
+        if parts.next().is_some() {
+            continue;
+        }
+        let span = identifier.as_span();
+        result.push_str(&text[pos..span.start() - start]);
+        result.push_str(&to_user(normalize_name_from_sql(identifier.as_str())));
+        pos = span.end() - start;
+    }
+    result.push_str(&text[pos..]);
+    SmolStr::from(result)
+}
 
-                    let column_ty_node_id = column_def_children
+/// Returns the columns referenced by the `CHECK` expression.
+fn parse_check_expr(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<Vec<SmolStr>, SbroadError> {
+    debug_assert_eq!(node.rule, Rule::CheckExpr);
+    parse_row_expr_columns(ast, node, "CHECK constraint")
+}
+
+/// Returns the columns referenced by the expression of the generated column.
+fn parse_generated_expr(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<Vec<SmolStr>, SbroadError> {
+    debug_assert_eq!(node.rule, Rule::GeneratedExpr);
+    parse_row_expr_columns(ast, node, "generated column expression")
+}
+
+/// Returns the columns referenced by the expression calculated over a single
+/// row of the table (`what` names the expression in the errors).
+fn parse_row_expr_columns(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+    what: &str,
+) -> Result<Vec<SmolStr>, SbroadError> {
+    let mut columns = Vec::new();
+    let mut stack = node.children.clone();
+    while let Some(id) = stack.pop() {
+        let child = ast.nodes.get_node(id)?;
+        let forbidden = match child.rule {
+            Rule::Parameter => Some("parameters"),
+            Rule::SubQuery => Some("subqueries"),
+            Rule::Over => Some("window functions"),
+            Rule::IdentifierWithOptionalContinuation => match child.children.get(1) {
+                None => {
+                    columns.push(parse_identifier(ast, child.first_child())?);
+                    None
+                }
+                Some(id) if ast.nodes.get_node(*id)?.rule == Rule::ReferenceContinuation => {
+                    Some("qualified column references")
+                }
+                Some(_) => None,
+            },
+            _ => None,
+        };
+        if let Some(forbidden) = forbidden {
+            return Err(SbroadError::Unsupported(
+                Entity::Expression,
+                Some(format_smolstr!("{forbidden} in {what}")),
+            ));
+        }
+        stack.extend(child.children.iter().copied());
+    }
+    Ok(columns)
+}
+
+fn parse_identifier_list(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<Vec<SmolStr>, SbroadError> {
+    node.children
+        .iter()
+        .map(|id| parse_identifier(ast, *id))
+        .collect()
+}
+
+/// Parses `CHECK` and `FOREIGN KEY` constraints of `CREATE TABLE`, a column
+/// constraint is parsed as a table one over this column. Returns the constraint
+/// (with an empty name if it is not specified) and the columns it refers to.
+fn parse_table_constraint(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+    column: Option<&SmolStr>,
+) -> Result<(TableConstraint, Vec<SmolStr>), SbroadError> {
+    let mut name = SmolStr::default();
+    let mut body_id = node.first_child();
+    if let Some(name_id) = node.children.get(1) {
+        let name_node = ast.nodes.get_node(body_id)?;
+        name = parse_identifier(ast, name_node.first_child())?;
+        body_id = *name_id;
+    }
+    let body_node = ast.nodes.get_node(body_id)?;
+    let (columns, references_node) = match body_node.rule {
+        Rule::CheckConstraint => {
+            let expr_node = ast.nodes.get_node(body_node.first_child())?;
+            let columns = parse_check_expr(ast, expr_node)?;
+            let expr = expr_node
+                .value
+                .clone()
+                .expect("CHECK expression must not be empty");
+            return Ok((TableConstraint::Check { name, expr }, columns));
+        }
+        Rule::ForeignKey => {
+            let columns_node = ast.nodes.get_node(body_node.first_child())?;
+            let references_id = body_node
+                .children
+                .get(1)
+                .expect("FOREIGN KEY must have a REFERENCES child");
+            (
+                parse_identifier_list(ast, columns_node)?,
+                ast.nodes.get_node(*references_id)?,
+            )
+        }
+        Rule::References => {
+            let column = column.expect("REFERENCES without FOREIGN KEY is a column constraint");
+            (vec![column.clone()], body_node)
+        }
+        _ => panic!("Unexpected rule met under {:?}.", node.rule),
+    };
+
+    let parent = parse_identifier(ast, references_node.first_child())?;
+    let parent_columns = match references_node.children.get(1) {
+        Some(id) => parse_identifier_list(ast, ast.nodes.get_node(*id)?)?,
+        None => Vec::new(),
+    };
+    if !parent_columns.is_empty() && parent_columns.len() != columns.len() {
+        return Err(SbroadError::Invalid(
+            Entity::Ddl,
+            Some("number of referencing and referenced columns for foreign key disagree.".into()),
+        ));
+    }
+    let constraint = TableConstraint::ForeignKey {
+        name,
+        columns: columns.clone(),
+        parent,
+        parent_columns,
+    };
+    Ok((constraint, columns))
+}
+
+/// Names the constraints declared without a name after the table and
+/// the columns like PostgreSQL does and checks the referenced columns.
+fn resolve_table_constraints(
+    table_name: &str,
+    columns: &[ColumnDef],
+    constraints: Vec<(TableConstraint, Vec<SmolStr>)>,
+) -> Result<Vec<TableConstraint>, SbroadError> {
+    let mut names: AHashSet<SmolStr> = AHashSet::new();
+    for (constraint, _) in &constraints {
+        let name = constraint.name();
+        if !name.is_empty() && !names.insert(name.clone()) {
+            return Err(SbroadError::Invalid(
+                Entity::Ddl,
+                Some(format_smolstr!(
+                    "constraint {name} is specified more than once."
+                )),
+            ));
+        }
+    }
+
+    let table = table_name
+        .rsplit_once('.')
+        .map_or(table_name, |(_, table)| table);
+    let mut result = Vec::with_capacity(constraints.len());
+    for (mut constraint, referenced) in constraints {
+        if constraint.name().is_empty() {
+            let base = match &constraint {
+                TableConstraint::Check { .. } => match referenced.iter().unique().exactly_one() {
+                    Ok(column) => format_smolstr!("{table}_{column}_check"),
+                    Err(_) => format_smolstr!("{table}_check"),
+                },
+                TableConstraint::ForeignKey { .. } => {
+                    format_smolstr!("{table}_{}_fkey", referenced.join("_"))
+                }
+            };
+            let mut unique_name = base.clone();
+            let mut suffix = 0;
+            while names.contains(&unique_name) {
+                suffix += 1;
+                unique_name = format_smolstr!("{base}{suffix}");
+            }
+            names.insert(unique_name.clone());
+            match &mut constraint {
+                TableConstraint::Check { name, .. } | TableConstraint::ForeignKey { name, .. } => {
+                    *name = unique_name;
+                }
+            }
+        }
+        for column in &referenced {
+            if !columns.iter().any(|c| &c.name == column) {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "Constraint {} column {column} not found.",
+                        constraint.name()
+                    )),
+                ));
+            }
+        }
+        result.push(constraint);
+    }
+    Ok(result)
+}
+
+#[allow(clippy::too_many_lines)]
+#[allow(clippy::uninlined_format_args)]
+fn parse_create_table(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<CreateTable, SbroadError> {
+    assert_eq!(
+        node.rule,
+        Rule::CreateTable,
+        "Expected rule CreateTable, got {:?}.",
+        node.rule
+    );
+    let mut table_name = SmolStr::default();
+    let mut columns: Vec<ColumnDef> = Vec::new();
+    let mut pk_keys: Vec<SmolStr> = Vec::new();
+    let mut shard_key: Vec<SmolStr> = Vec::new();
+    let mut engine_type: SpaceEngineType = SpaceEngineType::default();
+    let mut explicit_null_columns: AHashSet<SmolStr> = AHashSet::new();
+    let mut timeout = get_default_timeout();
+    let mut tier = None;
+    let mut is_global = false;
+    let mut if_not_exists = DEFAULT_IF_NOT_EXISTS;
+    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+    let mut partition_by = None;
+    let mut constraints = Vec::new();
+    let mut generated_columns: Vec<(SmolStr, Vec<SmolStr>)> = Vec::new();
+
+    let nullable_primary_key_column_error = Err(SbroadError::Invalid(
+        Entity::Column,
+        Some(SmolStr::from(
+            "Primary key mustn't contain nullable columns.",
+        )),
+    ));
+    let primary_key_already_declared_error = Err(SbroadError::Invalid(
+        Entity::Node,
+        Some(format_smolstr!("Primary key has been already declared.",)),
+    ));
+
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::IfNotExists => if_not_exists = true,
+            Rule::NewTable => {
+                table_name = parse_identifier(ast, *child_id)?;
+            }
+            Rule::Columns => {
+                let columns_node = ast.nodes.get_node(*child_id)?;
+                for col_id in &columns_node.children {
+                    let column_def_node = ast.nodes.get_node(*col_id)?;
+                    let column_def_children = &column_def_node.children;
+
+                    let name_node_id = column_def_children
+                        .first()
+                        .expect("ColumnDef should have a name child node");
+                    let name = parse_identifier(ast, *name_node_id)?;
+
+                    let column_ty_node_id = column_def_children
                         .get(1)
                         .expect("ColumnDef should have a type child node");
                     let column_ty_node = ast.nodes.get_node(*column_ty_node_id)?;
-                    let ty_node_id = column_ty_node
-                        .children
-                        .first()
-                        .expect("ColumnDefType must have a type child");
-                    let ty_node = ast.nodes.get_node(*ty_node_id)?;
-                    let data_type = parse_column_def_type(ty_node)?;
+                    let (data_type, mut sequence) = if column_ty_node.rule == Rule::ColumnDefSerial
+                    {
+                        let sequence = implicit_sequence_name(&table_name, &name);
+                        (RelationType::Integer, Some(sequence))
+                    } else {
+                        let ty_node_id = column_ty_node
+                            .children
+                            .first()
+                            .expect("ColumnDefType must have a type child");
+                        let ty_node = ast.nodes.get_node(*ty_node_id)?;
+                        (parse_column_def_type(ty_node)?, None)
+                    };
                     let mut is_nullable = true;
+                    let mut default = None;
+                    let mut generated_always = false;
 
                     for def_child_id in column_def_children.iter().skip(2) {
                         let def_child_node = ast.nodes.get_node(*def_child_id)?;
@@ -817,13 +1431,74 @@ fn parse_create_table(
                                 is_nullable = false;
                                 pk_keys.push(name);
                             }
+                            Rule::ColumnDefault => {
+                                default = Some(parse_column_default(ast, def_child_node)?);
+                            }
+                            Rule::ColumnDefIdentity => {
+                                let kind_node = ast.nodes.get_node(def_child_node.first_child())?;
+                                generated_always = kind_node.rule == Rule::IdentityAlways;
+                                if !matches!(
+                                    data_type,
+                                    RelationType::Integer | RelationType::Unsigned
+                                ) {
+                                    return Err(SbroadError::Invalid(
+                                        Entity::Column,
+                                        Some(format_smolstr!(
+                                            "identity column {name} must be of integer type."
+                                        )),
+                                    ));
+                                }
+                                sequence = Some(implicit_sequence_name(&table_name, &name));
+                            }
+                            Rule::ColumnDefGenerated => {
+                                let expr_node = ast.nodes.get_node(def_child_node.first_child())?;
+                                let referenced = parse_generated_expr(ast, expr_node)?;
+                                generated_columns.push((name.clone(), referenced));
+                                default = Some(
+                                    expr_node
+                                        .value
+                                        .clone()
+                                        .expect("generated column expression must not be empty"),
+                                );
+                                generated_always = true;
+                            }
+                            Rule::ColumnConstraint => {
+                                constraints.push(parse_table_constraint(
+                                    ast,
+                                    def_child_node,
+                                    Some(&name),
+                                )?);
+                            }
                             _ => panic!("Unexpected rules met under ColumnDef."),
                         }
                     }
+                    if let Some(sequence) = &sequence {
+                        if default.is_some() {
+                            return Err(SbroadError::Invalid(
+                                Entity::Column,
+                                Some(format_smolstr!(
+                                    "both default and sequence specified for column {name}."
+                                )),
+                            ));
+                        }
+                        if explicit_null_columns.contains(&name) {
+                            return Err(SbroadError::Invalid(
+                                Entity::Column,
+                                Some(format_smolstr!(
+                                    "column {name} backed by a sequence can't be nullable."
+                                )),
+                            ));
+                        }
+                        default = Some(nextval_default(sequence));
+                        is_nullable = false;
+                    }
                     let column_def = ColumnDef {
                         name,
                         data_type,
                         is_nullable,
+                        default,
+                        sequence,
+                        generated_always,
                     };
                     columns.push(column_def);
                 }
@@ -964,7 +1639,10 @@ fn parse_create_table(
                 wait_applied_globally = false;
             }
             Rule::Partition => {
-                warn!(None, "PARTITION BY option is not supported yet.");
+                partition_by = Some(parse_partition_by(ast, child_node)?);
+            }
+            Rule::TableConstraint => {
+                constraints.push(parse_table_constraint(ast, child_node, None)?);
             }
             _ => panic!("Unexpected rule met under CreateTable."),
         }
@@ -975,6 +1653,50 @@ fn parse_create_table(
             Some(format_smolstr!("Primary key must be declared.")),
         ));
     }
+    for (column, referenced) in &generated_columns {
+        for name in referenced {
+            if !columns.iter().any(|c| &c.name == name) {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "column {name} referenced by generated column {column} not found."
+                    )),
+                ));
+            }
+            if generated_columns.iter().any(|(c, _)| c == name) {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "generated column {column} can't refer to generated column {name}."
+                    )),
+                ));
+            }
+        }
+    }
+    let constraints = resolve_table_constraints(&table_name, &columns, constraints)?;
+    if let Some(PartitionBy { column, .. }) = &partition_by {
+        if is_global {
+            return Err(SbroadError::NotImplemented(
+                Entity::Ddl,
+                "partitioning of global tables".into(),
+            ));
+        }
+        if !columns.iter().any(|c| &c.name == column) {
+            return Err(SbroadError::Invalid(
+                Entity::Column,
+                Some(format_smolstr!("Partition key column {column} not found.")),
+            ));
+        }
+        // Uniqueness of the primary key is checked only within a partition.
+        if !pk_keys.contains(column) {
+            return Err(SbroadError::Invalid(
+                Entity::PrimaryKey,
+                Some(format_smolstr!(
+                    "Primary key must contain the partition key column {column}."
+                )),
+            ));
+        }
+    }
     // infer sharding key from primary key
     if shard_key.is_empty() && !is_global {
         shard_key.clone_from(&pk_keys);
@@ -1002,6 +1724,291 @@ fn parse_create_table(
         wait_applied_globally,
         timeout,
         tier,
+        partition_by,
+        constraints,
+    })
+}
+
+fn parse_create_table_as(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<CreateTableAs, SbroadError> {
+    assert_eq!(node.rule, Rule::CreateTableAs);
+    let mut name = SmolStr::default();
+    let mut query = SmolStr::default();
+    let mut primary_key = Vec::new();
+    let mut sharding_key = Some(Vec::new());
+    let mut engine_type = SpaceEngineType::default();
+    let mut tier = None;
+    let mut if_not_exists = DEFAULT_IF_NOT_EXISTS;
+    let mut timeout = get_default_timeout();
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::IfNotExists => if_not_exists = true,
+            Rule::NewTable => name = parse_identifier(ast, *child_id)?,
+            Rule::PrimaryKey => {
+                // First child is a `PrimaryKeyMark` that we should skip.
+                for key_id in child_node.children.iter().skip(1) {
+                    primary_key.push(parse_identifier(ast, *key_id)?);
+                }
+            }
+            Rule::Engine => {
+                let engine_node = ast.nodes.get_node(child_node.first_child())?;
+                engine_type = match engine_node.rule {
+                    Rule::Memtx => SpaceEngineType::Memtx,
+                    Rule::Vinyl => SpaceEngineType::Vinyl,
+                    _ => panic!("Unexpected rule met under Engine."),
+                };
+            }
+            Rule::Distribution => {
+                let distribution_node = ast.nodes.get_node(child_node.first_child())?;
+                match distribution_node.rule {
+                    Rule::Global => sharding_key = None,
+                    Rule::Sharding => {
+                        let mut keys = Vec::new();
+                        for key_id in &distribution_node.children {
+                            let key_node = ast.nodes.get_node(*key_id)?;
+                            match key_node.rule {
+                                Rule::Identifier => keys.push(parse_identifier(ast, *key_id)?),
+                                Rule::Tier => {
+                                    tier = Some(parse_identifier(ast, key_node.first_child())?);
+                                }
+                                _ => panic!("Unexpected rule met under Sharding."),
+                            }
+                        }
+                        sharding_key = Some(keys);
+                    }
+                    _ => panic!("Unexpected rule met under Distribution."),
+                }
+            }
+            Rule::CreateTableAsBody => {
+                query = child_node
+                    .value
+                    .as_ref()
+                    .expect("query of CREATE TABLE AS must not be empty")
+                    .clone();
+            }
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            _ => panic!("Unexpected create table as node: {child_node:?}"),
+        }
+    }
+    Ok(CreateTableAs {
+        name,
+        query,
+        primary_key,
+        sharding_key,
+        engine_type,
+        if_not_exists,
+        wait_applied_globally: DEFAULT_WAIT_APPLIED_GLOBALLY,
+        timeout,
+        tier,
+        params: Vec::new(),
+    })
+}
+
+/// `SELECT <projection> INTO [TABLE] <name> [FROM ...]` creates a table
+/// the same way as `CREATE TABLE <name> AS SELECT <projection> [FROM ...]`.
+fn parse_select_into(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<CreateTableAs, SbroadError> {
+    assert_eq!(node.rule, Rule::SelectInto);
+    let mut name = SmolStr::default();
+    let mut projection = SmolStr::default();
+    let mut body = None;
+    let mut timeout = get_default_timeout();
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        let text = || {
+            child_node
+                .value
+                .clone()
+                .expect("query part of SELECT INTO must not be empty")
+        };
+        match child_node.rule {
+            Rule::NewTable => name = parse_identifier(ast, *child_id)?,
+            Rule::SelectIntoProjection => projection = text(),
+            Rule::SelectIntoBody => body = Some(text()),
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            _ => panic!("Unexpected select into node: {child_node:?}"),
+        }
+    }
+    let query = match body {
+        Some(body) => format_smolstr!("SELECT {projection} {body}"),
+        None => format_smolstr!("SELECT {projection}"),
+    };
+    Ok(CreateTableAs {
+        name,
+        query,
+        primary_key: Vec::new(),
+        sharding_key: Some(Vec::new()),
+        engine_type: SpaceEngineType::default(),
+        if_not_exists: DEFAULT_IF_NOT_EXISTS,
+        wait_applied_globally: DEFAULT_WAIT_APPLIED_GLOBALLY,
+        timeout,
+        tier: None,
+        params: Vec::new(),
+    })
+}
+
+fn parse_partition_by(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+) -> Result<PartitionBy, SbroadError> {
+    debug_assert_eq!(node.rule, Rule::Partition);
+    let type_node = ast.nodes.get_node(node.first_child())?;
+    let strategy_node = ast.nodes.get_node(type_node.first_child())?;
+    let strategy = match strategy_node.rule {
+        Rule::PartitionRange => PartitionStrategy::Range,
+        Rule::PartitionList => PartitionStrategy::List,
+        Rule::PartitionHash => PartitionStrategy::Hash,
+        rule => unreachable!("pest should not allow rule: {rule:?}"),
+    };
+    let spec_node = ast.nodes.get_node(node.child_n(1))?;
+    let [column_id] = spec_node.children.as_slice() else {
+        return Err(SbroadError::NotImplemented(
+            Entity::Ddl,
+            "partition key with several columns".into(),
+        ));
+    };
+    let column = parse_identifier(ast, *column_id)?;
+    Ok(PartitionBy { strategy, column })
+}
+
+/// Parse `CREATE TABLE ... PARTITION OF`. The bounds must be literals,
+/// they are cast to the partition key type when the partition is created.
+fn parse_create_partition<M: Metadata>(
+    ast: &AbstractSyntaxTree,
+    node: &ParseNode,
+    type_analyzer: &mut TypeAnalyzer,
+    pairs_map: &mut ParsingPairsMap,
+    worker: &mut ExpressionsWorker<M>,
+    plan: &mut Plan,
+) -> Result<CreatePartition, SbroadError> {
+    assert_eq!(node.rule, Rule::CreatePartition);
+    let mut name = None;
+    let mut parent = None;
+    let mut bound = None;
+    let mut if_not_exists = DEFAULT_IF_NOT_EXISTS;
+    let mut wait_applied_globally = DEFAULT_WAIT_APPLIED_GLOBALLY;
+    let mut timeout = get_default_timeout();
+
+    let mut parse_literal = |pairs_map: &mut ParsingPairsMap,
+                             plan: &mut Plan,
+                             id: usize|
+     -> Result<Value, SbroadError> {
+        let expr_pair = pairs_map.remove_pair(id);
+        let expr_id = parse_scalar_expr(
+            Pairs::single(expr_pair),
+            type_analyzer,
+            &[],
+            worker,
+            plan,
+            true,
+        )?;
+        match plan.get_node(expr_id)? {
+            Node::Expression(Expression::Constant(Constant { value })) => Ok(value.clone()),
+            _ => Err(SbroadError::Unsupported(
+                Entity::Expression,
+                Some("partition bounds other than literals".into()),
+            )),
+        }
+    };
+
+    for child_id in &node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::NewTable => name = Some(parse_identifier(ast, *child_id)?),
+            Rule::TableNameIdentifier => parent = Some(parse_identifier(ast, *child_id)?),
+            Rule::IfNotExists => if_not_exists = true,
+            Rule::PartitionOfSpec => {
+                let Some(spec_id) = child_node.children.first() else {
+                    bound = Some(PartitionBound::Default);
+                    continue;
+                };
+                let spec_node = ast.nodes.get_node(*spec_id)?;
+                match spec_node.rule {
+                    Rule::ForValuesSpecIn => {
+                        let row_node = ast.nodes.get_node(spec_node.first_child())?;
+                        let mut values = Vec::with_capacity(row_node.children.len());
+                        for id in &row_node.children {
+                            values.push(parse_literal(pairs_map, plan, *id)?);
+                        }
+                        bound = Some(PartitionBound::List { values });
+                    }
+                    Rule::ForValuesSpecFromTo => {
+                        let mut range = Vec::with_capacity(2);
+                        for row_id in &spec_node.children {
+                            let row_node = ast.nodes.get_node(*row_id)?;
+                            let [value_id] = row_node.children.as_slice() else {
+                                return Err(SbroadError::NotImplemented(
+                                    Entity::Ddl,
+                                    "partition bounds with several values".into(),
+                                ));
+                            };
+                            let value_node = ast.nodes.get_node(*value_id)?;
+                            let value = match ast.nodes.get_node(value_node.first_child())?.rule {
+                                Rule::MinValue | Rule::MaxValue => None,
+                                _ => {
+                                    Some(parse_literal(pairs_map, plan, value_node.first_child())?)
+                                }
+                            };
+                            range.push(value);
+                        }
+                        let (Some(to), Some(from)) = (range.pop(), range.pop()) else {
+                            unreachable!("pest should not allow range without bounds");
+                        };
+                        bound = Some(PartitionBound::Range { from, to });
+                    }
+                    Rule::ForValuesSpecWith => {
+                        let mut numbers = Vec::with_capacity(2);
+                        for (id, name) in spec_node.children.iter().zip(["modulus", "remainder"]) {
+                            let number = match parse_literal(pairs_map, plan, *id)? {
+                                Value::Unsigned(v) => v,
+                                Value::Integer(v) if v >= 0 => v.unsigned_abs(),
+                                value => {
+                                    return Err(SbroadError::Invalid(
+                                        Entity::Value,
+                                        Some(format_smolstr!(
+                                            "{name} for hash partition must be a non-negative integer, got {value}"
+                                        )),
+                                    ))
+                                }
+                            };
+                            numbers.push(number);
+                        }
+                        let (Some(remainder), Some(modulus)) = (numbers.pop(), numbers.pop())
+                        else {
+                            unreachable!("pest should not allow hash bound without numbers");
+                        };
+                        bound = Some(PartitionBound::Hash { modulus, remainder });
+                    }
+                    rule => unreachable!("pest should not allow rule: {rule:?}"),
+                }
+            }
+            Rule::Partition => {
+                return Err(SbroadError::NotImplemented(
+                    Entity::Ddl,
+                    "sub-partitioning".into(),
+                ))
+            }
+            Rule::Timeout => timeout = get_timeout(ast, *child_id)?,
+            Rule::WaitAppliedGlobally => wait_applied_globally = true,
+            Rule::WaitAppliedLocally => wait_applied_globally = false,
+            rule => unreachable!("pest should not allow rule: {rule:?}"),
+        }
+    }
+    let (Some(name), Some(parent)) = (name, parent) else {
+        unreachable!("pest should not allow partition without names");
+    };
+    Ok(CreatePartition {
+        name,
+        parent,
+        bound: bound.expect("partition bound must be parsed"),
+        if_not_exists,
+        wait_applied_globally,
+        timeout,
     })
 }
 
@@ -1043,33 +2050,66 @@ fn parse_alter_table(
                                 ast.nodes.get_node(data_type_node.first_child())?;
                             let data_type = parse_column_def_type(data_type_node)?;
 
-                            let is_nullable = if let Some(id) = node.children.get(2) {
+                            // column is nullable by default unless otherwise specified
+                            let mut is_nullable = true;
+                            let mut default = None;
+                            for id in node.children.iter().skip(2) {
                                 let node = ast.nodes.get_node(*id)?;
-                                debug_assert_eq!(node.rule, Rule::ColumnDefIsNull);
-                                match (node.children.first(), node.children.get(1)) {
-                                    (None, None) => true, // NULL explicitly specified
-                                    (Some(child_id), None) => {
-                                        let not_flag_node = ast.nodes.get_node(*child_id)?;
-                                        if let Rule::NotFlag = not_flag_node.rule {
-                                            false // NOT NULL specified
-                                        } else {
-                                            panic!(
-                                                "Expected NotFlag rule, got: {:?}.",
-                                                not_flag_node.rule
-                                            );
-                                        }
+                                match node.rule {
+                                    Rule::ColumnDefIsNull => {
+                                        is_nullable =
+                                            match (node.children.first(), node.children.get(1)) {
+                                                (None, None) => true, // NULL explicitly specified
+                                                (Some(child_id), None) => {
+                                                    let not_flag_node =
+                                                        ast.nodes.get_node(*child_id)?;
+                                                    if let Rule::NotFlag = not_flag_node.rule {
+                                                        false // NOT NULL specified
+                                                    } else {
+                                                        panic!(
+                                                            "Expected NotFlag rule, got: {:?}.",
+                                                            not_flag_node.rule
+                                                        );
+                                                    }
+                                                }
+                                                _ => panic!(
+                                                    "Unexpected rule met under ColumnDefIsNull."
+                                                ),
+                                            };
                                     }
-                                    _ => panic!("Unexpected rule met under ColumnDefIsNull."),
+                                    Rule::ColumnDefault => {
+                                        default = Some(parse_column_default(ast, node)?);
+                                    }
+                                    Rule::ColumnDefIdentity => {
+                                        return Err(SbroadError::Unsupported(
+                                            Entity::Ddl,
+                                            Some(
+                                                "identity columns in ALTER TABLE ADD COLUMN"
+                                                    .to_smolstr(),
+                                            ),
+                                        ));
+                                    }
+                                    Rule::ColumnDefGenerated => {
+                                        return Err(SbroadError::Unsupported(
+                                            Entity::Ddl,
+                                            Some(
+                                                "generated columns in ALTER TABLE ADD COLUMN"
+                                                    .to_smolstr(),
+                                            ),
+                                        ));
+                                    }
+                                    rule => unreachable!("pest should not allow rule: {rule:?}"),
                                 }
-                            } else {
-                                true // column is nullable by default unless otherwise specified
-                            };
+                            }
 
                             add_ops.push(AlterColumn::Add {
                                 column: ColumnDef {
                                     name,
                                     data_type,
                                     is_nullable,
+                                    default,
+                                    sequence: None,
+                                    generated_always: false,
                                 },
                                 if_not_exists,
                             });
@@ -1097,6 +2137,12 @@ fn parse_alter_table(
                 let new_table_name = parse_identifier(ast, id)?;
                 op = Some(AlterTableOp::RenameTable { new_table_name });
             }
+            Rule::AlterTableDetachPartition => {
+                // it's the only child
+                let id = node.first_child();
+                let partition = parse_identifier(ast, id)?;
+                op = Some(AlterTableOp::DetachPartition { partition });
+            }
             Rule::AlterTableColumnDrop
             | Rule::AlterTableColumnAlter
             | Rule::AlterTableColumnRename => {
@@ -1233,7 +2279,16 @@ fn parse_set_param(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<SetPara
                             .first()
                             .expect("Param name expected under NamedParam.");
                         let param_name = parse_identifier(ast, *param_name_id)?;
-                        param_value = Some(SetParamValue::NamedParam { name: param_name });
+                        if param_name == SEARCH_PATH_PARAM {
+                            let schemas = parse_search_path(ast, &conf_param_child.children[1..])?;
+                            param_value = Some(SetParamValue::SearchPath { schemas });
+                        } else if param_name == STATEMENT_TIMEOUT_PARAM {
+                            let millis =
+                                parse_statement_timeout(ast, &conf_param_child.children[1..])?;
+                            param_value = Some(SetParamValue::StatementTimeout { millis });
+                        } else {
+                            param_value = Some(SetParamValue::NamedParam { name: param_name });
+                        }
                     }
                     Rule::TimeZoneParam => param_value = Some(SetParamValue::TimeZone),
                     _ => panic!("Unexpected rule met under ConfParam."),
@@ -1249,6 +2304,91 @@ fn parse_set_param(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<SetPara
     })
 }
 
+/// Parse the list of schemas of `SET search_path`.
+/// `DEFAULT` resets the search path.
+fn parse_search_path(
+    ast: &AbstractSyntaxTree,
+    value_ids: &[usize],
+) -> Result<Vec<SmolStr>, SbroadError> {
+    let mut schemas = Vec::with_capacity(value_ids.len());
+    for value_id in value_ids {
+        let value_node = ast.nodes.get_node(*value_id)?;
+        let value_child_id = value_node.first_child();
+        let value_child = ast.nodes.get_node(value_child_id)?;
+        match value_child.rule {
+            Rule::ParamValueDefault if value_ids.len() == 1 => {
+                schemas.push(SmolStr::from(DEFAULT_SCHEMA));
+            }
+            Rule::Identifier => schemas.push(parse_identifier(ast, value_child_id)?),
+            Rule::SingleQuotedString => {
+                schemas.push(retrieve_string_literal(ast, value_child_id)?);
+            }
+            _ => {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "invalid value for {SEARCH_PATH_PARAM}: {}",
+                        value_child.value.as_deref().unwrap_or_default()
+                    )),
+                ))
+            }
+        }
+    }
+    Ok(schemas)
+}
+
+/// Parse the value of `SET statement_timeout` into milliseconds.
+/// As in PostgreSQL, a value without units is in milliseconds
+/// and `DEFAULT` resets the timeout.
+fn parse_statement_timeout(
+    ast: &AbstractSyntaxTree,
+    value_ids: &[usize],
+) -> Result<Option<u64>, SbroadError> {
+    let invalid = |value: &str| {
+        SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!(
+                "invalid value for {STATEMENT_TIMEOUT_PARAM}: {value}"
+            )),
+        )
+    };
+    let [value_id] = value_ids else {
+        return Err(invalid("expected a single value"));
+    };
+    let value_node = ast.nodes.get_node(*value_id)?;
+    let value_child_id = value_node.first_child();
+    let value_child = ast.nodes.get_node(value_child_id)?;
+    let text = match value_child.rule {
+        Rule::ParamValueDefault => return Ok(None),
+        Rule::SingleQuotedString => retrieve_string_literal(ast, value_child_id)?,
+        Rule::Integer | Rule::Decimal | Rule::Double => {
+            value_child.value.clone().unwrap_or_default()
+        }
+        _ => return Err(invalid(value_child.value.as_deref().unwrap_or_default())),
+    };
+
+    let text = text.trim();
+    let unit_start = text
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E'))
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(unit_start);
+    let millis_in_unit = match unit.trim() {
+        "" | "ms" => 1.0,
+        "us" => 0.001,
+        "s" => 1000.0,
+        "min" => 60_000.0,
+        "h" => 3_600_000.0,
+        "d" => 86_400_000.0,
+        _ => return Err(invalid(text)),
+    };
+    let number: f64 = number.parse().map_err(|_| invalid(text))?;
+    let millis = (number * millis_in_unit).round();
+    if !millis.is_finite() || millis > u64::MAX as f64 {
+        return Err(invalid(text));
+    }
+    Ok(Some(millis as u64))
+}
+
 fn parse_deallocate(ast: &AbstractSyntaxTree, node: &ParseNode) -> Result<Deallocate, SbroadError> {
     let param_name = if let Some(identifier_node_id) = node.children.first() {
         Some(parse_identifier(ast, *identifier_node_id)?)
@@ -1273,7 +2413,7 @@ fn parse_select_full<M: Metadata>(
     for child_id in &node.children {
         let child_node = ast.nodes.get_node(*child_id)?;
         match child_node.rule {
-            Rule::Cte => continue,
+            Rule::Cte | Rule::Recursive => continue,
             Rule::SelectStatement => {
                 top_id = Some(parse_select_statement(
                     ast,
@@ -1306,6 +2446,7 @@ fn parse_select_statement<M: Metadata>(
     assert_eq!(node.rule, Rule::SelectStatement);
     let mut top_id = None;
     let mut limit = None;
+    let mut offset = None;
     for child_id in &node.children {
         let child_node = ast.nodes.get_node(*child_id)?;
         match child_node.rule {
@@ -1321,6 +2462,17 @@ fn parse_select_statement<M: Metadata>(
                     _ => unreachable!("Unexpected limit child: {child_node:?}"),
                 }
             }
+            Rule::Fetch => {
+                // FETCH FIRST ROW ONLY is the same as FETCH FIRST 1 ROW ONLY.
+                limit = match child_node.children.first() {
+                    Some(count_id) => Some(parse_unsigned(ast.nodes.get_node(*count_id)?)?),
+                    None => Some(1),
+                };
+            }
+            Rule::Offset => {
+                let child_node = ast.nodes.get_node(child_node.children[0])?;
+                offset = Some(parse_unsigned(child_node)?);
+            }
             Rule::OrderBy => {
                 top_id = Some(ast.parse_order_by(
                     plan,
@@ -1336,8 +2488,10 @@ fn parse_select_statement<M: Metadata>(
         }
     }
     let top_id = top_id.expect("SelectStatement must have at least one child");
-    if let Some(limit) = limit {
-        return plan.add_limit(top_id, limit);
+    // OFFSET 0 is the same as omitting the OFFSET clause.
+    let offset = offset.unwrap_or_default();
+    if limit.is_some() || offset > 0 {
+        return plan.add_limit(top_id, limit, offset);
     }
     Ok(top_id)
 }
@@ -1348,6 +2502,7 @@ fn parse_scan_cte_or_table<M>(
     node_id: usize,
     map: &mut Translation,
     ctes: &mut CTEs,
+    recursive_ctes: &mut RecursiveCTEs,
     plan: &mut Plan,
 ) -> Result<(), SbroadError>
 where
@@ -1359,6 +2514,14 @@ where
     // First we try to find CTE with the given name, cause CTE should have higher precedence
     // over table with the same name
     let cte = ctes.get(&scan_name).copied();
+    if cte.is_none() {
+        // The CTE is not defined yet, but it can be a recursive
+        // CTE referred from its own recursive part.
+        if let Some(ref_id) = parse_recursive_cte_ref(ast, &scan_name, map, recursive_ctes, plan)? {
+            map.add(node_id, ref_id);
+            return Ok(());
+        }
+    }
     match cte {
         Some(cte_id) => {
             map.add(node_id, cte_id);
@@ -1384,12 +2547,90 @@ where
     Ok(())
 }
 
+/// Collect CTEs declared in `WITH RECURSIVE` clauses.
+fn collect_recursive_ctes(ast: &AbstractSyntaxTree) -> Result<RecursiveCTEs, SbroadError> {
+    let mut recursive_ctes = RecursiveCTEs::new();
+    for node in &ast.nodes.arena {
+        if node.rule != Rule::SelectFull {
+            continue;
+        }
+        let Some(first_id) = node.children.first() else {
+            continue;
+        };
+        if ast.nodes.get_node(*first_id)?.rule != Rule::Recursive {
+            continue;
+        }
+        for child_id in &node.children {
+            let child_node = ast.nodes.get_node(*child_id)?;
+            if child_node.rule != Rule::Cte {
+                continue;
+            }
+            let name = parse_normalized_identifier(ast, child_node.first_child())?;
+            recursive_ctes.insert(
+                name,
+                RecursiveCteDecl {
+                    ast_id: *child_id,
+                    is_self_referenced: false,
+                },
+            );
+        }
+    }
+    Ok(recursive_ctes)
+}
+
+/// Try to build a reference to the recursive CTE from its own recursive part.
+/// Returns `None` when the CTE with the given name is not recursive or its
+/// non-recursive (anchor) part is not built yet, i.e. the scan is not located
+/// in the recursive part of the CTE.
+fn parse_recursive_cte_ref(
+    ast: &AbstractSyntaxTree,
+    name: &SmolStr,
+    map: &Translation,
+    recursive_ctes: &mut RecursiveCTEs,
+    plan: &mut Plan,
+) -> Result<Option<NodeId>, SbroadError> {
+    let Some(decl) = recursive_ctes.get_mut(name) else {
+        return Ok(None);
+    };
+    let cte_node = ast.nodes.get_node(decl.ast_id)?;
+    let mut columns = Vec::with_capacity(cte_node.children.len());
+    let mut anchor_ast_id = None;
+    for child_id in &cte_node.children {
+        let child_node = ast.nodes.get_node(*child_id)?;
+        match child_node.rule {
+            Rule::CteColumn => {
+                columns.push(parse_normalized_identifier(ast, *child_id)?);
+            }
+            Rule::SelectStatement => {
+                // The anchor is the first select of the union.
+                let select_node = ast.nodes.get_node(child_node.first_child())?;
+                if select_node.rule == Rule::SelectWithOptionalContinuation
+                    && select_node.children.len() > 1
+                {
+                    anchor_ast_id = Some(select_node.first_child());
+                }
+            }
+            _ => {}
+        }
+    }
+    let Some(anchor_ast_id) = anchor_ast_id else {
+        return Ok(None);
+    };
+    let Ok(anchor_id) = map.get(anchor_ast_id) else {
+        return Ok(None);
+    };
+    let ref_id = plan.add_recursive_cte_ref(anchor_id, name.clone(), columns)?;
+    decl.is_self_referenced = true;
+    Ok(Some(ref_id))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn parse_cte<M: Metadata>(
     ast: &AbstractSyntaxTree,
     node_id: usize,
     map: &mut Translation,
     ctes: &mut CTEs,
+    recursive_ctes: &RecursiveCTEs,
     plan: &mut Plan,
     type_analyzer: &mut TypeAnalyzer,
     pairs_map: &mut ParsingPairsMap,
@@ -1440,7 +2681,16 @@ fn parse_cte<M: Metadata>(
             )),
         ));
     }
-    let cte_id = plan.add_cte(child_id, name.clone(), columns)?;
+    let is_self_referenced = recursive_ctes
+        .get(&name)
+        .is_some_and(|decl| decl.is_self_referenced);
+    let cte_id = if is_self_referenced {
+        // Column names were already applied to the anchor part
+        // while building the self-reference.
+        plan.add_recursive_cte(child_id, name.clone())?
+    } else {
+        plan.add_cte(child_id, name.clone(), columns)?
+    };
     ctes.insert(name, cte_id);
     map.add(node_id, cte_id);
     Ok(())
@@ -1531,6 +2781,15 @@ fn parse_grant_revoke(
                     let table_name = parse_identifier(ast, *table_node_id)?;
                     GrantRevokeType::specific_table(privilege, table_name)?
                 }
+                Rule::PrivBlockSchema => GrantRevokeType::schema(privilege)?,
+                Rule::PrivBlockSpecificSchema => {
+                    let schema_node_id = inner_privilege_block_node
+                        .children
+                        .first()
+                        .expect("Expected to see Name as a first child of PrivBlockSpecificSchema");
+                    let schema_name = parse_identifier(ast, *schema_node_id)?;
+                    GrantRevokeType::specific_schema(privilege, schema_name)?
+                }
                 Rule::PrivBlockProcedure => GrantRevokeType::procedure(privilege)?,
                 Rule::PrivBlockSpecificProcedure => {
                     let proc_node_id = inner_privilege_block_node.children.first().expect(
@@ -1762,7 +3021,7 @@ fn parse_param<M: Metadata>(
 lazy_static::lazy_static! {
     static ref PRATT_PARSER: PrattParser<Rule> = {
         use pest::pratt_parser::{Assoc::Left, Op};
-        use Rule::{Add, And, Between, ConcatInfixOp, Divide, Eq, Escape, Gt, GtEq, In, IsPostfix, CastPostfix, Like, Similar, Lt, LtEq, Multiply, NotEq, Or, Subtract, UnaryNot};
+        use Rule::{Add, And, Between, ConcatInfixOp, Divide, Eq, Escape, Gt, GtEq, In, IsPostfix, CastPostfix, JsonContains, JsonGet, JsonGetPath, JsonGetPathText, JsonGetText, Like, Similar, Lt, LtEq, Multiply, NotEq, Or, Subtract, UnaryNot};
 
         // Precedence is defined lowest to highest.
         PrattParser::new()
@@ -1780,7 +3039,14 @@ lazy_static::lazy_static! {
                 | Op::infix(LtEq, Left) | Op::infix(In, Left)
             )
             .op(Op::infix(Add, Left) | Op::infix(Subtract, Left))
-            .op(Op::infix(Multiply, Left) | Op::infix(Divide, Left) | Op::infix(ConcatInfixOp, Left))
+            // JSON operators share precedence with concatenation, so
+            // `doc ->> 'a' || 'b'` is `(doc ->> 'a') || 'b'`.
+            .op(
+                Op::infix(Multiply, Left) | Op::infix(Divide, Left) | Op::infix(ConcatInfixOp, Left)
+                | Op::infix(JsonGet, Left) | Op::infix(JsonGetText, Left)
+                | Op::infix(JsonGetPath, Left) | Op::infix(JsonGetPathText, Left)
+                | Op::infix(JsonContains, Left)
+            )
             .op(Op::postfix(IsPostfix))
             .op(Op::postfix(CastPostfix))
     };
@@ -1789,14 +3055,17 @@ lazy_static::lazy_static! {
 lazy_static::lazy_static! {
     static ref SELECT_PRATT_PARSER: PrattParser<Rule> = {
         use pest::pratt_parser::{Assoc::Left, Op};
-        use Rule::{UnionOp, UnionAllOp, ExceptOp};
+        use Rule::{UnionOp, UnionAllOp, ExceptOp, ExceptAllOp, IntersectOp, IntersectAllOp};
 
+        // INTERSECT binds tighter than UNION and EXCEPT (as in the SQL standard).
         PrattParser::new()
             .op(
                 Op::infix(UnionOp, Left)
             | Op::infix(UnionAllOp, Left)
             | Op::infix(ExceptOp, Left)
+            | Op::infix(ExceptAllOp, Left)
         )
+            .op(Op::infix(IntersectOp, Left) | Op::infix(IntersectAllOp, Left))
     };
 }
 
@@ -1887,6 +3156,13 @@ where
     /// Vector of all window nodes in the query plan.
     /// Stores window definitions in order of appearance, including both named and inline windows.
     windows: Vec<NodeId>,
+    /// Map of { column_name -> expression_id } used to parse the generated
+    /// column expressions on update: an unqualified reference to the column
+    /// is replaced with a copy of the expression.
+    column_substitutions: HashMap<SmolStr, NodeId>,
+    /// Set-returning functions met during parsing. They are checked
+    /// to be the columns of the top projection after the plan is built.
+    set_returning_functions: Vec<NodeId>,
 }
 
 impl<'worker, M> ExpressionsWorker<'worker, M>
@@ -1913,6 +3189,8 @@ where
             current_time: OffsetDateTime::now_utc(),
             named_windows_map: HashMap::new(),
             windows: Vec::new(),
+            column_substitutions: HashMap::new(),
+            set_returning_functions: Vec::new(),
         }
     }
 
@@ -1981,6 +3259,15 @@ enum ParseExpressionInfixOperator {
     Escape,
 }
 
+/// Ordering of the aggregate function arguments:
+/// `string_agg(a, ',' ORDER BY b)` or `WITHIN GROUP (ORDER BY b)`.
+#[derive(Clone, Debug)]
+struct ParseAggregateOrderBy {
+    expr: Box<ParseExpression>,
+    is_desc: bool,
+    within_group: bool,
+}
+
 #[derive(Clone, Debug)]
 enum ParseExpression {
     PlanId {
@@ -1999,6 +3286,7 @@ enum ParseExpression {
         name: String,
         args: Vec<ParseExpression>,
         feature: Option<FunctionFeature>,
+        order_by: Option<ParseAggregateOrderBy>,
     },
     Like {
         left: Box<ParseExpression>,
@@ -2074,12 +3362,16 @@ pub enum SelectOp {
     Union,
     UnionAll,
     Except,
+    ExceptAll,
+    Intersect,
+    IntersectAll,
 }
 
 /// Helper struct denoting any combination of
 /// * SELECT
 /// * UNION (ALL)
-/// * EXCEPT
+/// * EXCEPT (ALL)
+/// * INTERSECT (ALL)
 #[derive(Clone)]
 pub enum SelectSet {
     PlanId {
@@ -2098,13 +3390,23 @@ impl SelectSet {
             SelectSet::PlanId { plan_id } => Ok(*plan_id),
             SelectSet::Infix { op, left, right } => {
                 let left_id = left.populate_plan(plan)?;
-                let right_id = right.populate_plan(plan)?;
+                let mut right_id = right.populate_plan(plan)?;
+                if let SelectSet::Infix { .. } = right.as_ref() {
+                    // Local SQL engine evaluates compound selects from left to right
+                    // and doesn't allow parentheses around them, so the right operand
+                    // (possible due to INTERSECT precedence) is wrapped into a subquery.
+                    let sq_id = plan.add_sub_query(right_id, None)?;
+                    right_id = plan.add_proj(sq_id, vec![], &[], false, false)?;
+                }
                 match op {
                     u @ (SelectOp::Union | SelectOp::UnionAll) => {
                         let remove_duplicates = matches!(u, SelectOp::Union);
                         plan.add_union(left_id, right_id, remove_duplicates)
                     }
                     SelectOp::Except => plan.add_except(left_id, right_id),
+                    SelectOp::Intersect => plan.add_intersect(left_id, right_id),
+                    SelectOp::ExceptAll => plan.add_multiset_op(left_id, right_id, true),
+                    SelectOp::IntersectAll => plan.add_multiset_op(left_id, right_id, false),
                 }
             }
         }
@@ -2563,6 +3865,7 @@ impl ParseExpression {
                 name,
                 args,
                 feature,
+                order_by,
             } => {
                 let is_distinct = matches!(feature, Some(FunctionFeature::Distinct));
                 let mut plan_arg_ids = Vec::new();
@@ -2571,12 +3874,47 @@ impl ParseExpression {
                     plan_arg_ids.push(arg_plan_id);
                 }
                 if let Some(kind) = AggregateKind::from_name(name) {
-                    plan.add_aggregate_function(name, kind, plan_arg_ids, is_distinct)?
+                    if is_distinct && matches!(kind, AggregateKind::COUNT) && plan_arg_ids.len() > 1
+                    {
+                        plan_arg_ids = vec![plan.add_count_distinct_key(&plan_arg_ids)?];
+                    }
+                    let order_by = if let Some(order_by) = order_by {
+                        let expr_id = order_by.expr.populate_plan(plan, worker)?;
+                        Some(AggregateOrderBy {
+                            expr: expr_id,
+                            is_desc: order_by.is_desc,
+                            within_group: order_by.within_group,
+                        })
+                    } else {
+                        None
+                    };
+                    plan.add_aggregate_function(name, kind, plan_arg_ids, is_distinct, order_by)?
+                } else if order_by.is_some() {
+                    return Err(SbroadError::Invalid(
+                        Entity::Query,
+                        Some("ORDER BY modifier is allowed only for aggregate functions".into()),
+                    ));
                 } else if is_distinct {
                     return Err(SbroadError::Invalid(
                         Entity::Query,
                         Some("DISTINCT modifier is allowed only for aggregate functions".into()),
                     ));
+                } else if let Some(func_id) =
+                    plan.add_variadic_json_function(name, &plan_arg_ids)?
+                {
+                    func_id
+                } else if matches!(
+                    name.to_lowercase().as_str(),
+                    "json_array_elements" | "jsonb_array_elements"
+                ) {
+                    let func = Function::new_stable(
+                        JSON_ARRAY_ELEMENTS.into(),
+                        DerivedType::new(Type::Any),
+                        false,
+                    );
+                    let func_id = plan.add_stable_function(&func, plan_arg_ids, None)?;
+                    worker.set_returning_functions.push(func_id);
+                    func_id
                 } else {
                     let func = worker.metadata.function(name)?;
                     match func.volatility {
@@ -3161,6 +4499,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args: vec![string_expr, from_expr, for_expr],
                 feature: Some(FunctionFeature::Substring(Substring::FromFor)),
+                order_by: None,
             })
         }
         Rule::SubstringRegular => {
@@ -3192,6 +4531,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args: vec![string_expr, from_expr, for_expr],
                 feature: Some(FunctionFeature::Substring(Substring::Regular)),
+                order_by: None,
             })
         }
         Rule::SubstringFor => {
@@ -3226,6 +4566,7 @@ fn parse_substring<M: Metadata>(
                     ParseExpression::PlanId { plan_id: for_id },
                 ],
                 feature: Some(FunctionFeature::Substring(Substring::For)),
+                order_by: None,
             })
         }
         Rule::SubstringFrom => {
@@ -3250,6 +4591,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args: vec![string_expr, from_expr],
                 feature: Some(FunctionFeature::Substring(Substring::From)),
+                order_by: None,
             })
         }
         Rule::SubstringSimilar => {
@@ -3301,6 +4643,7 @@ fn parse_substring<M: Metadata>(
                 name: "substring".to_string(),
                 args,
                 feature: Some(FunctionFeature::Substring(Substring::Similar)),
+                order_by: None,
             })
         }
         _ => Err(SbroadError::ParsingError(
@@ -3392,6 +4735,7 @@ where
                                 let mut function_name = String::from(first_identifier);
                                 let mut args_pairs = continuation.into_inner();
                                 let mut feature = None;
+                                let mut order_by = None;
                                 let mut parse_exprs_args = Vec::new();
                                 let function_args = args_pairs.next();
                                 if let Some(function_args) = function_args {
@@ -3451,6 +4795,16 @@ where
                                             }
 
                                             for arg in arg_pairs_to_parse {
+                                                if let Rule::AggregateOrderBy = arg.as_rule() {
+                                                    order_by = Some(parse_aggregate_order_by(
+                                                        arg,
+                                                        false,
+                                                        referred_relation_ids,
+                                                        worker,
+                                                        plan,
+                                                    )?);
+                                                    continue;
+                                                }
                                                 let arg_expr = parse_expr_pratt(
                                                     arg.into_inner(),
                                                     referred_relation_ids,
@@ -3464,16 +4818,44 @@ where
                                         rule => unreachable!("{}", format!("Unexpected rule under FunctionInvocation: {rule:?}"))
                                     }
                                 }
+                                if let Some(within_group) = inner_pairs.next() {
+                                    let order_by_pair = within_group
+                                        .into_inner()
+                                        .next()
+                                        .expect("ORDER BY expected under WITHIN GROUP");
+                                    order_by = Some(parse_aggregate_order_by(
+                                        order_by_pair,
+                                        true,
+                                        referred_relation_ids,
+                                        worker,
+                                        plan,
+                                    )?);
+                                }
+                                if function_name.eq_ignore_ascii_case("age") && parse_exprs_args.len() == 1 {
+                                    // `age(ts)` is a shorthand for `age(current_date, ts)`.
+                                    let date = worker.current_time.replace_time(Time::MIDNIGHT);
+                                    let val = Value::Datetime(Datetime::from_inner(date));
+                                    let plan_id = plan.add_const(val);
+                                    parse_exprs_args.insert(0, ParseExpression::PlanId { plan_id });
+                                }
                                 return Ok(ParseExpression::Function {
                                     name: function_name,
                                     args: parse_exprs_args,
                                     feature,
+                                    order_by,
                                 })
                             }
                             rule => unreachable!("Expr::parse expected identifier continuation, found {:?}", rule)
                         }
                     };
 
+                    if scan_name.is_none() {
+                        if let Some(expr_id) = worker.column_substitutions.get(&col_name).copied() {
+                            let plan_id = SubtreeCloner::clone_subtree(plan, expr_id)?;
+                            return Ok(ParseExpression::PlanId { plan_id });
+                        }
+                    }
+
                     if referred_relation_ids.is_empty() {
                         return Err(SbroadError::Invalid(
                             Entity::Expression,
@@ -3581,6 +4963,8 @@ where
                 | Rule::Null
                 | Rule::True
                 | Rule::SingleQuotedString
+                | Rule::Varbinary
+                | Rule::Interval
                 | Rule::Integer
                 | Rule::False => {
                     let val = Value::from_node(&primary)?;
@@ -3610,6 +4994,28 @@ where
                 }
                 Rule::Trim => parse_trim(primary, referred_relation_ids, worker, plan)?,
                 Rule::Substring => parse_substring(primary, referred_relation_ids, worker, plan)?,
+                Rule::Extract => {
+                    // `extract(field from source)` is a syntax sugar for
+                    // `date_part('field', source)`.
+                    let mut inner_pairs = primary.into_inner();
+                    let field_pair = inner_pairs.next().expect("Extract has no field child");
+                    let field = Value::from(field_pair.as_str().to_lowercase());
+                    let field_expr = ParseExpression::PlanId { plan_id: plan.add_const(field) };
+                    let source_pair = inner_pairs.next().expect("Extract has no source child");
+                    let source_expr = parse_expr_pratt(
+                        source_pair.into_inner(),
+                        referred_relation_ids,
+                        worker,
+                        plan,
+                        safe_for_volatile_function,
+                    )?;
+                    ParseExpression::Function {
+                        name: "date_part".to_string(),
+                        args: vec![field_expr, source_expr],
+                        feature: None,
+                        order_by: None,
+                    }
+                }
                 Rule::CastOp => {
                     let mut inner_pairs = primary.into_inner();
                     let expr_pair = inner_pairs.next().expect("Cast has no expr child.");
@@ -3707,7 +5113,11 @@ where
                     let precision = primary.into_inner().next()
                         .map(|p| p.as_str().parse::<usize>().unwrap_or(usize::MAX).min(6))
                         .unwrap_or(6); // Default for Postgres is 6
-                    let plan_id = plan.nodes.push(LocalTimestamp{precision}.into());
+                    let plan_id = plan.nodes.push(LocalTimestamp{precision, with_time_zone: false}.into());
+                    ParseExpression::PlanId { plan_id }
+                }
+                Rule::CurrentTimestamp => {
+                    let plan_id = plan.nodes.push(LocalTimestamp{precision: 6, with_time_zone: true}.into());
                     ParseExpression::PlanId { plan_id }
                 }
                 Rule::CountAsterisk => {
@@ -3767,6 +5177,22 @@ where
                 Rule::Multiply      => ParseExpressionInfixOperator::InfixArithmetic(Arithmetic::Multiply),
                 Rule::Add        => ParseExpressionInfixOperator::InfixArithmetic(Arithmetic::Add),
                 Rule::ConcatInfixOp => ParseExpressionInfixOperator::Concat,
+                Rule::JsonGet | Rule::JsonGetText | Rule::JsonGetPath | Rule::JsonGetPathText | Rule::JsonContains => {
+                    // JSON operators are executed by the corresponding builtin functions.
+                    let name = match op.as_rule() {
+                        Rule::JsonGet => "json_get",
+                        Rule::JsonGetText => "json_get_text",
+                        Rule::JsonGetPath => "json_get_path",
+                        Rule::JsonGetPathText => "json_get_path_text",
+                        _ => "json_contains",
+                    };
+                    return Ok(ParseExpression::Function {
+                        name: name.to_string(),
+                        args: vec![lhs, rhs],
+                        feature: None,
+                        order_by: None,
+                    })
+                }
                 rule           => unreachable!("Expr::parse expected infix operation, found {:?}", rule),
             };
 
@@ -3883,6 +5309,9 @@ fn parse_select_set_pratt(
                 Rule::UnionOp => SelectOp::Union,
                 Rule::UnionAllOp => SelectOp::UnionAll,
                 Rule::ExceptOp => SelectOp::Except,
+                Rule::ExceptAllOp => SelectOp::ExceptAll,
+                Rule::IntersectOp => SelectOp::Intersect,
+                Rule::IntersectAllOp => SelectOp::IntersectAll,
                 rule => unreachable!("Expr::parse expected infix operation, found {:?}", rule),
             };
             Ok(SelectSet::Infix {
@@ -3894,6 +5323,40 @@ fn parse_select_set_pratt(
         .parse(select_pairs)
 }
 
+/// Parse `ORDER BY` clause of the aggregate function.
+fn parse_aggregate_order_by<M>(
+    pair: Pair<Rule>,
+    within_group: bool,
+    referred_relation_ids: &[NodeId],
+    worker: &mut ExpressionsWorker<M>,
+    plan: &mut Plan,
+) -> Result<ParseAggregateOrderBy, SbroadError>
+where
+    M: Metadata,
+{
+    let mut element_inner = pair
+        .into_inner()
+        .next()
+        .expect("Expected ORDER BY element in aggregate function")
+        .into_inner();
+    let expr_pair = element_inner
+        .next()
+        .expect("Expected expression in ORDER BY");
+    let expr = parse_expr_pratt(
+        Pairs::single(expr_pair),
+        referred_relation_ids,
+        worker,
+        plan,
+        false,
+    )?;
+    let is_desc = element_inner.any(|p| p.as_rule() == Rule::Desc);
+    Ok(ParseAggregateOrderBy {
+        expr: Box::new(expr),
+        is_desc,
+        within_group,
+    })
+}
+
 fn parse_expr_no_type_check<M>(
     expression_pairs: Pairs<Rule>,
     referred_relation_ids: &[NodeId],
@@ -3921,23 +5384,142 @@ where
 fn parse_scalar_expr<M>(
     expression_pairs: Pairs<Rule>,
     type_analyzer: &mut TypeAnalyzer,
-    referred_relation_ids: &[NodeId],
+    referred_relation_ids: &[NodeId],
+    worker: &mut ExpressionsWorker<M>,
+    plan: &mut Plan,
+    safe_for_volatile_function: bool,
+) -> Result<NodeId, SbroadError>
+where
+    M: Metadata,
+{
+    let expr_id = parse_expr_no_type_check(
+        expression_pairs,
+        referred_relation_ids,
+        worker,
+        plan,
+        safe_for_volatile_function,
+    )?;
+    type_system::analyze_scalar_expr(type_analyzer, expr_id, plan, &worker.subquery_replaces)?;
+    Ok(expr_id)
+}
+
+/// Returns the columns referenced by the expression stored as SQL text
+/// (`DEFAULT` or generated column expression).
+fn stored_expr_columns(text: &str) -> Result<Vec<SmolStr>, SbroadError> {
+    let pairs = ParseTree::parse(Rule::Expr, text)
+        .map_err(|e| SbroadError::ParsingError(Entity::Expression, format_smolstr!("{e}")))?;
+    let mut columns = Vec::new();
+    for pair in pairs.flatten() {
+        if pair.as_rule() != Rule::IdentifierWithOptionalContinuation {
+            continue;
+        }
+        let mut parts = pair.into_inner();
+        let identifier = parts.next().expect("identifier expected");
+        if parts.next().is_none() {
+            columns.push(normalize_name_from_sql(identifier.as_str()));
+        }
+    }
+    Ok(columns)
+}
+
+/// Parse the expression of the column `name` stored as SQL text
+/// (`DEFAULT` or generated column expression) over `rel_id`.
+fn parse_stored_expr<M>(
+    plan: &mut Plan,
+    name: &str,
+    text: &str,
+    rel_id: NodeId,
+    type_analyzer: &mut TypeAnalyzer,
+    worker: &mut ExpressionsWorker<M>,
+) -> Result<NodeId, SbroadError>
+where
+    M: Metadata,
+{
+    let expr_pairs = ParseTree::parse(Rule::Expr, text).map_err(|e| {
+        SbroadError::ParsingError(
+            Entity::Expression,
+            format_smolstr!("DEFAULT of column {}: {e}", to_user(name)),
+        )
+    })?;
+    let expr_id = parse_scalar_expr(expr_pairs, type_analyzer, &[rel_id], worker, plan, true)?;
+    if plan.contains_aggregates(expr_id, true)? {
+        return Err(SbroadError::Invalid(
+            Entity::Query,
+            Some(format_smolstr!(
+                "aggregate functions are not allowed in DEFAULT of column {}",
+                to_user(name)
+            )),
+        ));
+    }
+    Ok(expr_id)
+}
+
+/// Add the new values of the generated columns depending on the updated
+/// columns to `update_defs`. The generated expression is parsed over
+/// `rel_child_id` with the references to the updated columns replaced by
+/// copies of their update expressions. When `qualifier` is set, the other
+/// columns are referred by it (the update is built over a join).
+#[allow(clippy::too_many_arguments)]
+fn add_generated_updates<M>(
+    plan: &mut Plan,
+    columns: &[Column],
+    update_defs: &mut HashMap<ColumnPosition, ExpressionId, RepeatableState>,
+    with_subqueries: &HashSet<ColumnPosition>,
+    rel_child_id: NodeId,
+    qualifier: Option<&str>,
+    type_analyzer: &mut TypeAnalyzer,
     worker: &mut ExpressionsWorker<M>,
-    plan: &mut Plan,
-    safe_for_volatile_function: bool,
-) -> Result<NodeId, SbroadError>
+) -> Result<(), SbroadError>
 where
     M: Metadata,
 {
-    let expr_id = parse_expr_no_type_check(
-        expression_pairs,
-        referred_relation_ids,
-        worker,
-        plan,
-        safe_for_volatile_function,
-    )?;
-    type_system::analyze_scalar_expr(type_analyzer, expr_id, plan, &worker.subquery_replaces)?;
-    Ok(expr_id)
+    for (pos, column) in columns.iter().enumerate() {
+        let Some(expr) = column.default.as_ref().filter(|_| column.generated_always) else {
+            continue;
+        };
+        let referenced = stored_expr_columns(expr)?;
+        let mut substitutions = HashMap::with_capacity(referenced.len());
+        let mut is_affected = false;
+        for name in &referenced {
+            let Some(ref_pos) = columns.iter().position(|c| &c.name == name) else {
+                continue;
+            };
+            if let Some(expr_id) = update_defs.get(&ref_pos) {
+                if with_subqueries.contains(&ref_pos) {
+                    return Err(SbroadError::Unsupported(
+                        Entity::Query,
+                        Some(format_smolstr!(
+                            "subqueries in update of column {} referred by generated column {}",
+                            to_user(name),
+                            to_user(&column.name)
+                        )),
+                    ));
+                }
+                substitutions.insert(name.clone(), *expr_id);
+                is_affected = true;
+            } else if let Some(qualifier) = qualifier {
+                let text = format_smolstr!("{}.{}", to_user(qualifier), to_user(name));
+                let expr_id =
+                    parse_stored_expr(plan, name, &text, rel_child_id, type_analyzer, worker)?;
+                substitutions.insert(name.clone(), expr_id);
+            }
+        }
+        if !is_affected {
+            continue;
+        }
+        worker.column_substitutions = substitutions;
+        let expr_id = parse_stored_expr(
+            plan,
+            &column.name,
+            expr,
+            rel_child_id,
+            type_analyzer,
+            worker,
+        );
+        worker.column_substitutions.clear();
+        update_defs.insert(pos, expr_id?);
+    }
+    Ok(())
 }
 
 fn parse_values_rows<M>(
@@ -4363,19 +5945,62 @@ impl AbstractSyntaxTree {
                     .update_value(arena_node_id, Some(SmolStr::from(body)))?;
             }
 
+            // `CHECK` expression is compiled by the storages, so it is kept
+            // as text with the column names quoted. The same goes for the
+            // expression of the generated column calculated on the router.
+            if matches!(
+                stack_node.pair.as_rule(),
+                Rule::CheckExpr | Rule::GeneratedExpr
+            ) {
+                let expr = check_expr_text(&stack_node.pair);
+                self.nodes.update_value(arena_node_id, Some(expr))?;
+            }
+
             // Update parent's node children list.
             self.nodes
                 .add_child(stack_node.arena_parent_id, arena_node_id)?;
 
             // Clean parent values (only leafs and special nodes like
-            // procedure body should contain data)
+            // procedure body or column default should contain data)
             if let Some(parent) = stack_node.arena_parent_id {
                 let parent_node = self.nodes.get_node(parent)?;
-                if parent_node.rule != Rule::ProcBody {
+                if !matches!(
+                    parent_node.rule,
+                    Rule::ProcBody
+                        | Rule::ColumnDefaultExpr
+                        | Rule::CheckExpr
+                        | Rule::GeneratedExpr
+                ) {
                     self.nodes.update_value(parent, None)?;
                 }
             }
 
+            // View query is kept as text and is not planned here: it is expanded
+            // as a subquery wherever the view is referenced. The same goes for
+            // the query of `CREATE TABLE AS`, it is planned on execution.
+            // Parameters of `CREATE TABLE AS` are bound on execution, while
+            // the view query has nowhere to take them from.
+            match stack_node.pair.as_rule() {
+                Rule::ViewBody => {
+                    let has_parameters = stack_node
+                        .pair
+                        .into_inner()
+                        .flatten()
+                        .any(|pair| pair.as_rule() == Rule::Parameter);
+                    if has_parameters {
+                        return Err(SbroadError::Invalid(
+                            Entity::Query,
+                            Some("parameters are not allowed in view query".into()),
+                        ));
+                    }
+                    continue;
+                }
+                Rule::CreateTableAsBody | Rule::SelectIntoProjection | Rule::SelectIntoBody => {
+                    continue
+                }
+                _ => {}
+            }
+
             match stack_node.pair.as_rule() {
                 Rule::Expr
                 | Rule::Row
@@ -4402,60 +6027,491 @@ impl AbstractSyntaxTree {
                 _ => {}
             }
 
-            for parse_child in stack_node.pair.into_inner() {
-                stack.push(StackParseNode::new(parse_child, Some(arena_node_id)));
+            for parse_child in stack_node.pair.into_inner() {
+                stack.push(StackParseNode::new(parse_child, Some(arena_node_id)));
+            }
+        }
+
+        // Sort parameters pairs in their appearance order.
+        // This allows to enumerate tnt parameters: `select ?, ?` -> `select $1, $2`
+        parameters.sort_by_key(|p| p.line_col());
+        *parameters_ordered = parameters;
+
+        self.set_top(0)?;
+
+        self.transform_update()?;
+        self.transform_delete()?;
+        self.transform_select()?;
+        Ok(())
+    }
+
+    fn parse_projection<M: Metadata>(
+        &self,
+        plan: &mut Plan,
+        node_id: usize,
+        type_analyzer: &mut TypeAnalyzer,
+        map: &mut Translation,
+        pairs_map: &mut ParsingPairsMap,
+        worker: &mut ExpressionsWorker<M>,
+    ) -> Result<(), SbroadError> {
+        let node = self.nodes.get_node(node_id)?;
+        let Some((rel_child_id, mut other_children_ids)) = node.children.split_first() else {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some("Projection must have some children".into()),
+            ));
+        };
+        let mut is_distinct: bool = false;
+        let Some(first_col_ast_id) = other_children_ids.first() else {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some("Projection must have at least a single column".into()),
+            ));
+        };
+        if let Rule::Distinct = self.nodes.get_node(*first_col_ast_id)?.rule {
+            is_distinct = true;
+            let Some((_, ids)) = other_children_ids.split_first() else {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some("Projection with distinct must have at least a single column".into()),
+                ));
+            };
+            other_children_ids = ids;
+        }
+
+        let plan_rel_child_id = map.get(*rel_child_id)?;
+
+        let mut named_windows = HashMap::new();
+        let rel_child = plan.get_relation_node(plan_rel_child_id)?;
+        if let Relational::NamedWindows(NamedWindows {
+            windows: window_ids,
+            ..
+        }) = rel_child
+        {
+            named_windows.reserve(window_ids.len());
+            for window_id in window_ids {
+                let window = plan.get_expression_node(*window_id)?;
+                let Expression::Window(Window { name, .. }) = window else {
+                    panic!("Expected Window node, got {:?}", window);
+                };
+                let name = name.as_ref().expect("Window name must be set").clone();
+                type_system::analyze_scalar_expr(
+                    type_analyzer,
+                    *window_id,
+                    plan,
+                    &worker.subquery_replaces,
+                )?;
+                named_windows.insert(name, *window_id);
+            }
+        }
+        worker.named_windows_map = named_windows;
+
+        let proj_columns = self.parse_projection_columns(
+            plan,
+            plan_rel_child_id,
+            other_children_ids,
+            type_analyzer,
+            pairs_map,
+            worker,
+        )?;
+        let windows = std::mem::take(&mut worker.windows);
+
+        let projection_id =
+            plan.add_proj_internal(vec![plan_rel_child_id], &proj_columns, is_distinct, windows)?;
+
+        plan.fix_subquery_rows(worker, projection_id)?;
+        map.add(node_id, projection_id);
+        Ok(())
+    }
+
+    /// Build the `RETURNING` projection of the DML node:
+    /// `Projection -> Motion(Full) -> ScanRelation`. The motion is
+    /// materialized on the router with the tuples affected on storages.
+    ///
+    /// The projection doesn't belong to the DML subtree and is evaluated
+    /// on the router: storages only check whether the clause is present
+    /// to send the affected tuples back.
+    fn parse_returning<M: Metadata>(
+        &self,
+        plan: &mut Plan,
+        dml_id: NodeId,
+        returning_ast_id: usize,
+        type_analyzer: &mut TypeAnalyzer,
+        pairs_map: &mut ParsingPairsMap,
+        worker: &mut ExpressionsWorker<M>,
+    ) -> Result<(), SbroadError> {
+        let table = plan.dml_node_table(dml_id)?;
+        if table.is_global() {
+            return Err(SbroadError::Unsupported(
+                Entity::Query,
+                Some("RETURNING clause is not supported for global tables".into()),
+            ));
+        }
+        let relation = table.name().clone();
+        let scan_id = plan.add_scan(&relation, Some(&relation))?;
+        // Motion invalidates the sharding column, so it is cut off beforehand.
+        let proj_id = plan.add_proj(scan_id, vec![], &[], false, false)?;
+        let motion_id = plan.add_motion(proj_id, &MotionPolicy::Full, Program::default())?;
+
+        worker.named_windows_map = HashMap::new();
+        let returning_node = self.nodes.get_node(returning_ast_id)?;
+        let columns = self.parse_projection_columns(
+            plan,
+            motion_id,
+            &returning_node.children,
+            type_analyzer,
+            pairs_map,
+            worker,
+        )?;
+        if !worker.windows.is_empty() || !worker.sub_queries_to_fix_queue.is_empty() {
+            return Err(SbroadError::Unsupported(
+                Entity::Query,
+                Some("window functions and subqueries in RETURNING clause".into()),
+            ));
+        }
+        for column_id in &columns {
+            if plan.contains_aggregates(*column_id, true)? {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some("aggregate functions are not allowed in RETURNING clause".into()),
+                ));
+            }
+        }
+        let returning_id = plan.add_proj_internal(vec![motion_id], &columns, false, vec![])?;
+        plan.set_dml_returning(dml_id, returning_id)
+    }
+
+    /// Check that the target of `ON CONFLICT (..)` clause is the primary key
+    /// of the table (the only unique index checked on insertion).
+    fn check_conflict_target(
+        &self,
+        plan: &Plan,
+        insert_id: NodeId,
+        target_ast_id: usize,
+    ) -> Result<(), SbroadError> {
+        let table = plan.dml_node_table(insert_id)?;
+        let target = self.nodes.get_node(target_ast_id)?;
+        let mut positions = Vec::with_capacity(target.children.len());
+        for col_id in &target.children {
+            let col_name = parse_normalized_identifier(self, *col_id)?;
+            let Some(pos) = table.columns.iter().position(|c| c.name == col_name) else {
+                return Err(SbroadError::NotFound(
+                    Entity::Column,
+                    format_smolstr!("{} among table columns", to_user(col_name)),
+                ));
+            };
+            positions.push(pos);
+        }
+        positions.sort_unstable();
+        positions.dedup();
+        let mut pk_positions = table.primary_key.positions.clone();
+        pk_positions.sort_unstable();
+        if positions != pk_positions {
+            return Err(SbroadError::Unsupported(
+                Entity::Query,
+                Some(format_smolstr!(
+                    "ON CONFLICT target must be the primary key of table {}",
+                    to_user(table.name())
+                )),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Append `DEFAULT` expressions of the table columns omitted in `INSERT`
+    /// to the inserted tuples with a projection over `rel_child_id`. So the
+    /// defaults are calculated along with the tuples (on the router for
+    /// `VALUES`). The names of the appended columns are added to
+    /// `selected_col_names`.
+    ///
+    /// Generated columns refer to the inserted values, so they are calculated
+    /// by one more projection over the first one with the columns named
+    /// after the table columns.
+    fn add_column_defaults<M: Metadata>(
+        plan: &mut Plan,
+        relation: &str,
+        rel_child_id: NodeId,
+        selected_col_names: &mut Vec<SmolStr>,
+        type_analyzer: &mut TypeAnalyzer,
+        worker: &mut ExpressionsWorker<M>,
+    ) -> Result<NodeId, SbroadError> {
+        let table = plan.get_relation_or_error(relation)?;
+        let mut defaults: Vec<(SmolStr, SmolStr)> = Vec::new();
+        let mut generated: Vec<(SmolStr, SmolStr)> = Vec::new();
+        let mut referenced: Vec<SmolStr> = Vec::new();
+        for column in &table.columns {
+            let Some(default) = &column.default else {
+                continue;
+            };
+            if selected_col_names.contains(&column.name) {
+                continue;
+            }
+            if column.generated_always {
+                referenced.extend(stored_expr_columns(default)?);
+                generated.push((column.name.clone(), default.clone()));
+            } else {
+                defaults.push((column.name.clone(), default.clone()));
+            }
+        }
+        // Omitted columns referred by the generated columns are NULL.
+        let mut nulls: Vec<(SmolStr, Option<CastType>)> = Vec::new();
+        for column in &table.columns {
+            if referenced.contains(&column.name)
+                && column.default.is_none()
+                && !selected_col_names.contains(&column.name)
+            {
+                let to = match column.r#type.get() {
+                    Some(ty) => Some(CastType::try_from(ty)?),
+                    None => None,
+                };
+                nulls.push((column.name.clone(), to));
+            }
+        }
+        if defaults.is_empty() && generated.is_empty() {
+            return Ok(rel_child_id);
+        }
+
+        let mut columns = plan.new_columns(
+            &NewColumnsSource::Other {
+                child: rel_child_id,
+                columns_spec: None,
+                asterisk_source: None,
+            },
+            true,
+            false,
+        )?;
+        // The mismatch of the inserted columns is reported by the `Insert`.
+        let generated_supported = columns.len() == selected_col_names.len();
+        if !generated.is_empty() && generated_supported {
+            for (alias_id, name) in columns.iter().zip(selected_col_names.iter()) {
+                if let MutExpression::Alias(Alias { name: alias, .. }) =
+                    plan.get_mut_expression_node(*alias_id)?
+                {
+                    alias.clone_from(name);
+                }
+            }
+            for (name, to) in nulls {
+                let mut null_id = plan.add_const(Value::Null);
+                if let Some(to) = to {
+                    null_id = plan.add_cast(null_id, to)?;
+                }
+                columns.push(plan.nodes.add_alias(&name, null_id)?);
+                selected_col_names.push(name);
+            }
+        }
+        for (name, default) in defaults {
+            let expr_id =
+                parse_stored_expr(plan, &name, &default, rel_child_id, type_analyzer, worker)?;
+            columns.push(plan.nodes.add_alias(&name, expr_id)?);
+            selected_col_names.push(name);
+        }
+        let proj_id = plan.add_proj_internal(vec![rel_child_id], &columns, false, vec![])?;
+        if generated.is_empty() || !generated_supported {
+            return Ok(proj_id);
+        }
+
+        let mut columns = plan.new_columns(
+            &NewColumnsSource::Other {
+                child: proj_id,
+                columns_spec: None,
+                asterisk_source: None,
+            },
+            true,
+            false,
+        )?;
+        for (name, expr) in generated {
+            let expr_id = parse_stored_expr(plan, &name, &expr, proj_id, type_analyzer, worker)?;
+            columns.push(plan.nodes.add_alias(&name, expr_id)?);
+            selected_col_names.push(name);
+        }
+        plan.add_proj_internal(vec![proj_id], &columns, false, vec![])
+    }
+
+    /// Build the local `Update` of `ON CONFLICT DO UPDATE` clause for
+    /// the given `Insert`.
+    ///
+    /// The tuples rejected by insertion are materialized on the storage into
+    /// the `excluded` virtual table, so the `Update` is built over
+    /// `t JOIN excluded ON <primary key equality>` and never leaves the
+    /// storage owning the bucket. That is why the sharding key can't be updated.
+    #[allow(clippy::too_many_lines)]
+    fn parse_conflict_update<M: Metadata>(
+        &self,
+        plan: &mut Plan,
+        insert_id: NodeId,
+        do_update_ast_id: usize,
+        type_analyzer: &mut TypeAnalyzer,
+        pairs_map: &mut ParsingPairsMap,
+        worker: &mut ExpressionsWorker<M>,
+    ) -> Result<(), SbroadError> {
+        let table = plan.dml_node_table(insert_id)?;
+        if table.is_global() {
+            return Err(SbroadError::Unsupported(
+                Entity::Query,
+                Some("ON CONFLICT DO UPDATE is not supported for global tables".into()),
+            ));
+        }
+        let relation = table.name().clone();
+        let columns = table.columns.clone();
+        let pk_positions = table.primary_key.positions.clone();
+        let sk_positions = table.get_sk()?.to_vec();
+        let bucket_id_pos = table.get_bucket_id_position()?;
+
+        // The `excluded` tuples are materialized without "bucket_id".
+        let scan_id = plan.add_scan(&relation, None)?;
+        let excluded_scan_id = plan.add_scan(&relation, Some(EXCLUDED_SCAN_NAME))?;
+        let excluded_proj_id = plan.add_proj(excluded_scan_id, vec![], &[], false, false)?;
+        let excluded_sq_id = plan.add_sub_query(excluded_proj_id, Some(EXCLUDED_SCAN_NAME))?;
+        let excluded_id =
+            plan.add_motion(excluded_sq_id, &MotionPolicy::Local, Program::default())?;
+
+        let mut condition_id = None;
+        for pos in &pk_positions {
+            let col_type = columns[*pos].r#type;
+            let excluded_pos = match bucket_id_pos {
+                Some(bucket_id_pos) if *pos > bucket_id_pos => *pos - 1,
+                _ => *pos,
+            };
+            let left_id = plan
+                .nodes
+                .add_ref(None, Some(vec![0]), *pos, col_type, None);
+            let right_id = plan
+                .nodes
+                .add_ref(None, Some(vec![1]), excluded_pos, col_type, None);
+            let eq_id = plan.nodes.add_bool(left_id, Bool::Eq, right_id)?;
+            condition_id = Some(match condition_id {
+                Some(prev_id) => plan.nodes.add_bool(prev_id, Bool::And, eq_id)?,
+                None => eq_id,
+            });
+        }
+        let condition_id = condition_id.expect("primary key must not be empty");
+        let join_id = plan.add_join(scan_id, excluded_id, condition_id, JoinKind::Inner)?;
+
+        let do_update = self.nodes.get_node(do_update_ast_id)?;
+        let update_list_id = do_update.first_child();
+        let mut rel_child_id = join_id;
+        if let Some(filter_ast_id) = do_update.children.get(1) {
+            let expr_ast_id = self.nodes.get_node(*filter_ast_id)?.first_child();
+            let expr_pair = pairs_map.remove_pair(expr_ast_id);
+            let filter_id = parse_scalar_expr(
+                Pairs::single(expr_pair),
+                type_analyzer,
+                &[join_id],
+                worker,
+                plan,
+                false,
+            )?;
+            rel_child_id = plan.add_select(&[join_id], filter_id)?;
+        }
+
+        let update_list = self.nodes.get_node(update_list_id)?;
+        let mut update_defs: HashMap<ColumnPosition, ExpressionId, RepeatableState> =
+            HashMap::with_capacity_and_hasher(update_list.children.len(), RepeatableState);
+        for update_item_id in &update_list.children {
+            let update_item = self.nodes.get_node(*update_item_id)?;
+            let ast_column_id = update_item
+                .children
+                .first()
+                .expect("Column expected as first child of UpdateItem");
+            let expr_ast_id = update_item
+                .children
+                .get(1)
+                .expect("Expression expected as second child of UpdateItem");
+
+            let expr_pair = pairs_map.remove_pair(*expr_ast_id);
+            let expr_id = parse_scalar_expr(
+                Pairs::single(expr_pair),
+                type_analyzer,
+                &[rel_child_id],
+                worker,
+                plan,
+                true,
+            )?;
+            if plan.contains_aggregates(expr_id, true)? {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some("aggregate functions are not supported in update expression.".into()),
+                ));
+            }
+
+            let col_name = parse_normalized_identifier(self, *ast_column_id)?;
+            let Some(pos) = columns.iter().position(|c| c.name == col_name) else {
+                return Err(SbroadError::NotFound(Entity::Column, col_name));
+            };
+            if let ColumnRole::Sharding = columns[pos].get_role() {
+                return Err(SbroadError::FailedTo(
+                    Action::Update,
+                    Some(Entity::Column),
+                    format_smolstr!("system column {} cannot be updated", to_user(col_name)),
+                ));
+            }
+            if columns[pos].generated_always {
+                return Err(generated_always_column_error(&col_name, Action::Update));
+            }
+            if pk_positions.contains(&pos) {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "it is illegal to update primary key column: {}",
+                        to_user(col_name)
+                    )),
+                ));
+            }
+            if sk_positions.contains(&pos) {
+                return Err(SbroadError::Unsupported(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "ON CONFLICT DO UPDATE of sharding key column {}",
+                        to_user(col_name)
+                    )),
+                ));
+            }
+            if update_defs.insert(pos, expr_id).is_some() {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "The same column is specified twice in update list: {}",
+                        to_user(col_name)
+                    )),
+                ));
             }
         }
+        if !worker.sub_queries_to_fix_queue.is_empty() {
+            return Err(SbroadError::Unsupported(
+                Entity::Query,
+                Some("subqueries in ON CONFLICT DO UPDATE clause".into()),
+            ));
+        }
 
-        // Sort parameters pairs in their appearance order.
-        // This allows to enumerate tnt parameters: `select ?, ?` -> `select $1, $2`
-        parameters.sort_by_key(|p| p.line_col());
-        *parameters_ordered = parameters;
-
-        self.set_top(0)?;
-
-        self.transform_update()?;
-        self.transform_delete()?;
-        self.transform_select()?;
-        Ok(())
+        add_generated_updates(
+            plan,
+            &columns,
+            &mut update_defs,
+            &HashSet::new(),
+            rel_child_id,
+            Some(&relation),
+            type_analyzer,
+            worker,
+        )?;
+        let (proj_id, update_id) =
+            plan.add_update(&relation, &update_defs, rel_child_id, Some(&relation))?;
+        let motion_id = plan.add_motion(proj_id, &MotionPolicy::Local, Program::default())?;
+        plan.set_relational_children(update_id, vec![motion_id]);
+        plan.set_insert_conflict_update(insert_id, update_id)
     }
 
-    fn parse_projection<M: Metadata>(
+    /// Parse columns of the projection (or of the `RETURNING` clause)
+    /// built over the relational node `plan_rel_child_id`.
+    fn parse_projection_columns<M: Metadata>(
         &self,
         plan: &mut Plan,
-        node_id: usize,
+        plan_rel_child_id: NodeId,
+        ast_columns_ids: &[usize],
         type_analyzer: &mut TypeAnalyzer,
-        map: &mut Translation,
         pairs_map: &mut ParsingPairsMap,
         worker: &mut ExpressionsWorker<M>,
-    ) -> Result<(), SbroadError> {
-        let node = self.nodes.get_node(node_id)?;
-        let Some((rel_child_id, mut other_children_ids)) = node.children.split_first() else {
-            return Err(SbroadError::Invalid(
-                Entity::Query,
-                Some("Projection must have some children".into()),
-            ));
-        };
-        let mut is_distinct: bool = false;
-        let Some(first_col_ast_id) = other_children_ids.first() else {
-            return Err(SbroadError::Invalid(
-                Entity::Query,
-                Some("Projection must have at least a single column".into()),
-            ));
-        };
-        if let Rule::Distinct = self.nodes.get_node(*first_col_ast_id)?.rule {
-            is_distinct = true;
-            let Some((_, ids)) = other_children_ids.split_first() else {
-                return Err(SbroadError::Invalid(
-                    Entity::Query,
-                    Some("Projection with distinct must have at least a single column".into()),
-                ));
-            };
-            other_children_ids = ids;
-        }
-
-        let plan_rel_child_id = map.get(*rel_child_id)?;
-        let mut proj_columns: Vec<NodeId> = Vec::with_capacity(other_children_ids.len());
+    ) -> Result<Vec<NodeId>, SbroadError> {
+        let mut proj_columns: Vec<NodeId> = Vec::with_capacity(ast_columns_ids.len());
 
         let mut unnamed_col_pos = 0;
         // Unique identifier for each "*" met under projection. Uniqueness is local
@@ -4463,32 +6519,7 @@ impl AbstractSyntaxTree {
         // like `select *, * from t`, where there are several of them.
         let mut asterisk_id = 0;
 
-        let mut named_windows = HashMap::new();
-        let rel_child = plan.get_relation_node(plan_rel_child_id)?;
-        if let Relational::NamedWindows(NamedWindows {
-            windows: window_ids,
-            ..
-        }) = rel_child
-        {
-            named_windows.reserve(window_ids.len());
-            for window_id in window_ids {
-                let window = plan.get_expression_node(*window_id)?;
-                let Expression::Window(Window { name, .. }) = window else {
-                    panic!("Expected Window node, got {:?}", window);
-                };
-                let name = name.as_ref().expect("Window name must be set").clone();
-                type_system::analyze_scalar_expr(
-                    type_analyzer,
-                    *window_id,
-                    plan,
-                    &worker.subquery_replaces,
-                )?;
-                named_windows.insert(name, *window_id);
-            }
-        }
-        worker.named_windows_map = named_windows;
-
-        for ast_column_id in other_children_ids {
+        for ast_column_id in ast_columns_ids {
             let ast_column = self.nodes.get_node(*ast_column_id)?;
 
             match ast_column.rule {
@@ -4568,14 +6599,7 @@ impl AbstractSyntaxTree {
                 }
             }
         }
-        let windows = std::mem::take(&mut worker.windows);
-
-        let projection_id =
-            plan.add_proj_internal(vec![plan_rel_child_id], &proj_columns, is_distinct, windows)?;
-
-        plan.fix_subquery_rows(worker, projection_id)?;
-        map.add(node_id, projection_id);
-        Ok(())
+        Ok(proj_columns)
     }
 
     fn parse_named_windows<M: Metadata>(
@@ -5059,6 +7083,7 @@ impl AbstractSyntaxTree {
         let mut col_idx: usize = 0;
         let mut worker = ExpressionsWorker::new(metadata, sq_pair_to_ast_ids, parameters_positions);
         let mut ctes = CTEs::new();
+        let mut recursive_ctes = collect_recursive_ctes(self)?;
         // This flag disables resolving of table names for DROP TABLE queries,
         // as it can be used with tables that are not presented in metadata.
         // Unresolved table names are handled in picodata depending in IF EXISTS options.
@@ -5079,14 +7104,39 @@ impl AbstractSyntaxTree {
                     map.add(id, rel_child_id_plan);
                     if let Some(ast_alias_id) = node.children.get(1) {
                         let alias_name = parse_normalized_identifier(self, *ast_alias_id)?;
+                        // Explicit column aliases: `(VALUES (1, 2)) AS t (a, b)`.
+                        let columns = node.children[2..]
+                            .iter()
+                            .map(|id| parse_normalized_identifier(self, *id))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        if !columns.is_empty()
+                            && !matches!(rel_child_node, Relational::ScanSubQuery(_))
+                        {
+                            return Err(SbroadError::Unsupported(
+                                Entity::Query,
+                                Some("column aliases of tables and CTEs".into()),
+                            ));
+                        }
                         // CTE scans can have different aliases, so clone the CTE scan node,
                         // preserving its subtree.
-                        if let Relational::ScanCte(ScanCte { child, .. }) = rel_child_node {
-                            let scan_id = plan.add_cte(*child, alias_name, vec![])?;
+                        if let Relational::ScanCte(ScanCte {
+                            child, recursive, ..
+                        }) = rel_child_node
+                        {
+                            let (child, recursive) = (*child, recursive.clone());
+                            let scan_id = plan.add_cte_scan(child, alias_name, recursive)?;
                             map.add(id, scan_id);
                         } else {
                             let mut scan = plan.get_mut_relation_node(rel_child_id_plan)?;
                             scan.set_scan_name(Some(alias_name.to_smolstr()))?;
+                            if !columns.is_empty() {
+                                let sq_id = plan.add_sub_query_with_columns(
+                                    rel_child_id_plan,
+                                    &alias_name,
+                                    columns,
+                                )?;
+                                map.add(id, sq_id);
+                            }
                         }
                     }
                 }
@@ -5102,7 +7152,15 @@ impl AbstractSyntaxTree {
                     map.add(id, scan_id);
                 }
                 Rule::ScanCteOrTable => {
-                    parse_scan_cte_or_table(self, metadata, id, &mut map, &mut ctes, &mut plan)?;
+                    parse_scan_cte_or_table(
+                        self,
+                        metadata,
+                        id,
+                        &mut map,
+                        &mut ctes,
+                        &mut recursive_ctes,
+                        &mut plan,
+                    )?;
                 }
                 Rule::Cte => {
                     parse_cte(
@@ -5110,6 +7168,7 @@ impl AbstractSyntaxTree {
                         id,
                         &mut map,
                         &mut ctes,
+                        &recursive_ctes,
                         &mut plan,
                         &mut type_analyzer,
                         pairs_map,
@@ -5159,6 +7218,17 @@ impl AbstractSyntaxTree {
                         val,
                     });
                 }
+                Rule::RecursionRowMax => {
+                    let ast_child_id = node
+                        .children
+                        .first()
+                        .expect("no children for sql_recursion_row_max option");
+                    let val = parse_option(self, *ast_child_id, pairs_map, &mut worker, &mut plan)?;
+                    plan.raw_options.push(OptionSpec {
+                        kind: OptionKind::RecursionRowMax,
+                        val,
+                    });
+                }
                 Rule::GroupBy => {
                     // Reminder: first GroupBy child in `node.children` is always a relational node.
                     let mut children: Vec<NodeId> = Vec::with_capacity(node.children.len());
@@ -5195,6 +7265,8 @@ impl AbstractSyntaxTree {
                     let ast_kind_node = self.nodes.get_node(*ast_kind_id)?;
                     let kind = match ast_kind_node.rule {
                         Rule::LeftJoinKind => JoinKind::LeftOuter,
+                        Rule::RightJoinKind => JoinKind::RightOuter,
+                        Rule::FullJoinKind => JoinKind::FullOuter,
                         Rule::InnerJoinKind => JoinKind::Inner,
                         _ => {
                             return Err(SbroadError::Invalid(
@@ -5213,6 +7285,17 @@ impl AbstractSyntaxTree {
                         .expect("Right not found among Join children.");
                     let plan_right_id = map.get(*ast_right_id)?;
 
+                    // Local SQL engine executes right join as a left one with swapped
+                    // children, which requires a single relation on the left side.
+                    if let JoinKind::RightOuter = kind {
+                        if let Relational::Join(_) = plan.get_relation_node(plan_left_id)? {
+                            return Err(SbroadError::Unsupported(
+                                Entity::Operator,
+                                Some("right join after another join, use left join instead".into()),
+                            ));
+                        }
+                    }
+
                     let ast_expr_id = node
                         .children
                         .get(3)
@@ -5394,6 +7477,8 @@ impl AbstractSyntaxTree {
                         col_name_to_position_map.insert(c.name.as_str(), (c.get_role(), i));
                     });
 
+                    // Updated columns with subqueries in their new values.
+                    let mut with_subqueries: HashSet<ColumnPosition> = HashSet::new();
                     let mut pk_positions: HashSet<usize> =
                         HashSet::with_capacity(relation.primary_key.positions.len());
                     relation.primary_key.positions.iter().for_each(|pos| {
@@ -5411,6 +7496,7 @@ impl AbstractSyntaxTree {
                             .expect("Expression expected as second child of UpdateItem");
 
                         let expr_pair = pairs_map.remove_pair(*expr_ast_id);
+                        let subqueries_before = worker.sub_queries_to_fix_queue.len();
                         let expr_plan_node_id = parse_scalar_expr(
                             Pairs::single(expr_pair),
                             &mut type_analyzer,
@@ -5419,6 +7505,8 @@ impl AbstractSyntaxTree {
                             &mut plan,
                             true,
                         )?;
+                        let has_subqueries =
+                            worker.sub_queries_to_fix_queue.len() > subqueries_before;
 
                         if plan.contains_aggregates(expr_plan_node_id, true)? {
                             return Err(SbroadError::Invalid(
@@ -5432,6 +7520,12 @@ impl AbstractSyntaxTree {
                         let col_name = parse_normalized_identifier(self, *ast_column_id)?;
                         match col_name_to_position_map.get(col_name.as_str()) {
                             Some((&ColumnRole::User, pos)) => {
+                                if relation.columns[*pos].generated_always {
+                                    return Err(generated_always_column_error(
+                                        &col_name,
+                                        Action::Update,
+                                    ));
+                                }
                                 if pk_positions.contains(pos) {
                                     return Err(SbroadError::Invalid(
                                         Entity::Query,
@@ -5451,6 +7545,9 @@ impl AbstractSyntaxTree {
                                     ));
                                 }
                                 update_defs.insert(*pos, expr_plan_node_id);
+                                if has_subqueries {
+                                    with_subqueries.insert(*pos);
+                                }
                             }
                             Some((&ColumnRole::Sharding, _)) => {
                                 return Err(SbroadError::FailedTo(
@@ -5470,9 +7567,29 @@ impl AbstractSyntaxTree {
                             }
                         }
                     }
+                    add_generated_updates(
+                        &mut plan,
+                        &relation.columns,
+                        &mut update_defs,
+                        &with_subqueries,
+                        rel_child_id,
+                        None,
+                        &mut type_analyzer,
+                        &mut worker,
+                    )?;
                     let (proj_id, update_id) =
-                        plan.add_update(&scan_relation, &update_defs, rel_child_id)?;
+                        plan.add_update(&scan_relation, &update_defs, rel_child_id, None)?;
                     plan.fix_subquery_rows(&mut worker, proj_id)?;
+                    if let Some(returning_ast_id) = node.children.get(3).copied() {
+                        self.parse_returning(
+                            &mut plan,
+                            update_id,
+                            returning_ast_id,
+                            &mut type_analyzer,
+                            pairs_map,
+                            &mut worker,
+                        )?;
+                    }
                     map.add(id, update_id);
                 }
                 Rule::Delete => {
@@ -5484,6 +7601,7 @@ impl AbstractSyntaxTree {
                         .first()
                         .expect("Delete must have at least one child");
                     let first_child_node = self.nodes.get_node(*first_child_id)?;
+                    let returning_ast_id = node.children.get(1).copied();
                     let (proj_child_id, table_name) = match first_child_node.rule {
                         Rule::ScanTable => {
                             let plan_scan_id = map.get(*first_child_id)?;
@@ -5494,10 +7612,17 @@ impl AbstractSyntaxTree {
                                 unreachable!("Scan expected under ScanTable")
                             };
 
+                            let table = metadata.table(relation)?;
+                            // DELETE with RETURNING also needs the deleted tuples on storages.
+                            let has_returning = returning_ast_id.is_some();
                             // See below where `proj_child_id`` is used. We don't want to apply such
                             // an optimization for global tables yet (see issue
                             // https://git.picodata.io/picodata/sbroad/-/issues/861).
-                            let proj_child_id = if metadata.table(relation)?.is_global() {
+                            let is_global = table.is_global();
+                            // Partitioned table itself is empty, the tuples are deleted from
+                            // the partitions found by the scan.
+                            let is_partitioned = table.partitioning.is_some();
+                            let proj_child_id = if has_returning || is_global || is_partitioned {
                                 Some(plan_scan_id)
                             } else {
                                 None
@@ -5598,19 +7723,57 @@ impl AbstractSyntaxTree {
                     };
 
                     let plan_delete_id = plan.add_delete(table.name, plan_proj_id)?;
+                    if let Some(returning_ast_id) = returning_ast_id {
+                        self.parse_returning(
+                            &mut plan,
+                            plan_delete_id,
+                            returning_ast_id,
+                            &mut type_analyzer,
+                            pairs_map,
+                            &mut worker,
+                        )?;
+                    }
                     map.add(id, plan_delete_id);
                 }
                 Rule::Insert => {
-                    let ast_table_id = node.children.first().expect("Insert has no children.");
+                    // The last child may be a `RETURNING` clause.
+                    let (returning_ast_id, children) = match node.children.split_last() {
+                        Some((last_id, rest))
+                            if self.nodes.get_node(*last_id)?.rule == Rule::Returning =>
+                        {
+                            (Some(*last_id), rest)
+                        }
+                        _ => (None, node.children.as_slice()),
+                    };
+                    let ast_table_id = children.first().expect("Insert has no children.");
                     let relation = parse_normalized_identifier(self, *ast_table_id)?;
 
-                    let ast_child_id = node
-                        .children
+                    let ast_child_id = children
                         .get(1)
                         .expect("Second child not found among Insert children");
+                    // `ON CONFLICT` clause may contain a conflict target.
+                    let mut conflict_target_ast_id = None;
+                    let mut do_update_ast_id = None;
+                    for child_id in children.iter().skip(2) {
+                        match self.nodes.get_node(*child_id)?.rule {
+                            Rule::ConflictTarget => conflict_target_ast_id = Some(*child_id),
+                            Rule::DoUpdate => do_update_ast_id = Some(*child_id),
+                            _ => {}
+                        }
+                    }
+                    if do_update_ast_id.is_some()
+                        && metadata.table(&relation)?.partitioning.is_some()
+                    {
+                        return Err(SbroadError::Unsupported(
+                            Entity::Query,
+                            Some("ON CONFLICT DO UPDATE for partitioned tables".into()),
+                        ));
+                    }
                     let get_conflict_strategy =
                         |child_idx: usize| -> Result<ConflictStrategy, SbroadError> {
-                            let Some(child_id) = node.children.get(child_idx).copied() else {
+                            let child_idx =
+                                child_idx + usize::from(conflict_target_ast_id.is_some());
+                            let Some(child_id) = children.get(child_idx).copied() else {
                                 return Ok(ConflictStrategy::DoFail);
                             };
                             let rule = &self.nodes.get_node(child_id)?.rule;
@@ -5618,6 +7781,7 @@ impl AbstractSyntaxTree {
                                 Rule::DoNothing => ConflictStrategy::DoNothing,
                                 Rule::DoReplace => ConflictStrategy::DoReplace,
                                 Rule::DoFail => ConflictStrategy::DoFail,
+                                Rule::DoUpdate => ConflictStrategy::DoUpdate,
                                 _ => {
                                     return Err(SbroadError::Invalid(
                                         Entity::AST,
@@ -5666,7 +7830,17 @@ impl AbstractSyntaxTree {
                             if let ColumnRole::Sharding = column.get_role() {
                                 continue;
                             }
-                            if !column.is_nullable && !selected_col_names.contains(&column.name) {
+                            if column.generated_always && selected_col_names.contains(&column.name)
+                            {
+                                return Err(generated_always_column_error(
+                                    &column.name,
+                                    Action::Insert,
+                                ));
+                            }
+                            if !column.is_nullable
+                                && column.default.is_none()
+                                && !selected_col_names.contains(&column.name)
+                            {
                                 return Err(SbroadError::Invalid(
                                     Entity::Column,
                                     Some(format_smolstr!(
@@ -5677,11 +7851,17 @@ impl AbstractSyntaxTree {
                             }
                         }
 
-                        let ast_rel_child_id = node
-                            .children
+                        let ast_rel_child_id = children
                             .get(2)
                             .expect("Third child not found among Insert children");
-                        let plan_rel_child_id = map.get(*ast_rel_child_id)?;
+                        let plan_rel_child_id = Self::add_column_defaults(
+                            &mut plan,
+                            &relation,
+                            map.get(*ast_rel_child_id)?,
+                            &mut selected_col_names,
+                            &mut type_analyzer,
+                            &mut worker,
+                        )?;
                         let conflict_strategy = get_conflict_strategy(3)?;
                         plan.add_insert(
                             &relation,
@@ -5691,16 +7871,46 @@ impl AbstractSyntaxTree {
                         )?
                     } else {
                         // insert into t ...
+                        let rel = plan.get_relation_or_error(&relation)?;
+                        if let Some(column) = rel.columns.iter().find(|c| c.generated_always) {
+                            return Err(generated_always_column_error(
+                                &column.name,
+                                Action::Insert,
+                            ));
+                        }
                         let plan_child_id = map.get(*ast_child_id)?;
                         let conflict_strategy = get_conflict_strategy(2)?;
                         plan.add_insert(&relation, plan_child_id, &[], conflict_strategy)?
                     };
+                    if let Some(target_ast_id) = conflict_target_ast_id {
+                        self.check_conflict_target(&plan, plan_insert_id, target_ast_id)?;
+                    }
+                    if let Some(do_update_ast_id) = do_update_ast_id {
+                        self.parse_conflict_update(
+                            &mut plan,
+                            plan_insert_id,
+                            do_update_ast_id,
+                            &mut type_analyzer,
+                            pairs_map,
+                            &mut worker,
+                        )?;
+                    }
+                    if let Some(returning_ast_id) = returning_ast_id {
+                        self.parse_returning(
+                            &mut plan,
+                            plan_insert_id,
+                            returning_ast_id,
+                            &mut type_analyzer,
+                            pairs_map,
+                            &mut worker,
+                        )?;
+                    }
                     map.add(id, plan_insert_id);
                 }
                 Rule::Explain => {
-                    plan.mark_as_explain();
+                    plan.mark_as_explain(parse_explain_options(self, node)?);
 
-                    let ast_child_id = node.children.first().expect("Explain has no children.");
+                    let ast_child_id = node.children.last().expect("Explain has no children.");
                     map.add(0, map.get(*ast_child_id)?);
                 }
                 Rule::Query => {
@@ -5737,7 +7947,7 @@ impl AbstractSyntaxTree {
                     map.add(id, plan_id);
                 }
                 Rule::CreateSchema => {
-                    let create_schema = DdlOwned::CreateSchema;
+                    let create_schema = parse_create_schema(self, node)?;
                     let plan_id = plan.nodes.push(create_schema.into());
                     map.add(id, plan_id);
                 }
@@ -5764,10 +7974,16 @@ impl AbstractSyntaxTree {
                     map.add(id, plan_id);
                 }
                 Rule::CreatePartition => {
-                    return Err(SbroadError::NotImplemented(
-                        Entity::Rule,
-                        format_smolstr!("PARTITION OF logic is not supported yet."),
-                    ));
+                    let create_partition = parse_create_partition(
+                        self,
+                        node,
+                        &mut type_analyzer,
+                        pairs_map,
+                        &mut worker,
+                        &mut plan,
+                    )?;
+                    let plan_id = plan.nodes.push(create_partition.into());
+                    map.add(id, plan_id);
                 }
                 Rule::GrantPrivilege => {
                     let (grant_type, grantee_name, timeout) = parse_grant_revoke(node, self)?;
@@ -5794,11 +8010,46 @@ impl AbstractSyntaxTree {
                     let plan_id = plan.nodes.push(drop_index.into());
                     map.add(id, plan_id);
                 }
+                Rule::CreateSequence => {
+                    let create_sequence = parse_create_sequence(self, node)?;
+                    let plan_id = plan.nodes.push(create_sequence.into());
+                    map.add(id, plan_id);
+                }
+                Rule::DropSequence => {
+                    let drop_sequence = parse_drop_sequence(self, node)?;
+                    let plan_id = plan.nodes.push(drop_sequence.into());
+                    map.add(id, plan_id);
+                }
+                Rule::CreateTableAs => {
+                    let create_table_as = parse_create_table_as(self, node)?;
+                    let plan_id = plan.nodes.push(create_table_as.into());
+                    map.add(id, plan_id);
+                }
+                Rule::SelectInto => {
+                    let create_table_as = parse_select_into(self, node)?;
+                    let plan_id = plan.nodes.push(create_table_as.into());
+                    map.add(id, plan_id);
+                }
+                Rule::CreateView => {
+                    let create_view = parse_create_view(self, node)?;
+                    let plan_id = plan.nodes.push(create_view.into());
+                    map.add(id, plan_id);
+                }
+                Rule::DropView => {
+                    let drop_view = parse_drop_view(self, node)?;
+                    let plan_id = plan.nodes.push(drop_view.into());
+                    map.add(id, plan_id);
+                }
                 Rule::DropSchema => {
-                    let drop_schema = DdlOwned::DropSchema;
+                    let drop_schema = parse_drop_schema(self, node)?;
                     let plan_id = plan.nodes.push(drop_schema.into());
                     map.add(id, plan_id);
                 }
+                Rule::Analyze => {
+                    let analyze = parse_analyze(self, node)?;
+                    let plan_id = plan.nodes.push(analyze.into());
+                    map.add(id, plan_id);
+                }
                 Rule::DropRole => {
                     let mut name = None;
                     let mut timeout = get_default_timeout();
@@ -5890,7 +8141,7 @@ impl AbstractSyntaxTree {
                                         let auth_method_node =
                                             self.nodes.get_node(*auth_method_node_id)?;
                                         auth_method = match auth_method_node.rule {
-                                            method @ (Rule::ChapSha1 | Rule::Md5) => auth_method_from_auth_rule(method),
+                                            method @ (Rule::ChapSha1 | Rule::Md5 | Rule::ScramSha256) => auth_method_from_auth_rule(method),
                                             _ => {
                                                 return Err(SbroadError::Invalid(
                                                     Entity::Node,
@@ -5927,6 +8178,25 @@ impl AbstractSyntaxTree {
                                 new_name: identifier,
                             }
                         }
+                        Rule::AlterSetParam => {
+                            let named_param_id = alter_option_node.first_child();
+                            let named_param = self.nodes.get_node(named_param_id)?;
+                            let param_name_id = named_param
+                                .children
+                                .first()
+                                .expect("Param name expected under NamedParam.");
+                            let param_name = parse_identifier(self, *param_name_id)?;
+                            if param_name != STATEMENT_TIMEOUT_PARAM {
+                                return Err(SbroadError::Unsupported(
+                                    Entity::Option,
+                                    Some(format_smolstr!(
+                                        "ALTER USER supports only {STATEMENT_TIMEOUT_PARAM} parameter, got {param_name}"
+                                    )),
+                                ));
+                            }
+                            let millis = parse_statement_timeout(self, &named_param.children[1..])?;
+                            AlterOption::StatementTimeout { millis }
+                        }
                         _ => {
                             return Err(SbroadError::Invalid(
                                 Entity::ParseNode,
@@ -5967,7 +8237,10 @@ impl AbstractSyntaxTree {
                             Rule::Timeout => {
                                 timeout = get_timeout(self, *child_id)?;
                             }
-                            method @ (Rule::ChapSha1 | Rule::Md5 | Rule::Ldap) => {
+                            method @ (Rule::ChapSha1
+                            | Rule::Md5
+                            | Rule::ScramSha256
+                            | Rule::Ldap) => {
                                 auth_method = auth_method_from_auth_rule(method);
                             }
                             _ => {
@@ -6115,6 +8388,20 @@ impl AbstractSyntaxTree {
                 SbroadError::Invalid(Entity::AST, Some("no top in AST".into()))
             })?)?;
         plan.set_top(plan_top_id)?;
+        plan.check_set_returning_functions(&worker.set_returning_functions)?;
+
+        // Scans of the partitioned tables are replaced with the scans
+        // of their partitions (see `Plan::expand_partitions`).
+        let partitions: Vec<SmolStr> = plan
+            .relations
+            .tables
+            .values()
+            .filter_map(|table| table.partitioning.as_ref())
+            .flat_map(|partitioning| partitioning.partitions.iter().map(|p| p.name.clone()))
+            .collect();
+        for name in partitions {
+            plan.add_rel(metadata.table(&name)?);
+        }
 
         let mut tiers = plan
             .relations
@@ -6160,6 +8447,11 @@ impl Ast for AbstractSyntaxTree {
         let mut pos_to_ast_id: SelectChildPairTranslation = HashMap::new();
         let mut sq_pair_to_ast_ids: PairToAstIdTranslation = HashMap::new();
 
+        let resolved = schema::resolve_schemas(query, metadata)?;
+        let query = resolved.as_deref().unwrap_or(query);
+        let expanded = view::expand_views(query, metadata)?;
+        let query = expanded.as_ref().map_or(query, |e| e.query.as_str());
+
         let mut ast = AbstractSyntaxTree::empty();
         let mut parameters_positions = Vec::new();
         ast.fill(
@@ -6175,13 +8467,17 @@ impl Ast for AbstractSyntaxTree {
             return Ok(Plan::empty());
         }
 
-        ast.resolve_metadata(
+        let mut plan = ast.resolve_metadata(
             metadata,
             &mut ast_id_to_pairs_map,
             &mut pos_to_ast_id,
             &sq_pair_to_ast_ids,
             parameters_positions,
-        )
+        )?;
+        if let Some(expanded) = expanded {
+            expanded.fill_plan_views(&mut plan);
+        }
+        Ok(plan)
     }
 }
 
@@ -6206,12 +8502,143 @@ impl Plan {
         };
         Ok(row_id)
     }
+
+    /// Combine arguments of `count(distinct a, b)` into a single key:
+    /// `CASE WHEN a IS NULL OR b IS NULL THEN NULL ELSE quote(a) || ',' || quote(b) END`.
+    ///
+    /// Rows with NULL in any of the arguments are not counted.
+    fn add_count_distinct_key(&mut self, args: &[NodeId]) -> Result<NodeId, SbroadError> {
+        let quote = Function::new_stable("quote".into(), DerivedType::new(Type::String), true);
+        let mut null_check_id = None;
+        let mut key_id = None;
+        for arg_id in args {
+            let arg_copy_id = SubtreeCloner::clone_subtree(self, *arg_id)?;
+            let arg_row_id = self.row(*arg_id)?;
+            let is_null_id = self.add_unary(Unary::IsNull, arg_row_id)?;
+            null_check_id = Some(match null_check_id {
+                Some(left_id) => self.add_cond(left_id, Bool::Or, is_null_id)?,
+                None => is_null_id,
+            });
+            let quoted_id = self.add_stable_function(&quote, vec![arg_copy_id], None)?;
+            key_id = Some(match key_id {
+                Some(left_id) => {
+                    let sep_id = self.add_const(Value::String(",".into()));
+                    let left_id = self.add_concat(left_id, sep_id);
+                    self.add_concat(left_id, quoted_id)
+                }
+                None => quoted_id,
+            });
+        }
+        let (Some(null_check_id), Some(key_id)) = (null_check_id, key_id) else {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some("count(distinct ..) requires at least one argument".into()),
+            ));
+        };
+        let null_id = self.add_const(Value::Null);
+        Ok(self.add_case(None, vec![(null_check_id, null_id)], Some(key_id)))
+    }
+
+    /// Variadic JSON functions are executed as chains of their fixed-arity
+    /// counterparts, as builtin functions are registered with a fixed list
+    /// of parameters:
+    /// * `json_extract_path(d, a, b)` -> `json_extract_path(json_extract_path(d, a), b)`;
+    /// * `json_build_object(k1, v1, k2, v2)` ->
+    ///   `json_object_set(json_object_set(NULL, k1, v1), k2, v2)`;
+    /// * `json_build_array(v1, v2)` -> `json_array_append(json_array_append(NULL, v1), v2)`.
+    ///
+    /// Returns `None` if the function is not a variadic JSON one.
+    fn add_variadic_json_function(
+        &mut self,
+        name: &str,
+        args: &[NodeId],
+    ) -> Result<Option<NodeId>, SbroadError> {
+        let invalid = |msg: SmolStr| SbroadError::Invalid(Entity::Query, Some(msg));
+        let name = name.to_lowercase();
+        let func_id = match name.as_str() {
+            "json_extract_path" | "json_extract_path_text" => {
+                let Some((doc_id, path)) = args.split_first().filter(|(_, p)| !p.is_empty()) else {
+                    return Err(invalid(format_smolstr!(
+                        "{name} function requires a JSON value and at least one path element"
+                    )));
+                };
+                let mut func_id = *doc_id;
+                for (i, elem_id) in path.iter().enumerate() {
+                    let func = if i + 1 == path.len() && name == "json_extract_path_text" {
+                        Function::new_stable(
+                            "json_extract_path_text".into(),
+                            DerivedType::new(Type::String),
+                            false,
+                        )
+                    } else {
+                        Function::new_stable(
+                            "json_extract_path".into(),
+                            DerivedType::new(Type::Any),
+                            false,
+                        )
+                    };
+                    func_id = self.add_stable_function(&func, vec![func_id, *elem_id], None)?;
+                }
+                func_id
+            }
+            "json_build_object" => {
+                if args.len() % 2 == 1 {
+                    return Err(invalid(format_smolstr!(
+                        "{name} function requires an even number of arguments"
+                    )));
+                }
+                let mut func_id = self.add_stable_function(
+                    &Function::new_stable(
+                        "json_object_new".into(),
+                        DerivedType::new(Type::Map),
+                        false,
+                    ),
+                    vec![],
+                    None,
+                )?;
+                let func = Function::new_stable(
+                    "json_object_set".into(),
+                    DerivedType::new(Type::Map),
+                    false,
+                );
+                for pair in args.chunks(2) {
+                    let children = vec![func_id, pair[0], pair[1]];
+                    func_id = self.add_stable_function(&func, children, None)?;
+                }
+                func_id
+            }
+            "json_build_array" => {
+                let mut func_id = self.add_stable_function(
+                    &Function::new_stable(
+                        "json_array_new".into(),
+                        DerivedType::new(Type::Array),
+                        false,
+                    ),
+                    vec![],
+                    None,
+                )?;
+                let func = Function::new_stable(
+                    "json_array_append".into(),
+                    DerivedType::new(Type::Array),
+                    false,
+                );
+                for arg_id in args {
+                    func_id = self.add_stable_function(&func, vec![func_id, *arg_id], None)?;
+                }
+                func_id
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(func_id))
+    }
 }
 
 pub mod ast;
 pub mod ir;
+pub mod schema;
 pub mod tree;
 mod type_system;
+mod view;
 
 fn parse_plugin_opts<T: Default>(
     ast: &AbstractSyntaxTree,
@@ -135,6 +135,22 @@ impl ParseNodes {
         Ok(())
     }
 
+    /// Detach the trailing `Returning` child of the DML node (if any),
+    /// so that DML transformations can rely on the children positions.
+    ///
+    /// # Errors
+    /// - failed to get node from arena
+    pub fn pop_returning_child(&mut self, node_id: usize) -> Result<Option<usize>, SbroadError> {
+        let Some(last_id) = self.get_node(node_id)?.children.last().copied() else {
+            return Ok(None);
+        };
+        if self.get_node(last_id)?.rule != Rule::Returning {
+            return Ok(None);
+        }
+        self.get_mut_node(node_id)?.children.pop();
+        Ok(Some(last_id))
+    }
+
     /// Sets node children to given children
     ///
     /// # Errors
@@ -256,20 +272,47 @@ impl AbstractSyntaxTree {
     /// Bring join AST to expected kind
     ///
     /// Inner join can be specified as `inner join` or `join` in user query,
-    /// add `inner` to join if the second form was used
+    /// add `inner` to join if the second form was used.
+    ///
+    /// Cross join (`cross join t` or `, t`) is an inner join with `true`
+    /// condition, so the missing kind and condition are added for it.
     pub(super) fn normalize_join_ast(&mut self, join_id: usize) -> Result<(), SbroadError> {
         let node = self.nodes.get_node(join_id)?;
         if let Rule::Join = node.rule {
-            if node.children.len() < 3 {
+            let mut children = node.children.clone();
+            let (Some(first_id), Some(last_id)) = (children.first(), children.last()) else {
+                return Err(SbroadError::UnexpectedNumberOfValues(
+                    "Join node has no children.".into(),
+                ));
+            };
+            let first_rule = self.nodes.get_node(*first_id)?.rule;
+            let last_rule = self.nodes.get_node(*last_id)?.rule;
+            if let Rule::Scan = last_rule {
+                if let Rule::CrossJoinKind = first_rule {
+                    children.remove(0);
+                }
+                let true_id = self.nodes.push_node(ParseNode {
+                    children: vec![],
+                    rule: Rule::True,
+                    value: Some("true".into()),
+                });
+                let expr_id = self.nodes.push_node(ParseNode {
+                    children: vec![true_id],
+                    rule: Rule::Expr,
+                    value: None,
+                });
+                children.push(expr_id);
+            }
+            if children.len() < 3 {
                 let inner_node = ParseNode {
                     children: vec![],
                     rule: Rule::InnerJoinKind,
                     value: Some("inner".into()),
                 };
                 let inner_id = self.nodes.push_node(inner_node);
-                let mut_node = self.nodes.get_mut_node(join_id)?;
-                mut_node.children.insert(0, inner_id);
+                children.insert(0, inner_id);
             }
+            self.nodes.set_children(join_id, children)?;
         } else {
             return Err(SbroadError::Invalid(
                 Entity::ParseNode,
@@ -326,6 +369,7 @@ impl AbstractSyntaxTree {
                 return Ok(());
             }
         };
+        let returning_id = self.nodes.pop_returning_child(update_id)?;
         let node = self.nodes.get_node(update_id)?;
         let table_id = *node
             .children
@@ -409,6 +453,9 @@ impl AbstractSyntaxTree {
                 ))
             }
         }
+        if let Some(returning_id) = returning_id {
+            self.nodes.push_back_child(update_id, returning_id)?;
+        }
         Ok(())
     }
 
@@ -420,27 +467,30 @@ impl AbstractSyntaxTree {
             if node.rule != Rule::Delete {
                 continue;
             }
-            let (table_id, filter_id) = if let (Some(table_id), Some(filter_id)) =
-                (node.children.first(), node.children.get(1))
-            {
-                (*table_id, *filter_id)
-            } else {
-                continue;
-            };
-            let filter_node = self.nodes.get_node(filter_id)?;
-            if filter_node.rule != Rule::DeleteFilter {
-                return Err(SbroadError::Invalid(
-                    Entity::ParseNode,
-                    Some(format_smolstr!(
-                        "expected delete filter as a second child, got: {filter_node:?}"
-                    )),
-                ));
+            let returning_id = self.nodes.pop_returning_child(id)?;
+            let node = self.nodes.get_node(id)?;
+            if let (Some(table_id), Some(filter_id)) = (
+                node.children.first().copied(),
+                node.children.get(1).copied(),
+            ) {
+                let filter_node = self.nodes.get_node(filter_id)?;
+                if filter_node.rule != Rule::DeleteFilter {
+                    return Err(SbroadError::Invalid(
+                        Entity::ParseNode,
+                        Some(format_smolstr!(
+                            "expected delete filter as a second child, got: {filter_node:?}"
+                        )),
+                    ));
+                }
+                let mut new_filter_children = Vec::with_capacity(filter_node.children.len() + 1);
+                new_filter_children.push(table_id);
+                new_filter_children.extend(filter_node.children.iter().copied());
+                self.nodes.set_children(filter_id, new_filter_children)?;
+                self.nodes.set_children(id, vec![filter_id])?;
+            }
+            if let Some(returning_id) = returning_id {
+                self.nodes.push_back_child(id, returning_id)?;
             }
-            let mut new_filter_children = Vec::with_capacity(filter_node.children.len() + 1);
-            new_filter_children.push(table_id);
-            new_filter_children.extend(filter_node.children.iter().copied());
-            self.nodes.set_children(filter_id, new_filter_children)?;
-            self.nodes.set_children(id, vec![filter_id])?;
         }
         Ok(())
     }
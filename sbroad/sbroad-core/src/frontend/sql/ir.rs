@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::str::FromStr;
 
 use ahash::AHashMap;
 use pest::iterators::Pair;
@@ -20,6 +21,8 @@ use crate::ir::operator::{OrderByElement, OrderByEntity};
 use crate::ir::transformation::redistribution::MotionOpcode;
 use crate::ir::tree::traversal::{LevelNode, PostOrder};
 use crate::ir::value::double::Double;
+use crate::ir::value::interval::Interval;
+use crate::ir::value::varbinary::Varbinary;
 use crate::ir::value::Value;
 use crate::ir::Plan;
 
@@ -79,6 +82,21 @@ impl Value {
                 let inner = &pair_str[1..pair_str.len() - 1];
                 Ok(escape_single_quotes(inner).into())
             }
+            Rule::Varbinary => {
+                // Strip `X'` prefix and `'` suffix.
+                let digits = &pair_string[2..pair_string.len() - 1];
+                Ok(Varbinary::from_hex(digits)?.into())
+            }
+            Rule::Interval => {
+                let literal = pair
+                    .clone()
+                    .into_inner()
+                    .next()
+                    .expect("Interval literal must contain a string");
+                let literal_str = literal.as_str();
+                let inner = &literal_str[1..literal_str.len() - 1];
+                Ok(Interval::from_str(&escape_single_quotes(inner))?.into())
+            }
             _ => Err(SbroadError::Unsupported(
                 Entity::Type,
                 Some("can not create Value from ParseNode".into()),
@@ -343,6 +361,8 @@ impl SubtreeCloner {
                 child: _,
                 output: _,
                 conflict_strategy: _,
+                conflict_update: _,
+                returning: _,
             })
             | RelOwned::Update(Update {
                 relation: _,
@@ -351,11 +371,13 @@ impl SubtreeCloner {
                 strategy: _,
                 pk_positions: _,
                 output: _,
+                returning: _,
             })
             | RelOwned::Delete(Delete {
                 relation: _,
                 child: _,
                 output: _,
+                returning: _,
             })
             | RelOwned::ScanRelation(ScanRelation {
                 alias: _,
@@ -366,6 +388,7 @@ impl SubtreeCloner {
                 alias: _,
                 output: _,
                 child: _,
+                recursive: _,
             })
             | RelOwned::ScanSubQuery(ScanSubQuery {
                 alias: _,
@@ -394,6 +417,7 @@ impl SubtreeCloner {
             })
             | RelOwned::Limit(Limit {
                 limit: _,
+                offset: _,
                 child: _,
                 output: _,
             }) => {}
@@ -439,10 +463,15 @@ impl SubtreeCloner {
                             // so it is safe to look up motion_id in map
                             *motion_id = self.get_new_id(*motion_id)?;
                         }
+                        MotionOpcode::AddMissingRowsForFullJoin { motion_id } => {
+                            // Projection -> THIS Motion -> Projection -> FullJoin -> SubQuery -> Projection -> Motion (== motion_id)
+                            *motion_id = self.get_new_id(*motion_id)?;
+                        }
                         MotionOpcode::PrimaryKey(_)
                         | MotionOpcode::RemoveDuplicates
                         | MotionOpcode::ReshardIfNeeded
-                        | MotionOpcode::SerializeAsEmptyTable(_) => {}
+                        | MotionOpcode::SerializeAsEmptyTable(_)
+                        | MotionOpcode::UnnestArrays(_) => {}
                     }
                 }
             }
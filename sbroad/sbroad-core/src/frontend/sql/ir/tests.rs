@@ -1710,6 +1710,99 @@ fn front_sql_string_agg_alias_to_group_concat() {
     "#);
 }
 
+#[test]
+fn front_sql_bool_and_aggregate() {
+    let input = r#"SELECT bool_and("product_units") FROM "hash_testing""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection (min(("bool_and_1"::boolean))::boolean -> "col_1")
+        motion [policy: full]
+            projection (min(("hash_testing"."product_units"::boolean))::boolean -> "bool_and_1")
+                scan "hash_testing"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_percentile_aggregate() {
+    let input = r#"SELECT percentile_disc(0.5) WITHIN GROUP (ORDER BY "a") FROM "t""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("percentile_disc_finalize"(("percentile_disc"((0.5::decimal, "a"::unsigned, false::boolean))::map))::unsigned -> "col_1")
+        motion [policy: full]
+            projection ("t"."a"::unsigned -> "a", "t"."b"::unsigned -> "b", "t"."c"::unsigned -> "c", "t"."d"::unsigned -> "d", "t"."bucket_id"::unsigned -> "bucket_id")
+                scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_ordered_array_agg_two_stage() {
+    let input = r#"SELECT array_agg("a" ORDER BY "b" DESC) FROM "t""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("ordered_array_agg_finalize"(("ordered_agg_merge"(("ordered_array_agg_1"::array, true::boolean))::array))::array -> "col_1")
+        motion [policy: full]
+            projection ("ordered_array_agg"(("t"."a"::unsigned, "t"."b"::unsigned, true::boolean))::array -> "ordered_array_agg_1")
+                scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_variance_two_stage() {
+    let input = r#"SELECT var_samp("a") FROM "t""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("variance_finalize"(("variance_merge"(("var_samp_1"::double))::array, true::boolean))::double -> "col_1")
+        motion [policy: full]
+            projection ("variance_state"(("t"."a"::unsigned))::array -> "var_samp_1")
+                scan "t"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn front_sql_aggregate_order_by_errors() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"SELECT sum("a" ORDER BY "b") FROM "t""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        r#"invalid query: ORDER BY is allowed only for string_agg, group_concat and array_agg aggregate functions. Got: "sum""#,
+        err.to_string()
+    );
+
+    let input = r#"SELECT percentile_cont(0.5) FROM "t""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        r#"invalid query: "percentile_cont" aggregate function requires WITHIN GROUP (ORDER BY ..)"#,
+        err.to_string()
+    );
+
+    let input = r#"SELECT array_agg(distinct "a" ORDER BY "a") FROM "t""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        r#"invalid query: DISTINCT and ORDER BY can't be used together in aggregate function "array_agg""#,
+        err.to_string()
+    );
+
+    let input = r#"SELECT var_samp(distinct "a") FROM "t""#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        r#"invalid query: DISTINCT is not supported for aggregate function "var_samp""#,
+        err.to_string()
+    );
+}
+
 #[test]
 fn front_sql_count_asterisk1() {
     let input = r#"SELECT count(*), count(*) FROM "t""#;
@@ -3954,6 +4047,118 @@ fn front_different_values_row_len() {
     assert_eq!("VALUES lists must all be the same length", err.to_string());
 }
 
+#[test]
+fn front_sql_values_column_aliases() {
+    let input = r#"SELECT a, b FROM (VALUES (1, 'x'), (2, 'y')) AS t (a, b) WHERE a = 1"#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t"."a"::unsigned -> "a", "t"."b"::string -> "b")
+        selection ROW("t"."a"::unsigned) = ROW(1::unsigned)
+            scan "t"
+                projection ("t"."COLUMN_3"::unsigned -> "a", "t"."COLUMN_4"::string -> "b")
+                    scan "t"
+                        values
+                            value row (data=ROW(1::unsigned, 'x'::string))
+                            value row (data=ROW(2::unsigned, 'y'::string))
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+
+    // The columns without aliases keep their names.
+    let input = r#"SELECT * FROM (SELECT "a", "b" FROM "t") t (x)"#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+    let top = plan.get_top().unwrap();
+    assert_eq!(plan.get_relational_aliases(top).unwrap(), vec!["x", "b"]);
+
+    let metadata = &RouterConfigurationMock::new();
+    let input = r#"SELECT * FROM (VALUES (1)) AS t (a, b)"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        "unexpected number of values: subquery \"t\" has 1 columns available but 2 columns specified",
+        err.to_string()
+    );
+
+    let input = r#"SELECT * FROM "t" AS t (x)"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        "unsupported query: column aliases of tables and CTEs",
+        err.to_string()
+    );
+}
+
+#[test]
+fn front_sql_scram_auth_method() {
+    use crate::ir::acl::{AlterOption, AuthMethod};
+    use crate::ir::node::acl::Acl;
+    use crate::ir::node::{AlterUser, CreateUser};
+
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(
+        r#"create user "emir" with password 'vildanov' using scram-sha-256"#,
+        metadata,
+    )
+    .unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Acl::CreateUser(CreateUser { auth_method, .. }) = plan.get_acl_node(top_id).unwrap() else {
+        panic!("expected create user")
+    };
+    assert_eq!(auth_method, &AuthMethod::ScramSha256);
+
+    let plan = AbstractSyntaxTree::transform_into_plan(
+        r#"alter user "emir" password 'vildanov' using SCRAM-SHA-256"#,
+        metadata,
+    )
+    .unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Acl::AlterUser(AlterUser { alter_option, .. }) = plan.get_acl_node(top_id).unwrap() else {
+        panic!("expected alter user")
+    };
+    assert_eq!(
+        alter_option,
+        &AlterOption::Password {
+            password: "vildanov".into(),
+            auth_method: AuthMethod::ScramSha256,
+        }
+    );
+}
+
+#[test]
+fn front_sql_alter_user_statement_timeout() {
+    use crate::ir::acl::AlterOption;
+    use crate::ir::node::acl::Acl;
+    use crate::ir::node::AlterUser;
+
+    let metadata = &RouterConfigurationMock::new();
+    let alter_option = |query: &str| {
+        let plan = AbstractSyntaxTree::transform_into_plan(query, metadata)?;
+        let top_id = plan.get_top().unwrap();
+        let Acl::AlterUser(AlterUser { alter_option, .. }) = plan.get_acl_node(top_id).unwrap()
+        else {
+            panic!("expected alter user")
+        };
+        Ok::<_, SbroadError>(alter_option.clone())
+    };
+
+    assert_eq!(
+        alter_option(r#"alter user "emir" set statement_timeout = '5s'"#).unwrap(),
+        AlterOption::StatementTimeout { millis: Some(5000) }
+    );
+    assert_eq!(
+        alter_option(r#"alter user "emir" set statement_timeout to 100"#).unwrap(),
+        AlterOption::StatementTimeout { millis: Some(100) }
+    );
+    assert_eq!(
+        alter_option(r#"alter user "emir" set statement_timeout to default"#).unwrap(),
+        AlterOption::StatementTimeout { millis: None }
+    );
+    let err = alter_option(r#"alter user "emir" set search_path = s"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "unsupported option: ALTER USER supports only statement_timeout parameter, got search_path"
+    );
+}
+
 #[test]
 fn front_sql_whitespaces_are_not_ignored() {
     // Deletion of any WHITESPACE in those query will transform
@@ -4070,16 +4275,21 @@ fn syntax_error(query: &str) -> impl '_ + FnOnce(SbroadError) {
 
 mod coalesce;
 mod cte;
+mod datetime;
 mod ddl;
 mod funcs;
 mod global;
 mod insert;
 mod join;
+mod json;
 mod like;
 mod limit;
 mod params;
+mod schema;
 mod single;
 mod subtree_cloner;
 mod trim;
 mod union;
 mod update;
+mod varbinary;
+mod view;
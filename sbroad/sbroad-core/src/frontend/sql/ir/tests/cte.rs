@@ -425,3 +425,83 @@ fn cte_with_left_join() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn recursive_cte() {
+    let sql = r#"
+        WITH RECURSIVE cte (a) AS (
+            SELECT "a" FROM "global_t"
+            UNION ALL
+            SELECT "a" + 1 FROM cte WHERE "a" < 10
+        )
+        SELECT * FROM cte
+    "#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("cte"."a"::integer -> "a")
+        scan cte cte($0)
+    subquery $0:
+    limit 5001
+                union all
+                    projection ("global_t"."a"::integer -> "a")
+                        scan "global_t"
+                    projection (ROW("cte"."a"::integer) + ROW(1::unsigned) -> "col_1")
+                        selection ROW("cte"."a"::integer) < ROW(10::unsigned)
+                            scan recursive cte cte
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn recursive_cte_row_max() {
+    let sql = r#"
+        WITH RECURSIVE cte (a) AS (
+            SELECT 1
+            UNION
+            SELECT "a" + 1 FROM cte WHERE "a" < 10
+        )
+        SELECT * FROM cte
+        OPTION (SQL_RECURSION_ROW_MAX = 10)
+    "#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("cte"."a"::unsigned -> "a")
+        scan cte cte($0)
+    subquery $0:
+    limit 11
+                union
+                    projection (1::unsigned -> "a")
+                    projection (ROW("cte"."a"::unsigned) + ROW(1::unsigned) -> "col_1")
+                        selection ROW("cte"."a"::unsigned) < ROW(10::unsigned)
+                            scan recursive cte cte
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn recursive_cte_over_sharded_table() {
+    let sql = r#"
+        WITH RECURSIVE cte (a) AS (
+            SELECT "a" FROM "t"
+            UNION ALL
+            SELECT "a" + 1 FROM cte WHERE "a" < 10
+        )
+        SELECT * FROM cte
+    "#;
+    let metadata = &RouterConfigurationMock::new();
+    let mut plan = AbstractSyntaxTree::transform_into_plan(sql, metadata).unwrap();
+    let err = plan.add_motions().unwrap_err();
+    assert_eq!(
+        err,
+        SbroadError::Unsupported(
+            Entity::Cte,
+            Some("recursive CTE is supported only over global tables".into())
+        )
+    );
+}
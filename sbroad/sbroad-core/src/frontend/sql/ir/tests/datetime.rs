@@ -0,0 +1,65 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
+use crate::ir::node::{LocalTimestamp, Node64};
+use crate::ir::transformation::helpers::{sql_to_ir, sql_to_optimized_ir};
+
+#[test]
+fn datetime_interval_arithmetic() {
+    let input = r#"select ts + interval '1 day 2 hours', ts - ts, duration + duration from events where duration > interval '1 hour'"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection (ROW("events"."ts"::datetime) + ROW('1 day 02:00:00'::interval) -> "col_1", ROW("events"."ts"::datetime) - ROW("events"."ts"::datetime) -> "col_2", ROW("events"."duration"::interval) + ROW("events"."duration"::interval) -> "col_3")
+        selection ROW("events"."duration"::interval) > ROW('01:00:00'::interval)
+            scan "events"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn datetime_functions() {
+    let input = r#"select date_trunc('hour', ts), extract(YEAR from ts), date_part('epoch', duration), age(ts, ts) from events"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("date_trunc"(('hour'::string, "events"."ts"::datetime))::datetime -> "col_1", "date_part"(('year'::string, "events"."ts"::datetime))::double -> "col_2", "date_part"(('epoch'::string, "events"."duration"::interval))::double -> "col_3", "age"(("events"."ts"::datetime, "events"."ts"::datetime))::interval -> "col_4")
+        scan "events"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn datetime_current_timestamp() {
+    let input = r#"select now(), current_timestamp, localtimestamp from events"#;
+
+    let plan = sql_to_ir(input, vec![]);
+
+    let with_time_zone: Vec<bool> = plan
+        .nodes
+        .iter64()
+        .filter_map(|node| match node {
+            Node64::LocalTimestamp(LocalTimestamp { with_time_zone, .. }) => Some(*with_time_zone),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(with_time_zone, vec![true, true, false]);
+}
+
+#[test]
+fn interval_errors() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"select interval 'abc'"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        r#"value parsing error: invalid input syntax for type interval: "abc""#,
+        err.to_string()
+    );
+}
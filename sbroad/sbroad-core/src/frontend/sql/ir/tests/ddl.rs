@@ -1,6 +1,9 @@
 use crate::{
     frontend::Ast,
-    ir::node::{ddl::Ddl, CreateTable},
+    ir::node::{
+        ddl::Ddl, AlterTable, AlterTableOp, Analyze, CreatePartition, CreateSequence, CreateTable,
+        CreateTableAs, CreateView, DropSequence, DropView,
+    },
 };
 use pretty_assertions::assert_eq;
 use smol_str::{SmolStr, ToSmolStr};
@@ -8,7 +11,11 @@ use smol_str::{SmolStr, ToSmolStr};
 use crate::{
     executor::engine::mock::RouterConfigurationMock,
     frontend::sql::ast::AbstractSyntaxTree,
-    ir::{ddl::ColumnDef, relation::Type},
+    ir::{
+        ddl::{ColumnDef, PartitionBound, PartitionBy, PartitionStrategy, TableConstraint},
+        relation::Type,
+        value::Value,
+    },
 };
 
 #[test]
@@ -33,6 +40,9 @@ fn infer_not_null_on_pk1() {
         name: "a".into(),
         data_type: Type::Integer,
         is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     assert_eq!(format, &vec![def]);
@@ -64,18 +74,27 @@ fn infer_not_null_on_pk2() {
         name: "a".into(),
         data_type: Type::Integer,
         is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     let def_b = ColumnDef {
         name: "b".into(),
         data_type: Type::Integer,
         is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     let def_c = ColumnDef {
         name: "c".into(),
         data_type: Type::Integer,
         is_nullable: true,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     assert_eq!(format, &vec![def_a, def_b, def_c]);
@@ -134,30 +153,45 @@ fn infer_alias_int2_int4_int8_bigint_smallint() {
         name: "a".into(),
         data_type: Type::Integer,
         is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     let def_b = ColumnDef {
         name: "b".into(),
         data_type: Type::Integer,
         is_nullable: true,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     let def_c = ColumnDef {
         name: "c".into(),
         data_type: Type::Integer,
         is_nullable: true,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     let def_d = ColumnDef {
         name: "d".into(),
         data_type: Type::Integer,
         is_nullable: true,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     let def_e = ColumnDef {
         name: "e".into(),
         data_type: Type::Integer,
         is_nullable: true,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     assert_eq!(format, &vec![def_a, def_b, def_c, def_d, def_e]);
@@ -179,6 +213,9 @@ fn infer_alias_numeric_two_parameters() {
         name: "a".into(),
         data_type: Type::Decimal,
         is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     assert_eq!(format, &vec![def_a]);
@@ -200,6 +237,9 @@ fn infer_alias_numeric_one_parameter() {
         name: "a".into(),
         data_type: Type::Decimal,
         is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     assert_eq!(format, &vec![def_a]);
@@ -221,7 +261,685 @@ fn infer_alias_numeric_no_parameters() {
         name: "a".into(),
         data_type: Type::Decimal,
         is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
     };
 
     assert_eq!(format, &vec![def_a]);
 }
+
+#[test]
+fn column_default() {
+    let input = r#"create table t (a int primary key, b int default 1 + 2 not null, c datetime default localtimestamp)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateTable(CreateTable { format, .. }) = top_node else {
+        panic!("expected create table")
+    };
+
+    let def_a = ColumnDef {
+        name: "a".into(),
+        data_type: Type::Integer,
+        is_nullable: false,
+        default: None,
+        sequence: None,
+        generated_always: false,
+    };
+
+    let def_b = ColumnDef {
+        name: "b".into(),
+        data_type: Type::Integer,
+        is_nullable: false,
+        default: Some("1 + 2".into()),
+        sequence: None,
+        generated_always: false,
+    };
+
+    let def_c = ColumnDef {
+        name: "c".into(),
+        data_type: Type::Datetime,
+        is_nullable: true,
+        default: Some("localtimestamp".into()),
+        sequence: None,
+        generated_always: false,
+    };
+
+    assert_eq!(format, &vec![def_a, def_b, def_c]);
+}
+
+#[test]
+fn column_default_forbidden_expressions() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected) in [
+        (
+            r#"create table t (a int primary key, b int default a)"#,
+            "unsupported expression: column references in column DEFAULT expression",
+        ),
+        (
+            r#"create table t (a int primary key, b int default ?)"#,
+            "unsupported expression: parameters in column DEFAULT expression",
+        ),
+        (
+            r#"create table t (a int primary key, b int default (select 1))"#,
+            "unsupported expression: subqueries in column DEFAULT expression",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+#[test]
+fn table_constraints() {
+    let input = r#"create table "T" (
+        a int primary key,
+        "B" int check ("B" > 0 and a <> "B") references p,
+        c text constraint c_not_empty check (length(C) > 0),
+        d int,
+        check (a < 10),
+        check (a > 0),
+        foreign key (a, d) references s.p2 (x, y)
+    ) distributed by (a)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateTable(CreateTable { constraints, .. }) = top_node else {
+        panic!("expected create table")
+    };
+
+    let expected = vec![
+        TableConstraint::Check {
+            name: "T_check".into(),
+            expr: r#""B" > 0 and "a" <> "B""#.into(),
+        },
+        TableConstraint::ForeignKey {
+            name: "T_B_fkey".into(),
+            columns: vec!["B".into()],
+            parent: "p".into(),
+            parent_columns: vec![],
+        },
+        TableConstraint::Check {
+            name: "c_not_empty".into(),
+            expr: r#"length("c") > 0"#.into(),
+        },
+        TableConstraint::Check {
+            name: "T_a_check".into(),
+            expr: r#""a" < 10"#.into(),
+        },
+        TableConstraint::Check {
+            name: "T_a_check1".into(),
+            expr: r#""a" > 0"#.into(),
+        },
+        TableConstraint::ForeignKey {
+            name: "T_a_d_fkey".into(),
+            columns: vec!["a".into(), "d".into()],
+            parent: "s.p2".into(),
+            parent_columns: vec!["x".into(), "y".into()],
+        },
+    ];
+    assert_eq!(constraints, &expected);
+}
+
+#[test]
+fn table_constraints_errors() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected) in [
+        (
+            r#"create table t (a int primary key, check (b > 0))"#,
+            "invalid column: Constraint t_b_check column b not found.",
+        ),
+        (
+            r#"create table t (a int primary key, foreign key (b) references p)"#,
+            "invalid column: Constraint t_b_fkey column b not found.",
+        ),
+        (
+            r#"create table t (a int primary key, b int, foreign key (a, b) references p (x))"#,
+            "invalid DDL: number of referencing and referenced columns for foreign key disagree.",
+        ),
+        (
+            r#"create table t (a int primary key constraint c check (a > 0), constraint c check (a < 5))"#,
+            "invalid DDL: constraint c is specified more than once.",
+        ),
+        (
+            r#"create table t (a int primary key check (a > ?))"#,
+            "unsupported expression: parameters in CHECK constraint",
+        ),
+        (
+            r#"create table t (a int primary key check (a in (select 1)))"#,
+            "unsupported expression: subqueries in CHECK constraint",
+        ),
+        (
+            r#"create table t (a int primary key check (t.a > 0))"#,
+            "unsupported expression: qualified column references in CHECK constraint",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+#[test]
+fn serial_and_identity_columns() {
+    let input = r#"create table "T" (a serial primary key, b bigint generated by default as identity, c int generated always as identity)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateTable(CreateTable { format, .. }) = top_node else {
+        panic!("expected create table")
+    };
+
+    let def_a = ColumnDef {
+        name: "a".into(),
+        data_type: Type::Integer,
+        is_nullable: false,
+        default: Some(r#"nextval('"T_a_seq"')"#.into()),
+        sequence: Some("T_a_seq".into()),
+        generated_always: false,
+    };
+
+    let def_b = ColumnDef {
+        name: "b".into(),
+        data_type: Type::Integer,
+        is_nullable: false,
+        default: Some(r#"nextval('"T_b_seq"')"#.into()),
+        sequence: Some("T_b_seq".into()),
+        generated_always: false,
+    };
+
+    let def_c = ColumnDef {
+        name: "c".into(),
+        data_type: Type::Integer,
+        is_nullable: false,
+        default: Some(r#"nextval('"T_c_seq"')"#.into()),
+        sequence: Some("T_c_seq".into()),
+        generated_always: true,
+    };
+
+    assert_eq!(format, &vec![def_a, def_b, def_c]);
+}
+
+#[test]
+fn generated_columns() {
+    let input =
+        r#"create table t (a int primary key, b int, c int generated always as (A + "b") stored)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateTable(CreateTable { format, .. }) = top_node else {
+        panic!("expected create table")
+    };
+
+    let def_c = ColumnDef {
+        name: "c".into(),
+        data_type: Type::Integer,
+        is_nullable: true,
+        default: Some(r#""a" + "b""#.into()),
+        sequence: None,
+        generated_always: true,
+    };
+    assert_eq!(format[2], def_c);
+
+    for (input, expected) in [
+        (
+            r#"create table t (a int primary key, b int generated always as (c + 1))"#,
+            "invalid column: column c referenced by generated column b not found.",
+        ),
+        (
+            r#"create table t (a int primary key, b int generated always as (a), c int generated always as (b))"#,
+            "invalid column: generated column c can't refer to generated column b.",
+        ),
+        (
+            r#"create table t (a int primary key, b int generated always as (a + ?))"#,
+            "unsupported expression: parameters in generated column expression",
+        ),
+        (
+            r#"alter table t add column b int generated always as (a + 1)"#,
+            "unsupported DDL: generated columns in ALTER TABLE ADD COLUMN",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+#[test]
+fn serial_and_identity_columns_errors() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected) in [
+        (
+            r#"create table t (a serial default 1 primary key)"#,
+            "invalid column: both default and sequence specified for column a.",
+        ),
+        (
+            r#"create table t (a int primary key, b serial null)"#,
+            "invalid column: column b backed by a sequence can't be nullable.",
+        ),
+        (
+            r#"create table t (a text generated by default as identity primary key)"#,
+            "invalid column: identity column a must be of integer type.",
+        ),
+        (
+            r#"alter table t add column b int generated by default as identity"#,
+            "unsupported DDL: identity columns in ALTER TABLE ADD COLUMN",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+#[test]
+fn create_sequence() {
+    let input = r#"create sequence if not exists s start with -5 increment by 10"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateSequence(CreateSequence {
+        name,
+        start,
+        increment,
+        if_not_exists,
+        ..
+    }) = top_node
+    else {
+        panic!("expected create sequence")
+    };
+    assert_eq!(name, "s");
+    assert_eq!((*start, *increment, *if_not_exists), (-5, 10, true));
+
+    let input = r#"create sequence s increment 0"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: sequence INCREMENT must not be zero"
+    );
+}
+
+#[test]
+fn drop_sequence() {
+    let input = r#"drop sequence if exists "S""#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::DropSequence(DropSequence {
+        name, if_exists, ..
+    }) = top_node
+    else {
+        panic!("expected drop sequence")
+    };
+    assert_eq!(name, "S");
+    assert!(*if_exists);
+}
+
+#[test]
+fn create_view() {
+    let input =
+        r#"create or replace view "V" as select a, b from t where c > 1 option (timeout = 3)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateView(CreateView {
+        name,
+        query,
+        or_replace,
+        ..
+    }) = top_node
+    else {
+        panic!("expected create view")
+    };
+    assert_eq!(name, "V");
+    assert_eq!(query, "select a, b from t where c > 1");
+    assert!(*or_replace);
+    assert_eq!(top_node.timeout().unwrap().to_string(), "3");
+
+    // Views referenced by the view query are not expanded.
+    let input = r#"create view v as select * from v_t"#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateView(CreateView { query, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create view")
+    };
+    assert_eq!(query, "select * from v_t");
+
+    let input = r#"create view v as select * from t where a = ?"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: parameters are not allowed in view query"
+    );
+}
+
+#[test]
+fn create_table_as() {
+    let input = r#"create table if not exists "T" (primary key (a, s)) distributed by (s)
+        as select a, c::text as s, d::double as f from t where b > 1 option (timeout = 3)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateTableAs(create_table_as) = top_node else {
+        panic!("expected create table as")
+    };
+    assert_eq!(create_table_as.name, "T");
+    assert_eq!(
+        create_table_as.query,
+        "select a, c::text as s, d::double as f from t where b > 1"
+    );
+    assert_eq!(create_table_as.primary_key, ["a", "s"]);
+    assert_eq!(create_table_as.sharding_key, Some(vec![SmolStr::from("s")]));
+    assert!(create_table_as.if_not_exists);
+    assert_eq!(top_node.timeout().unwrap().to_string(), "3");
+
+    let query = AbstractSyntaxTree::transform_into_plan(&create_table_as.query, metadata).unwrap();
+    let create_table = create_table_as.create_table(&query).unwrap();
+    assert_eq!(create_table.name, "T");
+    let columns: Vec<_> = create_table
+        .format
+        .iter()
+        .map(|c| (c.name.as_str(), c.data_type, c.is_nullable))
+        .collect();
+    assert_eq!(
+        columns,
+        [
+            ("a", Type::Unsigned, false),
+            ("s", Type::String, false),
+            ("f", Type::Double, true),
+        ]
+    );
+    assert_eq!(create_table.primary_key, ["a", "s"]);
+    assert_eq!(create_table.sharding_key, Some(vec![SmolStr::from("s")]));
+    assert!(create_table.wait_applied_globally);
+
+    // The first column is the primary key by default, the table
+    // is sharded by the primary key.
+    let input = r#"create table t2 as select b, a from t"#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateTableAs(create_table_as) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create table as")
+    };
+    let query = AbstractSyntaxTree::transform_into_plan(&create_table_as.query, metadata).unwrap();
+    let create_table = create_table_as.create_table(&query).unwrap();
+    assert_eq!(create_table.primary_key, ["b"]);
+    assert_eq!(create_table.sharding_key, Some(vec![SmolStr::from("b")]));
+
+    let input = r#"create table g distributed globally as values (1, 'a')"#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateTableAs(create_table_as) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create table as")
+    };
+    assert_eq!(create_table_as.sharding_key, None);
+    let query = AbstractSyntaxTree::transform_into_plan(&create_table_as.query, metadata).unwrap();
+    let create_table = create_table_as.create_table(&query).unwrap();
+    assert_eq!(create_table.primary_key, ["COLUMN_1"]);
+    assert_eq!(create_table.sharding_key, None);
+}
+
+#[test]
+fn create_table_as_errors() {
+    let metadata = &RouterConfigurationMock::new();
+    let create_table = |input: &str| {
+        let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Ddl::CreateTableAs(create_table_as) = plan.get_ddl_node(top_id).unwrap() else {
+            panic!("expected create table as")
+        };
+        let query =
+            AbstractSyntaxTree::transform_into_plan(&create_table_as.query, metadata).unwrap();
+        create_table_as
+            .create_table(&query)
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(
+        create_table(r#"create table t2 as select a, null as n from t"#),
+        "invalid column: can't derive type of column n, cast it explicitly."
+    );
+    assert_eq!(
+        create_table(r#"create table t2 as select a, b as a from t"#),
+        "invalid column: column a is specified more than once."
+    );
+    assert_eq!(
+        create_table(r#"create table t2 (primary key (e)) as select a from t"#),
+        "invalid column: Primary key column e not found."
+    );
+    assert_eq!(
+        create_table(r#"create table t2 distributed by (e) as select a from t"#),
+        "invalid column: Sharding key column e not found."
+    );
+}
+
+#[test]
+fn create_table_as_with_parameters() {
+    let input = r#"create table t2 as select * from t where a = ?"#;
+    let metadata = &RouterConfigurationMock::new();
+    let mut plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    plan.bind_ddl_params(vec![Value::from(1_u64)]).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateTableAs(create_table_as) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create table as")
+    };
+    assert_eq!(create_table_as.query, "select * from t where a = ?");
+    assert_eq!(create_table_as.params, [Value::from(1_u64)]);
+}
+
+#[test]
+fn select_into() {
+    let input = r#"select a, s into table t2 from t where a > 1 order by a"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateTableAs(create_table_as) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create table as")
+    };
+    assert_eq!(create_table_as.name, "t2");
+    assert_eq!(
+        create_table_as.query,
+        "SELECT a, s from t where a > 1 order by a"
+    );
+    assert_eq!(create_table_as.sharding_key, Some(vec![]));
+
+    let query = AbstractSyntaxTree::transform_into_plan(&create_table_as.query, metadata).unwrap();
+    let create_table = create_table_as.create_table(&query).unwrap();
+    assert_eq!(create_table.primary_key, ["a"]);
+
+    // Without INTO it is still a plain query.
+    let plan = AbstractSyntaxTree::transform_into_plan("select a from t", metadata).unwrap();
+    assert!(!plan.is_ddl().unwrap());
+}
+
+#[test]
+fn drop_view() {
+    let input = r#"drop view if exists v"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::DropView(DropView {
+        name, if_exists, ..
+    }) = top_node
+    else {
+        panic!("expected drop view")
+    };
+    assert_eq!(name, "v");
+    assert!(*if_exists);
+}
+
+#[test]
+fn create_partitioned_table() {
+    let input = r#"create table t (a int, b datetime, primary key (a, b)) partition by range (b)"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let top_node = plan.get_ddl_node(top_id).unwrap();
+
+    let Ddl::CreateTable(CreateTable { partition_by, .. }) = top_node else {
+        panic!("expected create table")
+    };
+    assert_eq!(
+        partition_by,
+        &Some(PartitionBy {
+            strategy: PartitionStrategy::Range,
+            column: "b".into(),
+        })
+    );
+
+    let input = r#"create table t (a int, b int, primary key (a, b)) partition by hash (b)"#;
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateTable(CreateTable { partition_by, .. }) = plan.get_ddl_node(top_id).unwrap()
+    else {
+        panic!("expected create table")
+    };
+    assert_eq!(
+        partition_by,
+        &Some(PartitionBy {
+            strategy: PartitionStrategy::Hash,
+            column: "b".into(),
+        })
+    );
+
+    for (input, expected) in [
+        (
+            r#"create table t (a int primary key, b int) partition by list (b)"#,
+            "invalid primary key: Primary key must contain the partition key column b.",
+        ),
+        (
+            r#"create table t (a int primary key, b int) partition by list (c)"#,
+            "invalid column: Partition key column c not found.",
+        ),
+        (
+            r#"create table t (a int, b int, primary key (a, b)) partition by range (a, b)"#,
+            "DDL partition key with several columns not implemented",
+        ),
+        (
+            r#"create table t (a int, b int, primary key (a, b)) distributed globally partition by list (b)"#,
+            "DDL partitioning of global tables not implemented",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+#[test]
+fn create_partition() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected) in [
+        (
+            r#"create table p partition of t for values from (10) to (maxvalue)"#,
+            PartitionBound::Range {
+                from: Some(Value::from(10_i64)),
+                to: None,
+            },
+        ),
+        (
+            r#"create table p partition of t for values in ('x', 'y')"#,
+            PartitionBound::List {
+                values: vec![Value::from("x"), Value::from("y")],
+            },
+        ),
+        (
+            r#"create table p partition of t default"#,
+            PartitionBound::Default,
+        ),
+        (
+            r#"create table p partition of t for values with (modulus 4, remainder 3)"#,
+            PartitionBound::Hash {
+                modulus: 4,
+                remainder: 3,
+            },
+        ),
+    ] {
+        let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Ddl::CreatePartition(CreatePartition {
+            name,
+            parent,
+            bound,
+            ..
+        }) = plan.get_ddl_node(top_id).unwrap()
+        else {
+            panic!("expected create partition")
+        };
+        assert_eq!(name, "p");
+        assert_eq!(parent, "t");
+        assert_eq!(bound, &expected);
+    }
+
+    for (input, expected) in [
+        (
+            r#"create table p partition of t for values from (1 + 2) to (10)"#,
+            "unsupported expression: partition bounds other than literals",
+        ),
+        (
+            r#"create table p partition of t for values from (1, 2) to (10, 20)"#,
+            "DDL partition bounds with several values not implemented",
+        ),
+        (
+            r#"create table p partition of t for values with (modulus 2.5, remainder 0)"#,
+            "invalid value: modulus for hash partition must be a non-negative integer, got 2.5",
+        ),
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+        assert_eq!(err.to_string(), expected);
+    }
+}
+
+#[test]
+fn detach_partition() {
+    let input = r#"alter table t detach partition p"#;
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+
+    let Ddl::AlterTable(AlterTable { name, op, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected alter table")
+    };
+    assert_eq!(name, "t");
+    assert_eq!(
+        op,
+        &AlterTableOp::DetachPartition {
+            partition: "p".into()
+        }
+    );
+}
+
+#[test]
+fn analyze() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected_table) in [
+        (r#"analyze"#, None),
+        (r#"analyze hash_testing"#, Some("hash_testing")),
+        (r#"analyze s.t option (timeout = 1)"#, Some("s.t")),
+    ] {
+        let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Ddl::Analyze(Analyze { table, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+            panic!("expected analyze")
+        };
+        assert_eq!(table.as_deref(), expected_table);
+    }
+
+    assert!(AbstractSyntaxTree::transform_into_plan(r#"analyze unknown_table"#, metadata).is_err());
+}
@@ -49,3 +49,43 @@ fn insert3() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn insert_column_defaults() {
+    let pattern = r#"INSERT INTO "t_def"("a") VALUES (1)"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+    let explain = plan.as_explain().unwrap();
+
+    assert!(explain.contains(r#"insert "t_def" on conflict: fail"#));
+    assert!(explain.contains(r#"42::unsigned -> "b""#));
+    assert!(explain.contains(r#"ROW('x'::string) || ROW('y'::string) -> "c""#));
+}
+
+#[test]
+fn insert_column_defaults_explicit() {
+    // Explicitly listed columns must not be overridden by their defaults.
+    let pattern = r#"INSERT INTO "t_def"("a", "b", "c") VALUES (1, 2, 'z')"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    insert "t_def" on conflict: fail
+        motion [policy: segment([ref("COLUMN_1")])]
+            values
+                value row (data=ROW(1::unsigned, 2::unsigned, 'z'::string))
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn insert_generated_column() {
+    // The omitted column referred by the generated one is NULL.
+    let pattern = r#"INSERT INTO "t_gen"("a") VALUES (1)"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+    let explain = plan.as_explain().unwrap();
+
+    assert!(explain.contains(r#"insert "t_gen" on conflict: fail"#));
+    assert!(explain.contains(r#"-> "b""#));
+    assert!(explain.contains(r#"-> "d")"#));
+}
@@ -1,3 +1,6 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
 use crate::ir::transformation::helpers::sql_to_optimized_ir;
 
 #[test]
@@ -122,3 +125,111 @@ fn milti_join4() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn right_join1() {
+    let input = r#"SELECT * FROM "t1_2" "t1" RIGHT JOIN "t2" ON "t1"."a" = "t2"."e""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    // Inner child is preserved by the right join, so the outer one is broadcasted.
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::integer -> "a", "t1"."b"::integer -> "b", "t2"."e"::unsigned -> "e", "t2"."f"::unsigned -> "f", "t2"."g"::unsigned -> "g", "t2"."h"::unsigned -> "h")
+        right join on ROW("t1"."a"::integer) = ROW("t2"."e"::unsigned)
+            motion [policy: full]
+                scan "t1"
+                    projection ("t1"."a"::integer -> "a", "t1"."b"::integer -> "b")
+                        scan "t1_2" -> "t1"
+            scan "t2"
+                projection ("t2"."e"::unsigned -> "e", "t2"."f"::unsigned -> "f", "t2"."g"::unsigned -> "g", "t2"."h"::unsigned -> "h")
+                    scan "t2"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn right_join2() {
+    let input = r#"SELECT "t1"."a" FROM "t1" JOIN "t1" as "t2" ON "t1"."a" = "t2"."a"
+    RIGHT OUTER JOIN "t3" ON "t1"."a" = "t3"."a"
+"#;
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+
+    assert_eq!(
+        "unsupported operator: right join after another join, use left join instead",
+        err.to_string()
+    );
+}
+
+#[test]
+fn full_join1() {
+    let input =
+        r#"SELECT "t1"."a", "t2"."e" FROM "t1_2" "t1" FULL JOIN "t2" ON "t1"."a" = "t2"."e""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    // Full join is executed as a left one, the inner rows without
+    // a match are added by the motion above the join. The marker
+    // tells the inner rows from the padding of the outer ones.
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("a"::integer -> "a", "e"::unsigned -> "e")
+        motion [policy: full]
+            projection ("t1"."a"::integer -> "a", "t1"."b"::integer -> "b", "t2"."e"::unsigned -> "e", "t2"."f"::unsigned -> "f", "t2"."g"::unsigned -> "g", "t2"."h"::unsigned -> "h", "t2"."full_join_marker"::boolean -> "full_join_marker")
+                full join on ROW("t1"."a"::integer) = ROW("t2"."e"::unsigned)
+                    scan "t1"
+                        projection ("t1"."a"::integer -> "a", "t1"."b"::integer -> "b")
+                            scan "t1_2" -> "t1"
+                    scan "t2"
+                        projection ("t2"."e"::unsigned -> "e", "t2"."f"::unsigned -> "f", "t2"."g"::unsigned -> "g", "t2"."h"::unsigned -> "h", true::boolean -> "full_join_marker")
+                            motion [policy: full]
+                                scan "t2"
+                                    projection ("t2"."e"::unsigned -> "e", "t2"."f"::unsigned -> "f", "t2"."g"::unsigned -> "g", "t2"."h"::unsigned -> "h")
+                                        scan "t2"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn cross_join1() {
+    let input = r#"SELECT * FROM "t1_2" "t1" CROSS JOIN "t4""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::integer -> "a", "t1"."b"::integer -> "b", "t4"."c"::string -> "c", "t4"."d"::integer -> "d")
+        join on true::boolean
+            scan "t1"
+                projection ("t1"."a"::integer -> "a", "t1"."b"::integer -> "b")
+                    scan "t1_2" -> "t1"
+            motion [policy: full]
+                scan "t4"
+                    projection ("t4"."c"::string -> "c", "t4"."d"::integer -> "d")
+                        scan "t4"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn comma_join1() {
+    let input = r#"SELECT "t1"."a", "t4"."c" FROM "t1_2" "t1", "t4" WHERE "t1"."a" = "t4"."d""#;
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("t1"."a"::integer -> "a", "t4"."c"::string -> "c")
+        selection ROW("t1"."a"::integer) = ROW("t4"."d"::integer)
+            join on true::boolean
+                scan "t1"
+                    projection ("t1"."a"::integer -> "a", "t1"."b"::integer -> "b")
+                        scan "t1_2" -> "t1"
+                motion [policy: full]
+                    scan "t4"
+                        projection ("t4"."c"::string -> "c", "t4"."d"::integer -> "d")
+                            scan "t4"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
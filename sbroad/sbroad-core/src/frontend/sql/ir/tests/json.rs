@@ -0,0 +1,113 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
+use crate::ir::transformation::helpers::sql_to_optimized_ir;
+
+#[test]
+fn json_operators() {
+    let input = r#"select doc -> 'a', doc ->> 'a', doc -> 'a' -> 0, doc #> '{a,b}', doc #>> '{a,b}', doc @> '{"a": 1}' from docs"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("json_get"(("docs"."doc"::map, 'a'::string))::any -> "col_1", "json_get_text"(("docs"."doc"::map, 'a'::string))::string -> "col_2", "json_get"(("json_get"(("docs"."doc"::map, 'a'::string))::any, 0::unsigned))::any -> "col_3", "json_get_path"(("docs"."doc"::map, '{a,b}'::string))::any -> "col_4", "json_get_path_text"(("docs"."doc"::map, '{a,b}'::string))::string -> "col_5", "json_contains"(("docs"."doc"::map, '{"a": 1}'::string))::boolean -> "col_6")
+        scan "docs"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn json_operators_precedence() {
+    let input = r#"select doc ->> 'a' || 'b', (doc ->> 'n')::int + 1 from docs"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection (ROW("json_get_text"(("docs"."doc"::map, 'a'::string))::string) || ROW('b'::string) -> "col_1", ROW("json_get_text"(("docs"."doc"::map, 'n'::string))::string::int) + ROW(1::unsigned) -> "col_2")
+        scan "docs"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn json_variadic_functions() {
+    let input = r#"select json_extract_path_text(doc, 'a', 'b'), json_build_object('id', id, 'doc', doc), json_build_array(id, 'x') from docs"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("json_extract_path_text"(("json_extract_path"(("docs"."doc"::map, 'a'::string))::any, 'b'::string))::string -> "col_1", "json_object_set"(("json_object_set"(("json_object_new"(())::map, 'id'::string, "docs"."id"::unsigned))::map, 'doc'::string, "docs"."doc"::map))::map -> "col_2", "json_array_append"(("json_array_append"(("json_array_new"(())::array, "docs"."id"::unsigned))::array, 'x'::string))::array -> "col_3")
+        scan "docs"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn json_errors() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"select json_build_object('a') from docs"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        "invalid query: json_build_object function requires an even number of arguments",
+        err.to_string()
+    );
+
+    let input = r#"select json_extract_path(doc) from docs"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        "invalid query: json_extract_path function requires a JSON value and at least one path element",
+        err.to_string()
+    );
+
+    let input = r#"select json_array_elements(doc) + 1 from docs"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        "invalid query: set-returning functions are allowed only in the select list of the top-level query without DISTINCT, ORDER BY and LIMIT",
+        err.to_string()
+    );
+
+    let input = r#"select id from docs where id in (select jsonb_array_elements(doc) from docs)"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        "invalid query: set-returning functions are allowed only in the select list of the top-level query without DISTINCT, ORDER BY and LIMIT",
+        err.to_string()
+    );
+}
+
+#[test]
+fn json_build_without_arguments() {
+    let input = r#"select json_build_object(), json_build_array() from docs"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("json_object_new"(())::map -> "col_1", "json_array_new"(())::array -> "col_2")
+        scan "docs"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn json_array_elements() {
+    let input = r#"select id, jsonb_array_elements(doc -> 'tags') as tag from docs"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    motion [policy: full]
+        projection ("docs"."id"::unsigned -> "id", "json_array_elements"(("json_get"(("docs"."doc"::map, 'tags'::string))::any))::any -> "tag")
+            scan "docs"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
@@ -167,3 +167,85 @@ fn limit_null() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn limit_offset() {
+    let sql = r#"SELECT "id" FROM "test_space" LIMIT 10 OFFSET 5"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    // Storages return limit + offset rows, offset is applied on the router.
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    limit 10 offset 5
+        motion [policy: full]
+            limit 15
+                projection ("test_space"."id"::unsigned -> "id")
+                    scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn offset_without_limit() {
+    let sql = r#"SELECT "id" FROM "test_space" OFFSET 5 ROWS"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    offset 5
+        motion [policy: full]
+            projection ("test_space"."id"::unsigned -> "id")
+                scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn offset_zero() {
+    let sql = r#"SELECT "id" FROM "test_space" OFFSET 0"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection ("test_space"."id"::unsigned -> "id")
+        scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn fetch_first() {
+    let sql = r#"SELECT "id" FROM "test_space" OFFSET 20 ROWS FETCH FIRST 10 ROWS ONLY"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    limit 10 offset 20
+        motion [policy: full]
+            limit 30
+                projection ("test_space"."id"::unsigned -> "id")
+                    scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn fetch_next_row() {
+    let sql = r#"SELECT "id" FROM "test_space" FETCH NEXT ROW ONLY"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    limit 1
+        motion [policy: full]
+            limit 1
+                projection ("test_space"."id"::unsigned -> "id")
+                    scan "test_space"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
@@ -0,0 +1,276 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::sql::schema::resolve_relation_name;
+use crate::frontend::Ast;
+use crate::ir::acl::{GrantRevokeType, Privilege};
+use crate::ir::ddl::SetParamValue;
+use crate::ir::node::acl::Acl;
+use crate::ir::node::ddl::Ddl;
+use crate::ir::node::{CreateSchema, CreateView, DropSchema, GrantPrivilege, SetParam};
+use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::Plan;
+use pretty_assertions::assert_eq;
+use smol_str::SmolStr;
+
+fn sql_to_optimized_ir_with_search_path(query: &str, search_path: &[&str]) -> Plan {
+    let mut metadata = RouterConfigurationMock::new();
+    metadata.set_search_path(search_path);
+    let mut plan = AbstractSyntaxTree::transform_into_plan(query, &metadata).unwrap();
+    plan.optimize().unwrap();
+    plan
+}
+
+#[test]
+fn qualified_table_name() {
+    let plan = sql_to_optimized_ir(r#"select t.a from s.t where b = 1"#, vec![]);
+    let expected = sql_to_optimized_ir(r#"select t.a from "s.t" as "t" where b = 1"#, vec![]);
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+
+    // Explicit alias is kept.
+    let plan = sql_to_optimized_ir(r#"select x.a from s.t as x"#, vec![]);
+    let expected = sql_to_optimized_ir(r#"select x.a from "s.t" as x"#, vec![]);
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn public_schema_is_default() {
+    let plan = sql_to_optimized_ir(r#"select a from public.t"#, vec![]);
+    let expected = sql_to_optimized_ir(r#"select a from t"#, vec![]);
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn search_path_resolution() {
+    let plan = sql_to_optimized_ir_with_search_path(
+        r#"select t.a from t join t1 on t.a = t1.a"#,
+        &["s", "public"],
+    );
+    let expected = sql_to_optimized_ir(
+        r#"select t.a from "s.t" as "t" join "s.t1" as "t1" on t.a = t1.a"#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+
+    // Tables missing in the schemas of the search path are looked up in public.
+    let plan = sql_to_optimized_ir_with_search_path(r#"select * from hash_testing"#, &["s"]);
+    let expected = sql_to_optimized_ir(r#"select * from hash_testing"#, vec![]);
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+
+    // CTEs shadow the tables.
+    let plan = sql_to_optimized_ir_with_search_path(
+        r#"with t as (select 1 as a) select a from t"#,
+        &["s", "public"],
+    );
+    let expected = sql_to_optimized_ir(r#"with t as (select 1 as a) select a from t"#, vec![]);
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn relation_name_resolution() {
+    let mut metadata = RouterConfigurationMock::new();
+    metadata.set_search_path(&["s", "public"]);
+    for (input, expected) in [
+        (r#"t"#, "s.t"),
+        (r#"s.t"#, "s.t"),
+        (r#"public.t"#, "t"),
+        (r#""S"."T""#, "S.T"),
+        (r#"hash_testing"#, "hash_testing"),
+    ] {
+        assert_eq!(resolve_relation_name(&metadata, input).unwrap(), expected);
+    }
+}
+
+#[test]
+fn view_in_schema() {
+    let plan = sql_to_optimized_ir(r#"select a from s.v_t"#, vec![]);
+    assert_eq!(plan.views.referenced, vec!["s.v_t"]);
+    assert!(plan.views.hidden_tables.contains("s.t"));
+}
+
+#[test]
+fn view_query_is_resolved() {
+    let mut metadata = RouterConfigurationMock::new();
+    metadata.set_search_path(&["s", "public"]);
+    let plan =
+        AbstractSyntaxTree::transform_into_plan(r#"create view s.v as select a from t"#, &metadata)
+            .unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateView(CreateView { name, query, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+        panic!("expected create view")
+    };
+    assert_eq!(name, "s.v");
+    // The view query doesn't depend on the search path of the session using it.
+    assert_eq!(query, r#"select a from "s.t" as "t""#);
+}
+
+#[test]
+fn create_schema() {
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(
+        r#"create schema if not exists "S" option (timeout = 3)"#,
+        metadata,
+    )
+    .unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Ddl::CreateSchema(CreateSchema {
+        name,
+        if_not_exists,
+        ..
+    }) = plan.get_ddl_node(top_id).unwrap()
+    else {
+        panic!("expected create schema")
+    };
+    assert_eq!(name, "S");
+    assert!(*if_not_exists);
+
+    let err =
+        AbstractSyntaxTree::transform_into_plan(r#"create schema public"#, metadata).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: schema public already exists"
+    );
+}
+
+#[test]
+fn drop_schema() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected_cascade) in [
+        (r#"drop schema if exists s"#, false),
+        (r#"drop schema if exists s restrict"#, false),
+        (r#"drop schema if exists s cascade"#, true),
+    ] {
+        let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Ddl::DropSchema(DropSchema {
+            name,
+            if_exists,
+            cascade,
+            ..
+        }) = plan.get_ddl_node(top_id).unwrap()
+        else {
+            panic!("expected drop schema")
+        };
+        assert_eq!(name, "s");
+        assert!(*if_exists);
+        assert_eq!(*cascade, expected_cascade);
+    }
+
+    let err =
+        AbstractSyntaxTree::transform_into_plan(r#"drop schema public"#, metadata).unwrap_err();
+    assert_eq!(err.to_string(), "unsupported query: dropping schema public");
+}
+
+#[test]
+fn set_search_path() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected) in [
+        (r#"set search_path to s, public"#, vec!["s", "public"]),
+        (r#"set search_path = 'S', "T""#, vec!["S", "T"]),
+        (r#"set search_path to default"#, vec!["public"]),
+    ] {
+        let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Ddl::SetParam(SetParam { param_value, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+            panic!("expected set param")
+        };
+        let expected: Vec<SmolStr> = expected.into_iter().map(SmolStr::from).collect();
+        assert_eq!(
+            param_value,
+            &SetParamValue::SearchPath { schemas: expected }
+        );
+    }
+
+    let err = AbstractSyntaxTree::transform_into_plan(r#"set search_path to s, 1"#, metadata)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid query: invalid value for search_path: 1"
+    );
+}
+
+#[test]
+fn set_statement_timeout() {
+    let metadata = &RouterConfigurationMock::new();
+    for (input, expected) in [
+        (r#"set statement_timeout to 1500"#, Some(1500)),
+        (r#"set statement_timeout = '2s'"#, Some(2000)),
+        (r#"set statement_timeout = '1 min'"#, Some(60_000)),
+        (r#"set statement_timeout = '250ms'"#, Some(250)),
+        (r#"set statement_timeout = 0"#, Some(0)),
+        (r#"set statement_timeout to default"#, None),
+    ] {
+        let plan = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap();
+        let top_id = plan.get_top().unwrap();
+        let Ddl::SetParam(SetParam { param_value, .. }) = plan.get_ddl_node(top_id).unwrap() else {
+            panic!("expected set param")
+        };
+        assert_eq!(
+            param_value,
+            &SetParamValue::StatementTimeout { millis: expected },
+            "{input}"
+        );
+    }
+
+    for input in [
+        r#"set statement_timeout = '5 years'"#,
+        r#"set statement_timeout = 'soon'"#,
+        r#"set statement_timeout to 1, 2"#,
+    ] {
+        let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid query: invalid value for statement_timeout"),
+            "{input}: {err}"
+        );
+    }
+}
+
+#[test]
+fn grant_on_schema() {
+    let metadata = &RouterConfigurationMock::new();
+    let plan = AbstractSyntaxTree::transform_into_plan(r#"grant usage on schema s to u"#, metadata)
+        .unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Acl::GrantPrivilege(GrantPrivilege { grant_type, .. }) = plan.get_acl_node(top_id).unwrap()
+    else {
+        panic!("expected grant privilege")
+    };
+    assert_eq!(
+        grant_type,
+        &GrantRevokeType::SpecificSchema {
+            privilege: Privilege::Usage,
+            schema_name: "s".into(),
+        }
+    );
+
+    let plan =
+        AbstractSyntaxTree::transform_into_plan(r#"grant create schema to u"#, metadata).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let Acl::GrantPrivilege(GrantPrivilege { grant_type, .. }) = plan.get_acl_node(top_id).unwrap()
+    else {
+        panic!("expected grant privilege")
+    };
+    assert_eq!(
+        grant_type,
+        &GrantRevokeType::Schema {
+            privilege: Privilege::Create,
+        }
+    );
+
+    // Usage is granted on a specific schema only.
+    assert!(
+        AbstractSyntaxTree::transform_into_plan(r#"grant usage schema to u"#, metadata).is_err()
+    );
+}
+
+#[test]
+fn rename_to_another_schema() {
+    let metadata = &RouterConfigurationMock::new();
+    let err =
+        AbstractSyntaxTree::transform_into_plan(r#"alter table s.t rename to public.t2"#, metadata)
+            .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"unsupported query: moving table to schema "public" by renaming it"#
+    );
+}
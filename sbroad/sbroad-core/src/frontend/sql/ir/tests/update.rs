@@ -34,3 +34,19 @@ fn update2() {
         sql_motion_row_max = 5000
     "#);
 }
+
+#[test]
+fn update_generated_column() {
+    // The generated column is recalculated with the new value of "b".
+    let pattern = r#"UPDATE "t_gen" SET "b" = "c" + 1"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.contains(r#""b" = "col_"#));
+    assert!(explain.contains(r#""d" = "col_"#));
+
+    // The generated column doesn't depend on "c".
+    let pattern = r#"UPDATE "t_gen" SET "c" = 1"#;
+    let plan = sql_to_optimized_ir(pattern, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(!explain.contains(r#""d" = "col_"#));
+}
@@ -0,0 +1,32 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
+use crate::ir::transformation::helpers::sql_to_optimized_ir;
+
+#[test]
+fn varbinary_literals_and_functions() {
+    let input = r#"select X'DEADBEEF', data, length(data), substring(data from 2 for 2) from bins where data = '\x00ff'::bytea"#;
+
+    let plan = sql_to_optimized_ir(input, vec![]);
+
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    projection (X'deadbeef'::varbinary -> "col_1", "bins"."data"::varbinary -> "data", length(("bins"."data"::varbinary))::integer -> "col_2", substring(("bins"."data"::varbinary, 2::unsigned, 2::unsigned))::varbinary -> "col_3")
+        selection ROW("bins"."data"::varbinary) = ROW(X'00ff'::varbinary)
+            scan "bins"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn varbinary_errors() {
+    let metadata = &RouterConfigurationMock::new();
+
+    let input = r#"select X'ABC'"#;
+    let err = AbstractSyntaxTree::transform_into_plan(input, metadata).unwrap_err();
+    assert_eq!(
+        "value parsing error: invalid hexadecimal data: ABC",
+        err.to_string()
+    );
+}
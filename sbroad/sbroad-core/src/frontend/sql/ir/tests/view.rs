@@ -0,0 +1,85 @@
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
+use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn view_expanded_as_subquery() {
+    let plan = sql_to_optimized_ir(r#"select a from v_t where b = 1"#, vec![]);
+    let expected = sql_to_optimized_ir(
+        r#"select a from (select a, b from t where c > 1) as "v_t" where b = 1"#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+
+    assert_eq!(plan.views.referenced, vec!["v_t"]);
+    assert_eq!(
+        plan.views.queries.get("v_t").map(|q| q.as_str()),
+        Some("select a, b from t where c > 1")
+    );
+    assert!(plan.views.hidden_tables.contains("t"));
+}
+
+#[test]
+fn view_with_alias() {
+    let plan = sql_to_optimized_ir(
+        r#"select x.a from public.v_t as x join t on x.a = t.a"#,
+        vec![],
+    );
+    let expected = sql_to_optimized_ir(
+        r#"select x.a from (select a, b from t where c > 1) as x join t on x.a = t.a"#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+
+    // The table is referenced by the query itself, so it is not hidden.
+    assert!(plan.views.hidden_tables.is_empty());
+}
+
+#[test]
+fn nested_views() {
+    let plan = sql_to_optimized_ir(r#"select * from v_v"#, vec![]);
+    let expected = sql_to_optimized_ir(
+        r#"select * from (select a from (select a, b from t where c > 1) as "v_t") as "v_v""#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+
+    // Only the views referenced by the query itself need privileges.
+    assert_eq!(plan.views.referenced, vec!["v_v"]);
+    assert_eq!(plan.views.queries.len(), 2);
+}
+
+#[test]
+fn cte_shadows_view() {
+    let plan = sql_to_optimized_ir(r#"with v_t as (select d from t) select * from v_t"#, vec![]);
+    assert!(plan.views.referenced.is_empty());
+}
+
+#[test]
+fn cte_does_not_shadow_view_tables() {
+    // The CTE has the same name as the table of the view query,
+    // so it is renamed not to be taken for the table.
+    let plan = sql_to_optimized_ir(
+        r#"with t as (select d from t) select v_t.a, t.d from v_t join t on true"#,
+        vec![],
+    );
+    let expected = sql_to_optimized_ir(
+        r#"with "t_1" as (select d from t) select v_t.a, t.d
+        from (select a, b from t where c > 1) as "v_t" join "t_1" as "t" on true"#,
+        vec![],
+    );
+    assert_eq!(plan.as_explain().unwrap(), expected.as_explain().unwrap());
+}
+
+#[test]
+fn recursive_view() {
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(r#"select * from v_recursive"#, metadata)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        r#"invalid query: views are nested deeper than 32 levels, probably view "v_recursive" references itself"#
+    );
+}
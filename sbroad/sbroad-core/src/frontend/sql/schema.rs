@@ -0,0 +1,236 @@
+//! Resolution of schema-qualified names.
+//!
+//! Tables and views of a schema other than `public` are stored under
+//! qualified names: table `t` of schema `s` is stored as `s.t`. Before the
+//! query is parsed into the AST, every table and view name is replaced with
+//! the name it is stored under. A qualified name is taken as is (`public.t`
+//! is `t`), while an unqualified one is looked up in the schemas of the
+//! search path: `select * from t` with the search path `s, public` becomes
+//! `select * from "s.t" as "t"` if schema `s` has table `t`.
+
+use std::collections::HashSet;
+
+use pest::iterators::Pair;
+use pest::Parser;
+use smol_str::{format_smolstr, SmolStr};
+
+use crate::errors::{Entity, SbroadError};
+use crate::executor::engine::helpers::{
+    normalize_name_from_sql, qualified_name, to_user, DEFAULT_SCHEMA,
+};
+use crate::executor::engine::Metadata;
+use crate::frontend::sql::ast::{ParseTree, Rule};
+
+enum NameKind {
+    /// Existing table or view.
+    Relation,
+    /// Table, view or CTE in the `FROM` clause.
+    Scan { has_alias: bool },
+    /// Table or view being created.
+    New,
+    /// New name of a renamed table.
+    Rename,
+}
+
+/// Table or view name met in the query.
+struct NameRef {
+    kind: NameKind,
+    schema: Option<SmolStr>,
+    name: SmolStr,
+    start: usize,
+    end: usize,
+}
+
+impl NameRef {
+    fn new(kind: NameKind, pair: &Pair<'_, Rule>) -> Self {
+        let (schema, name) = split_qualified_name(pair.as_str());
+        NameRef {
+            kind,
+            schema,
+            name,
+            start: pair.as_span().start(),
+            end: pair.as_span().end(),
+        }
+    }
+}
+
+/// Split `schema.name` into normalized schema and name.
+fn split_qualified_name(text: &str) -> (Option<SmolStr>, SmolStr) {
+    let mut quoted = false;
+    for (pos, c) in text.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '.' if !quoted => {
+                return (
+                    Some(normalize_name_from_sql(&text[..pos])),
+                    normalize_name_from_sql(&text[pos + 1..]),
+                );
+            }
+            _ => {}
+        }
+    }
+    (None, normalize_name_from_sql(text))
+}
+
+/// Collect the table and view names and the names of the CTEs.
+fn collect_names(top: Pair<'_, Rule>) -> (Vec<NameRef>, HashSet<SmolStr>) {
+    let mut names = Vec::new();
+    let mut ctes = HashSet::new();
+    let mut stack = vec![top];
+    while let Some(pair) = stack.pop() {
+        match pair.as_rule() {
+            Rule::Cte => {
+                if let Some(name) = pair.clone().into_inner().next() {
+                    ctes.insert(normalize_name_from_sql(name.as_str()));
+                }
+            }
+            Rule::Scan => {
+                let mut inner = pair.clone().into_inner();
+                if let Some(relation) = inner.next() {
+                    if relation.as_rule() == Rule::ScanCteOrTable {
+                        let has_alias = inner.next().is_some();
+                        names.push(NameRef::new(NameKind::Scan { has_alias }, &relation));
+                    }
+                }
+            }
+            Rule::Table | Rule::TableNameIdentifier => {
+                names.push(NameRef::new(NameKind::Relation, &pair));
+            }
+            Rule::NewTable => names.push(NameRef::new(NameKind::New, &pair)),
+            Rule::AlterTableRename => {
+                for name in pair.into_inner() {
+                    names.push(NameRef::new(NameKind::Rename, &name));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        stack.extend(pair.into_inner());
+    }
+    names.sort_by_key(|name| name.start);
+    (names, ctes)
+}
+
+/// Find the schema of an existing table or view.
+fn lookup_schema<M: Metadata>(
+    metadata: &M,
+    search_path: &[SmolStr],
+    name: &str,
+) -> Result<SmolStr, SbroadError> {
+    for schema in search_path {
+        let candidate = qualified_name(schema, name);
+        if metadata.table(&candidate).is_ok() || metadata.view(&candidate)?.is_some() {
+            return Ok(schema.clone());
+        }
+    }
+    // Objects of the default schema (the system tables among them)
+    // are visible even if the schema is not in the search path.
+    if metadata.table(name).is_ok() || metadata.view(name)?.is_some() {
+        return Ok(SmolStr::from(DEFAULT_SCHEMA));
+    }
+    // The missing object is reported later as missing in the first schema.
+    Ok(search_path
+        .first()
+        .cloned()
+        .unwrap_or_else(|| SmolStr::from(DEFAULT_SCHEMA)))
+}
+
+/// Resolve a table or view name written in SQL, either qualified or not,
+/// into the name it is stored under. An unqualified name is looked up in
+/// the schemas of the search path, just like in the queries.
+///
+/// # Errors
+/// - Failed to get a table or a view from the metadata.
+pub fn resolve_relation_name<M: Metadata>(
+    metadata: &M,
+    name: &str,
+) -> Result<SmolStr, SbroadError> {
+    let (schema, name) = split_qualified_name(name);
+    let schema = match schema {
+        Some(schema) => schema,
+        None => lookup_schema(metadata, &metadata.search_path(), &name)?,
+    };
+    Ok(qualified_name(&schema, &name))
+}
+
+/// Replace the table and view names in the query with the names they
+/// are stored under. Returns `None` if the query doesn't need to change.
+///
+/// # Errors
+/// - Failed to get a table or a view from the metadata.
+/// - Renamed table is moved to another schema.
+pub(super) fn resolve_schemas<M: Metadata>(
+    query: &str,
+    metadata: &M,
+) -> Result<Option<String>, SbroadError> {
+    let search_path = metadata.search_path();
+    let is_default_path = matches!(search_path.as_slice(), [schema] if schema == DEFAULT_SCHEMA);
+    if is_default_path && !query.contains('.') {
+        return Ok(None);
+    }
+
+    // Parsing errors are reported later, when the AST is built.
+    let Ok(mut pairs) = ParseTree::parse(Rule::Command, query) else {
+        return Ok(None);
+    };
+    let Some(top) = pairs.next() else {
+        return Ok(None);
+    };
+    let (names, ctes) = collect_names(top);
+
+    let default_schema = search_path
+        .first()
+        .cloned()
+        .unwrap_or_else(|| SmolStr::from(DEFAULT_SCHEMA));
+    // Schema of the last met existing table, the renamed table stays in it.
+    let mut table_schema = default_schema.clone();
+    let mut replacements = Vec::new();
+    for name_ref in names {
+        let schema = match (&name_ref.kind, name_ref.schema.clone()) {
+            (NameKind::Scan { .. }, None) if ctes.contains(&name_ref.name) => continue,
+            (NameKind::Relation | NameKind::Scan { .. }, schema) => {
+                let schema = match schema {
+                    Some(schema) => schema,
+                    None => lookup_schema(metadata, &search_path, &name_ref.name)?,
+                };
+                table_schema = schema.clone();
+                schema
+            }
+            (NameKind::New, schema) => schema.unwrap_or_else(|| default_schema.clone()),
+            (NameKind::Rename, Some(schema)) if schema != table_schema => {
+                return Err(SbroadError::Unsupported(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "moving table to schema {} by renaming it",
+                        to_user(schema)
+                    )),
+                ));
+            }
+            (NameKind::Rename, _) => table_schema.clone(),
+        };
+        let resolved = qualified_name(&schema, &name_ref.name);
+        if name_ref.schema.is_none() && resolved == name_ref.name {
+            continue;
+        }
+        let replacement = match name_ref.kind {
+            NameKind::Scan { has_alias: false } if resolved != name_ref.name => {
+                format!("{} as {}", to_user(&resolved), to_user(&name_ref.name))
+            }
+            _ => to_user(&resolved).to_string(),
+        };
+        replacements.push((name_ref.start, name_ref.end, replacement));
+    }
+    if replacements.is_empty() {
+        return Ok(None);
+    }
+
+    let mut resolved = String::with_capacity(query.len());
+    let mut pos = 0;
+    for (start, end, replacement) in replacements {
+        resolved.push_str(&query[pos..start]);
+        resolved.push_str(&replacement);
+        pos = end;
+    }
+    resolved.push_str(&query[pos..]);
+    Ok(Some(resolved))
+}
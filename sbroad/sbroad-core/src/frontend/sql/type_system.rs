@@ -37,10 +37,12 @@ impl From<SbroadType> for Type {
             SbroadType::String => Type::Text,
             SbroadType::Boolean => Type::Boolean,
             SbroadType::Datetime => Type::Datetime,
+            SbroadType::Interval => Type::Interval,
             SbroadType::Any => Type::Unknown,
             SbroadType::Uuid => Type::Uuid,
             SbroadType::Array => Type::Array,
             SbroadType::Map => Type::Map,
+            SbroadType::Varbinary => Type::Bytea,
         }
     }
 }
@@ -55,10 +57,12 @@ impl From<CastType> for Type {
             CastType::String | CastType::Text | CastType::Varchar(_) => Type::Text,
             CastType::Boolean => Type::Boolean,
             CastType::Datetime => Type::Datetime,
+            CastType::Interval => Type::Interval,
             // TODO: forbid casting to any
             CastType::Any => Type::Unknown,
             CastType::Uuid => Type::Uuid,
             CastType::Map => Type::Map,
+            CastType::Varbinary => Type::Bytea,
         }
     }
 }
@@ -385,7 +389,7 @@ static TYPE_SYSTEM: LazyLock<TypeSystem> = LazyLock::new(default_type_system);
 fn default_type_system() -> TypeSystem {
     use sbroad_type_system::expr::Type::*;
 
-    let functions = vec![
+    let mut functions = vec![
         // Arithmetic operations.
         // - unsigned
         Function::new_operator("+", [Unsigned, Unsigned], Integer),
@@ -407,6 +411,13 @@ fn default_type_system() -> TypeSystem {
         Function::new_operator("-", [Numeric, Numeric], Numeric),
         Function::new_operator("/", [Numeric, Numeric], Numeric),
         Function::new_operator("*", [Numeric, Numeric], Numeric),
+        // - datetime & interval
+        Function::new_operator("+", [Datetime, Interval], Datetime),
+        Function::new_operator("+", [Interval, Datetime], Datetime),
+        Function::new_operator("-", [Datetime, Interval], Datetime),
+        Function::new_operator("-", [Datetime, Datetime], Interval),
+        Function::new_operator("+", [Interval, Interval], Interval),
+        Function::new_operator("-", [Interval, Interval], Interval),
         // Logical operations.
         Function::new_operator("or", [Boolean, Boolean], Boolean),
         Function::new_operator("and", [Boolean, Boolean], Boolean),
@@ -418,19 +429,65 @@ fn default_type_system() -> TypeSystem {
             [],
             Text,
         ),
+        Function::new_scalar(
+            get_real_function_name("nextval").expect("shouldn't fail"),
+            [Text],
+            Integer,
+        ),
         Function::new_scalar("like", [Text, Text, Text], Boolean),
         Function::new_scalar("trim", [Text], Text),
         Function::new_scalar("trim", [Text, Text], Text),
         Function::new_scalar("to_date", [Text, Text], Datetime),
         Function::new_scalar("to_char", [Datetime, Text], Text),
+        Function::new_scalar("date_trunc", [Text, Datetime], Datetime),
+        Function::new_scalar("date_part", [Text, Datetime], Double),
+        Function::new_scalar("date_part", [Text, Interval], Double),
+        Function::new_scalar("age", [Datetime, Datetime], Interval),
+        Function::new_scalar("age", [Datetime], Interval),
+        Function::new_scalar("length", [Text], Integer),
+        Function::new_scalar("length", [Bytea], Integer),
         Function::new_scalar("substr", [Text, Integer], Text),
         Function::new_scalar("substr", [Text, Integer, Integer], Text),
+        Function::new_scalar("substr", [Bytea, Integer], Bytea),
+        Function::new_scalar("substr", [Bytea, Integer, Integer], Bytea),
         Function::new_scalar("lower", [Text], Text),
         Function::new_scalar("upper", [Text], Text),
         Function::new_scalar("substring", [Text, Integer], Text),
         Function::new_scalar("substring", [Text, Integer, Integer], Text),
         Function::new_scalar("substring", [Text, Text], Text),
         Function::new_scalar("substring", [Text, Text, Text], Text),
+        Function::new_scalar("substring", [Bytea, Integer], Bytea),
+        Function::new_scalar("substring", [Bytea, Integer, Integer], Bytea),
+        // Used to build the key of `count(distinct a, b)`.
+        Function::new_scalar("quote", [Unsigned], Text),
+        Function::new_scalar("quote", [Integer], Text),
+        Function::new_scalar("quote", [Double], Text),
+        Function::new_scalar("quote", [Numeric], Text),
+        Function::new_scalar("quote", [Text], Text),
+        Function::new_scalar("quote", [Boolean], Text),
+        Function::new_scalar("quote", [Datetime], Text),
+        Function::new_scalar("quote", [Interval], Text),
+        Function::new_scalar("quote", [Uuid], Text),
+        Function::new_scalar("quote", [Bytea], Text),
+        // JSON functions and operators (`->`, `->>`, `#>`, `#>>`, `@>`).
+        // JSON documents are either maps, arrays or their text representation,
+        // so they are accepted as `any`.
+        Function::new_scalar("json_get", [Any, Text], Any),
+        Function::new_scalar("json_get", [Any, Integer], Any),
+        Function::new_scalar("json_get_text", [Any, Text], Text),
+        Function::new_scalar("json_get_text", [Any, Integer], Text),
+        Function::new_scalar("json_get_path", [Any, Text], Any),
+        Function::new_scalar("json_get_path_text", [Any, Text], Text),
+        Function::new_scalar("json_contains", [Any, Any], Boolean),
+        Function::new_scalar("json_extract_path", [Any, Text], Any),
+        Function::new_scalar("json_extract_path_text", [Any, Text], Text),
+        Function::new_scalar("json_object_set", [Any, Text, Any], Map),
+        Function::new_scalar("json_array_append", [Any, Any], Array),
+        Function::new_scalar("json_object_new", [], Map),
+        Function::new_scalar("json_array_new", [], Array),
+        Function::new_scalar("json_array_elements", [Any], Any),
+        Function::new_scalar("json_array_length", [Any], Integer),
+        Function::new_scalar("json_typeof", [Any], Text),
         // Aggregates.
         // - count
         // TODO: consider adding `any` type
@@ -522,6 +579,62 @@ fn default_type_system() -> TypeSystem {
         Function::new_window("row_number", [], Integer),
     ];
 
+    // Aggregates with many overloads.
+    let comparable = [
+        Unsigned, Integer, Double, Numeric, Text, Boolean, Datetime, Uuid,
+    ];
+    // - bool_and & bool_or
+    for name in ["bool_and", "bool_or", "every"] {
+        functions.push(Function::new_aggregate(name, [Boolean], Boolean));
+    }
+    // - variance & stddev
+    for name in [
+        "var_samp",
+        "var_pop",
+        "variance",
+        "stddev_samp",
+        "stddev_pop",
+        "stddev",
+    ] {
+        for ty in [Integer, Double, Numeric] {
+            functions.push(Function::new_aggregate(name, [ty], Double));
+        }
+    }
+    // - array_agg
+    for ty in comparable {
+        functions.push(Function::new_aggregate("array_agg", [ty], Array));
+        for key in comparable {
+            functions.push(Function::new_aggregate(
+                "ordered_array_agg",
+                [ty, key, Boolean],
+                Array,
+            ));
+        }
+    }
+    // - string_agg & group_concat with ORDER BY
+    for key in comparable {
+        functions.push(Function::new_aggregate(
+            "ordered_string_agg",
+            [Text, Text, key, Boolean],
+            Text,
+        ));
+    }
+    // - percentile_cont & percentile_disc
+    for key in [Integer, Double, Numeric] {
+        functions.push(Function::new_aggregate(
+            "percentile_cont",
+            [Numeric, key, Boolean],
+            Double,
+        ));
+    }
+    for key in comparable {
+        functions.push(Function::new_aggregate(
+            "percentile_disc",
+            [Numeric, key, Boolean],
+            key,
+        ));
+    }
+
     TypeSystem::new(functions)
 }
 
@@ -543,3 +543,60 @@ fn cast() {
         ) = ($1, $2, $3, $4, $5, $6)",
     );
 }
+
+#[test]
+fn json() {
+    assert_ok("select doc -> 'a' -> 0 ->> 'b' from docs");
+    assert_ok("select (doc ->> 'a')::int + 1 from docs");
+    assert_ok("select * from docs where doc ->> 'a' = 'b'");
+    assert_ok("select * from docs where doc @> '{\"a\": 1}'");
+    assert_ok("select json_typeof(doc -> 'a'), json_array_length(doc -> 'b') from docs");
+    assert_ok("select doc -> $1::text from docs");
+
+    // JSON values must be converted to text before comparison, like in PostgreSQL.
+    assert_fails_with_error(
+        "select * from docs where doc -> 'a' = 1",
+        "could not resolve operator overload for =(any, unsigned)",
+    );
+
+    // JSON documents are accepted as is, so the parameter type can't be inferred.
+    assert_fails_with_error(
+        "select $1 -> 'a'",
+        "could not determine data type of parameter $1",
+    );
+}
+
+#[test]
+fn varbinary() {
+    assert_ok("select length(data), substring(data from 1 for 2) from bins");
+    assert_ok("select * from bins where data = X'00'");
+    assert_ok("select * from bins where data = $1");
+    // Parameters of string functions are resolved to text, like in PostgreSQL.
+    assert_ok("select length($1), substring($1 from 1 for 2)");
+
+    assert_fails_with_error(
+        "select substring(data from 'a') from bins",
+        "could not resolve function overload for substring(bytea, text)",
+    );
+}
+
+#[test]
+fn datetime_and_interval() {
+    assert_ok("select ts + duration, duration + ts, ts - duration, ts - ts from events");
+    assert_ok("select duration + interval '1 day', duration - interval '1 hour' from events");
+    assert_ok("select date_trunc('day', ts), date_part('doy', ts), extract(hour from duration) from events");
+    assert_ok("select age(ts), age(ts, ts) from events where ts + $1 > ts");
+
+    assert_fails_with_error(
+        "select ts + 1 from events",
+        "could not resolve operator overload for +(datetime, unsigned)",
+    );
+    assert_fails_with_error(
+        "select duration - ts from events",
+        "could not resolve operator overload for -(interval, datetime)",
+    );
+    assert_fails_with_error(
+        "select date_trunc('day', duration) from events",
+        "could not resolve function overload for date_trunc(text, interval)",
+    );
+}
@@ -0,0 +1,289 @@
+//! Expansion of views.
+//!
+//! A view is stored as the text of its query. Before the query is parsed
+//! into the AST, every reference to a view in the `FROM` clause is replaced
+//! with the view query wrapped into a subquery: `select * from v` with view
+//! `v` defined as `select a from t` becomes
+//! `select * from (select a from t) as "v"`. The views referenced by the
+//! view queries are expanded the same way before the view queries are
+//! inlined, so the CTEs of the outer query don't shadow the views and the
+//! tables referenced by the view queries.
+
+use std::collections::{HashMap, HashSet};
+
+use pest::iterators::Pair;
+use pest::Parser;
+use smol_str::{format_smolstr, SmolStr};
+
+use crate::errors::{Entity, SbroadError};
+use crate::executor::engine::helpers::{normalize_name_from_sql, to_user};
+use crate::executor::engine::Metadata;
+use crate::frontend::sql::ast::{ParseTree, Rule};
+use crate::ir::{Plan, PlanViews};
+
+/// Maximum depth of nested views. Besides limiting the query size it
+/// protects from views referencing themselves (`CREATE OR REPLACE VIEW`
+/// makes it possible).
+const MAX_VIEW_DEPTH: usize = 32;
+
+/// Reference to a table, CTE or view in the `FROM` clause.
+struct ScanRef {
+    name: SmolStr,
+    /// Start of the reference including the schema prefix.
+    start: usize,
+    end: usize,
+    has_alias: bool,
+}
+
+/// Definition of a CTE.
+struct CteDef {
+    /// Position of the CTE name.
+    name_start: usize,
+    name_end: usize,
+    /// Position of the whole definition.
+    start: usize,
+    end: usize,
+    recursive: bool,
+}
+
+impl CteDef {
+    /// Checks that the scan refers to the CTE rather than to the relation
+    /// of the same name: the CTE must be defined before the scan.
+    fn is_referenced_by(&self, scan: &ScanRef) -> bool {
+        scan.start >= self.end || (self.recursive && scan.start >= self.start)
+    }
+}
+
+#[derive(Default)]
+struct References {
+    scans: Vec<ScanRef>,
+    /// CTEs defined in the query, they take precedence over views.
+    ctes: HashMap<SmolStr, CteDef>,
+    /// Other tables, e.g. the ones modified by DML.
+    tables: HashSet<SmolStr>,
+}
+
+fn collect_references(top: Pair<'_, Rule>) -> References {
+    let mut refs = References::default();
+    // Start positions of the CTEs defined with `WITH RECURSIVE`.
+    let mut recursive_ctes = HashSet::new();
+    let mut stack = vec![top];
+    while let Some(pair) = stack.pop() {
+        match pair.as_rule() {
+            // The queries of `CREATE VIEW` and `CREATE TABLE AS` are stored as is.
+            Rule::ViewBody | Rule::CreateTableAsBody => continue,
+            Rule::SelectFull => {
+                let inner = pair.clone().into_inner();
+                if inner.clone().any(|p| p.as_rule() == Rule::Recursive) {
+                    recursive_ctes.extend(
+                        inner
+                            .filter(|p| p.as_rule() == Rule::Cte)
+                            .map(|p| p.as_span().start()),
+                    );
+                }
+            }
+            Rule::Cte => {
+                if let Some(name) = pair.clone().into_inner().next() {
+                    let span = pair.as_span();
+                    let cte = CteDef {
+                        name_start: name.as_span().start(),
+                        name_end: name.as_span().end(),
+                        start: span.start(),
+                        end: span.end(),
+                        recursive: recursive_ctes.contains(&span.start()),
+                    };
+                    refs.ctes
+                        .insert(normalize_name_from_sql(name.as_str()), cte);
+                }
+            }
+            Rule::Scan => {
+                let mut inner = pair.clone().into_inner();
+                if let Some(relation) = inner.next() {
+                    if relation.as_rule() == Rule::ScanCteOrTable {
+                        refs.scans.push(ScanRef {
+                            name: normalize_name_from_sql(relation.as_str()),
+                            start: pair.as_span().start(),
+                            end: relation.as_span().end(),
+                            has_alias: inner.next().is_some(),
+                        });
+                    }
+                }
+            }
+            Rule::Table => {
+                refs.tables.insert(normalize_name_from_sql(pair.as_str()));
+            }
+            _ => {}
+        }
+        stack.extend(pair.into_inner());
+    }
+    refs
+}
+
+/// Query with expanded views.
+pub(super) struct ExpandedQuery {
+    pub(super) query: String,
+    views: PlanViews,
+    /// Relations referenced by the original query itself.
+    relations: HashSet<SmolStr>,
+}
+
+impl ExpandedQuery {
+    /// Save the information about the expanded views into the plan
+    /// built from the expanded query.
+    pub(super) fn fill_plan_views(self, plan: &mut Plan) {
+        let mut views = self.views;
+        views.hidden_tables = plan
+            .relations
+            .tables
+            .keys()
+            .filter(|name| !self.relations.contains(*name))
+            .cloned()
+            .collect();
+        plan.views = views;
+    }
+}
+
+/// Replace references to views in the query with the view queries.
+/// Returns `None` if the query doesn't reference any views.
+///
+/// # Errors
+/// - Failed to get a view from the metadata.
+/// - Views are nested too deep.
+pub(super) fn expand_views<M: Metadata>(
+    query: &str,
+    metadata: &M,
+) -> Result<Option<ExpandedQuery>, SbroadError> {
+    let mut views = PlanViews::default();
+    let mut relations = HashSet::new();
+    let mut nested_relations = HashSet::new();
+    let Some(query) = expand_text(
+        query,
+        metadata,
+        0,
+        &mut views,
+        &mut relations,
+        &mut nested_relations,
+    )?
+    else {
+        return Ok(None);
+    };
+    Ok(Some(ExpandedQuery {
+        query,
+        views,
+        relations,
+    }))
+}
+
+/// Expand the views referenced by the text, which is either the query
+/// itself or a view query. Names of the relations referenced by the text
+/// are added to `relations`, the ones referenced by the inlined view
+/// queries are added to `nested_relations`. Returns `None` if the text
+/// doesn't reference any views.
+fn expand_text<M: Metadata>(
+    text: &str,
+    metadata: &M,
+    depth: usize,
+    views: &mut PlanViews,
+    relations: &mut HashSet<SmolStr>,
+    nested_relations: &mut HashSet<SmolStr>,
+) -> Result<Option<String>, SbroadError> {
+    // Parsing errors are reported later, when the AST is built.
+    let refs = match ParseTree::parse(Rule::Command, text) {
+        Ok(mut pairs) => match pairs.next() {
+            Some(top) => collect_references(top),
+            None => return Ok(None),
+        },
+        Err(_) => return Ok(None),
+    };
+    relations.extend(refs.tables.iter().cloned());
+
+    let mut inner_relations = HashSet::new();
+    let mut replacements = Vec::new();
+    for scan in &refs.scans {
+        let is_cte = refs
+            .ctes
+            .get(&scan.name)
+            .is_some_and(|cte| cte.is_referenced_by(scan));
+        let view_query = if is_cte {
+            None
+        } else {
+            metadata.view(&scan.name)?
+        };
+        let Some(view_query) = view_query else {
+            relations.insert(scan.name.clone());
+            continue;
+        };
+        if depth == MAX_VIEW_DEPTH {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some(format_smolstr!(
+                    "views are nested deeper than {MAX_VIEW_DEPTH} levels, \
+                    probably view {} references itself",
+                    to_user(&scan.name)
+                )),
+            ));
+        }
+        let mut view_relations = HashSet::new();
+        let expanded = expand_text(
+            &view_query,
+            metadata,
+            depth + 1,
+            views,
+            &mut view_relations,
+            &mut inner_relations,
+        )?;
+        inner_relations.extend(view_relations);
+        let view_text = expanded.as_deref().unwrap_or(&view_query);
+        let subquery = if scan.has_alias {
+            format!("({view_text})")
+        } else {
+            format!("({view_text}) as {}", to_user(&scan.name))
+        };
+        if depth == 0 && !views.referenced.contains(&scan.name) {
+            views.referenced.push(scan.name.clone());
+        }
+        views.queries.insert(scan.name.clone(), view_query);
+        replacements.push((scan.start, scan.end, subquery));
+    }
+    if replacements.is_empty() {
+        return Ok(None);
+    }
+
+    // CTE names are global for the whole plan, so a CTE of the text would
+    // be taken for the relation of the same name referenced by an inlined
+    // view query. Such CTEs are renamed.
+    for (name, cte) in &refs.ctes {
+        if !inner_relations.contains(name) {
+            continue;
+        }
+        let renamed = (1..)
+            .map(|n| format_smolstr!("{name}_{n}"))
+            .find(|renamed| !inner_relations.contains(renamed) && !refs.ctes.contains_key(renamed))
+            .expect("unused CTE name must exist");
+        replacements.push((cte.name_start, cte.name_end, to_user(&renamed).to_string()));
+        let cte_scans = refs
+            .scans
+            .iter()
+            .filter(|scan| &scan.name == name && cte.is_referenced_by(scan));
+        for scan in cte_scans {
+            let replacement = if scan.has_alias {
+                to_user(&renamed).to_string()
+            } else {
+                format!("{} as {}", to_user(&renamed), to_user(name))
+            };
+            replacements.push((scan.start, scan.end, replacement));
+        }
+    }
+    nested_relations.extend(inner_relations);
+
+    replacements.sort_by_key(|(start, ..)| *start);
+    let mut expanded = String::with_capacity(text.len());
+    let mut pos = 0;
+    for (start, end, replacement) in replacements {
+        expanded.push_str(&text[pos..start]);
+        expanded.push_str(&replacement);
+        pos = end;
+    }
+    expanded.push_str(&text[pos..]);
+    Ok(Some(expanded))
+}
@@ -1,5 +1,10 @@
 local compat = require('compat')
 local compat_mt = compat ~= nil and getmetatable(compat) or nil
+local fiber = require('fiber')
+
+--- How often (in seconds) a fiber waiting for the storages checks
+--- whether its query was cancelled.
+local CANCEL_CHECK_INTERVAL = 0.1
 
 --- Checks if building against picodata - mainly needed because stored procs should be used then.
 local function pico_compat()
@@ -87,6 +92,31 @@ local function unwrap_execute_result(result)
     end
 end
 
+--- Returns an error if the query executed by the current fiber was cancelled.
+local function check_cancelled()
+  if not pico_compat() or _G.pico._check_query_cancelled == nil then
+    return nil
+  end
+  return _G.pico._check_query_cancelled()
+end
+
+--- Same as `future:wait_result(timeout)`, but stops waiting
+--- as soon as the query is cancelled.
+local function wait_result(future, timeout)
+  local deadline = fiber.clock() + (timeout or math.huge)
+  while true do
+    local err = check_cancelled()
+    if err ~= nil then
+      return nil, err
+    end
+    local remaining = deadline - fiber.clock()
+    local res, wait_err = future:wait_result(math.max(0, math.min(remaining, CANCEL_CHECK_INTERVAL)))
+    if res ~= nil or future:is_ready() or remaining <= CANCEL_CHECK_INTERVAL then
+      return res, wait_err
+    end
+  end
+end
+
 local function table_size(t)
   local count = 0
   for _, _ in pairs(t) do count = count + 1 end
@@ -102,5 +132,7 @@ return {
     dql_error = dql_error,
     format_result = format_result,
     unwrap_execute_result = unwrap_execute_result,
-    table_size = table_size
+    table_size = table_size,
+    check_cancelled = check_cancelled,
+    wait_result = wait_result
 }
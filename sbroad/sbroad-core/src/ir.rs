@@ -15,21 +15,24 @@ use std::cell::{RefCell, RefMut};
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 use std::slice::Iter;
+use std::time::Instant;
 use tree::traversal::LevelNode;
 
 use self::relation::Relations;
 use self::transformation::redistribution::MotionPolicy;
+use crate::cbo::{SelectivityMap, TableRowsMap};
 use crate::errors::Entity::Query;
 use crate::errors::{Action, Entity, SbroadError, TypeError};
 use crate::executor::engine::helpers::to_user;
 use crate::executor::engine::TableVersionMap;
+use crate::ir::explain::ExplainOptions;
 use crate::ir::node::plugin::{MutPlugin, Plugin};
 use crate::ir::node::tcl::Tcl;
 use crate::ir::node::{
     Alias, ArenaType, ArithmeticExpr, BoolExpr, Case, Cast, Concat, Constant, GroupBy, Having,
     Insert, Limit, Motion, MutNode, Node, Node136, Node232, Node32, Node64, Node96, NodeId,
-    NodeOwned, OrderBy, Projection, Reference, Row, ScalarFunction, ScanRelation, Selection, Trim,
-    UnaryExpr, Values,
+    NodeOwned, OrderBy, Projection, RecursiveCte, Reference, Row, ScalarFunction, ScanCte,
+    ScanRelation, Selection, Trim, UnaryExpr, Values,
 };
 use crate::ir::operator::{Bool, OrderByEntity};
 use crate::ir::relation::{Column, DerivedType};
@@ -63,6 +66,7 @@ pub mod value;
 
 pub const DEFAULT_SQL_MOTION_ROW_MAX: u64 = 5000;
 pub const DEFAULT_SQL_VDBE_OPCODE_MAX: u64 = 45000;
+pub const DEFAULT_SQL_RECURSION_ROW_MAX: u64 = 5000;
 
 /// Plan nodes storage.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -90,7 +94,6 @@ impl Nodes {
                 Node32::Except(except) => Node::Relational(Relational::Except(except)),
                 Node32::Intersect(intersect) => Node::Relational(Relational::Intersect(intersect)),
                 Node32::Invalid(inv) => Node::Invalid(inv),
-                Node32::Limit(limit) => Node::Relational(Relational::Limit(limit)),
                 Node32::SelectWithoutScan(select) => {
                     Node::Relational(Relational::SelectWithoutScan(select))
                 }
@@ -105,8 +108,6 @@ impl Nodes {
                     Tcl::Commit => Node::Tcl(Tcl::Commit),
                     Tcl::Rollback => Node::Tcl(Tcl::Rollback),
                 },
-                Node32::CreateSchema => Node::Ddl(Ddl::CreateSchema),
-                Node32::DropSchema => Node::Ddl(Ddl::DropSchema),
             }),
             ArenaType::Arena64 => self.arena64.get(id.offset as usize).map(|node| match node {
                 Node64::Over(over) => Node::Expression(Expression::Over(over)),
@@ -116,6 +117,11 @@ impl Nodes {
                 Node64::CreateRole(create_role) => Node::Acl(Acl::CreateRole(create_role)),
                 Node64::Delete(delete) => Node::Relational(Relational::Delete(delete)),
                 Node64::DropIndex(drop_index) => Node::Ddl(Ddl::DropIndex(drop_index)),
+                Node64::DropSequence(drop_sequence) => Node::Ddl(Ddl::DropSequence(drop_sequence)),
+                Node64::DropView(drop_view) => Node::Ddl(Ddl::DropView(drop_view)),
+                Node64::CreateSchema(create_schema) => Node::Ddl(Ddl::CreateSchema(create_schema)),
+                Node64::DropSchema(drop_schema) => Node::Ddl(Ddl::DropSchema(drop_schema)),
+                Node64::Analyze(analyze) => Node::Ddl(Ddl::Analyze(analyze)),
                 Node64::DropRole(drop_role) => Node::Acl(Acl::DropRole(drop_role)),
                 Node64::DropTable(drop_table) => Node::Ddl(Ddl::DropTable(drop_table)),
                 Node64::TruncateTable(truncate_table) => {
@@ -125,12 +131,12 @@ impl Nodes {
                 Node64::DropUser(drop_user) => Node::Acl(Acl::DropUser(drop_user)),
                 Node64::GroupBy(group_by) => Node::Relational(Relational::GroupBy(group_by)),
                 Node64::Having(having) => Node::Relational(Relational::Having(having)),
+                Node64::Limit(limit) => Node::Relational(Relational::Limit(limit)),
                 Node64::Join(join) => Node::Relational(Relational::Join(join)),
                 Node64::OrderBy(order_by) => Node::Relational(Relational::OrderBy(order_by)),
                 Node64::Parameter(param) => Node::Expression(Expression::Parameter(param)),
                 Node64::Procedure(proc) => Node::Block(Block::Procedure(proc)),
                 Node64::Projection(proj) => Node::Relational(Relational::Projection(proj)),
-                Node64::ScanCte(scan_cte) => Node::Relational(Relational::ScanCte(scan_cte)),
                 Node64::ScanRelation(scan_rel) => {
                     Node::Relational(Relational::ScanRelation(scan_rel))
                 }
@@ -149,7 +155,11 @@ impl Nodes {
             ArenaType::Arena96 => self.arena96.get(id.offset as usize).map(|node| match node {
                 Node96::Reference(reference) => Node::Expression(Expression::Reference(reference)),
                 Node96::DropProc(drop_proc) => Node::Ddl(Ddl::DropProc(drop_proc)),
+                Node96::CreateSequence(create_sequence) => {
+                    Node::Ddl(Ddl::CreateSequence(create_sequence))
+                }
                 Node96::Insert(insert) => Node::Relational(Relational::Insert(insert)),
+                Node96::ScanCte(scan_cte) => Node::Relational(Relational::ScanCte(scan_cte)),
                 Node96::Invalid(inv) => Node::Invalid(inv),
                 Node96::ScalarFunction(stable_func) => {
                     Node::Expression(Expression::ScalarFunction(stable_func))
@@ -166,6 +176,7 @@ impl Nodes {
                     Node136::AlterUser(alter_user) => Node::Acl(Acl::AlterUser(alter_user)),
                     Node136::AlterTable(alter_table) => Node::Ddl(Ddl::AlterTable(alter_table)),
                     Node136::CreateProc(create_proc) => Node::Ddl(Ddl::CreateProc(create_proc)),
+                    Node136::CreateView(create_view) => Node::Ddl(Ddl::CreateView(create_view)),
                     Node136::RevokePrivilege(revoke_priv) => {
                         Node::Acl(Acl::RevokePrivilege(revoke_priv))
                     }
@@ -192,6 +203,12 @@ impl Nodes {
                 .map(|node| match node {
                     Node232::CreateIndex(create_index) => Node::Ddl(Ddl::CreateIndex(create_index)),
                     Node232::CreateTable(create_table) => Node::Ddl(Ddl::CreateTable(create_table)),
+                    Node232::CreateTableAs(create_table_as) => {
+                        Node::Ddl(Ddl::CreateTableAs(create_table_as))
+                    }
+                    Node232::CreatePartition(create_partition) => {
+                        Node::Ddl(Ddl::CreatePartition(create_partition))
+                    }
                     Node232::Invalid(inv) => Node::Invalid(inv),
                     Node232::AppendServiceToTier(append) => {
                         Node::Plugin(Plugin::AppendServiceToTier(append))
@@ -215,7 +232,6 @@ impl Nodes {
                         MutNode::Expression(MutExpression::Arithmetic(arithm))
                     }
                     Node32::Bool(bool) => MutNode::Expression(MutExpression::Bool(bool)),
-                    Node32::Limit(limit) => MutNode::Relational(MutRelational::Limit(limit)),
                     Node32::Concat(concat) => MutNode::Expression(MutExpression::Concat(concat)),
                     Node32::Cast(cast) => MutNode::Expression(MutExpression::Cast(cast)),
                     Node32::CountAsterisk(count) => {
@@ -243,8 +259,6 @@ impl Nodes {
                         Tcl::Commit => MutNode::Tcl(node::tcl::Tcl::Commit),
                         Tcl::Rollback => MutNode::Tcl(node::tcl::Tcl::Rollback),
                     },
-                    Node32::CreateSchema => MutNode::Ddl(MutDdl::CreateSchema),
-                    Node32::DropSchema => MutNode::Ddl(MutDdl::DropSchema),
                 }),
             ArenaType::Arena64 => self
                 .arena64
@@ -261,6 +275,17 @@ impl Nodes {
                     }
                     Node64::Delete(delete) => MutNode::Relational(MutRelational::Delete(delete)),
                     Node64::DropIndex(drop_index) => MutNode::Ddl(MutDdl::DropIndex(drop_index)),
+                    Node64::DropSequence(drop_sequence) => {
+                        MutNode::Ddl(MutDdl::DropSequence(drop_sequence))
+                    }
+                    Node64::DropView(drop_view) => MutNode::Ddl(MutDdl::DropView(drop_view)),
+                    Node64::CreateSchema(create_schema) => {
+                        MutNode::Ddl(MutDdl::CreateSchema(create_schema))
+                    }
+                    Node64::DropSchema(drop_schema) => {
+                        MutNode::Ddl(MutDdl::DropSchema(drop_schema))
+                    }
+                    Node64::Analyze(analyze) => MutNode::Ddl(MutDdl::Analyze(analyze)),
                     Node64::Row(row) => MutNode::Expression(MutExpression::Row(row)),
                     Node64::DropRole(drop_role) => MutNode::Acl(MutAcl::DropRole(drop_role)),
                     Node64::DropTable(drop_table) => MutNode::Ddl(MutDdl::DropTable(drop_table)),
@@ -272,6 +297,7 @@ impl Nodes {
                         MutNode::Relational(MutRelational::GroupBy(group_by))
                     }
                     Node64::Having(having) => MutNode::Relational(MutRelational::Having(having)),
+                    Node64::Limit(limit) => MutNode::Relational(MutRelational::Limit(limit)),
                     Node64::Join(join) => MutNode::Relational(MutRelational::Join(join)),
                     Node64::OrderBy(order_by) => {
                         MutNode::Relational(MutRelational::OrderBy(order_by))
@@ -283,9 +309,6 @@ impl Nodes {
                     Node64::Projection(proj) => {
                         MutNode::Relational(MutRelational::Projection(proj))
                     }
-                    Node64::ScanCte(scan_cte) => {
-                        MutNode::Relational(MutRelational::ScanCte(scan_cte))
-                    }
                     Node64::ScanRelation(scan_rel) => {
                         MutNode::Relational(MutRelational::ScanRelation(scan_rel))
                     }
@@ -315,7 +338,13 @@ impl Nodes {
                         MutNode::Expression(MutExpression::Reference(reference))
                     }
                     Node96::DropProc(drop_proc) => MutNode::Ddl(MutDdl::DropProc(drop_proc)),
+                    Node96::CreateSequence(create_sequence) => {
+                        MutNode::Ddl(MutDdl::CreateSequence(create_sequence))
+                    }
                     Node96::Insert(insert) => MutNode::Relational(MutRelational::Insert(insert)),
+                    Node96::ScanCte(scan_cte) => {
+                        MutNode::Relational(MutRelational::ScanCte(scan_cte))
+                    }
                     Node96::Invalid(inv) => MutNode::Invalid(inv),
                     Node96::ScalarFunction(scalar_func) => {
                         MutNode::Expression(MutExpression::ScalarFunction(scalar_func))
@@ -338,6 +367,9 @@ impl Nodes {
                         Node136::CreateProc(create_proc) => {
                             MutNode::Ddl(MutDdl::CreateProc(create_proc))
                         }
+                        Node136::CreateView(create_view) => {
+                            MutNode::Ddl(MutDdl::CreateView(create_view))
+                        }
                         Node136::GrantPrivilege(grant_priv) => {
                             MutNode::Acl(MutAcl::GrantPrivilege(grant_priv))
                         }
@@ -382,6 +414,12 @@ impl Nodes {
                         Node232::CreateTable(create_table) => {
                             MutNode::Ddl(MutDdl::CreateTable(create_table))
                         }
+                        Node232::CreateTableAs(create_table_as) => {
+                            MutNode::Ddl(MutDdl::CreateTableAs(create_table_as))
+                        }
+                        Node232::CreatePartition(create_partition) => {
+                            MutNode::Ddl(MutDdl::CreatePartition(create_partition))
+                        }
                         Node232::AppendServiceToTier(append) => {
                             MutNode::Plugin(MutPlugin::AppendServiceToTier(append))
                         }
@@ -627,6 +665,7 @@ pub struct OptionSpec {
 pub enum OptionKind {
     VdbeOpcodeMax,
     MotionRowMax,
+    RecursionRowMax,
 }
 
 impl Display for OptionKind {
@@ -634,6 +673,7 @@ impl Display for OptionKind {
         let s = match self {
             OptionKind::VdbeOpcodeMax => "sql_vdbe_opcode_max",
             OptionKind::MotionRowMax => "sql_motion_row_max",
+            OptionKind::RecursionRowMax => "sql_recursion_row_max",
         };
         write!(f, "{s}")
     }
@@ -656,6 +696,9 @@ pub struct Options {
     /// Options passed to `box.execute` function on storages. Currently there is only one option
     /// `sql_vdbe_opcode_max`.
     pub sql_vdbe_opcode_max: u64,
+    /// Maximum number of rows a recursive CTE can produce, the query fails when
+    /// the limit is exceeded. Value of `0` indicates that this limit is disabled.
+    pub sql_recursion_row_max: u64,
 }
 
 impl Default for Options {
@@ -670,6 +713,7 @@ impl Options {
         Options {
             sql_motion_row_max,
             sql_vdbe_opcode_max,
+            sql_recursion_row_max: DEFAULT_SQL_RECURSION_ROW_MAX,
         }
     }
 }
@@ -696,6 +740,9 @@ pub struct Plan {
     /// The flag is enabled if user wants to get a query plan only.
     /// In this case we don't need to execute query.
     is_explain: bool,
+    /// Options of the `EXPLAIN` command (output format, whether
+    /// the query should be executed to gather runtime statistics).
+    explain_options: ExplainOptions,
     /// The undo log keeps the history of the plan transformations. It can
     /// be used to revert the plan subtree to some previous snapshot if needed.
     pub(crate) undo: TransformationLog,
@@ -724,6 +771,45 @@ pub struct Plan {
     /// is not applicable - for example cartridge case(vshard.router.static used).
     #[serde(skip)]
     pub tier: Option<SmolStr>,
+    /// Identifier of the interactive transaction the plan is executed in.
+    /// Set by the router, Option::None means autocommit mode.
+    #[serde(skip)]
+    pub txn_id: Option<SmolStr>,
+    /// Identifier of the query execution used to cancel it on the router
+    /// and on the storages. Set by the router.
+    #[serde(skip)]
+    pub request_id: Option<SmolStr>,
+    /// Moment the statement timeout of the query expires.
+    /// Set by the router, Option::None means no timeout.
+    #[serde(skip)]
+    pub statement_deadline: Option<Instant>,
+    /// Views referenced by the query. Filled on the router only.
+    #[serde(skip)]
+    pub views: PlanViews,
+    /// Number of rows in the referenced tables according to the
+    /// statistics gathered by `ANALYZE`. Filled on the router before
+    /// the plan optimization (see `set_statistics`).
+    #[serde(skip)]
+    pub table_rows: TableRowsMap,
+    /// Selectivity of the filters and join conditions estimated with
+    /// the column statistics. Filled along with `table_rows`.
+    #[serde(skip)]
+    pub selectivity: SelectivityMap,
+}
+
+/// Views are expanded into subqueries before the plan is built,
+/// so the plan nodes know nothing about them. This structure keeps
+/// the information about the expanded views for the privilege checks
+/// and the plan cache invalidation.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct PlanViews {
+    /// Queries of all the expanded views (including the nested ones)
+    /// by view names.
+    pub queries: HashMap<SmolStr, SmolStr>,
+    /// Views referenced by the query itself rather than by other views.
+    pub referenced: Vec<SmolStr>,
+    /// Tables that are referenced only by the view queries.
+    pub hidden_tables: HashSet<SmolStr>,
 }
 
 /// Helper structures used to build the plan
@@ -851,6 +937,7 @@ impl Plan {
             slices: Slices { slices: vec![] },
             top: None,
             is_explain: false,
+            explain_options: ExplainOptions::default(),
             undo: TransformationLog::new(),
             constants: HashMap::new(),
             raw_options: vec![],
@@ -858,6 +945,12 @@ impl Plan {
             version_map: TableVersionMap::new(),
             context: Some(RefCell::new(BuildContext::default())),
             tier: None,
+            txn_id: None,
+            request_id: None,
+            statement_deadline: None,
+            views: PlanViews::default(),
+            table_rows: TableRowsMap::new(),
+            selectivity: SelectivityMap::new(),
         }
     }
 
@@ -946,6 +1039,54 @@ impl Plan {
                         ));
                     }
                 }
+                OptionKind::RecursionRowMax => {
+                    if let Value::Unsigned(limit) = val {
+                        self.options.sql_recursion_row_max = limit;
+                    } else {
+                        return Err(SbroadError::Invalid(
+                            Entity::OptionSpec,
+                            Some(format_smolstr!(
+                                "expected option {} to be unsigned got: {val:?}",
+                                opt.kind
+                            )),
+                        ));
+                    }
+                }
+            }
+        }
+        // The option may also come with the default options of the query.
+        self.apply_recursion_row_max()?;
+        Ok(())
+    }
+
+    /// Recursive CTEs are executed by the local SQL engine with their
+    /// row limit inlined into the query as `LIMIT` (see [`RecursiveCte::limit`]),
+    /// so propagate the actual option value to the plan.
+    fn apply_recursion_row_max(&mut self) -> Result<(), SbroadError> {
+        let mut limit_ids: Vec<NodeId> = Vec::new();
+        let mut bfs =
+            BreadthFirst::with_capacity(|x| self.nodes.rel_iter(x), REL_CAPACITY, REL_CAPACITY);
+        bfs.populate_nodes(self.get_top()?);
+        for LevelNode(_, id) in bfs.take_nodes() {
+            if let Relational::ScanCte(ScanCte {
+                child,
+                recursive:
+                    Some(RecursiveCte {
+                        is_self_reference: false,
+                        ..
+                    }),
+                ..
+            }) = self.get_relation_node(id)?
+            {
+                limit_ids.push(*child);
+            }
+        }
+        let row_max = self.options.sql_recursion_row_max;
+        for limit_id in limit_ids {
+            if let MutRelational::Limit(Limit { limit, .. }) =
+                self.get_mut_relation_node(limit_id)?
+            {
+                *limit = RecursiveCte::limit(row_max);
             }
         }
         Ok(())
@@ -1269,8 +1410,22 @@ impl Plan {
     }
 
     /// Marks plan as query explain
-    pub fn mark_as_explain(&mut self) {
+    pub fn mark_as_explain(&mut self, options: ExplainOptions) {
         self.is_explain = true;
+        self.explain_options = options;
+    }
+
+    /// Options of the explain query.
+    #[must_use]
+    pub fn explain_options(&self) -> &ExplainOptions {
+        &self.explain_options
+    }
+
+    /// Checks that plan is `EXPLAIN ANALYZE` query, i.e. it has to be
+    /// executed to annotate the explain with the runtime statistics.
+    #[must_use]
+    pub fn is_explain_analyze(&self) -> bool {
+        self.is_explain && self.explain_options.analyze
     }
 
     /// Checks that plan is explain query
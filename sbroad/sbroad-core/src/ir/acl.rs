@@ -26,6 +26,7 @@ use super::{
 
 /// Helper enum representing one of possible revoking/granting options:
 /// * Table: for specific table.
+/// * Schema: for specific schema.
 /// * Universe: for any object supporting passed privilege.
 /// * Role: for making user/role a part of another role.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -59,6 +60,13 @@ pub enum GrantRevokeType {
         proc_name: SmolStr,
         proc_params: Option<Vec<ParamDef>>,
     },
+    Schema {
+        privilege: Privilege,
+    },
+    SpecificSchema {
+        privilege: Privilege,
+        schema_name: SmolStr,
+    },
     RolePass {
         role_name: SmolStr,
     },
@@ -172,12 +180,42 @@ impl GrantRevokeType {
         })
     }
 
+    /// # Errors
+    /// - Unacceptable privilege for schema was passed.
+    pub fn schema(privilege: Privilege) -> Result<Self, SbroadError> {
+        check_privilege(privilege, &[Privilege::Create])?;
+        Ok(Self::Schema { privilege })
+    }
+
+    /// # Errors
+    /// - Unacceptable privilege for specific schema was passed.
+    pub fn specific_schema(
+        privilege: Privilege,
+        schema_name: SmolStr,
+    ) -> Result<Self, SbroadError> {
+        check_privilege(privilege, &[Privilege::Usage, Privilege::Create])?;
+        Ok(Self::SpecificSchema {
+            privilege,
+            schema_name,
+        })
+    }
+
     #[must_use]
     pub fn role_pass(role_name: SmolStr) -> Self {
         Self::RolePass { role_name }
     }
 }
 
+::tarantool::define_str_enum! {
+    /// Authentication method of a user.
+    pub enum AuthMethod {
+        ChapSha1 = "chap-sha1",
+        Md5 = "md5",
+        Ldap = "ldap",
+        ScramSha256 = "scram-sha-256",
+    }
+}
+
 /// Type of ALTER USER command execution.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum AlterOption {
@@ -185,11 +223,16 @@ pub enum AlterOption {
     NoLogin,
     Password {
         password: SmolStr,
-        auth_method: tarantool::auth::AuthMethod,
+        auth_method: AuthMethod,
     },
     Rename {
         new_name: SmolStr,
     },
+    /// Default statement timeout of the user's sessions in milliseconds.
+    /// Option::None resets the timeout to the system default.
+    StatementTimeout {
+        millis: Option<u64>,
+    },
 }
 
 impl Plan {
@@ -7,6 +7,7 @@ use crate::ir::helpers::RepeatableState;
 use crate::ir::node::{NodeId, Reference, ScalarFunction};
 use crate::ir::operator::Arithmetic;
 use crate::ir::relation::Type as RelType;
+use crate::ir::value::Value;
 use crate::ir::Plan;
 use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
@@ -35,6 +36,29 @@ pub enum AggregateKind {
     MIN,
     MAX,
     GRCONCAT,
+    BOOLAND,
+    BOOLOR,
+    VARSAMP,
+    VARPOP,
+    STDDEVSAMP,
+    STDDEVPOP,
+    ORDEREDGRCONCAT,
+    ARRAYAGG,
+    ORDEREDARRAYAGG,
+    PERCENTILECONT,
+    PERCENTILEDISC,
+    /// The kinds below are never met in the user query and are used only
+    /// on the local and final stages of the aggregates implemented in lua.
+    ///
+    /// Concatenation of the arrays of the local `array_agg`.
+    ARRAYAGGMERGE,
+    /// Merge of the sorted `{key, value}` pairs of the local ordered aggregates.
+    ORDEREDAGGMERGE,
+    /// State of variance and standard deviation: count, mean and the sum
+    /// of squared differences from the mean (M2).
+    VARIANCESTATE,
+    /// Merge of the local variance states.
+    VARIANCEMERGE,
 }
 
 impl Display for AggregateKind {
@@ -47,6 +71,21 @@ impl Display for AggregateKind {
             AggregateKind::MIN => "min",
             AggregateKind::MAX => "max",
             AggregateKind::GRCONCAT => "group_concat",
+            AggregateKind::BOOLAND => "bool_and",
+            AggregateKind::BOOLOR => "bool_or",
+            AggregateKind::VARSAMP => "var_samp",
+            AggregateKind::VARPOP => "var_pop",
+            AggregateKind::STDDEVSAMP => "stddev_samp",
+            AggregateKind::STDDEVPOP => "stddev_pop",
+            AggregateKind::ORDEREDGRCONCAT => "ordered_string_agg",
+            AggregateKind::ARRAYAGG => "array_agg",
+            AggregateKind::ORDEREDARRAYAGG => "ordered_array_agg",
+            AggregateKind::PERCENTILECONT => "percentile_cont",
+            AggregateKind::PERCENTILEDISC => "percentile_disc",
+            AggregateKind::ARRAYAGGMERGE => "array_agg_merge",
+            AggregateKind::ORDEREDAGGMERGE => "ordered_agg_merge",
+            AggregateKind::VARIANCESTATE => "variance_state",
+            AggregateKind::VARIANCEMERGE => "variance_merge",
         };
         write!(f, "{name}")
     }
@@ -65,6 +104,17 @@ impl AggregateKind {
             "min" => AggregateKind::MIN,
             "max" => AggregateKind::MAX,
             "group_concat" | "string_agg" => AggregateKind::GRCONCAT,
+            "bool_and" | "every" => AggregateKind::BOOLAND,
+            "bool_or" => AggregateKind::BOOLOR,
+            "var_samp" | "variance" => AggregateKind::VARSAMP,
+            "var_pop" => AggregateKind::VARPOP,
+            "stddev_samp" | "stddev" => AggregateKind::STDDEVSAMP,
+            "stddev_pop" => AggregateKind::STDDEVPOP,
+            "ordered_string_agg" => AggregateKind::ORDEREDGRCONCAT,
+            "array_agg" => AggregateKind::ARRAYAGG,
+            "ordered_array_agg" => AggregateKind::ORDEREDARRAYAGG,
+            "percentile_cont" => AggregateKind::PERCENTILECONT,
+            "percentile_disc" => AggregateKind::PERCENTILEDISC,
             _ => return None,
         };
         Some(kind)
@@ -75,9 +125,21 @@ impl AggregateKind {
         let ty =
             match self {
                 AggregateKind::COUNT => RelType::Unsigned,
-                AggregateKind::TOTAL => RelType::Double,
-                AggregateKind::GRCONCAT => RelType::String,
+                AggregateKind::TOTAL
+                | AggregateKind::VARSAMP
+                | AggregateKind::VARPOP
+                | AggregateKind::STDDEVSAMP
+                | AggregateKind::STDDEVPOP
+                | AggregateKind::PERCENTILECONT => RelType::Double,
+                AggregateKind::GRCONCAT | AggregateKind::ORDEREDGRCONCAT => RelType::String,
                 AggregateKind::SUM | AggregateKind::AVG => RelType::Decimal,
+                AggregateKind::BOOLAND | AggregateKind::BOOLOR => RelType::Boolean,
+                AggregateKind::ARRAYAGG
+                | AggregateKind::ORDEREDARRAYAGG
+                | AggregateKind::ARRAYAGGMERGE
+                | AggregateKind::ORDEREDAGGMERGE
+                | AggregateKind::VARIANCESTATE
+                | AggregateKind::VARIANCEMERGE => RelType::Array,
                 AggregateKind::MIN | AggregateKind::MAX => {
                     let child_node = args.first().ok_or(SbroadError::UnexpectedNumberOfValues(
                         format_smolstr!("expected at least 1 argument, got 0"),
@@ -85,10 +147,29 @@ impl AggregateKind {
                     let expr_node = plan.get_expression_node(*child_node)?;
                     return expr_node.calculate_type(plan);
                 }
+                AggregateKind::PERCENTILEDISC => {
+                    // The result is one of the values of the ordering expression.
+                    return Self::get_arg_type(1, plan, args);
+                }
             };
         Ok(DerivedType::new(ty))
     }
 
+    /// Get type of the aggregate function on the local stage. The ordered
+    /// aggregates and percentiles return their state, which is converted
+    /// into the result by the finalize function.
+    pub fn get_local_type(self, plan: &Plan, args: &[NodeId]) -> Result<DerivedType, SbroadError> {
+        match self {
+            AggregateKind::ORDEREDGRCONCAT | AggregateKind::ORDEREDARRAYAGG => {
+                Ok(DerivedType::new(RelType::Array))
+            }
+            AggregateKind::PERCENTILECONT | AggregateKind::PERCENTILEDISC => {
+                Ok(DerivedType::new(RelType::Map))
+            }
+            _ => self.get_type(plan, args),
+        }
+    }
+
     /// Get aggregate functions that must be present on the local (Map) stage
     /// of two stage aggregation in order to calculate given aggregate (`self`)
     /// on the reduce stage.
@@ -102,6 +183,140 @@ impl AggregateKind {
             AggregateKind::MIN => vec![AggregateKind::MIN],
             AggregateKind::MAX => vec![AggregateKind::MAX],
             AggregateKind::GRCONCAT => vec![AggregateKind::GRCONCAT],
+            AggregateKind::BOOLAND => vec![AggregateKind::MIN],
+            AggregateKind::BOOLOR => vec![AggregateKind::MAX],
+            AggregateKind::VARSAMP
+            | AggregateKind::VARPOP
+            | AggregateKind::STDDEVSAMP
+            | AggregateKind::STDDEVPOP => vec![AggregateKind::VARIANCESTATE],
+            AggregateKind::ORDEREDGRCONCAT
+            | AggregateKind::ARRAYAGG
+            | AggregateKind::ORDEREDARRAYAGG
+            | AggregateKind::PERCENTILECONT
+            | AggregateKind::PERCENTILEDISC
+            | AggregateKind::ARRAYAGGMERGE
+            | AggregateKind::ORDEREDAGGMERGE
+            | AggregateKind::VARIANCESTATE
+            | AggregateKind::VARIANCEMERGE => vec![*self],
+        }
+    }
+
+    /// Whether the aggregate needs all the values of the group at once and
+    /// can't be calculated from the partial results of the local stage
+    /// (e.g. percentiles).
+    #[must_use]
+    pub fn is_holistic(&self) -> bool {
+        matches!(
+            self,
+            AggregateKind::PERCENTILECONT | AggregateKind::PERCENTILEDISC
+        )
+    }
+
+    /// Whether the aggregate is not implemented by the storage and is
+    /// calculated from its local aggregates even in a single stage
+    /// (e.g. the result of the ordered aggregates is built from their
+    /// state by the finalize function).
+    #[must_use]
+    pub fn is_composite(&self) -> bool {
+        matches!(
+            self,
+            AggregateKind::BOOLAND
+                | AggregateKind::BOOLOR
+                | AggregateKind::VARSAMP
+                | AggregateKind::VARPOP
+                | AggregateKind::STDDEVSAMP
+                | AggregateKind::STDDEVPOP
+                | AggregateKind::ORDEREDGRCONCAT
+                | AggregateKind::ORDEREDARRAYAGG
+                | AggregateKind::PERCENTILECONT
+                | AggregateKind::PERCENTILEDISC
+        )
+    }
+
+    /// Whether the aggregate is builtin in the storage SQL. Otherwise it is
+    /// implemented in lua (see `builtins.lua`) and its name must be quoted.
+    #[must_use]
+    pub fn is_system(&self) -> bool {
+        !matches!(
+            self,
+            AggregateKind::ORDEREDGRCONCAT
+                | AggregateKind::ARRAYAGG
+                | AggregateKind::ORDEREDARRAYAGG
+                | AggregateKind::PERCENTILECONT
+                | AggregateKind::PERCENTILEDISC
+                | AggregateKind::ARRAYAGGMERGE
+                | AggregateKind::ORDEREDAGGMERGE
+                | AggregateKind::VARIANCESTATE
+                | AggregateKind::VARIANCEMERGE
+        )
+    }
+
+    /// Create expression calculating the aggregate (`self`) from
+    /// its local aggregates kinds (see `get_local_aggregates_kinds`).
+    ///
+    /// # Arguments
+    /// * `aggregates` - map of { local aggregate kind -> expression }. On the reduce
+    ///   stage expressions are final aggregates over the local ones, in a single stage
+    ///   they are local aggregates over the original arguments.
+    /// * `args` - arguments of the original aggregate function.
+    fn combine_aggregates(
+        &self,
+        plan: &mut Plan,
+        aggregates: &HashMap<AggregateKind, NodeId>,
+        args: &[NodeId],
+    ) -> Result<NodeId, SbroadError> {
+        let get = |kind: AggregateKind| -> NodeId {
+            *aggregates
+                .get(&kind)
+                .unwrap_or_else(|| panic!("{kind} aggregate expr should exist for {self}"))
+        };
+        match self {
+            AggregateKind::AVG => {
+                let sum_aggr = get(AggregateKind::SUM);
+                let count_aggr = get(AggregateKind::COUNT);
+                plan.add_arithmetic_to_plan(sum_aggr, Arithmetic::Divide, count_aggr)
+            }
+            AggregateKind::BOOLAND => Ok(get(AggregateKind::MIN)),
+            AggregateKind::BOOLOR => Ok(get(AggregateKind::MAX)),
+            AggregateKind::VARSAMP
+            | AggregateKind::VARPOP
+            | AggregateKind::STDDEVSAMP
+            | AggregateKind::STDDEVPOP => {
+                let is_sample = matches!(self, AggregateKind::VARSAMP | AggregateKind::STDDEVSAMP);
+                let is_sample = plan.add_const(Value::Boolean(is_sample));
+                let name = if matches!(self, AggregateKind::VARSAMP | AggregateKind::VARPOP) {
+                    "variance_finalize"
+                } else {
+                    "stddev_finalize"
+                };
+                let fun =
+                    Function::new_stable(name.into(), DerivedType::new(RelType::Double), false);
+                let children = vec![get(AggregateKind::VARIANCESTATE), is_sample];
+                plan.add_stable_function(&fun, children, None)
+            }
+            AggregateKind::ORDEREDGRCONCAT
+            | AggregateKind::ORDEREDARRAYAGG
+            | AggregateKind::PERCENTILECONT
+            | AggregateKind::PERCENTILEDISC => {
+                let mut children = vec![get(*self)];
+                if let AggregateKind::ORDEREDGRCONCAT = self {
+                    let delimiter_id = *args
+                        .get(1)
+                        .expect("ordered string_agg should have a delimiter");
+                    children.push(SubtreeCloner::clone_subtree(plan, delimiter_id)?);
+                }
+                let fun = Function::new_stable(
+                    format_smolstr!("{self}_finalize"),
+                    self.get_type(plan, args)?,
+                    false,
+                );
+                plan.add_stable_function(&fun, children, None)
+            }
+            _ => {
+                unreachable!(
+                    "The only aggregates combined from local aggregates are AVG and composite ones, got {self}"
+                )
+            }
         }
     }
 
@@ -135,9 +350,21 @@ impl AggregateKind {
             (AggregateKind::COUNT | AggregateKind::AVG, AggregateKind::COUNT)
             | (AggregateKind::SUM | AggregateKind::AVG, AggregateKind::SUM) => AggregateKind::SUM,
             (AggregateKind::TOTAL, AggregateKind::TOTAL) => AggregateKind::TOTAL,
-            (AggregateKind::MIN, AggregateKind::MIN) => AggregateKind::MIN,
-            (AggregateKind::MAX, AggregateKind::MAX) => AggregateKind::MAX,
+            (AggregateKind::MIN | AggregateKind::BOOLAND, AggregateKind::MIN) => AggregateKind::MIN,
+            (AggregateKind::MAX | AggregateKind::BOOLOR, AggregateKind::MAX) => AggregateKind::MAX,
             (AggregateKind::GRCONCAT, AggregateKind::GRCONCAT) => AggregateKind::GRCONCAT,
+            (AggregateKind::ARRAYAGG, AggregateKind::ARRAYAGG) => AggregateKind::ARRAYAGGMERGE,
+            (AggregateKind::ORDEREDGRCONCAT, AggregateKind::ORDEREDGRCONCAT)
+            | (AggregateKind::ORDEREDARRAYAGG, AggregateKind::ORDEREDARRAYAGG) => {
+                AggregateKind::ORDEREDAGGMERGE
+            }
+            (
+                AggregateKind::VARSAMP
+                | AggregateKind::VARPOP
+                | AggregateKind::STDDEVSAMP
+                | AggregateKind::STDDEVPOP,
+                AggregateKind::VARIANCESTATE,
+            ) => AggregateKind::VARIANCEMERGE,
             (_, _) => {
                 return Err(SbroadError::Invalid(
                     Entity::Aggregate,
@@ -151,6 +378,17 @@ impl AggregateKind {
     }
 }
 
+/// Ordering of the aggregate input: `string_agg(a, ',' ORDER BY b DESC)`
+/// or `percentile_cont(0.5) WITHIN GROUP (ORDER BY b)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggregateOrderBy {
+    /// Expression used as the sorting key.
+    pub expr: NodeId,
+    pub is_desc: bool,
+    /// Whether the order was specified with `WITHIN GROUP` clause.
+    pub within_group: bool,
+}
+
 /// Pair of (aggregate kind, its position in the output).
 pub(crate) type PositionKind = (Position, AggregateKind);
 
@@ -273,6 +511,19 @@ impl Aggregate {
                     vec![ref_id]
                 }
             }
+            AggregateKind::ORDEREDGRCONCAT | AggregateKind::ORDEREDARRAYAGG => {
+                let Expression::ScalarFunction(ScalarFunction { children, .. }) =
+                    plan.get_expression_node(self.fun_id)?
+                else {
+                    unreachable!("Aggregate should reference expression by fun_id")
+                };
+
+                // The sorted local states are merged in the order of the direction.
+                let is_desc_id = *children
+                    .last()
+                    .expect("ordered aggregate should have the order direction");
+                vec![ref_id, SubtreeCloner::clone_subtree(plan, is_desc_id)?]
+            }
             _ => vec![ref_id],
         };
         let feature = if self.is_distinct {
@@ -280,13 +531,19 @@ impl Aggregate {
         } else {
             None
         };
-        let func_type = self.kind.get_type(plan, &children)?;
+        // Composite aggregates are replaced with the expression over the final
+        // aggregates, so every final aggregate keeps its own type.
+        let func_type = if self.kind.is_composite() {
+            final_kind.get_type(plan, &children)?
+        } else {
+            self.kind.get_type(plan, &children)?
+        };
         let final_aggr = ScalarFunction {
             name: final_kind.to_smolstr(),
             children,
             feature,
             func_type,
-            is_system: true,
+            is_system: final_kind.is_system(),
             volatility_type: super::expression::VolatilityType::Stable,
         };
         let aggr_id = plan.nodes.push(final_aggr.into());
@@ -338,23 +595,17 @@ impl Aggregate {
             }
         }
 
-        let final_expr_id = if final_aggregates.len() == 1 {
+        let final_expr_id = if final_aggregates.len() == 1 && !self.kind.is_composite() {
             *final_aggregates.values().next().unwrap()
         } else {
-            match self.kind {
-                AggregateKind::AVG => {
-                    let sum_aggr = *final_aggregates
-                        .get(&AggregateKind::SUM)
-                        .expect("SUM aggregate expr should exist for final AVG");
-                    let count_aggr = *final_aggregates
-                        .get(&AggregateKind::COUNT)
-                        .expect("COUNT aggregate expr should exist for final AVG");
-                    plan.add_arithmetic_to_plan(sum_aggr, Arithmetic::Divide, count_aggr)?
-                }
-                _ => {
-                    unreachable!("The only aggregate with multiple final aggregates is AVG")
-                }
-            }
+            let Expression::ScalarFunction(ScalarFunction { children, .. }) =
+                plan.get_expression_node(self.fun_id)?
+            else {
+                unreachable!("Aggregate should reference expression by fun_id")
+            };
+            let args = children.clone();
+            self.kind
+                .combine_aggregates(plan, &final_aggregates, &args)?
         };
         Ok(final_expr_id)
     }
@@ -515,18 +766,69 @@ impl Plan {
         arguments: &[NodeId],
         local_alias: &str,
     ) -> Result<NodeId, SbroadError> {
+        let local_fun_id = self.create_local_aggregate_function(kind, arguments)?;
+        let alias_id = self.nodes.add_alias(local_alias, local_fun_id)?;
+        Ok(alias_id)
+    }
+
+    fn create_local_aggregate_function(
+        &mut self,
+        kind: AggregateKind,
+        arguments: &[NodeId],
+    ) -> Result<NodeId, SbroadError> {
+        let func_type = kind.get_local_type(self, arguments)?;
+        // We can reuse aggregate expression between local aggregates, because
+        // all local aggregates are located inside the same motion subtree and we
+        // assume that each local aggregate does not need to modify its expression
         let fun: Function = Function {
             name: kind.to_smolstr(),
-            func_type: kind.get_type(self, arguments)?,
-            is_system: true,
+            func_type,
+            is_system: kind.is_system(),
             volatility: super::expression::VolatilityType::Stable,
         };
-        // We can reuse aggregate expression between local aggregates, because
-        // all local aggregates are located inside the same motion subtree and we
-        // assume that each local aggregate does not need to modify its expression
-        let local_fun_id = self.add_stable_function(&fun, arguments.to_vec(), None)?;
-        let alias_id = self.nodes.add_alias(local_alias, local_fun_id)?;
-        Ok(alias_id)
+        self.add_stable_function(&fun, arguments.to_vec(), None)
+    }
+
+    /// Replace composite aggregates (see `AggregateKind::is_composite`) under the
+    /// final nodes of the projection with the expressions over the aggregates the
+    /// storage supports. It is used when aggregation is done in a single stage.
+    ///
+    /// For example, `select bool_and(a) from global_t` is executed as
+    /// `select min(a) from global_t`.
+    pub fn lower_composite_aggregates(&mut self, final_proj_id: NodeId) -> Result<(), SbroadError> {
+        let (finals, _) = self.split_group_by(final_proj_id)?;
+        let mut finals_with_aggregates = Vec::with_capacity(finals.len());
+        for node_id in finals {
+            if !matches!(
+                self.get_relation_node(node_id)?,
+                Relational::NamedWindows(_)
+            ) {
+                finals_with_aggregates.push(node_id);
+            }
+        }
+        let aggrs = self.collect_aggregates(&finals_with_aggregates)?;
+        for aggr in aggrs.iter().filter(|aggr| aggr.kind.is_composite()) {
+            let Expression::ScalarFunction(ScalarFunction {
+                children: arguments,
+                ..
+            }) = self.get_expression_node(aggr.fun_id)?
+            else {
+                unreachable!("Aggregate should reference StableFunction by fun_id")
+            };
+            let arguments = arguments.clone();
+
+            let mut local_aggregates: HashMap<AggregateKind, NodeId> =
+                HashMap::with_capacity(AGGR_CAPACITY);
+            for kind in aggr.kind.get_local_aggregates_kinds() {
+                let local_fun_id = self.create_local_aggregate_function(kind, &arguments)?;
+                local_aggregates.insert(kind, local_fun_id);
+            }
+            let expr_id = aggr
+                .kind
+                .combine_aggregates(self, &local_aggregates, &arguments)?;
+            self.replace_expression(aggr.parent_expr, aggr.fun_id, expr_id)?;
+        }
+        Ok(())
     }
 
     /// Adds aggregates columns in `output_cols` for local `Projection`
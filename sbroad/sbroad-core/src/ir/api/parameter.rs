@@ -1,6 +1,7 @@
 use crate::errors::{Entity, SbroadError};
 use crate::frontend::sql::is_negative_number;
 use crate::ir::expression::{FunctionFeature, Substring};
+use crate::ir::node::ddl::MutDdl;
 use crate::ir::node::expression::{Expression, MutExpression};
 use crate::ir::node::relational::Relational;
 use crate::ir::node::ArithmeticExpr;
@@ -326,6 +327,19 @@ impl Plan {
         Ok(())
     }
 
+    /// Keeps the parameter values in the `CREATE TABLE AS` node, its query
+    /// is planned and bound on execution. Other DDL have no parameters.
+    ///
+    /// # Errors
+    /// - the plan is invalid
+    pub fn bind_ddl_params(&mut self, values: Vec<Value>) -> Result<(), SbroadError> {
+        let top_id = self.get_top()?;
+        if let MutDdl::CreateTableAs(create_table_as) = self.get_mut_ddl_node(top_id)? {
+            create_table_as.params = values;
+        }
+        Ok(())
+    }
+
     /// Substitute parameters to the plan.
     /// The purpose of this function is to find every `Expression::Parameter` node and replace it
     /// with `Expression::Constant` (under the row).
@@ -389,16 +403,23 @@ impl Plan {
                     arena_type: ArenaType::Arena64,
                 };
 
-                if let Node::Expression(Expression::LocalTimestamp(LocalTimestamp { precision })) =
-                    self.get_node(node_id)?
+                if let Node::Expression(Expression::LocalTimestamp(LocalTimestamp {
+                    precision,
+                    with_time_zone,
+                })) = self.get_node(node_id)?
                 {
-                    local_timestamps.push((node_id, *precision));
+                    local_timestamps.push((node_id, *precision, *with_time_zone));
                 }
             }
         }
 
-        for (node_id, precision) in local_timestamps {
-            let value = Self::create_datetime_value(local_datetime, precision);
+        for (node_id, precision, with_time_zone) in local_timestamps {
+            let timestamp = if with_time_zone {
+                datetime
+            } else {
+                local_datetime
+            };
+            let value = Self::create_datetime_value(timestamp, precision);
             self.nodes
                 .replace(node_id, Node64::Constant(Constant { value }))?;
         }
@@ -498,11 +519,11 @@ impl Plan {
                 ..
             })) = self.get_node(node_id)?
             {
-                let is_first_parameter_string = matches!(
-                    self.calculate_expression_type(children[0])?
-                        .unwrap_or(Type::String),
-                    Type::String,
-                );
+                // Binary strings support only the numeric forms of substring.
+                let is_first_parameter_string = match self.calculate_expression_type(children[0])? {
+                    Some(Type::Varbinary) => name == "substr",
+                    ty => matches!(ty.unwrap_or(Type::String), Type::String),
+                };
                 let is_second_parameter_number = matches!(
                     self.calculate_expression_type(children[1])?
                         .unwrap_or(Type::Integer),
@@ -1,11 +1,16 @@
-use crate::ir::value::Value;
+use crate::executor::engine::helpers::{SEARCH_PATH_PARAM, STATEMENT_TIMEOUT_PARAM};
+use crate::executor::hash::{str_to_bucket_id, ToHashString};
+use crate::ir::value::{TrivalentOrdering, Value};
 use crate::{
     errors::{Entity, SbroadError},
-    ir::node::{MutNode, NodeId},
+    ir::node::expression::Expression,
+    ir::node::{Alias, CreateTable, CreateTableAs, MutNode, NodeId},
     ir::{relation::Type as RelationType, Node, Plan},
 };
 use serde::{Deserialize, Serialize};
 use smol_str::{format_smolstr, SmolStr};
+use tarantool::msgpack;
+use tarantool::space::SpaceEngineType;
 
 use super::node::ddl::{Ddl, MutDdl};
 
@@ -14,6 +19,167 @@ pub struct ColumnDef {
     pub name: SmolStr,
     pub data_type: RelationType,
     pub is_nullable: bool,
+    /// SQL text of the `DEFAULT` expression calculated by the router
+    /// when the column is omitted in `INSERT`.
+    pub default: Option<SmolStr>,
+    /// Name of the sequence implicitly created for a `SERIAL` or an
+    /// identity column, its `DEFAULT` is a `nextval` call over it.
+    pub sequence: Option<SmolStr>,
+    /// `GENERATED ALWAYS` column, its value can't be specified explicitly.
+    #[serde(default)]
+    pub generated_always: bool,
+}
+
+/// Table constraint declared in `CREATE TABLE`, it is checked
+/// on the storages when the tuples are inserted or updated.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TableConstraint {
+    /// `CHECK (expr)`, the tuple is rejected if the expression is false.
+    Check {
+        name: SmolStr,
+        /// SQL text of the expression with the quoted column names.
+        expr: SmolStr,
+    },
+    /// `FOREIGN KEY (columns) REFERENCES parent (parent_columns)`.
+    ForeignKey {
+        name: SmolStr,
+        columns: Vec<SmolStr>,
+        parent: SmolStr,
+        /// Empty if the primary key of the parent table is referenced.
+        parent_columns: Vec<SmolStr>,
+    },
+}
+
+impl TableConstraint {
+    #[must_use]
+    pub fn name(&self) -> &SmolStr {
+        match self {
+            TableConstraint::Check { name, .. } | TableConstraint::ForeignKey { name, .. } => name,
+        }
+    }
+}
+
+/// Strategy of the declarative table partitioning.
+#[derive(
+    Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize, msgpack::Encode, msgpack::Decode,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionStrategy {
+    /// Every partition stores a continuous range of the key values.
+    Range,
+    /// Every partition stores an explicit list of the key values.
+    List,
+    /// Every partition stores the key values with the given remainder
+    /// of the key hash divided by the modulus.
+    Hash,
+}
+
+/// `PARTITION BY` clause of `CREATE TABLE`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct PartitionBy {
+    pub strategy: PartitionStrategy,
+    /// Name of the partition key column.
+    pub column: SmolStr,
+}
+
+/// Values of the partition key stored in a partition.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, msgpack::Encode, msgpack::Decode)]
+#[serde(rename_all = "snake_case")]
+pub enum PartitionBound {
+    /// `FOR VALUES FROM (from) TO (to)`, the upper bound is exclusive.
+    /// `None` stands for `MINVALUE` and `MAXVALUE`.
+    Range {
+        from: Option<Value>,
+        to: Option<Value>,
+    },
+    /// `FOR VALUES IN (...)`.
+    List { values: Vec<Value> },
+    /// `FOR VALUES WITH (MODULUS modulus, REMAINDER remainder)`.
+    Hash { modulus: u64, remainder: u64 },
+    /// `DEFAULT`: the values not stored by the other partitions.
+    Default,
+}
+
+impl PartitionBound {
+    /// Check if the partition key value belongs to the bound. `NULL`
+    /// keys belong to the default partition or, like in PostgreSQL,
+    /// have zero hash.
+    #[must_use]
+    pub fn contains(&self, value: &Value) -> bool {
+        if matches!(value, Value::Null) {
+            return matches!(
+                self,
+                PartitionBound::Default | PartitionBound::Hash { remainder: 0, .. }
+            );
+        }
+        match self {
+            PartitionBound::Range { from, to } => {
+                let above_from = from.as_ref().map_or(true, |from| {
+                    matches!(
+                        value.partial_cmp(from),
+                        Some(TrivalentOrdering::Greater | TrivalentOrdering::Equal)
+                    )
+                });
+                let below_to = to.as_ref().map_or(true, |to| {
+                    matches!(value.partial_cmp(to), Some(TrivalentOrdering::Less))
+                });
+                above_from && below_to
+            }
+            PartitionBound::List { values } => values
+                .iter()
+                .any(|v| matches!(value.partial_cmp(v), Some(TrivalentOrdering::Equal))),
+            PartitionBound::Hash { modulus, remainder } => {
+                // The key is hashed just like the sharding key, so the
+                // value must be cast to the partition key type beforehand.
+                str_to_bucket_id(&value.to_hash_string(), *modulus) - 1 == *remainder
+            }
+            PartitionBound::Default => false,
+        }
+    }
+
+    /// Check if the bound has common values with the other one.
+    /// Default bounds never overlap with the other bounds.
+    #[must_use]
+    pub fn overlaps(&self, other: &PartitionBound) -> bool {
+        match (self, other) {
+            (
+                PartitionBound::Range { from, to },
+                PartitionBound::Range {
+                    from: other_from,
+                    to: other_to,
+                },
+            ) => {
+                // Ranges [a, b) and [c, d) overlap when a < d and c < b.
+                let less = |lower: &Option<Value>, upper: &Option<Value>| match (lower, upper) {
+                    (Some(lower), Some(upper)) => {
+                        matches!(lower.partial_cmp(upper), Some(TrivalentOrdering::Less))
+                    }
+                    _ => true,
+                };
+                less(from, other_to) && less(other_from, to)
+            }
+            (PartitionBound::List { values }, other) | (other, PartitionBound::List { values }) => {
+                values.iter().any(|v| other.contains(v))
+            }
+            (
+                PartitionBound::Hash { modulus, remainder },
+                PartitionBound::Hash {
+                    modulus: other_modulus,
+                    remainder: other_remainder,
+                },
+            ) => {
+                // Some hash has both remainders when they are equal
+                // modulo the greatest common divisor of the moduli.
+                let (mut a, mut b) = (*modulus, *other_modulus);
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                remainder % a == other_remainder % a
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -36,8 +202,19 @@ pub enum SetParamScopeType {
 // TODO: Fill with actual values.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum SetParamValue {
-    NamedParam { name: SmolStr },
+    NamedParam {
+        name: SmolStr,
+    },
     TimeZone,
+    /// Schemas to look up unqualified table names in.
+    SearchPath {
+        schemas: Vec<SmolStr>,
+    },
+    /// Statement timeout in milliseconds, zero disables the timeout.
+    /// Option::None resets the timeout to the default.
+    StatementTimeout {
+        millis: Option<u64>,
+    },
 }
 
 impl SetParamValue {
@@ -46,6 +223,8 @@ impl SetParamValue {
         match self {
             SetParamValue::NamedParam { name } => name.clone(),
             SetParamValue::TimeZone => SmolStr::from("TimeZone"),
+            SetParamValue::SearchPath { .. } => SmolStr::from(SEARCH_PATH_PARAM),
+            SetParamValue::StatementTimeout { .. } => SmolStr::from(STATEMENT_TIMEOUT_PARAM),
         }
     }
 }
@@ -62,6 +241,118 @@ pub enum AlterSystemType {
     },
 }
 
+impl CreateTableAs {
+    /// Build the definition of the table from the output columns
+    /// of the planned query.
+    ///
+    /// # Errors
+    /// - the type of a column can't be derived
+    /// - duplicate column names
+    /// - primary or sharding key columns are not found
+    pub fn create_table(&self, query: &Plan) -> Result<CreateTable, SbroadError> {
+        let top_id = query.get_top()?;
+        let output_id = query.get_relation_node(top_id)?.output();
+        let mut format: Vec<ColumnDef> = Vec::new();
+        for col_id in query.get_row_list(output_id)? {
+            let column = query.get_expression_node(*col_id)?;
+            let Expression::Alias(Alias { name, .. }) = column else {
+                return Err(SbroadError::Invalid(
+                    Entity::Expression,
+                    Some(format_smolstr!("expected alias, got {column:?}")),
+                ));
+            };
+            let Some(data_type) = *column.calculate_type(query)?.get() else {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "can't derive type of column {name}, cast it explicitly."
+                    )),
+                ));
+            };
+            if format.iter().any(|c| &c.name == name) {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "column {name} is specified more than once."
+                    )),
+                ));
+            }
+            format.push(ColumnDef {
+                name: name.clone(),
+                data_type,
+                is_nullable: true,
+                default: None,
+                sequence: None,
+                generated_always: false,
+            });
+        }
+
+        let primary_key = if self.primary_key.is_empty() {
+            let first = format.first().ok_or_else(|| {
+                SbroadError::Invalid(Entity::Query, Some("query has no columns.".into()))
+            })?;
+            vec![first.name.clone()]
+        } else {
+            self.primary_key.clone()
+        };
+        for key in &primary_key {
+            let Some(column) = format.iter_mut().find(|c| &c.name == key) else {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!("Primary key column {key} not found.")),
+                ));
+            };
+            column.is_nullable = false;
+        }
+
+        let sharding_key = match &self.sharding_key {
+            None => {
+                if self.engine_type != SpaceEngineType::Memtx {
+                    return Err(SbroadError::Unsupported(
+                        Entity::Query,
+                        Some("global spaces can use only memtx engine".into()),
+                    ));
+                }
+                None
+            }
+            Some(keys) if keys.is_empty() => Some(primary_key.clone()),
+            Some(keys) => Some(keys.clone()),
+        };
+        for key in sharding_key.iter().flatten() {
+            let Some(column) = format.iter().find(|c| &c.name == key) else {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!("Sharding key column {key} not found.")),
+                ));
+            };
+            if !column.data_type.is_scalar() {
+                return Err(SbroadError::Invalid(
+                    Entity::Column,
+                    Some(format_smolstr!(
+                        "Sharding key column {key} is not of scalar type."
+                    )),
+                ));
+            }
+        }
+
+        Ok(CreateTable {
+            name: self.name.clone(),
+            format,
+            primary_key,
+            sharding_key,
+            engine_type: self.engine_type,
+            if_not_exists: self.if_not_exists,
+            // The table is filled right after creation, so it must
+            // already exist on all the storages.
+            wait_applied_globally: true,
+            timeout: self.timeout,
+            tier: self.tier.clone(),
+            partition_by: None,
+            constraints: Vec::new(),
+        })
+    }
+}
+
 impl Plan {
     /// Get DDL node from the plan arena.
     ///
@@ -2,21 +2,23 @@ use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Write as _};
 
 use itertools::Itertools;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value as JsonValue};
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 
-use crate::errors::{Entity, SbroadError};
+use crate::errors::{Action, Entity, SbroadError};
 use crate::executor::bucket::Buckets;
 use crate::executor::engine::helpers::to_user;
 use crate::executor::engine::Router;
 use crate::executor::Query;
-use crate::ir::explain::execution_info::BucketsInfo;
+use crate::ir::explain::execution_info::{BucketsInfo, DispatchStats, RuntimeStats};
 use crate::ir::expression::cast::Type as CastType;
 use crate::ir::expression::TrimKind;
 use crate::ir::node::{
     Alias, ArithmeticExpr, BoolExpr, Case, Cast, Constant, Delete, Having, Insert, Join,
-    Motion as MotionRel, NodeId, Reference, Row as RowExpr, ScalarFunction, ScanCte, ScanRelation,
-    ScanSubQuery, Selection, Trim, UnaryExpr, Update as UpdateRel, Values, ValuesRow,
+    LocalTimestamp, Motion as MotionRel, NodeId, RecursiveCte, Reference, Row as RowExpr,
+    ScalarFunction, ScanCte, ScanRelation, ScanSubQuery, Selection, Trim, UnaryExpr,
+    Update as UpdateRel, Values, ValuesRow,
 };
 use crate::ir::operator::{ConflictStrategy, JoinKind, OrderByElement, OrderByEntity, OrderByType};
 use crate::ir::transformation::redistribution::{
@@ -33,6 +35,25 @@ use super::relation::DerivedType;
 use super::tree::traversal::{LevelNode, PostOrder, EXPR_CAPACITY, REL_CAPACITY};
 use super::value::Value;
 
+/// Output format of the `EXPLAIN` command.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum ExplainFormat {
+    /// Indented tree of the plan nodes, one node per line.
+    #[default]
+    Text,
+    /// Single line JSON document.
+    Json,
+}
+
+/// Options of the `EXPLAIN` command.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ExplainOptions {
+    /// Execute the query and annotate the plan with the runtime statistics
+    /// (`EXPLAIN ANALYZE`). The result of the query is discarded.
+    pub analyze: bool,
+    pub format: ExplainFormat,
+}
+
 #[derive(Debug, PartialEq, Serialize)]
 enum ColExpr {
     Parentheses(Box<ColExpr>),
@@ -455,11 +476,14 @@ impl ColExpr {
                     let alias_expr = ColExpr::Unary(op.clone(), Box::new(child_expr));
                     stack.push((alias_expr, id));
                 }
-                Expression::LocalTimestamp(_) => {
-                    let expr = ColExpr::Column(
-                        "LocalTimestamp".to_string(),
-                        current_node.calculate_type(plan)?,
-                    );
+                Expression::LocalTimestamp(LocalTimestamp { with_time_zone, .. }) => {
+                    let name = if *with_time_zone {
+                        "CurrentTimestamp"
+                    } else {
+                        "LocalTimestamp"
+                    };
+                    let expr =
+                        ColExpr::Column(name.to_string(), current_node.calculate_type(plan)?);
                     stack.push((expr, id));
                 }
                 Expression::Parameter(_) => (),
@@ -1138,7 +1162,7 @@ struct InnerJoin {
 impl Display for InnerJoin {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let kind = match self.kind {
-            JoinKind::LeftOuter => {
+            JoinKind::LeftOuter | JoinKind::RightOuter | JoinKind::FullOuter => {
                 let mut s = self.kind.to_string();
                 s.push(' ');
                 s
@@ -1171,7 +1195,8 @@ enum ExplainNode {
     SubQuery(SubQuery),
     Motion(Motion),
     Cte(SmolStr, Ref),
-    Limit(u64),
+    RecursiveCteRef(SmolStr),
+    Limit(Option<u64>, u64),
     NamedWindows(NamedWindowsExplain),
 }
 
@@ -1187,6 +1212,7 @@ impl Display for ExplainNode {
                 format_smolstr!("windows: {windows_str}")
             }
             ExplainNode::Cte(s, r) => format_smolstr!("scan cte {s}({r})"),
+            ExplainNode::RecursiveCteRef(s) => format_smolstr!("scan recursive cte {s}"),
             ExplainNode::Delete(s) => format_smolstr!("delete \"{s}\""),
             ExplainNode::Except => "except".to_smolstr(),
             ExplainNode::InnerJoin(i) => i.to_smolstr(),
@@ -1207,21 +1233,66 @@ impl Display for ExplainNode {
             ExplainNode::Update(u) => u.to_smolstr(),
             ExplainNode::SubQuery(s) => s.to_smolstr(),
             ExplainNode::Motion(m) => m.to_smolstr(),
-            ExplainNode::Limit(l) => format_smolstr!("limit {l}"),
+            ExplainNode::Limit(Some(l), 0) => format_smolstr!("limit {l}"),
+            ExplainNode::Limit(Some(l), o) => format_smolstr!("limit {l} offset {o}"),
+            ExplainNode::Limit(None, o) => format_smolstr!("offset {o}"),
         };
 
         write!(f, "{s}")
     }
 }
 
+/// Actual statistics of the plan node gathered by `EXPLAIN ANALYZE`.
+#[derive(Debug, Default, Serialize)]
+struct ActualStats {
+    rows: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time_us: Option<u64>,
+}
+
+impl From<&DispatchStats> for ActualStats {
+    fn from(stats: &DispatchStats) -> Self {
+        ActualStats {
+            rows: stats.rows,
+            bytes: Some(stats.bytes),
+            time_us: Some(stats.time_us),
+        }
+    }
+}
+
+impl Display for ActualStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "(actual rows={}", self.rows)?;
+        if let Some(bytes) = self.bytes {
+            write!(f, ", bytes={bytes}")?;
+        }
+        if let Some(time_us) = self.time_us {
+            write!(f, ", time={}", format_time(time_us))?;
+        }
+        write!(f, ")")
+    }
+}
+
+fn format_time(time_us: u64) -> String {
+    format!("{}.{:03}ms", time_us / 1000, time_us % 1000)
+}
+
 /// Describe sql query (or subquery) as recursive type
 #[derive(Debug, Serialize)]
 struct ExplainTreePart {
     /// Level helps to detect count of idents
     #[serde(skip_serializing)]
     level: usize,
+    /// Plan node described by the part.
+    #[serde(skip_serializing)]
+    node_id: Option<NodeId>,
     /// Current node of sql query
     current: Option<ExplainNode>,
+    /// Actual statistics of the node (`EXPLAIN ANALYZE`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    actual: Option<ActualStats>,
     /// Children nodes of current sql node
     children: Vec<ExplainTreePart>,
 }
@@ -1235,8 +1306,10 @@ impl PartialEq for ExplainTreePart {
 impl Display for ExplainTreePart {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut s = String::new();
-        if let Some(c) = &self.current {
-            writeln!(s, "{}", &c.to_string())?;
+        match (&self.current, &self.actual) {
+            (Some(c), Some(actual)) => writeln!(s, "{c} {actual}")?,
+            (Some(c), None) => writeln!(s, "{c}")?,
+            _ => {}
         }
 
         let ident = (0..=self.level).map(|_| "    ").collect::<String>();
@@ -1254,7 +1327,9 @@ impl Default for ExplainTreePart {
     fn default() -> Self {
         Self {
             level: 0,
+            node_id: None,
             current: None,
+            actual: None,
             children: Vec::with_capacity(200),
         }
     }
@@ -1264,10 +1339,91 @@ impl ExplainTreePart {
     fn with_level(level: usize) -> Self {
         ExplainTreePart {
             level,
+            node_id: None,
             current: None,
+            actual: None,
             children: Vec::with_capacity(100),
         }
     }
+
+    fn to_json(&self) -> JsonValue {
+        let children: Vec<JsonValue> = self.children.iter().map(Self::to_json).collect();
+        let mut node = json!({
+            "node": self.current.as_ref().map(ToString::to_string),
+            "children": children,
+        });
+        if let Some(actual) = &self.actual {
+            node["actual"] = json!(actual);
+        }
+        node
+    }
+}
+
+/// Runtime statistics of the executed query (`EXPLAIN ANALYZE`).
+#[derive(Debug)]
+struct ExecutionStats {
+    stats: RuntimeStats,
+    /// Explain lines of the materialized motions.
+    motion_labels: HashMap<NodeId, SmolStr>,
+}
+
+impl ExecutionStats {
+    fn dispatches(&self) -> impl Iterator<Item = (SmolStr, &DispatchStats)> + '_ {
+        let motions = self.stats.motions.iter().map(|(id, stats)| {
+            let label = self
+                .motion_labels
+                .get(id)
+                .cloned()
+                .unwrap_or_else(|| "motion".into());
+            (label, stats)
+        });
+        let top = self
+            .stats
+            .top
+            .iter()
+            .map(|stats| (SmolStr::from("final dispatch"), stats));
+        motions.chain(top)
+    }
+}
+
+impl Display for ExecutionStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{:4}rows={}, time={}",
+            "",
+            self.stats.rows,
+            format_time(self.stats.time_us)
+        )?;
+        for (label, dispatch) in self.dispatches() {
+            writeln!(
+                f,
+                "{:4}{label}: rows={}, bytes={}, time={}",
+                "",
+                dispatch.rows,
+                dispatch.bytes,
+                format_time(dispatch.time_us)
+            )?;
+            for rs in &dispatch.replicasets {
+                write!(
+                    f,
+                    "{:8}replicaset \"{}\": rows={}, bytes={}",
+                    "", rs.replicaset, rs.rows, rs.bytes
+                )?;
+                if let Some(storage) = &rs.storage {
+                    let cache = if storage.cache_hit { "hit" } else { "miss" };
+                    write!(
+                        f,
+                        ", cache {cache}, storage time={}, vdbe opcodes={}",
+                        format_time(storage.time_us),
+                        storage.vdbe_opcodes
+                    )?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Default)]
@@ -1278,10 +1434,16 @@ struct FullExplain {
     subqueries: Vec<ExplainTreePart>,
     /// Windows under Projection.
     windows: Vec<ExplainTreePart>,
+    /// Local update of the `INSERT .. ON CONFLICT DO UPDATE` clause.
+    on_conflict: Option<ExplainTreePart>,
+    /// Columns of the DML `RETURNING` clause.
+    returning: Option<Projection>,
     /// Options imposed during query execution
     exec_options: Vec<(OptionKind, Value)>,
     /// Info related to plan execution
     buckets_info: Option<BucketsInfo>,
+    /// Runtime statistics of the query execution (`EXPLAIN ANALYZE`).
+    execution_stats: Option<ExecutionStats>,
 }
 
 fn buckets_repr(buckets: &Buckets, bucket_count: u64) -> String {
@@ -1322,6 +1484,22 @@ fn buckets_repr(buckets: &Buckets, bucket_count: u64) -> String {
     }
 }
 
+/// Buckets estimation representation along with the flag
+/// whether the estimation is exact.
+fn buckets_estimate(info: &BucketsInfo) -> Option<(String, bool)> {
+    match info {
+        BucketsInfo::Unknown => None,
+        BucketsInfo::Calculated(calculated) => {
+            let repr = buckets_repr(&calculated.buckets, calculated.bucket_count);
+            // For buckets ANY and ALL there is no sense to handle in the
+            // output the case when bucket count is not exact.
+            let is_exact =
+                matches!(calculated.buckets, Buckets::Any | Buckets::All) || calculated.is_exact;
+            Some((repr, is_exact))
+        }
+    }
+}
+
 impl Display for FullExplain {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut s = self.main_query.to_string();
@@ -1334,6 +1512,19 @@ impl Display for FullExplain {
             writeln!(s, "window ${pos}:")?;
             s.push_str(&window.to_string());
         }
+        if let Some(on_conflict) = &self.on_conflict {
+            writeln!(s, "on conflict:")?;
+            s.push_str(&on_conflict.to_string());
+        }
+        if let Some(returning) = &self.returning {
+            let cols = returning
+                .cols
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(", ");
+            writeln!(s, "returning ({cols})")?;
+        }
         if !self.exec_options.is_empty() {
             writeln!(s, "execution options:")?;
             for opt in &self.exec_options {
@@ -1341,20 +1532,16 @@ impl Display for FullExplain {
             }
         }
         if let Some(info) = &self.buckets_info {
-            match info {
-                BucketsInfo::Unknown => writeln!(s, "buckets = unknown")?,
-                BucketsInfo::Calculated(calculated) => {
-                    let repr = buckets_repr(&calculated.buckets, calculated.bucket_count);
-                    // For buckets ANY and ALL there is no sense to handle in the
-                    // output the case when bucket count is not exact.
-                    match calculated.buckets {
-                        Buckets::Any | Buckets::All => writeln!(s, "buckets = {repr}",)?,
-                        _ if calculated.is_exact => writeln!(s, "buckets = {repr}",)?,
-                        _ => writeln!(s, "buckets <= {repr}",)?,
-                    }
-                }
+            match buckets_estimate(info) {
+                None => writeln!(s, "buckets = unknown")?,
+                Some((repr, true)) => writeln!(s, "buckets = {repr}")?,
+                Some((repr, false)) => writeln!(s, "buckets <= {repr}")?,
             }
         }
+        if let Some(stats) = &self.execution_stats {
+            writeln!(s, "execution statistics:")?;
+            write!(s, "{stats}")?;
+        }
 
         write!(f, "{s}")
     }
@@ -1416,6 +1603,7 @@ impl FullExplain {
         let mut dft_post = PostOrder::with_capacity(|node| ir.nodes.rel_iter(node), REL_CAPACITY);
         for LevelNode(level, id) in dft_post.iter(top_id) {
             let mut current_node = ExplainTreePart::with_level(level);
+            current_node.node_id = Some(id);
             let node = ir.get_relation_node(id)?;
 
             current_node.current = match &node {
@@ -1494,6 +1682,20 @@ impl FullExplain {
                     );
                     Some(ExplainNode::Scan(s))
                 }
+                Relational::ScanCte(ScanCte {
+                    alias,
+                    recursive:
+                        Some(RecursiveCte {
+                            is_self_reference: true,
+                            ..
+                        }),
+                    ..
+                }) => {
+                    // The anchor part under the self-reference is shown
+                    // in the CTE subquery, so skip it here.
+                    stack.pop().expect("CTE node must have exactly one child");
+                    Some(ExplainNode::RecursiveCteRef(alias.clone()))
+                }
                 Relational::ScanCte(ScanCte { alias, .. }) => {
                     let child = stack.pop().expect("CTE node must have exactly one child");
                     let existing_pos = result.subqueries.iter().position(|sq| *sq == child);
@@ -1687,7 +1889,7 @@ impl FullExplain {
 
                     Some(ExplainNode::Delete(relation.to_smolstr()))
                 }
-                Relational::Limit(Limit { limit, .. }) => {
+                Relational::Limit(Limit { limit, offset, .. }) => {
                     let child = stack.pop().ok_or_else(|| {
                         SbroadError::UnexpectedNumberOfValues(
                             "Limit node must have exactly one child".into(),
@@ -1696,7 +1898,7 @@ impl FullExplain {
 
                     current_node.children.push(child);
 
-                    Some(ExplainNode::Limit(*limit))
+                    Some(ExplainNode::Limit(*limit, *offset))
                 }
             };
 
@@ -1705,12 +1907,118 @@ impl FullExplain {
         result.main_query = stack
             .pop()
             .ok_or_else(|| SbroadError::NotFound(Entity::Node, "that is explain top".into()))?;
+        if let Relational::Insert(Insert {
+            conflict_update: Some(update_id),
+            ..
+        }) = ir.get_relation_node(top_id)?
+        {
+            result.on_conflict = Some(FullExplain::new(ir, *update_id)?.main_query);
+        }
+        if ir.get_relation_node(top_id)?.is_dml() {
+            if let Some(proj_id) = ir.dml_returning(top_id)? {
+                let output = ir.get_relation_node(proj_id)?.output();
+                result.returning = Some(Projection::new(ir, output, &HashMap::new())?);
+            }
+        }
         Ok(result)
     }
 
     fn add_execution_info(&mut self, info: BucketsInfo) {
         self.buckets_info = Some(info);
     }
+
+    /// Annotate the materialized motions and the plan top with
+    /// the actual statistics of the query execution.
+    fn add_runtime_stats(&mut self, stats: RuntimeStats) {
+        let mut motion_labels = HashMap::with_capacity(stats.motions.len());
+        let mut parts: Vec<&mut ExplainTreePart> = vec![&mut self.main_query];
+        parts.extend(self.subqueries.iter_mut());
+        parts.extend(self.windows.iter_mut());
+        parts.extend(self.on_conflict.iter_mut());
+        while let Some(part) = parts.pop() {
+            if let (Some(id), Some(current)) = (part.node_id, &part.current) {
+                if let Some(dispatch) = stats.motion(id) {
+                    motion_labels.insert(id, current.to_smolstr());
+                    part.actual = Some(ActualStats::from(dispatch));
+                }
+            }
+            parts.extend(part.children.iter_mut());
+        }
+
+        if self.main_query.actual.is_none() {
+            let mut actual = stats
+                .top
+                .as_ref()
+                .map(ActualStats::from)
+                .unwrap_or_default();
+            actual.rows = stats.rows;
+            self.main_query.actual = Some(actual);
+        }
+        self.execution_stats = Some(ExecutionStats {
+            stats,
+            motion_labels,
+        });
+    }
+
+    fn to_json(&self) -> JsonValue {
+        let parts_to_json = |parts: &[ExplainTreePart]| -> Vec<JsonValue> {
+            parts.iter().map(ExplainTreePart::to_json).collect()
+        };
+        let mut explain = json!({
+            "plan": self.main_query.to_json(),
+            "subqueries": parts_to_json(&self.subqueries),
+            "windows": parts_to_json(&self.windows),
+        });
+        if let Some(on_conflict) = &self.on_conflict {
+            explain["on_conflict"] = on_conflict.to_json();
+        }
+        if let Some(returning) = &self.returning {
+            let cols: Vec<String> = returning.cols.iter().map(ToString::to_string).collect();
+            explain["returning"] = json!(cols);
+        }
+        let mut options = serde_json::Map::with_capacity(self.exec_options.len());
+        for (kind, value) in &self.exec_options {
+            let value = match value {
+                Value::Unsigned(value) => json!(value),
+                value => json!(value.to_string()),
+            };
+            options.insert(kind.to_string(), value);
+        }
+        explain["execution_options"] = JsonValue::Object(options);
+        if let Some(info) = &self.buckets_info {
+            explain["buckets"] = match buckets_estimate(info) {
+                None => json!("unknown"),
+                Some((repr, is_exact)) => json!({ "estimate": repr, "exact": is_exact }),
+            };
+        }
+        if let Some(execution) = &self.execution_stats {
+            let dispatches: Vec<JsonValue> = execution
+                .dispatches()
+                .map(|(label, stats)| json!({ "node": label, "stats": stats }))
+                .collect();
+            explain["execution"] = json!({
+                "rows": execution.stats.rows,
+                "time_us": execution.stats.time_us,
+                "dispatches": dispatches,
+            });
+        }
+        explain
+    }
+
+    fn format(&self, format: ExplainFormat) -> Result<SmolStr, SbroadError> {
+        match format {
+            ExplainFormat::Text => Ok(self.to_smolstr()),
+            ExplainFormat::Json => serde_json::to_string(&self.to_json())
+                .map(SmolStr::from)
+                .map_err(|e| {
+                    SbroadError::FailedTo(
+                        Action::Serialize,
+                        Some(Entity::Plan),
+                        format_smolstr!("explain to JSON: {e}"),
+                    )
+                }),
+        }
+    }
 }
 
 impl Plan {
@@ -1722,20 +2030,33 @@ impl Plan {
     pub fn as_explain(&self) -> Result<SmolStr, SbroadError> {
         let top_id = self.get_top()?;
         let explain = FullExplain::new(self, top_id)?;
-        Ok(explain.to_smolstr())
+        explain.format(self.explain_options().format)
     }
 }
 
 impl<C: Router> Query<'_, C> {
+    /// Display query explain. `EXPLAIN ANALYZE` executes the query
+    /// and annotates the explain with the runtime statistics.
+    ///
+    /// # Errors
+    /// - Failed to build explain
+    /// - Failed to execute the query
     pub fn as_explain(&mut self) -> Result<SmolStr, SbroadError> {
         let plan = self.get_exec_plan().get_ir_plan();
         let top_id = plan.get_top()?;
+        let options = plan.explain_options().clone();
         let mut explain = FullExplain::new(plan, top_id)?;
 
         let info = BucketsInfo::new_from_query(self)?;
         explain.add_execution_info(info);
 
-        Ok(explain.to_smolstr())
+        if options.analyze {
+            // Execution consumes the plan, so the explain is built beforehand.
+            let stats = self.execute_with_stats()?;
+            explain.add_runtime_stats(stats);
+        }
+
+        explain.format(options.format)
     }
 }
 
@@ -1743,4 +2064,4 @@ impl<C: Router> Query<'_, C> {
 #[cfg(test)]
 mod tests;
 
-mod execution_info;
+pub mod execution_info;
@@ -1,4 +1,10 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
 use ahash::AHashSet;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
 
 use crate::{
     errors::SbroadError,
@@ -206,3 +212,117 @@ impl BucketsInfo {
         Ok(can_estimate)
     }
 }
+
+/// Statistics of the local execution of the dispatched plan
+/// reported by the storage for `EXPLAIN ANALYZE`.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct StorageStats {
+    /// True if the statement was found in the storage statement cache.
+    pub cache_hit: bool,
+    /// Time of the local execution in microseconds.
+    pub time_us: u64,
+    /// Number of opcodes in the compiled VDBE program.
+    pub vdbe_opcodes: u64,
+}
+
+/// Statistics of the replicaset answered the dispatched request.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ReplicasetStats {
+    pub replicaset: SmolStr,
+    /// Number of the rows returned by the replicaset.
+    pub rows: u64,
+    /// Size of the response in bytes.
+    pub bytes: u64,
+    /// Statistics reported by the storage, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub storage: Option<StorageStats>,
+}
+
+/// Statistics of the single dispatch: either materialization of
+/// a motion or execution of the final plan subtree.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct DispatchStats {
+    /// Number of the rows produced by the dispatched subtree.
+    pub rows: u64,
+    /// Total size of the replicaset responses in bytes.
+    pub bytes: u64,
+    /// Time spent on the router in microseconds.
+    pub time_us: u64,
+    pub replicasets: Vec<ReplicasetStats>,
+}
+
+/// Runtime statistics of the query execution gathered by `EXPLAIN ANALYZE`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RuntimeStats {
+    /// Materialized motions in the order of their execution.
+    pub motions: Vec<(NodeId, DispatchStats)>,
+    /// Dispatch of the final plan subtree. It is absent when the
+    /// result is taken from the virtual table of the top motion.
+    pub top: Option<DispatchStats>,
+    /// Number of the rows returned by the query.
+    pub rows: u64,
+    /// Total execution time in microseconds.
+    pub time_us: u64,
+    /// Replicasets answered since the last finished dispatch.
+    #[serde(skip)]
+    pending: Vec<ReplicasetStats>,
+}
+
+/// Shared handle to the statistics filled in while the query is executed.
+pub type RuntimeStatsRef = Rc<RefCell<RuntimeStats>>;
+
+fn micros(duration: Duration) -> u64 {
+    u64::try_from(duration.as_micros()).unwrap_or(u64::MAX)
+}
+
+impl StorageStats {
+    #[must_use]
+    pub fn new(cache_hit: bool, time: Duration, vdbe_opcodes: u64) -> Self {
+        StorageStats {
+            cache_hit,
+            time_us: micros(time),
+            vdbe_opcodes,
+        }
+    }
+}
+
+impl RuntimeStats {
+    /// Record the response of the replicaset to the current dispatch.
+    pub fn add_replicaset(&mut self, stats: ReplicasetStats) {
+        self.pending.push(stats);
+    }
+
+    fn finish_dispatch(&mut self, rows: u64, time: Duration) -> DispatchStats {
+        let replicasets = std::mem::take(&mut self.pending);
+        DispatchStats {
+            rows,
+            bytes: replicasets.iter().map(|rs| rs.bytes).sum(),
+            time_us: micros(time),
+            replicasets,
+        }
+    }
+
+    /// Finish the dispatch materializing the motion.
+    pub fn finish_motion(&mut self, motion_id: NodeId, rows: u64, time: Duration) {
+        let stats = self.finish_dispatch(rows, time);
+        self.motions.push((motion_id, stats));
+    }
+
+    /// Finish the dispatch of the final plan subtree.
+    pub fn finish_top(&mut self, rows: u64, time: Duration) {
+        self.top = Some(self.finish_dispatch(rows, time));
+    }
+
+    /// Finish the whole query execution.
+    pub fn finish(&mut self, rows: u64, time: Duration) {
+        self.rows = rows;
+        self.time_us = micros(time);
+    }
+
+    #[must_use]
+    pub fn motion(&self, motion_id: NodeId) -> Option<&DispatchStats> {
+        self.motions
+            .iter()
+            .find_map(|(id, stats)| (*id == motion_id).then_some(stats))
+    }
+}
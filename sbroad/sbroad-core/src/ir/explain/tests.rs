@@ -532,7 +532,10 @@ fn check_buckets_repr() {
     assert_eq!("[]", buckets_repr(&Buckets::Filtered(collection!()), bc));
 }
 
+mod analyze;
 mod cast_constants;
 mod concat;
 mod delete;
+mod on_conflict;
 mod query_explain;
+mod returning;
@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+use pretty_assertions::assert_eq;
+use serde_json::Value as JsonValue;
+
+use crate::executor::{engine::mock::RouterRuntimeMock, Query};
+use crate::ir::explain::execution_info::{ReplicasetStats, RuntimeStats, StorageStats};
+use crate::ir::explain::{ExplainFormat, ExplainOptions, FullExplain};
+use crate::ir::transformation::helpers::sql_to_optimized_ir;
+use crate::ir::Plan;
+
+const MOTION_JOIN: &str = r#"SELECT "t1"."FIRST_NAME"
+FROM (SELECT "id", "FIRST_NAME" FROM "test_space" WHERE "id" = 3) as "t1"
+    JOIN (SELECT "identification_number", "product_code" FROM "hash_testing") as "t2" ON "t1"."id"="t2"."identification_number"
+WHERE "t2"."product_code" = '123'"#;
+
+fn explain_with_stats(plan: &Plan) -> FullExplain {
+    let motion_id = *plan.slices.slice(0).unwrap().position(0).unwrap();
+
+    let mut stats = RuntimeStats::default();
+    stats.add_replicaset(ReplicasetStats {
+        replicaset: "r1".into(),
+        rows: 2,
+        bytes: 40,
+        storage: Some(StorageStats::new(false, Duration::from_micros(1500), 12)),
+    });
+    stats.add_replicaset(ReplicasetStats {
+        replicaset: "r2".into(),
+        rows: 1,
+        bytes: 20,
+        storage: None,
+    });
+    stats.finish_motion(motion_id, 3, Duration::from_micros(2250));
+    stats.add_replicaset(ReplicasetStats {
+        replicaset: "r1".into(),
+        rows: 1,
+        bytes: 15,
+        storage: None,
+    });
+    stats.finish_top(1, Duration::from_micros(1000));
+    stats.finish(1, Duration::from_micros(3500));
+
+    let mut explain = FullExplain::new(plan, plan.get_top().unwrap()).unwrap();
+    explain.add_runtime_stats(stats);
+    explain
+}
+
+#[test]
+fn explain_options_parsing() {
+    let cases = [
+        (r#"EXPLAIN SELECT "a" FROM "t""#, ExplainOptions::default()),
+        (
+            r#"EXPLAIN ANALYZE SELECT "a" FROM "t""#,
+            ExplainOptions {
+                analyze: true,
+                format: ExplainFormat::Text,
+            },
+        ),
+        (
+            r#"EXPLAIN (ANALYZE, FORMAT JSON) SELECT "a" FROM "t""#,
+            ExplainOptions {
+                analyze: true,
+                format: ExplainFormat::Json,
+            },
+        ),
+        (
+            r#"EXPLAIN (ANALYZE FALSE, FORMAT TEXT) SELECT "a" FROM "t""#,
+            ExplainOptions::default(),
+        ),
+        (
+            r#"EXPLAIN (FORMAT JSON) SELECT "a" FROM "t""#,
+            ExplainOptions {
+                analyze: false,
+                format: ExplainFormat::Json,
+            },
+        ),
+    ];
+
+    for (sql, expected) in cases {
+        let plan = sql_to_optimized_ir(sql, vec![]);
+        assert!(plan.is_explain(), "{sql}");
+        assert_eq!(plan.explain_options(), &expected, "{sql}");
+    }
+}
+
+#[test]
+fn explain_analyze_text() {
+    let plan = sql_to_optimized_ir(MOTION_JOIN, vec![]);
+    let explain = explain_with_stats(&plan);
+
+    insta::assert_snapshot!(explain.to_string(), @r#"
+    projection ("t1"."FIRST_NAME"::string -> "FIRST_NAME") (actual rows=1, bytes=15, time=1.000ms)
+        selection ROW("t2"."product_code"::string) = ROW('123'::string)
+            join on ROW("t1"."id"::unsigned) = ROW("t2"."identification_number"::integer)
+                scan "t1"
+                    projection ("test_space"."id"::unsigned -> "id", "test_space"."FIRST_NAME"::string -> "FIRST_NAME")
+                        selection ROW("test_space"."id"::unsigned) = ROW(3::unsigned)
+                            scan "test_space"
+                motion [policy: segment([ref("identification_number")])] (actual rows=3, bytes=60, time=2.250ms)
+                    scan "t2"
+                        projection ("hash_testing"."identification_number"::integer -> "identification_number", "hash_testing"."product_code"::string -> "product_code")
+                            scan "hash_testing"
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    execution statistics:
+        rows=1, time=3.500ms
+        motion [policy: segment([ref("identification_number")])]: rows=3, bytes=60, time=2.250ms
+            replicaset "r1": rows=2, bytes=40, cache miss, storage time=1.500ms, vdbe opcodes=12
+            replicaset "r2": rows=1, bytes=20
+        final dispatch: rows=1, bytes=15, time=1.000ms
+            replicaset "r1": rows=1, bytes=15
+    "#);
+}
+
+#[test]
+fn explain_analyze_json() {
+    let plan = sql_to_optimized_ir(MOTION_JOIN, vec![]);
+    let explain = explain_with_stats(&plan);
+
+    let json: JsonValue =
+        serde_json::from_str(&explain.format(ExplainFormat::Json).unwrap()).unwrap();
+
+    let top = &json["plan"];
+    assert_eq!(
+        top["node"],
+        r#"projection ("t1"."FIRST_NAME"::string -> "FIRST_NAME")"#
+    );
+    assert_eq!(
+        top["actual"],
+        serde_json::json!({ "rows": 1, "bytes": 15, "time_us": 1000 })
+    );
+
+    let join = &top["children"][0]["children"][0];
+    let motion = &join["children"][1];
+    assert_eq!(
+        motion["node"],
+        r#"motion [policy: segment([ref("identification_number")])]"#
+    );
+    assert_eq!(
+        motion["actual"],
+        serde_json::json!({ "rows": 3, "bytes": 60, "time_us": 2250 })
+    );
+    assert!(join["children"][0].get("actual").is_none());
+
+    assert_eq!(json["execution_options"]["sql_motion_row_max"], 5000);
+    assert_eq!(json["execution"]["rows"], 1);
+    assert_eq!(json["execution"]["time_us"], 3500);
+
+    let dispatches = json["execution"]["dispatches"].as_array().unwrap();
+    assert_eq!(dispatches.len(), 2);
+    assert_eq!(dispatches[0]["node"], motion["node"]);
+    assert_eq!(
+        dispatches[0]["stats"]["replicasets"][0],
+        serde_json::json!({
+            "replicaset": "r1",
+            "rows": 2,
+            "bytes": 40,
+            "storage": { "cache_hit": false, "time_us": 1500, "vdbe_opcodes": 12 },
+        })
+    );
+    assert_eq!(dispatches[1]["node"], "final dispatch");
+    assert_eq!(dispatches[1]["stats"]["rows"], 1);
+}
+
+#[test]
+fn explain_format_json_without_analyze() {
+    let sql = r#"EXPLAIN (FORMAT JSON) SELECT * FROM t3 WHERE a = 'kek'"#;
+    let metadata = &RouterRuntimeMock::new();
+    let mut query = Query::new(metadata, sql, vec![]).unwrap();
+
+    let json: JsonValue = serde_json::from_str(&query.to_explain().unwrap()).unwrap();
+    assert_eq!(
+        json["plan"]["node"],
+        r#"projection ("t3"."a"::string -> "a", "t3"."b"::integer -> "b")"#
+    );
+    assert_eq!(json["buckets"]["estimate"], "[1610]");
+    assert_eq!(json["buckets"]["exact"], true);
+    assert!(json.get("execution").is_none());
+}
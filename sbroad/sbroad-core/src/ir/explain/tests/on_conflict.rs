@@ -0,0 +1,74 @@
+use super::*;
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
+
+fn on_conflict_error(sql: &str) -> String {
+    let metadata = &RouterConfigurationMock::new();
+    AbstractSyntaxTree::transform_into_plan(sql, metadata)
+        .unwrap_err()
+        .to_string()
+}
+
+#[test]
+fn insert_on_conflict_do_update() {
+    let sql = r#"INSERT INTO "test_space"("id", "sys_op") VALUES (1, 1)
+        ON CONFLICT ("id") DO UPDATE SET "sys_op" = "test_space"."sys_op" + "excluded"."sys_op""#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    let explain = plan.as_explain().unwrap();
+    assert!(explain.starts_with("insert \"test_space\" on conflict: update\n"));
+    let (_, on_conflict) = explain
+        .split_once("on conflict:\n")
+        .expect("ON CONFLICT DO UPDATE subtree must be explained");
+    assert!(on_conflict.starts_with("update \"test_space\"\n"));
+    assert!(on_conflict.contains("motion [policy: local]"));
+    assert!(on_conflict.contains("join on"));
+    assert!(on_conflict.contains("scan \"excluded\""));
+}
+
+#[test]
+fn insert_on_conflict_do_update_with_filter() {
+    let sql = r#"INSERT INTO "test_space"("id", "sys_op") VALUES (1, 1)
+        ON CONFLICT DO UPDATE SET "FIRST_NAME" = 'updated'
+        WHERE "excluded"."sys_op" > "test_space"."sys_op""#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    let explain = plan.as_explain().unwrap();
+    let (_, on_conflict) = explain.split_once("on conflict:\n").unwrap();
+    assert!(on_conflict.contains("selection ROW(\"excluded\".\"sys_op\"::unsigned) > ROW(\"test_space\".\"sys_op\"::unsigned)"));
+}
+
+#[test]
+fn on_conflict_do_update_global_table() {
+    let sql = r#"INSERT INTO "global_t" VALUES (1, 1) ON CONFLICT DO UPDATE SET "b" = 2"#;
+    assert_eq!(
+        "unsupported query: ON CONFLICT DO UPDATE is not supported for global tables",
+        on_conflict_error(sql)
+    );
+}
+
+#[test]
+fn on_conflict_wrong_target() {
+    let sql = r#"INSERT INTO "t" VALUES (1, 1, 1, 1) ON CONFLICT ("a") DO UPDATE SET "c" = 2"#;
+    assert_eq!(
+        "unsupported query: ON CONFLICT target must be the primary key of table \"t\"",
+        on_conflict_error(sql)
+    );
+}
+
+#[test]
+fn on_conflict_update_primary_key() {
+    let sql = r#"INSERT INTO "t" VALUES (1, 1, 1, 1) ON CONFLICT ("b") DO UPDATE SET "b" = 2"#;
+    assert_eq!(
+        "invalid query: it is illegal to update primary key column: \"b\"",
+        on_conflict_error(sql)
+    );
+}
+
+#[test]
+fn on_conflict_update_sharding_key() {
+    let sql = r#"INSERT INTO "t" VALUES (1, 1, 1, 1) ON CONFLICT DO UPDATE SET "a" = 2"#;
+    assert_eq!(
+        "unsupported query: ON CONFLICT DO UPDATE of sharding key column \"a\"",
+        on_conflict_error(sql)
+    );
+}
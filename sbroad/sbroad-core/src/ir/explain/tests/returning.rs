@@ -0,0 +1,76 @@
+use super::*;
+use crate::executor::engine::mock::RouterConfigurationMock;
+use crate::frontend::sql::ast::AbstractSyntaxTree;
+use crate::frontend::Ast;
+
+#[test]
+fn insert_returning() {
+    let sql = r#"INSERT INTO "test_space"("id", "FIRST_NAME") VALUES (1, 'test') RETURNING "id", "FIRST_NAME" || '!' AS "name""#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    insert "test_space" on conflict: fail
+        motion [policy: segment([ref("COLUMN_1")])]
+            values
+                value row (data=ROW(1::unsigned, 'test'::string))
+    returning ("id"::unsigned -> "id", ROW("FIRST_NAME"::string) || ROW('!'::string) -> "name")
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn update_returning() {
+    let sql = r#"UPDATE "test_space" SET "FIRST_NAME" = 'test' WHERE "id" = 1 RETURNING *"#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    update "test_space"
+    "FIRST_NAME" = "col_0"
+        motion [policy: local]
+            projection ('test'::string -> "col_0", "test_space"."id"::unsigned -> "col_1")
+                selection ROW("test_space"."id"::unsigned) = ROW(1::unsigned)
+                    scan "test_space"
+    returning ("id"::unsigned -> "id", "sysFrom"::unsigned -> "sysFrom", "FIRST_NAME"::string -> "FIRST_NAME", "sys_op"::unsigned -> "sys_op")
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn delete_returning() {
+    let sql = r#"DELETE FROM "t1" RETURNING "a""#;
+    let plan = sql_to_optimized_ir(sql, vec![]);
+    insta::assert_snapshot!(plan.as_explain().unwrap(), @r#"
+    delete "t1"
+        motion [policy: local]
+            projection ("t1"."a"::string -> "pk_col_0", "t1"."b"::integer -> "pk_col_1")
+                scan "t1"
+    returning ("a"::string -> "a")
+    execution options:
+        sql_vdbe_opcode_max = 45000
+        sql_motion_row_max = 5000
+    "#);
+}
+
+#[test]
+fn returning_global_table() {
+    let sql = r#"DELETE FROM "global_t" RETURNING "a""#;
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(sql, metadata).unwrap_err();
+    assert_eq!(
+        "unsupported query: RETURNING clause is not supported for global tables",
+        err.to_string()
+    );
+}
+
+#[test]
+fn returning_aggregate() {
+    let sql = r#"DELETE FROM "t1" RETURNING count("a")"#;
+    let metadata = &RouterConfigurationMock::new();
+    let err = AbstractSyntaxTree::transform_into_plan(sql, metadata).unwrap_err();
+    assert_eq!(
+        "invalid query: aggregate functions are not allowed in RETURNING clause",
+        err.to_string()
+    );
+}
@@ -14,7 +14,7 @@ use std::collections::{BTreeMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::ops::Bound::Included;
 
-use super::node::{Bound, BoundType, Like, Over, Window};
+use super::node::{Bound, BoundType, Like, LocalTimestamp, Over, Window};
 use super::operator::OrderByEntity;
 use super::{
     distribution, operator, Alias, ArithmeticExpr, BoolExpr, Case, Cast, Concat, Constant,
@@ -850,8 +850,9 @@ impl<'plan> Comparator<'plan> {
             Expression::CountAsterisk(_) => {
                 "CountAsterisk".hash(state);
             }
-            Expression::LocalTimestamp(_) => {
+            Expression::LocalTimestamp(LocalTimestamp { with_time_zone, .. }) => {
                 "LocalTimestamp".hash(state);
+                with_time_zone.hash(state);
             }
             Expression::Parameter(_) => {
                 "Parameter".hash(state);
@@ -20,10 +20,12 @@ pub enum Type {
     Decimal,
     Double,
     Integer,
+    Interval,
     String,
     Text,
     Uuid,
     Unsigned,
+    Varbinary,
     Varchar(usize),
 }
 
@@ -41,10 +43,12 @@ impl TryFrom<&Rule> for Type {
             Rule::TypeDecimal => Ok(Type::Decimal),
             Rule::TypeDouble => Ok(Type::Double),
             Rule::TypeInt => Ok(Type::Integer),
+            Rule::TypeInterval => Ok(Type::Interval),
             Rule::TypeString => Ok(Type::String),
             Rule::TypeText => Ok(Type::Text),
             Rule::TypeUuid => Ok(Type::Uuid),
             Rule::TypeUnsigned => Ok(Type::Unsigned),
+            Rule::TypeVarbinary => Ok(Type::Varbinary),
             _ => Err(SbroadError::Unsupported(
                 Entity::Type,
                 Some(format_smolstr!("{ast_type:?}")),
@@ -63,11 +67,13 @@ impl TryFrom<&RelationType> for Type {
             RelationType::Decimal => Ok(Type::Decimal),
             RelationType::Double => Ok(Type::Double),
             RelationType::Integer => Ok(Type::Integer),
+            RelationType::Interval => Ok(Type::Interval),
             RelationType::String => Ok(Type::String),
             RelationType::Unsigned => Ok(Type::Unsigned),
             RelationType::Map => Ok(Type::Map),
             RelationType::Any => Ok(Type::Any),
             RelationType::Uuid => Ok(Type::Uuid),
+            RelationType::Varbinary => Ok(Type::Varbinary),
             RelationType::Array => Err(SbroadError::Unsupported(
                 Entity::Type,
                 Some("array in the cast operation".to_smolstr()),
@@ -86,10 +92,12 @@ impl From<&Type> for SmolStr {
             Type::Decimal => "decimal".to_smolstr(),
             Type::Double => "double".to_smolstr(),
             Type::Integer => "int".to_smolstr(),
+            Type::Interval => "interval".to_smolstr(),
             Type::String => "string".to_smolstr(),
             Type::Text => "text".to_smolstr(),
             Type::Uuid => "uuid".to_smolstr(),
             Type::Unsigned => "unsigned".to_smolstr(),
+            Type::Varbinary => "varbinary".to_smolstr(),
             Type::Varchar(length) => match length {
                 0 => "varchar".to_smolstr(),
                 _ => format_smolstr!("varchar({length})"),
@@ -115,9 +123,11 @@ impl Type {
             Type::Decimal => RelationType::Decimal,
             Type::Double => RelationType::Double,
             Type::Integer => RelationType::Integer,
+            Type::Interval => RelationType::Interval,
             Type::Uuid => RelationType::Uuid,
             Type::String | Type::Text | Type::Varchar(_) => RelationType::String,
             Type::Unsigned => RelationType::Unsigned,
+            Type::Varbinary => RelationType::Varbinary,
         }
     }
 }
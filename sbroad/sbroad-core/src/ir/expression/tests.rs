@@ -74,6 +74,8 @@ fn derive_expr_type() {
             r#type: DerivedType::new(ty),
             role: Default::default(),
             is_nullable: false,
+            default: None,
+            generated_always: false,
         }
     }
 
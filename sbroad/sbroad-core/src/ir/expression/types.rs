@@ -5,6 +5,7 @@ use crate::{
     executor::vtable::calculate_unified_types,
     ir::{
         node::Parameter,
+        operator::Arithmetic,
         relation::{DerivedType, Type},
         Plan,
     },
@@ -120,6 +121,22 @@ impl Expression<'_> {
                     (Type::Integer, Type::Unsigned | Type::Integer)
                     | (Type::Unsigned, Type::Integer) => Type::Integer,
                     (Type::Unsigned, Type::Unsigned) => Type::Unsigned,
+                    (Type::Datetime, Type::Interval)
+                        if matches!(op, Arithmetic::Add | Arithmetic::Subtract) =>
+                    {
+                        Type::Datetime
+                    }
+                    (Type::Interval, Type::Datetime) if matches!(op, Arithmetic::Add) => {
+                        Type::Datetime
+                    }
+                    (Type::Datetime, Type::Datetime) if matches!(op, Arithmetic::Subtract) => {
+                        Type::Interval
+                    }
+                    (Type::Interval, Type::Interval)
+                        if matches!(op, Arithmetic::Add | Arithmetic::Subtract) =>
+                    {
+                        Type::Interval
+                    }
                     _ => return Err(SbroadError::Invalid(
                         Entity::Expression,
                         Some(format_smolstr!("types {left_type} and {right_type} are not supported for arithmetic expression ({:?} {op:?} {:?})",
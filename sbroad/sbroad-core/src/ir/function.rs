@@ -1,13 +1,22 @@
 use crate::errors::{Entity, SbroadError};
 use crate::executor::engine::helpers::to_user;
-use crate::ir::aggregates::AggregateKind;
-use crate::ir::node::{NodeId, ScalarFunction};
+use crate::ir::aggregates::{AggregateKind, AggregateOrderBy};
+use crate::ir::node::expression::Expression;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::{Alias, NodeId, Projection, ScalarFunction};
+use crate::ir::transformation::redistribution::ColumnPosition;
+use crate::ir::value::Value;
 use crate::ir::Plan;
 use serde::{Deserialize, Serialize};
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 
 use super::expression::{FunctionFeature, VolatilityType};
-use super::relation::DerivedType;
+use super::relation::{DerivedType, Type};
+
+/// Set-returning function expanding a JSON array into rows
+/// (`json_array_elements` and `jsonb_array_elements`). On the storages it
+/// returns the whole array, which is unnested by the top motion.
+pub const JSON_ARRAY_ELEMENTS: &str = "json_array_elements";
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub struct Function {
@@ -71,11 +80,20 @@ impl Plan {
                 Some(format_smolstr!("function {} is not stable", function.name)),
             ));
         }
+        let mut func_type = function.func_type;
+        if matches!(function.name.as_str(), "substr" | "substring") {
+            // Substring of a binary string is a binary string as well.
+            if let Some(first_id) = children.first() {
+                if let Some(Type::Varbinary) = self.calculate_expression_type(*first_id)? {
+                    func_type = DerivedType::new(Type::Varbinary);
+                }
+            }
+        }
         let func_expr = ScalarFunction {
             name: function.name.to_smolstr(),
             children,
             feature,
-            func_type: function.func_type,
+            func_type,
             is_system: function.is_system,
             volatility_type: function.volatility,
         };
@@ -116,14 +134,113 @@ impl Plan {
         Ok(func_id)
     }
 
+    /// Positions of the projection columns computed by the set-returning
+    /// functions. Empty for other relational nodes.
+    ///
+    /// # Errors
+    /// - invalid projection output
+    pub fn set_returning_columns(
+        &self,
+        rel_id: NodeId,
+    ) -> Result<Vec<ColumnPosition>, SbroadError> {
+        let Relational::Projection(Projection { output, .. }) = self.get_relation_node(rel_id)?
+        else {
+            return Ok(Vec::new());
+        };
+        let mut positions = Vec::new();
+        for (pos, col_id) in self.get_row_list(*output)?.iter().enumerate() {
+            let Expression::Alias(Alias { child, .. }) = self.get_expression_node(*col_id)? else {
+                continue;
+            };
+            if let Expression::ScalarFunction(ScalarFunction { name, .. }) =
+                self.get_expression_node(*child)?
+            {
+                if name == JSON_ARRAY_ELEMENTS {
+                    positions.push(pos);
+                }
+            }
+        }
+        Ok(positions)
+    }
+
+    /// Check that the set-returning functions are used only as the columns
+    /// of the top projection: their results are expanded into rows after
+    /// the whole query is executed.
+    ///
+    /// # Errors
+    /// - set-returning function is used elsewhere
+    pub fn check_set_returning_functions(&self, func_ids: &[NodeId]) -> Result<(), SbroadError> {
+        let mut columns = Vec::new();
+        let top_id = self.get_top()?;
+        if let Relational::Projection(Projection {
+            output,
+            is_distinct: false,
+            ..
+        }) = self.get_relation_node(top_id)?
+        {
+            for col_id in self.get_row_list(*output)? {
+                if let Expression::Alias(Alias { child, .. }) = self.get_expression_node(*col_id)? {
+                    columns.push(*child);
+                }
+            }
+        }
+        if func_ids.iter().any(|id| !columns.contains(id)) {
+            return Err(SbroadError::Invalid(
+                Entity::Query,
+                Some(
+                    "set-returning functions are allowed only in the select list \
+                    of the top-level query without DISTINCT, ORDER BY and LIMIT"
+                        .into(),
+                ),
+            ));
+        }
+        Ok(())
+    }
+
     /// Add aggregate function to plan
+    #[allow(clippy::too_many_lines)]
     pub fn add_aggregate_function(
         &mut self,
         function: &str,
         kind: AggregateKind,
-        children: Vec<NodeId>,
-        is_distinct: bool,
+        mut children: Vec<NodeId>,
+        mut is_distinct: bool,
+        order_by: Option<AggregateOrderBy>,
     ) -> Result<NodeId, SbroadError> {
+        let is_percentile = matches!(
+            kind,
+            AggregateKind::PERCENTILECONT | AggregateKind::PERCENTILEDISC
+        );
+        let order_by_error = match order_by {
+            Some(AggregateOrderBy {
+                within_group: true, ..
+            }) if !is_percentile => Some(format_smolstr!(
+                "WITHIN GROUP is allowed only for percentile_cont and percentile_disc aggregate functions. Got: {}",
+                to_user(function)
+            )),
+            Some(AggregateOrderBy {
+                within_group: false,
+                ..
+            }) if !matches!(kind, AggregateKind::GRCONCAT | AggregateKind::ARRAYAGG) => {
+                Some(format_smolstr!(
+                    "ORDER BY is allowed only for string_agg, group_concat and array_agg aggregate functions. Got: {}",
+                    to_user(function)
+                ))
+            }
+            None if is_percentile => Some(format_smolstr!(
+                "{} aggregate function requires WITHIN GROUP (ORDER BY ..)",
+                to_user(function)
+            )),
+            Some(_) if is_distinct => Some(format_smolstr!(
+                "DISTINCT and ORDER BY can't be used together in aggregate function {}",
+                to_user(function)
+            )),
+            _ => None,
+        };
+        if let Some(error) = order_by_error {
+            return Err(SbroadError::Invalid(Entity::Query, Some(error)));
+        }
+
         match kind {
             AggregateKind::GRCONCAT => {
                 if children.len() > 2 || children.is_empty() {
@@ -155,17 +272,60 @@ impl Plan {
                 }
             }
         }
+
+        let mut name = function.to_lowercase().to_smolstr();
+        let mut kind = kind;
+        match kind {
+            AggregateKind::BOOLAND | AggregateKind::BOOLOR => {
+                // Duplicates don't change the result.
+                is_distinct = false;
+            }
+            AggregateKind::VARSAMP
+            | AggregateKind::VARPOP
+            | AggregateKind::STDDEVSAMP
+            | AggregateKind::STDDEVPOP
+                if is_distinct =>
+            {
+                return Err(SbroadError::Invalid(
+                    Entity::Query,
+                    Some(format_smolstr!(
+                        "DISTINCT is not supported for aggregate function {}",
+                        to_user(function)
+                    )),
+                ));
+            }
+            _ => {}
+        }
+        // The values are passed to the holistic aggregates together with
+        // the ordering expression and the direction of the order.
+        if let Some(AggregateOrderBy { expr, is_desc, .. }) = order_by {
+            if let AggregateKind::GRCONCAT = kind {
+                kind = AggregateKind::ORDEREDGRCONCAT;
+                if children.len() == 1 {
+                    children.push(self.add_const(Value::String(",".into())));
+                }
+            } else if let AggregateKind::ARRAYAGG = kind {
+                kind = AggregateKind::ORDEREDARRAYAGG;
+            }
+            if !is_percentile {
+                name = kind.to_smolstr();
+            }
+            children.push(expr);
+            children.push(self.add_const(Value::Boolean(is_desc)));
+        }
+
         let feature = if is_distinct {
             Some(FunctionFeature::Distinct)
         } else {
             None
         };
         let func_expr = ScalarFunction {
-            name: function.to_lowercase().to_smolstr(),
+            name,
             func_type: kind.get_type(self, &children)?,
             children,
             feature,
-            is_system: true,
+            // Some aggregates are implemented in lua (see `builtins.lua`).
+            is_system: kind.is_system(),
             volatility_type: super::expression::VolatilityType::Stable,
         };
         let id = self.nodes.push(func_expr.into());
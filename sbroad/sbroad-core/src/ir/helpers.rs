@@ -290,8 +290,16 @@ impl Plan {
                     writeln_with_tabulation(buf, tabulation_number + 1, "Right child")?;
                     self.formatted_arena_node(buf, tabulation_number + 1, *right)?;
                 }
-                Expression::LocalTimestamp(LocalTimestamp { precision }) => {
-                    writeln!(buf, "LocalTimestamp [precision = {precision}]")?;
+                Expression::LocalTimestamp(LocalTimestamp {
+                    precision,
+                    with_time_zone,
+                }) => {
+                    let name = if *with_time_zone {
+                        "CurrentTimestamp"
+                    } else {
+                        "LocalTimestamp"
+                    };
+                    writeln!(buf, "{name} [precision = {precision}]")?;
                 }
                 Expression::Parameter(Parameter { param_type, index }) => {
                     writeln!(buf, "Parameter [type = {param_type}, index = {index}]")?;
@@ -455,7 +463,9 @@ impl Plan {
                     Relational::Insert(_) => writeln!(buf, "Insert")?,
                     Relational::Intersect(_) => writeln!(buf, "Intersect")?,
                     Relational::Except(_) => writeln!(buf, "Except")?,
-                    Relational::Limit(Limit { limit, .. }) => writeln!(buf, "Limit {limit}")?,
+                    Relational::Limit(Limit { limit, offset, .. }) => {
+                        writeln!(buf, "Limit {limit:?} offset {offset}")?;
+                    }
                 }
                 // Print children.
                 match relation {
@@ -22,8 +22,11 @@ use super::{
     relation::DerivedType,
 };
 use crate::ir::{
-    acl::{AlterOption, GrantRevokeType},
-    ddl::{ColumnDef, Language, ParamDef, SetParamScopeType, SetParamValue},
+    acl::{AlterOption, AuthMethod, GrantRevokeType},
+    ddl::{
+        ColumnDef, Language, ParamDef, PartitionBound, PartitionBy, SetParamScopeType,
+        SetParamValue, TableConstraint,
+    },
     distribution::Distribution,
     helpers::RepeatableState,
     transformation::redistribution::{ColumnPosition, MotionPolicy, Program},
@@ -386,6 +389,9 @@ impl From<CountAsterisk> for NodeAligned {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct LocalTimestamp {
     pub precision: usize,
+    /// `CURRENT_TIMESTAMP` (or `now()`): keep the offset of the local
+    /// time zone instead of treating the wall clock time as UTC.
+    pub with_time_zone: bool,
 }
 
 impl From<LocalTimestamp> for NodeAligned {
@@ -394,6 +400,29 @@ impl From<LocalTimestamp> for NodeAligned {
     }
 }
 
+/// CTE declared in a `WITH RECURSIVE` clause.
+///
+/// The recursive CTE node's child is a `LIMIT` over the union of the
+/// non-recursive (anchor) and the recursive parts. The references to
+/// the CTE from its own recursive part are CTE scans as well, but their
+/// child is the anchor part used only to describe the output tuple.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct RecursiveCte {
+    /// CTE's name. It is kept apart from the scan alias,
+    /// as the same name must be used in the self-references.
+    pub name: SmolStr,
+    pub is_self_reference: bool,
+}
+
+impl RecursiveCte {
+    /// `LIMIT` of the CTE for the `sql_recursion_row_max` option. One extra
+    /// row is fetched to tell the exceeded row max from the reached one.
+    #[must_use]
+    pub fn limit(row_max: u64) -> Option<u64> {
+        (row_max > 0).then(|| row_max.saturating_add(1))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct ScanCte {
     /// CTE's name.
@@ -401,11 +430,13 @@ pub struct ScanCte {
     pub child: NodeId,
     /// An output tuple with aliases.
     pub output: NodeId,
+    /// Recursion role of the scan (`None` for ordinary CTEs).
+    pub recursive: Option<RecursiveCte>,
 }
 
 impl From<ScanCte> for NodeAligned {
     fn from(value: ScanCte) -> Self {
-        Self::Node64(Node64::ScanCte(value))
+        Self::Node96(Node96::ScanCte(value))
     }
 }
 
@@ -434,6 +465,8 @@ pub struct Delete {
     pub child: Option<NodeId>,
     /// The output tuple (reserved for `delete returning`).
     pub output: Option<NodeId>,
+    /// Projection of the `RETURNING` clause, if any.
+    pub returning: Option<NodeId>,
 }
 
 impl From<Delete> for NodeAligned {
@@ -454,6 +487,11 @@ pub struct Insert {
     pub output: NodeId,
     /// What to do in case there is a conflict during insert on storage
     pub conflict_strategy: ConflictStrategy,
+    /// Local `Update` of the `ON CONFLICT DO UPDATE` clause. It is applied
+    /// on storage to the tuples rejected by insertion.
+    pub conflict_update: Option<NodeId>,
+    /// Projection of the `RETURNING` clause, if any.
+    pub returning: Option<NodeId>,
 }
 
 impl From<Insert> for NodeAligned {
@@ -495,6 +533,8 @@ pub struct Update {
     pub pk_positions: Vec<ColumnPosition>,
     /// Output id.
     pub output: NodeId,
+    /// Projection of the `RETURNING` clause, if any.
+    pub returning: Option<NodeId>,
 }
 
 impl From<Update> for NodeAligned {
@@ -528,8 +568,11 @@ impl From<Join> for NodeAligned {
 pub struct Limit {
     /// Output tuple.
     pub output: NodeId,
-    // The limit value constant that comes after LIMIT keyword.
-    pub limit: u64,
+    /// The limit value constant that comes after LIMIT (or FETCH FIRST) keyword.
+    /// `None` means that all the rows are returned (e.g. `OFFSET` without `LIMIT`).
+    pub limit: Option<u64>,
+    /// The number of rows to skip (value after OFFSET keyword).
+    pub offset: u64,
     /// Select statement that is being limited.
     /// Note that it can be a complex statement, like SELECT .. UNION ALL SELECT .. LIMIT 100,
     /// in that case limit is applied to the result of union.
@@ -538,7 +581,7 @@ pub struct Limit {
 
 impl From<Limit> for NodeAligned {
     fn from(value: Limit) -> Self {
-        Self::Node32(Node32::Limit(value))
+        Self::Node64(Node64::Limit(value))
     }
 }
 
@@ -785,7 +828,7 @@ pub struct CreateUser {
     pub name: SmolStr,
     pub password: SmolStr,
     pub if_not_exists: bool,
-    pub auth_method: tarantool::auth::AuthMethod,
+    pub auth_method: AuthMethod,
     pub timeout: Decimal,
 }
 
@@ -852,6 +895,29 @@ pub struct CreateTable {
     /// 1) Global table.
     /// 2) Sharded table without [ON TIER] part. In this case picodata will use default tier.
     pub tier: Option<SmolStr>,
+    /// Partitioned table stores the tuples in its partitions
+    /// created with `CREATE TABLE ... PARTITION OF`.
+    pub partition_by: Option<PartitionBy>,
+    pub constraints: Vec<TableConstraint>,
+}
+
+/// `CREATE TABLE ... PARTITION OF`, the partition inherits the columns,
+/// the primary key and the distribution of the partitioned table.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CreatePartition {
+    pub name: SmolStr,
+    /// Name of the partitioned table.
+    pub parent: SmolStr,
+    pub bound: PartitionBound,
+    pub if_not_exists: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+}
+
+impl From<CreatePartition> for NodeAligned {
+    fn from(value: CreatePartition) -> Self {
+        Self::Node232(Node232::CreatePartition(value))
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -871,7 +937,13 @@ impl From<AlterTable> for NodeAligned {
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum AlterTableOp {
     AlterColumn(Vec<AlterColumn>),
-    RenameTable { new_table_name: SmolStr },
+    RenameTable {
+        new_table_name: SmolStr,
+    },
+    /// `DETACH PARTITION`: the partition becomes a standalone table.
+    DetachPartition {
+        partition: SmolStr,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -888,6 +960,34 @@ impl From<CreateTable> for NodeAligned {
     }
 }
 
+/// `CREATE TABLE ... AS <query>`, the columns of the table are derived
+/// from the query output when the statement is executed.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CreateTableAs {
+    pub name: SmolStr,
+    /// SQL text of the query filling the table.
+    pub query: SmolStr,
+    /// If empty, the first column of the query is used.
+    pub primary_key: Vec<SmolStr>,
+    /// If `None`, create global table. If empty, the table
+    /// is sharded by the primary key.
+    pub sharding_key: Option<Vec<SmolStr>>,
+    pub engine_type: SpaceEngineType,
+    pub if_not_exists: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+    pub tier: Option<SmolStr>,
+    /// Values of the query parameters. The query is planned on execution,
+    /// so they are bound then.
+    pub params: Vec<Value>,
+}
+
+impl From<CreateTableAs> for NodeAligned {
+    fn from(value: CreateTableAs) -> Self {
+        Self::Node232(Node232::CreateTableAs(value))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct DropTable {
     pub name: SmolStr,
@@ -1104,6 +1204,113 @@ impl From<DropIndex> for NodeAligned {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CreateSequence {
+    pub name: SmolStr,
+    /// The first value returned by `nextval`.
+    pub start: i64,
+    /// The step between two consecutive values, must not be zero.
+    pub increment: i64,
+    pub if_not_exists: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+}
+
+impl From<CreateSequence> for NodeAligned {
+    fn from(value: CreateSequence) -> Self {
+        Self::Node96(Node96::CreateSequence(value))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DropSequence {
+    pub name: SmolStr,
+    pub if_exists: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+}
+
+impl From<DropSequence> for NodeAligned {
+    fn from(value: DropSequence) -> Self {
+        Self::Node64(Node64::DropSequence(value))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CreateView {
+    pub name: SmolStr,
+    /// SQL text of the view query, it is expanded as a subquery
+    /// wherever the view is referenced.
+    pub query: SmolStr,
+    pub or_replace: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+}
+
+impl From<CreateView> for NodeAligned {
+    fn from(value: CreateView) -> Self {
+        Self::Node136(Node136::CreateView(value))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DropView {
+    pub name: SmolStr,
+    pub if_exists: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+}
+
+impl From<DropView> for NodeAligned {
+    fn from(value: DropView) -> Self {
+        Self::Node64(Node64::DropView(value))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct CreateSchema {
+    pub name: SmolStr,
+    pub if_not_exists: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+}
+
+impl From<CreateSchema> for NodeAligned {
+    fn from(value: CreateSchema) -> Self {
+        Self::Node64(Node64::CreateSchema(value))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DropSchema {
+    pub name: SmolStr,
+    pub if_exists: bool,
+    /// Drop all the tables and views of the schema as well.
+    pub cascade: bool,
+    pub wait_applied_globally: bool,
+    pub timeout: Decimal,
+}
+
+impl From<DropSchema> for NodeAligned {
+    fn from(value: DropSchema) -> Self {
+        Self::Node64(Node64::DropSchema(value))
+    }
+}
+
+/// Gather the statistics of the table (or of all the user tables
+/// when the table is not specified) used by the planner.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Analyze {
+    pub table: Option<SmolStr>,
+    pub timeout: Decimal,
+}
+
+impl From<Analyze> for NodeAligned {
+    fn from(value: Analyze) -> Self {
+        Self::Node64(Node64::Analyze(value))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct SetParam {
     pub scope_type: SetParamScopeType,
@@ -1194,7 +1401,6 @@ pub enum Node32 {
     Concat(Concat),
     Like(Like),
     Bool(BoolExpr),
-    Limit(Limit),
     Arithmetic(ArithmeticExpr),
     Trim(Trim),
     Cast(Cast),
@@ -1206,8 +1412,6 @@ pub enum Node32 {
     Values(Values),
     Deallocate(Deallocate),
     Tcl(Tcl),
-    CreateSchema,
-    DropSchema,
 }
 
 impl Node32 {
@@ -1217,7 +1421,6 @@ impl Node32 {
             Node32::Alias(alias) => NodeOwned::Expression(ExprOwned::Alias(alias)),
             Node32::Arithmetic(arithm) => NodeOwned::Expression(ExprOwned::Arithmetic(arithm)),
             Node32::Bool(bool) => NodeOwned::Expression(ExprOwned::Bool(bool)),
-            Node32::Limit(limit) => NodeOwned::Relational(RelOwned::Limit(limit)),
             Node32::Cast(cast) => NodeOwned::Expression(ExprOwned::Cast(cast)),
             Node32::Concat(concat) => NodeOwned::Expression(ExprOwned::Concat(concat)),
             Node32::CountAsterisk(count) => NodeOwned::Expression(ExprOwned::CountAsterisk(count)),
@@ -1239,8 +1442,6 @@ impl Node32 {
                 Tcl::Commit => NodeOwned::Tcl(Tcl::Commit),
                 Tcl::Rollback => NodeOwned::Tcl(Tcl::Rollback),
             },
-            Node32::CreateSchema => NodeOwned::Ddl(DdlOwned::CreateSchema),
-            Node32::DropSchema => NodeOwned::Ddl(DdlOwned::DropSchema),
         }
     }
 }
@@ -1248,7 +1449,6 @@ impl Node32 {
 #[allow(clippy::module_name_repetitions)]
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum Node64 {
-    ScanCte(ScanCte),
     Case(Case),
     Parameter(Parameter),
     Constant(Constant),
@@ -1268,6 +1468,11 @@ pub enum Node64 {
     CreateRole(CreateRole),
     DropTable(DropTable),
     DropIndex(DropIndex),
+    DropSequence(DropSequence),
+    DropView(DropView),
+    CreateSchema(CreateSchema),
+    DropSchema(DropSchema),
+    Analyze(Analyze),
     GroupBy(GroupBy),
     SetParam(SetParam),
     SetTransaction(SetTransaction),
@@ -1277,6 +1482,7 @@ pub enum Node64 {
     Over(Over),
     NamedWindows(NamedWindows),
     TruncateTable(TruncateTable),
+    Limit(Limit),
 }
 
 impl Node64 {
@@ -1290,6 +1496,15 @@ impl Node64 {
             Node64::CreateRole(create_role) => NodeOwned::Acl(AclOwned::CreateRole(create_role)),
             Node64::Delete(delete) => NodeOwned::Relational(RelOwned::Delete(delete)),
             Node64::DropIndex(drop_index) => NodeOwned::Ddl(DdlOwned::DropIndex(drop_index)),
+            Node64::DropSequence(drop_sequence) => {
+                NodeOwned::Ddl(DdlOwned::DropSequence(drop_sequence))
+            }
+            Node64::DropView(drop_view) => NodeOwned::Ddl(DdlOwned::DropView(drop_view)),
+            Node64::CreateSchema(create_schema) => {
+                NodeOwned::Ddl(DdlOwned::CreateSchema(create_schema))
+            }
+            Node64::DropSchema(drop_schema) => NodeOwned::Ddl(DdlOwned::DropSchema(drop_schema)),
+            Node64::Analyze(analyze) => NodeOwned::Ddl(DdlOwned::Analyze(analyze)),
             Node64::DropRole(drop_role) => NodeOwned::Acl(AclOwned::DropRole(drop_role)),
             Node64::DropTable(drop_table) => NodeOwned::Ddl(DdlOwned::DropTable(drop_table)),
             Node64::TruncateTable(truncate_table) => {
@@ -1298,13 +1513,13 @@ impl Node64 {
             Node64::DropUser(drop_user) => NodeOwned::Acl(AclOwned::DropUser(drop_user)),
             Node64::GroupBy(group_by) => NodeOwned::Relational(RelOwned::GroupBy(group_by)),
             Node64::Having(having) => NodeOwned::Relational(RelOwned::Having(having)),
+            Node64::Limit(limit) => NodeOwned::Relational(RelOwned::Limit(limit)),
             Node64::Join(join) => NodeOwned::Relational(RelOwned::Join(join)),
             Node64::OrderBy(order_by) => NodeOwned::Relational(RelOwned::OrderBy(order_by)),
             Node64::Parameter(param) => NodeOwned::Expression(ExprOwned::Parameter(param)),
             Node64::Row(row) => NodeOwned::Expression(ExprOwned::Row(row)),
             Node64::Procedure(proc) => NodeOwned::Block(BlockOwned::Procedure(proc)),
             Node64::Projection(proj) => NodeOwned::Relational(RelOwned::Projection(proj)),
-            Node64::ScanCte(scan_cte) => NodeOwned::Relational(RelOwned::ScanCte(scan_cte)),
             Node64::ScanRelation(scan_rel) => {
                 NodeOwned::Relational(RelOwned::ScanRelation(scan_rel))
             }
@@ -1332,7 +1547,9 @@ pub enum Node96 {
     Invalid(Invalid),
     ScalarFunction(ScalarFunction),
     DropProc(DropProc),
+    CreateSequence(CreateSequence),
     Insert(Insert),
+    ScanCte(ScanCte),
     CreatePlugin(CreatePlugin),
     EnablePlugin(EnablePlugin),
     DisablePlugin(DisablePlugin),
@@ -1345,7 +1562,11 @@ impl Node96 {
         match self {
             Node96::Reference(reference) => NodeOwned::Expression(ExprOwned::Reference(reference)),
             Node96::DropProc(drop_proc) => NodeOwned::Ddl(DdlOwned::DropProc(drop_proc)),
+            Node96::CreateSequence(create_sequence) => {
+                NodeOwned::Ddl(DdlOwned::CreateSequence(create_sequence))
+            }
             Node96::Insert(insert) => NodeOwned::Relational(RelOwned::Insert(insert)),
+            Node96::ScanCte(scan_cte) => NodeOwned::Relational(RelOwned::ScanCte(scan_cte)),
             Node96::Invalid(inv) => NodeOwned::Invalid(inv),
             Node96::ScalarFunction(scalar_func) => {
                 NodeOwned::Expression(ExprOwned::ScalarFunction(scalar_func))
@@ -1369,6 +1590,7 @@ pub enum Node136 {
     AlterSystem(AlterSystem),
     AlterTable(AlterTable),
     CreateProc(CreateProc),
+    CreateView(CreateView),
     RenameRoutine(RenameRoutine),
     Motion(Motion),
     GrantPrivilege(GrantPrivilege),
@@ -1389,6 +1611,7 @@ impl Node136 {
             }
             Node136::AlterTable(alter_table) => NodeOwned::Ddl(DdlOwned::AlterTable(alter_table)),
             Node136::CreateProc(create_proc) => NodeOwned::Ddl(DdlOwned::CreateProc(create_proc)),
+            Node136::CreateView(create_view) => NodeOwned::Ddl(DdlOwned::CreateView(create_view)),
             Node136::GrantPrivilege(grant_privelege) => {
                 NodeOwned::Acl(AclOwned::GrantPrivilege(grant_privelege))
             }
@@ -1416,6 +1639,8 @@ impl Node136 {
 pub enum Node232 {
     Invalid(Invalid),
     CreateTable(CreateTable),
+    CreateTableAs(CreateTableAs),
+    CreatePartition(CreatePartition),
     CreateIndex(CreateIndex),
     AppendServiceToTier(AppendServiceToTier),
     RemoveServiceFromTier(RemoveServiceFromTier),
@@ -1428,6 +1653,12 @@ impl Node232 {
             Node232::CreateTable(create_table) => {
                 NodeOwned::Ddl(DdlOwned::CreateTable(create_table))
             }
+            Node232::CreateTableAs(create_table_as) => {
+                NodeOwned::Ddl(DdlOwned::CreateTableAs(create_table_as))
+            }
+            Node232::CreatePartition(create_partition) => {
+                NodeOwned::Ddl(DdlOwned::CreatePartition(create_partition))
+            }
             Node232::CreateIndex(create_index) => {
                 NodeOwned::Ddl(DdlOwned::CreateIndex(create_index))
             }
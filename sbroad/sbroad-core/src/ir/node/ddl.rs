@@ -1,9 +1,10 @@
 use super::{
-    AlterSystem, AlterTable, CreateIndex, CreateProc, CreateTable, DropIndex, DropProc, DropTable,
-    NodeAligned, RenameRoutine, SetParam, SetTransaction, TruncateTable,
+    AlterSystem, AlterTable, Analyze, CreateIndex, CreatePartition, CreateProc, CreateSchema,
+    CreateSequence, CreateTable, CreateTableAs, CreateView, DropIndex, DropProc, DropSchema,
+    DropSequence, DropTable, DropView, NodeAligned, RenameRoutine, SetParam, SetTransaction,
+    TruncateTable,
 };
 use crate::errors::{Entity, SbroadError};
-use crate::ir::Node32;
 use serde::Serialize;
 use smol_str::{format_smolstr, ToSmolStr};
 
@@ -11,6 +12,8 @@ use smol_str::{format_smolstr, ToSmolStr};
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub enum DdlOwned {
     CreateTable(CreateTable),
+    CreateTableAs(CreateTableAs),
+    CreatePartition(CreatePartition),
     DropTable(DropTable),
     AlterTable(AlterTable),
     TruncateTable(TruncateTable),
@@ -20,8 +23,13 @@ pub enum DdlOwned {
     AlterSystem(AlterSystem),
     CreateIndex(CreateIndex),
     DropIndex(DropIndex),
-    CreateSchema,
-    DropSchema,
+    CreateSequence(CreateSequence),
+    DropSequence(DropSequence),
+    CreateView(CreateView),
+    DropView(DropView),
+    CreateSchema(CreateSchema),
+    DropSchema(DropSchema),
+    Analyze(Analyze),
     SetParam(SetParam),
     SetTransaction(SetTransaction),
 }
@@ -34,11 +42,20 @@ impl DdlOwned {
     pub fn timeout(&self) -> Result<f64, SbroadError> {
         match self {
             DdlOwned::CreateTable(CreateTable { ref timeout, .. })
+            | DdlOwned::CreateTableAs(CreateTableAs { ref timeout, .. })
+            | DdlOwned::CreatePartition(CreatePartition { ref timeout, .. })
             | DdlOwned::DropTable(DropTable { ref timeout, .. })
             | DdlOwned::TruncateTable(TruncateTable { ref timeout, .. })
             | DdlOwned::AlterTable(AlterTable { ref timeout, .. })
             | DdlOwned::CreateIndex(CreateIndex { ref timeout, .. })
             | DdlOwned::DropIndex(DropIndex { ref timeout, .. })
+            | DdlOwned::CreateSequence(CreateSequence { ref timeout, .. })
+            | DdlOwned::DropSequence(DropSequence { ref timeout, .. })
+            | DdlOwned::CreateView(CreateView { ref timeout, .. })
+            | DdlOwned::DropView(DropView { ref timeout, .. })
+            | DdlOwned::CreateSchema(CreateSchema { ref timeout, .. })
+            | DdlOwned::DropSchema(DropSchema { ref timeout, .. })
+            | DdlOwned::Analyze(Analyze { ref timeout, .. })
             | DdlOwned::SetParam(SetParam { ref timeout, .. })
             | DdlOwned::SetTransaction(SetTransaction { ref timeout, .. })
             | DdlOwned::AlterSystem(AlterSystem { ref timeout, .. })
@@ -52,7 +69,6 @@ impl DdlOwned {
                     )
                 })
             }
-            DdlOwned::CreateSchema | DdlOwned::DropSchema => Ok(0.0),
         }
     }
 
@@ -62,6 +78,14 @@ impl DdlOwned {
                 wait_applied_globally,
                 ..
             })
+            | DdlOwned::CreateTableAs(CreateTableAs {
+                wait_applied_globally,
+                ..
+            })
+            | DdlOwned::CreatePartition(CreatePartition {
+                wait_applied_globally,
+                ..
+            })
             | DdlOwned::DropTable(DropTable {
                 wait_applied_globally,
                 ..
@@ -82,6 +106,30 @@ impl DdlOwned {
                 wait_applied_globally,
                 ..
             })
+            | DdlOwned::CreateSequence(CreateSequence {
+                wait_applied_globally,
+                ..
+            })
+            | DdlOwned::DropSequence(DropSequence {
+                wait_applied_globally,
+                ..
+            })
+            | DdlOwned::CreateView(CreateView {
+                wait_applied_globally,
+                ..
+            })
+            | DdlOwned::DropView(DropView {
+                wait_applied_globally,
+                ..
+            })
+            | DdlOwned::CreateSchema(CreateSchema {
+                wait_applied_globally,
+                ..
+            })
+            | DdlOwned::DropSchema(DropSchema {
+                wait_applied_globally,
+                ..
+            })
             | DdlOwned::CreateProc(CreateProc {
                 wait_applied_globally,
                 ..
@@ -105,13 +153,20 @@ impl From<DdlOwned> for NodeAligned {
             DdlOwned::CreateIndex(create_index) => create_index.into(),
             DdlOwned::CreateProc(create_proc) => create_proc.into(),
             DdlOwned::CreateTable(create_table) => create_table.into(),
-            DdlOwned::CreateSchema => Self::Node32(Node32::CreateSchema),
+            DdlOwned::CreateTableAs(create_table_as) => create_table_as.into(),
+            DdlOwned::CreatePartition(create_partition) => create_partition.into(),
+            DdlOwned::CreateSchema(create_schema) => create_schema.into(),
             DdlOwned::DropIndex(drop_index) => drop_index.into(),
+            DdlOwned::CreateSequence(create_sequence) => create_sequence.into(),
+            DdlOwned::DropSequence(drop_sequence) => drop_sequence.into(),
+            DdlOwned::CreateView(create_view) => create_view.into(),
+            DdlOwned::DropView(drop_view) => drop_view.into(),
             DdlOwned::DropProc(drop_proc) => drop_proc.into(),
             DdlOwned::DropTable(drop_table) => drop_table.into(),
             DdlOwned::AlterTable(alter_table) => alter_table.into(),
             DdlOwned::TruncateTable(truncate_table) => truncate_table.into(),
-            DdlOwned::DropSchema => Self::Node32(Node32::DropSchema),
+            DdlOwned::DropSchema(drop_schema) => drop_schema.into(),
+            DdlOwned::Analyze(analyze) => analyze.into(),
             DdlOwned::AlterSystem(alter_system) => alter_system.into(),
             DdlOwned::RenameRoutine(rename) => rename.into(),
             DdlOwned::SetParam(set_param) => set_param.into(),
@@ -124,6 +179,8 @@ impl From<DdlOwned> for NodeAligned {
 #[derive(Debug, PartialEq, Eq, Serialize)]
 pub enum MutDdl<'a> {
     CreateTable(&'a mut CreateTable),
+    CreateTableAs(&'a mut CreateTableAs),
+    CreatePartition(&'a mut CreatePartition),
     DropTable(&'a mut DropTable),
     TruncateTable(&'a mut TruncateTable),
     AlterTable(&'a mut AlterTable),
@@ -133,8 +190,13 @@ pub enum MutDdl<'a> {
     AlterSystem(&'a mut AlterSystem),
     CreateIndex(&'a mut CreateIndex),
     DropIndex(&'a mut DropIndex),
-    CreateSchema,
-    DropSchema,
+    CreateSequence(&'a mut CreateSequence),
+    DropSequence(&'a mut DropSequence),
+    CreateView(&'a mut CreateView),
+    DropView(&'a mut DropView),
+    CreateSchema(&'a mut CreateSchema),
+    DropSchema(&'a mut DropSchema),
+    Analyze(&'a mut Analyze),
     SetParam(&'a mut SetParam),
     SetTransaction(&'a mut SetTransaction),
 }
@@ -143,6 +205,8 @@ pub enum MutDdl<'a> {
 #[derive(Clone, Debug, PartialEq, Eq, Serialize)]
 pub enum Ddl<'a> {
     CreateTable(&'a CreateTable),
+    CreateTableAs(&'a CreateTableAs),
+    CreatePartition(&'a CreatePartition),
     DropTable(&'a DropTable),
     TruncateTable(&'a TruncateTable),
     AlterTable(&'a AlterTable),
@@ -152,8 +216,13 @@ pub enum Ddl<'a> {
     AlterSystem(&'a AlterSystem),
     CreateIndex(&'a CreateIndex),
     DropIndex(&'a DropIndex),
-    CreateSchema,
-    DropSchema,
+    CreateSequence(&'a CreateSequence),
+    DropSequence(&'a DropSequence),
+    CreateView(&'a CreateView),
+    DropView(&'a DropView),
+    CreateSchema(&'a CreateSchema),
+    DropSchema(&'a DropSchema),
+    Analyze(&'a Analyze),
     SetParam(&'a SetParam),
     SetTransaction(&'a SetTransaction),
 }
@@ -166,11 +235,20 @@ impl Ddl<'_> {
     pub fn timeout(&self) -> Result<f64, SbroadError> {
         match self {
             Ddl::CreateTable(CreateTable { ref timeout, .. })
+            | Ddl::CreateTableAs(CreateTableAs { ref timeout, .. })
+            | Ddl::CreatePartition(CreatePartition { ref timeout, .. })
             | Ddl::DropTable(DropTable { ref timeout, .. })
             | Ddl::TruncateTable(TruncateTable { ref timeout, .. })
             | Ddl::AlterTable(AlterTable { ref timeout, .. })
             | Ddl::CreateIndex(CreateIndex { ref timeout, .. })
             | Ddl::DropIndex(DropIndex { ref timeout, .. })
+            | Ddl::CreateSequence(CreateSequence { ref timeout, .. })
+            | Ddl::DropSequence(DropSequence { ref timeout, .. })
+            | Ddl::CreateView(CreateView { ref timeout, .. })
+            | Ddl::DropView(DropView { ref timeout, .. })
+            | Ddl::CreateSchema(CreateSchema { ref timeout, .. })
+            | Ddl::DropSchema(DropSchema { ref timeout, .. })
+            | Ddl::Analyze(Analyze { ref timeout, .. })
             | Ddl::SetParam(SetParam { ref timeout, .. })
             | Ddl::SetTransaction(SetTransaction { ref timeout, .. })
             | Ddl::AlterSystem(AlterSystem { ref timeout, .. })
@@ -184,7 +262,6 @@ impl Ddl<'_> {
                     )
                 })
             }
-            Ddl::CreateSchema | Ddl::DropSchema => Ok(0.0),
         }
     }
 
@@ -194,6 +271,14 @@ impl Ddl<'_> {
                 wait_applied_globally,
                 ..
             })
+            | Ddl::CreateTableAs(CreateTableAs {
+                wait_applied_globally,
+                ..
+            })
+            | Ddl::CreatePartition(CreatePartition {
+                wait_applied_globally,
+                ..
+            })
             | Ddl::DropTable(DropTable {
                 wait_applied_globally,
                 ..
@@ -214,6 +299,30 @@ impl Ddl<'_> {
                 wait_applied_globally,
                 ..
             })
+            | Ddl::CreateSequence(CreateSequence {
+                wait_applied_globally,
+                ..
+            })
+            | Ddl::DropSequence(DropSequence {
+                wait_applied_globally,
+                ..
+            })
+            | Ddl::CreateView(CreateView {
+                wait_applied_globally,
+                ..
+            })
+            | Ddl::DropView(DropView {
+                wait_applied_globally,
+                ..
+            })
+            | Ddl::CreateSchema(CreateSchema {
+                wait_applied_globally,
+                ..
+            })
+            | Ddl::DropSchema(DropSchema {
+                wait_applied_globally,
+                ..
+            })
             | Ddl::CreateProc(CreateProc {
                 wait_applied_globally,
                 ..
@@ -236,9 +345,22 @@ impl Ddl<'_> {
             Ddl::CreateIndex(create_index) => DdlOwned::CreateIndex((*create_index).clone()),
             Ddl::CreateProc(create_proc) => DdlOwned::CreateProc((*create_proc).clone()),
             Ddl::CreateTable(create_table) => DdlOwned::CreateTable((*create_table).clone()),
+            Ddl::CreateTableAs(create_table_as) => {
+                DdlOwned::CreateTableAs((*create_table_as).clone())
+            }
+            Ddl::CreatePartition(create_partition) => {
+                DdlOwned::CreatePartition((*create_partition).clone())
+            }
             Ddl::DropIndex(drop_index) => DdlOwned::DropIndex((*drop_index).clone()),
-            Ddl::CreateSchema => DdlOwned::CreateSchema,
-            Ddl::DropSchema => DdlOwned::DropSchema,
+            Ddl::CreateSequence(create_sequence) => {
+                DdlOwned::CreateSequence((*create_sequence).clone())
+            }
+            Ddl::DropSequence(drop_sequence) => DdlOwned::DropSequence((*drop_sequence).clone()),
+            Ddl::CreateView(create_view) => DdlOwned::CreateView((*create_view).clone()),
+            Ddl::DropView(drop_view) => DdlOwned::DropView((*drop_view).clone()),
+            Ddl::CreateSchema(create_schema) => DdlOwned::CreateSchema((*create_schema).clone()),
+            Ddl::DropSchema(drop_schema) => DdlOwned::DropSchema((*drop_schema).clone()),
+            Ddl::Analyze(analyze) => DdlOwned::Analyze((*analyze).clone()),
             Ddl::DropProc(drop_proc) => DdlOwned::DropProc((*drop_proc).clone()),
             Ddl::DropTable(drop_table) => DdlOwned::DropTable((*drop_table).clone()),
             Ddl::TruncateTable(truncate_table) => {
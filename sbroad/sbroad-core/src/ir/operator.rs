@@ -9,8 +9,8 @@ use crate::ir::api::children::Children;
 use crate::ir::expression::PlanExpr;
 use crate::ir::node::{
     Alias, Delete, Except, GroupBy, Having, Insert, Intersect, Join, Motion, MutNode, NodeId,
-    OrderBy, Projection, Reference, Row, ScanCte, ScanRelation, ScanSubQuery, Selection, Union,
-    UnionAll, Update, Values, ValuesRow,
+    OrderBy, Over, Projection, RecursiveCte, Reference, Row, ScanCte, ScanRelation, ScanSubQuery,
+    Selection, Union, UnionAll, Update, Values, ValuesRow, Window,
 };
 use crate::ir::Plan;
 use ahash::RandomState;
@@ -196,6 +196,8 @@ impl Display for Unary {
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub enum JoinKind {
     LeftOuter,
+    RightOuter,
+    FullOuter,
     Inner,
 }
 
@@ -203,6 +205,8 @@ impl Display for JoinKind {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let kind = match self {
             JoinKind::LeftOuter => "left",
+            JoinKind::RightOuter => "right",
+            JoinKind::FullOuter => "full",
             JoinKind::Inner => "inner",
         };
         write!(f, "{kind}")
@@ -220,6 +224,9 @@ pub enum ConflictStrategy {
     /// storage. But for other storages the insertion may be successful.
     #[default]
     DoFail,
+    /// Update the conflicting tuple with the `SET` expressions
+    /// over the existing and the new (`excluded`) tuples.
+    DoUpdate,
 }
 
 impl Display for ConflictStrategy {
@@ -228,6 +235,7 @@ impl Display for ConflictStrategy {
             ConflictStrategy::DoNothing => "nothing",
             ConflictStrategy::DoReplace => "replace",
             ConflictStrategy::DoFail => "fail",
+            ConflictStrategy::DoUpdate => "update",
         };
         write!(f, "{s}")
     }
@@ -357,6 +365,7 @@ impl Plan {
             relation: table,
             child,
             output,
+            returning: None,
         };
         let delete_id = self.add_relational(delete.into())?;
         if let Some(output) = output {
@@ -400,6 +409,122 @@ impl Plan {
         Ok(except_id)
     }
 
+    /// Adds intersect node.
+    ///
+    /// # Errors
+    /// - children nodes are not relational
+    /// - children tuples are invalid
+    /// - children tuples have mismatching structure
+    pub fn add_intersect(&mut self, left: NodeId, right: NodeId) -> Result<NodeId, SbroadError> {
+        let child_row_len = |child: NodeId, plan: &Plan| -> Result<usize, SbroadError> {
+            let child_output = plan.get_relation_node(child)?.output();
+            Ok(plan
+                .get_expression_node(child_output)?
+                .get_row_list()?
+                .len())
+        };
+
+        let left_row_len = child_row_len(left, self)?;
+        let right_row_len = child_row_len(right, self)?;
+        if left_row_len != right_row_len {
+            return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                "children tuples have mismatching amount of columns in intersect node: left {left_row_len}, right {right_row_len}"
+            )));
+        }
+
+        let output = self.add_row_for_union_except(left, right)?;
+        let intersect = Intersect {
+            left,
+            right,
+            output,
+        };
+
+        let intersect_id = self.add_relational(intersect.into())?;
+        self.replace_parent_in_subtree(output, None, Some(intersect_id))?;
+        Ok(intersect_id)
+    }
+
+    /// Adds `EXCEPT ALL` (when `is_except` is true) or `INTERSECT ALL` operation.
+    ///
+    /// Local SQL engine supports only the distinct versions of these operations,
+    /// so every row of the children is numbered among its duplicates first:
+    /// ```text
+    /// Projection (columns)
+    ///     ScanSubQuery
+    ///         Except/Intersect
+    ///             Projection (columns, row_number() over (partition by columns))
+    ///                 ScanSubQuery (left)
+    ///             Projection (columns, row_number() over (partition by columns))
+    ///                 ScanSubQuery (right)
+    /// ```
+    /// Numbered rows are unique, so the distinct operation over them preserves
+    /// the multiset semantics.
+    ///
+    /// # Errors
+    /// - children nodes are not relational
+    /// - children tuples are invalid
+    /// - children tuples have mismatching structure
+    pub fn add_multiset_op(
+        &mut self,
+        left: NodeId,
+        right: NodeId,
+        is_except: bool,
+    ) -> Result<NodeId, SbroadError> {
+        let numbered_left = self.add_duplicates_numbering(left)?;
+        let numbered_right = self.add_duplicates_numbering(right)?;
+        let set_op_id = if is_except {
+            self.add_except(numbered_left, numbered_right)?
+        } else {
+            self.add_intersect(numbered_left, numbered_right)?
+        };
+        let sq_id = self.add_sub_query(set_op_id, None)?;
+
+        // Remove the duplicates number column.
+        let mut names = self.get_relational_aliases(sq_id)?;
+        names.pop();
+        let names: Vec<&str> = names.iter().map(SmolStr::as_str).collect();
+        self.add_proj(sq_id, vec![], &names, false, false)
+    }
+
+    /// Adds projection with all the columns of the relational node and
+    /// an additional column numbering the duplicates of each row:
+    /// `row_number() over (partition by <all columns>)`.
+    fn add_duplicates_numbering(&mut self, child: NodeId) -> Result<NodeId, SbroadError> {
+        let sq_id = self.add_sub_query(child, None)?;
+        let sq_output = self.get_relational_output(sq_id)?;
+        let sq_columns = self.get_row_list(sq_output)?.clone();
+
+        let mut columns = Vec::with_capacity(sq_columns.len() + 1);
+        let mut partition = Vec::with_capacity(sq_columns.len());
+        for (pos, alias_id) in sq_columns.iter().enumerate() {
+            let alias = self.get_expression_node(*alias_id)?;
+            let name = alias.get_alias_name()?.to_smolstr();
+            let col_type = alias.calculate_type(self)?;
+            let col_ref = self.nodes.add_ref(None, Some(vec![0]), pos, col_type, None);
+            columns.push(self.nodes.add_alias(&name, col_ref)?);
+            partition.push(self.nodes.add_ref(None, Some(vec![0]), pos, col_type, None));
+        }
+
+        let window = Window {
+            name: None,
+            partition: Some(partition),
+            ordering: None,
+            frame: None,
+        };
+        let window_id = self.nodes.push(window.into());
+        let over = Over {
+            func_name: "row_number".into(),
+            func_args: vec![],
+            filter: None,
+            window: window_id,
+            ref_by_name: false,
+        };
+        let over_id = self.nodes.push(over.into());
+        columns.push(self.nodes.add_alias("row_number", over_id)?);
+
+        self.add_proj_internal(vec![sq_id], &columns, false, vec![window_id])
+    }
+
     /// Add `Update` relational node.
     ///
     /// This function first looks whether some sharding column is
@@ -454,6 +579,8 @@ impl Plan {
     ///   and corresponding update expression.
     /// * `relation` - name of the table being updated.
     /// * `rel_child_id` - id of `Update` child
+    /// * `scan` - name of the table scan in the child output (when the
+    ///   table columns can't be found by their names only)
     ///
     /// # Errors
     /// - invalid update table
@@ -465,6 +592,7 @@ impl Plan {
         relation: &str,
         update_defs: &HashMap<ColumnPosition, ExpressionId, RepeatableState>,
         rel_child_id: NodeId,
+        scan: Option<&str>,
     ) -> Result<(NodeId, NodeId), SbroadError> {
         // Create Reference node from given table column.
         fn create_ref_from_column(
@@ -522,7 +650,7 @@ impl Plan {
         let mut update_columns_map =
             HashMap::with_capacity_and_hasher(update_defs.len(), RepeatableState);
         // Helper map between table column position and corresponding column position in child's output
-        let child_map = self.table_position_map(relation, rel_child_id)?;
+        let child_map = self.table_position_map(relation, rel_child_id, scan)?;
 
         let update_kind = if is_sharded_update {
             // For sharded Update Projection has the following format:
@@ -665,6 +793,7 @@ impl Plan {
             update_columns_map,
             output: upd_output,
             strategy: update_kind,
+            returning: None,
         };
         let update_id = self.add_relational(update_node.into())?;
         self.replace_parent_in_subtree(upd_output, None, Some(update_id))?;
@@ -761,6 +890,8 @@ impl Plan {
             child,
             output,
             conflict_strategy,
+            conflict_update: None,
+            returning: None,
         };
         let insert_id = self.nodes.push(insert.into());
         self.replace_parent_in_subtree(output, None, Some(insert_id))?;
@@ -1270,41 +1401,162 @@ impl Plan {
                     .get_relational_output(child_id)
                     .expect("projection has an output tuple");
             }
+            self.rename_cte_columns(child_output_id, columns)?;
+        }
 
-            // If CTE has explicit column names, let's rename the columns in the child projection.
-            let child_columns = self
-                .get_expression_node(child_output_id)
-                .expect("output row")
-                .clone_row_list()?;
-            if child_columns.len() != columns.len() {
-                return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
-                    "expected {} columns in CTE, got {}",
-                    child_columns.len(),
-                    columns.len()
-                )));
-            }
-            for (col_id, col_name) in child_columns.into_iter().zip(columns.into_iter()) {
-                let col_alias = self
-                    .get_mut_expression_node(col_id)
-                    .expect("column expression");
-                if let MutExpression::Alias(Alias { name, .. }) = col_alias {
-                    *name = col_name;
-                } else {
-                    panic!("Expected a row of aliases in the output tuple");
-                };
-            }
+        self.add_cte_scan(child_id, alias, None)
+    }
+
+    /// Renames the columns of the subquery in the `FROM` clause with explicit
+    /// column aliases: `(VALUES (1, 2)) AS t (a, b)`. Like in CTEs, the subquery
+    /// is wrapped with a projection renaming the columns and with another
+    /// subquery. The columns without aliases keep their names.
+    ///
+    /// # Errors
+    /// - more aliases than the subquery columns.
+    pub fn add_sub_query_with_columns(
+        &mut self,
+        sq_id: NodeId,
+        alias: &str,
+        mut columns: Vec<SmolStr>,
+    ) -> Result<NodeId, SbroadError> {
+        let proj_id = self.add_proj(sq_id, vec![], &[], false, false)?;
+        let proj_output_id = self.get_relational_output(proj_id)?;
+        let names = self.get_relational_aliases(proj_id)?;
+        if columns.len() > names.len() {
+            return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                "subquery {} has {} columns available but {} columns specified",
+                to_user(alias),
+                names.len(),
+                columns.len()
+            )));
         }
+        columns.extend(names.into_iter().skip(columns.len()));
+        self.rename_cte_columns(proj_output_id, columns)?;
+        self.add_sub_query(proj_id, Some(alias))
+    }
 
-        let output = self.add_row_for_output(child_id, &[], true, None)?;
+    /// Appends a reference to the recursive CTE from its own recursive part.
+    /// The output of the reference is described by the anchor (non-recursive)
+    /// part of the CTE, so the explicit CTE column names are applied to it.
+    ///
+    /// # Errors
+    /// - anchor is not a simple select;
+    /// - CTE has incorrect amount of columns.
+    pub fn add_recursive_cte_ref(
+        &mut self,
+        anchor: NodeId,
+        alias: SmolStr,
+        columns: Vec<SmolStr>,
+    ) -> Result<NodeId, SbroadError> {
+        let anchor_node = self.get_relation_node(anchor)?;
+        if !matches!(
+            anchor_node,
+            Relational::Projection { .. } | Relational::SelectWithoutScan { .. }
+        ) {
+            return Err(SbroadError::Unsupported(
+                Entity::Cte,
+                Some(format_smolstr!(
+                    "non-recursive part of the recursive CTE {} must be a simple SELECT",
+                    to_user(&alias)
+                )),
+            ));
+        }
+        if !columns.is_empty() {
+            let anchor_output_id = anchor_node.output();
+            self.rename_cte_columns(anchor_output_id, columns)?;
+        }
+        let recursive = RecursiveCte {
+            name: alias.clone(),
+            is_self_reference: true,
+        };
+        self.add_cte_scan(anchor, alias, Some(recursive))
+    }
+
+    /// Appends a new recursive CTE node to the plan arena. The child must be
+    /// a union of the non-recursive and the recursive parts. The query fails
+    /// if the CTE produces more rows than `sql_recursion_row_max` option allows.
+    ///
+    /// # Errors
+    /// - child is not a UNION [ALL] node.
+    pub fn add_recursive_cte(
+        &mut self,
+        child: NodeId,
+        alias: SmolStr,
+    ) -> Result<NodeId, SbroadError> {
+        let child_node = self.get_relation_node(child)?;
+        if !matches!(
+            child_node,
+            Relational::Union { .. } | Relational::UnionAll { .. }
+        ) {
+            return Err(SbroadError::Invalid(
+                Entity::Cte,
+                Some(format_smolstr!(
+                    "recursive CTE {} must be a UNION [ALL] of non-recursive and recursive parts",
+                    to_user(&alias)
+                )),
+            ));
+        }
+        let limit = RecursiveCte::limit(self.options.sql_recursion_row_max);
+        let limit_id = self.add_limit(child, limit, 0)?;
+        let recursive = RecursiveCte {
+            name: alias.clone(),
+            is_self_reference: false,
+        };
+        self.add_cte_scan(limit_id, alias, Some(recursive))
+    }
+
+    /// Appends a CTE scan node over already prepared CTE subtree.
+    ///
+    /// # Errors
+    /// - failed to build the output tuple.
+    pub fn add_cte_scan(
+        &mut self,
+        child: NodeId,
+        alias: SmolStr,
+        recursive: Option<RecursiveCte>,
+    ) -> Result<NodeId, SbroadError> {
+        let output = self.add_row_for_output(child, &[], true, None)?;
         let cte = ScanCte {
             alias,
-            child: child_id,
+            child,
             output,
+            recursive,
         };
         let cte_id = self.add_relational(cte.into())?;
         Ok(cte_id)
     }
 
+    /// Renames the columns of the CTE child output tuple with explicit CTE column names.
+    fn rename_cte_columns(
+        &mut self,
+        output_id: NodeId,
+        columns: Vec<SmolStr>,
+    ) -> Result<(), SbroadError> {
+        let child_columns = self
+            .get_expression_node(output_id)
+            .expect("output row")
+            .clone_row_list()?;
+        if child_columns.len() != columns.len() {
+            return Err(SbroadError::UnexpectedNumberOfValues(format_smolstr!(
+                "expected {} columns in CTE, got {}",
+                child_columns.len(),
+                columns.len()
+            )));
+        }
+        for (col_id, col_name) in child_columns.into_iter().zip(columns.into_iter()) {
+            let col_alias = self
+                .get_mut_expression_node(col_id)
+                .expect("column expression");
+            if let MutExpression::Alias(Alias { name, .. }) = col_alias {
+                *name = col_name;
+            } else {
+                panic!("Expected a row of aliases in the output tuple");
+            };
+        }
+        Ok(())
+    }
+
     /// Adds union all node.
     ///
     /// # Errors
@@ -1359,11 +1611,17 @@ impl Plan {
     ///
     /// # Errors
     /// - Row node is not of a row type
-    pub fn add_limit(&mut self, select: NodeId, limit: u64) -> Result<NodeId, SbroadError> {
+    pub fn add_limit(
+        &mut self,
+        select: NodeId,
+        limit: Option<u64>,
+        offset: u64,
+    ) -> Result<NodeId, SbroadError> {
         let output = self.add_row_for_output(select, &[], true, None)?;
         let limit = Limit {
             output,
             limit,
+            offset,
             child: select,
         };
 
@@ -1742,6 +2000,7 @@ impl Plan {
         &self,
         table_name: &str,
         rel_id: NodeId,
+        scan: Option<&str>,
     ) -> Result<HashMap<ColumnPosition, ColumnPosition>, SbroadError> {
         let table = self.get_relation_or_error(table_name)?;
         let alias_to_pos = ColumnPositionMap::new(self, rel_id)?;
@@ -1751,7 +2010,11 @@ impl Plan {
             if let ColumnRole::Sharding = col.role {
                 continue;
             }
-            let output_pos = alias_to_pos.get(col.name.as_str())?;
+            let output_pos = if scan.is_some() {
+                alias_to_pos.get_with_scan(col.name.as_str(), scan)?
+            } else {
+                alias_to_pos.get(col.name.as_str())?
+            };
             map.insert(table_pos, output_pos);
         }
         Ok(map)
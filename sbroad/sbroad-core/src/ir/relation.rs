@@ -21,6 +21,7 @@ use serde::ser::{Serialize as SerSerialize, SerializeMap, Serializer};
 use serde::{Deserialize, Deserializer, Serialize};
 
 use crate::errors::{Action, Entity, SbroadError};
+use crate::ir::ddl::{PartitionBound, PartitionStrategy};
 use crate::ir::value::Value;
 
 use super::distribution::Key;
@@ -39,9 +40,11 @@ pub enum Type {
     Decimal,
     Double,
     Integer,
+    Interval,
     String,
     Uuid,
     Unsigned,
+    Varbinary,
 }
 
 /// Derived type (`Some<Type>`) or its absence (`None`).
@@ -80,9 +83,11 @@ impl fmt::Display for Type {
             Type::Datetime => write!(f, "datetime"),
             Type::Double => write!(f, "double"),
             Type::Integer => write!(f, "integer"),
+            Type::Interval => write!(f, "interval"),
             Type::String => write!(f, "string"),
             Type::Uuid => write!(f, "uuid"),
             Type::Unsigned => write!(f, "unsigned"),
+            Type::Varbinary => write!(f, "varbinary"),
             Type::Any => write!(f, "any"),
             Type::Map => write!(f, "map"),
         }
@@ -106,9 +111,11 @@ impl From<&Type> for FieldType {
             Type::Datetime => FieldType::Datetime,
             Type::Double => FieldType::Double,
             Type::Integer => FieldType::Integer,
+            Type::Interval => FieldType::Interval,
             Type::Uuid => FieldType::Uuid,
             Type::String => FieldType::String,
             Type::Unsigned => FieldType::Unsigned,
+            Type::Varbinary => FieldType::Varbinary,
             Type::Array => FieldType::Array,
             Type::Any => FieldType::Any,
             Type::Map => FieldType::Map,
@@ -124,9 +131,11 @@ impl From<&Type> for SpaceFieldType {
             Type::Decimal => SpaceFieldType::Decimal,
             Type::Double => SpaceFieldType::Double,
             Type::Integer => SpaceFieldType::Integer,
+            Type::Interval => SpaceFieldType::Interval,
             Type::String => SpaceFieldType::String,
             Type::Uuid => SpaceFieldType::Uuid,
             Type::Unsigned => SpaceFieldType::Unsigned,
+            Type::Varbinary => SpaceFieldType::Varbinary,
             Type::Array => SpaceFieldType::Array,
             Type::Any => SpaceFieldType::Any,
             Type::Map => SpaceFieldType::Map,
@@ -148,10 +157,9 @@ impl TryFrom<SpaceFieldType> for Type {
             SpaceFieldType::Unsigned => Ok(Type::Unsigned),
             SpaceFieldType::Array => Ok(Type::Array),
             SpaceFieldType::Uuid => Ok(Type::Uuid),
-            SpaceFieldType::Any
-            | SpaceFieldType::Varbinary
-            | SpaceFieldType::Map
-            | SpaceFieldType::Interval => Err(SbroadError::NotImplemented(
+            SpaceFieldType::Varbinary => Ok(Type::Varbinary),
+            SpaceFieldType::Interval => Ok(Type::Interval),
+            SpaceFieldType::Any | SpaceFieldType::Map => Err(SbroadError::NotImplemented(
                 Entity::Type,
                 field_type.to_smolstr(),
             )),
@@ -177,9 +185,11 @@ impl Type {
             "decimal" => Ok(Type::Decimal),
             "double" => Ok(Type::Double),
             "integer" => Ok(Type::Integer),
+            "interval" => Ok(Type::Interval),
             "string" | "text" => Ok(Type::String),
             "uuid" => Ok(Type::Uuid),
             "unsigned" => Ok(Type::Unsigned),
+            "varbinary" | "bytea" => Ok(Type::Varbinary),
             "array" => Ok(Type::Array),
             "any" => Ok(Type::Any),
             "map" => Ok(Type::Map),
@@ -204,6 +214,7 @@ impl Type {
                 | Type::String
                 | Type::Uuid
                 | Type::Unsigned
+                | Type::Varbinary
         )
     }
 
@@ -219,6 +230,8 @@ impl Type {
                     Type::Double | Type::Integer | Type::Unsigned | Type::Decimal,
                 )
                 | (Type::String | Type::Uuid, Type::String | Type::Uuid)
+                | (Type::String | Type::Varbinary, Type::Varbinary)
+                | (Type::String | Type::Interval, Type::Interval)
         )
     }
 }
@@ -245,6 +258,12 @@ pub struct Column {
     /// Column is_nullable status.
     /// Possibly `None` (e.g. in case it's taken from Tarantool local query execution metatada).
     pub is_nullable: bool,
+    /// SQL text of the default expression used when the column
+    /// is omitted in `INSERT`.
+    pub default: Option<SmolStr>,
+    /// `GENERATED ALWAYS` column, explicit values of the column are
+    /// rejected by `INSERT` and `UPDATE`.
+    pub generated_always: bool,
 }
 
 impl Default for Column {
@@ -254,6 +273,8 @@ impl Default for Column {
             r#type: DerivedType::unknown(),
             role: ColumnRole::default(),
             is_nullable: true,
+            default: None,
+            generated_always: false,
         }
     }
 }
@@ -267,9 +288,11 @@ impl From<Column> for Field {
                 Type::Decimal => Field::decimal(column.name),
                 Type::Double => Field::double(column.name),
                 Type::Integer => Field::integer(column.name),
+                Type::Interval => Field::interval(column.name),
                 Type::String => Field::string(column.name),
                 Type::Uuid => Field::uuid(column.name),
                 Type::Unsigned => Field::unsigned(column.name),
+                Type::Varbinary => Field::varbinary(column.name),
                 Type::Array => Field::array(column.name),
                 Type::Any => Field::any(column.name),
                 Type::Map => Field::map(column.name),
@@ -314,9 +337,11 @@ impl SerSerialize for Column {
                 Type::Decimal => "decimal",
                 Type::Double => "double",
                 Type::Integer => "integer",
+                Type::Interval => "interval",
                 Type::String => "string",
                 Type::Uuid => "uuid",
                 Type::Unsigned => "unsigned",
+                Type::Varbinary => "varbinary",
                 Type::Array => "array",
                 Type::Any => "any",
                 Type::Map => "map",
@@ -378,10 +403,12 @@ impl<'de> Visitor<'de> for ColumnVisitor {
             "decimal" | "numeric" => DerivedType::new(Type::Decimal),
             "double" => DerivedType::new(Type::Double),
             "integer" => DerivedType::new(Type::Integer),
+            "interval" => DerivedType::new(Type::Interval),
             "string" | "text" | "varchar" => DerivedType::new(Type::String),
             "unsigned" => DerivedType::new(Type::Unsigned),
             "array" => DerivedType::new(Type::Array),
             "uuid" => DerivedType::new(Type::Uuid),
+            "varbinary" | "bytea" => DerivedType::new(Type::Varbinary),
             "map" => DerivedType::new(Type::Map),
             "unknown" => DerivedType::unknown(),
             s => return Err(Error::custom(format!("unsupported column type: {s}"))),
@@ -408,9 +435,18 @@ impl Column {
             r#type: ty,
             role,
             is_nullable,
+            default: None,
+            generated_always: false,
         }
     }
 
+    /// Set the default expression of the column.
+    #[must_use]
+    pub fn with_default(mut self, default: Option<SmolStr>) -> Self {
+        self.default = default;
+        self
+    }
+
     /// Get column role.
     #[must_use]
     pub fn get_role(&self) -> &ColumnRole {
@@ -569,6 +605,47 @@ fn table_new_impl<'column>(
     Ok((pos_map, Key::new(primary_positions)))
 }
 
+/// Partition of a partitioned table, it is a table itself.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Partition {
+    /// Name of the partition table.
+    pub name: SmolStr,
+    pub bound: PartitionBound,
+}
+
+/// Declarative partitioning of a table. The partitioned table
+/// is always empty, the tuples are stored in its partitions.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Partitioning {
+    pub strategy: PartitionStrategy,
+    /// Position of the partition key column.
+    pub column: usize,
+    pub partitions: Vec<Partition>,
+}
+
+impl Partitioning {
+    /// Get the partition storing the tuples with the given partition key.
+    ///
+    /// # Errors
+    /// - No partition can store the key.
+    pub fn partition_for(&self, key: &Value) -> Result<&Partition, SbroadError> {
+        let mut default = None;
+        for partition in &self.partitions {
+            if partition.bound == PartitionBound::Default {
+                default = Some(partition);
+            } else if partition.bound.contains(key) {
+                return Ok(partition);
+            }
+        }
+        default.ok_or_else(|| {
+            SbroadError::NotFound(
+                Entity::Table,
+                format_smolstr!("partition for the partition key value {key}"),
+            )
+        })
+    }
+}
+
 /// Table is a tuple storage in the cluster.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 pub struct Table {
@@ -580,6 +657,8 @@ pub struct Table {
     pub name: SmolStr,
     pub kind: TableKind,
     pub tier: Option<SmolStr>,
+    /// Partitions of the table if it is partitioned.
+    pub partitioning: Option<Partitioning>,
 }
 
 impl Table {
@@ -629,6 +708,7 @@ impl Table {
             primary_key,
             kind,
             tier: None,
+            partitioning: None,
         })
     }
 
@@ -650,6 +730,7 @@ impl Table {
             primary_key,
             kind,
             tier: None,
+            partitioning: None,
         })
     }
 
@@ -671,6 +752,7 @@ impl Table {
             primary_key,
             kind,
             tier: None,
+            partitioning: None,
         })
     }
 
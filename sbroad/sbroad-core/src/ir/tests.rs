@@ -19,6 +19,8 @@ pub fn column_integer_user_non_null(name: SmolStr) -> Column {
         r#type: DerivedType::new(Type::Integer),
         role: ColumnRole::User,
         is_nullable: false,
+        default: None,
+        generated_always: false,
     }
 }
 
@@ -46,6 +48,8 @@ pub fn column_user_non_null(name: SmolStr, r#type: Type) -> Column {
         r#type: DerivedType::new(r#type),
         role: ColumnRole::User,
         is_nullable: false,
+        default: None,
+        generated_always: false,
     }
 }
 
@@ -74,6 +78,8 @@ pub fn sharding_column() -> Column {
         r#type: DerivedType::new(Type::Unsigned),
         role: ColumnRole::Sharding,
         is_nullable: true,
+        default: None,
+        generated_always: false,
     }
 }
 
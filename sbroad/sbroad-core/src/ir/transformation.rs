@@ -8,6 +8,7 @@ pub mod dnf;
 pub mod equality_propagation;
 pub mod merge_tuples;
 pub mod not_push_down;
+pub mod partition;
 pub mod redistribution;
 pub mod split_columns;
 
@@ -0,0 +1,317 @@
+//! Replace scans of the partitioned tables with scans of their partitions.
+//!
+//! A partitioned table is always empty, its tuples are stored in the
+//! partitions. A scan of a partitioned table is replaced with the scans
+//! of its partitions combined with `UNION ALL`. For example, the query
+//! with table `t` partitioned by column `a`:
+//! ```sql
+//! SELECT * FROM t WHERE a >= 10
+//! ```
+//! would be converted to:
+//! ```sql
+//! SELECT * FROM (
+//!     SELECT * FROM t_10_20 UNION ALL SELECT * FROM t_20_30
+//! ) AS t WHERE a >= 10
+//! ```
+//! The partitions that can't contain the tuples satisfying the filter over
+//! the partition key are skipped. When a single partition is left, it is
+//! scanned directly.
+
+use crate::errors::SbroadError;
+use crate::ir::ddl::{PartitionBound, PartitionStrategy};
+use crate::ir::node::expression::Expression;
+use crate::ir::node::relational::{MutRelational, Relational};
+use crate::ir::node::{BoolExpr, Constant, NodeId, Reference, Row, ScanRelation, Selection};
+use crate::ir::operator::Bool;
+use crate::ir::relation::{DerivedType, Partitioning};
+use crate::ir::tree::traversal::{LevelNode, PostOrder, REL_CAPACITY};
+use crate::ir::value::{TrivalentOrdering, Value};
+use crate::ir::Plan;
+use smol_str::SmolStr;
+
+/// Check `left op right`. The comparisons with unknown result
+/// are considered satisfied.
+fn satisfies(left: &Value, op: Bool, right: &Value) -> bool {
+    let Some(ordering) = left.partial_cmp(right) else {
+        return true;
+    };
+    match ordering {
+        TrivalentOrdering::Unknown => true,
+        TrivalentOrdering::Less => matches!(op, Bool::Lt | Bool::LtEq),
+        TrivalentOrdering::Equal => matches!(op, Bool::Eq | Bool::LtEq | Bool::GtEq),
+        TrivalentOrdering::Greater => matches!(op, Bool::Gt | Bool::GtEq),
+    }
+}
+
+/// Check if the partition may contain the tuples with `key op value`.
+fn bound_may_match(bound: &PartitionBound, op: Bool, value: &Value) -> bool {
+    match bound {
+        PartitionBound::Range { from, to } => {
+            // The range is [from, to).
+            let from_matches = |op| {
+                from.as_ref()
+                    .map_or(true, |from| satisfies(from, op, value))
+            };
+            let to_matches = || {
+                to.as_ref()
+                    .map_or(true, |to| satisfies(to, Bool::Gt, value))
+            };
+            match op {
+                Bool::Eq => from_matches(Bool::LtEq) && to_matches(),
+                Bool::Lt | Bool::LtEq => from_matches(op),
+                Bool::Gt | Bool::GtEq => to_matches(),
+                _ => true,
+            }
+        }
+        PartitionBound::List { values } => values.iter().any(|v| satisfies(v, op, value)),
+        // Hashes are not ordered, only the equality prunes the partitions.
+        PartitionBound::Hash { .. } => op != Bool::Eq || bound.contains(value),
+        PartitionBound::Default => true,
+    }
+}
+
+/// Partitions that may contain the tuples with `key op value`.
+fn matching_partitions(partitioning: &Partitioning, op: Bool, value: &Value) -> Vec<bool> {
+    let partitions = &partitioning.partitions;
+    partitions
+        .iter()
+        .map(|partition| match partition.bound {
+            // Default partition stores only the keys not stored by the others.
+            PartitionBound::Default if op == Bool::Eq => {
+                !partitions.iter().any(|p| p.bound.contains(value))
+            }
+            _ => bound_may_match(&partition.bound, op, value),
+        })
+        .collect()
+}
+
+/// Swap the operands of the comparison.
+pub(crate) fn flip(op: Bool) -> Bool {
+    match op {
+        Bool::Lt => Bool::Gt,
+        Bool::LtEq => Bool::GtEq,
+        Bool::Gt => Bool::Lt,
+        Bool::GtEq => Bool::LtEq,
+        _ => op,
+    }
+}
+
+impl Plan {
+    /// Replace scans of the partitioned tables with scans of the partitions
+    /// that may contain the tuples satisfying the query filters.
+    ///
+    /// # Errors
+    /// - Plan is inconsistent.
+    pub fn expand_partitions(&mut self) -> Result<(), SbroadError> {
+        if self
+            .relations
+            .tables
+            .values()
+            .all(|table| table.partitioning.is_none())
+        {
+            return Ok(());
+        }
+
+        let top_id = self.get_top()?;
+        let mut post_tree =
+            PostOrder::with_capacity(|node| self.nodes.rel_iter(node), REL_CAPACITY);
+        post_tree.populate_nodes(top_id);
+        let nodes = post_tree.take_nodes();
+        drop(post_tree);
+
+        // Pairs of the partitioned table scan and its parent.
+        let mut scans = Vec::new();
+        for LevelNode(_, parent_id) in nodes {
+            for child_id in self.get_relation_node(parent_id)?.children().iter() {
+                if let Relational::ScanRelation(ScanRelation { relation, .. }) =
+                    self.get_relation_node(*child_id)?
+                {
+                    if self.get_relation_or_error(relation)?.partitioning.is_some() {
+                        scans.push((*child_id, parent_id));
+                    }
+                }
+            }
+        }
+
+        for (scan_id, parent_id) in scans {
+            self.expand_partitioned_scan(scan_id, parent_id)?;
+        }
+        Ok(())
+    }
+
+    fn expand_partitioned_scan(
+        &mut self,
+        scan_id: NodeId,
+        parent_id: NodeId,
+    ) -> Result<(), SbroadError> {
+        let Relational::ScanRelation(ScanRelation {
+            relation, alias, ..
+        }) = self.get_relation_node(scan_id)?
+        else {
+            unreachable!("partitioned table scan expected");
+        };
+        let alias = alias.clone().unwrap_or_else(|| relation.clone());
+        let table = self.get_relation_or_error(relation)?;
+        let Some(partitioning) = &table.partitioning else {
+            unreachable!("partitioned table expected");
+        };
+        let key_type = table.columns[partitioning.column].r#type;
+
+        let mask = match self.get_relation_node(parent_id)? {
+            Relational::Selection(Selection {
+                children, filter, ..
+            }) if children.first() == Some(&scan_id) => {
+                self.filter_partitions(parent_id, *filter, partitioning, &key_type)?
+            }
+            _ => vec![true; partitioning.partitions.len()],
+        };
+        let partitions: Vec<SmolStr> = partitioning
+            .partitions
+            .iter()
+            .zip(mask)
+            .filter_map(|(partition, matches)| matches.then(|| partition.name.clone()))
+            .collect();
+
+        match partitions.as_slice() {
+            // The partitioned table itself is empty, so
+            // its scan produces no tuples as expected.
+            [] => {}
+            [partition] => {
+                if let MutRelational::ScanRelation(ScanRelation {
+                    relation,
+                    alias: scan_alias,
+                    ..
+                }) = self.get_mut_relation_node(scan_id)?
+                {
+                    *relation = partition.clone();
+                    *scan_alias = Some(alias);
+                }
+            }
+            [first, other @ ..] => {
+                let first_scan_id = self.add_scan(first, None)?;
+                let mut union_id = self.add_proj(first_scan_id, vec![], &[], false, true)?;
+                for partition in other {
+                    let scan_id = self.add_scan(partition, None)?;
+                    let proj_id = self.add_proj(scan_id, vec![], &[], false, true)?;
+                    union_id = self.add_union(union_id, proj_id, false)?;
+                }
+                let sq_id = self.add_sub_query(union_id, Some(alias.as_str()))?;
+                self.change_child(parent_id, scan_id, sq_id)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the mask of the partitions that may contain the tuples
+    /// satisfying the selection filter.
+    fn filter_partitions(
+        &self,
+        selection_id: NodeId,
+        expr_id: NodeId,
+        partitioning: &Partitioning,
+        key_type: &DerivedType,
+    ) -> Result<Vec<bool>, SbroadError> {
+        let all = vec![true; partitioning.partitions.len()];
+        let Expression::Bool(BoolExpr { left, op, right }) = self.get_expression_node(expr_id)?
+        else {
+            return Ok(all);
+        };
+        let (left, op, right) = (*left, *op, *right);
+        match op {
+            Bool::And | Bool::Or => {
+                let left = self.filter_partitions(selection_id, left, partitioning, key_type)?;
+                let right = self.filter_partitions(selection_id, right, partitioning, key_type)?;
+                Ok(left
+                    .into_iter()
+                    .zip(right)
+                    .map(|(l, r)| if op == Bool::And { l && r } else { l || r })
+                    .collect())
+            }
+            Bool::In => {
+                if !self.is_partition_key(selection_id, left, partitioning)? {
+                    return Ok(all);
+                }
+                let Expression::Row(Row { list, .. }) = self.get_expression_node(right)? else {
+                    return Ok(all);
+                };
+                let mut mask = vec![false; partitioning.partitions.len()];
+                for id in list {
+                    let Some(value) = self.get_partition_constant(*id, partitioning, key_type)?
+                    else {
+                        return Ok(all);
+                    };
+                    let matching = matching_partitions(partitioning, Bool::Eq, &value);
+                    for (m, matches) in mask.iter_mut().zip(matching) {
+                        *m |= matches;
+                    }
+                }
+                Ok(mask)
+            }
+            Bool::Eq | Bool::Lt | Bool::LtEq | Bool::Gt | Bool::GtEq => {
+                let (op, value_id) = if self.is_partition_key(selection_id, left, partitioning)? {
+                    (op, right)
+                } else if self.is_partition_key(selection_id, right, partitioning)? {
+                    (flip(op), left)
+                } else {
+                    return Ok(all);
+                };
+                match self.get_partition_constant(value_id, partitioning, key_type)? {
+                    Some(value) => Ok(matching_partitions(partitioning, op, &value)),
+                    None => Ok(all),
+                }
+            }
+            _ => Ok(all),
+        }
+    }
+
+    /// Unwrap the expression from a single column row.
+    pub(crate) fn unwrap_single_column(&self, expr_id: NodeId) -> Result<NodeId, SbroadError> {
+        if let Expression::Row(Row { list, .. }) = self.get_expression_node(expr_id)? {
+            if let [inner_id] = list.as_slice() {
+                return Ok(*inner_id);
+            }
+        }
+        Ok(expr_id)
+    }
+
+    fn is_partition_key(
+        &self,
+        selection_id: NodeId,
+        expr_id: NodeId,
+        partitioning: &Partitioning,
+    ) -> Result<bool, SbroadError> {
+        let expr_id = self.unwrap_single_column(expr_id)?;
+        let is_key = matches!(
+            self.get_expression_node(expr_id)?,
+            Expression::Reference(Reference {
+                parent: Some(parent),
+                targets: Some(targets),
+                position,
+                ..
+            }) if *parent == selection_id && targets == &[0] && *position == partitioning.column
+        );
+        Ok(is_key)
+    }
+
+    /// Get the constant compared with the partition key. Hash partitions
+    /// are pruned only by the constants of the partition key type, as the
+    /// hash depends on the value type.
+    fn get_partition_constant(
+        &self,
+        expr_id: NodeId,
+        partitioning: &Partitioning,
+        key_type: &DerivedType,
+    ) -> Result<Option<Value>, SbroadError> {
+        let expr_id = self.unwrap_single_column(expr_id)?;
+        let Expression::Constant(Constant { value }) = self.get_expression_node(expr_id)? else {
+            return Ok(None);
+        };
+        if partitioning.strategy != PartitionStrategy::Hash {
+            return Ok(Some(value.clone()));
+        }
+        Ok(key_type.get().and_then(|ty| value.clone().cast(ty).ok()))
+    }
+}
+
+#[cfg(test)]
+mod tests;
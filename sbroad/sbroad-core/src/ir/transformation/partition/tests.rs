@@ -0,0 +1,155 @@
+use crate::ir::ddl::PartitionBound;
+use crate::ir::node::relational::Relational;
+use crate::ir::node::ScanRelation;
+use crate::ir::transformation::helpers::sql_to_ir;
+use crate::ir::tree::traversal::{PostOrder, REL_CAPACITY};
+use crate::ir::value::Value;
+use pretty_assertions::assert_eq;
+
+/// Expand the partitioned tables in the query and return
+/// the names of the scanned tables.
+fn scanned_tables(query: &str, params: Vec<Value>) -> Vec<String> {
+    let mut plan = sql_to_ir(query, params);
+    plan.expand_partitions().unwrap();
+    let top_id = plan.get_top().unwrap();
+    let mut post_tree = PostOrder::with_capacity(|node| plan.nodes.rel_iter(node), REL_CAPACITY);
+    post_tree.populate_nodes(top_id);
+    let mut tables: Vec<String> = post_tree
+        .take_nodes()
+        .into_iter()
+        .filter_map(
+            |level_node| match plan.get_relation_node(level_node.1).unwrap() {
+                Relational::ScanRelation(ScanRelation { relation, .. }) => {
+                    Some(relation.to_string())
+                }
+                _ => None,
+            },
+        )
+        .collect();
+    tables.sort();
+    tables
+}
+
+#[test]
+fn partition_range_eq() {
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE "d" = 5"#, vec![]);
+    assert_eq!(tables, vec!["events_old"]);
+
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE 15 = "d""#, vec![]);
+    assert_eq!(tables, vec!["events_new"]);
+
+    // The key is not stored by any other partition.
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE "d" = 25"#, vec![]);
+    assert_eq!(tables, vec!["events_default"]);
+}
+
+#[test]
+fn partition_range_cmp() {
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE "d" >= 10"#, vec![]);
+    assert_eq!(tables, vec!["events_default", "events_new"]);
+
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE "d" < 10"#, vec![]);
+    assert_eq!(tables, vec!["events_default", "events_old"]);
+
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE 10 > "d""#, vec![]);
+    assert_eq!(tables, vec!["events_default", "events_old"]);
+}
+
+#[test]
+fn partition_range_bool() {
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE "d" IN (1, 15)"#, vec![]);
+    assert_eq!(tables, vec!["events_new", "events_old"]);
+
+    let tables = scanned_tables(
+        r#"SELECT * FROM "events" WHERE "d" = 1 OR "d" = 15"#,
+        vec![],
+    );
+    assert_eq!(tables, vec!["events_new", "events_old"]);
+
+    let tables = scanned_tables(
+        r#"SELECT * FROM "events" WHERE "d" >= 10 AND "d" < 20"#,
+        vec![],
+    );
+    assert_eq!(tables, vec!["events_default", "events_new"]);
+}
+
+#[test]
+fn partition_range_params() {
+    let tables = scanned_tables(
+        r#"SELECT * FROM "events" WHERE "d" = ?"#,
+        vec![Value::from(12_i64)],
+    );
+    assert_eq!(tables, vec!["events_new"]);
+}
+
+#[test]
+fn partition_no_pruning() {
+    let all = vec!["events_default", "events_new", "events_old"];
+
+    let tables = scanned_tables(r#"SELECT * FROM "events""#, vec![]);
+    assert_eq!(tables, all);
+
+    let tables = scanned_tables(r#"SELECT * FROM "events" WHERE "id" = 1"#, vec![]);
+    assert_eq!(tables, all);
+
+    let tables = scanned_tables(
+        r#"SELECT * FROM "events" WHERE "d" = 1 OR "id" = 1"#,
+        vec![],
+    );
+    assert_eq!(tables, all);
+}
+
+#[test]
+fn partition_list() {
+    let tables = scanned_tables(r#"SELECT * FROM "lt" WHERE "b" = 'x'"#, vec![]);
+    assert_eq!(tables, vec!["lt_xy"]);
+
+    let tables = scanned_tables(r#"SELECT * FROM "lt" WHERE "b" IN ('y', 'z')"#, vec![]);
+    assert_eq!(tables, vec!["lt_xy", "lt_z"]);
+
+    // No partition can store the key, the scan of the
+    // (always empty) partitioned table is kept.
+    let tables = scanned_tables(r#"SELECT * FROM "lt" WHERE "b" = 'q'"#, vec![]);
+    assert_eq!(tables, vec!["lt"]);
+}
+
+#[test]
+fn partition_hash() {
+    let bound = |remainder| PartitionBound::Hash {
+        modulus: 2,
+        remainder,
+    };
+    for key in 1..=4_i64 {
+        let expected = if bound(0).contains(&Value::from(key)) {
+            "ht_0"
+        } else {
+            "ht_1"
+        };
+        let query = format!(r#"SELECT * FROM "ht" WHERE "b" = {key}"#);
+        let tables = scanned_tables(&query, vec![]);
+        assert_eq!(tables, vec![expected]);
+        assert!(bound(0).contains(&Value::from(key)) != bound(1).contains(&Value::from(key)));
+    }
+
+    // Hashes are not ordered, so only the equality prunes the partitions.
+    let tables = scanned_tables(r#"SELECT * FROM "ht" WHERE "b" > 1"#, vec![]);
+    assert_eq!(tables, vec!["ht_0", "ht_1"]);
+}
+
+#[test]
+fn partition_join() {
+    let tables = scanned_tables(
+        r#"SELECT * FROM "events" JOIN "lt" ON "events"."id" = "lt"."a" WHERE "lt"."b" = 'z'"#,
+        vec![],
+    );
+    assert_eq!(
+        tables,
+        vec![
+            "events_default",
+            "events_new",
+            "events_old",
+            "lt_xy",
+            "lt_z"
+        ]
+    );
+}
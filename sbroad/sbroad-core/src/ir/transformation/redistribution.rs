@@ -12,13 +12,13 @@ use crate::ir::api::children::Children;
 use crate::ir::distribution::{Distribution, Key, KeySet};
 use crate::ir::expression::ColumnPositionMap;
 use crate::ir::node::expression::Expression;
-use crate::ir::node::relational::{RelOwned, Relational};
+use crate::ir::node::relational::{MutRelational, RelOwned, Relational};
 use crate::ir::operator::{Bool, JoinKind, OrderByEntity, Unary, UpdateStrategy};
 
 use crate::ir::node::{
     BoolExpr, Except, GroupBy, Having, Intersect, Join, Limit, NamedWindows, NodeId, OrderBy,
-    Projection, Reference, ScanCte, ScanRelation, ScanSubQuery, SelectWithoutScan, Selection,
-    UnaryExpr, Union, UnionAll, Update, Values, ValuesRow, Window,
+    Projection, RecursiveCte, Reference, ScanCte, ScanRelation, ScanSubQuery, SelectWithoutScan,
+    Selection, UnaryExpr, Union, UnionAll, Update, Values, ValuesRow, Window,
 };
 use crate::ir::transformation::redistribution::eq_cols::EqualityCols;
 use crate::ir::tree::traversal::{
@@ -29,8 +29,11 @@ use crate::ir::{Node, Plan};
 
 pub(crate) mod dml;
 pub(crate) mod eq_cols;
+pub(crate) mod full_join;
 pub(crate) mod groupby;
+pub(crate) mod inner_join;
 pub(crate) mod left_join;
+pub(crate) mod right_join;
 
 const CTE_CAPACITY: usize = 8;
 
@@ -142,6 +145,12 @@ pub enum MotionOpcode {
     AddMissingRowsForLeftJoin {
         motion_id: NodeId,
     },
+    /// Add the rows of the inner join child (materialized by motion with
+    /// `motion_id`) that have no match in the full join result. The last
+    /// column of the result is the marker of the inner rows.
+    AddMissingRowsForFullJoin {
+        motion_id: NodeId,
+    },
     /// When set to `true` this opcode serializes motion subtree to sql that produces
     /// empty table.
     ///
@@ -157,6 +166,9 @@ pub enum MotionOpcode {
     /// to `false`.
     SerializeAsEmptyTable(bool),
     RemoveDuplicates,
+    /// Call `unnest_arrays` method on vtable: expand the arrays returned
+    /// by the set-returning functions in the given columns into rows.
+    UnnestArrays(Vec<ColumnPosition>),
 }
 
 /// Helper struct that unwraps `Expression::Bool` fields.
@@ -1296,6 +1308,15 @@ impl Plan {
         cond_id: NodeId,
         join_kind: &JoinKind,
     ) -> Result<(), SbroadError> {
+        // Full join is executed as a left one with the missing inner
+        // rows added on the reduce stage, see `full_join` module.
+        if let JoinKind::FullOuter = join_kind {
+            let strategy = self.calculate_strategy_for_full_join(rel_id)?;
+            self.create_motion_nodes(strategy)?;
+            self.set_rows_distributions_in_expr(cond_id)?;
+            return Ok(());
+        }
+
         // If one of the children has Distribution::Single, then we can't compute Distribution of
         // Rows in condition, because in case of Single it depends on join condition, and computing
         // distribution of Row in condition makes no sense, so we handle the single distribution separately
@@ -1469,6 +1490,18 @@ impl Plan {
             };
             inner_map.insert(node_id, new_inner_policy.clone());
         }
+        if let JoinKind::RightOuter = join_kind {
+            if self.fix_strategy_for_right_join(rel_id, &new_inner_policy, &mut strategy)? {
+                self.create_motion_nodes(strategy)?;
+                return Ok(());
+            }
+        }
+        if let JoinKind::Inner = join_kind {
+            if self.fix_strategy_for_inner_join(rel_id, &new_inner_policy, &mut strategy)? {
+                self.create_motion_nodes(strategy)?;
+                return Ok(());
+            }
+        }
         strategy.add_child(inner_child, new_inner_policy, Program::default());
 
         {
@@ -1632,6 +1665,14 @@ impl Plan {
                 targets: vec![Target::Reference(0)],
             });
             inner_policy = MotionPolicy::Full;
+        } else if let (JoinKind::RightOuter, JoinChild::Outer) = (join_kind, segmented_child) {
+            // the same for the right join: we can't broadcast right (inner) table,
+            // so we broadcast the outer table and rehash the inner one
+            outer_policy = MotionPolicy::Full;
+            inner_policy = MotionPolicy::Segment(MotionKey {
+                // we can choose any distribution columns here
+                targets: vec![Target::Reference(0)],
+            });
         }
         (outer_policy, inner_policy)
     }
@@ -1740,7 +1781,20 @@ impl Plan {
                     join_kind,
                 )
             }
-            (Distribution::Any, Distribution::Single) => (MotionPolicy::None, MotionPolicy::Full),
+            (Distribution::Any, Distribution::Single) => {
+                if let JoinKind::RightOuter = join_kind {
+                    // inner table can't be safely broadcasted in case of RightJoin
+                    (
+                        MotionPolicy::Full,
+                        MotionPolicy::Segment(MotionKey {
+                            // we can choose any distribution columns here
+                            targets: vec![Target::Reference(0)],
+                        }),
+                    )
+                } else {
+                    (MotionPolicy::None, MotionPolicy::Full)
+                }
+            }
             (Distribution::Single, Distribution::Any) => {
                 if let JoinKind::LeftOuter = join_kind {
                     // outer table can't be safely broadcasted in case of LeftJoin see
@@ -2159,6 +2213,79 @@ impl Plan {
         Ok(map)
     }
 
+    /// Intersect is executed locally on the storages, so the equal rows
+    /// of both children must reside on the same node.
+    fn resolve_intersect_conflicts(&mut self, rel_id: NodeId) -> Result<Strategy, SbroadError> {
+        if !matches!(self.get_relation_node(rel_id)?, Relational::Intersect(_)) {
+            return Err(SbroadError::Invalid(
+                Entity::Relational,
+                Some("expected Intersect node".into()),
+            ));
+        }
+
+        let mut map = Strategy::new(rel_id);
+        let left_id = self.get_relational_child(rel_id, 0)?;
+        let right_id = self.get_relational_child(rel_id, 1)?;
+        let left_dist = self.get_rel_distribution(left_id)?;
+        let right_dist = self.get_rel_distribution(right_id)?;
+
+        let first_key = |keys: &KeySet| -> Result<MotionPolicy, SbroadError> {
+            let key = keys.iter().next().ok_or_else(|| {
+                SbroadError::Invalid(
+                    Entity::Distribution,
+                    Some("segment distribution is invalid: no keys found in the set".into()),
+                )
+            })?;
+            Ok(MotionPolicy::Segment(key.into()))
+        };
+        // Rows without a known distribution key are segmented by the first column.
+        let by_first_column = || {
+            MotionPolicy::Segment(MotionKey {
+                targets: vec![Target::Reference(0)],
+            })
+        };
+
+        let (left_motion, right_motion) = match (left_dist, right_dist) {
+            (
+                Distribution::Segment { keys: left_keys },
+                Distribution::Segment { keys: right_keys },
+            ) => {
+                if right_keys.intersection(left_keys).iter().next().is_some() {
+                    // Distribution key sets have common keys, no need for the data motion.
+                    (MotionPolicy::None, MotionPolicy::None)
+                } else {
+                    (MotionPolicy::None, first_key(left_keys)?)
+                }
+            }
+            (Distribution::Segment { keys }, Distribution::Any | Distribution::Single) => {
+                (MotionPolicy::None, first_key(keys)?)
+            }
+            (Distribution::Any | Distribution::Single, Distribution::Segment { keys }) => {
+                (first_key(keys)?, MotionPolicy::None)
+            }
+            // Global child is present on every node, so the equal rows of
+            // the other child are always found locally.
+            (
+                Distribution::Global | Distribution::Segment { .. } | Distribution::Single,
+                Distribution::Global,
+            )
+            | (Distribution::Global, Distribution::Segment { .. } | Distribution::Single) => {
+                (MotionPolicy::None, MotionPolicy::None)
+            }
+            (Distribution::Any, Distribution::Global) => (by_first_column(), MotionPolicy::None),
+            (Distribution::Global, Distribution::Any) => (MotionPolicy::None, by_first_column()),
+            (
+                Distribution::Any | Distribution::Single,
+                Distribution::Any | Distribution::Single,
+            ) => (by_first_column(), by_first_column()),
+        };
+
+        map.add_child(left_id, left_motion, Program::default());
+        map.add_child(right_id, right_motion, Program::default());
+
+        Ok(map)
+    }
+
     /// Resolves the case when left child has distribution Global
     /// and right child has Any or Segment distribution.
     /// If distributions are different returns `false`, otherwise modifies the
@@ -2402,6 +2529,23 @@ impl Plan {
         // Map of { old relational child -> new relational child }
         // used to fix Union nodes.
         let mut old_new: AHashMap<NodeId, NodeId> = AHashMap::new();
+        // Unions of recursive CTEs are evaluated by the local SQL engine
+        // (including duplicates removal), so they are not wrapped with motions.
+        let mut recursive_unions: AHashSet<NodeId> = AHashSet::new();
+        for LevelNode(_, id) in &nodes {
+            if let Relational::ScanCte(ScanCte {
+                child,
+                recursive:
+                    Some(RecursiveCte {
+                        is_self_reference: false,
+                        ..
+                    }),
+                ..
+            }) = self.get_relation_node(*id)?
+            {
+                recursive_unions.insert(self.get_relational_child(*child, 0)?);
+            }
+        }
 
         for LevelNode(_, id) in nodes {
             if visited.contains(&id) {
@@ -2434,7 +2578,12 @@ impl Plan {
                     // i.e. to the plan without any motion nodes.
                     panic!("IR mustn't contain Motion nodes at the stage of redistribution.")
                 }
-                RelOwned::Limit(Limit { output, limit, .. }) => {
+                RelOwned::Limit(Limit {
+                    output,
+                    limit,
+                    offset,
+                    ..
+                }) => {
                     let rel_child_id = self.get_relational_child(id, 0)?;
                     let child_dist =
                         self.get_distribution(self.get_relational_output(rel_child_id)?)?;
@@ -2448,24 +2597,47 @@ impl Plan {
                             // Rows are distributed, so motion needed with full policy to
                             // bring them on a single node.
                             let child_dist = child_dist.clone();
-                            // We don't need more than limit rows, so we can add a limit for the
-                            // queries sent during the map stage.
-                            let limit_id = self.add_limit(id, limit)?;
-                            self.set_dist(
-                                self.get_relational_output(limit_id)?,
-                                Distribution::Single,
-                            )?;
-                            old_new.insert(id, limit_id);
-                            let mut strategy = Strategy::new(limit_id);
-                            strategy.add_child(id, MotionPolicy::Full, Program::default());
-                            self.create_motion_nodes(strategy)?;
-                            self.set_dist(output, child_dist)?;
+                            if let Some(limit) = limit {
+                                // We don't need more than limit + offset rows, so we can add
+                                // a limit for the queries sent during the map stage. The offset
+                                // is applied only on the router, as we don't know how many rows
+                                // should be skipped on each storage.
+                                if let MutRelational::Limit(Limit {
+                                    limit: map_limit,
+                                    offset: map_offset,
+                                    ..
+                                }) = self.get_mut_relation_node(id)?
+                                {
+                                    *map_limit = Some(limit.saturating_add(offset));
+                                    *map_offset = 0;
+                                }
+                                let limit_id = self.add_limit(id, Some(limit), offset)?;
+                                self.set_dist(
+                                    self.get_relational_output(limit_id)?,
+                                    Distribution::Single,
+                                )?;
+                                old_new.insert(id, limit_id);
+                                let mut strategy = Strategy::new(limit_id);
+                                strategy.add_child(id, MotionPolicy::Full, Program::default());
+                                self.create_motion_nodes(strategy)?;
+                                self.set_dist(output, child_dist)?;
+                            } else {
+                                // Without a limit we need all the rows from the storages,
+                                // so the offset is simply applied on the router.
+                                let mut strategy = Strategy::new(id);
+                                strategy.add_child(
+                                    rel_child_id,
+                                    MotionPolicy::Full,
+                                    Program::default(),
+                                );
+                                self.create_motion_nodes(strategy)?;
+                                self.set_dist(output, Distribution::Single)?;
+                            }
                         }
                     }
                 }
                 RelOwned::ScanRelation(ScanRelation { output, .. })
                 | RelOwned::ScanSubQuery(ScanSubQuery { output, .. })
-                | RelOwned::Intersect(Intersect { output, .. })
                 | RelOwned::Having(Having { output, .. }) => {
                     // Note: For `Having` true distribution is calculated
                     //       at the end of `add_two_stage_aggregation` function
@@ -2554,6 +2726,7 @@ impl Plan {
                         } else {
                             self.set_dist(output, child_dist.clone())?;
                         }
+                        self.lower_composite_aggregates(id)?;
                         // Nothing else to do here.
                         continue;
                     }
@@ -2585,6 +2758,7 @@ impl Plan {
                             let rel = self.get_relation_node(node_id)?;
                             self.set_dist(rel.output(), Distribution::Single)?;
                         }
+                        self.lower_composite_aggregates(id)?;
                         // Nothing else to do here.
                         continue;
                     }
@@ -2593,6 +2767,7 @@ impl Plan {
                         // We have successfully added two stage aggregation.
                         continue;
                     }
+                    self.lower_composite_aggregates(id)?;
 
                     if !windows.is_empty() {
                         // If we have window functions without aggregates, lets deal with them.
@@ -2642,22 +2817,50 @@ impl Plan {
                     self.create_motion_nodes(strategy)?;
                     self.set_distribution(output)?;
                 }
+                RelOwned::Intersect(Intersect { output, .. }) => {
+                    let strategy = self.resolve_intersect_conflicts(id)?;
+                    self.create_motion_nodes(strategy)?;
+                    self.set_distribution(output)?;
+                }
                 RelOwned::Union(Union { output, .. }) => {
                     let strategy = self.resolve_union_conflicts(id)?;
                     self.create_motion_nodes(strategy)?;
                     self.set_distribution(output)?;
-                    let new_top_id = self.add_motion(
-                        id,
-                        &MotionPolicy::Full,
-                        Program::new(vec![MotionOpcode::RemoveDuplicates]),
-                    )?;
-                    old_new.insert(id, new_top_id);
+                    // The union of a recursive CTE removes duplicates on every
+                    // iteration of the local SQL engine, a motion can't be
+                    // executed between the iterations.
+                    if !recursive_unions.contains(&id) {
+                        let new_top_id = self.add_motion(
+                            id,
+                            &MotionPolicy::Full,
+                            Program::new(vec![MotionOpcode::RemoveDuplicates]),
+                        )?;
+                        old_new.insert(id, new_top_id);
+                    }
                 }
                 RelOwned::UnionAll(UnionAll { output, .. }) => {
                     let strategy = self.resolve_union_conflicts(id)?;
                     self.create_motion_nodes(strategy)?;
                     self.set_distribution(output)?;
                 }
+                RelOwned::ScanCte(ScanCte {
+                    output,
+                    child,
+                    recursive: Some(_),
+                    ..
+                }) => {
+                    // Recursive CTE is executed by the local SQL engine as a whole
+                    // (iterations can't be materialized between the instances),
+                    // so all its data must be available on any node.
+                    let child_dist = self.get_distribution(self.get_relational_output(child)?)?;
+                    if !matches!(child_dist, Distribution::Global) {
+                        return Err(SbroadError::Unsupported(
+                            Entity::Cte,
+                            Some("recursive CTE is supported only over global tables".into()),
+                        ));
+                    }
+                    self.set_dist(output, Distribution::Global)?;
+                }
                 RelOwned::ScanCte(ScanCte { output, child, .. }) => {
                     // Possible, current CTE subtree has already been resolved and we
                     // can just copy the corresponding motion node.
@@ -2698,6 +2901,19 @@ impl Plan {
             self.set_top(*old_new.values().next().unwrap())?;
         }
 
+        // Set-returning functions return arrays on the storages,
+        // they are expanded into rows by the top motion.
+        let top_id = self.get_top()?;
+        let srf_positions = self.set_returning_columns(top_id)?;
+        if !srf_positions.is_empty() {
+            let motion_id = self.add_motion(
+                top_id,
+                &MotionPolicy::Full,
+                Program::new(vec![MotionOpcode::UnnestArrays(srf_positions)]),
+            )?;
+            self.set_top(motion_id)?;
+        }
+
         let top_id = self.get_top()?;
         let slices = self.calculate_slices(top_id)?;
         self.set_slices(slices);
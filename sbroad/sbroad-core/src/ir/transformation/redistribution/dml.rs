@@ -48,6 +48,79 @@ impl Plan {
         ))
     }
 
+    /// Return the local `Update` of the `ON CONFLICT DO UPDATE` clause
+    /// for given insert node (if any).
+    ///
+    /// # Errors
+    /// - node is not an `Insert`
+    pub fn insert_conflict_update(&self, insert_id: NodeId) -> Result<Option<NodeId>, SbroadError> {
+        let insert = self.get_relation_node(insert_id)?;
+        if let Relational::Insert(Insert {
+            conflict_update, ..
+        }) = insert
+        {
+            return Ok(*conflict_update);
+        }
+        Err(SbroadError::Invalid(
+            Entity::Node,
+            Some(format_smolstr!(
+                "INSERT with id {insert_id:?} (conflict update))"
+            )),
+        ))
+    }
+
+    /// Set the local `Update` of the `ON CONFLICT DO UPDATE` clause
+    /// for given insert node.
+    ///
+    /// # Errors
+    /// - node is not an `Insert`
+    pub fn set_insert_conflict_update(
+        &mut self,
+        insert_id: NodeId,
+        update_id: NodeId,
+    ) -> Result<(), SbroadError> {
+        let insert = self.get_mut_relation_node(insert_id)?;
+        if let MutRelational::Insert(Insert {
+            conflict_update, ..
+        }) = insert
+        {
+            *conflict_update = Some(update_id);
+            return Ok(());
+        }
+        Err(SbroadError::Invalid(
+            Entity::Node,
+            Some(format_smolstr!(
+                "INSERT with id {insert_id:?} (conflict update))"
+            )),
+        ))
+    }
+
+    /// Return the motion with the tuples rejected by insertion (`excluded`)
+    /// in the subtree of the `ON CONFLICT DO UPDATE` local `Update`.
+    ///
+    /// # Errors
+    /// - conflict update subtree is invalid
+    pub fn conflict_excluded_motion(&self, update_id: NodeId) -> Result<NodeId, SbroadError> {
+        // Update -> Motion -> Projection -> [Selection ->] Join (table, excluded)
+        let mut node_id = self.dml_child_id(update_id)?;
+        loop {
+            match self.get_relation_node(node_id)? {
+                Relational::Join(_) => return self.get_relational_child(node_id, 1),
+                Relational::Motion(_) | Relational::Projection(_) | Relational::Selection(_) => {
+                    node_id = self.get_relational_child(node_id, 0)?;
+                }
+                node => {
+                    return Err(SbroadError::Invalid(
+                        Entity::Node,
+                        Some(format_smolstr!(
+                            "unexpected node in conflict update subtree: {node:?}"
+                        )),
+                    ))
+                }
+            }
+        }
+    }
+
     pub(crate) fn insert_motion_key(&self, insert_id: NodeId) -> Result<MotionKey, SbroadError> {
         let columns = self.insert_columns(insert_id)?;
         // Revert map of { pos_in_child_node -> pos_in_relation }
@@ -128,6 +201,47 @@ impl Plan {
         panic!("Expected DML node to get table from. Got {node:?}.")
     }
 
+    /// Return the `RETURNING` projection of the DML node (if any).
+    ///
+    /// # Errors
+    /// - node is not a DML node
+    pub fn dml_returning(&self, node_id: NodeId) -> Result<Option<NodeId>, SbroadError> {
+        let node = self.get_relation_node(node_id)?;
+        if let Relational::Insert(Insert { returning, .. })
+        | Relational::Update(Update { returning, .. })
+        | Relational::Delete(Delete { returning, .. }) = node
+        {
+            return Ok(*returning);
+        }
+        Err(SbroadError::Invalid(
+            Entity::Node,
+            Some(format_smolstr!("expected DML node, got: {node:?}")),
+        ))
+    }
+
+    /// Set the `RETURNING` projection of the DML node.
+    ///
+    /// # Errors
+    /// - node is not a DML node
+    pub fn set_dml_returning(
+        &mut self,
+        node_id: NodeId,
+        proj_id: NodeId,
+    ) -> Result<(), SbroadError> {
+        let node = self.get_mut_relation_node(node_id)?;
+        if let MutRelational::Insert(Insert { returning, .. })
+        | MutRelational::Update(Update { returning, .. })
+        | MutRelational::Delete(Delete { returning, .. }) = node
+        {
+            *returning = Some(proj_id);
+            return Ok(());
+        }
+        Err(SbroadError::Invalid(
+            Entity::Node,
+            Some(format_smolstr!("expected DML node, got: {node:?}")),
+        ))
+    }
+
     /// Set the length of tuple to delete for
     /// sharded `Update`.
     ///
@@ -0,0 +1,110 @@
+//! Full Join transformation logic.
+//!
+//! Local SQL engine doesn't support full joins, so they are executed
+//! as left ones: the inner child is materialized and broadcasted to
+//! the nodes with the outer child, while the inner rows without a match
+//! are added to the join result on the reduce stage.
+//!
+//! The inner child is extended with a non-null marker column, so the
+//! NULL padding of the outer rows without a match is never confused
+//! with the inner rows consisting of NULLs.
+
+use smol_str::{format_smolstr, SmolStr};
+
+use crate::{
+    errors::{Entity, SbroadError},
+    ir::{
+        distribution::Distribution,
+        node::NodeId,
+        relation::{DerivedType, Type},
+        value::Value,
+        Plan,
+    },
+};
+
+use super::{
+    left_join::{create_projection, materialize_join_child},
+    MotionOpcode, MotionPolicy, Program, Strategy,
+};
+
+impl Plan {
+    pub(super) fn calculate_strategy_for_full_join(
+        &mut self,
+        join_id: NodeId,
+    ) -> Result<Strategy, SbroadError> {
+        let Some(parent_id) = self.find_parent_rel(join_id)? else {
+            return Err(SbroadError::Invalid(
+                Entity::Plan,
+                Some(format_smolstr!("join ({join_id:?}) has no parent!")),
+            ));
+        };
+
+        // Sub-queries from the join condition are broadcasted
+        // to the nodes where the outer child resides.
+        let mut sq_strategy = Strategy::new(join_id);
+        for sq_id in &self.get_relational_children(join_id)?[2..] {
+            if !matches!(self.get_rel_distribution(*sq_id)?, Distribution::Global) {
+                sq_strategy.add_child(*sq_id, MotionPolicy::Full, Program::default());
+            }
+        }
+        self.create_motion_nodes(sq_strategy)?;
+
+        // Inner child must be materialized in any case: it is both
+        // broadcasted for the join and used to find the missing rows.
+        let inner_id = self.get_relational_child(join_id, 1)?;
+        let inner_child_motion_id = materialize_join_child(self, join_id, inner_id)?;
+        add_inner_marker(self, join_id)?;
+        self.set_distribution(self.get_relational_output(join_id)?)?;
+
+        let projection_id = create_projection(self, join_id)?;
+        self.set_distribution(self.get_relational_output(projection_id)?)?;
+        self.change_child(parent_id, join_id, projection_id)?;
+
+        // Add motion which will do the reduce stage of joining:
+        // adding missing inner rows.
+        let motion_op = MotionOpcode::AddMissingRowsForFullJoin {
+            motion_id: inner_child_motion_id,
+        };
+        let mut strategy = Strategy::new(parent_id);
+        strategy.add_child(projection_id, MotionPolicy::Full, Program(vec![motion_op]));
+
+        Ok(strategy)
+    }
+}
+
+/// Name of the column marking the rows of the full join inner child.
+const INNER_MARKER: &str = "full_join_marker";
+
+/// Append a `true` marker column to the inner child of the full join and
+/// to the join output. The marker is the last column of the join result:
+/// it is NULL only in the padding of the outer rows without a match.
+fn add_inner_marker(plan: &mut Plan, join_id: NodeId) -> Result<(), SbroadError> {
+    let inner_id = plan.get_relational_child(join_id, 1)?;
+    let inner_len = plan
+        .get_row_list(plan.get_relational_output(inner_id)?)?
+        .len();
+    let alias = plan.scan_name(inner_id, 0)?.map(SmolStr::from);
+
+    let proj_id = plan.add_proj(inner_id, vec![], &[], false, true)?;
+    let proj_output_id = plan.get_relational_output(proj_id)?;
+    let marker_id = plan.add_const(Value::Boolean(true));
+    let marker_alias_id = plan.nodes.add_alias(INNER_MARKER, marker_id)?;
+    plan.get_mut_row_list(proj_output_id)?.push(marker_alias_id);
+    plan.set_distribution(proj_output_id)?;
+
+    let sq_id = plan.add_sub_query(proj_id, alias.as_deref())?;
+    plan.set_distribution(plan.get_relational_output(sq_id)?)?;
+    plan.change_child(join_id, inner_id, sq_id)?;
+
+    let ref_id = plan.nodes.add_ref(
+        Some(join_id),
+        Some(vec![1]),
+        inner_len,
+        DerivedType::new(Type::Boolean),
+        None,
+    );
+    let ref_alias_id = plan.nodes.add_alias(INNER_MARKER, ref_id)?;
+    let join_output_id = plan.get_relational_output(join_id)?;
+    plan.get_mut_row_list(join_output_id)?.push(ref_alias_id);
+    Ok(())
+}
@@ -1119,6 +1119,38 @@ impl Plan {
         Ok(())
     }
 
+    /// Gather all the rows under the final nodes (or under the GroupBy node,
+    /// if it is present) with a Full motion and compute the aggregates on a
+    /// single node.
+    fn add_motion_for_holistic_aggregates(
+        &mut self,
+        final_proj_id: NodeId,
+        finals: &[NodeId],
+        upper_id: NodeId,
+    ) -> Result<(), SbroadError> {
+        let (parent_id, child_id) =
+            if let Relational::GroupBy(_) = self.get_relation_node(upper_id)? {
+                (upper_id, self.get_relational_child(upper_id, 0)?)
+            } else {
+                let last_final_id = *finals.last().expect("finals should not be empty");
+                (last_final_id, upper_id)
+            };
+        let mut strategy = Strategy::new(parent_id);
+        strategy.add_child(child_id, MotionPolicy::Full, Program::default());
+        self.add_proj_for_strategy(&mut strategy, parent_id, child_id)?;
+        self.create_motion_nodes(strategy)?;
+
+        if parent_id == upper_id {
+            self.set_dist(self.get_relational_output(upper_id)?, Distribution::Single)?;
+        }
+        for node_id in finals {
+            self.set_dist(self.get_relational_output(*node_id)?, Distribution::Single)?;
+        }
+
+        self.lower_composite_aggregates(final_proj_id)?;
+        self.fix_subqueries_under_having(finals)
+    }
+
     /// Create Motion nodes for scalar subqueries present under Having node.
     fn fix_subqueries_under_having(&mut self, finals: &[NodeId]) -> Result<(), SbroadError> {
         let mut having_id: Option<NodeId> = None;
@@ -1202,6 +1234,15 @@ impl Plan {
             self.check_refs_out_of_aggregates(&finals)?;
         }
 
+        if aggrs.iter().any(|a| a.kind.is_holistic()) {
+            // Holistic aggregates (e.g. `percentile_cont`) can't be split into
+            // local and final stages, so all the rows are gathered on the router.
+            //
+            // Example: `select percentile_cont(0.5) within group (order by a) from t`.
+            self.add_motion_for_holistic_aggregates(final_proj_id, &finals, upper_id)?;
+            return Ok(true);
+        }
+
         let distinct_aggrs_are_present = aggrs.iter().any(|a| a.is_distinct);
         if groupby_info.is_none() && distinct_aggrs_are_present {
             // GroupBy doesn't exist and we have to create it just for
@@ -0,0 +1,61 @@
+//! Inner join transformation logic.
+//!
+//! When the children of the join are not co-located, the inner child is
+//! broadcasted to the outer one. The children of the inner join are
+//! interchangeable, so if the table statistics show that the outer child
+//! is smaller, it is cheaper to broadcast it instead.
+
+use crate::{
+    errors::SbroadError,
+    ir::{distribution::Distribution, node::NodeId, Plan},
+};
+
+use super::{MotionPolicy, Program, Strategy};
+
+impl Plan {
+    /// Fix the strategy computed for the inner child of the inner join.
+    ///
+    /// When the inner child is going to be broadcasted and the outer child
+    /// is estimated to produce fewer rows, the outer child is broadcasted instead.
+    ///
+    /// Returns `true` if the strategy was changed.
+    pub(super) fn fix_strategy_for_inner_join(
+        &self,
+        join_id: NodeId,
+        inner_policy: &MotionPolicy,
+        strategy: &mut Strategy,
+    ) -> Result<bool, SbroadError> {
+        if !matches!(inner_policy, MotionPolicy::Full) {
+            return Ok(false);
+        }
+        // Sub-queries may be co-located with the outer child,
+        // so the outer child can't be moved.
+        if self.get_relational_children(join_id)?.len() > 2 {
+            return Ok(false);
+        }
+        let outer_id = self.get_relational_child(join_id, 0)?;
+        let inner_id = self.get_relational_child(join_id, 1)?;
+        if matches!(
+            (
+                self.get_rel_distribution(outer_id)?,
+                self.get_rel_distribution(inner_id)?,
+            ),
+            (Distribution::Global, _) | (_, Distribution::Global)
+        ) {
+            return Ok(false);
+        }
+
+        let (Some(outer_rows), Some(inner_rows)) =
+            (self.estimate_rows(outer_id)?, self.estimate_rows(inner_id)?)
+        else {
+            return Ok(false);
+        };
+        if outer_rows >= inner_rows {
+            return Ok(false);
+        }
+
+        strategy.add_child(outer_id, MotionPolicy::Full, Program::default());
+        strategy.add_child(inner_id, MotionPolicy::None, Program::default());
+        Ok(true)
+    }
+}
@@ -55,27 +55,7 @@ impl Plan {
         // In case there are no motions under outer child,
         // we need to add one, because we need to materialize
         // the subtree from which missing rows will be added.
-        let outer_child_motion_id = {
-            let child = self.get_relation_node(outer_id)?;
-            let mut motion_child_id = None;
-            // Check if there is already motion under outer child
-            if child.is_subquery_or_cte() {
-                let sq_child = self.get_relational_child(outer_id, 0)?;
-                if self.get_relation_node(sq_child)?.is_motion() {
-                    motion_child_id = Some(sq_child);
-                }
-            } else if child.is_motion() {
-                motion_child_id = Some(outer_id);
-            }
-
-            if motion_child_id.is_none() {
-                let motion_id =
-                    self.add_motion(outer_id, &MotionPolicy::Full, Program::default())?;
-                self.change_child(join_id, outer_id, motion_id)?;
-                motion_child_id = Some(motion_id);
-            }
-            motion_child_id.unwrap()
-        };
+        let outer_child_motion_id = materialize_join_child(self, join_id, outer_id)?;
 
         // Add motion which will do the reduce stage of joining:
         // adding missing rows.
@@ -90,7 +70,30 @@ impl Plan {
     }
 }
 
-fn create_projection(plan: &mut Plan, join_id: NodeId) -> Result<NodeId, SbroadError> {
+/// Returns the motion materializing the join child. If there is
+/// no such motion yet, a full one is added between the join and the child.
+pub(super) fn materialize_join_child(
+    plan: &mut Plan,
+    join_id: NodeId,
+    child_id: NodeId,
+) -> Result<NodeId, SbroadError> {
+    let child = plan.get_relation_node(child_id)?;
+    // Check if there is already motion under the child.
+    if child.is_subquery_or_cte() {
+        let sq_child = plan.get_relational_child(child_id, 0)?;
+        if plan.get_relation_node(sq_child)?.is_motion() {
+            return Ok(sq_child);
+        }
+    } else if child.is_motion() {
+        return Ok(child_id);
+    }
+
+    let motion_id = plan.add_motion(child_id, &MotionPolicy::Full, Program::default())?;
+    plan.change_child(join_id, child_id, motion_id)?;
+    Ok(motion_id)
+}
+
+pub(super) fn create_projection(plan: &mut Plan, join_id: NodeId) -> Result<NodeId, SbroadError> {
     let proj_id = plan.add_proj(join_id, vec![], &[], false, false)?;
     let output_id = plan.get_relational_output(proj_id)?;
     plan.replace_parent_in_subtree(output_id, Some(join_id), Some(proj_id))?;
@@ -0,0 +1,52 @@
+//! Right Join transformation logic.
+//!
+//! Right join preserves the rows of the inner child, so unlike inner
+//! and left joins the inner child can't be broadcasted to the outer one:
+//! every node would produce its own copy of the inner rows without a match.
+
+use crate::{
+    errors::SbroadError,
+    ir::{distribution::Distribution, node::NodeId, Plan},
+};
+
+use super::{MotionPolicy, Program, Strategy};
+
+impl Plan {
+    /// Fix the strategy computed for the inner child of the right join.
+    ///
+    /// When the children are not co-located, the outer child is broadcasted
+    /// instead of the inner one (and so are the sub-queries).
+    ///
+    /// Returns `true` if the strategy was changed.
+    pub(super) fn fix_strategy_for_right_join(
+        &self,
+        join_id: NodeId,
+        inner_policy: &MotionPolicy,
+        strategy: &mut Strategy,
+    ) -> Result<bool, SbroadError> {
+        let children = self.get_relational_children(join_id)?;
+        let outer_id = self.get_relational_child(join_id, 0)?;
+        let inner_id = self.get_relational_child(join_id, 1)?;
+
+        let is_co_located = match (
+            self.get_rel_distribution(outer_id)?,
+            self.get_rel_distribution(inner_id)?,
+        ) {
+            (Distribution::Global, _) => true,
+            (_, Distribution::Global) => false,
+            _ => matches!(inner_policy, MotionPolicy::None | MotionPolicy::Segment(_)),
+        };
+        if is_co_located {
+            return Ok(false);
+        }
+
+        strategy.add_child(outer_id, MotionPolicy::Full, Program::default());
+        strategy.add_child(inner_id, MotionPolicy::None, Program::default());
+        for sq_id in &children[2..] {
+            if !matches!(self.get_rel_distribution(*sq_id)?, Distribution::Global) {
+                strategy.add_child(*sq_id, MotionPolicy::Full, Program::default());
+            }
+        }
+        Ok(true)
+    }
+}
@@ -351,5 +351,7 @@ fn test_slices_3() {
 
 mod between;
 mod except;
+mod inner_join;
+mod intersect;
 mod not_in;
 mod segment;
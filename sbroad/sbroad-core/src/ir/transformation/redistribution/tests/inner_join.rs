@@ -0,0 +1,74 @@
+use crate::executor::engine::mock::RouterRuntimeMock;
+use crate::ir::transformation::helpers::sql_to_ir;
+use crate::ir::transformation::redistribution::MotionPolicy;
+use crate::ir::Plan;
+use pretty_assertions::assert_eq;
+use smol_str::SmolStr;
+
+use super::{Motion, Relational};
+
+const QUERY: &str = r#"SELECT * FROM "hash_testing" INNER JOIN "test_space" ON true"#;
+
+fn plan_with_table_rows(rows: &[(&str, u64)]) -> Plan {
+    let mut plan = sql_to_ir(QUERY, vec![]);
+    for (table, rows_number) in rows {
+        plan.table_rows.insert(SmolStr::from(*table), *rows_number);
+    }
+    plan.add_motions().unwrap();
+    plan
+}
+
+fn join_child_policy(plan: &Plan, position: usize) -> Option<MotionPolicy> {
+    let top_id = plan.get_top().unwrap();
+    let join_id = plan.get_relational_child(top_id, 0).unwrap();
+    let child_id = plan.get_relational_child(join_id, position).unwrap();
+    if let Relational::Motion(Motion { policy, .. }) = plan.get_relation_node(child_id).unwrap() {
+        Some(policy.clone())
+    } else {
+        None
+    }
+}
+
+#[test]
+fn inner_join_without_statistics() {
+    let plan = plan_with_table_rows(&[("hash_testing", 100)]);
+    assert_eq!(join_child_policy(&plan, 0), None);
+    assert_eq!(join_child_policy(&plan, 1), Some(MotionPolicy::Full));
+}
+
+#[test]
+fn inner_join_moves_smaller_outer_child() {
+    let plan = plan_with_table_rows(&[("hash_testing", 100), ("test_space", 1000)]);
+    assert_eq!(join_child_policy(&plan, 0), Some(MotionPolicy::Full));
+    assert_eq!(join_child_policy(&plan, 1), None);
+}
+
+#[test]
+fn inner_join_keeps_smaller_inner_child() {
+    let plan = plan_with_table_rows(&[("hash_testing", 1000), ("test_space", 1000)]);
+    assert_eq!(join_child_policy(&plan, 0), None);
+    assert_eq!(join_child_policy(&plan, 1), Some(MotionPolicy::Full));
+}
+
+#[test]
+fn inner_join_uses_filter_selectivity() {
+    // Statistics: "hash_testing" has 10000 rows, "test_space" has 25000 rows
+    // and 2% of them have "id" = 4 (the most common value).
+    let query = r#"SELECT * FROM "hash_testing"
+        INNER JOIN (SELECT "id" FROM "test_space" WHERE "id" = 4) AS t ON true"#;
+    let mut plan = sql_to_ir(query, vec![]);
+    plan.set_statistics(&RouterRuntimeMock::new()).unwrap();
+    plan.add_motions().unwrap();
+    assert_eq!(join_child_policy(&plan, 0), None);
+    assert_eq!(join_child_policy(&plan, 1), Some(MotionPolicy::Full));
+}
+
+#[test]
+fn inner_join_rows_estimation() {
+    let query = r#"SELECT * FROM "hash_testing" INNER JOIN "test_space" ON true"#;
+    let mut plan = sql_to_ir(query, vec![]);
+    plan.set_statistics(&RouterRuntimeMock::new()).unwrap();
+    let top_id = plan.get_top().unwrap();
+    let join_id = plan.get_relational_child(top_id, 0).unwrap();
+    assert_eq!(plan.estimate_rows(join_id).unwrap(), Some(10000 * 25000));
+}
@@ -0,0 +1,152 @@
+use crate::ir::node::{Projection, ScanSubQuery, UnionAll};
+use crate::ir::transformation::helpers::sql_to_ir;
+use crate::ir::transformation::redistribution::tests::get_motion_id;
+use crate::ir::transformation::redistribution::{Key, MotionKey, MotionPolicy, Target};
+use crate::ir::Slices;
+use pretty_assertions::assert_eq;
+
+use super::{Motion, Relational};
+
+#[test]
+fn intersect1() {
+    let query = r#"SELECT "identification_number", "product_code" FROM "hash_testing"
+        INTERSECT
+        SELECT "identification_number" as "id", "product_code" as "pc" FROM "hash_testing_hist""#;
+
+    let mut plan = sql_to_ir(query, vec![]);
+    plan.add_motions().unwrap();
+    assert_eq!(Slices::empty(), plan.slices);
+}
+
+#[test]
+fn intersect2() {
+    let query = r#"SELECT "identification_number", "product_code" FROM "hash_testing"
+        INTERSECT
+        SELECT 1, 2 FROM "hash_testing_hist""#;
+
+    let mut plan = sql_to_ir(query, vec![]);
+    plan.add_motions().unwrap();
+    let motion_id = *get_motion_id(&plan, 0, 0).unwrap();
+    let motion = plan.get_relation_node(motion_id).unwrap();
+    if let Relational::Motion(Motion { policy, .. }) = motion {
+        assert_eq!(
+            *policy,
+            MotionPolicy::Segment(
+                (Key {
+                    positions: vec![0, 1]
+                })
+                .into()
+            )
+        );
+    } else {
+        panic!("Expected a motion node");
+    }
+    let no_other_motions = get_motion_id(&plan, 0, 1).is_none();
+    assert_eq!(no_other_motions, true);
+}
+
+#[test]
+fn intersect3() {
+    let query = r#"SELECT 1, 2 FROM "hash_testing"
+        INTERSECT
+        SELECT 1, 2 FROM "hash_testing_hist""#;
+
+    // Both children have unknown distribution, so they are
+    // segmented by the first column.
+    let mut plan = sql_to_ir(query, vec![]);
+    plan.add_motions().unwrap();
+    for idx in 0..2 {
+        let motion_id = *get_motion_id(&plan, 0, idx).unwrap();
+        let motion = plan.get_relation_node(motion_id).unwrap();
+        if let Relational::Motion(Motion { policy, .. }) = motion {
+            assert_eq!(
+                *policy,
+                MotionPolicy::Segment(MotionKey {
+                    targets: vec![Target::Reference(0)]
+                })
+            );
+        } else {
+            panic!("Expected a motion node");
+        }
+    }
+}
+
+#[test]
+fn intersect_all1() {
+    let query = r#"SELECT "identification_number", "product_code" FROM "hash_testing"
+        INTERSECT ALL
+        SELECT "identification_number", "product_code" FROM "hash_testing_hist""#;
+
+    // Duplicates are numbered within the partitions by all the columns,
+    // that already contain the sharding key, so no motions are needed.
+    let mut plan = sql_to_ir(query, vec![]);
+    plan.add_motions().unwrap();
+    assert_eq!(Slices::empty(), plan.slices);
+
+    // Duplicates number column is removed from the result.
+    let top_id = plan.get_top().unwrap();
+    let Relational::Projection(Projection { output, .. }) = plan.get_relation_node(top_id).unwrap()
+    else {
+        panic!("Expected a projection node");
+    };
+    assert_eq!(plan.get_row_list(*output).unwrap().len(), 2);
+}
+
+#[test]
+fn except_all1() {
+    let query = r#"SELECT "identification_number", "product_code" FROM "hash_testing"
+        EXCEPT ALL
+        SELECT 1, 2 FROM "hash_testing_hist""#;
+
+    // Right child is segmented by all the columns to number the duplicates.
+    let mut plan = sql_to_ir(query, vec![]);
+    plan.add_motions().unwrap();
+    let motion_id = *get_motion_id(&plan, 0, 0).unwrap();
+    let motion = plan.get_relation_node(motion_id).unwrap();
+    if let Relational::Motion(Motion { policy, .. }) = motion {
+        assert_eq!(
+            *policy,
+            MotionPolicy::Segment(
+                (Key {
+                    positions: vec![0, 1]
+                })
+                .into()
+            )
+        );
+    } else {
+        panic!("Expected a motion node");
+    }
+    let no_other_motions = get_motion_id(&plan, 0, 1).is_none();
+    assert_eq!(no_other_motions, true);
+}
+
+#[test]
+fn intersect_precedence() {
+    let query = r#"SELECT "identification_number" FROM "hash_testing"
+        UNION ALL
+        SELECT "identification_number" FROM "hash_testing_hist"
+        INTERSECT
+        SELECT "identification_number" FROM "hash_testing""#;
+
+    // Intersect binds tighter than union, so it is wrapped into a subquery.
+    let plan = sql_to_ir(query, vec![]);
+    let top_id = plan.get_top().unwrap();
+    let Relational::UnionAll(UnionAll { right, .. }) = plan.get_relation_node(top_id).unwrap()
+    else {
+        panic!("Expected a union all node");
+    };
+    let Relational::Projection(Projection { children, .. }) =
+        plan.get_relation_node(*right).unwrap()
+    else {
+        panic!("Expected a projection node");
+    };
+    let Relational::ScanSubQuery(ScanSubQuery { child, .. }) =
+        plan.get_relation_node(children[0]).unwrap()
+    else {
+        panic!("Expected a subquery node");
+    };
+    assert!(matches!(
+        plan.get_relation_node(*child).unwrap(),
+        Relational::Intersect(_)
+    ));
+}
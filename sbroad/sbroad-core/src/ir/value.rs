@@ -24,6 +24,8 @@ use crate::errors::{Entity, SbroadError};
 use crate::executor::hash::ToHashString;
 use crate::ir::relation::DerivedType;
 use crate::ir::value::double::Double;
+use crate::ir::value::interval::{Interval, MP_INTERVAL};
+use crate::ir::value::varbinary::Varbinary;
 
 use super::relation::Type;
 
@@ -146,6 +148,10 @@ pub enum Value {
     Tuple(Tuple),
     /// Uuid type
     Uuid(Uuid),
+    /// Binary string type.
+    Varbinary(Varbinary),
+    /// Interval type.
+    Interval(Interval),
 }
 
 impl<'de> Decode<'de> for Value {
@@ -196,6 +202,13 @@ impl<'de> Decode<'de> for Value {
             | Marker::Ext8
             | Marker::Ext16
             | Marker::Ext32 => {
+                // Interval is not supported by `ExtStruct`, so decode it separately.
+                let mut peek = *r;
+                if let Ok(meta) = rmp::decode::read_ext_meta(&mut peek) {
+                    if meta.typeid == MP_INTERVAL {
+                        return Ok(Value::Interval(Interval::decode(r, context)?));
+                    }
+                }
                 let ext: ExtStruct = Decode::decode(r, context)?;
 
                 match ext.tag {
@@ -213,12 +226,10 @@ impl<'de> Decode<'de> for Value {
                     ))),
                 }
             }
-            Marker::FixMap(_)
-            | Marker::Map16
-            | Marker::Map32
-            | Marker::Bin8
-            | Marker::Bin16
-            | Marker::Bin32 => {
+            Marker::Bin8 | Marker::Bin16 | Marker::Bin32 => {
+                Ok(Value::Varbinary(Varbinary::decode(r, context)?))
+            }
+            Marker::FixMap(_) | Marker::Map16 | Marker::Map32 => {
                 let value = rmpv::decode::read_value(r).map_err(DecodeError::new::<Self>)?;
                 Err(DecodeError::new::<Self>(format_smolstr!(
                     "unexpected value: {value:?}"
@@ -245,6 +256,8 @@ impl Encode for Value {
             Value::Unsigned(v) => v.encode(w, context),
             Value::Tuple(v) => v.encode(w, context),
             Value::Uuid(v) => v.encode(w, context),
+            Value::Varbinary(v) => v.encode(w, context),
+            Value::Interval(v) => v.encode(w, context),
         }
     }
 }
@@ -304,6 +317,8 @@ impl fmt::Display for Value {
             Value::String(v) => write!(f, "'{v}'"),
             Value::Tuple(v) => write!(f, "{v}"),
             Value::Uuid(v) => fmt::Display::fmt(v, f),
+            Value::Varbinary(v) => write!(f, "X'{}'", v.to_hex()),
+            Value::Interval(v) => write!(f, "'{v}'"),
         }
     }
 }
@@ -416,6 +431,18 @@ impl From<Uuid> for Value {
     }
 }
 
+impl From<Varbinary> for Value {
+    fn from(v: Varbinary) -> Self {
+        Value::Varbinary(v)
+    }
+}
+
+impl From<Interval> for Value {
+    fn from(v: Interval) -> Self {
+        Value::Interval(v)
+    }
+}
+
 /// Helper function to extract inner numerical value from `value` and cast it to `Decimal`.
 ///
 /// # Errors
@@ -583,7 +610,9 @@ impl Value {
                 | Value::Double(_)
                 | Value::String(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => Trivalent::False,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
             },
             Value::Null => Trivalent::Unknown,
             Value::Integer(s) => match other {
@@ -591,7 +620,9 @@ impl Value {
                 | Value::String(_)
                 | Value::Uuid(_)
                 | Value::Tuple(_)
-                | Value::Datetime(_) => Trivalent::False,
+                | Value::Datetime(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Integer(o) => (s == o).into(),
                 Value::Decimal(o) => (&Decimal::from(*s) == o).into(),
@@ -606,7 +637,9 @@ impl Value {
                 | Value::String(_)
                 | Value::Tuple(_)
                 | Value::Uuid(_)
-                | Value::Datetime(_) => Trivalent::False,
+                | Value::Datetime(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Integer(o) => (*s == Double::from(*o)).into(),
                 // If double can't be converted to decimal without error then it is not equal to decimal.
@@ -622,7 +655,9 @@ impl Value {
                 | Value::String(_)
                 | Value::Tuple(_)
                 | Value::Uuid(_)
-                | Value::Datetime(_) => Trivalent::False,
+                | Value::Datetime(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Integer(o) => (s == &Decimal::from(*o)).into(),
                 Value::Decimal(o) => (s == o).into(),
@@ -635,7 +670,9 @@ impl Value {
                 | Value::String(_)
                 | Value::Uuid(_)
                 | Value::Tuple(_)
-                | Value::Datetime(_) => Trivalent::False,
+                | Value::Datetime(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Integer(o) => (Decimal::from(*s) == *o).into(),
                 Value::Decimal(o) => (&Decimal::from(*s) == o).into(),
@@ -653,7 +690,9 @@ impl Value {
                 | Value::Double(_)
                 | Value::Unsigned(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => Trivalent::False,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::String(o) => s.eq(o).into(),
             },
@@ -666,7 +705,9 @@ impl Value {
                 | Value::Unsigned(_)
                 | Value::String(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => Trivalent::False,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
             },
             Value::Uuid(s) => match other {
@@ -677,7 +718,9 @@ impl Value {
                 | Value::Double(_)
                 | Value::String(_)
                 | Value::Unsigned(_)
-                | Value::Tuple(_) => Trivalent::False,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Uuid(o) => s.eq(o).into(),
             },
@@ -689,10 +732,40 @@ impl Value {
                 | Value::Double(_)
                 | Value::Unsigned(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => Trivalent::False,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => Trivalent::False,
                 Value::Null => Trivalent::Unknown,
                 Value::Datetime(o) => s.eq(o).into(),
             },
+            Value::Varbinary(s) => match other {
+                Value::Boolean(_)
+                | Value::Integer(_)
+                | Value::Datetime(_)
+                | Value::Decimal(_)
+                | Value::Double(_)
+                | Value::Unsigned(_)
+                | Value::String(_)
+                | Value::Uuid(_)
+                | Value::Tuple(_)
+                | Value::Interval(_) => Trivalent::False,
+                Value::Null => Trivalent::Unknown,
+                Value::Varbinary(o) => s.eq(o).into(),
+            },
+            Value::Interval(s) => match other {
+                Value::Boolean(_)
+                | Value::Integer(_)
+                | Value::Datetime(_)
+                | Value::Decimal(_)
+                | Value::Double(_)
+                | Value::Unsigned(_)
+                | Value::String(_)
+                | Value::Uuid(_)
+                | Value::Tuple(_)
+                | Value::Varbinary(_) => Trivalent::False,
+                Value::Null => Trivalent::Unknown,
+                Value::Interval(o) => s.eq(o).into(),
+            },
         }
     }
 
@@ -708,6 +781,8 @@ impl Value {
             Value::String(_) => FieldType::String,
             Value::Tuple(_) => FieldType::Array,
             Value::Uuid(_) => FieldType::Uuid,
+            Value::Varbinary(_) => FieldType::Varbinary,
+            Value::Interval(_) => FieldType::Interval,
             Value::Null => FieldType::Any,
         };
         KeyDefPart {
@@ -740,7 +815,9 @@ impl Value {
                 | Value::Double(_)
                 | Value::String(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
             },
             Value::Null => TrivalentOrdering::Unknown.into(),
             Value::Integer(s) => match other {
@@ -748,7 +825,9 @@ impl Value {
                 | Value::Datetime(_)
                 | Value::String(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Integer(o) => TrivalentOrdering::from(s.cmp(o)).into(),
                 Value::Decimal(o) => TrivalentOrdering::from(Decimal::from(*s).cmp(o)).into(),
@@ -773,7 +852,9 @@ impl Value {
                 | Value::Unsigned(_)
                 | Value::Uuid(_)
                 | Value::String(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Datetime(o) => TrivalentOrdering::from(s.cmp(o)).into(),
             },
@@ -782,7 +863,9 @@ impl Value {
                 | Value::Datetime(_)
                 | Value::String(_)
                 | Value::Tuple(_)
-                | Value::Uuid(_) => None,
+                | Value::Uuid(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Integer(o) => {
                     if let Some(ord) = s.partial_cmp(&Double::from(*o)) {
@@ -820,7 +903,9 @@ impl Value {
                 | Value::Datetime(_)
                 | Value::String(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Integer(o) => TrivalentOrdering::from(s.cmp(&Decimal::from(*o))).into(),
                 Value::Decimal(o) => TrivalentOrdering::from(s.cmp(o)).into(),
@@ -839,7 +924,9 @@ impl Value {
                 | Value::Datetime(_)
                 | Value::String(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Integer(o) => {
                     TrivalentOrdering::from(Decimal::from(*s).cmp(&Decimal::from(*o))).into()
@@ -863,7 +950,9 @@ impl Value {
                 | Value::Double(_)
                 | Value::Unsigned(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::String(o) => TrivalentOrdering::from(s.cmp(o)).into(),
             },
@@ -875,7 +964,9 @@ impl Value {
                 | Value::Double(_)
                 | Value::Unsigned(_)
                 | Value::String(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
                 Value::Uuid(o) => TrivalentOrdering::from(u.cmp(o)).into(),
             },
@@ -888,8 +979,38 @@ impl Value {
                 | Value::Unsigned(_)
                 | Value::String(_)
                 | Value::Uuid(_)
-                | Value::Tuple(_) => None,
+                | Value::Tuple(_)
+                | Value::Varbinary(_)
+                | Value::Interval(_) => None,
+                Value::Null => TrivalentOrdering::Unknown.into(),
+            },
+            Value::Varbinary(s) => match other {
+                Value::Boolean(_)
+                | Value::Integer(_)
+                | Value::Datetime(_)
+                | Value::Decimal(_)
+                | Value::Double(_)
+                | Value::Unsigned(_)
+                | Value::String(_)
+                | Value::Uuid(_)
+                | Value::Tuple(_)
+                | Value::Interval(_) => None,
                 Value::Null => TrivalentOrdering::Unknown.into(),
+                Value::Varbinary(o) => TrivalentOrdering::from(s.cmp(o)).into(),
+            },
+            Value::Interval(s) => match other {
+                Value::Boolean(_)
+                | Value::Integer(_)
+                | Value::Datetime(_)
+                | Value::Decimal(_)
+                | Value::Double(_)
+                | Value::Unsigned(_)
+                | Value::String(_)
+                | Value::Uuid(_)
+                | Value::Tuple(_)
+                | Value::Varbinary(_) => None,
+                Value::Null => TrivalentOrdering::Unknown.into(),
+                Value::Interval(o) => TrivalentOrdering::from(s.cmp(o)).into(),
             },
         }
     }
@@ -960,6 +1081,22 @@ impl Value {
                 Value::Null => Ok(Value::Null),
                 _ => Err(cast_error(&self, column_type)),
             },
+            Type::Varbinary => match self {
+                Value::Varbinary(_) => Ok(self),
+                Value::String(ref v) => Ok(Value::Varbinary(
+                    Varbinary::from_str(v).map_err(|_| cast_error(&self, column_type))?,
+                )),
+                Value::Null => Ok(Value::Null),
+                _ => Err(cast_error(&self, column_type)),
+            },
+            Type::Interval => match self {
+                Value::Interval(_) => Ok(self),
+                Value::String(ref v) => Ok(Value::Interval(
+                    Interval::from_str(v).map_err(|_| cast_error(&self, column_type))?,
+                )),
+                Value::Null => Ok(Value::Null),
+                _ => Err(cast_error(&self, column_type)),
+            },
             Type::Uuid => match self {
                 Value::Uuid(_) => Ok(self),
                 Value::String(ref v) => Ok(Value::Uuid(
@@ -1010,6 +1147,8 @@ impl Value {
             (Type::String, Value::String(_)) => return Ok(self.into()),
             (Type::Uuid, Value::Uuid(_)) => return Ok(self.into()),
             (Type::Unsigned, Value::Unsigned(_)) => return Ok(self.into()),
+            (Type::Varbinary, Value::Varbinary(_)) => return Ok(self.into()),
+            (Type::Interval, Value::Interval(_)) => return Ok(self.into()),
             _ => (),
         }
 
@@ -1029,6 +1168,8 @@ impl Value {
             Value::String(_) => Type::String,
             Value::Tuple(_) => Type::Array,
             Value::Uuid(_) => Type::Uuid,
+            Value::Varbinary(_) => Type::Varbinary,
+            Value::Interval(_) => Type::Interval,
             Value::Null => return DerivedType::unknown(),
         };
         DerivedType::new(ty)
@@ -1052,6 +1193,8 @@ impl ToHashString for Value {
             Value::String(v) => v.to_string(),
             Value::Tuple(v) => v.to_string(),
             Value::Uuid(v) => v.to_string(),
+            Value::Varbinary(v) => v.to_string(),
+            Value::Interval(v) => v.to_string(),
             Value::Null => "NULL".to_string(),
         }
     }
@@ -1082,6 +1225,8 @@ impl Serialize for EncodedValue<'_> {
             EncodedValue::Owned(Value::Unsigned(v)) => v.serialize(serializer),
             EncodedValue::Owned(Value::Tuple(v)) => v.serialize(serializer),
             EncodedValue::Owned(Value::Uuid(v)) => v.serialize(serializer),
+            EncodedValue::Owned(Value::Varbinary(v)) => v.serialize(serializer),
+            EncodedValue::Owned(Value::Interval(v)) => v.serialize(serializer),
         }
     }
 }
@@ -1128,6 +1273,8 @@ pub enum MsgPackValue<'v> {
     String(&'v String),
     Tuple(&'v Tuple),
     Uuid(&'v Uuid),
+    Varbinary(&'v Varbinary),
+    Interval(&'v Interval),
     Null(()),
 }
 
@@ -1143,6 +1290,8 @@ impl<'v> From<&'v Value> for MsgPackValue<'v> {
             Value::String(v) => MsgPackValue::String(v),
             Value::Tuple(v) => MsgPackValue::Tuple(v),
             Value::Uuid(v) => MsgPackValue::Uuid(v),
+            Value::Varbinary(v) => MsgPackValue::Varbinary(v),
+            Value::Interval(v) => MsgPackValue::Interval(v),
             Value::Unsigned(v) => MsgPackValue::Unsigned(v),
         }
     }
@@ -1160,6 +1309,8 @@ impl<'v> From<EncodedValue<'v>> for Value {
             EncodedValue::Ref(MsgPackValue::String(v)) => Value::String(v.clone()),
             EncodedValue::Ref(MsgPackValue::Tuple(v)) => Value::Tuple(v.clone()),
             EncodedValue::Ref(MsgPackValue::Uuid(v)) => Value::Uuid(*v),
+            EncodedValue::Ref(MsgPackValue::Varbinary(v)) => Value::Varbinary(v.clone()),
+            EncodedValue::Ref(MsgPackValue::Interval(v)) => Value::Interval(*v),
             EncodedValue::Ref(MsgPackValue::Null(())) => Value::Null,
             EncodedValue::Owned(v) => v,
         }
@@ -1178,6 +1329,8 @@ impl From<Value> for String {
             Value::String(v) => v,
             Value::Tuple(v) => v.to_string(),
             Value::Uuid(v) => v.to_string(),
+            Value::Varbinary(v) => v.to_string(),
+            Value::Interval(v) => v.to_string(),
             Value::Null => "NULL".to_string(),
         }
     }
@@ -1197,6 +1350,8 @@ impl<L: tlua::AsLua> tlua::Push<L> for Value {
             Value::String(v) => v.push_to_lua(lua),
             Value::Tuple(v) => v.push_to_lua(lua),
             Value::Uuid(v) => v.push_to_lua(lua),
+            Value::Varbinary(v) => v.push_to_lua(lua),
+            Value::Interval(v) => v.push_to_lua(lua),
             Value::Null => tlua::Null.push_to_lua(lua),
         }
     }
@@ -1219,6 +1374,8 @@ where
             Value::String(v) => v.push_into_lua(lua),
             Value::Tuple(v) => v.push_into_lua(lua),
             Value::Uuid(v) => v.push_into_lua(lua),
+            Value::Varbinary(v) => v.push_into_lua(lua),
+            Value::Interval(v) => v.push_into_lua(lua),
             Value::Null => tlua::Null.push_into_lua(lua),
         }
     }
@@ -1292,5 +1449,7 @@ where
 }
 
 pub mod double;
+pub mod interval;
 #[cfg(test)]
 mod tests;
+pub mod varbinary;
@@ -0,0 +1,514 @@
+//! Interval type module.
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::errors::{Entity, SbroadError};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use smol_str::format_smolstr;
+use tarantool::msgpack::{Context, Decode, DecodeError, Encode, EncodeError};
+use tarantool::tlua;
+
+/// Msgpack extension type of the tarantool `interval`.
+pub const MP_INTERVAL: i8 = 6;
+
+const NANOS_PER_MICRO: i64 = 1_000;
+const NANOS_PER_MILLI: i64 = 1_000_000;
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const NANOS_PER_MINUTE: i64 = 60 * NANOS_PER_SECOND;
+const NANOS_PER_HOUR: i64 = 60 * NANOS_PER_MINUTE;
+const NANOS_PER_DAY: i64 = 24 * NANOS_PER_HOUR;
+const DAYS_PER_MONTH: i64 = 30;
+const MONTHS_PER_YEAR: i32 = 12;
+
+/// Fields of the tarantool `interval` msgpack representation.
+const FIELD_YEAR: u8 = 0;
+const FIELD_MONTH: u8 = 1;
+const FIELD_WEEK: u8 = 2;
+const FIELD_DAY: u8 = 3;
+const FIELD_HOUR: u8 = 4;
+const FIELD_MINUTE: u8 = 5;
+const FIELD_SECOND: u8 = 6;
+const FIELD_NANOSECOND: u8 = 7;
+const FIELD_ADJUST: u8 = 8;
+
+/// Time interval (`interval` type of tarantool and PostgreSQL).
+///
+/// Like in PostgreSQL, the interval keeps months, days and the time part
+/// separately, as the number of days in a month and of hours in a day
+/// depend on the datetime the interval is added to. Intervals are compared
+/// by the total duration with 30-day months and 24-hour days, so `1 mon`,
+/// `30 days` and `720 hours` are equal values.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Interval {
+    pub months: i32,
+    pub days: i32,
+    pub nanoseconds: i64,
+}
+
+/// Unit of the interval text representation.
+#[derive(Clone, Copy)]
+enum Unit {
+    Months(i64),
+    Days(i64),
+    Nanoseconds(i64),
+}
+
+impl Unit {
+    fn from_name(name: &str) -> Option<Self> {
+        let unit = match name {
+            "us" | "usec" | "usecs" | "microsecond" | "microseconds" => {
+                Unit::Nanoseconds(NANOS_PER_MICRO)
+            }
+            "ms" | "msec" | "msecs" | "millisecond" | "milliseconds" => {
+                Unit::Nanoseconds(NANOS_PER_MILLI)
+            }
+            "s" | "sec" | "secs" | "second" | "seconds" => Unit::Nanoseconds(NANOS_PER_SECOND),
+            "m" | "min" | "mins" | "minute" | "minutes" => Unit::Nanoseconds(NANOS_PER_MINUTE),
+            "h" | "hr" | "hrs" | "hour" | "hours" => Unit::Nanoseconds(NANOS_PER_HOUR),
+            "d" | "day" | "days" => Unit::Days(1),
+            "w" | "week" | "weeks" => Unit::Days(7),
+            "mon" | "mons" | "month" | "months" => Unit::Months(1),
+            "y" | "yr" | "yrs" | "year" | "years" => Unit::Months(12),
+            "decade" | "decades" => Unit::Months(120),
+            "century" | "centuries" => Unit::Months(1200),
+            "millennium" | "millennia" => Unit::Months(12000),
+            _ => return None,
+        };
+        Some(unit)
+    }
+}
+
+/// Accumulates interval fields with a wide range to detect overflows
+/// only once the whole text is parsed.
+#[derive(Default)]
+struct Accumulator {
+    months: i64,
+    days: i64,
+    nanoseconds: i128,
+}
+
+impl Accumulator {
+    /// Add the number of units. Like in PostgreSQL, fractional months
+    /// are spilled into days and fractional days into the time part.
+    fn add(&mut self, number: &str, unit: Unit) -> Option<()> {
+        let (is_negative, number) = match number.strip_prefix('-') {
+            Some(number) => (true, number),
+            None => (false, number.strip_prefix('+').unwrap_or(number)),
+        };
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty() && fraction.is_empty()
+            || !whole
+                .chars()
+                .chain(fraction.chars())
+                .all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+        let whole = if whole.is_empty() {
+            0
+        } else {
+            whole.parse::<i64>().ok()?
+        };
+        let fraction = if fraction.is_empty() {
+            0.0
+        } else {
+            format!("0.{fraction}").parse::<f64>().ok()?
+        };
+        let (whole, fraction) = if is_negative {
+            (-whole, -fraction)
+        } else {
+            (whole, fraction)
+        };
+        match unit {
+            Unit::Months(n) => {
+                self.months += whole.checked_mul(n)?;
+                let days = fraction * (n * DAYS_PER_MONTH) as f64;
+                self.days += days.trunc() as i64;
+                self.nanoseconds += (days.fract() * NANOS_PER_DAY as f64).round() as i128;
+            }
+            Unit::Days(n) => {
+                self.days += whole.checked_mul(n)?;
+                self.nanoseconds += (fraction * (n * NANOS_PER_DAY) as f64).round() as i128;
+            }
+            Unit::Nanoseconds(n) => {
+                self.nanoseconds += i128::from(whole) * i128::from(n);
+                self.nanoseconds += (fraction * n as f64).round() as i128;
+            }
+        }
+        Some(())
+    }
+
+    /// Add the time part in the `[-]hh:mm[:ss[.fff]]` format.
+    fn add_time(&mut self, time: &str) -> Option<()> {
+        let (sign, time) = match time.strip_prefix('-') {
+            Some(time) => ("-", time),
+            None => ("", time.strip_prefix('+').unwrap_or(time)),
+        };
+        let mut parts = time.split(':');
+        let hours = parts.next()?;
+        let minutes = parts.next()?;
+        let seconds = parts.next().unwrap_or("0");
+        if parts.next().is_some()
+            || [hours, minutes]
+                .iter()
+                .any(|p| p.is_empty() || p.contains('.'))
+        {
+            return None;
+        }
+        self.add(&format!("{sign}{hours}"), Unit::Nanoseconds(NANOS_PER_HOUR))?;
+        self.add(
+            &format!("{sign}{minutes}"),
+            Unit::Nanoseconds(NANOS_PER_MINUTE),
+        )?;
+        self.add(
+            &format!("{sign}{seconds}"),
+            Unit::Nanoseconds(NANOS_PER_SECOND),
+        )
+    }
+
+    fn negate(&mut self) {
+        self.months = -self.months;
+        self.days = -self.days;
+        self.nanoseconds = -self.nanoseconds;
+    }
+
+    fn finish(self) -> Option<Interval> {
+        Some(Interval {
+            months: i32::try_from(self.months).ok()?,
+            days: i32::try_from(self.days).ok()?,
+            nanoseconds: i64::try_from(self.nanoseconds).ok()?,
+        })
+    }
+}
+
+impl Interval {
+    #[must_use]
+    pub fn new(months: i32, days: i32, nanoseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            nanoseconds,
+        }
+    }
+
+    /// Total duration used for comparison, see `interval_cmp_value`
+    /// of PostgreSQL.
+    fn total_nanoseconds(&self) -> i128 {
+        let days = i128::from(self.months) * i128::from(DAYS_PER_MONTH) + i128::from(self.days);
+        days * i128::from(NANOS_PER_DAY) + i128::from(self.nanoseconds)
+    }
+
+    /// Parse the verbose PostgreSQL format: `1 year 2 mons -3 days 04:05:06 ago`.
+    fn parse_verbose(s: &str) -> Option<Self> {
+        let s = s.strip_prefix('@').unwrap_or(s);
+        let mut acc = Accumulator::default();
+        let mut is_empty = true;
+        let mut tokens = s.split_whitespace().peekable();
+        while let Some(token) = tokens.next() {
+            if token == "ago" && !is_empty && tokens.peek().is_none() {
+                acc.negate();
+                break;
+            }
+            is_empty = false;
+            if token.contains(':') {
+                acc.add_time(token)?;
+                continue;
+            }
+            // The unit may be attached to the number (`1day`) or follow it.
+            let split = token
+                .find(|c: char| c.is_ascii_alphabetic())
+                .unwrap_or(token.len());
+            let (number, unit) = token.split_at(split);
+            let unit = match (unit, tokens.peek()) {
+                ("", Some(next)) if Unit::from_name(next).is_some() => {
+                    tokens.next().and_then(Unit::from_name)?
+                }
+                // A number without a unit is the number of seconds.
+                ("", _) => Unit::Nanoseconds(NANOS_PER_SECOND),
+                (unit, _) => Unit::from_name(unit)?,
+            };
+            acc.add(number, unit)?;
+        }
+        if is_empty {
+            return None;
+        }
+        acc.finish()
+    }
+
+    /// Parse the ISO 8601 format with designators: `P1Y2M3DT4H5M6S`.
+    fn parse_iso8601(s: &str) -> Option<Self> {
+        let s = s.strip_prefix('p')?;
+        let mut acc = Accumulator::default();
+        let mut is_time = false;
+        let mut is_empty = true;
+        let mut number = String::new();
+        for c in s.chars() {
+            let unit = match c {
+                't' if number.is_empty() && !is_time => {
+                    is_time = true;
+                    continue;
+                }
+                '0'..='9' | '.' | '-' | '+' => {
+                    number.push(c);
+                    continue;
+                }
+                'y' if !is_time => Unit::Months(12),
+                'm' if !is_time => Unit::Months(1),
+                'w' if !is_time => Unit::Days(7),
+                'd' if !is_time => Unit::Days(1),
+                'h' if is_time => Unit::Nanoseconds(NANOS_PER_HOUR),
+                'm' if is_time => Unit::Nanoseconds(NANOS_PER_MINUTE),
+                's' if is_time => Unit::Nanoseconds(NANOS_PER_SECOND),
+                _ => return None,
+            };
+            if number.is_empty() {
+                return None;
+            }
+            acc.add(&number, unit)?;
+            number.clear();
+            is_empty = false;
+        }
+        if is_empty || !number.is_empty() {
+            return None;
+        }
+        acc.finish()
+    }
+
+    /// Payload of the tarantool msgpack extension: the number of non-zero
+    /// fields followed by the pairs of the field id and its value.
+    /// The number of fields and field ids are stored as raw bytes.
+    #[must_use]
+    pub fn to_ext_data(&self) -> Vec<u8> {
+        let nanos = self.nanoseconds;
+        let fields = [
+            (FIELD_YEAR, i64::from(self.months / MONTHS_PER_YEAR)),
+            (FIELD_MONTH, i64::from(self.months % MONTHS_PER_YEAR)),
+            (FIELD_DAY, i64::from(self.days)),
+            (FIELD_HOUR, nanos / NANOS_PER_HOUR),
+            (FIELD_MINUTE, nanos % NANOS_PER_HOUR / NANOS_PER_MINUTE),
+            (FIELD_SECOND, nanos % NANOS_PER_MINUTE / NANOS_PER_SECOND),
+            (FIELD_NANOSECOND, nanos % NANOS_PER_SECOND),
+        ];
+        let count = fields.iter().filter(|(_, v)| *v != 0).count();
+        let mut data = Vec::with_capacity(1 + count * 10);
+        data.push(count as u8);
+        for (field, value) in fields.into_iter().filter(|(_, v)| *v != 0) {
+            data.push(field);
+            let res = if value > 0 {
+                rmp::encode::write_uint(&mut data, value as u64)
+            } else {
+                rmp::encode::write_sint(&mut data, value)
+            };
+            res.expect("writing to a vector never fails");
+        }
+        data
+    }
+
+    /// Decode the payload of the tarantool msgpack extension.
+    #[must_use]
+    pub fn from_ext_data(mut data: &[u8]) -> Option<Self> {
+        let mut acc = Accumulator::default();
+        let (count, rest) = data.split_first()?;
+        data = rest;
+        for _ in 0..*count {
+            let (field, rest) = data.split_first()?;
+            data = rest;
+            let value: i64 = rmp::decode::read_int(&mut data).ok()?;
+            match *field {
+                FIELD_YEAR => acc.months += value.checked_mul(12)?,
+                FIELD_MONTH => acc.months += value,
+                FIELD_WEEK => acc.days += value.checked_mul(7)?,
+                FIELD_DAY => acc.days += value,
+                FIELD_HOUR => acc.nanoseconds += i128::from(value) * i128::from(NANOS_PER_HOUR),
+                FIELD_MINUTE => {
+                    acc.nanoseconds += i128::from(value) * i128::from(NANOS_PER_MINUTE);
+                }
+                FIELD_SECOND => {
+                    acc.nanoseconds += i128::from(value) * i128::from(NANOS_PER_SECOND);
+                }
+                FIELD_NANOSECOND => acc.nanoseconds += i128::from(value),
+                // Adjustment of the month end is ignored, as
+                // PostgreSQL always limits the day by the month end.
+                FIELD_ADJUST => {}
+                _ => return None,
+            }
+        }
+        acc.finish()
+    }
+}
+
+/// Parse the text representation of `interval`: either the PostgreSQL
+/// verbose format (`1 day 02:00:00`) or ISO 8601 (`P1DT2H`).
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.total_nanoseconds() == other.total_nanoseconds()
+    }
+}
+
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.total_nanoseconds().cmp(&other.total_nanoseconds())
+    }
+}
+
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.total_nanoseconds().hash(state);
+    }
+}
+
+impl FromStr for Interval {
+    type Err = SbroadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim().to_lowercase();
+        let interval = if text.starts_with('p') {
+            Self::parse_iso8601(&text)
+        } else {
+            Self::parse_verbose(&text)
+        };
+        interval.ok_or_else(|| {
+            SbroadError::ParsingError(
+                Entity::Value,
+                format_smolstr!("invalid input syntax for type interval: \"{s}\""),
+            )
+        })
+    }
+}
+
+impl<'de> Decode<'de> for Interval {
+    fn decode(r: &mut &'de [u8], _context: &Context) -> Result<Self, DecodeError> {
+        let meta = rmp::decode::read_ext_meta(r).map_err(DecodeError::from_vre::<Self>)?;
+        if meta.typeid != MP_INTERVAL {
+            return Err(DecodeError::new::<Self>(format_smolstr!(
+                "unexpected extension type {}",
+                meta.typeid
+            )));
+        }
+        let len = meta.size as usize;
+        if r.len() < len {
+            return Err(DecodeError::new::<Self>("not enough data"));
+        }
+        let (data, rest) = r.split_at(len);
+        *r = rest;
+        Self::from_ext_data(data).ok_or_else(|| DecodeError::new::<Self>("invalid interval"))
+    }
+}
+
+impl Encode for Interval {
+    fn encode(&self, w: &mut impl Write, _context: &Context) -> Result<(), EncodeError> {
+        let data = self.to_ext_data();
+        rmp::encode::write_ext_meta(w, data.len() as u32, MP_INTERVAL)?;
+        w.write_all(&data)
+            .map_err(rmp::encode::ValueWriteError::InvalidDataWrite)?;
+        Ok(())
+    }
+}
+
+/// Serialized as the msgpack extension, like other tarantool types.
+impl Serialize for Interval {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct _ExtStruct((i8, serde_bytes::ByteBuf));
+
+        let data = serde_bytes::ByteBuf::from(self.to_ext_data());
+        _ExtStruct((MP_INTERVAL, data)).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Interval {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct _ExtStruct((i8, serde_bytes::ByteBuf));
+
+        let _ExtStruct((tag, data)) = _ExtStruct::deserialize(deserializer)?;
+        if tag != MP_INTERVAL {
+            return Err(serde::de::Error::custom(format!(
+                "unexpected extension type {tag}"
+            )));
+        }
+        Self::from_ext_data(&data).ok_or_else(|| serde::de::Error::custom("invalid interval"))
+    }
+}
+
+/// Display in the verbose PostgreSQL format: `1 year 2 mons -3 days +04:05:06`.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let plural = |v: i32| if v == 1 { "" } else { "s" };
+        let years = self.months / MONTHS_PER_YEAR;
+        let months = self.months % MONTHS_PER_YEAR;
+        let mut parts = Vec::new();
+        if years != 0 {
+            parts.push(format!("{years} year{}", plural(years)));
+        }
+        if months != 0 {
+            parts.push(format!("{months} mon{}", plural(months)));
+        }
+        if self.days != 0 {
+            parts.push(format!("{} day{}", self.days, plural(self.days)));
+        }
+        if self.nanoseconds != 0 || parts.is_empty() {
+            let sign = if self.nanoseconds < 0 {
+                "-"
+            } else if self.months < 0 || self.days < 0 {
+                "+"
+            } else {
+                ""
+            };
+            let nanos = self.nanoseconds.unsigned_abs();
+            let (hours, minutes, seconds, fraction) = (
+                nanos / NANOS_PER_HOUR as u64,
+                nanos % NANOS_PER_HOUR as u64 / NANOS_PER_MINUTE as u64,
+                nanos % NANOS_PER_MINUTE as u64 / NANOS_PER_SECOND as u64,
+                nanos % NANOS_PER_SECOND as u64,
+            );
+            let mut time = format!("{sign}{hours:02}:{minutes:02}:{seconds:02}");
+            if fraction != 0 {
+                let digits = format!("{fraction:09}");
+                time.push('.');
+                time.push_str(digits.trim_end_matches('0'));
+            }
+            parts.push(time);
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// Lua has no native interval values, so intervals are pushed as text.
+impl<L: tlua::AsLua> tlua::Push<L> for Interval {
+    type Err = tlua::Void;
+
+    fn push_to_lua(&self, lua: L) -> Result<tlua::PushGuard<L>, (Self::Err, L)> {
+        self.to_string().push_into_lua(lua)
+    }
+}
+
+impl<L> tlua::PushInto<L> for Interval
+where
+    L: tlua::AsLua,
+{
+    type Err = tlua::Void;
+    fn push_into_lua(self, lua: L) -> Result<tlua::PushGuard<L>, (tlua::Void, L)> {
+        self.to_string().push_into_lua(lua)
+    }
+}
+
+impl<L> tlua::PushOneInto<L> for Interval where L: tlua::AsLua {}
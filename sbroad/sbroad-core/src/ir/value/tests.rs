@@ -55,6 +55,156 @@ fn uuid_negative() {
     );
 }
 
+#[test]
+fn varbinary() {
+    let bytes = Varbinary(vec![0xde, 0xad, 0xbe, 0xef]);
+    let v_bin = Value::from(bytes.clone());
+
+    assert_eq!(Value::Varbinary(bytes.clone()), v_bin);
+    assert_eq!(format!("{}", v_bin), "X'deadbeef'");
+    assert_eq!(bytes.to_string(), r"\xdeadbeef");
+    assert_eq!(v_bin.get_type(), DerivedType::new(Type::Varbinary));
+    assert_eq!(v_bin.eq(&Value::Varbinary(bytes.clone())), Trivalent::True);
+    assert_eq!(
+        v_bin.eq(&Value::Varbinary(Varbinary(vec![]))),
+        Trivalent::False
+    );
+    assert_eq!(
+        v_bin.eq(&Value::String("deadbeef".into())),
+        Trivalent::False
+    );
+    assert_eq!(
+        v_bin.partial_cmp(&Value::Varbinary(Varbinary(vec![0xde, 0xae]))),
+        Some(TrivalentOrdering::Less)
+    );
+    assert_eq!(v_bin.partial_cmp(&Value::String("a".into())), None);
+
+    // Hex and escape text formats.
+    assert_eq!(
+        Value::String(r"\xDEADbeef".into())
+            .cast(Type::Varbinary)
+            .unwrap(),
+        v_bin
+    );
+    assert_eq!(
+        Varbinary::from_str(r"a\\b\000\377").unwrap(),
+        Varbinary(vec![b'a', b'\\', b'b', 0, 0xff])
+    );
+    assert_eq!(Varbinary::from_str("").unwrap(), Varbinary(vec![]));
+
+    // Varbinary is encoded as msgpack bin.
+    let encoded = tarantool::msgpack::encode(&v_bin);
+    assert_eq!(encoded, [0xc4, 0x04, 0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(
+        tarantool::msgpack::decode::<Value>(&encoded).unwrap(),
+        v_bin
+    );
+}
+
+#[test]
+fn varbinary_negative() {
+    assert_eq!(
+        Varbinary::from_hex("abc").unwrap_err(),
+        SbroadError::ParsingError(
+            Entity::Value,
+            SmolStr::from("invalid hexadecimal data: abc")
+        )
+    );
+    assert_eq!(
+        Varbinary::from_str(r"\xzz").unwrap_err(),
+        SbroadError::ParsingError(Entity::Value, SmolStr::from("invalid hexadecimal data: zz"))
+    );
+    assert_eq!(
+        Varbinary::from_str(r"a\9").unwrap_err(),
+        SbroadError::ParsingError(
+            Entity::Value,
+            SmolStr::from(r"invalid input syntax for type bytea: a\9")
+        )
+    );
+    assert_eq!(
+        Value::Integer(1)
+            .cast_and_encode(&DerivedType::new(Type::Varbinary))
+            .unwrap_err(),
+        SbroadError::Invalid(
+            Entity::Value,
+            Some(SmolStr::from("Failed to cast 1 to varbinary."))
+        )
+    );
+}
+
+#[test]
+fn interval() {
+    let iv = Interval::new(0, 1, 2 * 3_600_000_000_000);
+    let v_iv = Value::from(iv);
+
+    assert_eq!(Interval::from_str("1 day 2 hours").unwrap(), iv);
+    assert_eq!(Interval::from_str("@ 1 DAY 120 MINUTES").unwrap(), iv);
+    assert_eq!(Interval::from_str("P1DT2H").unwrap(), iv);
+    assert_eq!(format!("{}", v_iv), "'1 day 02:00:00'");
+    assert_eq!(v_iv.get_type(), DerivedType::new(Type::Interval));
+    assert_eq!(
+        Value::String("1 day 02:00:00".into())
+            .cast(Type::Interval)
+            .unwrap(),
+        v_iv
+    );
+
+    // PostgreSQL text format.
+    for (input, output) in [
+        (
+            "1 year 2 mons 3 days 04:05:06",
+            "1 year 2 mons 3 days 04:05:06",
+        ),
+        ("-1 day 2 hours", "-1 days +02:00:00"),
+        ("1.5 days", "1 day 12:00:00"),
+        ("2 hours ago", "-02:00:00"),
+        ("1 week", "7 days"),
+        ("00:00:00.000001", "00:00:00.000001"),
+        ("0", "00:00:00"),
+    ] {
+        assert_eq!(Interval::from_str(input).unwrap().to_string(), output);
+    }
+
+    assert_eq!(
+        v_iv.partial_cmp(&Value::Interval(Interval::new(1, 0, 0))),
+        Some(TrivalentOrdering::Less)
+    );
+    assert_eq!(v_iv.partial_cmp(&Value::String("a".into())), None);
+
+    // Like in PostgreSQL, intervals are compared by the total duration
+    // with 30-day months and 24-hour days.
+    let hours_48 = Interval::from_str("48 hours").unwrap();
+    assert!(hours_48 > Interval::from_str("1 day").unwrap());
+    assert!(hours_48 < Interval::from_str("1 mon").unwrap());
+    assert_eq!(
+        Interval::from_str("1 mon").unwrap(),
+        Interval::from_str("30 days").unwrap()
+    );
+    assert_eq!(
+        Interval::from_str("1 day").unwrap(),
+        Interval::from_str("24 hours").unwrap()
+    );
+
+    // Interval is encoded as tarantool msgpack extension:
+    // the number of fields followed by pairs of field id and value.
+    let encoded = tarantool::msgpack::encode(&v_iv);
+    assert_eq!(encoded, [0xc7, 0x05, 0x06, 0x02, 0x03, 0x01, 0x04, 0x02]);
+    assert_eq!(tarantool::msgpack::decode::<Value>(&encoded).unwrap(), v_iv);
+}
+
+#[test]
+fn interval_negative() {
+    for input in ["", "abc", "1 bogus", "--1 day", "ago", "P", "1:2:3:4"] {
+        assert_eq!(
+            Interval::from_str(input).unwrap_err(),
+            SbroadError::ParsingError(
+                Entity::Value,
+                format_smolstr!("invalid input syntax for type interval: \"{input}\""),
+            )
+        );
+    }
+}
+
 #[test]
 fn decimal() {
     assert_eq!(
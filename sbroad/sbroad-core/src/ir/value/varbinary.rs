@@ -0,0 +1,176 @@
+//! Varbinary type module.
+
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::errors::{Entity, SbroadError};
+use serde::{Deserialize, Serialize};
+use smol_str::format_smolstr;
+use tarantool::msgpack::{Context, Decode, DecodeError, Encode, EncodeError};
+use tarantool::tlua;
+
+/// Binary string stored in the tarantool `varbinary` field
+/// (`bytea` in terms of PostgreSQL).
+#[derive(Serialize, Deserialize, Hash, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Clone)]
+#[serde(transparent)]
+pub struct Varbinary(#[serde(with = "serde_bytes")] pub Vec<u8>);
+
+impl Varbinary {
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Hex representation without the `\x` prefix.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        use fmt::Write as _;
+
+        let mut hex = String::with_capacity(self.0.len() * 2);
+        for byte in &self.0 {
+            write!(hex, "{byte:02x}").expect("writing to a string never fails");
+        }
+        hex
+    }
+
+    /// Parse digits of the hex literal (`X'DEADBEEF'` or `\xDEADBEEF`).
+    ///
+    /// # Errors
+    /// - Odd number of digits or a non-hex digit.
+    pub fn from_hex(hex: &str) -> Result<Self, SbroadError> {
+        let invalid = || {
+            SbroadError::ParsingError(
+                Entity::Value,
+                format_smolstr!("invalid hexadecimal data: {hex}"),
+            )
+        };
+        let digits = hex.as_bytes();
+        if digits.len() % 2 != 0 || !digits.iter().all(u8::is_ascii_hexdigit) {
+            return Err(invalid());
+        }
+        let mut bytes = Vec::with_capacity(digits.len() / 2);
+        for pair in digits.chunks(2) {
+            let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+            bytes.push(u8::from_str_radix(pair, 16).map_err(|_| invalid())?);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Parse the text representation in the escape format: all characters
+    /// are taken as is, except for `\\` (backslash) and `\ooo` (octal byte).
+    ///
+    /// # Errors
+    /// - Invalid escape sequence.
+    fn from_escape(s: &str) -> Result<Self, SbroadError> {
+        let invalid = || {
+            SbroadError::ParsingError(
+                Entity::Value,
+                format_smolstr!("invalid input syntax for type bytea: {s}"),
+            )
+        };
+        let input = s.as_bytes();
+        let mut bytes = Vec::with_capacity(input.len());
+        let mut i = 0;
+        while i < input.len() {
+            if input[i] != b'\\' {
+                bytes.push(input[i]);
+                i += 1;
+                continue;
+            }
+            match input.get(i + 1..) {
+                Some([b'\\', ..]) => {
+                    bytes.push(b'\\');
+                    i += 2;
+                }
+                Some(&[a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7', ..]) => {
+                    bytes.push(((a - b'0') << 6) | ((b - b'0') << 3) | (c - b'0'));
+                    i += 4;
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(Self(bytes))
+    }
+}
+
+/// Parse the text representation of `bytea`: either the hex format
+/// (`\xDEADBEEF`) or the escape format (`a\\b\000`).
+impl FromStr for Varbinary {
+    type Err = SbroadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix("\\x").or_else(|| s.strip_prefix("\\X")) {
+            Some(hex) => Self::from_hex(hex),
+            None => Self::from_escape(s),
+        }
+    }
+}
+
+impl From<Vec<u8>> for Varbinary {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<&[u8]> for Varbinary {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl<'de> Decode<'de> for Varbinary {
+    fn decode(r: &mut &'de [u8], _context: &Context) -> Result<Self, DecodeError> {
+        let len = rmp::decode::read_bin_len(r).map_err(DecodeError::from_vre::<Self>)? as usize;
+        if r.len() < len {
+            return Err(DecodeError::new::<Self>("not enough data"));
+        }
+        let (bytes, rest) = r.split_at(len);
+        *r = rest;
+        Ok(Self(bytes.to_vec()))
+    }
+}
+
+impl Encode for Varbinary {
+    fn encode(&self, w: &mut impl Write, _context: &Context) -> Result<(), EncodeError> {
+        rmp::encode::write_bin(w, &self.0)?;
+        Ok(())
+    }
+}
+
+/// Display in the hex format of `bytea`.
+impl fmt::Display for Varbinary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\\x{}", self.to_hex())
+    }
+}
+
+impl<L: tlua::AsLua> tlua::Push<L> for Varbinary {
+    type Err = tlua::Void;
+
+    fn push_to_lua(&self, lua: L) -> Result<tlua::PushGuard<L>, (Self::Err, L)> {
+        tlua::AnyLuaString(self.0.clone()).push_into_lua(lua)
+    }
+}
+
+impl<L> tlua::PushInto<L> for Varbinary
+where
+    L: tlua::AsLua,
+{
+    type Err = tlua::Void;
+    fn push_into_lua(self, lua: L) -> Result<tlua::PushGuard<L>, (tlua::Void, L)> {
+        tlua::AnyLuaString(self.0).push_into_lua(lua)
+    }
+}
+
+impl<L> tlua::PushOneInto<L> for Varbinary where L: tlua::AsLua {}
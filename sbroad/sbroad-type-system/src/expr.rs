@@ -7,11 +7,17 @@ pub enum Type {
     Double,
     Numeric,
     Text,
+    Bytea,
     Boolean,
     Datetime,
+    Interval,
     Uuid,
     Array,
     Map,
+    /// Pseudo-type of function parameters that accept values of any type
+    /// and of function results whose type is known only at runtime
+    /// (e.g. a field of a JSON document).
+    Any,
     // Type of NULL literal.
     Unknown,
 }
@@ -24,11 +30,14 @@ impl Type {
             Type::Double => "double",
             Type::Numeric => "numeric",
             Type::Text => "text",
+            Type::Bytea => "bytea",
             Type::Boolean => "bool",
             Type::Datetime => "datetime",
+            Type::Interval => "interval",
             Type::Uuid => "uuid",
             Type::Array => "array",
             Type::Map => "map",
+            Type::Any => "any",
             Type::Unknown => "unknown",
         }
     }
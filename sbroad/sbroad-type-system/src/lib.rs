@@ -124,6 +124,10 @@ mod tests {
         expr(ExprKind::Null)
     }
 
+    fn func(name: impl Into<String>, args: Vec<Expr>) -> Expr {
+        expr(ExprKind::Function(name.into(), args))
+    }
+
     fn case(when_exprs: Vec<Expr>, result_exprs: Vec<Expr>) -> Expr {
         expr(ExprKind::Case {
             when_exprs,
@@ -594,4 +598,95 @@ mod tests {
             assert_eq!(report.get_type(&expr), result_type);
         }
     }
+
+    #[test]
+    fn any_type_tests() {
+        let type_system = TypeSystem::new(vec![
+            Function::new_scalar("json_get", [Any, Text], Any),
+            Function::new_scalar("json_get", [Any, Integer], Any),
+            Function::new_scalar("json_get_text", [Any, Text], Text),
+            Function::new_operator("=", [Integer, Integer], Boolean),
+            Function::new_operator("=", [Text, Text], Boolean),
+        ]);
+
+        let exprs = [
+            (func("json_get", vec![lit(Map), lit(Text)]), Any),
+            (func("json_get", vec![lit(Map), lit(Unsigned)]), Any),
+            (func("json_get", vec![lit(Text), param("$1")]), Any),
+            (func("json_get", vec![null(), lit(Text)]), Any),
+            (
+                func(
+                    "json_get_text",
+                    vec![func("json_get", vec![lit(Map), lit(Integer)]), lit(Text)],
+                ),
+                Text,
+            ),
+            (
+                binary(
+                    "=",
+                    func("json_get_text", vec![lit(Map), lit(Text)]),
+                    lit(Text),
+                ),
+                Boolean,
+            ),
+            (
+                cast(func("json_get_text", vec![lit(Map), lit(Text)]), Integer),
+                Integer,
+            ),
+        ];
+
+        for (expr, result_type) in exprs {
+            let mut analyzer = TypeAnalyzer::new(&type_system);
+            let report = analyzer.analyze(&expr, None).unwrap();
+            assert_eq!(report.get_type(&expr), result_type);
+        }
+
+        let errors = [
+            (
+                func("json_get", vec![param("$1"), lit(Text)]),
+                "could not determine data type of parameter $1",
+            ),
+            (
+                binary(
+                    "=",
+                    func("json_get", vec![lit(Map), lit(Text)]),
+                    lit(Integer),
+                ),
+                "could not resolve operator overload for =(any, int)",
+            ),
+        ];
+
+        for (expr, err_msg) in errors {
+            let mut analyzer = TypeAnalyzer::new(&type_system);
+            let error = analyzer.analyze(&expr, None).unwrap_err();
+            assert_eq!(error.to_string(), err_msg);
+        }
+    }
+
+    #[test]
+    fn untyped_args_prefer_text_tests() {
+        let type_system = TypeSystem::new(vec![
+            Function::new_scalar("length", [Text], Integer),
+            Function::new_scalar("length", [Bytea], Integer),
+            Function::new_scalar("substr", [Text, Integer], Text),
+            Function::new_scalar("substr", [Bytea, Integer], Bytea),
+        ]);
+
+        let exprs = [
+            (func("length", vec![param("$1")]), Integer),
+            (func("length", vec![null()]), Integer),
+            (func("length", vec![lit(Bytea)]), Integer),
+            (func("substr", vec![param("$1"), lit(Unsigned)]), Text),
+            (func("substr", vec![null(), lit(Unsigned)]), Text),
+            (func("substr", vec![lit(Text), param("$1")]), Text),
+            (func("substr", vec![lit(Bytea), param("$1")]), Bytea),
+            (func("substr", vec![lit(Bytea), null()]), Bytea),
+        ];
+
+        for (expr, result_type) in exprs {
+            let mut analyzer = TypeAnalyzer::new(&type_system);
+            let report = analyzer.analyze(&expr, None).unwrap();
+            assert_eq!(report.get_type(&expr), result_type);
+        }
+    }
 }
@@ -277,9 +277,9 @@ impl<'a, Id: Hash + Eq + Clone> TypeAnalyzer<'a, Id> {
                 Ok(report)
             }
             ExprKind::Parameter(name) => {
-                if let Some(desired_type) = desired_type {
-                    // TODO: ensure type is not a pseudo-type
-                    // https://www.postgresql.org/docs/current/datatype-pseudo.html
+                // Pseudo-types cannot be used as parameter types.
+                // https://www.postgresql.org/docs/current/datatype-pseudo.html
+                if let Some(desired_type) = desired_type.filter(|t| *t != Type::Any) {
                     // TEST: `select $1 = null;`
                     let mut report = TypeReport::new();
                     report.report(&expr.id, desired_type);
@@ -540,10 +540,13 @@ impl<'a, Id: Hash + Eq + Clone> TypeAnalyzer<'a, Id> {
             let mut report = self.analyze_many(args, &overload.args_types)?;
             let args_types = args.iter().map(|e| report.get_type(e));
 
-            if zip(args_types, &overload.args_types).all(|(t1, t2)| self.can_coerce(t1, *t2)) {
+            if zip(args_types, &overload.args_types)
+                .all(|(t1, t2)| *t2 == Type::Any || self.can_coerce(t1, *t2))
+            {
                 // Make arguments match overload types.
+                // Values of any type are passed to `any` parameters as is.
                 for (arg, ty) in zip(args, &overload.args_types) {
-                    if report.get_type(arg) != *ty {
+                    if *ty != Type::Any && report.get_type(arg) != *ty {
                         report.cast(&arg.id, *ty);
                         report.report(&arg.id, *ty);
                     }
@@ -582,6 +585,18 @@ impl<'a, Id: Hash + Eq + Clone> TypeAnalyzer<'a, Id> {
             }
         }
 
+        // Arguments of unknown type (parameters and NULLs) are resolved to `text`,
+        // which is the preferred type for them, like in PostgreSQL.
+        // Example: `length($1)` with `length(text)` and `length(bytea)` overloads.
+        let mut text_matches = best_matches.iter().filter(|(func, _)| {
+            zip(args, &func.args_types).all(|(arg, ty)| {
+                *ty == Type::Text || !matches!(arg.kind, ExprKind::Parameter(_) | ExprKind::Null)
+            })
+        });
+        if let (Some((func, report)), None) = (text_matches.next(), text_matches.next()) {
+            return Ok(Some((func.return_type, report.clone())));
+        }
+
         Ok(None)
     }
 
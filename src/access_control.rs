@@ -28,6 +28,7 @@
 //! make them based on effective user.
 use std::collections::{HashMap, HashSet};
 
+use sbroad::executor::engine::helpers::DEFAULT_SCHEMA;
 use tarantool::auth::AuthMethod;
 use tarantool::error::TarantoolErrorCode::{self, AccessDenied};
 use tarantool::{
@@ -46,8 +47,9 @@ use crate::traft::error::Error;
 use crate::traft::op::Dml;
 use crate::{
     schema::{
-        PrivilegeDef, PrivilegeType, SchemaObjectType as PicoSchemaObjectType, ADMIN_ID,
-        PICO_SERVICE_ID, PICO_SERVICE_USER_NAME,
+        split_schema_name, PrivilegeDef, PrivilegeType, SchemaDef,
+        SchemaObjectType as PicoSchemaObjectType, SequenceDef, TableConstraint, TableDef,
+        TablePartition, ViewDef, ADMIN_ID, PICO_SERVICE_ID, PICO_SERVICE_USER_NAME,
     },
     storage::{make_routine_not_found, space_by_id, Catalog, ToEntryIter},
     traft::{
@@ -249,13 +251,43 @@ fn access_check_dml(storage: &Catalog, dml: &Dml, as_user: UserId) -> tarantool:
 fn access_check_ddl(storage: &Catalog, ddl: &op::Ddl, as_user: UserId) -> tarantool::Result<()> {
     match ddl {
         op::Ddl::CreateTable {
-            id, name, owner, ..
+            id,
+            name,
+            owner,
+            partition,
+            constraints,
+            ..
         } => {
             assert_eq!(
                 *owner, as_user,
                 "when creating objects creator is the owner"
             );
 
+            access_check_create_in_schema(storage, name, as_user)?;
+
+            // Adding a partition alters the partitioned table.
+            if let Some(TablePartition::Of { parent, .. }) = partition {
+                let space = space_by_id(*parent)?;
+                let meta = space.meta()?;
+
+                box_access_check_ddl_as_user(
+                    &meta.name,
+                    *parent,
+                    meta.user_id,
+                    TntSchemaObjectType::Space,
+                    PrivType::Alter,
+                    as_user,
+                )?;
+            }
+
+            // Foreign key reveals which keys the referenced table contains.
+            for constraint in constraints {
+                if let TableConstraint::ForeignKey { parent, .. } = constraint {
+                    let _su = session::su(as_user)?;
+                    box_access_check_space(*parent, PrivType::Read)?;
+                }
+            }
+
             box_access_check_ddl_as_user(
                 name,
                 *id,
@@ -390,6 +422,155 @@ fn access_check_ddl(storage: &Catalog, ddl: &op::Ddl, as_user: UserId) -> tarant
             PrivType::Alter,
             as_user,
         ),
+        op::Ddl::DetachPartition { parent_id, .. } => {
+            let space = space_by_id(*parent_id)?;
+            let meta = space.meta()?;
+
+            box_access_check_ddl_as_user(
+                &meta.name,
+                *parent_id,
+                meta.user_id,
+                TntSchemaObjectType::Space,
+                PrivType::Alter,
+                as_user,
+            )
+        }
+        op::Ddl::CreateSequence {
+            id, name, owner, ..
+        } => {
+            assert_eq!(
+                *owner, as_user,
+                "when creating objects creator is the owner"
+            );
+
+            // Sequences don't have a tarantool counterpart, so creating one
+            // requires the same privilege as creating a table.
+            box_access_check_ddl_as_user(
+                name,
+                *id,
+                *owner,
+                TntSchemaObjectType::Space,
+                PrivType::Create,
+                as_user,
+            )
+        }
+        op::Ddl::DropSequence { id, .. } => {
+            let Some(sequence) = storage.sequences.by_id(*id)? else {
+                return Err(BoxError::new(
+                    TarantoolErrorCode::IllegalParams,
+                    format!("no such sequence #{id}"),
+                )
+                .into());
+            };
+
+            // Only owner or admin can drop a sequence.
+            if sequence.owner != as_user && !is_superuser(as_user) {
+                let user = user_by_id(as_user)?;
+                let (sequence_name, user_name) = (&sequence.name, &user.name);
+                #[rustfmt::skip]
+                return Err(BoxError::new(AccessDenied, format!("Drop access to sequence '{sequence_name}' is denied for user '{user_name}'")).into());
+            }
+
+            Ok(())
+        }
+        op::Ddl::CreateView {
+            id,
+            name,
+            owner,
+            replace,
+            ..
+        } => {
+            if *replace {
+                // Only owner or admin can replace a view.
+                if *owner != as_user && !is_superuser(as_user) {
+                    let user = user_by_id(as_user)?;
+                    let user_name = &user.name;
+                    #[rustfmt::skip]
+                    return Err(BoxError::new(AccessDenied, format!("Alter access to view '{name}' is denied for user '{user_name}'")).into());
+                }
+                return Ok(());
+            }
+
+            assert_eq!(
+                *owner, as_user,
+                "when creating objects creator is the owner"
+            );
+
+            access_check_create_in_schema(storage, name, as_user)?;
+
+            // Like sequences, views don't have a tarantool counterpart.
+            box_access_check_ddl_as_user(
+                name,
+                *id,
+                *owner,
+                TntSchemaObjectType::Space,
+                PrivType::Create,
+                as_user,
+            )
+        }
+        op::Ddl::DropView { id, .. } => {
+            let Some(view) = storage.views.by_id(*id)? else {
+                return Err(BoxError::new(
+                    TarantoolErrorCode::IllegalParams,
+                    format!("no such view #{id}"),
+                )
+                .into());
+            };
+
+            // Only owner or admin can drop a view.
+            if view.owner != as_user && !is_superuser(as_user) {
+                let user = user_by_id(as_user)?;
+                let (view_name, user_name) = (&view.name, &user.name);
+                #[rustfmt::skip]
+                return Err(BoxError::new(AccessDenied, format!("Drop access to view '{view_name}' is denied for user '{user_name}'")).into());
+            }
+
+            Ok(())
+        }
+        op::Ddl::CreateSchema { name, owner, .. } => {
+            assert_eq!(
+                *owner, as_user,
+                "when creating objects creator is the owner"
+            );
+
+            // Schemas don't have a tarantool counterpart, the privilege
+            // to create them is granted on the entire object type.
+            if is_superuser(as_user)
+                || has_pico_privilege(
+                    storage,
+                    PicoSchemaObjectType::Schema,
+                    PrivilegeType::Create,
+                    None,
+                    as_user,
+                )?
+            {
+                return Ok(());
+            }
+
+            let user = user_by_id(as_user)?;
+            let user_name = &user.name;
+            #[rustfmt::skip]
+            return Err(BoxError::new(AccessDenied, format!("Create access to schema '{name}' is denied for user '{user_name}'")).into());
+        }
+        op::Ddl::DropSchema { id, .. } => {
+            let Some(schema) = storage.schemas.by_id(*id)? else {
+                return Err(BoxError::new(
+                    TarantoolErrorCode::IllegalParams,
+                    format!("no such schema #{id}"),
+                )
+                .into());
+            };
+
+            // Only owner or admin can drop a schema.
+            if schema.owner != as_user && !is_superuser(as_user) {
+                let user = user_by_id(as_user)?;
+                let (schema_name, user_name) = (&schema.name, &user.name);
+                #[rustfmt::skip]
+                return Err(BoxError::new(AccessDenied, format!("Drop access to schema '{schema_name}' is denied for user '{user_name}'")).into());
+            }
+
+            Ok(())
+        }
     }
 }
 
@@ -631,6 +812,39 @@ fn access_check_grant_revoke(
                 grantor_id,
             );
         }
+        PicoSchemaObjectType::View => {
+            let Some(view) = storage.views.by_id(object_id)? else {
+                return Err(BoxError::new(
+                    TarantoolErrorCode::IllegalParams,
+                    format!("no such view #{object_id}"),
+                )
+                .into());
+            };
+
+            // Only owner or admin can grant on view.
+            // Views don't have a tarantool counterpart, so there is nothing more to check.
+            if view.owner != grantor_id && grantor_id != ADMIN_ID {
+                let view_name = &view.name;
+                #[rustfmt::skip]
+                return Err(BoxError::new(AccessDenied, format!("{access_name} to {object_type} '{view_name}' is denied for user '{user_name}'")).into());
+            }
+        }
+        PicoSchemaObjectType::Schema => {
+            let Some(schema) = storage.schemas.by_id(object_id)? else {
+                return Err(BoxError::new(
+                    TarantoolErrorCode::IllegalParams,
+                    format!("no such schema #{object_id}"),
+                )
+                .into());
+            };
+
+            // Only owner or admin can grant on schema.
+            if schema.owner != grantor_id && grantor_id != ADMIN_ID {
+                let schema_name = &schema.name;
+                #[rustfmt::skip]
+                return Err(BoxError::new(AccessDenied, format!("{access_name} to {object_type} '{schema_name}' is denied for user '{user_name}'")).into());
+            }
+        }
     }
 
     Ok(())
@@ -652,7 +866,9 @@ fn access_check_acl(storage: &Catalog, acl: &op::Acl, as_user: UserId) -> tarant
                 as_user,
             )
         }
-        op::Acl::ChangeAuth { user_id, .. } | op::Acl::RenameUser { user_id, .. } => {
+        op::Acl::ChangeAuth { user_id, .. }
+        | op::Acl::ChangeStatementTimeout { user_id, .. }
+        | op::Acl::RenameUser { user_id, .. } => {
             let sys_user = user_by_id(*user_id)?;
 
             assert_eq!(sys_user.id, *user_id, "user metadata id mismatch");
@@ -778,6 +994,179 @@ pub(crate) fn access_check_plugin_system(as_user: UserId) -> tarantool::Result<(
     Ok(())
 }
 
+/// Checks whether the user or one of the user's roles has the privilege
+/// in `_pico_privilege`. A privilege granted on the entire object type
+/// (`object_id` is `None`) counts as granted on every object of the type.
+fn has_pico_privilege(
+    storage: &Catalog,
+    object_type: PicoSchemaObjectType,
+    privilege: PrivilegeType,
+    object_id: Option<u32>,
+    as_user: UserId,
+) -> tarantool::Result<bool> {
+    let mut visited = HashSet::from([as_user]);
+    let mut queue = vec![as_user];
+    while let Some(grantee_id) = queue.pop() {
+        for granted in storage.privileges.by_grantee_id(grantee_id)? {
+            match (granted.object_type(), granted.privilege()) {
+                (t, p) if t == object_type && p == privilege => {
+                    let granted_id = granted.object_id();
+                    if granted_id.is_none() || granted_id == object_id {
+                        return Ok(true);
+                    }
+                }
+                (PicoSchemaObjectType::Role, PrivilegeType::Execute) => {
+                    if let Some(role_id) = granted.object_id() {
+                        if visited.insert(role_id) {
+                            queue.push(role_id);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// Checks that the user can read from the view. Views don't exist in
+/// tarantool, so the privileges are looked up in `_pico_privilege`
+/// including the ones granted to the user's roles.
+///
+/// Called from [`crate::sql::dispatch`] for each view referenced by a query.
+pub(crate) fn access_check_view(
+    storage: &Catalog,
+    view: &ViewDef,
+    as_user: UserId,
+) -> tarantool::Result<()> {
+    if view.owner == as_user || is_superuser(as_user) {
+        return Ok(());
+    }
+
+    if has_pico_privilege(
+        storage,
+        PicoSchemaObjectType::View,
+        PrivilegeType::Read,
+        Some(view.id),
+        as_user,
+    )? {
+        return Ok(());
+    }
+
+    let sys_user = user_by_id(as_user)?;
+    let (view_name, user_name) = (&view.name, &sys_user.name);
+    Err(BoxError::new(
+        AccessDenied,
+        format!("Read access to view '{view_name}' is denied for user '{user_name}'"),
+    )
+    .into())
+}
+
+/// Checks that the user has the privilege on the schema: `usage` to query
+/// the tables and views of the schema or `create` to create them.
+/// Like views, schemas don't exist in tarantool.
+///
+/// Called from [`crate::sql::dispatch`] for each schema referenced by a query.
+pub(crate) fn access_check_schema(
+    storage: &Catalog,
+    schema: &SchemaDef,
+    privilege: PrivilegeType,
+    as_user: UserId,
+) -> tarantool::Result<()> {
+    if schema.owner == as_user || is_superuser(as_user) {
+        return Ok(());
+    }
+
+    if has_pico_privilege(
+        storage,
+        PicoSchemaObjectType::Schema,
+        privilege,
+        Some(schema.id),
+        as_user,
+    )? {
+        return Ok(());
+    }
+
+    let sys_user = user_by_id(as_user)?;
+    let (schema_name, user_name) = (&schema.name, &sys_user.name);
+    let access_name = match privilege {
+        PrivilegeType::Usage => "Usage",
+        _ => "Create",
+    };
+    Err(BoxError::new(
+        AccessDenied,
+        format!("{access_name} access to schema '{schema_name}' is denied for user '{user_name}'"),
+    )
+    .into())
+}
+
+/// Checks that the user can gather the statistics of the table with `ANALYZE`.
+/// Like in `PostgreSQL`, only the owner of the table or a superuser can do it.
+///
+/// Called from [`crate::sql::analyze`] for each analyzed table.
+pub(crate) fn access_check_analyze(table: &TableDef, as_user: UserId) -> tarantool::Result<()> {
+    if table.owner == as_user || is_superuser(as_user) {
+        return Ok(());
+    }
+
+    let sys_user = user_by_id(as_user)?;
+    let (table_name, user_name) = (&table.name, &sys_user.name);
+    Err(BoxError::new(
+        AccessDenied,
+        format!("Analyze access to table '{table_name}' is denied for user '{user_name}'"),
+    )
+    .into())
+}
+
+/// Checks that the user can take the values of the sequence with `nextval`.
+/// The sequence of a `SERIAL` or identity column is available to those who
+/// can write to its table, other sequences only to their owners.
+///
+/// Called from [`crate::sequence::proc_sequence_nextval`].
+pub(crate) fn access_check_sequence(
+    sequence: &SequenceDef,
+    as_user: UserId,
+) -> tarantool::Result<()> {
+    if sequence.owner == as_user || is_superuser(as_user) {
+        return Ok(());
+    }
+
+    if let Some(table_id) = sequence.table_id {
+        let _su = session::su(as_user)?;
+        return box_access_check_space(table_id, PrivType::Write);
+    }
+
+    let sys_user = user_by_id(as_user)?;
+    let (sequence_name, user_name) = (&sequence.name, &sys_user.name);
+    Err(BoxError::new(
+        AccessDenied,
+        format!("Usage access to sequence '{sequence_name}' is denied for user '{user_name}'"),
+    )
+    .into())
+}
+
+/// Checks that the user can create the table or view `name` in its schema.
+/// Objects of the `public` schema need no schema privileges.
+fn access_check_create_in_schema(
+    storage: &Catalog,
+    name: &str,
+    as_user: UserId,
+) -> tarantool::Result<()> {
+    let (schema_name, _) = split_schema_name(name);
+    if schema_name == DEFAULT_SCHEMA {
+        return Ok(());
+    }
+
+    let Some(schema) = storage.schemas.by_name(schema_name)? else {
+        return Err(BoxError::new(
+            TarantoolErrorCode::IllegalParams,
+            format!("no such schema '{schema_name}'"),
+        )
+        .into());
+    };
+    access_check_schema(storage, &schema, PrivilegeType::Create, as_user)
+}
+
 // TODO: use this function everywhere we check `id != ADMIN_ID`
 #[inline(always)]
 pub fn is_superuser(user_id: UserId) -> bool {
@@ -849,6 +1238,8 @@ mod tests {
             auth: Some(dummy_auth_def()),
             owner: owner.unwrap_or_else(|| session::uid().unwrap()),
             ty: UserMetadataKind::User,
+            scram_verifier: None,
+            statement_timeout: None,
         }
     }
 
@@ -941,6 +1332,10 @@ mod tests {
                 distribution: Distribution::Global,
                 engine: SpaceEngineType::Blackhole,
                 owner: user_id,
+                defaults: vec![],
+                partition: None,
+                constraints: vec![],
+                sequences: vec![],
             };
 
             let e = access_check_ddl(&storage, &space_to_be_created, user_id).unwrap_err();
@@ -1253,6 +1648,7 @@ mod tests {
                     auth: dummy_auth_def(),
                     initiator: actor_user_id,
                     schema_version: 0,
+                    scram_verifier: None,
                 },
                 actor_user_id,
             )
@@ -1279,6 +1675,7 @@ mod tests {
                     auth: dummy_auth_def(),
                     initiator: actor_user_id,
                     schema_version: 0,
+                    scram_verifier: None,
                 },
                 actor_user_id,
             )
@@ -1348,6 +1745,7 @@ mod tests {
                     auth: dummy_auth_def(),
                     initiator: actor_user_id,
                     schema_version: 0,
+                    scram_verifier: None,
                 },
                 actor_user_id,
             )
@@ -1374,6 +1772,7 @@ mod tests {
                     auth: dummy_auth_def(),
                     initiator: actor_user_id,
                     schema_version: 0,
+                    scram_verifier: None,
                 },
                 actor_user_id,
             )
@@ -1525,6 +1924,7 @@ mod tests {
                     auth: dummy_auth_def(),
                     initiator,
                     schema_version: 0,
+                    scram_verifier: None,
                 })
             };
 
@@ -1577,6 +1977,8 @@ mod tests {
             owner: ADMIN_ID,
             auth: None,
             ty: UserMetadataKind::Role,
+            scram_verifier: None,
+            statement_timeout: None,
         };
         on_master_create_role(&role_def).expect("create role shouldnt fail");
 
@@ -1589,6 +1991,8 @@ mod tests {
                 owner: user_id,
                 auth: None,
                 ty: UserMetadataKind::Role,
+                scram_verifier: None,
+                statement_timeout: None,
             };
 
             let e = access_check_acl(
@@ -1725,6 +2129,8 @@ mod tests {
                 owner: user_id,
                 auth: None,
                 ty: UserMetadataKind::Role,
+                scram_verifier: None,
+                statement_timeout: None,
             };
             on_master_create_role(&role_def).expect("create role shouldn't fail");
 
@@ -1775,6 +2181,8 @@ mod tests {
                 owner: ADMIN_ID,
                 auth: None,
                 ty: UserMetadataKind::Role,
+                scram_verifier: None,
+                statement_timeout: None,
             };
 
             on_master_create_role(&role_def).expect("create role shouldn't fail");
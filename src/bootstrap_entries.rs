@@ -124,7 +124,10 @@ pub(super) fn prepare(
         .expect("serialization cannot fail"),
         op::Dml::insert(
             storage::Properties::TABLE_ID,
-            &(PropertyName::SystemCatalogVersion, "25.1.0".to_owned()),
+            &(
+                PropertyName::SystemCatalogVersion,
+                schema::SYSTEM_CATALOG_VERSION.to_owned(),
+            ),
             ADMIN_ID,
         )
         .expect("serialization cannot fail"),
@@ -252,6 +255,7 @@ pub(super) fn prepare(
             auth,
             initiator: ADMIN_ID,
             schema_version,
+            scram_verifier: None,
         });
 
         let context = traft::EntryContext::Op(op_elem);
@@ -277,6 +281,7 @@ pub(super) fn prepare(
         ),
         initiator: ADMIN_ID,
         schema_version,
+        scram_verifier: None,
     });
 
     let context = traft::EntryContext::Op(op_elem);
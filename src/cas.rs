@@ -1120,6 +1120,13 @@ fn modifies_operable(op: &Op, space: SpaceId, storage: &Catalog) -> bool {
         Ddl::CreateProcedure { .. } => false,
         Ddl::DropProcedure { .. } => false,
         Ddl::RenameProcedure { .. } => false,
+        Ddl::CreateSequence { .. } => false,
+        Ddl::DropSequence { .. } => false,
+        Ddl::CreateView { .. } => false,
+        Ddl::DropView { .. } => false,
+        Ddl::CreateSchema { .. } => false,
+        Ddl::DropSchema { .. } => false,
+        Ddl::DetachPartition { .. } => false,
     };
     match op {
         Op::DdlPrepare { ddl, .. } => ddl_modifies(ddl),
@@ -1175,6 +1182,10 @@ mod tests {
             distribution: Distribution::Global,
             engine: SpaceEngineType::Memtx,
             owner: ADMIN_ID,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
+            sequences: vec![],
         });
         let drop_space = builder.with_op(Ddl::DropTable {
             id: space_id,
@@ -1419,6 +1419,14 @@ pub struct MemtxSection {
     /// Corresponds to `box.cfg.memtx_max_tuple_size`.
     #[introspection(config_default = "1M")]
     pub max_tuple_size: Option<ByteSize>,
+
+    /// Whether the transactional manager (MVCC) is enabled for memtx.
+    /// It is required by interactive SQL transactions, which may yield
+    /// between statements. `BEGIN` fails when it is disabled.
+    ///
+    /// Corresponds to `box.cfg.memtx_use_mvcc_engine`.
+    #[introspection(config_default = false)]
+    pub use_mvcc_engine: Option<bool>,
 }
 
 tarantool::define_str_enum! {
@@ -1669,6 +1677,30 @@ pub struct AlterSystemParameters {
     #[introspection(config_default = 5000)]
     pub sql_motion_row_max: u64,
 
+    /// Maximum number of rows a recursive CTE can produce, the query
+    /// fails when it is exceeded. Zero means no limit.
+    ///
+    /// Can be overridden in the query with `OPTION (sql_recursion_row_max = ..)`.
+    #[introspection(sbroad_type = SbroadType::Unsigned)]
+    #[introspection(config_default = 5000)]
+    pub sql_recursion_row_max: u64,
+
+    /// Maximum execution time of an SQL statement in seconds, after which
+    /// the statement is cancelled. Zero disables the timeout.
+    ///
+    /// Can be overridden in the session with `SET statement_timeout`.
+    #[introspection(sbroad_type = SbroadType::Double)]
+    #[introspection(config_default = 0.0)]
+    pub sql_statement_timeout: f64,
+
+    /// Maximum number of SQL statements a single user can execute
+    /// concurrently on a router. Zero means no limit.
+    ///
+    /// The limit doesn't apply to admin.
+    #[introspection(sbroad_type = SbroadType::Unsigned)]
+    #[introspection(config_default = 0)]
+    pub sql_user_query_max: u64,
+
     /// Tarantool statement cache size capacity in bytes.
     ///
     /// Corresponds to `box.cfg.sql_cache_size`
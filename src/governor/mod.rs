@@ -27,7 +27,7 @@ use crate::rpc::replication::proc_replication_sync;
 use crate::rpc::sharding::bootstrap::proc_sharding_bootstrap;
 use crate::rpc::sharding::proc_sharding;
 use crate::rpc::sharding::proc_wait_bucket_count;
-use crate::schema::ADMIN_ID;
+use crate::schema::{ADMIN_ID, SYSTEM_CATALOG_VERSION};
 use crate::storage;
 use crate::storage::Catalog;
 use crate::storage::SystemTable;
@@ -170,6 +170,10 @@ impl Loop {
             .properties
             .cluster_version()
             .expect("storage should never fail");
+        let system_catalog_version = storage
+            .properties
+            .system_catalog_version()
+            .expect("storage should never fail");
 
         let plan = action_plan(
             term,
@@ -191,6 +195,7 @@ impl Loop {
             plugin_op.as_ref(),
             rpc_timeout,
             global_cluster_version,
+            system_catalog_version,
         );
         let plan = unwrap_ok_or!(plan,
             Err(e) => {
@@ -283,6 +288,19 @@ impl Loop {
                 }
             }
 
+            Plan::UpgradeSystemCatalog(UpgradeSystemCatalog { cas }) => {
+                set_status!("upgrade system catalog");
+                governor_step! {
+                    "upgrading system catalog" [
+                        "version" => SYSTEM_CATALOG_VERSION,
+                    ]
+                    async {
+                        let deadline = fiber::clock().saturating_add(raft_op_timeout);
+                        cas::compare_and_swap_local(&cas, deadline)?.no_retries()?;
+                    }
+                }
+            }
+
             Plan::ReplicasetMasterFailover(ReplicasetMasterFailover {
                 old_master_name,
                 new_master_name,
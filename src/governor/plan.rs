@@ -15,7 +15,8 @@ use crate::rpc;
 use crate::rpc::update_instance::prepare_update_instance_cas_request;
 use crate::schema::TableDef;
 use crate::schema::{
-    PluginConfigRecord, PluginDef, ServiceDef, ServiceRouteItem, ServiceRouteKey, ADMIN_ID,
+    system_catalog_upgrade_ops, PluginConfigRecord, PluginDef, ServiceDef, ServiceRouteItem,
+    ServiceRouteKey, ADMIN_ID, SYSTEM_CATALOG_VERSION,
 };
 use crate::storage;
 use crate::storage::PropertyName;
@@ -61,6 +62,7 @@ pub(super) fn action_plan<'i>(
     plugin_op: Option<&'i PluginOp>,
     sync_timeout: std::time::Duration,
     global_cluster_version: String,
+    system_catalog_version: Option<String>,
 ) -> Result<Plan<'i>> {
     // This function is specifically extracted, to separate the task
     // construction from any IO and/or other yielding operations.
@@ -988,6 +990,27 @@ pub(super) fn action_plan<'i>(
         }
     }
 
+    ////////////////////////////////////////////////////////////////////////////
+    // upgrade system catalog
+    // The new definitions may only be used by the instances of the new version.
+    let catalog_is_outdated = system_catalog_version.as_deref() != Some(SYSTEM_CATALOG_VERSION);
+    if catalog_is_outdated
+        && matches!(
+            rpc::join::compare_picodata_versions(SYSTEM_CATALOG_VERSION, &global_cluster_version),
+            Ok(0)
+        )
+    {
+        let ops = system_catalog_upgrade_ops(tables)?;
+        let mut ranges = Vec::with_capacity(ops.len());
+        for op in &ops {
+            ranges.push(cas::Range::for_dml(op)?);
+        }
+        let predicate = cas::Predicate::new(applied, ranges);
+        let cas = cas::Request::new(Op::BatchDml { ops }, predicate, ADMIN_ID)?;
+
+        return Ok(UpgradeSystemCatalog { cas }.into());
+    }
+
     ////////////////////////////////////////////////////////////////////////////
     // no action needed
     Ok(Plan::None)
@@ -1066,6 +1089,12 @@ pub mod stage {
             pub cas: cas::Request,
         }
 
+        pub struct UpgradeSystemCatalog {
+            /// Global DML operations in _pico_table, _pico_index and
+            /// _pico_property to upgrade the system catalog
+            pub cas: cas::Request,
+        }
+
         pub struct ReplicasetMasterFailover<'i> {
             /// This replicaset is changing it's master.
             pub replicaset_name: &'i ReplicasetName,
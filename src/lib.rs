@@ -17,7 +17,7 @@ use config::apply_parameter;
 use info::PICODATA_VERSION;
 use regex::Regex;
 use sbroad::frontend::sql::transform_to_regex_pattern;
-use sbroad::frontend::sql::NAMES_OF_FUNCTIONS_IN_SOURCES;
+use sbroad::frontend::sql::{NAMES_OF_FUNCTIONS_IN_SOURCES, RECURSION_ROW_MAX_EXCEEDED_FUNCTION};
 use serde::{Deserialize, Serialize};
 use storage::ToEntryIter;
 
@@ -76,7 +76,9 @@ pub mod reachability;
 pub mod replicaset;
 pub mod rpc;
 pub mod schema;
+pub mod scram;
 pub mod sentinel;
+pub mod sequence;
 pub mod sql;
 pub mod storage;
 pub mod sync;
@@ -405,7 +407,9 @@ fn init_handlers() {
     let lua = ::tarantool::lua_state();
     for proc in ::tarantool::proc::all_procs().iter() {
         let proc_name = proc.name();
-        if NAMES_OF_FUNCTIONS_IN_SOURCES.contains(&proc_name) {
+        if NAMES_OF_FUNCTIONS_IN_SOURCES.contains(&proc_name)
+            || proc_name == RECURSION_ROW_MAX_EXCEEDED_FUNCTION
+        {
             lua.exec_with(
                 "local name, is_public = ...
                 local proc_name = '.' .. name
@@ -449,6 +453,7 @@ fn init_handlers() {
 
     forbid_unsupported_iproto_requests();
     redirect_iproto_execute_requests();
+    sql::transaction::set_on_disconnect_trigger();
 }
 
 /// Sets interactive prompt to display `picodata>`.
@@ -479,100 +484,167 @@ fn redirect_interactive_sql() {
     .expect("overriding sql executor shouldn't fail")
 }
 
-/// Sets a check that will be performed when a user is logging in
-/// Checks for user exceeding maximum number of login attempts and if user was blocked.
-///
-/// Also see [`config::AlterSystemParameters::auth_login_attempt_max`].
-fn set_login_check(storage: Catalog) {
-    const MAX_ATTEMPTS_EXCEEDED: &str = "Maximum number of login attempts exceeded";
-    const NO_LOGIN_PRIVILEGE: &str = "User does not have login privilege";
-
-    enum Verdict {
-        AuthOk,
-        AuthFail,
-        UnknownUser,
-        UserBlocked(&'static str),
+const MAX_ATTEMPTS_EXCEEDED: &str = "Maximum number of login attempts exceeded";
+const NO_LOGIN_PRIVILEGE: &str = "User does not have login privilege";
+
+enum AuthVerdict {
+    AuthOk,
+    AuthFail,
+    UnknownUser,
+    UserBlocked(&'static str),
+}
+
+/// Determines the outcome of an authentication attempt.
+fn compute_auth_verdict(
+    storage: &Catalog,
+    user_name: String,
+    successful_authentication: bool,
+) -> AuthVerdict {
+    use std::collections::hash_map::Entry;
+
+    // If the user is pico service (used for internal communication) we don't perform any additional checks.
+    // Map result to print audit message, tarantool handles auth automatically.
+    //
+    // The reason for not performaing checks is twofold:
+    // 1. We might not have the user or required privileges in _pico_* spaces yet.
+    // 2. We should never block pico service user or instances would loose ability to communicate
+    // with each other.
+    if user_name == PICO_SERVICE_USER_NAME {
+        if successful_authentication {
+            return AuthVerdict::AuthOk;
+        } else {
+            return AuthVerdict::AuthFail;
+        }
     }
 
-    // Determines the outcome of an authentication attempt.
-    let compute_auth_verdict = move |user_name: String, successful_authentication: bool| {
-        use std::collections::hash_map::Entry;
-
-        // If the user is pico service (used for internal communication) we don't perform any additional checks.
-        // Map result to print audit message, tarantool handles auth automatically.
-        //
-        // The reason for not performaing checks is twofold:
-        // 1. We might not have the user or required privileges in _pico_* spaces yet.
-        // 2. We should never block pico service user or instances would loose ability to communicate
-        // with each other.
-        if user_name == PICO_SERVICE_USER_NAME {
+    // Switch to admin to access system spaces.
+    let admin_guard = session::su(ADMIN_ID).expect("switching to admin should not fail");
+    let Some(user) = storage
+        .users
+        .by_name(&user_name)
+        .expect("accessing storage should not fail")
+    else {
+        // Prevent DOS attacks by first checking whether the user exists.
+        // If it doesn't, we shouldn't even bother tracking its attempts.
+        // Too many hashmap records will cause a global OOM event.
+        debug_assert!(!successful_authentication);
+        return AuthVerdict::UnknownUser;
+    };
+    let max_login_attempts = storage
+        .db_config
+        .auth_login_attempt_max()
+        .expect("accessing storage should not fail");
+    if storage
+        .privileges
+        .get(user.id, "universe", 0, "login")
+        .expect("storage should not fail")
+        .is_none()
+    {
+        // User does not have login privilege so should not be allowed to connect.
+        return AuthVerdict::UserBlocked(NO_LOGIN_PRIVILEGE);
+    }
+    drop(admin_guard);
+
+    // Borrowing will not panic as there are no yields while it's borrowed
+    let mut attempts = storage.login_attempts.borrow_mut();
+    match attempts.entry(user_name) {
+        Entry::Occupied(e) if *e.get() >= max_login_attempts => {
+            // The account is suspended until instance is restarted
+            // or user is unlocked with `grant session` operation.
+            //
+            // See [`crate::storage::global_grant_privilege`]
+            AuthVerdict::UserBlocked(MAX_ATTEMPTS_EXCEEDED)
+        }
+        Entry::Occupied(mut e) => {
             if successful_authentication {
-                return Verdict::AuthOk;
+                // Forget about previous failures
+                e.remove();
+                AuthVerdict::AuthOk
             } else {
-                return Verdict::AuthFail;
+                *e.get_mut() += 1;
+                AuthVerdict::AuthFail
             }
         }
-
-        // Switch to admin to access system spaces.
-        let admin_guard = session::su(ADMIN_ID).expect("switching to admin should not fail");
-        let Some(user) = storage
-            .users
-            .by_name(&user_name)
-            .expect("accessing storage should not fail")
-        else {
-            // Prevent DOS attacks by first checking whether the user exists.
-            // If it doesn't, we shouldn't even bother tracking its attempts.
-            // Too many hashmap records will cause a global OOM event.
-            debug_assert!(!successful_authentication);
-            return Verdict::UnknownUser;
-        };
-        let max_login_attempts = storage
-            .db_config
-            .auth_login_attempt_max()
-            .expect("accessing storage should not fail");
-        if storage
-            .privileges
-            .get(user.id, "universe", 0, "login")
-            .expect("storage should not fail")
-            .is_none()
-        {
-            // User does not have login privilege so should not be allowed to connect.
-            return Verdict::UserBlocked(NO_LOGIN_PRIVILEGE);
-        }
-        drop(admin_guard);
-
-        // Borrowing will not panic as there are no yields while it's borrowed
-        let mut attempts = storage.login_attempts.borrow_mut();
-        match attempts.entry(user_name) {
-            Entry::Occupied(e) if *e.get() >= max_login_attempts => {
-                // The account is suspended until instance is restarted
-                // or user is unlocked with `grant session` operation.
-                //
-                // See [`crate::storage::global_grant_privilege`]
-                Verdict::UserBlocked(MAX_ATTEMPTS_EXCEEDED)
-            }
-            Entry::Occupied(mut e) => {
-                if successful_authentication {
-                    // Forget about previous failures
-                    e.remove();
-                    Verdict::AuthOk
-                } else {
-                    *e.get_mut() += 1;
-                    Verdict::AuthFail
-                }
+        Entry::Vacant(e) => {
+            if successful_authentication {
+                AuthVerdict::AuthOk
+            } else {
+                // Remember the failure, but don't raise an error yet
+                e.insert(1);
+                AuthVerdict::AuthFail
             }
-            Entry::Vacant(e) => {
-                if successful_authentication {
-                    Verdict::AuthOk
-                } else {
-                    // Remember the failure, but don't raise an error yet
-                    e.insert(1);
-                    Verdict::AuthFail
-                }
+        }
+    }
+}
+
+/// Checks for user exceeding maximum number of login attempts and if user was blocked,
+/// writes the outcome of the authentication attempt to the audit log.
+/// Returns an error if the user must be disconnected even if the authentication
+/// was successful.
+///
+/// Is called by tarantool's `on_auth` trigger, and directly for the
+/// authentication performed by Picodata itself (SCRAM in pgproto).
+pub(crate) fn on_auth(
+    storage: &Catalog,
+    user: &str,
+    successful_authentication: bool,
+) -> Result<(), &'static str> {
+    match compute_auth_verdict(storage, user.to_owned(), successful_authentication) {
+        AuthVerdict::AuthOk => {
+            // We don't want to spam admins with
+            // unneeded info about internal user
+            if user == PICO_SERVICE_USER_NAME {
+                return Ok(());
             }
+
+            crate::audit!(
+                message: "successfully authenticated user `{user}`",
+                title: "auth_ok",
+                severity: High,
+                user: user,
+                initiator: user,
+                verdict: "user is not blocked",
+            );
         }
-    };
+        AuthVerdict::AuthFail => {
+            crate::audit!(
+                message: "failed to authenticate user `{user}`",
+                title: "auth_fail",
+                severity: High,
+                user: user,
+                initiator: user,
+                verdict: "user is not blocked",
+            );
+        }
+        AuthVerdict::UnknownUser => {
+            crate::audit!(
+                message: "failed to authenticate unknown user `{user}`",
+                title: "auth_fail",
+                severity: High,
+                user: user,
+                initiator: user,
+                verdict: "user is not blocked",
+            );
+        }
+        AuthVerdict::UserBlocked(err) => {
+            crate::audit!(
+                message: "failed to authenticate user `{user}`",
+                title: "auth_fail",
+                severity: High,
+                user: user,
+                initiator: user,
+                verdict: format_args!("{err}; user blocked"),
+            );
+            return Err(err);
+        }
+    }
+    Ok(())
+}
 
+/// Sets a check that will be performed when a user is logging in, see [`on_auth`].
+///
+/// Also see [`config::AlterSystemParameters::auth_login_attempt_max`].
+fn set_login_check(storage: Catalog) {
     let lua = ::tarantool::lua_state();
     lua.exec_with(
         "
@@ -585,60 +657,13 @@ fn set_login_check(storage: Catalog) {
 
         box.session.on_auth(on_auth)",
         tlua::function3(move |user: String, status: bool, lua: tlua::LuaState| {
-            match compute_auth_verdict(user.clone(), status) {
-                Verdict::AuthOk => {
-                    // We don't want to spam admins with
-                    // unneeded info about internal user
-                    if user == PICO_SERVICE_USER_NAME {
-                        return;
-                    }
-
-                    crate::audit!(
-                        message: "successfully authenticated user `{user}`",
-                        title: "auth_ok",
-                        severity: High,
-                        user: &user,
-                        initiator: &user,
-                        verdict: "user is not blocked",
-                    );
-                }
-                Verdict::AuthFail => {
-                    crate::audit!(
-                        message: "failed to authenticate user `{user}`",
-                        title: "auth_fail",
-                        severity: High,
-                        user: &user,
-                        initiator: &user,
-                        verdict: "user is not blocked",
-                    );
-                }
-                Verdict::UnknownUser => {
-                    crate::audit!(
-                        message: "failed to authenticate unknown user `{user}`",
-                        title: "auth_fail",
-                        severity: High,
-                        user: &user,
-                        initiator: &user,
-                        verdict: "user is not blocked",
-                    );
-                }
-                Verdict::UserBlocked(err) => {
-                    crate::audit!(
-                        message: "failed to authenticate user `{user}`",
-                        title: "auth_fail",
-                        severity: High,
-                        user: &user,
-                        initiator: &user,
-                        verdict: format_args!("{err}; user blocked"),
-                    );
-
-                    // Raises an error instead of returning it as a function result.
-                    // This is the behavior required by `on_auth` trigger to drop the connection
-                    // even if auth was successful. If auth failed the connection will be dropped automatically.
-                    //
-                    // All the drop implementations are called, no need to clean anything up.
-                    tlua::error!(lua, "{}", err);
-                }
+            if let Err(err) = on_auth(&storage, &user, status) {
+                // Raises an error instead of returning it as a function result.
+                // This is the behavior required by `on_auth` trigger to drop the connection
+                // even if auth was successful. If auth failed the connection will be dropped automatically.
+                //
+                // All the drop implementations are called, no need to clean anything up.
+                tlua::error!(lua, "{}", err);
             }
         }),
     )
@@ -540,6 +540,75 @@ pub(crate) fn setup() {
         "#},
     );
 
+    luamod_set(
+        &l,
+        "cancel_query",
+        indoc! {"
+        pico.cancel_query(request_id)
+        =============================
+
+        Cancels an SQL query on every instance of the cluster.
+
+        The router stops waiting for the query results, storages reject
+        the rest of the query fragments. A fragment which is already
+        executed by a storage is not interrupted.
+
+        Params:
+
+            1. request_id (string), see pico.sql_queries()
+
+        Returns:
+
+            (boolean) true if the query was running on some router
+            or
+            (nil, string) in case of an error
+        "},
+        tlua::function1(|request_id: String| -> traft::Result<bool> {
+            crate::sql::cancel::cancel_query(&request_id.into())
+        }),
+    );
+    luamod_set(
+        &l,
+        "sql_queries",
+        indoc! {"
+        pico.sql_queries()
+        ==================
+
+        Returns SQL queries dispatched by the current instance.
+
+        Returns:
+
+            (table) array of queries with the following fields:
+                - request_id (string), use it in pico.cancel_query()
+                - session_kind (string), 'iproto' or 'pgproto'
+                - session_id (number)
+                - user_id (number)
+                - duration (number), in seconds
+                - cancelled (boolean)
+        "},
+        tlua::function0(crate::sql::cancel::running_queries),
+    );
+    luamod_set(
+        &l,
+        "_check_query_cancelled",
+        indoc! {"
+        pico._check_query_cancelled()
+
+        Internal API, see src/sql/cancel.rs for the details.
+
+        Returns:
+
+            (nil) if the query of the current fiber is not cancelled
+            or
+            (string) error message otherwise
+        "},
+        tlua::function0(|| -> Option<String> {
+            crate::sql::cancel::check_cancelled()
+                .err()
+                .map(|e| e.to_string())
+        }),
+    );
+
     // vclock
     ///////////////////////////////////////////////////////////////////////////
     luamod_set_help_only(
@@ -112,7 +112,9 @@ fn do_handle_client(
     tls_acceptor: Option<TlsAcceptor>,
     storage: &Catalog,
 ) -> PgResult<()> {
-    let mut client = PgClient::accept(stream, tls_acceptor, storage)?;
+    let Some(mut client) = PgClient::accept(stream, tls_acceptor, storage)? else {
+        return Ok(());
+    };
 
     // Send important parameters to the client.
     client
@@ -120,6 +122,7 @@ fn do_handle_client(
         .send_parameter("server_encoding", "UTF8")?
         .send_parameter("client_encoding", "UTF8")?
         .send_parameter("DateStyle", "ISO, MDY")?
+        .send_parameter("IntervalStyle", "postgres")?
         .send_parameter("integer_datetimes", "on")?
         .send_parameter("TimeZone", "UTC")?;
 
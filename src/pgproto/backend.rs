@@ -1,6 +1,6 @@
 use self::{
     describe::{PortalDescribe, QueryType, StatementDescribe},
-    result::ExecuteResult,
+    result::{ExecuteResult, Rows},
     storage::{Portal, Statement, PG_PORTALS, PG_STATEMENTS},
 };
 use super::{
@@ -12,22 +12,23 @@ use crate::{
     pgproto::value::{FieldFormat, RawFormat},
     schema::ADMIN_ID,
     sql::router::RouterRuntime,
+    sql::transaction::{self, SessionKey, TxnStatus},
 };
 use crate::{tlog, traft::error::Error};
 use bytes::Bytes;
+use pgwire::messages::response::TransactionStatus;
 use postgres_types::Oid;
-use sbroad::ir::{value::Value as SbroadValue, OptionKind};
+use sbroad::ir::{value::Value as SbroadValue, OptionKind, OptionParamValue};
 use sbroad::{errors::SbroadError, ir::OptionSpec};
 use sbroad::{
     executor::{
-        engine::{QueryCache, Router, TableVersionMap},
+        engine::{helpers::plan_cache_text, Metadata, QueryCache, Router, TableVersionMap},
         lru::Cache,
     },
     frontend::Ast,
     ir::{value::Value, Plan as IrPlan},
     utils::MutexLike,
 };
-use smol_str::ToSmolStr;
 use std::{
     iter::zip,
     sync::atomic::{AtomicU64, Ordering},
@@ -37,6 +38,7 @@ use tarantool::session::with_su;
 mod pgproc;
 mod well_known_queries;
 
+pub mod copy;
 pub mod describe;
 pub mod result;
 pub mod storage;
@@ -91,11 +93,12 @@ fn apply_default_options(
     default_options: &[OptionSpec],
 ) -> Vec<OptionSpec> {
     // First, set query options, as they have higher priority.
-    let (mut max_steps, mut max_rows) = (None, None);
+    let (mut max_steps, mut max_rows, mut max_recursion_rows) = (None, None, None);
     for opt in query_options {
         match opt.kind {
             OptionKind::VdbeOpcodeMax => max_steps = Some(opt),
             OptionKind::MotionRowMax => max_rows = Some(opt),
+            OptionKind::RecursionRowMax => max_recursion_rows = Some(opt),
         }
     }
 
@@ -104,12 +107,15 @@ fn apply_default_options(
         match opt.kind {
             OptionKind::VdbeOpcodeMax if max_steps.is_none() => max_steps = Some(opt),
             OptionKind::MotionRowMax if max_rows.is_none() => max_rows = Some(opt),
+            OptionKind::RecursionRowMax if max_recursion_rows.is_none() => {
+                max_recursion_rows = Some(opt)
+            }
             _ => {}
         }
     }
 
     // Keep only Some variants.
-    [max_steps, max_rows]
+    [max_steps, max_rows, max_recursion_rows]
         .into_iter()
         .filter_map(|x| x.cloned())
         .collect()
@@ -121,7 +127,7 @@ pub fn bind(
     portal_name: String,
     params: Vec<Value>,
     result_format: Vec<FieldFormat>,
-    default_options: Vec<OptionSpec>,
+    mut default_options: Vec<OptionSpec>,
 ) -> PgResult<()> {
     let key = storage::Key(id, stmt_name.into());
     let statement: Statement = PG_STATEMENTS
@@ -130,12 +136,25 @@ pub fn bind(
 
     let mut plan = statement.plan().clone();
     let is_dql = matches!(statement.describe().query_type(), QueryType::Dql);
-    if is_dql && !default_options.is_empty() {
+    if is_dql {
+        // Recursion limit has no connection parameter, take the system
+        // default just like `sql_dispatch` does for iproto queries.
+        let sql_recursion_row_max = crate::traft::node::global()?
+            .storage
+            .db_config
+            .sql_recursion_row_max()?;
+        default_options.push(OptionSpec {
+            kind: OptionKind::RecursionRowMax,
+            val: OptionParamValue::Value {
+                val: SbroadValue::Unsigned(sql_recursion_row_max),
+            },
+        });
         plan.raw_options = apply_default_options(&plan.raw_options, &default_options);
     }
 
-    if !plan.is_empty()
-        && !plan.is_ddl()?
+    if !plan.is_empty() && plan.is_ddl()? {
+        plan.bind_ddl_params(params)?;
+    } else if !plan.is_empty()
         && !plan.is_acl()?
         && !plan.is_plugin()?
         && !plan.is_deallocate()?
@@ -144,6 +163,8 @@ pub fn bind(
     {
         plan.bind_params(params)?;
         plan.apply_options()?;
+        let runtime = RouterRuntime::new().map_err(Error::from)?;
+        plan.set_statistics(&runtime)?;
         plan.optimize()?;
     }
 
@@ -166,11 +187,13 @@ pub fn execute(id: ClientId, name: String, max_rows: i64) -> PgResult<ExecuteRes
 
 pub fn parse(id: ClientId, name: String, query: &str, param_oids: Vec<Oid>) -> PgResult<()> {
     let runtime = RouterRuntime::new().map_err(Error::from)?;
+    let search_path = runtime.metadata().lock().search_path();
+    let cache_key = plan_cache_text(query, &search_path);
     let mut cache = runtime.cache().lock();
 
     let key = storage::Key(id, name.into());
 
-    let cache_entry = with_su(ADMIN_ID, || cache.get(&query.to_smolstr()))??;
+    let cache_entry = with_su(ADMIN_ID, || cache.get(&cache_key))??;
     if let Some(plan) = cache_entry {
         let statement = Statement::new(key.clone(), plan.clone(), param_oids)?;
         PG_STATEMENTS.with(|storage| storage.borrow_mut().put(key, statement.into()))?;
@@ -194,7 +217,7 @@ pub fn parse(id: ClientId, name: String, query: &str, param_oids: Vec<Oid>) -> P
     .map_err(PgError::other)??;
 
     if !plan.is_empty() && !plan.is_tcl()? && !plan.is_ddl()? && !plan.is_acl()? {
-        cache.put(query.into(), plan.clone())?;
+        cache.put(cache_key, plan.clone())?;
     }
 
     let statement = Statement::new(key.clone(), plan, param_oids)?;
@@ -268,9 +291,22 @@ impl Backend {
             ID_COUNTER.fetch_add(1, Ordering::Relaxed)
         }
 
-        Self {
-            client_id: unique_id(),
-            params,
+        let client_id = unique_id();
+        transaction::register_pgproto_client(client_id);
+
+        Self { client_id, params }
+    }
+
+    pub fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+
+    /// Transaction status reported to the client in ReadyForQuery message.
+    pub fn transaction_status(&self) -> TransactionStatus {
+        match transaction::txn_status(SessionKey::Pgproto(self.client_id)) {
+            TxnStatus::Idle => TransactionStatus::Idle,
+            TxnStatus::InProgress => TransactionStatus::Transaction,
+            TxnStatus::Failed => TransactionStatus::Error,
         }
     }
 
@@ -321,6 +357,38 @@ impl Backend {
         result
     }
 
+    /// Execute the query of a `COPY TO` command and return the rows to be copied.
+    ///
+    /// Like `simple_query`, it uses unnamed statement and portal, but the rows are encoded
+    /// in the format requested by the command. The result is not streamed: it is
+    /// materialized on the router, so it is limited by `sql_motion_row_max` like the
+    /// result of any other query.
+    pub fn copy_out(&self, sql: &str, format: FieldFormat) -> PgResult<Rows> {
+        let close_unnamed = || {
+            self.close_statement(None);
+            self.close_portal(None);
+        };
+
+        let do_copy_out = || {
+            close_unnamed();
+            self.parse(None, sql, vec![])?;
+            if !matches!(self.describe_statement(None)?.query_type(), QueryType::Dql) {
+                return Err(PgError::FeatureNotSupported(
+                    "COPY TO is only supported for SELECT queries".into(),
+                ));
+            }
+            self.bind(None, None, vec![], &[], &[format as RawFormat])?;
+            match self.execute(None, -1)? {
+                ExecuteResult::FinishedDql { rows, .. } => Ok(rows),
+                _ => Err(PgError::InternalError(
+                    "unexpected result of COPY TO query".into(),
+                )),
+            }
+        };
+
+        do_copy_out().inspect_err(|_| close_unnamed())
+    }
+
     /// This function is similar to `simple_query`, but the query can be parameterized.
     fn execute_query(&self, sql: &str, params: Vec<SbroadValue>) -> PgResult<ExecuteResult> {
         let close_unnamed = || {
@@ -438,6 +506,7 @@ impl Backend {
     fn on_disconnect(&self) {
         close_client_statements(self.client_id);
         close_client_portals(self.client_id);
+        transaction::unregister_pgproto_client(self.client_id);
     }
 }
 
@@ -0,0 +1,1095 @@
+//! Support for the `COPY` command.
+//!
+//! Sbroad knows nothing about `COPY`, so the statement is recognized here and
+//! executed with the ordinary queries: `COPY FROM STDIN` inserts batches of the
+//! decoded rows with an `INSERT ... VALUES` plan, and `COPY TO STDOUT` reads the
+//! rows of a `SELECT` query.
+
+use super::storage::{get_row_count_from_tuple, sbroad_type_to_pg};
+use crate::{
+    pgproto::{
+        error::{DecodingError, PgError, PgErrorCode, PgResult},
+        value::{FieldFormat, PgValue},
+    },
+    schema::ADMIN_ID,
+    sql::{
+        dispatch,
+        router::RouterRuntime,
+        transaction::{self, SessionKey},
+    },
+    traft::{error::Error, node},
+};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use pgwire::messages::data::DataRow;
+use postgres_types::Oid;
+use sbroad::{
+    executor::{
+        engine::{
+            helpers::{normalize_name_from_sql, to_user},
+            Metadata, Router,
+        },
+        ir::ExecutionPlan,
+        vtable::VTableTuple,
+        Query,
+    },
+    frontend::sql::schema::resolve_relation_name,
+    ir::{relation::ColumnRole, Options, Plan},
+    utils::MutexLike,
+};
+use sqlparser::{
+    ast::{
+        CopyLegacyCsvOption, CopyLegacyOption, CopyOption, CopySource, CopyTarget, ObjectName,
+        Statement,
+    },
+    dialect::PostgreSqlDialect,
+    parser::Parser,
+};
+use std::{collections::HashMap, iter::zip};
+use tarantool::session::with_su;
+
+/// Signature of the binary `COPY` format.
+/// See <https://www.postgresql.org/docs/current/sql-copy.html#SQL-COPY-BINARY-FORMAT>.
+const BINARY_SIGNATURE: &[u8] = b"PGCOPY\n\xff\r\n\0";
+
+/// Line that marks the end of data in text and csv formats.
+const END_OF_DATA: &[u8] = b"\\.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    Text,
+    Csv,
+    Binary,
+}
+
+/// Options of the `COPY` command.
+/// See <https://www.postgresql.org/docs/current/sql-copy.html#id-1.9.3.55.9>.
+#[derive(Debug, Clone)]
+pub struct CopyOptions {
+    format: CopyFormat,
+    delimiter: u8,
+    null: String,
+    header: bool,
+    quote: u8,
+    escape: u8,
+}
+
+fn single_byte(c: char, option: &str) -> PgResult<u8> {
+    u8::try_from(c)
+        .ok()
+        .filter(u8::is_ascii)
+        .ok_or_else(|| PgError::other(format!("COPY {option} must be a single one-byte character")))
+}
+
+impl CopyOptions {
+    fn from_ast(options: &[CopyOption], legacy_options: &[CopyLegacyOption]) -> PgResult<Self> {
+        let mut format = CopyFormat::Text;
+        let (mut delimiter, mut null, mut quote, mut escape) = (None, None, None, None);
+        let mut header = false;
+
+        for option in options {
+            match option {
+                CopyOption::Format(ident) => {
+                    format = match ident.value.to_lowercase().as_str() {
+                        "text" => CopyFormat::Text,
+                        "csv" => CopyFormat::Csv,
+                        "binary" => CopyFormat::Binary,
+                        other => {
+                            return Err(PgError::other(format!(
+                                "COPY format \"{other}\" not recognized"
+                            )))
+                        }
+                    }
+                }
+                CopyOption::Delimiter(c) => delimiter = Some(single_byte(*c, "delimiter")?),
+                CopyOption::Null(s) => null = Some(s.clone()),
+                CopyOption::Header(h) => header = *h,
+                CopyOption::Quote(c) => quote = Some(single_byte(*c, "quote")?),
+                CopyOption::Escape(c) => escape = Some(single_byte(*c, "escape")?),
+                other => return Err(PgError::FeatureNotSupported(format!("COPY option {other}"))),
+            }
+        }
+
+        for option in legacy_options {
+            match option {
+                CopyLegacyOption::Binary => format = CopyFormat::Binary,
+                CopyLegacyOption::Delimiter(c) => delimiter = Some(single_byte(*c, "delimiter")?),
+                CopyLegacyOption::Null(s) => null = Some(s.clone()),
+                CopyLegacyOption::Csv(csv_options) => {
+                    format = CopyFormat::Csv;
+                    for csv_option in csv_options {
+                        match csv_option {
+                            CopyLegacyCsvOption::Header => header = true,
+                            CopyLegacyCsvOption::Quote(c) => {
+                                quote = Some(single_byte(*c, "quote")?)
+                            }
+                            CopyLegacyCsvOption::Escape(c) => {
+                                escape = Some(single_byte(*c, "escape")?)
+                            }
+                            other => {
+                                return Err(PgError::FeatureNotSupported(format!(
+                                    "COPY option {other}"
+                                )))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // Defaults depend on the format, just like in PG.
+        let is_csv = format == CopyFormat::Csv;
+        let quote = quote.unwrap_or(b'"');
+        Ok(Self {
+            format,
+            delimiter: delimiter.unwrap_or(if is_csv { b',' } else { b'\t' }),
+            null: null.unwrap_or_else(|| if is_csv { "" } else { "\\N" }.into()),
+            header,
+            quote,
+            escape: escape.unwrap_or(quote),
+        })
+    }
+
+    /// Format of the whole data stream and of every field in `CopyInResponse`
+    /// and `CopyOutResponse` messages.
+    pub fn field_format(&self) -> FieldFormat {
+        match self.format {
+            CopyFormat::Binary => FieldFormat::Binary,
+            CopyFormat::Text | CopyFormat::Csv => FieldFormat::Text,
+        }
+    }
+
+    /// Encode the beginning of the `COPY TO` data.
+    pub fn encode_header(&self, names: &[String], buf: &mut BytesMut) {
+        match self.format {
+            CopyFormat::Binary => {
+                buf.put_slice(BINARY_SIGNATURE);
+                // Flags field.
+                buf.put_i32(0);
+                // Header extension area length.
+                buf.put_i32(0);
+            }
+            CopyFormat::Text | CopyFormat::Csv if self.header => {
+                for (i, name) in names.iter().enumerate() {
+                    if i > 0 {
+                        buf.put_u8(self.delimiter);
+                    }
+                    self.encode_field(name.as_bytes(), buf);
+                }
+                buf.put_u8(b'\n');
+            }
+            CopyFormat::Text | CopyFormat::Csv => {}
+        }
+    }
+
+    /// Encode a row of the `COPY TO` data.
+    ///
+    /// The row is expected to be encoded in [`Self::field_format`], so we only need
+    /// to rearrange its fields in the `COPY` way.
+    pub fn encode_row(&self, row: DataRow, buf: &mut BytesMut) -> PgResult<()> {
+        if self.format == CopyFormat::Binary {
+            // Binary tuples are laid out exactly like in DataRow messages.
+            buf.put_i16(row.field_count);
+            buf.put_slice(&row.data);
+            return Ok(());
+        }
+
+        let mut data = &row.data[..];
+        for i in 0..row.field_count {
+            if i > 0 {
+                buf.put_u8(self.delimiter);
+            }
+            if data.remaining() < 4 {
+                return Err(PgError::InternalError("malformed data row".into()));
+            }
+            let len = data.get_i32();
+            if len < 0 {
+                buf.put_slice(self.null.as_bytes());
+                continue;
+            }
+            let len = len as usize;
+            if data.remaining() < len {
+                return Err(PgError::InternalError("malformed data row".into()));
+            }
+            self.encode_field(&data[..len], buf);
+            data.advance(len);
+        }
+        buf.put_u8(b'\n');
+        Ok(())
+    }
+
+    /// Encode the end of the `COPY TO` data.
+    pub fn encode_trailer(&self, buf: &mut BytesMut) {
+        if self.format == CopyFormat::Binary {
+            buf.put_i16(-1);
+        }
+    }
+
+    fn encode_field(&self, value: &[u8], buf: &mut BytesMut) {
+        match self.format {
+            CopyFormat::Text => {
+                for &b in value {
+                    match b {
+                        b'\\' => buf.put_slice(b"\\\\"),
+                        b'\n' => buf.put_slice(b"\\n"),
+                        b'\r' => buf.put_slice(b"\\r"),
+                        b'\t' => buf.put_slice(b"\\t"),
+                        b if b == self.delimiter => buf.put_slice(&[b'\\', b]),
+                        b => buf.put_u8(b),
+                    }
+                }
+            }
+            CopyFormat::Csv => {
+                let needs_quotes = value.is_empty()
+                    || value == self.null.as_bytes()
+                    || value == END_OF_DATA
+                    || value.iter().any(|&b| {
+                        b == self.delimiter || b == self.quote || b == b'\n' || b == b'\r'
+                    });
+                if !needs_quotes {
+                    buf.put_slice(value);
+                    return;
+                }
+                buf.put_u8(self.quote);
+                for &b in value {
+                    if b == self.quote || b == self.escape {
+                        buf.put_u8(self.escape);
+                    }
+                    buf.put_u8(b);
+                }
+                buf.put_u8(self.quote);
+            }
+            CopyFormat::Binary => unreachable!("binary fields are not encoded one by one"),
+        }
+    }
+}
+
+/// Parsed `COPY` command.
+#[derive(Debug)]
+pub enum CopyStatement {
+    /// `COPY table [(columns)] FROM STDIN`.
+    From {
+        table: String,
+        columns: Vec<String>,
+        options: CopyOptions,
+    },
+    /// `COPY table [(columns)] TO STDOUT` or `COPY (query) TO STDOUT`.
+    /// The table form is turned into a `SELECT` query.
+    To { query: String, options: CopyOptions },
+}
+
+/// Table name as written in the query, possibly schema-qualified. It is resolved
+/// through the search path just like the table names of the ordinary queries.
+fn table_name(name: &ObjectName) -> PgResult<String> {
+    match name.0.as_slice() {
+        [] => Err(PgError::other("COPY requires a table name")),
+        [_] | [_, _] => Ok(name.to_string()),
+        _ => Err(PgError::other(format!(
+            "improper qualified name (too many dotted names): {name}"
+        ))),
+    }
+}
+
+/// Recognize a `COPY` command. Return `None` for any other query.
+pub fn parse(sql: &str) -> PgResult<Option<CopyStatement>> {
+    let sql_trimmed = sql.trim_start();
+    let is_copy = sql_trimmed
+        .get(..4)
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("copy"))
+        && !sql_trimmed[4..].starts_with(|c: char| c.is_alphanumeric() || c == '_');
+    if !is_copy {
+        return Ok(None);
+    }
+
+    // sqlparser expects `FROM STDIN` to be terminated with a semicolon followed
+    // by inline data, while clients usually omit the semicolon.
+    let sql = format!("{};", sql.trim_end().trim_end_matches(';'));
+    let statements = Parser::parse_sql(&PostgreSqlDialect {}, &sql)
+        .map_err(|e| PgError::WithExplicitCode(PgErrorCode::SyntaxError, e.to_string()))?;
+    let [Statement::Copy {
+        source,
+        to,
+        target,
+        options,
+        legacy_options,
+        values,
+    }] = statements.as_slice()
+    else {
+        return Err(PgError::WithExplicitCode(
+            PgErrorCode::SyntaxError,
+            "expected a single COPY statement".into(),
+        ));
+    };
+
+    match (to, target) {
+        (false, CopyTarget::Stdin) | (true, CopyTarget::Stdout) => {}
+        _ => {
+            return Err(PgError::FeatureNotSupported(
+                "COPY to or from a file or a program, use STDIN or STDOUT instead".into(),
+            ))
+        }
+    }
+    if !values.is_empty() {
+        return Err(PgError::FeatureNotSupported(
+            "COPY data inlined into the query".into(),
+        ));
+    }
+
+    let options = CopyOptions::from_ast(options, legacy_options)?;
+    let statement = match source {
+        CopySource::Table {
+            table_name: name,
+            columns,
+        } => {
+            let table = table_name(name)?;
+            let columns: Vec<String> = columns.iter().map(ToString::to_string).collect();
+            if *to {
+                let projection = if columns.is_empty() {
+                    "*".into()
+                } else {
+                    columns.join(", ")
+                };
+                CopyStatement::To {
+                    query: format!("SELECT {projection} FROM {table}"),
+                    options,
+                }
+            } else {
+                CopyStatement::From {
+                    table,
+                    columns,
+                    options,
+                }
+            }
+        }
+        CopySource::Query(query) if *to => CopyStatement::To {
+            query: query.to_string(),
+            options,
+        },
+        CopySource::Query(_) => {
+            return Err(PgError::WithExplicitCode(
+                PgErrorCode::SyntaxError,
+                "COPY FROM requires a table".into(),
+            ))
+        }
+    };
+
+    Ok(Some(statement))
+}
+
+/// Raw fields of a decoded row, `None` stands for NULL.
+type RawRow = Vec<Option<Bytes>>;
+
+/// Incremental decoder of `COPY FROM` data.
+///
+/// The data arrives in CopyData messages that may split rows at arbitrary
+/// positions, so incomplete rows are kept in the buffer until the rest comes.
+#[derive(Debug)]
+struct RowDecoder {
+    options: CopyOptions,
+    buf: BytesMut,
+    /// Whether the header (a header line or the binary signature) has been processed.
+    header_done: bool,
+    /// Whether the end-of-data marker has been seen.
+    finished: bool,
+}
+
+impl RowDecoder {
+    fn new(options: CopyOptions) -> Self {
+        Self {
+            options,
+            buf: BytesMut::new(),
+            header_done: false,
+            finished: false,
+        }
+    }
+
+    fn feed(&mut self, data: &[u8]) {
+        if !self.finished {
+            self.buf.extend_from_slice(data);
+        }
+    }
+
+    /// Decode the next complete row. With `eof` set the rest of the buffer
+    /// is treated as the last row, as no more data will come.
+    fn next_row(&mut self, eof: bool) -> PgResult<Option<RawRow>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        if self.options.format == CopyFormat::Binary {
+            let row = self.next_binary_row()?;
+            if row.is_none() && eof && !self.buf.is_empty() {
+                return Err(DecodingError::new("unexpected EOF in COPY data").into());
+            }
+            return Ok(row);
+        }
+
+        loop {
+            let Some(record) = self.next_record(eof) else {
+                return Ok(None);
+            };
+            if record == END_OF_DATA {
+                self.finished = true;
+                self.buf.clear();
+                return Ok(None);
+            }
+            if !self.header_done {
+                self.header_done = true;
+                if self.options.header {
+                    continue;
+                }
+            }
+
+            let row = match self.options.format {
+                CopyFormat::Text => split_text(&record, &self.options),
+                CopyFormat::Csv => split_csv(&record, &self.options)?,
+                CopyFormat::Binary => unreachable!("handled above"),
+            };
+            return Ok(Some(row));
+        }
+    }
+
+    /// Cut the next text or csv record without the line terminator from the buffer.
+    fn next_record(&mut self, eof: bool) -> Option<Bytes> {
+        let CopyOptions {
+            format,
+            quote,
+            escape,
+            ..
+        } = self.options;
+
+        let mut end = None;
+        let mut in_quotes = false;
+        let mut i = 0;
+        while i < self.buf.len() {
+            let b = self.buf[i];
+            if format == CopyFormat::Csv && in_quotes {
+                if b == escape
+                    && self
+                        .buf
+                        .get(i + 1)
+                        .is_some_and(|&n| n == quote || n == escape)
+                {
+                    i += 1;
+                } else if b == quote {
+                    in_quotes = false;
+                }
+            } else if format == CopyFormat::Csv && b == quote {
+                in_quotes = true;
+            } else if b == b'\n' {
+                end = Some(i);
+                break;
+            }
+            i += 1;
+        }
+
+        let mut record = match end {
+            Some(end) => {
+                let record = self.buf.split_to(end + 1);
+                record.freeze().slice(..end)
+            }
+            None if eof && !self.buf.is_empty() => self.buf.split().freeze(),
+            None => return None,
+        };
+        if record.last() == Some(&b'\r') {
+            record.truncate(record.len() - 1);
+        }
+        Some(record)
+    }
+
+    fn next_binary_row(&mut self) -> PgResult<Option<RawRow>> {
+        if !self.header_done {
+            // Signature, flags field and header extension area length.
+            let fixed_len = BINARY_SIGNATURE.len() + 8;
+            if self.buf.len() < fixed_len {
+                return Ok(None);
+            }
+            if &self.buf[..BINARY_SIGNATURE.len()] != BINARY_SIGNATURE {
+                return Err(DecodingError::new("invalid COPY file signature").into());
+            }
+            let mut lengths = &self.buf[BINARY_SIGNATURE.len()..fixed_len];
+            let _flags = lengths.get_i32();
+            let extension_len = usize::try_from(lengths.get_i32())
+                .map_err(|_| DecodingError::new("invalid COPY file header"))?;
+            if self.buf.len() < fixed_len + extension_len {
+                return Ok(None);
+            }
+            self.buf.advance(fixed_len + extension_len);
+            self.header_done = true;
+        }
+
+        // Make sure the whole tuple is in the buffer before consuming it.
+        let mut data = &self.buf[..];
+        if data.remaining() < 2 {
+            return Ok(None);
+        }
+        let field_count = data.get_i16();
+        if field_count == -1 {
+            self.finished = true;
+            self.buf.clear();
+            return Ok(None);
+        }
+        let field_count = usize::try_from(field_count)
+            .map_err(|_| DecodingError::new(format!("invalid field count {field_count}")))?;
+
+        let mut ranges = Vec::with_capacity(field_count);
+        for _ in 0..field_count {
+            if data.remaining() < 4 {
+                return Ok(None);
+            }
+            let len = data.get_i32();
+            let Ok(len) = usize::try_from(len) else {
+                ranges.push(None);
+                continue;
+            };
+            if data.remaining() < len {
+                return Ok(None);
+            }
+            let start = self.buf.len() - data.remaining();
+            ranges.push(Some(start..start + len));
+            data.advance(len);
+        }
+
+        let consumed = self.buf.len() - data.remaining();
+        let tuple = self.buf.split_to(consumed).freeze();
+        let row = ranges
+            .into_iter()
+            .map(|range| range.map(|range| tuple.slice(range)))
+            .collect();
+        Ok(Some(row))
+    }
+}
+
+/// Split a text format record into fields, processing backslash escapes.
+fn split_text(record: &[u8], options: &CopyOptions) -> RawRow {
+    let null = options.null.as_bytes();
+    let mut row = Vec::new();
+    let mut field = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    loop {
+        if i == record.len() || record[i] == options.delimiter {
+            let value = std::mem::take(&mut field);
+            // NULL is compared with the raw field, before escapes are processed.
+            row.push((&record[start..i] != null).then(|| Bytes::from(value)));
+            if i == record.len() {
+                break;
+            }
+            i += 1;
+            start = i;
+            continue;
+        }
+
+        if record[i] != b'\\' || i + 1 == record.len() {
+            field.push(record[i]);
+            i += 1;
+            continue;
+        }
+
+        i += 1;
+        match record[i] {
+            b'0'..=b'7' => {
+                let mut value: u32 = 0;
+                let digits = record[i..]
+                    .iter()
+                    .take(3)
+                    .take_while(|b| (b'0'..=b'7').contains(b));
+                for digit in digits {
+                    value = value * 8 + u32::from(digit - b'0');
+                    i += 1;
+                }
+                field.push(value as u8);
+            }
+            b'x' if record.get(i + 1).is_some_and(u8::is_ascii_hexdigit) => {
+                i += 1;
+                let mut value: u32 = 0;
+                let digits = record[i..]
+                    .iter()
+                    .take(2)
+                    .take_while(|b| b.is_ascii_hexdigit());
+                for digit in digits {
+                    value = value * 16 + (*digit as char).to_digit(16).expect("hex digit");
+                    i += 1;
+                }
+                field.push(value as u8);
+            }
+            escaped => {
+                field.push(match escaped {
+                    b'b' => 0x08,
+                    b'f' => 0x0c,
+                    b'n' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    b'v' => 0x0b,
+                    other => other,
+                });
+                i += 1;
+            }
+        }
+    }
+    row
+}
+
+/// Split a csv record into fields, processing quotes.
+fn split_csv(record: &[u8], options: &CopyOptions) -> PgResult<RawRow> {
+    let CopyOptions {
+        delimiter,
+        quote,
+        escape,
+        ..
+    } = *options;
+    let null = options.null.as_bytes();
+
+    let mut row = Vec::new();
+    let mut field = Vec::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut i = 0;
+    loop {
+        if i == record.len() || (!in_quotes && record[i] == delimiter) {
+            if in_quotes {
+                return Err(DecodingError::new("unterminated CSV quoted field").into());
+            }
+            let value = std::mem::take(&mut field);
+            // Only unquoted values can be NULL, so that "" is an empty string.
+            let is_null = !quoted && value == null;
+            row.push((!is_null).then(|| Bytes::from(value)));
+            quoted = false;
+            if i == record.len() {
+                break;
+            }
+            i += 1;
+            continue;
+        }
+
+        let b = record[i];
+        if in_quotes {
+            if b == escape
+                && record
+                    .get(i + 1)
+                    .is_some_and(|&n| n == quote || n == escape)
+            {
+                i += 1;
+                field.push(record[i]);
+            } else if b == quote {
+                in_quotes = false;
+            } else {
+                field.push(b);
+            }
+        } else if b == quote {
+            in_quotes = true;
+            quoted = true;
+        } else {
+            field.push(b);
+        }
+        i += 1;
+    }
+    Ok(row)
+}
+
+/// State of a running `COPY FROM STDIN`.
+///
+/// Decoded rows are collected into batches. Every batch is inserted with its own
+/// copy of the `INSERT ... VALUES` plan, where the VALUES subtree is replaced with
+/// the batch rows, so they are resharded by buckets and sent right to the storages.
+/// Outside a transaction every batch is committed on its own, so a failed load
+/// leaves the preceding batches in the table, which is reported in the error.
+/// Inside a transaction the whole load is rolled back along with it.
+#[derive(Debug)]
+pub struct CopyIn {
+    /// Optimized plan of the `INSERT` query, which is cloned for every batch.
+    plan: Plan,
+    /// Types of the inserted columns.
+    oids: Vec<Oid>,
+    decoder: RowDecoder,
+    batch: Vec<VTableTuple>,
+    batch_size: usize,
+    /// Number of the rows received from the client.
+    rows_received: usize,
+    /// Number of the rows inserted by the storages.
+    row_count: usize,
+    /// Number of the inserted batches.
+    batches_inserted: usize,
+    /// Number of the received rows belonging to the inserted batches.
+    rows_inserted: usize,
+    /// Insertion of the last batch has failed.
+    batch_failed: bool,
+    /// Rows are inserted in an interactive transaction.
+    in_transaction: bool,
+}
+
+impl CopyIn {
+    pub fn new(table: &str, columns: &[String], options: CopyOptions) -> PgResult<Self> {
+        let in_transaction = transaction::active_txn_id(SessionKey::current())?.is_some();
+        let runtime = RouterRuntime::new().map_err(Error::from)?;
+        let storage = &node::global()?.storage;
+        // Admin privileges are needed for reading tables metadata,
+        // access to the table itself is checked on insertion.
+        let (plan, oids) = with_su(ADMIN_ID, || -> PgResult<(Plan, Vec<Oid>)> {
+            let table = {
+                let metadata = runtime.metadata().lock();
+                metadata.table(&resolve_relation_name(&*metadata, table)?)?
+            };
+
+            let columns = if columns.is_empty() {
+                table
+                    .columns
+                    .iter()
+                    .filter(|column| column.role != ColumnRole::Sharding)
+                    .collect::<Vec<_>>()
+            } else {
+                columns
+                    .iter()
+                    .map(|name| {
+                        let name = normalize_name_from_sql(name);
+                        table
+                            .columns
+                            .iter()
+                            .find(|column| column.name == name)
+                            .ok_or_else(|| {
+                                PgError::other(format!(
+                                    "column \"{name}\" of relation \"{}\" does not exist",
+                                    table.name
+                                ))
+                            })
+                    })
+                    .collect::<PgResult<Vec<_>>>()?
+            };
+
+            let oids = columns
+                .iter()
+                .map(|column| {
+                    let ty = column.r#type.get().as_ref().ok_or_else(|| {
+                        PgError::InternalError(format!("column {} has no type", column.name))
+                    })?;
+                    Ok(sbroad_type_to_pg(ty)?.oid())
+                })
+                .collect::<PgResult<Vec<_>>>()?;
+
+            // The values are only placeholders, they are replaced with the
+            // decoded rows for every batch.
+            let names: Vec<_> = columns.iter().map(|c| format!("\"{}\"", c.name)).collect();
+            let sql = format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                to_user(&table.name),
+                names.join(", "),
+                vec!["NULL"; names.len()].join(", "),
+            );
+            let options = Options::new(
+                storage.db_config.sql_motion_row_max()?,
+                storage.db_config.sql_vdbe_opcode_max()?,
+            );
+            let query = Query::with_options(&runtime, &sql, vec![], Some(options))?;
+            Ok((query.get_exec_plan().get_ir_plan().clone(), oids))
+        })??;
+
+        // A batch is shipped as a virtual table, so it is limited just like
+        // any other motion.
+        let batch_size = plan.options.sql_motion_row_max.max(1) as usize;
+        Ok(Self {
+            plan,
+            oids,
+            decoder: RowDecoder::new(options),
+            batch: Vec::new(),
+            batch_size,
+            rows_received: 0,
+            row_count: 0,
+            batches_inserted: 0,
+            rows_inserted: 0,
+            batch_failed: false,
+            in_transaction,
+        })
+    }
+
+    /// Number of the columns to be copied.
+    pub fn ncolumns(&self) -> usize {
+        self.oids.len()
+    }
+
+    pub fn field_format(&self) -> FieldFormat {
+        self.decoder.options.field_format()
+    }
+
+    /// Handle a chunk of data from a CopyData message.
+    pub fn feed(&mut self, data: &[u8]) -> PgResult<()> {
+        self.decoder.feed(data);
+        self.process_rows(false)
+    }
+
+    /// Handle the end of data (CopyDone message) and return the number of inserted rows.
+    pub fn finish(&mut self) -> PgResult<usize> {
+        self.process_rows(true)?;
+        self.flush()?;
+        Ok(self.row_count)
+    }
+
+    /// Add to the error of the load which rows have already been committed,
+    /// so the client can resume loading the data from the first missing row.
+    pub fn load_error(&self, error: PgError) -> PgError {
+        if self.in_transaction || (self.batches_inserted == 0 && !self.batch_failed) {
+            return error;
+        }
+        let mut note = if self.batches_inserted == 0 {
+            "no batches have been committed".to_string()
+        } else {
+            format!(
+                "rows 1-{} (batches 1-{}) have been committed",
+                self.rows_inserted, self.batches_inserted
+            )
+        };
+        if self.batch_failed {
+            // The rows of a batch may belong to several replicasets,
+            // which commit their parts independently.
+            note.push_str(&format!(
+                ", batch {} (rows {}-{}) may have been committed partially",
+                self.batches_inserted + 1,
+                self.rows_inserted + 1,
+                self.rows_received,
+            ));
+        }
+        PgError::WithExplicitCode(error.code(), format!("{error}; {note}"))
+    }
+
+    fn process_rows(&mut self, eof: bool) -> PgResult<()> {
+        while let Some(row) = self.decoder.next_row(eof)? {
+            self.rows_received += 1;
+            let row = self.decode_row(row).map_err(|e| match e {
+                PgError::DecodingError(e) => {
+                    DecodingError::new(format!("COPY row {}: {e}", self.rows_received)).into()
+                }
+                e => e,
+            })?;
+            self.batch.push(row);
+            if self.batch.len() >= self.batch_size {
+                self.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_row(&self, row: RawRow) -> PgResult<VTableTuple> {
+        if row.len() != self.oids.len() {
+            return Err(DecodingError::new(format!(
+                "expected {} columns, got {}",
+                self.oids.len(),
+                row.len()
+            ))
+            .into());
+        }
+        let format = self.field_format();
+        zip(row, &self.oids)
+            .map(|(field, oid)| PgValue::decode(field.as_ref(), *oid, format)?.try_into())
+            .collect()
+    }
+
+    /// Insert the collected batch.
+    fn flush(&mut self) -> PgResult<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+        let rows = std::mem::take(&mut self.batch);
+        let runtime = RouterRuntime::new().map_err(Error::from)?;
+        let mut query = Query::from_parts(
+            false,
+            ExecutionPlan::from(self.plan.clone()),
+            &runtime,
+            HashMap::new(),
+        );
+        query.set_insert_values(rows)?;
+        let tuple = dispatch(query).inspect_err(|_| self.batch_failed = true)?;
+        self.row_count += get_row_count_from_tuple(&tuple)?;
+        self.batches_inserted += 1;
+        self.rows_inserted = self.rows_received;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(sql: &str) -> CopyOptions {
+        match parse(sql).unwrap().unwrap() {
+            CopyStatement::From { options, .. } | CopyStatement::To { options, .. } => options,
+        }
+    }
+
+    fn decode_all(options: CopyOptions, chunks: &[&[u8]]) -> Vec<RawRow> {
+        let mut decoder = RowDecoder::new(options);
+        let mut rows = vec![];
+        for chunk in chunks {
+            decoder.feed(chunk);
+            while let Some(row) = decoder.next_row(false).unwrap() {
+                rows.push(row);
+            }
+        }
+        while let Some(row) = decoder.next_row(true).unwrap() {
+            rows.push(row);
+        }
+        rows
+    }
+
+    fn row(fields: &[Option<&str>]) -> RawRow {
+        fields
+            .iter()
+            .map(|f| f.map(|s| Bytes::copy_from_slice(s.as_bytes())))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        assert!(parse("select 1").unwrap().is_none());
+        assert!(parse("copyright").unwrap().is_none());
+
+        let Some(CopyStatement::From { table, columns, .. }) =
+            parse(r#"COPY "T" (a, "B") FROM STDIN"#).unwrap()
+        else {
+            panic!("expected COPY FROM");
+        };
+        assert_eq!(table, r#""T""#);
+        assert_eq!(columns, vec!["a".to_string(), r#""B""#.to_string()]);
+
+        let Some(CopyStatement::To { query, .. }) = parse("copy t (a, b) to stdout").unwrap()
+        else {
+            panic!("expected COPY TO");
+        };
+        assert_eq!(query, "SELECT a, b FROM t");
+
+        // Schema-qualified names are kept to be resolved like in the queries.
+        let Some(CopyStatement::From { table, .. }) = parse("COPY s.t FROM STDIN").unwrap() else {
+            panic!("expected COPY FROM");
+        };
+        assert_eq!(table, "s.t");
+        let Some(CopyStatement::To { query, .. }) = parse(r#"COPY "S".t TO STDOUT"#).unwrap()
+        else {
+            panic!("expected COPY TO");
+        };
+        assert_eq!(query, r#"SELECT * FROM "S".t"#);
+        assert!(parse("COPY a.b.c FROM STDIN").is_err());
+
+        let Some(CopyStatement::To { query, .. }) =
+            parse("COPY (SELECT a FROM t WHERE b > 1) TO STDOUT").unwrap()
+        else {
+            panic!("expected COPY TO");
+        };
+        assert_eq!(query, "SELECT a FROM t WHERE b > 1");
+
+        let err = parse("COPY t FROM '/etc/passwd'").unwrap_err();
+        assert!(err.to_string().contains("COPY to or from a file"));
+
+        let csv = options("COPY t FROM STDIN WITH (FORMAT csv, HEADER true)");
+        assert_eq!(csv.format, CopyFormat::Csv);
+        assert_eq!(
+            (csv.delimiter, csv.null.as_str(), csv.header),
+            (b',', "", true)
+        );
+
+        let text = options("COPY t FROM STDIN DELIMITER '|' NULL 'nil'");
+        assert_eq!(text.format, CopyFormat::Text);
+        assert_eq!((text.delimiter, text.null.as_str()), (b'|', "nil"));
+
+        let binary = options("COPY t TO STDOUT BINARY");
+        assert_eq!(binary.format, CopyFormat::Binary);
+    }
+
+    #[test]
+    fn test_decode_text() {
+        let rows = decode_all(
+            options("COPY t FROM STDIN"),
+            &[
+                b"1\tfoo\\tbar\t\\N\n2\t",
+                b"a\\\\b\\101\\x41\t\r\n",
+                b"\\.\n3\tignored\n",
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                row(&[Some("1"), Some("foo\tbar"), None]),
+                row(&[Some("2"), Some("a\\bAA"), Some("")]),
+            ]
+        );
+
+        // The last line may have no terminator.
+        let rows = decode_all(options("COPY t FROM STDIN"), &[b"1\n2"]);
+        assert_eq!(rows, vec![row(&[Some("1")]), row(&[Some("2")])]);
+    }
+
+    #[test]
+    fn test_decode_csv() {
+        let rows = decode_all(
+            options("COPY t FROM STDIN (FORMAT csv, HEADER true)"),
+            &[
+                b"id,name,note\n1,\"multi\n",
+                b"line\",\n2,\"say \"\"hi\"\"\",\"\"\n",
+            ],
+        );
+        assert_eq!(
+            rows,
+            vec![
+                row(&[Some("1"), Some("multi\nline"), None]),
+                row(&[Some("2"), Some("say \"hi\""), Some("")]),
+            ]
+        );
+
+        let mut decoder = RowDecoder::new(options("COPY t FROM STDIN CSV"));
+        decoder.feed(b"1,\"unterminated\n");
+        assert!(decoder.next_row(true).is_err());
+    }
+
+    #[test]
+    fn test_decode_binary() {
+        let mut data = BytesMut::new();
+        data.put_slice(BINARY_SIGNATURE);
+        data.put_i32(0);
+        data.put_i32(0);
+        data.put_i16(2);
+        data.put_i32(8);
+        data.put_i64(42);
+        data.put_i32(-1);
+        data.put_i16(-1);
+
+        // Feed the data byte by byte to check incomplete rows handling.
+        let chunks: Vec<&[u8]> = data.chunks(1).collect();
+        let rows = decode_all(options("COPY t FROM STDIN (FORMAT binary)"), &chunks);
+        assert_eq!(
+            rows,
+            vec![vec![
+                Some(Bytes::copy_from_slice(&42_i64.to_be_bytes())),
+                None
+            ]]
+        );
+
+        let mut decoder = RowDecoder::new(options("COPY t FROM STDIN (FORMAT binary)"));
+        decoder.feed(b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0\0\x01");
+        assert!(decoder.next_row(true).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut data = BytesMut::new();
+        for field in [Some(&b"a\tb"[..]), None, Some(&b""[..])] {
+            match field {
+                Some(field) => {
+                    data.put_i32(field.len() as i32);
+                    data.put_slice(field);
+                }
+                None => data.put_i32(-1),
+            }
+        }
+        let data_row = DataRow {
+            data,
+            field_count: 3,
+        };
+
+        let mut buf = BytesMut::new();
+        let text = options("COPY t TO STDOUT");
+        text.encode_row(data_row.clone(), &mut buf).unwrap();
+        assert_eq!(&buf[..], b"a\\tb\t\\N\t\n");
+
+        let mut buf = BytesMut::new();
+        let csv = options("COPY t TO STDOUT (FORMAT csv, HEADER true)");
+        csv.encode_header(&["id".into(), "na,me".into()], &mut buf);
+        csv.encode_row(data_row, &mut buf).unwrap();
+        assert_eq!(&buf[..], b"id,\"na,me\"\na\tb,,\"\"\n");
+    }
+}
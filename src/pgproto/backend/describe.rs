@@ -47,6 +47,7 @@ pub enum CommandTag {
     AlterRole = 0,
     AlterSystem = 22,
     AlterTable = 41,
+    Analyze = 60,
     Begin = 52,
     CallProcedure = 16,
     CreateProcedure = 14,
@@ -55,8 +56,11 @@ pub enum CommandTag {
     CreateIndex = 18,
     CreatePlugin = 31,
     CreateSchema = 50,
+    CreateSequence = 56,
+    CreateView = 58,
     ChangeConfig = 39,
     Commit = 53,
+    Copy = 61,
     DropProcedure = 15,
     DropRole = 3,
     DropTable = 4,
@@ -67,6 +71,8 @@ pub enum CommandTag {
     DropIndex = 19,
     DropPlugin = 34,
     DropSchema = 51,
+    DropSequence = 57,
+    DropView = 59,
     EnablePlugin = 32,
     EmptyQuery = 55,
     Explain = 6,
@@ -93,18 +99,24 @@ impl CommandTag {
         match *self {
             Self::AlterRole => "ALTER ROLE",
             Self::AlterSystem => "ALTER SYSTEM",
+            Self::Analyze => "ANALYZE",
             Self::CreateRole => "CREATE ROLE",
             Self::CreateSchema => "CREATE SCHEMA",
+            Self::CreateSequence => "CREATE SEQUENCE",
             Self::CreateTable => "CREATE TABLE",
+            Self::CreateView => "CREATE VIEW",
             Self::CreateIndex => "CREATE INDEX",
             Self::Deallocate => "DEALLOCATE",
             Self::DeallocateAll => "DEALLOCATE ALL",
             Self::DropRole => "DROP ROLE",
             Self::DropSchema => "DROP SCHEMA",
+            Self::DropSequence => "DROP SEQUENCE",
             Self::DropTable => "DROP TABLE",
+            Self::DropView => "DROP VIEW",
             Self::TruncateTable => "TRUNCATE TABLE",
             Self::AlterTable => "ALTER TABLE",
             Self::DropIndex => "DROP INDEX",
+            Self::Copy => "COPY",
             Self::Delete => "DELETE",
             Self::Explain => "EXPLAIN",
             Self::Grant => "GRANT",
@@ -161,11 +173,16 @@ impl From<CommandTag> for QueryType {
             | CommandTag::CreateProcedure
             | CommandTag::CreateIndex
             | CommandTag::CreateSchema
+            | CommandTag::CreateSequence
+            | CommandTag::CreateView
             | CommandTag::RenameRoutine
             | CommandTag::DropIndex
             | CommandTag::DropSchema
+            | CommandTag::DropSequence
+            | CommandTag::DropView
             | CommandTag::SetParam
             | CommandTag::SetTransaction
+            | CommandTag::Analyze
             | CommandTag::CreatePlugin
             | CommandTag::DropPlugin
             | CommandTag::EnablePlugin
@@ -175,7 +192,8 @@ impl From<CommandTag> for QueryType {
             | CommandTag::RemoveTier
             | CommandTag::ChangeConfig
             | CommandTag::DropProcedure => QueryType::Ddl,
-            CommandTag::Delete
+            CommandTag::Copy
+            | CommandTag::Delete
             | CommandTag::Insert
             | CommandTag::Update
             | CommandTag::CallProcedure => QueryType::Dml,
@@ -213,13 +231,20 @@ impl TryFrom<&Node<'_>> for CommandTag {
                 Ddl::AlterSystem { .. } => Ok(CommandTag::AlterSystem),
                 Ddl::DropTable { .. } => Ok(CommandTag::DropTable),
                 Ddl::TruncateTable { .. } => Ok(CommandTag::TruncateTable),
-                Ddl::CreateTable { .. } => Ok(CommandTag::CreateTable),
+                Ddl::CreateTable { .. }
+                | Ddl::CreateTableAs { .. }
+                | Ddl::CreatePartition { .. } => Ok(CommandTag::CreateTable),
                 Ddl::CreateProc { .. } => Ok(CommandTag::CreateProcedure),
                 Ddl::CreateIndex { .. } => Ok(CommandTag::CreateIndex),
-                Ddl::CreateSchema => Ok(CommandTag::CreateSchema),
-                Ddl::DropSchema => Ok(CommandTag::DropSchema),
+                Ddl::CreateSchema { .. } => Ok(CommandTag::CreateSchema),
+                Ddl::DropSchema { .. } => Ok(CommandTag::DropSchema),
                 Ddl::DropProc { .. } => Ok(CommandTag::DropProcedure),
                 Ddl::DropIndex { .. } => Ok(CommandTag::DropIndex),
+                Ddl::CreateSequence { .. } => Ok(CommandTag::CreateSequence),
+                Ddl::DropSequence { .. } => Ok(CommandTag::DropSequence),
+                Ddl::CreateView { .. } => Ok(CommandTag::CreateView),
+                Ddl::DropView { .. } => Ok(CommandTag::DropView),
+                Ddl::Analyze { .. } => Ok(CommandTag::Analyze),
                 Ddl::RenameRoutine { .. } => Ok(CommandTag::RenameRoutine),
                 Ddl::SetParam { .. } => Ok(CommandTag::SetParam),
                 Ddl::SetTransaction { .. } => Ok(CommandTag::SetTransaction),
@@ -312,17 +337,19 @@ fn pg_type_from_sbroad(sbroad: &DerivedType) -> Type {
             SbroadType::Double => Type::FLOAT8,
             SbroadType::Decimal => Type::NUMERIC,
             SbroadType::Uuid => Type::UUID,
+            SbroadType::Varbinary => Type::BYTEA,
             SbroadType::Datetime => Type::TIMESTAMPTZ,
+            SbroadType::Interval => Type::INTERVAL,
         }
     } else {
         Type::UNKNOWN
     }
 }
 
-/// Get an output format from the dql query plan.
-fn dql_output_format(ir: &Plan) -> PgResult<Vec<MetadataColumn>> {
+/// Get an output format from the dql query plan (or from the `RETURNING`
+/// projection of DML) with the given top node.
+fn dql_output_format(ir: &Plan, top_id: NodeId) -> PgResult<Vec<MetadataColumn>> {
     // Get metadata (column types) from the top node's output tuple.
-    let top_id = ir.get_top()?;
     let top_output_id = ir.get_relation_node(top_id)?.output();
     let columns = ir.get_row_list(top_output_id)?;
     let mut metadata = Vec::with_capacity(columns.len());
@@ -397,7 +424,19 @@ impl Describe {
             let node = plan.get_node(top)?;
             CommandTag::try_from(&node)?
         };
-        let query_type = command_tag.into();
+        // DML with RETURNING clause produces rows just like DQL does,
+        // but it is still reported with its own command tag.
+        let returning_id = match command_tag {
+            CommandTag::Insert | CommandTag::Update | CommandTag::Delete => {
+                plan.dml_returning(plan.get_top()?)?
+            }
+            _ => None,
+        };
+        let query_type = if returning_id.is_some() {
+            QueryType::Dql
+        } else {
+            command_tag.into()
+        };
 
         let metadata = match query_type {
             QueryType::Acl
@@ -406,7 +445,13 @@ impl Describe {
             | QueryType::Tcl
             | QueryType::Deallocate
             | QueryType::Empty => vec![],
-            QueryType::Dql => dql_output_format(plan)?,
+            QueryType::Dql => {
+                let top_id = match returning_id {
+                    Some(proj_id) => proj_id,
+                    None => plan.get_top()?,
+                };
+                dql_output_format(plan, top_id)?
+            }
             QueryType::Explain => explain_output_format(),
         };
 
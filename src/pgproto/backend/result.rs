@@ -37,6 +37,13 @@ impl Rows {
         RowDescription::new(self.desc.iter().map(Into::into).collect())
     }
 
+    pub fn column_names(&self) -> Vec<String> {
+        self.desc
+            .iter()
+            .map(|field| field.name().to_owned())
+            .collect()
+    }
+
     pub fn values(&self) -> Vec<Vec<PgValue>> {
         self.rows.clone().collect()
     }
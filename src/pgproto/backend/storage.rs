@@ -316,8 +316,10 @@ pub(super) fn sbroad_type_to_pg(ty: &SbroadType) -> PgResult<postgres_types::Typ
         SbroadType::Integer | SbroadType::Unsigned => Ok(PgType::INT8),
         SbroadType::String => Ok(PgType::TEXT),
         SbroadType::Uuid => Ok(PgType::UUID),
+        SbroadType::Varbinary => Ok(PgType::BYTEA),
         SbroadType::Map | SbroadType::Array | SbroadType::Any => Ok(PgType::JSON),
         SbroadType::Datetime => Ok(PgType::TIMESTAMPTZ),
+        SbroadType::Interval => Ok(PgType::INTERVAL),
     }
 }
 
@@ -329,7 +331,9 @@ pub(super) fn pg_type_to_sbroad(ty: &PgType) -> PgResult<SbroadType> {
         &PgType::INT8 | &PgType::INT4 | &PgType::INT2 => Ok(SbroadType::Integer),
         &PgType::TEXT | &PgType::VARCHAR => Ok(SbroadType::String),
         &PgType::UUID => Ok(SbroadType::Uuid),
+        &PgType::BYTEA => Ok(SbroadType::Varbinary),
         &PgType::TIMESTAMPTZ => Ok(SbroadType::Datetime),
+        &PgType::INTERVAL => Ok(SbroadType::Interval),
         unsupported_type => Err(PgError::FeatureNotSupported(unsupported_type.to_string())),
     }
 }
@@ -385,7 +389,7 @@ fn get_rows_from_tuple(tuple: &Tuple) -> PgResult<Vec<Vec<Value>>> {
 }
 
 /// Get row_count from result tuple.
-fn get_row_count_from_tuple(tuple: &Tuple) -> PgResult<usize> {
+pub(super) fn get_row_count_from_tuple(tuple: &Tuple) -> PgResult<usize> {
     #[derive(Deserialize)]
     struct RowCount {
         row_count: usize,
@@ -656,6 +660,8 @@ mod test {
             (SbroadType::Unsigned, PgType::INT8),
             (SbroadType::String, PgType::TEXT),
             (SbroadType::Uuid, PgType::UUID),
+            (SbroadType::Varbinary, PgType::BYTEA),
+            (SbroadType::Interval, PgType::INTERVAL),
             (SbroadType::Any, PgType::JSON),
             (SbroadType::Array, PgType::JSON),
             (SbroadType::Map, PgType::JSON),
@@ -675,6 +681,8 @@ mod test {
             (PgType::INT2, SbroadType::Integer),
             (PgType::TEXT, SbroadType::String),
             (PgType::UUID, SbroadType::Uuid),
+            (PgType::BYTEA, SbroadType::Varbinary),
+            (PgType::INTERVAL, SbroadType::Interval),
         ] {
             assert!(pg_type_to_sbroad(&pg).unwrap() == expected_sbroad)
         }
@@ -5,16 +5,19 @@ use super::{
     stream::{BeMessage, FeMessage, PgStream},
     tls::TlsAcceptor,
 };
+use crate::sql::transaction::{self, SessionKey};
 use crate::{storage::Catalog, tlog};
 use pgwire::messages::startup::*;
 use std::io;
 
 mod auth;
+mod copy;
 mod extended_query;
 mod simple_query;
 mod startup;
 
 pub use startup::ClientParams;
+use startup::{BackendKey, Handshake};
 
 /// We generate those sequentially for every client connection.
 pub type ClientId = u64;
@@ -25,18 +28,28 @@ pub struct PgClient<S> {
     backend: Backend,
     /// Stream for network communication.
     stream: PgStream<S>,
+    /// Key the client uses to cancel its queries.
+    _key: BackendKey,
 
     loop_state: MessageLoopState,
 }
 
 impl<S: io::Read + io::Write> PgClient<S> {
     /// Create a client context by receiving a startup message and authenticating the client.
+    /// Returns `None` if the connection was opened to cancel a query of another client.
     pub fn accept(
         stream: PgStream<S>,
         tls_acceptor: Option<TlsAcceptor>,
         storage: &Catalog,
-    ) -> PgResult<PgClient<S>> {
-        let (mut stream, params) = startup::handshake(stream, tls_acceptor.as_ref())?;
+    ) -> PgResult<Option<PgClient<S>>> {
+        let (mut stream, params) = match startup::handshake(stream, tls_acceptor.as_ref())? {
+            Handshake::Startup(stream, params) => (stream, params),
+            Handshake::Cancel(request) => {
+                tlog!(Info, "processing cancel request");
+                startup::process_cancel_request(request);
+                return Ok(None);
+            }
+        };
         tlog!(Info, "processed startup");
 
         auth::authenticate(&mut stream, &params.username, storage).map_err(|error| {
@@ -47,11 +60,16 @@ impl<S: io::Read + io::Write> PgClient<S> {
         })?;
         tlog!(Info, "client authenticated");
 
-        Ok(PgClient {
-            backend: Backend::new(params),
+        let backend = Backend::new(params);
+        let key = BackendKey::issue(backend.client_id());
+        stream.write_message_noflush(key.message())?;
+
+        Ok(Some(PgClient {
+            backend,
             loop_state: MessageLoopState::ReadyForQuery,
             stream,
-        })
+            _key: key,
+        }))
     }
 
     /// Send paraneter to the frontend.
@@ -186,6 +204,8 @@ impl<S: io::Read + io::Write> PgClient<S> {
 
     fn process_error(&mut self, error: PgError) -> PgResult<()> {
         tlog!(Debug, "processing error: {error:?}");
+        // Like in PostgreSQL, any error aborts the current transaction.
+        transaction::mark_failed(SessionKey::current());
         self.stream
             .write_message(messages::error_response(error.info()))?;
         error.check_fatality()?;
@@ -214,7 +234,9 @@ impl<S: io::Read + io::Write> PgClient<S> {
         tlog!(Info, "entering the message handling loop");
         while !self.is_terminated() {
             if let MessageLoopState::ReadyForQuery = self.loop_state {
-                self.stream.write_message(messages::ready_for_query())?;
+                let status = self.backend.transaction_status();
+                self.stream
+                    .write_message(messages::ready_for_query(status))?;
             }
 
             match self.process_message() {
@@ -1,12 +1,16 @@
 use crate::pgproto::error::PgError;
 use crate::pgproto::stream::{FeMessage, PgStream};
 use crate::pgproto::{error::PgResult, messages};
+use crate::schema::UserDef;
+use crate::scram;
 use crate::storage::Catalog;
 use crate::tlog;
+use openssl::base64;
 use pgwire::messages::startup::PasswordMessageFamily;
 use std::{io, os::raw::c_int};
 use tarantool::auth::AuthMethod;
 use tarantool::error::BoxError;
+use tarantool::session;
 
 extern "C" {
     /// pointers must have valid and non-null values, salt must be at least 20 bytes
@@ -110,6 +114,184 @@ where
     Ok(extract_password(message))
 }
 
+fn read_password_message<S>(stream: &mut PgStream<S>) -> PgResult<PasswordMessageFamily>
+where
+    S: io::Read + io::Write,
+{
+    match stream.read_message()? {
+        FeMessage::PasswordMessageFamily(message) => Ok(message),
+        message => Err(PgError::ProtocolViolation(format!(
+            "expected SASLResponse, got {message:?}"
+        ))),
+    }
+}
+
+fn invalid_utf8() -> PgError {
+    PgError::ProtocolViolation("invalid UTF-8 in SCRAM message".into())
+}
+
+fn malformed_scram_message(message: &str) -> PgError {
+    PgError::ProtocolViolation(format!("malformed SCRAM message: {message:?}"))
+}
+
+/// Returns the value of the SCRAM message attribute, e.g. `r=<nonce>`.
+fn scram_attribute<'a>(attribute: Option<&'a str>, name: &str, message: &str) -> PgResult<&'a str> {
+    attribute
+        .and_then(|attribute| attribute.strip_prefix(name))
+        .and_then(|attribute| attribute.strip_prefix('='))
+        .ok_or_else(|| malformed_scram_message(message))
+}
+
+/// Server side of the SCRAM-SHA-256 exchange, see RFC 5802.
+/// Returns the server-final-message if the client proof is correct.
+///
+/// `channel_binding` is the `tls-server-end-point` binding data of a secure
+/// connection, `SCRAM-SHA-256-PLUS` mechanism is offered only if it is set.
+fn scram_exchange<S>(
+    stream: &mut PgStream<S>,
+    verifier: &scram::Verifier,
+    channel_binding: Option<&[u8]>,
+) -> PgResult<Option<String>>
+where
+    S: io::Read + io::Write,
+{
+    let mechanisms: &[&str] = match channel_binding {
+        Some(_) => &[scram::MECHANISM_PLUS, scram::MECHANISM],
+        None => &[scram::MECHANISM],
+    };
+    stream.write_message(messages::sasl_auth_request(mechanisms))?;
+
+    let initial = read_password_message(stream)?.into_sasl_initial_response()?;
+    let with_binding = match initial.auth_method.as_str() {
+        scram::MECHANISM_PLUS if channel_binding.is_some() => true,
+        scram::MECHANISM => false,
+        other => {
+            return Err(PgError::ProtocolViolation(format!(
+                "client selected an invalid SASL authentication mechanism: {other}"
+            )))
+        }
+    };
+    let data = initial.data.unwrap_or_default();
+    let client_first = std::str::from_utf8(&data).map_err(|_| invalid_utf8())?;
+
+    // client-first-message = gs2-cbind-flag "," [authzid] "," client-first-bare
+    let mut parts = client_first.splitn(3, ',');
+    let (Some(cbind_flag), Some(authzid), Some(client_first_bare)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(malformed_scram_message(client_first));
+    };
+    match (cbind_flag, with_binding) {
+        ("p=tls-server-end-point", true) | ("n", false) => {}
+        ("y", false) if channel_binding.is_none() => {}
+        // The client supports channel binding but thinks the server does not,
+        // which means the mechanisms list was tampered with.
+        ("y", false) => {
+            return Err(PgError::ProtocolViolation(
+                "SCRAM channel binding negotiation error".into(),
+            ))
+        }
+        _ => {
+            return Err(PgError::ProtocolViolation(format!(
+                "unexpected SCRAM channel binding flag: {cbind_flag}"
+            )))
+        }
+    }
+    if !authzid.is_empty() {
+        return Err(PgError::FeatureNotSupported(
+            "client uses authorization identity".into(),
+        ));
+    }
+    let gs2_header = &client_first[..client_first.len() - client_first_bare.len()];
+
+    // client-first-bare = username "," nonce ["," extensions]
+    // The user name is ignored like in PostgreSQL, it is taken from the startup message.
+    let mut attributes = client_first_bare.split(',');
+    scram_attribute(attributes.next(), "n", client_first)?;
+    let client_nonce = scram_attribute(attributes.next(), "r", client_first)?;
+
+    let server_nonce: [u8; 18] = rand::random();
+    let nonce = format!("{client_nonce}{}", base64::encode_block(&server_nonce));
+    let server_first = format!(
+        "r={nonce},s={},i={}",
+        base64::encode_block(&verifier.salt),
+        verifier.iterations
+    );
+    stream.write_message(messages::sasl_continue(server_first.clone()))?;
+
+    let response = read_password_message(stream)?.into_sasl_response()?;
+    let client_final = std::str::from_utf8(&response.data).map_err(|_| invalid_utf8())?;
+
+    // client-final-message = channel-binding "," nonce ["," extensions] "," proof
+    let (without_proof, proof) = client_final
+        .rsplit_once(",p=")
+        .ok_or_else(|| malformed_scram_message(client_final))?;
+    let mut attributes = without_proof.split(',');
+    let binding = scram_attribute(attributes.next(), "c", client_final)?;
+    let final_nonce = scram_attribute(attributes.next(), "r", client_final)?;
+
+    let mut expected_binding = gs2_header.as_bytes().to_vec();
+    if with_binding {
+        expected_binding.extend_from_slice(channel_binding.unwrap_or_default());
+    }
+    if base64::decode_block(binding).ok() != Some(expected_binding) {
+        return Err(PgError::ProtocolViolation(
+            "SCRAM channel binding check failed".into(),
+        ));
+    }
+    if final_nonce != nonce {
+        return Err(PgError::ProtocolViolation(
+            "SCRAM nonce does not match".into(),
+        ));
+    }
+    let proof = base64::decode_block(proof).map_err(|_| malformed_scram_message(client_final))?;
+
+    let auth_message = format!("{client_first_bare},{server_first},{without_proof}");
+    if !verifier.verify_client_proof(auth_message.as_bytes(), &proof) {
+        return Ok(None);
+    }
+    let signature = verifier.server_signature(auth_message.as_bytes());
+    Ok(Some(format!("v={}", base64::encode_block(&signature))))
+}
+
+/// Authenticate the user having a SCRAM verifier. Tarantool doesn't support
+/// SCRAM, so the exchange is performed here and the session is switched to
+/// the user afterwards.
+fn scram_authenticate<S>(
+    stream: &mut PgStream<S>,
+    user: &UserDef,
+    verifier: &str,
+    storage: &Catalog,
+) -> PgResult<()>
+where
+    S: io::Read + io::Write,
+{
+    let err = || PgError::InvalidPassword(user.name.clone());
+    let verifier: scram::Verifier = verifier.parse().map_err(|e| {
+        tlog!(Warning, "user {}: {e}", user.name);
+        err()
+    })?;
+
+    let channel_binding = stream.tls_server_end_point();
+    let server_final = scram_exchange(stream, &verifier, channel_binding.as_deref())?;
+
+    // Tarantool runs its `on_auth` trigger only for its own authentication,
+    // so the login checks and the audit are done explicitly.
+    let checked = session::with_su(crate::schema::ADMIN_ID, || {
+        crate::on_auth(storage, &user.name, server_final.is_some())
+    })?;
+    let Some(server_final) = server_final else {
+        return Err(err());
+    };
+    checked.map_err(|_| err())?;
+
+    stream.write_message_noflush(messages::sasl_final(server_final))?;
+    // The guard is leaked not to switch the session back,
+    // like tarantool's authentication does.
+    std::mem::forget(session::su(user.id)?);
+    Ok(())
+}
+
 /// Perform exchange of authentication messages and authentication.
 /// Authentication failure is treated as an error.
 pub fn authenticate<S>(stream: &mut PgStream<S>, username: &str, storage: &Catalog) -> PgResult<()>
@@ -122,6 +304,12 @@ where
         return Err(err());
     };
 
+    if let Some(verifier) = &user.scram_verifier {
+        scram_authenticate(stream, &user, verifier, storage)?;
+        stream.write_message_noflush(messages::auth_ok())?;
+        return Ok(());
+    }
+
     let auth = user.auth.ok_or_else(err)?;
 
     // Note: salt is not used by ldap, but `authenticate_raw` still needs it.
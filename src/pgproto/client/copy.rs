@@ -0,0 +1,106 @@
+use crate::pgproto::backend::copy::{CopyIn, CopyStatement};
+use crate::pgproto::backend::describe::CommandTag;
+use crate::pgproto::backend::Backend;
+use crate::pgproto::stream::FeMessage;
+use crate::pgproto::{
+    error::{PgError, PgErrorCode, PgResult},
+    messages,
+    stream::PgStream,
+};
+use crate::tlog;
+use bytes::BytesMut;
+use std::io::{Read, Write};
+
+/// Number of rows sent in a single CopyData message of `COPY TO`.
+const ROWS_PER_MESSAGE: usize = 1000;
+
+pub fn process_copy(
+    stream: &mut PgStream<impl Read + Write>,
+    backend: &Backend,
+    statement: CopyStatement,
+) -> PgResult<()> {
+    match statement {
+        CopyStatement::From {
+            table,
+            columns,
+            options,
+        } => {
+            let mut copy_in = CopyIn::new(&table, &columns, options)?;
+            copy_from(stream, &mut copy_in).map_err(|e| copy_in.load_error(e))
+        }
+        CopyStatement::To { query, options } => {
+            let mut rows = backend.copy_out(&query, options.field_format())?;
+            let ncolumns = rows.column_names().len();
+            stream.write_message_noflush(messages::copy_out_response(
+                options.field_format(),
+                ncolumns,
+            ))?;
+
+            let mut buf = BytesMut::new();
+            options.encode_header(&rows.column_names(), &mut buf);
+            let mut row_count = 0;
+            while let Some(row) = rows.encode_next()? {
+                options.encode_row(row, &mut buf)?;
+                row_count += 1;
+                if row_count % ROWS_PER_MESSAGE == 0 {
+                    stream.write_message(messages::copy_data(buf.split().freeze()))?;
+                }
+            }
+            options.encode_trailer(&mut buf);
+            if !buf.is_empty() {
+                stream.write_message_noflush(messages::copy_data(buf.freeze()))?;
+            }
+            stream.write_message_noflush(messages::copy_done())?;
+            stream.write_message(messages::command_complete_with_row_count(
+                &CommandTag::Copy,
+                row_count,
+            ))?;
+            Ok(())
+        }
+    }
+}
+
+fn copy_from(stream: &mut PgStream<impl Read + Write>, copy_in: &mut CopyIn) -> PgResult<()> {
+    stream.write_message(messages::copy_in_response(
+        copy_in.field_format(),
+        copy_in.ncolumns(),
+    ))?;
+
+    // If something goes wrong, the rest of the data is still expected to come,
+    // so we report the error only after the client finishes sending it.
+    let mut error = None;
+    loop {
+        match stream.read_message()? {
+            FeMessage::CopyData(data) => {
+                if error.is_none() {
+                    error = copy_in.feed(&data.data).err();
+                }
+            }
+            FeMessage::CopyDone(_) => break,
+            FeMessage::CopyFail(fail) => {
+                return Err(PgError::WithExplicitCode(
+                    PgErrorCode::QueryCanceled,
+                    format!("COPY from stdin failed: {}", fail.message),
+                ))
+            }
+            // Some clients send these along with the data, they are harmless.
+            FeMessage::Flush(_) | FeMessage::Sync(_) => {}
+            message => {
+                return Err(PgError::ProtocolViolation(format!(
+                    "unexpected message during COPY from stdin: {message:?}"
+                )))
+            }
+        }
+    }
+
+    if let Some(error) = error {
+        return Err(error);
+    }
+    let row_count = copy_in.finish()?;
+    tlog!(Debug, "copied {row_count} rows from stdin");
+    stream.write_message(messages::command_complete_with_row_count(
+        &CommandTag::Copy,
+        row_count,
+    ))?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+use super::copy;
+use crate::pgproto::backend::copy as copy_statement;
 use crate::pgproto::backend::result::ExecuteResult;
 use crate::pgproto::backend::Backend;
 use crate::pgproto::error::PgError;
@@ -10,6 +12,11 @@ pub fn process_query_message(
     backend: &Backend,
     query: Query,
 ) -> PgResult<()> {
+    // COPY is not supported by sbroad, so it is handled separately.
+    if let Some(statement) = copy_statement::parse(&query.query)? {
+        return copy::process_copy(stream, backend, statement);
+    }
+
     match backend.simple_query(&query.query)? {
         ExecuteResult::AclOrDdl { tag } => {
             stream.write_message(messages::command_complete(&tag))?;
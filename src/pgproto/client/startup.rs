@@ -1,12 +1,17 @@
+use super::ClientId;
 use crate::pgproto::error::{PgError, PgResult};
 use crate::pgproto::messages;
-use crate::pgproto::stream::{FeMessage, PgStream};
+use crate::pgproto::stream::{BeMessage, FeMessage, PgStream};
 use crate::pgproto::tls::TlsAcceptor;
+use crate::sql::cancel;
+use crate::sql::transaction::SessionKey;
 use crate::tlog;
+use pgwire::messages::cancel::CancelRequest;
 use pgwire::messages::startup::Startup;
 use sbroad::ir::value::Value as SbroadValue;
 use sbroad::ir::{OptionKind, OptionParamValue, OptionSpec};
-use std::collections::BTreeMap;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Read, Write};
 
 #[derive(Clone, Debug)]
@@ -92,6 +97,63 @@ impl ClientParams {
     }
 }
 
+thread_local! {
+    /// Keys issued to the connected clients: pid -> (secret key, client id).
+    static CANCEL_KEYS: RefCell<HashMap<i32, (i32, ClientId)>> = RefCell::new(HashMap::new());
+}
+
+/// Key the client uses to cancel its queries, see
+/// <https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-FLOW-CANCELING-REQUESTS>.
+///
+/// The key is revoked when dropped.
+pub struct BackendKey {
+    pid: i32,
+    secret_key: i32,
+}
+
+impl BackendKey {
+    pub fn issue(client_id: ClientId) -> Self {
+        // There are no processes, so we use client id as pid.
+        let pid = (client_id & i32::MAX as ClientId) as i32;
+        let secret_key = rand::random::<i32>();
+        CANCEL_KEYS.with(|keys| keys.borrow_mut().insert(pid, (secret_key, client_id)));
+        Self { pid, secret_key }
+    }
+
+    /// BackendKeyData message to be sent to the client.
+    pub fn message(&self) -> BeMessage {
+        messages::backend_key_data(self.pid, self.secret_key)
+    }
+}
+
+impl Drop for BackendKey {
+    fn drop(&mut self) {
+        CANCEL_KEYS.with(|keys| keys.borrow_mut().remove(&self.pid));
+    }
+}
+
+/// Cancel the query running in the connection the key was issued for.
+/// According to the protocol, the server doesn't respond to the request.
+pub fn process_cancel_request(request: CancelRequest) {
+    let client_id = CANCEL_KEYS.with(|keys| match keys.borrow().get(&request.pid) {
+        Some((secret_key, client_id)) if *secret_key == request.secret_key => Some(*client_id),
+        _ => None,
+    });
+    let Some(client_id) = client_id else {
+        tlog!(Warning, "ignoring cancel request with an invalid key");
+        return;
+    };
+
+    match cancel::cancel_session(SessionKey::Pgproto(client_id)) {
+        Ok(true) => tlog!(Info, "cancelled the query of client {client_id}"),
+        Ok(false) => tlog!(Debug, "client {client_id} has no query to cancel"),
+        Err(e) => tlog!(
+            Warning,
+            "failed to cancel the query of client {client_id}: {e}"
+        ),
+    }
+}
+
 fn parse_startup(startup: Startup) -> PgResult<ClientParams> {
     tlog!(Debug, "client parameters: {:?}", &startup.parameters);
     ClientParams::new(startup.parameters)
@@ -110,11 +172,18 @@ fn handle_ssl_request<S: Read + Write>(
     stream.into_secure(acceptor)
 }
 
+/// The first message of a connection is either Startup or CancelRequest.
+pub enum Handshake<S> {
+    Startup(PgStream<S>, ClientParams),
+    Cancel(CancelRequest),
+}
+
 /// Respond to SslRequest if you receive it, read startup message, verify parameters and return them.
+/// If the connection is opened to cancel a query, return the cancel request instead.
 pub fn handshake<S: Read + Write>(
     mut stream: PgStream<S>,
     tls_acceptor: Option<&TlsAcceptor>,
-) -> PgResult<(PgStream<S>, ClientParams)> {
+) -> PgResult<Handshake<S>> {
     let mut waiting_for_ssl = tls_acceptor.is_some();
     let mut client_attempted_ssl = false;
 
@@ -129,8 +198,10 @@ pub fn handshake<S: Read + Write>(
                     return Err(PgError::SslRequired);
                 }
 
-                return Ok((stream, parse_startup(startup)?));
+                return Ok(Handshake::Startup(stream, parse_startup(startup)?));
             }
+            // Cancel requests are accepted regardless of ssl, as postgres does.
+            FeMessage::CancelRequest(request) => return Ok(Handshake::Cancel(request)),
             FeMessage::SslRequest(_) => {
                 if client_attempted_ssl {
                     // ssl handshake was already attempted
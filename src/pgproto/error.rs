@@ -13,6 +13,8 @@ pub enum PgErrorCode {
     InvalidPassword,
     IoError,
     ProtocolViolation,
+    QueryCanceled,
+    SyntaxError,
 }
 
 impl PgErrorCode {
@@ -26,6 +28,8 @@ impl PgErrorCode {
             PgErrorCode::InvalidPassword => "28P01",
             PgErrorCode::IoError => "58030",
             PgErrorCode::ProtocolViolation => "08P01",
+            PgErrorCode::QueryCanceled => "57014",
+            PgErrorCode::SyntaxError => "42601",
         }
     }
 }
@@ -148,7 +152,7 @@ impl IntoBoxError for PgError {}
 
 impl PgError {
     /// Convert the error into a corresponding postgres error code.
-    fn code(&self) -> PgErrorCode {
+    pub fn code(&self) -> PgErrorCode {
         use PgError::*;
         match self {
             InternalError(_) => PgErrorCode::InternalError,
@@ -158,6 +162,10 @@ impl PgError {
             InvalidPassword(_) => PgErrorCode::InvalidPassword,
             IoError(_) => PgErrorCode::InvalidPassword,
             WithExplicitCode(code, _) => *code,
+            PicodataError(
+                crate::traft::error::Error::QueryCanceled
+                | crate::traft::error::Error::StatementTimeout,
+            ) => PgErrorCode::QueryCanceled,
             // TODO: make the code depending on the error kind
             _otherwise => PgErrorCode::InternalError,
         }
@@ -1,6 +1,9 @@
 use super::backend::describe::CommandTag;
 use super::stream::BeMessage;
+use super::value::{FieldFormat, RawFormat};
+use bytes::Bytes;
 use pgwire::error::ErrorInfo;
+use pgwire::messages::copy::{CopyData, CopyDone, CopyInResponse, CopyOutResponse};
 use pgwire::messages::data::{self, DataRow, ParameterDescription, RowDescription};
 use pgwire::messages::extendedquery::{
     BindComplete, CloseComplete, ParseComplete, PortalSuspended,
@@ -20,14 +23,35 @@ pub fn cleartext_auth_request() -> BeMessage {
     BeMessage::Authentication(Authentication::CleartextPassword)
 }
 
+/// AuthenticationSASL starts SASL authentication with one of the mechanisms.
+pub fn sasl_auth_request(mechanisms: &[&str]) -> BeMessage {
+    let mechanisms = mechanisms.iter().map(|m| m.to_string()).collect();
+    BeMessage::Authentication(Authentication::SASL(mechanisms))
+}
+
+/// AuthenticationSASLContinue sends the SASL challenge to the frontend.
+pub fn sasl_continue(data: String) -> BeMessage {
+    BeMessage::Authentication(Authentication::SASLContinue(Bytes::from(data)))
+}
+
+/// AuthenticationSASLFinal sends the SASL outcome to the frontend.
+pub fn sasl_final(data: String) -> BeMessage {
+    BeMessage::Authentication(Authentication::SASLFinal(Bytes::from(data)))
+}
+
 /// AuthOk informs the frontend that the authentication has been passed.
 pub const fn auth_ok() -> BeMessage {
     BeMessage::Authentication(Authentication::Ok)
 }
 
+/// BackendKeyData provides the key the frontend must use to cancel its queries.
+pub fn backend_key_data(pid: i32, secret_key: i32) -> BeMessage {
+    BeMessage::BackendKeyData(BackendKeyData::new(pid, secret_key))
+}
+
 /// ReadyForQuery informs the frontend that it can safely send a new command.
-pub fn ready_for_query() -> BeMessage {
-    BeMessage::ReadyForQuery(ReadyForQuery::new(TransactionStatus::Idle))
+pub fn ready_for_query(status: TransactionStatus) -> BeMessage {
+    BeMessage::ReadyForQuery(ReadyForQuery::new(status))
 }
 
 /// ErrorResponse informs the client about the error.
@@ -90,3 +114,33 @@ pub fn close_complete() -> BeMessage {
 pub fn parameter_description(type_ids: Vec<Oid>) -> BeMessage {
     BeMessage::ParameterDescription(ParameterDescription::new(type_ids))
 }
+
+/// CopyInResponse tells the frontend to start sending COPY data.
+pub fn copy_in_response(format: FieldFormat, ncolumns: usize) -> BeMessage {
+    let format = format as RawFormat;
+    BeMessage::CopyInResponse(CopyInResponse::new(
+        format as i8,
+        ncolumns as i16,
+        vec![format; ncolumns],
+    ))
+}
+
+/// CopyOutResponse tells the frontend that COPY data is coming.
+pub fn copy_out_response(format: FieldFormat, ncolumns: usize) -> BeMessage {
+    let format = format as RawFormat;
+    BeMessage::CopyOutResponse(CopyOutResponse::new(
+        format as i8,
+        ncolumns as i16,
+        vec![format; ncolumns],
+    ))
+}
+
+/// CopyData carries a chunk of COPY data.
+pub fn copy_data(data: Bytes) -> BeMessage {
+    BeMessage::CopyData(CopyData::new(data))
+}
+
+/// CopyDone informs the frontend that there is no more COPY data.
+pub fn copy_done() -> BeMessage {
+    BeMessage::CopyDone(CopyDone::new())
+}
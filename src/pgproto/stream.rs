@@ -1,9 +1,10 @@
 use super::{
     error::{PgError, PgResult},
-    tls::{TlsAcceptor, TlsStream},
+    tls::{self, TlsAcceptor, TlsStream},
 };
 use crate::tlog;
 use bytes::{BufMut, BytesMut};
+use pgwire::messages::cancel::CancelRequest;
 use pgwire::messages::startup::SslRequest;
 use std::io::{self, ErrorKind::UnexpectedEof, Write};
 
@@ -100,6 +101,11 @@ impl<S: io::Read + io::Write> PgStream<S> {
             return Ok(Some(FeMessage::SslRequest(ssl_request)));
         }
 
+        // CancelRequest is sent instead of Startup via a new connection.
+        if let Some(cancel_request) = CancelRequest::decode(&mut self.ibuf)? {
+            return Ok(Some(FeMessage::CancelRequest(cancel_request)));
+        }
+
         // This is done once at connection startup.
         let startup = Startup::decode(&mut self.ibuf)?.map(|x| {
             tlog!(Debug, "received StartupPacket from client");
@@ -150,6 +156,17 @@ impl<S: io::Read + io::Write> PgStream<S> {
     }
 }
 
+impl<S> PgStream<S> {
+    /// Channel binding data of `tls-server-end-point` type,
+    /// `None` if the connection is not secure.
+    pub fn tls_server_end_point(&self) -> Option<Vec<u8>> {
+        match &self.socket {
+            PgSocket::Plain(_) => None,
+            PgSocket::Secure(socket) => tls::server_end_point(socket.ssl()),
+        }
+    }
+}
+
 impl<S: io::Read + io::Write> PgStream<S> {
     pub fn into_secure(self, acceptor: &TlsAcceptor) -> PgResult<PgStream<S>> {
         let PgSocket::Plain(socket) = self.socket else {
@@ -1,4 +1,5 @@
-use openssl::ssl::SslVerifyMode;
+use openssl::hash::MessageDigest;
+use openssl::ssl::{SslRef, SslVerifyMode};
 use openssl::x509::store::X509StoreBuilder;
 use openssl::x509::X509;
 use openssl::{
@@ -66,6 +67,27 @@ impl TlsConfig {
 
 pub type TlsStream<S> = SslStream<S>;
 
+/// Hash of the server certificate used for `tls-server-end-point`
+/// channel binding, see RFC 5929, section 4.1.
+pub fn server_end_point(ssl: &SslRef) -> Option<Vec<u8>> {
+    let cert = ssl.certificate()?;
+    let sha256 = MessageDigest::sha256();
+    let digest = cert
+        .signature_algorithm()
+        .object()
+        .nid()
+        .signature_algorithms()
+        .and_then(|algorithms| MessageDigest::from_nid(algorithms.digest))
+        // MD5 and SHA-1 are replaced with SHA-256.
+        .filter(|digest| {
+            ![MessageDigest::md5(), MessageDigest::sha1()]
+                .iter()
+                .any(|weak| weak.type_() == digest.type_())
+        })
+        .unwrap_or(sha256);
+    cert.digest(digest).ok().map(|hash| hash.to_vec())
+}
+
 #[derive(Clone)]
 pub struct TlsAcceptor(Rc<ssl::SslAcceptor>);
 
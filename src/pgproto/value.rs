@@ -2,7 +2,9 @@ use crate::pgproto::error::{DecodingError, EncodingError, PgError, PgResult};
 use bytes::{BufMut, Bytes, BytesMut};
 use pgwire::{api::results::DataRowEncoder, error::PgWireResult, types::ToSqlText};
 use postgres_types::{FromSql, IsNull, Oid, ToSql, Type};
-use sbroad::ir::value::Value as SbroadValue;
+use sbroad::ir::value::{
+    interval::Interval as SbroadInterval, varbinary::Varbinary, Value as SbroadValue,
+};
 use smol_str::{StrExt, ToSmolStr};
 use std::{
     error::Error,
@@ -267,6 +269,100 @@ impl ToSql for Timestamptz {
     postgres_types::to_sql_checked!();
 }
 
+/// Bytea wrapper for smooth encoding & decoding.
+#[derive(Debug, Clone)]
+#[repr(transparent)]
+pub struct Bytea(Varbinary);
+
+impl FromStr for Bytea {
+    type Err = SqlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = Varbinary::from_str(s).map_err(|e| e.to_string())?;
+        Ok(Self(bytes))
+    }
+}
+
+impl<'a> FromSql<'a> for Bytea {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> SqlResult<Self> {
+        let bytes = Vec::<u8>::from_sql(ty, raw)?;
+        Ok(Self(bytes.into()))
+    }
+
+    postgres_types::accepts!(BYTEA);
+}
+
+impl ToSqlText for Bytea {
+    fn to_sql_text(&self, _ty: &Type, out: &mut BytesMut) -> SqlResult<IsNull> {
+        // Hex format, e.g. `\xdeadbeef`.
+        self.0.to_string().to_sql_text(&Type::TEXT, out)
+    }
+}
+
+impl ToSql for Bytea {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> SqlResult<IsNull> {
+        self.0.as_bytes().to_sql(ty, out)
+    }
+
+    postgres_types::accepts!(BYTEA);
+    postgres_types::to_sql_checked!();
+}
+
+/// Interval wrapper for smooth encoding & decoding.
+#[derive(Debug, Copy, Clone)]
+#[repr(transparent)]
+pub struct Interval(SbroadInterval);
+
+impl FromStr for Interval {
+    type Err = SqlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let interval = SbroadInterval::from_str(s).map_err(|e| e.to_string())?;
+        Ok(Self(interval))
+    }
+}
+
+impl<'a> FromSql<'a> for Interval {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> SqlResult<Self> {
+        // Binary format: microseconds (i64), days (i32), months (i32).
+        let raw: [u8; 16] = raw
+            .try_into()
+            .map_err(|_| format!("invalid interval length: {}", raw.len()))?;
+        let (micros, rest) = raw.split_at(8);
+        let (days, months) = rest.split_at(4);
+        let micros = i64::from_be_bytes(micros.try_into().expect("8 bytes"));
+        let days = i32::from_be_bytes(days.try_into().expect("4 bytes"));
+        let months = i32::from_be_bytes(months.try_into().expect("4 bytes"));
+        let nanoseconds = micros
+            .checked_mul(1000)
+            .ok_or_else(|| format!("interval out of range: {micros} microseconds"))?;
+        Ok(Self(SbroadInterval::new(months, days, nanoseconds)))
+    }
+
+    postgres_types::accepts!(INTERVAL);
+}
+
+impl ToSqlText for Interval {
+    fn to_sql_text(&self, _ty: &Type, out: &mut BytesMut) -> SqlResult<IsNull> {
+        // Postgres style, e.g. `1 year 2 mons 3 days 04:05:06`.
+        self.0.to_string().to_sql_text(&Type::TEXT, out)
+    }
+}
+
+impl ToSql for Interval {
+    fn to_sql(&self, _ty: &Type, out: &mut BytesMut) -> SqlResult<IsNull> {
+        // Tarantool intervals have nanosecond precision, but postgres
+        // transfers microseconds.
+        out.put_i64(self.0.nanoseconds / 1000);
+        out.put_i32(self.0.days);
+        out.put_i32(self.0.months);
+        Ok(IsNull::No)
+    }
+
+    postgres_types::accepts!(INTERVAL);
+    postgres_types::to_sql_checked!();
+}
+
 #[derive(Debug, Clone)]
 pub enum PgValue {
     Float(f64),
@@ -277,6 +373,8 @@ pub enum PgValue {
     Json(Json),
     Uuid(Uuid),
     Numeric(Decimal),
+    Bytea(Bytea),
+    Interval(Interval),
     Null,
 }
 
@@ -292,6 +390,8 @@ impl TryFrom<PgValue> for SbroadValue {
             PgValue::Numeric(v) => Ok(SbroadValue::from(v.0)),
             PgValue::Uuid(v) => Ok(SbroadValue::from(v.0)),
             PgValue::Timestamptz(datetime) => Ok(SbroadValue::Datetime(datetime.0)),
+            PgValue::Bytea(v) => Ok(SbroadValue::Varbinary(v.0)),
+            PgValue::Interval(v) => Ok(SbroadValue::Interval(v.0)),
             PgValue::Null => Ok(SbroadValue::Null),
             PgValue::Json(v) => {
                 // Anyhow, currently Sbroad cannot work with these types.
@@ -373,6 +473,13 @@ impl PgValue {
                 let datetime = rmpv::ext::from_value(value).map_err(EncodingError::new)?;
                 Ok(PgValue::Timestamptz(datetime))
             }
+            (Value::Binary(v), Type::BYTEA) => Ok(PgValue::Bytea(Bytea(v.clone().into()))),
+            (Value::Ext(6, data), Type::INTERVAL) => {
+                let interval = SbroadInterval::from_ext_data(data).ok_or_else(|| {
+                    EncodingError::new(format!("couldn't encode interval: {data:?}"))
+                })?;
+                Ok(PgValue::Interval(Interval(interval)))
+            }
             (_any, Type::JSON | Type::JSONB) => Ok(PgValue::Json(Json(value))),
 
             (value, ty) => Err(PgError::FeatureNotSupported(format!(
@@ -400,6 +507,8 @@ impl PgValue {
             PgValue::Uuid(v) => do_encode(encoder, v, Type::UUID, format),
             PgValue::Numeric(v) => do_encode(encoder, v, Type::NUMERIC, format),
             PgValue::Timestamptz(v) => do_encode(encoder, v, Type::TIMESTAMPTZ, format),
+            PgValue::Bytea(v) => do_encode(encoder, v, Type::BYTEA, format),
+            PgValue::Interval(v) => do_encode(encoder, v, Type::INTERVAL, format),
             PgValue::Null => {
                 // XXX: one could call this a clever hack...
                 do_encode(encoder, &None::<i64>, Type::INT8, format)
@@ -425,6 +534,8 @@ impl PgValue {
             Type::UUID => PgValue::Uuid(do_parse(&s)?),
             Type::JSON | Type::JSONB => PgValue::Json(do_parse(&s)?),
             Type::TIMESTAMPTZ => PgValue::Timestamptz(do_parse(&s)?),
+            Type::BYTEA => PgValue::Bytea(do_parse(&s)?),
+            Type::INTERVAL => PgValue::Interval(do_parse(&s)?),
             _ => return Err(PgError::FeatureNotSupported(format!("type {ty}"))),
         })
     }
@@ -446,6 +557,8 @@ impl PgValue {
             Type::UUID => PgValue::Uuid(do_decode(ty, bytes)?),
             Type::JSON | Type::JSONB => PgValue::Json(do_decode(ty, bytes)?),
             Type::TIMESTAMPTZ => PgValue::Timestamptz(do_decode(ty, bytes)?),
+            Type::BYTEA => PgValue::Bytea(do_decode(ty, bytes)?),
+            Type::INTERVAL => PgValue::Interval(do_decode(ty, bytes)?),
             _ => return Err(PgError::FeatureNotSupported(format!("type {ty}"))),
         })
     }
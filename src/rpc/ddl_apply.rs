@@ -1,4 +1,5 @@
 use crate::op::Ddl;
+use crate::storage::schema::ddl_add_fields_on_master;
 use crate::storage::schema::ddl_change_format_on_master;
 use crate::storage::schema::ddl_create_function_on_master;
 use crate::storage::schema::ddl_create_index_on_master;
@@ -218,8 +219,12 @@ pub fn apply_schema_change(
         Ddl::ChangeFormat {
             table_id,
             ref new_format,
+            ref added_values,
             ..
         } => {
+            if let Err(e) = ddl_add_fields_on_master(table_id, added_values) {
+                return Err(Error::Aborted(e.into()));
+            }
             if let Err(e) = ddl_change_format_on_master(table_id, new_format) {
                 return Err(Error::Aborted(e.into()));
             }
@@ -271,6 +276,25 @@ pub fn apply_schema_change(
                 return Err(Error::Aborted(e));
             }
         }
+
+        Ddl::CreateSequence { .. } | Ddl::DropSequence { .. } => {
+            // Sequences live only in picodata's system tables, there's no
+            // tarantool object to create or drop.
+        }
+
+        Ddl::CreateView { .. } | Ddl::DropView { .. } => {
+            // Same as sequences, views exist only in picodata's system tables.
+        }
+
+        Ddl::CreateSchema { .. } | Ddl::DropSchema { .. } => {
+            // Tables of a schema are ordinary tarantool spaces with qualified
+            // names, the schemas themselves exist only in picodata's system tables.
+        }
+
+        Ddl::DetachPartition { .. } => {
+            // Partitions are regular tables, only the metadata in
+            // picodata's system tables is changed.
+        }
     }
 
     if let Err(e) = set_local_schema_version(version) {
@@ -5,6 +5,7 @@ use crate::instance::InstanceName;
 use crate::pico_service::pico_service_password;
 use crate::plugin::PluginIdentifier;
 use crate::plugin::ServiceId;
+use crate::storage::schema::create_table_constraints;
 use crate::storage::*;
 use crate::tier::DEFAULT_TIER;
 use crate::tlog;
@@ -15,11 +16,13 @@ use crate::traft::{self, node, RaftIndex};
 use crate::util::effective_user_id;
 use ahash::AHashSet;
 use picodata_plugin::error_code::ErrorCode;
-use sbroad::ir::ddl::{Language, ParamDef};
+use sbroad::cbo::analyze::EncodedColumnStats;
+use sbroad::executor::engine::helpers::DEFAULT_SCHEMA;
+use sbroad::ir::ddl::{Language, ParamDef, PartitionBound, PartitionStrategy};
 use sbroad::ir::value::Value as IrValue;
 use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Display;
 use std::str::FromStr;
 use std::time::Duration;
@@ -53,6 +56,12 @@ use tarantool::util::{NumOrStr, Value};
 /// definition is builtin and is applied by default on all instances.
 pub const INITIAL_SCHEMA_VERSION: u64 = 0;
 
+/// Version of the system catalog of this picodata version, it is stored
+/// in `_pico_property.system_catalog_version`. A cluster bootstrapped by
+/// an older version is upgraded by the governor with
+/// [`system_catalog_upgrade_ops`] once all the instances are upgraded.
+pub const SYSTEM_CATALOG_VERSION: &str = "25.2.0";
+
 ////////////////////////////////////////////////////////////////////////////////
 // TableDef
 ////////////////////////////////////////////////////////////////////////////////
@@ -71,6 +80,13 @@ pub struct TableDef {
     pub engine: SpaceEngineType,
     pub owner: UserId,
     pub description: String,
+    /// Default values of the table columns. Is optional, so the tables
+    /// stored before the column defaults were introduced are still decoded.
+    pub defaults: Option<Vec<ColumnDefault>>,
+    /// Declarative partitioning of the table, if any.
+    pub partition: Option<TablePartition>,
+    /// `CHECK` and `FOREIGN KEY` constraints of the table.
+    pub constraints: Option<Vec<TableConstraint>>,
 }
 
 impl TableDef {
@@ -91,6 +107,9 @@ impl TableDef {
             Field::from(("engine", FieldType::String)).is_nullable(false),
             Field::from(("owner", FieldType::Unsigned)).is_nullable(false),
             Field::from(("description", FieldType::String)).is_nullable(false),
+            Field::from(("defaults", FieldType::Array)).is_nullable(true),
+            Field::from(("partition", FieldType::Map)).is_nullable(true),
+            Field::from(("constraints", FieldType::Array)).is_nullable(true),
         ]
     }
 
@@ -111,6 +130,9 @@ impl TableDef {
             engine: SpaceEngineType::Blackhole,
             owner: 42,
             description: "A table for tests".into(),
+            defaults: None,
+            partition: None,
+            constraints: None,
         }
     }
 
@@ -155,6 +177,9 @@ pub fn system_table_definitions() -> Vec<(TableDef, Vec<IndexDef>)> {
                     engine: SpaceEngineType::Memtx,
                     owner: ADMIN_ID,
                     description: $table::DESCRIPTION.into(),
+                    defaults: None,
+                    partition: None,
+                    constraints: None,
                 };
                 let index_defs = $table::index_definitions();
                 $result.push((table_def, index_defs));
@@ -180,7 +205,11 @@ pub fn system_table_definitions() -> Vec<(TableDef, Vec<IndexDef>)> {
         ServiceRouteTable,
         PluginMigrations,
         PluginConfig,
-        DbConfig
+        DbConfig,
+        Sequences,
+        Views,
+        Schemas,
+        TableStatistics
     );
 
     // TODO: there's also "_raft_log" & "_raft_state" spaces, but we don't treat
@@ -189,6 +218,42 @@ pub fn system_table_definitions() -> Vec<(TableDef, Vec<IndexDef>)> {
     result
 }
 
+/// Operations upgrading the definitions of builtin tables in "_pico_table" &
+/// "_pico_index" of a cluster bootstrapped by an older version of picodata to
+/// [`SYSTEM_CATALOG_VERSION`]: the new system tables are added and the formats
+/// of the existing ones get the new fields. The local spaces are already
+/// created or altered on the instances start.
+pub fn system_catalog_upgrade_ops(
+    tables: &HashMap<SpaceId, &TableDef>,
+) -> tarantool::Result<Vec<crate::traft::op::Dml>> {
+    use crate::traft::op::Dml;
+
+    let mut ops = Vec::new();
+    for (table_def, index_defs) in system_table_definitions() {
+        let is_new = match tables.get(&table_def.id) {
+            Some(current) if current.format == table_def.format => continue,
+            Some(_) => false,
+            None => true,
+        };
+        ops.push(Dml::replace_raw(
+            Tables::TABLE_ID,
+            msgpack::encode(&table_def),
+            ADMIN_ID,
+        )?);
+        if is_new {
+            for index_def in index_defs {
+                ops.push(Dml::replace(Indexes::TABLE_ID, &index_def, ADMIN_ID)?);
+            }
+        }
+    }
+    ops.push(Dml::replace(
+        Properties::TABLE_ID,
+        &(PropertyName::SystemCatalogVersion, SYSTEM_CATALOG_VERSION),
+        ADMIN_ID,
+    )?);
+    Ok(ops)
+}
+
 // FIXME: move this to tarantool-module
 pub fn fields_to_format(
     fields: &[tarantool::space::Field],
@@ -843,6 +908,15 @@ pub struct UserDef {
     pub owner: UserId,
     #[serde(rename = "type")]
     pub ty: UserMetadataKind,
+    /// SCRAM-SHA-256 verifier of the password, see [`crate::scram::Verifier`].
+    /// Is set for the users created with `USING scram-sha-256`, `auth` of
+    /// such users holds the chap-sha1 data used by iproto connections.
+    #[serde(default)]
+    pub scram_verifier: Option<String>,
+    /// Default statement timeout of the user's sessions in milliseconds,
+    /// see `ALTER USER ... SET statement_timeout`.
+    #[serde(default)]
+    pub statement_timeout: Option<u64>,
 }
 
 impl Encode for UserDef {}
@@ -872,6 +946,8 @@ impl UserDef {
             Field::from(("auth", FieldType::Array)).is_nullable(true),
             Field::from(("owner", FieldType::Unsigned)).is_nullable(false),
             Field::from(("type", FieldType::String)).is_nullable(false),
+            Field::from(("scram_verifier", FieldType::String)).is_nullable(true),
+            Field::from(("statement_timeout", FieldType::Unsigned)).is_nullable(true),
         ]
     }
 
@@ -885,6 +961,8 @@ impl UserDef {
             auth: Some(AuthDef::new(AuthMethod::Md5, "".into())),
             owner: 42,
             ty: UserMetadataKind::User,
+            scram_verifier: None,
+            statement_timeout: None,
         }
     }
 
@@ -998,6 +1076,8 @@ tarantool::define_str_enum! {
         Routine = "routine",
         User = "user",
         Universe = "universe",
+        View = "view",
+        Schema = "schema",
     }
 }
 
@@ -1034,6 +1114,8 @@ tarantool::define_str_enum! {
         Drop = "drop",
         /// Allows SQL queries: `ALTER`
         Alter = "alter",
+        /// Allows SQL queries referencing the tables and views of a schema
+        Usage = "usage",
     }
 }
 
@@ -1132,6 +1214,13 @@ impl PrivilegeDef {
                 Some(_) => &[Execute, Drop],
                 None => &[Create, Drop, Execute],
             },
+            // Views are dropped and replaced by their owners only.
+            SchemaObjectType::View => &[Read],
+            // Schemas are dropped by their owners only.
+            SchemaObjectType::Schema => match privilege_def.object_id() {
+                Some(_) => &[Usage, Create],
+                None => &[Create],
+            },
         };
 
         if !valid_privileges.contains(&privilege) {
@@ -1246,6 +1335,8 @@ impl PrivilegeDef {
                 return Ok(None);
             }
             SchemaObjectType::Routine => storage.routines.by_id(id).map(|t| t.map(|t| t.name)),
+            SchemaObjectType::View => storage.views.by_id(id).map(|t| t.map(|t| t.name)),
+            SchemaObjectType::Schema => storage.schemas.by_id(id).map(|t| t.map(|t| t.name)),
         }
         .expect("storage should not fail")
         .ok_or_else(|| Error::other(format!("object with id {id} should exist")))?;
@@ -1280,6 +1371,8 @@ pub fn system_user_definitions() -> Vec<(UserDef, Vec<PrivilegeDef>)> {
             )),
             owner: initiator,
             ty: UserMetadataKind::User,
+            scram_verifier: None,
+            statement_timeout: None,
         };
         let priv_defs = vec![
             PrivilegeDef {
@@ -1321,6 +1414,8 @@ pub fn system_user_definitions() -> Vec<(UserDef, Vec<PrivilegeDef>)> {
             auth: Some(AuthDef::new(AuthMethod::Md5, "".into())),
             owner: initiator,
             ty: UserMetadataKind::User,
+            scram_verifier: None,
+            statement_timeout: None,
         };
         let mut priv_defs = Vec::with_capacity(PrivilegeType::VARIANTS.len());
         // Grant all privileges on "universe" to "admin".
@@ -1358,6 +1453,8 @@ pub fn system_user_definitions() -> Vec<(UserDef, Vec<PrivilegeDef>)> {
             )),
             owner: initiator,
             ty: UserMetadataKind::User,
+            scram_verifier: None,
+            statement_timeout: None,
         };
         let mut priv_defs = Vec::with_capacity(PrivilegeType::VARIANTS.len() + 1);
         // Grant all privileges on "universe" to "pico_service".
@@ -1402,6 +1499,8 @@ pub fn system_role_definitions() -> Vec<(UserDef, Vec<PrivilegeDef>)> {
         owner: ADMIN_ID,
         auth: None,
         ty: UserMetadataKind::Role,
+        scram_verifier: None,
+        statement_timeout: None,
     };
     let public_privs = vec![
         // TODO:
@@ -1418,6 +1517,8 @@ pub fn system_role_definitions() -> Vec<(UserDef, Vec<PrivilegeDef>)> {
         owner: ADMIN_ID,
         auth: None,
         ty: UserMetadataKind::Role,
+        scram_verifier: None,
+        statement_timeout: None,
     };
     let super_privs = vec![
         // Special role, it's privileges are implicit
@@ -1432,6 +1533,8 @@ pub fn system_role_definitions() -> Vec<(UserDef, Vec<PrivilegeDef>)> {
         owner: ADMIN_ID,
         auth: None,
         ty: UserMetadataKind::Role,
+        scram_verifier: None,
+        statement_timeout: None,
     };
     let replication_privs = vec![];
     result.push((replication_def, replication_privs));
@@ -1673,6 +1776,242 @@ impl RoutineDef {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// SequenceDef
+////////////////////////////////////////////////////////////////////////////////
+
+/// Sequence definition.
+///
+/// Values of a sequence are `start + increment * n` for `n = 0, 1, ...`.
+/// Instances don't ask the raft leader for every single value, instead they
+/// pre-allocate ranges of `n` by moving the `allocated` mark forward and then
+/// hand out the values locally.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SequenceDef {
+    pub id: SequenceId,
+    pub name: String,
+    pub start: i64,
+    pub increment: i64,
+    /// Number of values already allocated to the instances.
+    pub allocated: u64,
+    pub operable: bool,
+    pub schema_version: u64,
+    pub owner: UserId,
+    /// Id of the table owning the sequence created for its `SERIAL` or
+    /// identity column. The sequence is dropped along with the table.
+    #[serde(default)]
+    pub table_id: Option<SpaceId>,
+}
+
+impl Encode for SequenceDef {}
+
+impl SequenceDef {
+    /// Format of the _pico_sequence global table.
+    #[inline(always)]
+    pub fn format() -> Vec<tarantool::space::Field> {
+        use tarantool::space::Field;
+        vec![
+            Field::from(("id", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("name", FieldType::String)).is_nullable(false),
+            Field::from(("start", FieldType::Integer)).is_nullable(false),
+            Field::from(("increment", FieldType::Integer)).is_nullable(false),
+            Field::from(("allocated", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("operable", FieldType::Boolean)).is_nullable(false),
+            Field::from(("schema_version", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("owner", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("table_id", FieldType::Unsigned)).is_nullable(true),
+        ]
+    }
+
+    /// Returns the `n`-th value of the sequence or `None` if it doesn't fit
+    /// into the integer range.
+    #[inline]
+    pub fn nth_value(&self, n: u64) -> Option<i64> {
+        let n = i64::try_from(n).ok()?;
+        self.increment.checked_mul(n)?.checked_add(self.start)
+    }
+
+    /// A dummy instance of the type for use in tests.
+    #[inline(always)]
+    pub fn for_tests() -> Self {
+        Self {
+            id: 3,
+            name: "seq".into(),
+            start: 1,
+            increment: -2,
+            allocated: 100,
+            operable: true,
+            schema_version: 421,
+            owner: 42,
+            table_id: Some(10569),
+        }
+    }
+}
+
+/// Sequence created along with the table for its `SERIAL` or identity column.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TableSequence {
+    pub id: SequenceId,
+    pub name: String,
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// ViewDef
+////////////////////////////////////////////////////////////////////////////////
+
+/// View definition.
+///
+/// The view query is stored as SQL text and is expanded as a subquery
+/// wherever the view is referenced.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ViewDef {
+    pub id: ViewId,
+    pub name: String,
+    pub query: String,
+    pub operable: bool,
+    pub schema_version: u64,
+    pub owner: UserId,
+}
+
+impl Encode for ViewDef {}
+
+impl ViewDef {
+    /// Format of the _pico_view global table.
+    #[inline(always)]
+    pub fn format() -> Vec<tarantool::space::Field> {
+        use tarantool::space::Field;
+        vec![
+            Field::from(("id", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("name", FieldType::String)).is_nullable(false),
+            Field::from(("query", FieldType::String)).is_nullable(false),
+            Field::from(("operable", FieldType::Boolean)).is_nullable(false),
+            Field::from(("schema_version", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("owner", FieldType::Unsigned)).is_nullable(false),
+        ]
+    }
+
+    /// A dummy instance of the type for use in tests.
+    #[inline(always)]
+    pub fn for_tests() -> Self {
+        Self {
+            id: 7,
+            name: "v".into(),
+            query: "select a from t".into(),
+            operable: true,
+            schema_version: 421,
+            owner: 42,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// SchemaDef
+////////////////////////////////////////////////////////////////////////////////
+
+/// SQL schema definition.
+///
+/// Tables and views of the schema are stored under qualified names:
+/// table `t` of schema `s` is named `s.t`. Schema `public` is builtin
+/// and is not stored.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SchemaDef {
+    pub id: SchemaId,
+    pub name: String,
+    pub operable: bool,
+    pub schema_version: u64,
+    pub owner: UserId,
+}
+
+impl Encode for SchemaDef {}
+
+impl SchemaDef {
+    /// Format of the _pico_schema global table.
+    #[inline(always)]
+    pub fn format() -> Vec<tarantool::space::Field> {
+        use tarantool::space::Field;
+        vec![
+            Field::from(("id", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("name", FieldType::String)).is_nullable(false),
+            Field::from(("operable", FieldType::Boolean)).is_nullable(false),
+            Field::from(("schema_version", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("owner", FieldType::Unsigned)).is_nullable(false),
+        ]
+    }
+
+    /// A dummy instance of the type for use in tests.
+    #[inline(always)]
+    pub fn for_tests() -> Self {
+        Self {
+            id: 3,
+            name: "s".into(),
+            operable: true,
+            schema_version: 421,
+            owner: 42,
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// TableStatsDef
+////////////////////////////////////////////////////////////////////////////////
+
+/// Statistics of a user table gathered by `ANALYZE`.
+///
+/// The planner uses the number of rows to choose which side of a join
+/// to move, the column statistics are used for selectivity estimation.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TableStatsDef {
+    pub table_id: SpaceId,
+    /// Number of rows in the table on all the replicasets.
+    pub rows_number: u64,
+    /// Statistics of the columns by their names. Columns with the types
+    /// not supported by the statistics are absent.
+    pub columns: BTreeMap<String, EncodedColumnStats>,
+}
+
+impl Encode for TableStatsDef {}
+
+impl TableStatsDef {
+    /// Format of the _pico_table_stats global table.
+    #[inline(always)]
+    pub fn format() -> Vec<tarantool::space::Field> {
+        use tarantool::space::Field;
+        vec![
+            Field::from(("table_id", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("rows_number", FieldType::Unsigned)).is_nullable(false),
+            Field::from(("columns", FieldType::Map)).is_nullable(false),
+        ]
+    }
+
+    /// A dummy instance of the type for use in tests.
+    #[inline(always)]
+    pub fn for_tests() -> Self {
+        let column = EncodedColumnStats {
+            min_value: IrValue::Unsigned(1),
+            max_value: IrValue::Unsigned(3),
+            avg_size: 8,
+            null_fraction: 0.0,
+            distinct_values_fraction: 0.75,
+            most_common_values: vec![(IrValue::Unsigned(2), 0.5)],
+            histogram_boundaries: vec![IrValue::Unsigned(1), IrValue::Unsigned(3)],
+        };
+        Self {
+            table_id: 1025,
+            rows_number: 4,
+            columns: BTreeMap::from([("a".into(), column)]),
+        }
+    }
+}
+
+/// Split the name of a table or a view into the schema and the name
+/// within the schema: `s.t` is (`s`, `t`), while `t` is (`public`, `t`).
+pub fn split_schema_name(name: &str) -> (&str, &str) {
+    match name.split_once('.') {
+        Some((schema, name)) => (schema, name),
+        None => (DEFAULT_SCHEMA, name),
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // ...
 ////////////////////////////////////////////////////////////////////////////////
@@ -1733,6 +2072,22 @@ pub enum CreateTableError {
     UnexistingTier { tier_name: String },
     #[error("specified tier '{tier_name}' doesn't contain at least one instance")]
     EmptyTier { tier_name: String },
+    #[error("foreign key {name} references table {parent} which is not global")]
+    ForeignKeyParentNotGlobal { name: String, parent: String },
+    #[error("foreign key {name} must reference the primary key ({key}) of table {parent}")]
+    ForeignKeyNotPrimaryKey {
+        name: String,
+        parent: String,
+        key: String,
+    },
+    #[error("foreign key {name} column {column} of type {ty} can't reference column {parent_column} of type {parent_ty}")]
+    ForeignKeyTypeMismatch {
+        name: String,
+        column: String,
+        ty: String,
+        parent_column: String,
+        parent_ty: String,
+    },
 }
 
 impl From<CreateTableError> for Error {
@@ -1782,6 +2137,92 @@ pub struct Field {
     pub is_nullable: bool,
 }
 
+/// Default value of a table column.
+#[derive(
+    Clone, Debug, Serialize, Deserialize, PartialEq, Eq, LuaRead, msgpack::Encode, msgpack::Decode,
+)]
+pub struct ColumnDefault {
+    /// Name of the column.
+    pub column: String,
+    /// SQL expression calculated by the router when the column
+    /// is omitted in `INSERT`. The expression of the generated column
+    /// refers to the other columns and is recalculated on `UPDATE`.
+    pub expr: String,
+    /// The column is `GENERATED ALWAYS`, its value can't be specified
+    /// explicitly in `INSERT` or `UPDATE`.
+    #[serde(default)]
+    pub always: bool,
+}
+
+/// Declarative partitioning of a table.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, msgpack::Encode, msgpack::Decode)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "kind")]
+pub enum TablePartition {
+    /// The table is partitioned. It is always empty,
+    /// its tuples are stored in the partitions.
+    By {
+        strategy: PartitionStrategy,
+        /// Name of the partition key column.
+        column: String,
+    },
+    /// The table is a partition of the partitioned table.
+    Of {
+        /// Id of the partitioned table.
+        parent: SpaceId,
+        bound: PartitionBound,
+    },
+}
+
+/// Constraint of a table checked on the storages when the tuples are
+/// inserted or updated. Each constraint is checked by a tarantool function
+/// created on the replicaset masters along with the space, see
+/// [`crate::storage::schema::create_table_constraints`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, msgpack::Encode, msgpack::Decode)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "kind")]
+pub enum TableConstraint {
+    /// The tuple is rejected if the expression is false.
+    Check {
+        name: String,
+        /// SQL expression over the table columns.
+        expr: String,
+    },
+    /// The tuple is rejected if the referenced global table has no row with
+    /// the primary key equal to the values of the columns. The global table
+    /// is present on every instance, so the key is looked up locally.
+    /// A referenced row of the global table can't be deleted and its primary
+    /// key can't be changed (`NO ACTION`), this is checked on the router.
+    ForeignKey {
+        name: String,
+        columns: Vec<String>,
+        /// Id of the referenced global table.
+        parent: SpaceId,
+    },
+}
+
+impl TableConstraint {
+    #[inline]
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Check { name, .. } | Self::ForeignKey { name, .. } => name,
+        }
+    }
+
+    /// Name of the tarantool function checking the constraint.
+    #[inline]
+    pub fn func_name(&self, table_id: SpaceId) -> String {
+        format!("{}{}", constraint_func_prefix(table_id), self.name())
+    }
+}
+
+/// Prefix of the names of the tarantool functions checking
+/// the constraints of the table.
+#[inline]
+pub fn constraint_func_prefix(table_id: SpaceId) -> String {
+    format!("pico_constraint_{table_id}_")
+}
+
 impl From<Field> for tarantool::space::Field {
     fn from(field: Field) -> Self {
         tarantool::space::Field {
@@ -2095,7 +2536,7 @@ impl CreateIndexParams {
     }
 }
 
-#[derive(Clone, Debug, LuaRead)]
+#[derive(Clone, Debug)]
 pub struct CreateTableParams {
     pub(crate) id: Option<SpaceId>,
     pub(crate) name: String,
@@ -2108,6 +2549,9 @@ pub struct CreateTableParams {
     pub(crate) engine: Option<SpaceEngineType>,
     pub(crate) owner: UserId,
     pub(crate) tier: Option<String>,
+    pub(crate) defaults: Vec<ColumnDefault>,
+    pub(crate) partition: Option<TablePartition>,
+    pub(crate) constraints: Vec<TableConstraint>,
     /// Timeout in seconds.
     ///
     /// Specifying the timeout identifies how long user is ready to wait for ddl to be applied.
@@ -2312,6 +2756,19 @@ impl CreateTableParams {
                 },
             )
             .map_err(Some)?;
+            let format: Vec<_> = self
+                .format
+                .iter()
+                .cloned()
+                .map(tarantool::space::Field::from)
+                .collect();
+            create_table_constraints(
+                id,
+                &format,
+                self.distribution == DistributionParam::Global,
+                &self.constraints,
+            )
+            .map_err(Some)?;
             // Rollback space creation
             Err(None)
         })
@@ -2417,6 +2874,10 @@ impl CreateTableParams {
             distribution,
             engine: self.engine.unwrap_or_default(),
             owner: self.owner,
+            defaults: self.defaults,
+            partition: self.partition,
+            constraints: self.constraints,
+            sequences: vec![],
         };
         Ok(res)
     }
@@ -2530,6 +2991,8 @@ mod tests {
                 auth: Some(AuthDef::new(AuthMethod::Md5, String::from(""))),
                 owner: ADMIN_ID,
                 ty: UserMetadataKind::User,
+                scram_verifier: None,
+                statement_timeout: None,
             })
             .unwrap();
         storage
@@ -2562,6 +3025,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .test_create_space(&storage)
         .unwrap();
@@ -2580,6 +3046,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .test_create_space(&storage)
         .unwrap();
@@ -2598,6 +3067,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .test_create_space(&storage)
         .unwrap_err();
@@ -2635,6 +3107,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2653,6 +3128,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2671,6 +3149,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2689,6 +3170,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2707,6 +3191,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2725,6 +3212,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2746,6 +3236,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2767,6 +3260,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap();
@@ -2784,6 +3280,9 @@ mod tests {
             timeout: None,
             owner: ADMIN_ID,
             tier: None,
+            defaults: vec![],
+            partition: None,
+            constraints: vec![],
         }
         .validate()
         .unwrap_err();
@@ -2915,6 +3414,46 @@ mod test {
         crate::util::check_tuple_matches_format(tuple_data.as_ref(), &format, "RoutineDef::format");
     }
 
+    #[test]
+    #[rustfmt::skip]
+    fn sequence_def_matches_format() {
+        let s = SequenceDef::for_tests();
+        let tuple_data = s.to_tuple_buffer().unwrap();
+        let format = SequenceDef::format();
+        crate::util::check_tuple_matches_format(tuple_data.as_ref(), &format, "SequenceDef::format");
+
+        assert_eq!(s.nth_value(0), Some(1));
+        assert_eq!(s.nth_value(3), Some(-5));
+        assert_eq!(s.nth_value(u64::MAX), None);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn view_def_matches_format() {
+        let v = ViewDef::for_tests();
+        let tuple_data = v.to_tuple_buffer().unwrap();
+        let format = ViewDef::format();
+        crate::util::check_tuple_matches_format(tuple_data.as_ref(), &format, "ViewDef::format");
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn schema_def_matches_format() {
+        let s = SchemaDef::for_tests();
+        let tuple_data = s.to_tuple_buffer().unwrap();
+        let format = SchemaDef::format();
+        crate::util::check_tuple_matches_format(tuple_data.as_ref(), &format, "SchemaDef::format");
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn table_stats_def_matches_format() {
+        let s = TableStatsDef::for_tests();
+        let tuple_data = s.to_tuple_buffer().unwrap();
+        let format = TableStatsDef::format();
+        crate::util::check_tuple_matches_format(tuple_data.as_ref(), &format, "TableStatsDef::format");
+    }
+
     #[test]
     #[rustfmt::skip]
     fn plugin_def_matches_format() {
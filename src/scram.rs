@@ -0,0 +1,198 @@
+//! SCRAM-SHA-256 password verifiers, see [RFC 5802] and [RFC 7677].
+//!
+//! A verifier is stored in `_pico_user` instead of the password and is enough
+//! to check the client proof of the SASL exchange, see
+//! [`crate::pgproto`] for the exchange itself. The textual form of the
+//! verifier is the same as in PostgreSQL:
+//! `SCRAM-SHA-256$<iterations>:<salt>$<StoredKey>:<ServerKey>`.
+//!
+//! [RFC 5802]: https://www.rfc-editor.org/rfc/rfc5802
+//! [RFC 7677]: https://www.rfc-editor.org/rfc/rfc7677
+
+use openssl::base64;
+use openssl::hash::MessageDigest;
+use openssl::pkey::PKey;
+use openssl::sign::Signer;
+use std::fmt;
+use std::str::FromStr;
+
+/// Name of the SASL mechanism.
+pub const MECHANISM: &str = "SCRAM-SHA-256";
+
+/// Name of the SASL mechanism with channel binding.
+pub const MECHANISM_PLUS: &str = "SCRAM-SHA-256-PLUS";
+
+/// Number of PBKDF2 iterations, the same as the PostgreSQL default.
+const ITERATIONS: u32 = 4096;
+
+/// Length of the random salt in bytes.
+const SALT_LEN: usize = 16;
+
+const KEY_LEN: usize = 32;
+
+type Key = [u8; KEY_LEN];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Verifier {
+    pub iterations: u32,
+    pub salt: Vec<u8>,
+    pub stored_key: Key,
+    pub server_key: Key,
+}
+
+impl Verifier {
+    /// Builds a verifier of the password with a random salt.
+    pub fn new(password: &str) -> Self {
+        let salt: [u8; SALT_LEN] = rand::random();
+        Self::with_salt(password, &salt, ITERATIONS)
+    }
+
+    pub fn with_salt(password: &str, salt: &[u8], iterations: u32) -> Self {
+        let salted_password = salted_password(password, salt, iterations);
+        let client_key = hmac(&salted_password, b"Client Key");
+        Self {
+            iterations,
+            salt: salt.to_vec(),
+            stored_key: openssl::sha::sha256(&client_key),
+            server_key: hmac(&salted_password, b"Server Key"),
+        }
+    }
+
+    /// Returns true if the verifier was built from the password.
+    pub fn verify_password(&self, password: &str) -> bool {
+        let other = Self::with_salt(password, &self.salt, self.iterations);
+        openssl::memcmp::eq(&self.stored_key, &other.stored_key)
+            && openssl::memcmp::eq(&self.server_key, &other.server_key)
+    }
+
+    /// Returns true if the client proof of the exchange with the given
+    /// `AuthMessage` is correct, i.e. the client knows the password.
+    pub fn verify_client_proof(&self, auth_message: &[u8], proof: &[u8]) -> bool {
+        if proof.len() != KEY_LEN {
+            return false;
+        }
+        let signature = hmac(&self.stored_key, auth_message);
+        let client_key: Vec<u8> = proof.iter().zip(signature).map(|(p, s)| p ^ s).collect();
+        openssl::memcmp::eq(&openssl::sha::sha256(&client_key), &self.stored_key)
+    }
+
+    /// Signature proving to the client that the server knows the password.
+    pub fn server_signature(&self, auth_message: &[u8]) -> Key {
+        hmac(&self.server_key, auth_message)
+    }
+}
+
+impl fmt::Display for Verifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{MECHANISM}${}:{}${}:{}",
+            self.iterations,
+            base64::encode_block(&self.salt),
+            base64::encode_block(&self.stored_key),
+            base64::encode_block(&self.server_key),
+        )
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("invalid SCRAM verifier")]
+pub struct InvalidVerifier;
+
+impl FromStr for Verifier {
+    type Err = InvalidVerifier;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s
+            .strip_prefix(MECHANISM)
+            .and_then(|rest| rest.strip_prefix('$'))
+            .ok_or(InvalidVerifier)?;
+        let (params, keys) = rest.split_once('$').ok_or(InvalidVerifier)?;
+        let (iterations, salt) = params.split_once(':').ok_or(InvalidVerifier)?;
+        let (stored_key, server_key) = keys.split_once(':').ok_or(InvalidVerifier)?;
+
+        let decode_key = |key: &str| -> Result<Key, InvalidVerifier> {
+            let key = base64::decode_block(key).map_err(|_| InvalidVerifier)?;
+            key.try_into().map_err(|_| InvalidVerifier)
+        };
+        Ok(Self {
+            iterations: iterations.parse().map_err(|_| InvalidVerifier)?,
+            salt: base64::decode_block(salt).map_err(|_| InvalidVerifier)?,
+            stored_key: decode_key(stored_key)?,
+            server_key: decode_key(server_key)?,
+        })
+    }
+}
+
+/// `Hi(Normalize(password), salt, i)` of RFC 5802. Like PostgreSQL, the
+/// password is used as is if it can't be normalized with SASLprep.
+fn salted_password(password: &str, salt: &[u8], iterations: u32) -> Key {
+    let password = stringprep::saslprep(password).unwrap_or(password.into());
+    let mut key = [0; KEY_LEN];
+    openssl::pkcs5::pbkdf2_hmac(
+        password.as_bytes(),
+        salt,
+        iterations as usize,
+        MessageDigest::sha256(),
+        &mut key,
+    )
+    .expect("pbkdf2 over sha256 should not fail");
+    key
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Key {
+    let key = PKey::hmac(key).expect("any key is valid for hmac");
+    let mut signer =
+        Signer::new(MessageDigest::sha256(), &key).expect("hmac over sha256 should not fail");
+    let mut result = [0; KEY_LEN];
+    signer
+        .sign_oneshot(&mut result, data)
+        .expect("hmac over sha256 should not fail");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The example exchange of RFC 7677, section 3.
+    const PASSWORD: &str = "pencil";
+    const SALT: &str = "W22ZaJ0SNY7soEsUEjb6gQ==";
+    const AUTH_MESSAGE: &str = "n=user,r=rOprNGfwEbeRWgbNEkqO,\
+        r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096,\
+        c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0";
+    const PROOF: &str = "dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_SIGNATURE: &str = "6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    #[test]
+    fn rfc7677_exchange() {
+        let salt = base64::decode_block(SALT).unwrap();
+        let verifier = Verifier::with_salt(PASSWORD, &salt, 4096);
+
+        let proof = base64::decode_block(PROOF).unwrap();
+        assert!(verifier.verify_client_proof(AUTH_MESSAGE.as_bytes(), &proof));
+        assert!(!verifier.verify_client_proof(b"n=user,r=other", &proof));
+        assert!(!verifier.verify_client_proof(AUTH_MESSAGE.as_bytes(), &proof[1..]));
+
+        let signature = verifier.server_signature(AUTH_MESSAGE.as_bytes());
+        assert_eq!(base64::encode_block(&signature), SERVER_SIGNATURE);
+    }
+
+    #[test]
+    fn verifier_roundtrip() {
+        let verifier = Verifier::new(PASSWORD);
+        assert_eq!(verifier.salt.len(), SALT_LEN);
+        assert_eq!(verifier.iterations, ITERATIONS);
+        assert!(verifier.verify_password(PASSWORD));
+        assert!(!verifier.verify_password("Pencil"));
+
+        let text = verifier.to_string();
+        assert!(text.starts_with("SCRAM-SHA-256$4096:"));
+        assert_eq!(text.parse::<Verifier>().unwrap(), verifier);
+
+        assert!("md5abc".parse::<Verifier>().is_err());
+        assert!("SCRAM-SHA-256$4096:c2FsdA==$a2V5:a2V5"
+            .parse::<Verifier>()
+            .is_err());
+    }
+}
@@ -0,0 +1,127 @@
+//! Sequences created by `CREATE SEQUENCE` or implicitly for `SERIAL`
+//! and identity columns.
+//!
+//! Sequence definitions live in the `_pico_sequence` system table. Values
+//! are handed out by the `nextval` SQL function. To avoid a raft operation
+//! per value every instance allocates [`CACHE_SIZE`] values at once by
+//! bumping the `allocated` field of the sequence with a CaS request and then
+//! serves them locally. As in PostgreSQL with `CACHE` greater than 1, the
+//! values are unique but not monotonic across instances, and the values not
+//! used by an instance before restart are lost.
+
+use crate::access_control::access_check_sequence;
+use crate::cas;
+use crate::column_name;
+use crate::schema::{SequenceDef, ADMIN_ID};
+use crate::sql::router::DEFAULT_QUERY_TIMEOUT;
+use crate::storage::{SequenceId, Sequences, SystemTable};
+use crate::traft::error::{DoesNotExist, Error};
+use crate::traft::op::Dml;
+use crate::traft::{self, node};
+use crate::util::effective_user_id;
+use sbroad::executor::engine::helpers::normalize_name_from_sql;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+use tarantool::proc;
+use tarantool::session::with_su;
+use tarantool::space::UpdateOps;
+use tarantool::time::Instant;
+
+/// Number of values allocated by an instance at once.
+pub const CACHE_SIZE: u64 = 100;
+
+/// Values of a sequence allocated by the current instance, i.e. `n`-th
+/// values of the sequence with `next <= n < end`.
+struct AllocatedRange {
+    /// Schema version of the sequence definition the range belongs to.
+    /// Sequence ids are reused after `DROP SEQUENCE`, so the range is
+    /// discarded if the definition has changed.
+    schema_version: u64,
+    next: u64,
+    end: u64,
+}
+
+thread_local! {
+    static ALLOCATED: RefCell<HashMap<SequenceId, AllocatedRange>> = RefCell::default();
+}
+
+/// Takes the next value allocated by the current instance if any.
+fn take_allocated(sequence: &SequenceDef) -> Option<u64> {
+    ALLOCATED.with_borrow_mut(|allocated| {
+        let range = allocated.get_mut(&sequence.id)?;
+        if range.schema_version != sequence.schema_version || range.next >= range.end {
+            return None;
+        }
+        let n = range.next;
+        range.next += 1;
+        Some(n)
+    })
+}
+
+/// Returns the next value of the sequence `name`. Allocates a new range of
+/// values via CaS when the ones allocated by the current instance are over.
+pub fn nextval(name: &str) -> traft::Result<i64> {
+    let node = node::global()?;
+    let storage = &node.storage;
+    let name = normalize_name_from_sql(name);
+    let deadline = Instant::now_fiber().saturating_add(Duration::from_secs(DEFAULT_QUERY_TIMEOUT));
+
+    loop {
+        let Some(sequence) = storage.sequences.by_name(&name)? else {
+            return Err(DoesNotExist::Sequence(name).into());
+        };
+        if !sequence.operable {
+            return Err(Error::other(format!("sequence {name} is not operable")));
+        }
+
+        if let Some(n) = take_allocated(&sequence) {
+            return sequence
+                .nth_value(n)
+                .ok_or_else(|| Error::other(format!("sequence {name} reached its limit")));
+        }
+
+        let Some(end) = sequence.allocated.checked_add(CACHE_SIZE) else {
+            return Err(Error::other(format!("sequence {name} reached its limit")));
+        };
+        let mut ops = UpdateOps::new();
+        ops.assign(column_name!(SequenceDef, allocated), end)?;
+        let dml = Dml::update(Sequences::TABLE_ID, &[sequence.id], ops, ADMIN_ID)?;
+        let ranges = vec![cas::Range::new(Sequences::TABLE_ID).eq([sequence.id])];
+        let predicate = cas::Predicate::with_applied_index(ranges);
+        let req = cas::Request::new(dml, predicate, ADMIN_ID)?;
+        match cas::compare_and_swap_and_wait(&req, deadline)? {
+            cas::CasResult::RetriableError(_) => continue,
+            cas::CasResult::Ok(_) => {}
+        }
+
+        ALLOCATED.with_borrow_mut(|allocated| {
+            let range = AllocatedRange {
+                schema_version: sequence.schema_version,
+                next: sequence.allocated,
+                end,
+            };
+            allocated.insert(sequence.id, range);
+        });
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// .proc_sequence_nextval
+////////////////////////////////////////////////////////////////////////////////
+
+/// Implementation of the `nextval` SQL function.
+#[proc]
+pub fn proc_sequence_nextval(name: String) -> traft::Result<i64> {
+    let as_user = effective_user_id();
+    // Sequences are stored in a system table, which is only accessible by admin.
+    with_su(ADMIN_ID, || -> traft::Result<i64> {
+        let storage = &node::global()?.storage;
+        let normalized = normalize_name_from_sql(&name);
+        let Some(sequence) = storage.sequences.by_name(&normalized)? else {
+            return Err(DoesNotExist::Sequence(normalized).into());
+        };
+        access_check_sequence(&sequence, as_user)?;
+        nextval(&name)
+    })?
+}
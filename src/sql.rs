@@ -1,14 +1,15 @@
 //! Clusterwide SQL query execution.
 
-use crate::access_control::access_check_plugin_system;
+use crate::access_control::{access_check_plugin_system, access_check_schema, access_check_view};
 use crate::access_control::{validate_password, UserMetadataKind};
 use crate::cas::Predicate;
 use crate::config::AlterSystemParameters;
 use crate::schema::{
-    wait_for_ddl_commit, CreateIndexParams, CreateProcParams, CreateTableParams, DistributionParam,
-    Field, IndexOption, PrivilegeDef, PrivilegeType, RenameRoutineParams, RoutineDef,
-    RoutineLanguage, RoutineParamDef, RoutineParams, RoutineSecurity, SchemaObjectType, ShardingFn,
-    UserDef, ADMIN_ID,
+    split_schema_name, wait_for_ddl_commit, ColumnDefault, CreateIndexParams, CreateProcParams,
+    CreateTableError, CreateTableParams, Distribution, DistributionParam, Field, IndexOption,
+    PrivilegeDef, PrivilegeType, RenameRoutineParams, RoutineDef, RoutineLanguage, RoutineParamDef,
+    RoutineParams, RoutineSecurity, SchemaObjectType, ShardingFn, TableConstraint, TableDef,
+    TablePartition, TableSequence, UserDef, ViewDef, ADMIN_ID,
 };
 use crate::sql::router::RouterRuntime;
 use crate::sql::storage::StorageRuntime;
@@ -19,30 +20,41 @@ use crate::traft::node::Node as TraftNode;
 use crate::traft::op::{Acl as OpAcl, Ddl as OpDdl, Dml, DmlKind, Op};
 use crate::traft::{self, node};
 use crate::util::{duration_from_secs_f64_clamped, effective_user_id};
-use crate::{cas, plugin, tlog};
+use crate::{cas, plugin, scram, tlog};
 
 use picodata_plugin::error_code::ErrorCode;
 use sbroad::errors::{Action, Entity, SbroadError};
+use sbroad::executor::engine::helpers::vshard::CacheInfo;
 use sbroad::executor::engine::helpers::{
     build_delete_args, build_insert_args, build_update_args, decode_msgpack,
     init_delete_tuple_builder, init_insert_tuple_builder, init_local_update_tuple_builder,
-    replace_metadata_in_dql_result, try_get_metadata_from_plan,
+    replace_metadata_in_dql_result, try_get_metadata_from_plan, OptionalBytes, DEFAULT_SCHEMA,
 };
+use sbroad::executor::engine::Router;
+use sbroad::executor::ir::QueryType;
 use sbroad::executor::protocol::{EncodedRequiredData, RequiredData};
-use sbroad::executor::result::ConsumerResult;
+use sbroad::executor::result::{ConsumerResult, DQLQueryResult};
+use sbroad::executor::vtable::VirtualTable;
 use sbroad::executor::Query;
-use sbroad::ir::acl::{AlterOption, GrantRevokeType, Privilege as SqlPrivilege};
-use sbroad::ir::ddl::{AlterSystemType, ParamDef};
+use sbroad::frontend::Ast;
+use sbroad::ir::acl::{
+    AlterOption, AuthMethod as SqlAuthMethod, GrantRevokeType, Privilege as SqlPrivilege,
+};
+use sbroad::ir::ddl::{
+    AlterSystemType, ColumnDef, ParamDef, PartitionBound, PartitionStrategy, SetParamScopeType,
+    SetParamValue, TableConstraint as IrTableConstraint,
+};
 use sbroad::ir::node::acl::AclOwned;
 use sbroad::ir::node::block::Block;
-use sbroad::ir::node::ddl::{Ddl, DdlOwned};
+use sbroad::ir::node::ddl::DdlOwned;
 use sbroad::ir::node::expression::ExprOwned;
 use sbroad::ir::node::relational::Relational;
 use sbroad::ir::node::{
-    AlterColumn, AlterSystem, AlterTableOp, AlterUser, Constant, CreateIndex, CreateProc,
-    CreateRole, CreateTable, CreateUser, Delete, DropIndex, DropProc, DropRole, DropTable,
-    DropUser, GrantPrivilege, Insert, Node as IrNode, NodeOwned, Procedure, RenameRoutine,
-    RevokePrivilege, ScanRelation, SetParam, Update,
+    AlterColumn, AlterSystem, AlterTable, AlterTableOp, AlterUser, Constant, CreateIndex,
+    CreatePartition, CreateProc, CreateRole, CreateSchema, CreateSequence, CreateTable,
+    CreateTableAs, CreateUser, CreateView, Delete, DropIndex, DropProc, DropRole, DropSchema,
+    DropSequence, DropTable, DropUser, DropView, GrantPrivilege, Insert, Node as IrNode, NodeOwned,
+    Procedure, RenameRoutine, RevokePrivilege, ScanRelation, SetParam, Update,
 };
 use sbroad::ir::node::{NodeId, TruncateTable};
 use tarantool::decimal::Decimal;
@@ -52,20 +64,22 @@ use sbroad::ir::node::plugin::{
     AppendServiceToTier, ChangeConfig, CreatePlugin, DisablePlugin, DropPlugin, EnablePlugin,
     MigrateTo, Plugin, RemoveServiceFromTier, SettingsPair,
 };
-use sbroad::ir::node::Node;
 use sbroad::ir::operator::ConflictStrategy;
 use sbroad::ir::relation::Type;
 use sbroad::ir::tree::traversal::{LevelNode, PostOrderWithFilter, REL_CAPACITY};
-use sbroad::ir::value::Value;
+use sbroad::ir::value::{EncodedValue, TrivalentOrdering, Value};
 use sbroad::ir::{Options, Plan as IrPlan};
+use sbroad::utils::MutexLike;
 use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use tarantool::access_control::{box_access_check_ddl, SchemaObjectType as TntSchemaObjectType};
 use tarantool::schema::function::func_next_reserved_id;
 use tarantool::tuple::{Decode, ToTupleBuffer};
+use tarantool::util::NumOrStr;
+use tarantool::uuid::Uuid;
 
 use crate::storage::Catalog;
 use ::tarantool::access_control::{box_access_check_space, PrivType};
-use ::tarantool::auth::{AuthData, AuthDef};
+use ::tarantool::auth::{AuthData, AuthDef, AuthMethod};
 use ::tarantool::error::BoxError;
 use ::tarantool::error::TarantoolErrorCode;
 use ::tarantool::proc;
@@ -73,15 +87,22 @@ use ::tarantool::session::{with_su, UserId};
 use ::tarantool::space::{FieldType, Space, SpaceId, SystemSpace};
 use ::tarantool::time::Instant;
 use ::tarantool::tuple::{RawBytes, Tuple};
+use std::collections::HashSet;
 use std::ops::{ControlFlow, ControlFlow::Break, ControlFlow::Continue};
 use std::rc::Rc;
 use std::time::Duration;
-use tarantool::{msgpack, session};
+use tarantool::msgpack;
 
+pub mod analyze;
+pub mod cancel;
+pub mod pg_catalog;
 pub mod router;
+pub mod session;
 pub mod storage;
+pub mod transaction;
 
 use self::router::DEFAULT_QUERY_TIMEOUT;
+use self::transaction::SessionKey;
 use serde::Serialize;
 
 pub const DEFAULT_BUCKET_COUNT: u64 = 3000;
@@ -117,6 +138,9 @@ fn check_table_privileges(plan: &IrPlan) -> traft::Result<()> {
     // We don't want to switch the user back and forth for each node, so we
     // collect all space ids and privileges and then check them all at once.
     let mut space_privs: Vec<(SpaceId, Privileges)> = Vec::with_capacity(nodes.len());
+    // Schemas of the referenced tables and views, the user needs
+    // the usage privilege on each of them except `public`.
+    let mut schemas: HashSet<&str> = HashSet::new();
 
     // Switch to admin to get space ids. At the moment we don't use space cache in tarantool
     // module and can't get space metadata without _space table read permissions.
@@ -146,6 +170,12 @@ fn check_table_privileges(plan: &IrPlan) -> traft::Result<()> {
                 // This should never happen as we have filtered out all other plan nodes.
                 _ => unreachable!("internal bug on the table privilege check"),
             };
+            // Tables used only by the views' queries are accessed with the
+            // privileges of the views' owners, the views are checked below.
+            if plan.views.hidden_tables.contains(relation) {
+                continue;
+            }
+            schemas.insert(split_schema_name(relation).0);
             let space = space_by_name(relation)?;
             space_privs.push((space.id(), privileges))
         }
@@ -165,9 +195,179 @@ fn check_table_privileges(plan: &IrPlan) -> traft::Result<()> {
             }
         }
     }
+
+    schemas.extend(
+        plan.views
+            .referenced
+            .iter()
+            .map(|name| split_schema_name(name).0),
+    );
+    schemas.remove(DEFAULT_SCHEMA);
+    // Catalog relations are readable by everyone.
+    schemas.retain(|name| !pg_catalog::is_catalog_schema(name));
+    if !schemas.is_empty() {
+        let as_user = effective_user_id();
+        with_su(ADMIN_ID, || -> traft::Result<()> {
+            let storage = &node::global()?.storage;
+            for name in schemas {
+                let schema = storage
+                    .schemas
+                    .by_name(name)?
+                    .ok_or_else(|| error::DoesNotExist::Schema(name.into()))?;
+                access_check_schema(storage, &schema, PrivilegeType::Usage, as_user)?;
+            }
+            Ok(())
+        })??;
+    }
+
+    if !plan.views.referenced.is_empty() {
+        let as_user = effective_user_id();
+        let views = with_su(ADMIN_ID, || -> traft::Result<Vec<ViewDef>> {
+            let storage = &node::global()?.storage;
+            let mut views = Vec::with_capacity(plan.views.referenced.len());
+            for name in &plan.views.referenced {
+                let Some(view) = storage.views.by_name(name)? else {
+                    if pg_catalog::is_catalog_relation(name) {
+                        continue;
+                    }
+                    return Err(error::DoesNotExist::View(name.clone()).into());
+                };
+                access_check_view(storage, &view, as_user)?;
+                views.push(view);
+            }
+            Ok(views)
+        })??;
+        // The tables hidden behind the views are read with the privileges
+        // of the views' owners, which may have been revoked since the views
+        // were created.
+        for view in views {
+            check_view_query(&view.query, view.owner)?;
+        }
+    }
     Ok(())
 }
 
+/// Builds a plan of a view query to make sure it is valid and that
+/// the owner of the view can read everything the query references.
+/// Nested views are checked with the privileges of their own owners.
+fn check_view_query(query: &str, owner: UserId) -> traft::Result<()> {
+    let runtime = RouterRuntime::new()?;
+    let plan = with_su(ADMIN_ID, || -> traft::Result<IrPlan> {
+        let metadata = runtime.metadata().lock();
+        let plan = <RouterRuntime as Router>::ParseTree::transform_into_plan(query, &*metadata)?;
+        Ok(plan)
+    })??;
+    with_su(owner, || check_table_privileges(&plan))?
+}
+
+/// Executes `CREATE TABLE .. AS <query>`: creates the table with the columns
+/// derived from the query output under a temporary name, fills it with
+/// `INSERT .. <query>` and renames it. So the table doesn't appear under its
+/// name until it is filled, and is dropped if it can't be filled.
+fn create_table_as(
+    node: &TraftNode,
+    create_table_as: CreateTableAs,
+) -> traft::Result<ConsumerResult> {
+    let runtime = RouterRuntime::new()?;
+    let select = with_su(ADMIN_ID, || {
+        Query::new(
+            &runtime,
+            &create_table_as.query,
+            create_table_as.params.clone(),
+        )
+    })??;
+    let plan = select.get_exec_plan().get_ir_plan();
+    // Check the privileges beforehand not to create a table
+    // which can't be filled.
+    check_table_privileges(plan)?;
+    let mut create_table = create_table_as.create_table(plan)?;
+
+    let CreateTableAs {
+        name,
+        query,
+        params,
+        if_not_exists,
+        wait_applied_globally,
+        timeout,
+        ..
+    } = create_table_as;
+    let exists = with_su(ADMIN_ID, || -> traft::Result<bool> {
+        let storage = &node.storage;
+        if storage.views.by_name(&name)?.is_some() {
+            return Err(error::AlreadyExists::View(name.clone()).into());
+        }
+        Ok(storage.tables.by_name(&name)?.is_some())
+    })??;
+    if exists {
+        if if_not_exists {
+            return Ok(ConsumerResult { row_count: 0 });
+        }
+        return Err(error::AlreadyExists::Table(name).into());
+    }
+
+    let temp_name = format_smolstr!("{name}__ctas_{}", Uuid::random());
+    create_table.name = temp_name.clone();
+    reenterable_schema_change_request(node, NodeOwned::Ddl(DdlOwned::CreateTable(create_table)))?;
+
+    let insert = format!("INSERT INTO {} {query}", quoted_table_name(&temp_name));
+    let filled = sql_dispatch(&insert, params).and_then(|tuple| {
+        let (result,): (ConsumerResult,) = tuple.decode()?;
+        Ok(result)
+    });
+    let renamed = filled.and_then(|result| {
+        let rename = AlterTable {
+            name: temp_name.clone(),
+            wait_applied_globally,
+            timeout,
+            op: AlterTableOp::RenameTable {
+                new_table_name: name.clone(),
+            },
+        };
+        reenterable_schema_change_request(node, NodeOwned::Ddl(DdlOwned::AlterTable(rename)))?;
+        Ok(result)
+    });
+    if renamed.is_err() {
+        let drop_table = DropTable {
+            name: temp_name.clone(),
+            if_exists: true,
+            wait_applied_globally,
+            timeout,
+        };
+        if let Err(e) =
+            reenterable_schema_change_request(node, NodeOwned::Ddl(DdlOwned::DropTable(drop_table)))
+        {
+            tlog!(
+                Warning,
+                "failed to drop table {temp_name} which wasn't filled: {e}"
+            );
+        }
+    }
+    renamed
+}
+
+/// Quotes the table name to be used in an SQL query.
+fn quoted_table_name(name: &str) -> String {
+    let (schema, table) = split_schema_name(name);
+    if schema == DEFAULT_SCHEMA {
+        format!(r#""{table}""#)
+    } else {
+        format!(r#""{schema}"."{table}""#)
+    }
+}
+
+/// Checks that the schema of a new table or view exists.
+/// The `public` schema always exists.
+fn check_schema_exists(storage: &Catalog, name: &str) -> traft::Result<()> {
+    let (schema, _) = split_schema_name(name);
+    if schema == DEFAULT_SCHEMA {
+        return Ok(());
+    }
+    match storage.schemas.by_name(schema)? {
+        Some(schema_def) if schema_def.operable => Ok(()),
+        _ => Err(error::DoesNotExist::Schema(schema.into()).into()),
+    }
+}
+
 fn routine_by_name(name: &str) -> traft::Result<RoutineDef> {
     // Switch to admin to get procedure definition.
     with_su(ADMIN_ID, || {
@@ -215,6 +415,21 @@ fn empty_query_response() -> traft::Result<Tuple> {
 }
 
 pub fn dispatch(mut query: Query<RouterRuntime>) -> traft::Result<Tuple> {
+    let guard = cancel::start_query()?;
+    let ir_plan = query.get_mut_exec_plan().get_mut_ir_plan();
+    ir_plan.request_id = Some(guard.request_id().clone());
+    ir_plan.statement_deadline = guard
+        .time_left()
+        .and_then(|time_left| std::time::Instant::now().checked_add(time_left));
+    dispatch_impl(query).map_err(|e| match cancel::check_cancelled() {
+        // Storages and the router report cancellation and timeouts in different
+        // ways, make sure the client sees the proper error.
+        Err(cancelled) => cancelled,
+        Ok(()) => e,
+    })
+}
+
+fn dispatch_impl(mut query: Query<RouterRuntime>) -> traft::Result<Tuple> {
     if query.is_empty() {
         return empty_query_response();
     }
@@ -227,41 +442,39 @@ pub fn dispatch(mut query: Query<RouterRuntime>) -> traft::Result<Tuple> {
         let ir_plan = query.get_exec_plan().get_ir_plan();
         let top_id = ir_plan.get_top()?;
         let tcl = ir_plan.get_tcl_node(top_id)?;
-        tlog!(
-            Warning,
-            "Transactions are currently unsupported. Empty query response provided for {}.",
-            tcl.as_str()
-        );
+        transaction::execute_tcl(SessionKey::current(), tcl)?;
         return empty_query_response();
     }
 
+    if let Some(txn_id) = transaction::active_txn_id(SessionKey::current())? {
+        if query.is_ddl()? || query.is_acl()? || query.is_plugin()? {
+            return Err(Error::other(
+                "DDL, ACL and plugin operations are not supported inside a transaction",
+            ));
+        }
+        let ir_plan = query.get_mut_exec_plan().get_mut_ir_plan();
+        if !ir_plan.is_block()? && ir_plan.is_dml_on_global_table()? {
+            return Err(Error::other(
+                "DML on global tables is not supported inside a transaction",
+            ));
+        }
+        ir_plan.txn_id = Some(txn_id);
+    }
+
     if query.is_ddl()? || query.is_acl()? {
         let ir_plan = query.get_exec_plan().get_ir_plan();
         let top_id = ir_plan.get_top()?;
-
-        if let Node::Ddl(_) = ir_plan.get_node(top_id)? {
-            let ddl_node = ir_plan.get_ddl_node(top_id)?;
-            if let Ddl::CreateSchema = ddl_node {
-                tlog!(
-                    Warning,
-                    "DDL for schemas is currently unsupported. Empty query response provided for CREATE SCHEMA."
-                );
-                return empty_query_response();
-            }
-            if let Ddl::DropSchema = ddl_node {
-                tlog!(
-                    Warning,
-                    "DDL for schemas is currently unsupported. Empty query response provided for DROP SCHEMA."
-                );
-                return empty_query_response();
-            }
-        }
-
         let ir_plan_mut = query.get_mut_exec_plan().get_mut_ir_plan();
 
         let ir_node = ir_plan_mut.replace_with_stub(top_id);
         let node = node::global()?;
-        let result = reenterable_schema_change_request(node, ir_node)?;
+        let result = match ir_node {
+            NodeOwned::Ddl(DdlOwned::Analyze(analyze)) => analyze::analyze(node, &analyze)?,
+            NodeOwned::Ddl(DdlOwned::CreateTableAs(create_table_as)) => {
+                create_table_as(node, create_table_as)?
+            }
+            ir_node => reenterable_schema_change_request(node, ir_node)?,
+        };
         let tuple = Tuple::new(&(result,))?;
         Ok(tuple)
     } else if query.is_plugin()? {
@@ -466,7 +679,8 @@ pub fn dispatch(mut query: Query<RouterRuntime>) -> traft::Result<Tuple> {
 
         let metadata = try_get_metadata_from_plan(query.get_exec_plan())?;
 
-        if query.is_explain() {
+        let is_explain_analyze = plan.is_explain_analyze();
+        if query.is_explain() && !is_explain_analyze {
             return Ok(*query
                 .produce_explain()?
                 .downcast::<Tuple>()
@@ -494,6 +708,12 @@ pub fn dispatch(mut query: Query<RouterRuntime>) -> traft::Result<Tuple> {
         })??;
 
         if plan.is_dml_on_global_table()? {
+            if is_explain_analyze {
+                return Err(Error::from(SbroadError::Unsupported(
+                    Entity::Query,
+                    Some("EXPLAIN ANALYZE for DML on global tables".into()),
+                )));
+            }
             let res = do_dml_on_global_tbl(query)?;
             return Ok(Tuple::new(&(res,))?);
         }
@@ -566,7 +786,21 @@ impl<'de> Decode<'de> for BindArgs {
 /// Part of public RPC API.
 #[proc(packed_args)]
 pub fn proc_sql_dispatch(args: BindArgs) -> traft::Result<Tuple> {
-    sql_dispatch(&args.pattern, args.params).map_err(err_for_tnt_console)
+    sql_dispatch(&args.pattern, args.params)
+        // Any failed statement aborts the transaction, including the errors
+        // raised on the router (e.g. while planning or checking privileges).
+        .inspect_err(|_| transaction::mark_failed(SessionKey::current()))
+        .map_err(err_for_tnt_console)
+}
+
+/// Called by the local SQL of a recursive CTE when it produces more rows
+/// than `sql_recursion_row_max` allows, see
+/// [`sbroad::frontend::sql::RECURSION_ROW_MAX_EXCEEDED_FUNCTION`].
+#[proc]
+pub fn proc_recursion_row_max_exceeded(row_max: u64) -> traft::Result<bool> {
+    Err(Error::other(format!(
+        "Exceeded maximum number of rows ({row_max}) in recursive CTE"
+    )))
 }
 
 pub fn sql_dispatch(pattern: &str, params: Vec<Value>) -> traft::Result<Tuple> {
@@ -576,8 +810,9 @@ pub fn sql_dispatch(pattern: &str, params: Vec<Value>) -> traft::Result<Tuple> {
     let query = with_su(ADMIN_ID, || {
         let sql_vdbe_opcode_max = node.storage.db_config.sql_vdbe_opcode_max()?;
         let sql_motion_row_max = node.storage.db_config.sql_motion_row_max()?;
-        let default_options = Some(Options::new(sql_motion_row_max, sql_vdbe_opcode_max));
-        Query::with_options(&runtime, pattern, params, default_options)
+        let mut default_options = Options::new(sql_motion_row_max, sql_vdbe_opcode_max);
+        default_options.sql_recursion_row_max = node.storage.db_config.sql_recursion_row_max()?;
+        Query::with_options(&runtime, pattern, params, Some(default_options))
     })??;
     dispatch(query)
 }
@@ -594,16 +829,16 @@ impl TryFrom<&SqlPrivilege> for PrivilegeType {
             SqlPrivilege::Alter => Ok(PrivilegeType::Alter),
             SqlPrivilege::Drop => Ok(PrivilegeType::Drop),
 
-            // Picodata does not allow to grant or revoke session or usage
+            // Usage is only granted on schemas, the privilege types
+            // are checked against the object types later.
+            SqlPrivilege::Usage => Ok(PrivilegeType::Usage),
+
+            // Picodata does not allow to grant or revoke session
             // Instead this should be done through alter user with login/nologin
             SqlPrivilege::Session => Err(SbroadError::Unsupported(
                 Entity::Privilege,
                 Some("session".into()),
             )),
-            SqlPrivilege::Usage => Err(SbroadError::Unsupported(
-                Entity::Privilege,
-                Some("usage".into()),
-            )),
         }
     }
 }
@@ -724,6 +959,13 @@ impl TraftNode {
                         privilege.try_into()?,
                         table_id as i64,
                     ))
+                } else if let Some(view) = self.storage.views.by_name(table_name)? {
+                    // Views are granted the same way as tables.
+                    Ok((
+                        SchemaObjectType::View,
+                        privilege.try_into()?,
+                        view.id as i64,
+                    ))
                 } else {
                     Err(Error::Sbroad(SbroadError::Invalid(
                         Entity::Acl,
@@ -755,6 +997,28 @@ impl TraftNode {
                     )))
                 }
             }
+            GrantRevokeType::Schema { privilege } => {
+                Ok((SchemaObjectType::Schema, privilege.try_into()?, -1))
+            }
+            GrantRevokeType::SpecificSchema {
+                privilege,
+                schema_name,
+            } => {
+                if let Some(schema) = self.storage.schemas.by_name(schema_name)? {
+                    Ok((
+                        SchemaObjectType::Schema,
+                        privilege.try_into()?,
+                        schema.id as i64,
+                    ))
+                } else {
+                    Err(Error::Sbroad(SbroadError::Invalid(
+                        Entity::Acl,
+                        Some(format_smolstr!(
+                            "There is no schema with name {schema_name}"
+                        )),
+                    )))
+                }
+            }
             GrantRevokeType::RolePass { role_name } => {
                 if let Some(role_id) = self.get_user_or_role_id(&role_name.to_string()) {
                     Ok((
@@ -844,6 +1108,27 @@ fn check_name_emptyness(name: &str) -> traft::Result<()> {
     Ok(())
 }
 
+/// Validates the password and builds the user's auth data. Tarantool doesn't
+/// support SCRAM, so the users with `scram-sha-256` method get the chap-sha1
+/// data used by iproto connections and the SCRAM verifier used by pgproto.
+fn user_auth(
+    name: &str,
+    password: &str,
+    auth_method: SqlAuthMethod,
+    storage: &Catalog,
+) -> traft::Result<(AuthDef, Option<scram::Verifier>)> {
+    let method = match auth_method {
+        SqlAuthMethod::ChapSha1 | SqlAuthMethod::ScramSha256 => AuthMethod::ChapSha1,
+        SqlAuthMethod::Md5 => AuthMethod::Md5,
+        SqlAuthMethod::Ldap => AuthMethod::Ldap,
+    };
+    validate_password(password, &method, storage)?;
+    let scram_verifier =
+        (auth_method == SqlAuthMethod::ScramSha256).then(|| scram::Verifier::new(password));
+    let data = AuthData::new(&method, name, password);
+    Ok((AuthDef::new(method, data.into_string()), scram_verifier))
+}
+
 fn alter_user_ir_node_to_op_or_result(
     name: &SmolStr,
     alter_option: &AlterOption,
@@ -868,15 +1153,22 @@ fn alter_user_ir_node_to_op_or_result(
             password,
             auth_method,
         } => {
-            validate_password(password, auth_method, storage)?;
-            let data = AuthData::new(auth_method, name, password);
-            let auth = AuthDef::new(*auth_method, data.into_string());
+            let (auth, scram_verifier) = user_auth(name, password, *auth_method, storage)?;
 
-            if user_def
+            let same_auth = user_def
                 .auth
                 .expect("user always should have non empty auth")
-                == auth
-            {
+                == auth;
+            // SCRAM verifiers are salted randomly, so they are compared by
+            // checking the password against the stored one.
+            let same_verifier = match (&scram_verifier, &user_def.scram_verifier) {
+                (None, None) => true,
+                (Some(_), Some(stored)) => stored
+                    .parse::<scram::Verifier>()
+                    .is_ok_and(|stored| stored.verify_password(password)),
+                _ => false,
+            };
+            if same_auth && same_verifier {
                 // Password is already the one given, no op needed.
                 return Ok(Break(ConsumerResult { row_count: 0 }));
             }
@@ -886,6 +1178,7 @@ fn alter_user_ir_node_to_op_or_result(
                 auth: auth.clone(),
                 initiator: current_user,
                 schema_version,
+                scram_verifier: scram_verifier.map(|verifier| verifier.to_string()),
             })))
         }
         AlterOption::Login => {
@@ -930,6 +1223,17 @@ fn alter_user_ir_node_to_op_or_result(
                 }))),
             }
         }
+        AlterOption::StatementTimeout { millis } => {
+            if user_def.statement_timeout == *millis {
+                return Ok(Break(ConsumerResult { row_count: 0 }));
+            }
+            Ok(Continue(Op::Acl(OpAcl::ChangeStatementTimeout {
+                user_id: user_def.id,
+                statement_timeout: *millis,
+                initiator: current_user,
+                schema_version,
+            })))
+        }
     }
 }
 
@@ -1018,6 +1322,8 @@ fn acl_ir_node_to_op_or_result(
                 owner: current_user,
                 auth: None,
                 ty: UserMetadataKind::Role,
+                scram_verifier: None,
+                statement_timeout: None,
             };
             Ok(Continue(Op::Acl(OpAcl::CreateRole { role_def })))
         }
@@ -1031,9 +1337,7 @@ fn acl_ir_node_to_op_or_result(
             check_name_emptyness(name)?;
             storage.users.check_user_limit()?;
 
-            validate_password(password, auth_method, storage)?;
-            let data = AuthData::new(auth_method, name, password);
-            let auth = AuthDef::new(*auth_method, data.into_string());
+            let (auth, scram_verifier) = user_auth(name, password, *auth_method, storage)?;
 
             let user_def = storage.users.by_name(name)?;
             if let Some(user_def) = user_def {
@@ -1054,6 +1358,8 @@ fn acl_ir_node_to_op_or_result(
                 auth: Some(auth.clone()),
                 owner: current_user,
                 ty: UserMetadataKind::User,
+                scram_verifier: scram_verifier.map(|verifier| verifier.to_string()),
+                statement_timeout: None,
             };
             Ok(Continue(Op::Acl(OpAcl::CreateUser { user_def })))
         }
@@ -1225,6 +1531,97 @@ fn alter_system_ir_node_to_op_or_result(
     }
 }
 
+/// Names of the primary key columns of the table.
+fn primary_key_columns(storage: &Catalog, table_def: &TableDef) -> traft::Result<Vec<String>> {
+    let primary_key = storage
+        .indexes
+        .get(table_def.id, 0)?
+        .ok_or_else(|| Error::other(format!("primary key of {} not found", table_def.name)))?
+        .parts
+        .into_iter()
+        .map(|part| match part.field {
+            NumOrStr::Str(name) => name,
+            NumOrStr::Num(index) => table_def.format[index as usize].name.clone(),
+        })
+        .collect();
+    Ok(primary_key)
+}
+
+/// Resolves the tables referenced by the foreign keys of `CREATE TABLE`.
+/// Only the primary key of a global table can be referenced: the global
+/// tables are present on every instance, so the keys are checked locally.
+fn resolve_table_constraints(
+    storage: &Catalog,
+    format: &[Field],
+    constraints: &[IrTableConstraint],
+) -> traft::Result<Vec<TableConstraint>> {
+    let mut result = Vec::with_capacity(constraints.len());
+    for constraint in constraints {
+        let constraint = match constraint {
+            IrTableConstraint::Check { name, expr } => TableConstraint::Check {
+                name: name.to_string(),
+                expr: expr.to_string(),
+            },
+            IrTableConstraint::ForeignKey {
+                name,
+                columns,
+                parent,
+                parent_columns,
+            } => {
+                let Some(parent_def) = storage.tables.by_name(parent)? else {
+                    return Err(error::DoesNotExist::Table(parent.clone()).into());
+                };
+                if parent_def.distribution != Distribution::Global {
+                    return Err(CreateTableError::ForeignKeyParentNotGlobal {
+                        name: name.to_string(),
+                        parent: parent.to_string(),
+                    }
+                    .into());
+                }
+                let primary_key = primary_key_columns(storage, &parent_def)?;
+                let references_primary_key =
+                    parent_columns.is_empty() || parent_columns.iter().eq(primary_key.iter());
+                if !references_primary_key || columns.len() != primary_key.len() {
+                    return Err(CreateTableError::ForeignKeyNotPrimaryKey {
+                        name: name.to_string(),
+                        parent: parent.to_string(),
+                        key: primary_key.join(", "),
+                    }
+                    .into());
+                }
+                for (column, parent_column) in columns.iter().zip(&primary_key) {
+                    let field = format
+                        .iter()
+                        .find(|f| f.name == *column)
+                        .expect("foreign key columns are checked on parsing");
+                    let parent_field = parent_def
+                        .format
+                        .iter()
+                        .find(|f| f.name == *parent_column)
+                        .expect("primary key column should exist");
+                    if field.r#type != parent_field.field_type {
+                        return Err(CreateTableError::ForeignKeyTypeMismatch {
+                            name: name.to_string(),
+                            column: column.to_string(),
+                            ty: field.r#type.to_string(),
+                            parent_column: parent_column.clone(),
+                            parent_ty: parent_field.field_type.to_string(),
+                        }
+                        .into());
+                    }
+                }
+                TableConstraint::ForeignKey {
+                    name: name.to_string(),
+                    columns: columns.iter().map(SmolStr::to_string).collect(),
+                    parent: parent_def.id,
+                }
+            }
+        };
+        result.push(constraint);
+    }
+    Ok(result)
+}
+
 fn ddl_ir_node_to_op_or_result(
     ddl: &DdlOwned,
     current_user: UserId,
@@ -1244,9 +1641,22 @@ fn ddl_ir_node_to_op_or_result(
             engine_type,
             tier,
             if_not_exists,
+            partition_by,
+            constraints,
             ..
         }) => {
-            let format = format
+            let defaults = format
+                .iter()
+                .filter_map(|f| {
+                    Some(ColumnDefault {
+                        column: f.name.to_string(),
+                        expr: f.default.as_ref()?.to_string(),
+                        always: f.generated_always,
+                    })
+                })
+                .collect();
+            let sequences: Vec<_> = format.iter().filter_map(|f| f.sequence.as_ref()).collect();
+            let format: Vec<_> = format
                 .iter()
                 .map(|f| Field {
                     name: f.name.to_string(),
@@ -1260,6 +1670,7 @@ fn ddl_ir_node_to_op_or_result(
                 DistributionParam::Global
             };
 
+            let constraints = resolve_table_constraints(storage, &format, constraints)?;
             let primary_key = primary_key.iter().cloned().map(String::from).collect();
             let sharding_key = sharding_key
                 .as_ref()
@@ -1278,6 +1689,12 @@ fn ddl_ir_node_to_op_or_result(
                 timeout: None,
                 owner: current_user,
                 tier: tier.as_ref().map(SmolStr::to_string),
+                defaults,
+                partition: partition_by.as_ref().map(|p| TablePartition::By {
+                    strategy: p.strategy,
+                    column: p.column.to_string(),
+                }),
+                constraints,
             };
             params.validate()?;
 
@@ -1288,9 +1705,143 @@ fn ddl_ir_node_to_op_or_result(
                     return Err(error::AlreadyExists::Table(params.name.to_smolstr()).into());
                 }
             }
+            if storage.views.by_name(name)?.is_some() {
+                return Err(error::AlreadyExists::View(name.clone()).into());
+            }
+            check_schema_exists(storage, name)?;
 
             params.check_tier_exists(storage)?;
 
+            // Sequences backing `SERIAL` and identity columns are created
+            // and dropped along with the table.
+            let first_sequence_id = storage.sequences.max_sequence_id()?.unwrap_or(0) + 1;
+            let mut table_sequences = Vec::with_capacity(sequences.len());
+            for (sequence, id) in sequences.into_iter().zip(first_sequence_id..) {
+                if storage.sequences.by_name(sequence)?.is_some() {
+                    return Err(error::AlreadyExists::Sequence(sequence.clone()).into());
+                }
+                table_sequences.push(TableSequence {
+                    id,
+                    name: sequence.to_string(),
+                });
+            }
+
+            params.choose_id_if_not_specified()?;
+            params.test_create_space(storage)?;
+            let mut ddl = params.into_ddl()?;
+            if let OpDdl::CreateTable { sequences, .. } = &mut ddl {
+                *sequences = table_sequences;
+            }
+            Ok(Continue(Op::DdlPrepare {
+                schema_version,
+                ddl,
+            }))
+        }
+        DdlOwned::CreatePartition(CreatePartition {
+            name,
+            parent,
+            bound,
+            if_not_exists,
+            ..
+        }) => {
+            if storage.tables.by_name(name)?.is_some() {
+                if *if_not_exists {
+                    return Ok(Break(ConsumerResult { row_count: 0 }));
+                } else {
+                    return Err(error::AlreadyExists::Table(name.clone()).into());
+                }
+            }
+            if storage.views.by_name(name)?.is_some() {
+                return Err(error::AlreadyExists::View(name.clone()).into());
+            }
+            check_schema_exists(storage, name)?;
+            let Some(parent_def) = storage.tables.by_name(parent)? else {
+                return Err(error::DoesNotExist::Table(parent.clone()).into());
+            };
+            let Some(TablePartition::By { strategy, column }) = &parent_def.partition else {
+                return Err(Error::Other(
+                    format!("table {parent} is not partitioned").into(),
+                ));
+            };
+
+            let key_field = parent_def
+                .format
+                .iter()
+                .find(|f| f.name == *column)
+                .expect("partition key column should exist");
+            let bound = partition_bound_for_field(bound, *strategy, key_field)?;
+            for partition in storage.tables.partitions_of(parent_def.id)? {
+                let Some(TablePartition::Of {
+                    bound: other_bound, ..
+                }) = &partition.partition
+                else {
+                    continue;
+                };
+                let both_default =
+                    bound == PartitionBound::Default && *other_bound == PartitionBound::Default;
+                if both_default || bound.overlaps(other_bound) {
+                    return Err(Error::Other(
+                        format!(
+                            "partition {name} would overlap partition {}",
+                            partition.name
+                        )
+                        .into(),
+                    ));
+                }
+            }
+
+            // The partition inherits the columns, the primary key
+            // and the distribution of the partitioned table.
+            let (distribution, sharding_key, tier) = match &parent_def.distribution {
+                Distribution::Global => (DistributionParam::Global, None, None),
+                Distribution::ShardedImplicitly {
+                    sharding_key, tier, ..
+                } => (
+                    DistributionParam::Sharded,
+                    Some(sharding_key.clone()),
+                    Some(tier.clone()),
+                ),
+                Distribution::ShardedByField { .. } => {
+                    return Err(Error::Unsupported(error::Unsupported::new(
+                        "partitions of tables sharded by field".into(),
+                        None,
+                    )));
+                }
+            };
+            let format = parent_def
+                .format
+                .iter()
+                .filter(|f| distribution == DistributionParam::Global || f.name != "bucket_id")
+                .map(|f| Field {
+                    name: f.name.clone(),
+                    r#type: f.field_type,
+                    is_nullable: f.is_nullable,
+                })
+                .collect();
+            let primary_key = primary_key_columns(storage, &parent_def)?;
+
+            let mut params = CreateTableParams {
+                id: None,
+                name: name.to_string(),
+                format,
+                primary_key,
+                distribution,
+                by_field: None,
+                sharding_key,
+                sharding_fn: Some(ShardingFn::Murmur3),
+                engine: Some(parent_def.engine),
+                timeout: None,
+                owner: current_user,
+                tier,
+                defaults: parent_def.defaults.clone().unwrap_or_default(),
+                partition: Some(TablePartition::Of {
+                    parent: parent_def.id,
+                    bound,
+                }),
+                constraints: parent_def.constraints.clone().unwrap_or_default(),
+            };
+            params.validate()?;
+            params.check_tier_exists(storage)?;
             params.choose_id_if_not_specified()?;
             params.test_create_space(storage)?;
             let ddl = params.into_ddl()?;
@@ -1309,8 +1860,28 @@ fn ddl_ir_node_to_op_or_result(
                     return Err(error::DoesNotExist::Table(name.clone()).into());
                 }
             };
+            for table_def in storage.tables.iter()? {
+                let constraints = table_def.constraints.iter().flatten();
+                for constraint in constraints {
+                    if matches!(constraint, TableConstraint::ForeignKey { parent, .. } if *parent == space_def.id)
+                    {
+                        return Err(Error::other(format!(
+                            "cannot drop table {name}: foreign key {} of table {} references it",
+                            constraint.name(),
+                            table_def.name,
+                        )));
+                    }
+                }
+            }
+            // Partitions are dropped one at a time before the partitioned
+            // table itself, the request is retried after each of them
+            // (see `reenterable_schema_change_request`).
+            let id = match storage.tables.partitions_of(space_def.id)?.first() {
+                Some(partition) => partition.id,
+                None => space_def.id,
+            };
             let ddl = OpDdl::DropTable {
-                id: space_def.id,
+                id,
                 initiator: current_user,
             };
             Ok(Continue(Op::DdlPrepare {
@@ -1322,6 +1893,12 @@ fn ddl_ir_node_to_op_or_result(
             let Some(space_def) = storage.tables.by_name(name)? else {
                 return Err(error::DoesNotExist::Table(name.clone()).into());
             };
+            if matches!(space_def.partition, Some(TablePartition::By { .. })) {
+                return Err(Error::Unsupported(error::Unsupported::new(
+                    "TRUNCATE of a partitioned table".into(),
+                    Some("truncate its partitions instead".into()),
+                )));
+            }
             let ddl = OpDdl::TruncateTable {
                 id: space_def.id,
                 initiator: current_user,
@@ -1544,58 +2121,184 @@ fn ddl_ir_node_to_op_or_result(
                 ddl,
             }))
         }
-        DdlOwned::AlterTable(alter_table) => {
-            let Some(table) = &storage.tables.by_name(&alter_table.name)? else {
-                return Err(error::DoesNotExist::Table(alter_table.name.clone()).into());
+        DdlOwned::CreateSequence(CreateSequence {
+            name,
+            start,
+            increment,
+            if_not_exists,
+            ..
+        }) => {
+            if storage.sequences.by_name(name)?.is_some() {
+                if *if_not_exists {
+                    return Ok(Break(ConsumerResult { row_count: 0 }));
+                } else {
+                    return Err(error::AlreadyExists::Sequence(name.clone()).into());
+                }
+            }
+            let ddl = OpDdl::CreateSequence {
+                id: storage.sequences.max_sequence_id()?.unwrap_or(0) + 1,
+                name: name.to_string(),
+                start: *start,
+                increment: *increment,
+                owner: current_user,
             };
-
-            match &alter_table.op {
-                AlterTableOp::AlterColumn(columns) => {
-                    let current_table_format = table.format.clone();
-                    let mut new_table_format = current_table_format.clone(); // inevitable clone
-
-                    for op in columns.iter() {
-                        match op {
-                            &AlterColumn::Add {
-                                ref column,
-                                if_not_exists,
-                            } => {
-                                // due to unclear semantics of IF NOT EXISTS
-                                if if_not_exists {
-                                    return Err(Error::Unsupported(error::Unsupported::new(
-                                        "IF NOT EXISTS".into(),
-                                        None,
-                                    )));
-                                }
-
-                                // do not add this column with the same name
-                                for table_field in &new_table_format {
-                                    if table_field.name == column.name {
-                                        return Err(error::AlreadyExists::Column(
-                                            column.name.clone(),
-                                        )
-                                        .into());
-                                    }
-                                }
-
-                                // append this new column
-                                let field = tarantool::space::Field {
-                                    name: column.name.to_string(),
-                                    field_type: FieldType::from(&column.data_type),
-                                    is_nullable: column.is_nullable,
-                                };
-                                new_table_format.push(field);
-                            }
-                        }
-                    }
-
-                    Ok(Continue(Op::DdlPrepare {
-                        schema_version,
+            Ok(Continue(Op::DdlPrepare {
+                schema_version,
+                ddl,
+            }))
+        }
+        DdlOwned::DropSequence(DropSequence {
+            name, if_exists, ..
+        }) => {
+            let Some(sequence) = storage.sequences.by_name(name)? else {
+                if *if_exists {
+                    return Ok(Break(ConsumerResult { row_count: 0 }));
+                } else {
+                    return Err(error::DoesNotExist::Sequence(name.clone()).into());
+                }
+            };
+            if let Some(table_id) = sequence.table_id {
+                let table = storage.tables.get(table_id)?.map(|t| t.name);
+                let table = table.unwrap_or_else(|| table_id.to_string());
+                return Err(Error::Other(
+                    format!("cannot drop sequence {name} because table {table} requires it").into(),
+                ));
+            }
+            let ddl = OpDdl::DropSequence {
+                id: sequence.id,
+                initiator: current_user,
+            };
+            Ok(Continue(Op::DdlPrepare {
+                schema_version,
+                ddl,
+            }))
+        }
+        DdlOwned::CreateView(CreateView {
+            name,
+            query,
+            or_replace,
+            ..
+        }) => {
+            if storage.tables.by_name(name)?.is_some() {
+                return Err(error::AlreadyExists::Table(name.clone()).into());
+            }
+            let existing = storage.views.by_name(name)?;
+            if existing.is_some() && !*or_replace {
+                return Err(error::AlreadyExists::View(name.clone()).into());
+            }
+            check_schema_exists(storage, name)?;
+            // A replaced view keeps its owner, so the new query is checked
+            // against the owner's privileges.
+            let owner = existing.as_ref().map_or(current_user, |view| view.owner);
+            check_view_query(query, owner)?;
+
+            let ddl = OpDdl::CreateView {
+                id: match &existing {
+                    Some(view) => view.id,
+                    None => storage.views.max_view_id()?.unwrap_or(0) + 1,
+                },
+                name: name.to_string(),
+                query: query.to_string(),
+                owner,
+                replace: existing.is_some(),
+            };
+            Ok(Continue(Op::DdlPrepare {
+                schema_version,
+                ddl,
+            }))
+        }
+        DdlOwned::DropView(DropView {
+            name, if_exists, ..
+        }) => {
+            let Some(view) = storage.views.by_name(name)? else {
+                if *if_exists {
+                    return Ok(Break(ConsumerResult { row_count: 0 }));
+                } else {
+                    return Err(error::DoesNotExist::View(name.clone()).into());
+                }
+            };
+            let ddl = OpDdl::DropView {
+                id: view.id,
+                initiator: current_user,
+            };
+            Ok(Continue(Op::DdlPrepare {
+                schema_version,
+                ddl,
+            }))
+        }
+        DdlOwned::AlterTable(alter_table) => {
+            let Some(table) = &storage.tables.by_name(&alter_table.name)? else {
+                return Err(error::DoesNotExist::Table(alter_table.name.clone()).into());
+            };
+
+            match &alter_table.op {
+                AlterTableOp::AlterColumn(columns) => {
+                    // Partitions must have the same columns as the partitioned table.
+                    if table.partition.is_some() {
+                        return Err(Error::Unsupported(error::Unsupported::new(
+                            "ALTER TABLE ADD COLUMN for partitioned tables and partitions".into(),
+                            None,
+                        )));
+                    }
+                    let current_table_format = table.format.clone();
+                    let mut new_table_format = current_table_format.clone(); // inevitable clone
+                    let current_table_defaults = table.defaults.clone().unwrap_or_default();
+                    let mut new_table_defaults = current_table_defaults.clone();
+                    let mut added_values = Vec::with_capacity(columns.len());
+
+                    for op in columns.iter() {
+                        match op {
+                            &AlterColumn::Add {
+                                ref column,
+                                if_not_exists,
+                            } => {
+                                // due to unclear semantics of IF NOT EXISTS
+                                if if_not_exists {
+                                    return Err(Error::Unsupported(error::Unsupported::new(
+                                        "IF NOT EXISTS".into(),
+                                        None,
+                                    )));
+                                }
+
+                                // do not add this column with the same name
+                                for table_field in &new_table_format {
+                                    if table_field.name == column.name {
+                                        return Err(error::AlreadyExists::Column(
+                                            column.name.clone(),
+                                        )
+                                        .into());
+                                    }
+                                }
+
+                                // append this new column
+                                let field = tarantool::space::Field {
+                                    name: column.name.to_string(),
+                                    field_type: FieldType::from(&column.data_type),
+                                    is_nullable: column.is_nullable,
+                                };
+                                new_table_format.push(field);
+                                if let Some(expr) = &column.default {
+                                    new_table_defaults.push(ColumnDefault {
+                                        column: column.name.to_string(),
+                                        expr: expr.to_string(),
+                                        always: false,
+                                    });
+                                }
+                                added_values.push(added_column_value(column)?);
+                            }
+                        }
+                    }
+
+                    Ok(Continue(Op::DdlPrepare {
+                        schema_version,
                         ddl: OpDdl::ChangeFormat {
                             table_id: table.id,
                             old_format: current_table_format,
                             new_format: new_table_format,
                             initiator_id: current_user,
+                            new_defaults: new_table_defaults,
+                            old_defaults: current_table_defaults,
+                            added_values,
                         },
                     }))
                 }
@@ -1616,9 +2319,65 @@ fn ddl_ir_node_to_op_or_result(
                         },
                     }))
                 }
+                AlterTableOp::DetachPartition { partition } => {
+                    if !matches!(table.partition, Some(TablePartition::By { .. })) {
+                        return Err(Error::Other(
+                            format!("table {} is not partitioned", table.name).into(),
+                        ));
+                    }
+                    let Some(partition_def) = storage.tables.by_name(partition)? else {
+                        return Err(error::DoesNotExist::Table(partition.clone()).into());
+                    };
+                    if !matches!(
+                        partition_def.partition,
+                        Some(TablePartition::Of { parent, .. }) if parent == table.id
+                    ) {
+                        return Err(Error::Other(
+                            format!(
+                                "table {partition} is not a partition of table {}",
+                                table.name
+                            )
+                            .into(),
+                        ));
+                    }
+
+                    Ok(Continue(Op::DdlPrepare {
+                        schema_version,
+                        ddl: OpDdl::DetachPartition {
+                            table_id: partition_def.id,
+                            parent_id: table.id,
+                            initiator: current_user,
+                        },
+                    }))
+                }
             }
         }
-        DdlOwned::SetParam(SetParam { param_value, .. }) => {
+        DdlOwned::SetParam(SetParam {
+            scope_type,
+            param_value,
+            ..
+        }) => {
+            if let SetParamValue::SearchPath { schemas } = param_value {
+                if *scope_type == SetParamScopeType::Local {
+                    return Err(Error::Unsupported(error::Unsupported::new(
+                        "SET LOCAL search_path".into(),
+                        Some("use SET search_path instead".into()),
+                    )));
+                }
+                session::set_search_path(SessionKey::current(), schemas.clone());
+                return Ok(Break(ConsumerResult { row_count: 0 }));
+            }
+            if let SetParamValue::StatementTimeout { millis } = param_value {
+                if *scope_type == SetParamScopeType::Local {
+                    return Err(Error::Unsupported(error::Unsupported::new(
+                        "SET LOCAL statement_timeout".into(),
+                        Some("use SET statement_timeout instead".into()),
+                    )));
+                }
+                let timeout = millis.map(Duration::from_millis);
+                session::set_statement_timeout(SessionKey::current(), timeout);
+                return Ok(Break(ConsumerResult { row_count: 0 }));
+            }
             tlog!(
                 Warning,
                 "Parameters setting is currently disabled. Skipping update for {}.",
@@ -1633,12 +2392,190 @@ fn ddl_ir_node_to_op_or_result(
             );
             Ok(Break(ConsumerResult { row_count: 0 }))
         }
-        DdlOwned::CreateSchema | DdlOwned::DropSchema => {
-            return Err(Error::Other(
-                "unreachable CreateSchema/DropSchema".to_string().into(),
-            ));
+        DdlOwned::CreateSchema(CreateSchema {
+            name,
+            if_not_exists,
+            ..
+        }) => {
+            // Catalog schemas are emulated and can't be created.
+            if storage.schemas.by_name(name)?.is_some() || pg_catalog::is_catalog_schema(name) {
+                if *if_not_exists {
+                    return Ok(Break(ConsumerResult { row_count: 0 }));
+                } else {
+                    return Err(error::AlreadyExists::Schema(name.clone()).into());
+                }
+            }
+            let ddl = OpDdl::CreateSchema {
+                id: storage.schemas.max_schema_id()?.unwrap_or(0) + 1,
+                name: name.to_string(),
+                owner: current_user,
+            };
+            Ok(Continue(Op::DdlPrepare {
+                schema_version,
+                ddl,
+            }))
+        }
+        DdlOwned::DropSchema(DropSchema {
+            name,
+            if_exists,
+            cascade,
+            ..
+        }) => {
+            let Some(schema) = storage.schemas.by_name(name)? else {
+                if *if_exists {
+                    return Ok(Break(ConsumerResult { row_count: 0 }));
+                } else {
+                    return Err(error::DoesNotExist::Schema(name.clone()).into());
+                }
+            };
+            let in_schema = |object: &str| split_schema_name(object).0 == schema.name;
+
+            // With CASCADE the views and then the tables of the schema are
+            // dropped one at a time before the schema itself, the request is
+            // retried after each of them (see `reenterable_schema_change_request`).
+            let view = storage.views.iter()?.find(|view| in_schema(&view.name));
+            let table = storage
+                .tables
+                .iter()?
+                .filter(|table| in_schema(&table.name))
+                // Partitions go before the partitioned tables.
+                .max_by_key(|table| matches!(table.partition, Some(TablePartition::Of { .. })));
+            let ddl = match (view, table) {
+                (None, None) => OpDdl::DropSchema {
+                    id: schema.id,
+                    initiator: current_user,
+                },
+                _ if !*cascade => {
+                    return Err(Error::other(format!(
+                        "schema {name} is not empty, use DROP SCHEMA {name} CASCADE to drop its tables and views as well"
+                    )));
+                }
+                (Some(view), _) => OpDdl::DropView {
+                    id: view.id,
+                    initiator: current_user,
+                },
+                (None, Some(table)) => OpDdl::DropTable {
+                    id: table.id,
+                    initiator: current_user,
+                },
+            };
+            Ok(Continue(Op::DdlPrepare {
+                schema_version,
+                ddl,
+            }))
+        }
+        DdlOwned::Analyze(_) => {
+            unreachable!("ANALYZE doesn't change the schema and is handled in dispatch")
+        }
+        DdlOwned::CreateTableAs(_) => {
+            unreachable!("CREATE TABLE AS is split into CREATE TABLE and INSERT in dispatch")
+        }
+    }
+}
+
+/// Calculates the value of the column added by `ALTER TABLE` for the
+/// existing rows: its `DEFAULT` or NULL. The value is evaluated once
+/// on the router and appended to the tuples along with the format change.
+fn added_column_value(column: &ColumnDef) -> traft::Result<Value> {
+    let Some(expr) = &column.default else {
+        return Ok(Value::Null);
+    };
+    let tuple = sql_dispatch(&format!("SELECT ({expr})"), vec![])?;
+    let (result,): (DQLQueryResult,) = tuple.decode()?;
+    let value = result
+        .rows
+        .into_iter()
+        .next()
+        .and_then(|row| row.into_iter().next())
+        .unwrap_or(Value::Null);
+    Ok(value.cast(column.data_type)?)
+}
+
+/// Cast the partition key value to the type of the partition key column.
+fn cast_partition_key(value: &Value, field: &tarantool::space::Field) -> traft::Result<Value> {
+    if let (FieldType::Datetime, Value::String(s)) = (field.field_type, value) {
+        use time::format_description::well_known::{Iso8601, Rfc3339};
+        use time::macros::format_description;
+
+        if let Ok(datetime) = time::OffsetDateTime::parse(s, &Rfc3339) {
+            return Ok(Value::Datetime(datetime.into()));
+        }
+        if let Ok(datetime) = time::OffsetDateTime::parse(s, &Iso8601::PARSING) {
+            return Ok(Value::Datetime(datetime.into()));
         }
+        if let Ok(date) = time::Date::parse(s, format_description!("[year]-[month]-[day]")) {
+            let datetime = date.midnight().assume_utc();
+            return Ok(Value::Datetime(datetime.into()));
+        }
+        return Err(Error::Other(
+            format!("failed to parse partition bound {s} as datetime").into(),
+        ));
     }
+    let ty = Type::new(field.field_type.as_str())?;
+    Ok(value.clone().cast(ty)?)
+}
+
+/// Validate the partition bound against the partitioning of the parent
+/// table and cast its values to the type of the partition key column.
+fn partition_bound_for_field(
+    bound: &PartitionBound,
+    strategy: PartitionStrategy,
+    field: &tarantool::space::Field,
+) -> traft::Result<PartitionBound> {
+    let cast = |value: &Value| cast_partition_key(value, field);
+    let bound = match (bound, strategy) {
+        (PartitionBound::Range { from, to }, PartitionStrategy::Range) => {
+            let from = from.as_ref().map(cast).transpose()?;
+            let to = to.as_ref().map(cast).transpose()?;
+            if let (Some(from), Some(to)) = (&from, &to) {
+                if !matches!(from.partial_cmp(to), Some(TrivalentOrdering::Less)) {
+                    return Err(Error::Other(
+                        format!("empty range bound specified for partition: from {from} to {to}")
+                            .into(),
+                    ));
+                }
+            }
+            PartitionBound::Range { from, to }
+        }
+        (PartitionBound::List { values }, PartitionStrategy::List) => PartitionBound::List {
+            values: values.iter().map(cast).collect::<traft::Result<_>>()?,
+        },
+        (&PartitionBound::Hash { modulus, remainder }, PartitionStrategy::Hash) => {
+            if modulus == 0 {
+                return Err(Error::Other(
+                    "modulus for hash partition must be an integer value greater than zero".into(),
+                ));
+            }
+            if remainder >= modulus {
+                return Err(Error::Other(
+                    "remainder for hash partition must be less than modulus".into(),
+                ));
+            }
+            PartitionBound::Hash { modulus, remainder }
+        }
+        (PartitionBound::Default, PartitionStrategy::Hash) => {
+            return Err(Error::Other(
+                "a hash-partitioned table may not have a default partition".into(),
+            ))
+        }
+        (PartitionBound::Default, _) => PartitionBound::Default,
+        (_, PartitionStrategy::Range) => {
+            return Err(Error::Other(
+                "invalid bound specification for a range partition".into(),
+            ))
+        }
+        (_, PartitionStrategy::List) => {
+            return Err(Error::Other(
+                "invalid bound specification for a list partition".into(),
+            ))
+        }
+        (_, PartitionStrategy::Hash) => {
+            return Err(Error::Other(
+                "invalid bound specification for a hash partition".into(),
+            ))
+        }
+    };
+    Ok(bound)
 }
 
 /// Check if operation is applied to the storage.
@@ -1833,6 +2770,119 @@ fn check_ddl_applied(
             // Can't guarantee that result of our operation anyway.
             error("Table format changed, but not sure that it's result of our operation.")
         }
+        OpDdl::CreateSequence { name, .. } => {
+            let Some(sequence_def) = storage.sequences.by_name(&name)? else {
+                tlog!(Warning, "Sequence `{name}` was already dropped");
+                return error("Sequence does not exist: either operation was aborted or sequence was dropped afterwards.");
+            };
+
+            if sequence_def.schema_version != schema_version {
+                #[rustfmt::skip]
+                tlog!(Warning, "Sequence `{name}` has changed, schema version: {}", sequence_def.schema_version);
+                #[rustfmt::skip]
+                return error("Can't find out the result of the operation, but sequence was changed afterwards.");
+            }
+
+            if !sequence_def.operable {
+                tlog!(Warning, "Sequence `{name}` is not operable. Try to found it's definition in _pico_sequence with schema_version={schema_version}. \
+                    If it's found there with operable=true, then operation was ended successfully, otherwise operation still in progress.");
+                return error("Sequence creation is in progress.");
+            }
+
+            Ok(())
+        }
+        OpDdl::DropSequence { id, .. } => {
+            let Some(sequence_def) = storage.sequences.by_id(id)? else {
+                tlog!(Warning, "Sequence with id `{id}` was already dropped, probably not by current DDL with schema_version={schema_version}");
+                return Ok(());
+            };
+
+            if sequence_def.operable {
+                #[rustfmt::skip]
+                tlog!(Warning, "Sequence with id `{id}` is operable while awaiting for result of DropSequence");
+                return error("Operation was aborted or after successfull operation was recreated");
+            }
+
+            #[rustfmt::skip]
+            tlog!(Warning, "Sequence with id `{id}` is not operable");
+            error("DropSequence not yet applied or another DropSequence on this sequence is in progress.")
+        }
+        OpDdl::CreateView { id, name, .. } => {
+            let Some(view_def) = storage.views.by_id(id)? else {
+                tlog!(Warning, "View `{name}` was already dropped");
+                return error("View does not exist: either operation was aborted or view was dropped afterwards.");
+            };
+
+            if view_def.schema_version != schema_version {
+                #[rustfmt::skip]
+                tlog!(Warning, "View `{name}` has changed, schema version: {}", view_def.schema_version);
+                #[rustfmt::skip]
+                return error("Can't find out the result of the operation, but view was changed afterwards.");
+            }
+
+            Ok(())
+        }
+        OpDdl::DropView { id, .. } => {
+            let Some(view_def) = storage.views.by_id(id)? else {
+                tlog!(Warning, "View with id `{id}` was already dropped, probably not by current DDL with schema_version={schema_version}");
+                return Ok(());
+            };
+
+            if view_def.operable {
+                #[rustfmt::skip]
+                tlog!(Warning, "View with id `{id}` is operable while awaiting for result of DropView");
+                return error("Operation was aborted or after successfull operation was recreated");
+            }
+
+            #[rustfmt::skip]
+            tlog!(Warning, "View with id `{id}` is not operable");
+            error("DropView not yet applied or another DropView on this view is in progress.")
+        }
+        OpDdl::CreateSchema { id, name, .. } => {
+            let Some(schema_def) = storage.schemas.by_id(id)? else {
+                tlog!(Warning, "Schema `{name}` was already dropped");
+                return error("Schema does not exist: either operation was aborted or schema was dropped afterwards.");
+            };
+
+            if schema_def.schema_version != schema_version {
+                #[rustfmt::skip]
+                tlog!(Warning, "Schema `{name}` has changed, schema version: {}", schema_def.schema_version);
+                #[rustfmt::skip]
+                return error("Can't find out the result of the operation, but schema was changed afterwards.");
+            }
+
+            Ok(())
+        }
+        OpDdl::DropSchema { id, .. } => {
+            let Some(schema_def) = storage.schemas.by_id(id)? else {
+                tlog!(Warning, "Schema with id `{id}` was already dropped, probably not by current DDL with schema_version={schema_version}");
+                return Ok(());
+            };
+
+            if schema_def.operable {
+                #[rustfmt::skip]
+                tlog!(Warning, "Schema with id `{id}` is operable while awaiting for result of DropSchema");
+                return error("Operation was aborted or after successfull operation was recreated");
+            }
+
+            #[rustfmt::skip]
+            tlog!(Warning, "Schema with id `{id}` is not operable");
+            error("DropSchema not yet applied or another DropSchema on this schema is in progress.")
+        }
+        OpDdl::DetachPartition { table_id, .. } => {
+            let Some(table_def) = storage.tables.get(table_id)? else {
+                tlog!(Warning, "Table with id `{table_id}` not found");
+                return error("Partition was dropped after the DetachPartition.");
+            };
+
+            if table_def.partition.is_some() {
+                #[rustfmt::skip]
+                tlog!(Warning, "Table `{}` is still a partition while awaiting for result of DetachPartition", table_def.name);
+                return error("DetachPartition was aborted or is not yet applied.");
+            }
+
+            Ok(())
+        }
         OpDdl::TruncateTable { .. } => {
             tlog!(Warning, "DdlPrepare for Truncate was compacted.");
             // Governor should deal with it anyway.
@@ -1859,7 +2909,8 @@ pub(crate) fn reenterable_schema_change_request(
     let timeout = duration_from_secs_f64_clamped(timeout);
     let deadline = Instant::now_fiber().saturating_add(timeout);
 
-    let _su = session::su(ADMIN_ID).expect("cant fail because admin should always have session");
+    let _su = tarantool::session::su(ADMIN_ID)
+        .expect("cant fail because admin should always have session");
 
     'retry: loop {
         if Instant::now_fiber() > deadline {
@@ -1900,6 +2951,30 @@ pub(crate) fn reenterable_schema_change_request(
             Continue(op) => op,
         };
 
+        // Partitions of the partitioned table and the contents of the
+        // dropped schema are dropped by separate operations, the requested
+        // object is handled on one of the next retries.
+        let is_dependent_object = match (&ir_node, &op) {
+            (
+                NodeOwned::Ddl(DdlOwned::DropTable(DropTable { name, .. })),
+                Op::DdlPrepare {
+                    ddl: OpDdl::DropTable { id, .. },
+                    ..
+                },
+            ) => storage
+                .tables
+                .get(*id)?
+                .is_some_and(|table_def| table_def.name != name.as_str()),
+            (
+                NodeOwned::Ddl(DdlOwned::DropSchema(_)),
+                Op::DdlPrepare {
+                    ddl: OpDdl::DropView { .. } | OpDdl::DropTable { .. },
+                    ..
+                },
+            ) => true,
+            _ => false,
+        };
+
         // TODO: Should look at https://git.picodata.io/picodata/picodata/picodata/-/issues/866.
         let predicate = cas::Predicate::new(index, cas::schema_change_ranges());
         let req = crate::cas::Request::new(op.clone(), predicate, current_user)?;
@@ -1942,6 +3017,10 @@ pub(crate) fn reenterable_schema_change_request(
             }
         }
 
+        if is_dependent_object {
+            continue 'retry;
+        }
+
         return Ok(ConsumerResult { row_count: 1 });
     }
 }
@@ -1951,8 +3030,34 @@ pub(crate) fn reenterable_schema_change_request(
 pub fn proc_sql_execute(raw: &RawBytes) -> traft::Result<Tuple> {
     let (raw_required, optional_bytes, cache_info) = decode_msgpack(raw)?;
     let mut required = RequiredData::try_from(EncodedRequiredData::from(raw_required))?;
+    let received = Instant::now_accurate();
+    crate::error_injection!(block "BLOCK_SQL_EXECUTE_ON_STORAGE");
+    let deadline = cancel::request_deadline(&required, received);
+    cancel::check_request(required.request_id.as_ref(), deadline)?;
+    if required.query_type == QueryType::DML {
+        wait_foreign_key_parents(&required, deadline)?;
+    }
+    if required.txn_id.is_some() {
+        return transaction::execute_on_storage(required, optional_bytes, cache_info, deadline);
+    }
+    execute_plan_on_storage(&mut required, optional_bytes, cache_info, deadline)
+}
+
+/// Executes a query sub-plan on the local storage in the current fiber.
+///
+/// `deadline` is the moment the statement timeout expires on this storage.
+pub(crate) fn execute_plan_on_storage(
+    required: &mut RequiredData,
+    optional_bytes: OptionalBytes,
+    cache_info: CacheInfo,
+    deadline: Option<Instant>,
+) -> traft::Result<Tuple> {
+    let _fragment = cancel::start_fragment(required.request_id.clone(), deadline);
     let runtime = StorageRuntime::new()?;
-    match runtime.execute_plan(&mut required, optional_bytes, cache_info) {
+    if required.collect_stats && required.query_type == QueryType::DQL {
+        return Ok(runtime.execute_plan_with_stats(required, optional_bytes, cache_info)?);
+    }
+    match runtime.execute_plan(required, optional_bytes, cache_info) {
         Ok(mut any_tuple) => {
             if let Some(tuple) = any_tuple.downcast_mut::<Tuple>() {
                 tlog!(Trace, "proc_sql_execute: Execution result: {tuple:?}");
@@ -1997,7 +3102,9 @@ fn do_dml_on_global_tbl(mut query: Query<RouterRuntime>) -> traft::Result<Consum
         if matches!(
             node,
             Relational::Insert(Insert {
-                conflict_strategy: ConflictStrategy::DoReplace | ConflictStrategy::DoNothing,
+                conflict_strategy: ConflictStrategy::DoReplace
+                    | ConflictStrategy::DoNothing
+                    | ConflictStrategy::DoUpdate,
                 ..
             })
         ) {
@@ -2032,6 +3139,20 @@ fn do_dml_on_global_tbl(mut query: Query<RouterRuntime>) -> traft::Result<Consum
         return Ok(ConsumerResult { row_count: 0 });
     }
 
+    let mut referenced_keys = None;
+    let mut restore_ops = Vec::new();
+    if matches!(dml_kind, DmlKind::Delete | DmlKind::Update) {
+        let ir = query.get_exec_plan().get_ir_plan();
+        referenced_keys = ReferencedKeysCheck::new(ir, &vtable, table_id)?;
+        if let Some(check) = &referenced_keys {
+            check.check_global()?;
+            check.check_sharded()?;
+            if !check.sharded.is_empty() {
+                restore_ops = restore_global_rows(ir, &vtable, table_id, current_user)?;
+            }
+        }
+    }
+
     // Convert virtual table to a batch of DML opcodes
 
     let ir = query.get_exec_plan().get_ir_plan();
@@ -2084,10 +3205,28 @@ fn do_dml_on_global_tbl(mut query: Query<RouterRuntime>) -> traft::Result<Consum
         let ops_count = ops.len();
         let op = crate::traft::op::Op::BatchDml { ops };
 
-        let predicate = Predicate::new(raft_index, []);
+        let ranges = referenced_keys
+            .as_ref()
+            .map(ReferencedKeysCheck::ranges)
+            .unwrap_or_default();
+        let predicate = Predicate::new(raft_index, ranges);
         let cas_req = crate::cas::Request::new(op, predicate, current_user)?;
         let res = crate::cas::compare_and_swap_and_wait(&cas_req, deadline)?;
-        res.no_retries()?;
+        let (index, _) = res.no_retries()?;
+
+        if let Some(check) = referenced_keys.filter(|check| !check.sharded.is_empty()) {
+            if let Err(e) = check.check_sharded() {
+                let ranges: Vec<_> = restore_ops
+                    .iter()
+                    .map(cas::Range::for_dml)
+                    .collect::<Result<_, _>>()?;
+                let op = crate::traft::op::Op::BatchDml { ops: restore_ops };
+                let predicate = Predicate::new(index, ranges);
+                let cas_req = crate::cas::Request::new(op, predicate, current_user)?;
+                crate::cas::compare_and_swap_and_wait(&cas_req, deadline)?.no_retries()?;
+                return Err(e);
+            }
+        }
 
         Ok(ConsumerResult {
             row_count: ops_count as u64,
@@ -2095,6 +3234,255 @@ fn do_dml_on_global_tbl(mut query: Query<RouterRuntime>) -> traft::Result<Consum
     })?
 }
 
+/// Foreign keys of the sharded tables are checked on the storage master
+/// against its replica of the referenced global table, which may lag behind
+/// the raft log. So before DML on a table with a foreign key the storage
+/// catches up with the raft leader to see the committed changes of the
+/// referenced rows.
+fn wait_foreign_key_parents(
+    required: &RequiredData,
+    deadline: Option<Instant>,
+) -> traft::Result<()> {
+    let node = node::global()?;
+    let mut has_foreign_key = false;
+    for table in required.schema_info.router_version_map.keys() {
+        let Some(table_def) = node.storage.tables.by_name(table)? else {
+            continue;
+        };
+        has_foreign_key |= table_def
+            .constraints
+            .iter()
+            .flatten()
+            .any(|c| matches!(c, TableConstraint::ForeignKey { .. }));
+    }
+    if !has_foreign_key {
+        return Ok(());
+    }
+    let timeout = match deadline {
+        Some(deadline) => deadline.duration_since(Instant::now_fiber()),
+        None => Duration::from_secs(DEFAULT_QUERY_TIMEOUT),
+    };
+    node.read_index(timeout)?;
+    Ok(())
+}
+
+/// Foreign key of a table referencing a global table.
+struct ReferencingKey {
+    table: String,
+    constraint: String,
+    columns: Vec<String>,
+}
+
+/// Rejects deleting the rows of a global table and changing their primary
+/// keys while they are referenced by foreign keys (the `NO ACTION` referential
+/// action). The referencing tables may be sharded, so the references are
+/// looked up with SQL queries.
+///
+/// The check of a global referencing table is made atomic with the CaS of the
+/// statement by a predicate over the referencing table. A sharded one can't be
+/// covered by the predicate, so it is checked once more after the CaS: the
+/// storages don't let new references in once the CaS is applied (see
+/// [`wait_foreign_key_parents`]), and the references inserted meanwhile make
+/// the statement restore the changed rows and fail.
+struct ReferencedKeysCheck {
+    table: String,
+    /// Referencing global tables, checked before the CaS.
+    global: Vec<(SpaceId, ReferencingKey)>,
+    /// Referencing sharded tables, checked before and after the CaS.
+    sharded: Vec<ReferencingKey>,
+    /// Primary keys of the deleted rows and of the updated rows whose key changes.
+    keys: Vec<Vec<Value>>,
+}
+
+impl ReferencedKeysCheck {
+    /// Returns `None` if the statement changes no referenced keys.
+    fn new(ir: &IrPlan, vtable: &VirtualTable, table_id: SpaceId) -> traft::Result<Option<Self>> {
+        let node = node::global()?;
+        let mut global = Vec::new();
+        let mut sharded = Vec::new();
+        with_su(ADMIN_ID, || -> traft::Result<()> {
+            for table_def in node.storage.tables.iter()? {
+                for constraint in table_def.constraints.iter().flatten() {
+                    let TableConstraint::ForeignKey {
+                        name,
+                        columns,
+                        parent,
+                    } = constraint
+                    else {
+                        continue;
+                    };
+                    if *parent != table_id {
+                        continue;
+                    }
+                    let key = ReferencingKey {
+                        table: table_def.name.clone(),
+                        constraint: name.clone(),
+                        columns: columns.clone(),
+                    };
+                    if matches!(table_def.distribution, Distribution::Global) {
+                        global.push((table_def.id, key));
+                    } else {
+                        sharded.push(key);
+                    }
+                }
+            }
+            Ok(())
+        })??;
+        if global.is_empty() && sharded.is_empty() {
+            return Ok(None);
+        }
+
+        let top = ir.get_top()?;
+        let table = ir.dml_node_table(top)?;
+        let mut keys = Vec::new();
+        match ir.get_relation_node(top)? {
+            // The child of the delete is a projection of the primary key.
+            Relational::Delete { .. } => {
+                let key_len = table.primary_key.positions.len();
+                for tuple in vtable.get_tuples() {
+                    keys.push(tuple[..key_len].to_vec());
+                }
+            }
+            Relational::Update(Update {
+                update_columns_map,
+                pk_positions,
+                ..
+            }) => {
+                for tuple in vtable.get_tuples() {
+                    let old_key: Vec<Value> =
+                        pk_positions.iter().map(|p| tuple[*p].clone()).collect();
+                    let key_changes =
+                        table
+                            .primary_key
+                            .positions
+                            .iter()
+                            .zip(&old_key)
+                            .any(|(column, old)| {
+                                update_columns_map
+                                    .get(column)
+                                    .is_some_and(|pos| tuple[*pos] != *old)
+                            });
+                    if key_changes {
+                        keys.push(old_key);
+                    }
+                }
+            }
+            _ => return Ok(None),
+        }
+        if keys.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            table: table.name.to_string(),
+            global,
+            sharded,
+            keys,
+        }))
+    }
+
+    /// Ranges of the referencing global tables for the CaS predicate.
+    fn ranges(&self) -> Vec<cas::Range> {
+        self.global
+            .iter()
+            .map(|(id, _)| cas::Range::new(*id))
+            .collect()
+    }
+
+    fn check_global(&self) -> traft::Result<()> {
+        for (_, key) in &self.global {
+            self.check(key)?;
+        }
+        Ok(())
+    }
+
+    fn check_sharded(&self) -> traft::Result<()> {
+        for key in &self.sharded {
+            self.check(key)?;
+        }
+        Ok(())
+    }
+
+    fn check(&self, key: &ReferencingKey) -> traft::Result<()> {
+        let mut params = Vec::with_capacity(self.keys.len() * key.columns.len());
+        let mut conditions = Vec::with_capacity(self.keys.len());
+        for values in &self.keys {
+            let equalities: Vec<_> = key
+                .columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| {
+                    params.push(value.clone());
+                    format!(r#""{column}" = ?"#)
+                })
+                .collect();
+            conditions.push(format!("({})", equalities.join(" AND ")));
+        }
+        let select = format!(
+            "SELECT 1 FROM {} WHERE {} LIMIT 1",
+            quoted_table_name(&key.table),
+            conditions.join(" OR ")
+        );
+        let tuple = with_su(ADMIN_ID, || sql_dispatch(&select, params))??;
+        let (result,): (DQLQueryResult,) = tuple.decode()?;
+        if !result.rows.is_empty() {
+            return Err(Error::other(format!(
+                "cannot delete or update a row of table {}: foreign key {} of table {} references it",
+                self.table, key.constraint, key.table,
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Operations restoring the rows of the global table changed by the DML,
+/// applied if the statement fails after its CaS.
+fn restore_global_rows(
+    ir: &IrPlan,
+    vtable: &VirtualTable,
+    table_id: SpaceId,
+    current_user: UserId,
+) -> traft::Result<Vec<Dml>> {
+    let top = ir.get_top()?;
+    let table = ir.dml_node_table(top)?;
+    let space = Space::from(table_id);
+    let mut ops = Vec::with_capacity(vtable.get_tuples().len());
+    for tuple in vtable.get_tuples() {
+        let old_key: Vec<Value> = match ir.get_relation_node(top)? {
+            Relational::Delete { .. } => tuple[..table.primary_key.positions.len()].to_vec(),
+            Relational::Update(Update {
+                update_columns_map,
+                pk_positions,
+                ..
+            }) => {
+                let old_key: Vec<Value> = pk_positions.iter().map(|p| tuple[*p].clone()).collect();
+                let new_key: Vec<Value> = table
+                    .primary_key
+                    .positions
+                    .iter()
+                    .zip(&old_key)
+                    .map(|(column, old)| match update_columns_map.get(column) {
+                        Some(pos) => tuple[*pos].clone(),
+                        None => old.clone(),
+                    })
+                    .collect();
+                if new_key != old_key {
+                    let new_key: Vec<EncodedValue> =
+                        new_key.iter().map(EncodedValue::from).collect();
+                    ops.push(Dml::delete(table_id, &new_key, current_user)?);
+                }
+                old_key
+            }
+            _ => continue,
+        };
+        let old_key: Vec<EncodedValue> = old_key.iter().map(EncodedValue::from).collect();
+        if let Some(old_tuple) = space.get(&old_key)? {
+            ops.push(Dml::replace(table_id, &old_tuple, current_user)?);
+        }
+    }
+    Ok(ops)
+}
+
 // TODO: move this to sbroad
 pub(crate) fn value_type_str(value: &Value) -> &'static str {
     match value {
@@ -2108,5 +3496,7 @@ pub(crate) fn value_type_str(value: &Value) -> &'static str {
         Value::Unsigned { .. } => "unsigned",
         Value::Tuple { .. } => "tuple",
         Value::Uuid { .. } => "uuid",
+        Value::Varbinary { .. } => "varbinary",
+        Value::Interval { .. } => "interval",
     }
 }
@@ -0,0 +1,246 @@
+//! `ANALYZE` command: gathering of the table statistics used by the planner.
+//!
+//! The router asks the masters of the replicasets storing the table for
+//! random samples of the table rows (see [`proc_sql_analyze`]), builds the
+//! statistics of the columns from the samples and stores them in the
+//! `_pico_table_stats` system table. Global tables are sampled on the
+//! router, as every instance stores all of their rows.
+
+use crate::access_control::{access_check_analyze, is_superuser};
+use crate::cas;
+use crate::instance::InstanceName;
+use crate::schema::{Distribution, TableDef, TableStatsDef, ADMIN_ID};
+use crate::storage::SPACE_ID_INTERNAL_MAX;
+use crate::storage::{space_by_id, Catalog, SystemTable, TableStatistics, ToEntryIter};
+use crate::traft::error::{self, Error};
+use crate::traft::node::Node as TraftNode;
+use crate::traft::op::{Dml, Op};
+use crate::traft::{self, RaftIndex};
+use crate::util::{duration_from_secs_f64_clamped, effective_user_id};
+use rand::Rng;
+use sbroad::cbo::analyze::{build_column_stats, TableSample, DEFAULT_SAMPLE_ROWS_NUMBER};
+use sbroad::executor::result::ConsumerResult;
+use sbroad::ir::node::Analyze;
+use sbroad::ir::relation::Type;
+use sbroad::ir::value::Value;
+use smol_str::ToSmolStr;
+use std::collections::BTreeMap;
+use tarantool::fiber;
+use tarantool::index::IteratorType;
+use tarantool::msgpack;
+use tarantool::session::{with_su, UserId};
+use tarantool::space::SpaceId;
+use tarantool::time::Instant;
+use tarantool::tuple::Tuple;
+
+/// Number of the scanned rows after which the sampling fiber yields,
+/// so that sampling of a big table doesn't block the other requests.
+const ROWS_PER_YIELD: usize = 1000;
+
+crate::define_rpc_request! {
+    /// Returns a random sample of the table rows stored on the current
+    /// instance along with the number of the stored rows.
+    fn proc_sql_analyze(req: Request) -> traft::Result<Response> {
+        let sample = sample_table(req.table_id, req.sample_size)?;
+        Ok(Response { sample })
+    }
+
+    /// Request to sample the rows of the table.
+    pub struct Request {
+        pub table_id: SpaceId,
+        /// Maximum number of the rows in the sample.
+        pub sample_size: usize,
+    }
+
+    pub struct Response {
+        pub sample: TableSample,
+    }
+}
+
+fn decode_row(tuple: &Tuple) -> tarantool::Result<Vec<Value>> {
+    Ok(msgpack::decode(&tuple.to_vec())?)
+}
+
+/// Sample the rows of the local table with the reservoir sampling:
+/// every row gets into the sample with the same probability.
+fn sample_table(table_id: SpaceId, sample_size: usize) -> traft::Result<TableSample> {
+    let space = space_by_id(table_id)?;
+    let mut rng = rand::thread_rng();
+    let mut rows = Vec::new();
+    let mut rows_number: u64 = 0;
+    for (position, tuple) in space.select(IteratorType::All, &())?.enumerate() {
+        rows_number += 1;
+        if rows.len() < sample_size {
+            rows.push(decode_row(&tuple)?);
+        } else {
+            let slot = rng.gen_range(0..=position);
+            if slot < sample_size {
+                rows[slot] = decode_row(&tuple)?;
+            }
+        }
+        if (position + 1) % ROWS_PER_YIELD == 0 {
+            fiber::reschedule();
+        }
+    }
+    Ok(TableSample { rows_number, rows })
+}
+
+/// Tables analyzed by the command: the specified table along with its
+/// partitions or all the user tables owned by the user.
+fn tables_to_analyze(
+    storage: &Catalog,
+    table: Option<&str>,
+    as_user: UserId,
+) -> traft::Result<Vec<TableDef>> {
+    let Some(name) = table else {
+        let tables = storage
+            .tables
+            .iter()?
+            .filter(|table| table.id > SPACE_ID_INTERNAL_MAX && table.operable)
+            .filter(|table| table.owner == as_user || is_superuser(as_user))
+            .collect();
+        return Ok(tables);
+    };
+
+    let Some(table) = storage.tables.by_name(name)? else {
+        return Err(error::DoesNotExist::Table(name.into()).into());
+    };
+    access_check_analyze(&table, as_user)?;
+    let mut tables = storage.tables.partitions_of(table.id)?;
+    tables.push(table);
+    Ok(tables)
+}
+
+/// Sample the table on all the replicasets storing it.
+fn collect_samples(
+    node: &TraftNode,
+    table: &TableDef,
+    deadline: Instant,
+) -> traft::Result<Vec<TableSample>> {
+    let tier = match &table.distribution {
+        Distribution::Global => {
+            return Ok(vec![sample_table(table.id, DEFAULT_SAMPLE_ROWS_NUMBER)?]);
+        }
+        Distribution::ShardedImplicitly { tier, .. }
+        | Distribution::ShardedByField { tier, .. } => tier,
+    };
+
+    let masters: Vec<InstanceName> = node
+        .topology_cache
+        .get()
+        .all_replicasets()
+        .filter(|replicaset| replicaset.tier == *tier)
+        .map(|replicaset| replicaset.current_master_name.clone())
+        .collect();
+    // The samples are weighted by the number of rows on the replicaset,
+    // so the total sample size doesn't depend on the number of replicasets.
+    let request = Request {
+        table_id: table.id,
+        sample_size: DEFAULT_SAMPLE_ROWS_NUMBER.div_ceil(masters.len().max(1)),
+    };
+
+    let mut futures = Vec::with_capacity(masters.len());
+    for master in &masters {
+        let future = node.pool.call(
+            master,
+            crate::proc_name!(proc_sql_analyze),
+            &request,
+            deadline.duration_since(Instant::now_fiber()),
+        )?;
+        futures.push(future);
+    }
+    let mut samples = Vec::with_capacity(futures.len());
+    for future in futures {
+        samples.push(fiber::block_on(future)?.sample);
+    }
+    Ok(samples)
+}
+
+fn gather_table_stats(
+    node: &TraftNode,
+    table: &TableDef,
+    deadline: Instant,
+) -> traft::Result<TableStatsDef> {
+    let samples = collect_samples(node, table, deadline)?;
+    let mut columns = BTreeMap::new();
+    for (position, field) in table.format.iter().enumerate() {
+        let column_type = Type::new(field.field_type.as_str())?;
+        if let Some(stats) = build_column_stats(&samples, position, &column_type)? {
+            columns.insert(field.name.clone(), stats);
+        }
+    }
+    Ok(TableStatsDef {
+        table_id: table.id,
+        rows_number: samples.iter().map(|sample| sample.rows_number).sum(),
+        columns,
+    })
+}
+
+/// Store the statistics unless their tables have been dropped
+/// since the raft `index`.
+fn store_table_stats(
+    node: &TraftNode,
+    mut stats: Vec<TableStatsDef>,
+    mut index: RaftIndex,
+    deadline: Instant,
+) -> traft::Result<()> {
+    loop {
+        let storage = &node.storage;
+        let mut ops = Vec::with_capacity(stats.len());
+        for table_stats in &stats {
+            let op = Dml::replace(TableStatistics::TABLE_ID, table_stats, ADMIN_ID)?;
+            ops.push(op);
+        }
+        if ops.is_empty() {
+            return Ok(());
+        }
+
+        // Dropping of the table conflicts with the schema change ranges,
+        // so the statistics of the dropped tables are never stored.
+        let predicate = cas::Predicate::new(index, cas::schema_change_ranges());
+        let req = cas::Request::new(Op::BatchDml { ops }, predicate, ADMIN_ID)?;
+        let res = cas::compare_and_swap_and_wait(&req, deadline)?;
+        if !res.is_retriable_error() {
+            return Ok(());
+        }
+
+        index = node.get_index();
+        stats.retain(|table_stats| {
+            with_su(ADMIN_ID, || storage.tables.get(table_stats.table_id))
+                .ok()
+                .and_then(Result::ok)
+                .flatten()
+                .is_some()
+        });
+    }
+}
+
+/// Gather the statistics of the tables and store them in `_pico_table_stats`.
+/// Returns the number of the analyzed tables.
+pub fn analyze(node: &TraftNode, analyze: &Analyze) -> traft::Result<ConsumerResult> {
+    let current_user = effective_user_id();
+    let timeout = analyze
+        .timeout
+        .to_smolstr()
+        .parse::<f64>()
+        .map_err(|e| Error::Other(e.into()))?;
+    let deadline = Instant::now_fiber().saturating_add(duration_from_secs_f64_clamped(timeout));
+
+    // Tables dropped after this index are not stored. Read index protects
+    // from analyzing the stale list of the tables.
+    let index = node.read_index(deadline.duration_since(Instant::now_fiber()))?;
+    let tables = with_su(ADMIN_ID, || {
+        tables_to_analyze(&node.storage, analyze.table.as_deref(), current_user)
+    })??;
+
+    let mut stats = Vec::with_capacity(tables.len());
+    for table in &tables {
+        if Instant::now_fiber() > deadline {
+            return Err(Error::Timeout);
+        }
+        stats.push(gather_table_stats(node, table, deadline)?);
+    }
+    let row_count = stats.len() as u64;
+    store_table_stats(node, stats, index, deadline)?;
+    Ok(ConsumerResult { row_count })
+}
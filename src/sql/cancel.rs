@@ -0,0 +1,420 @@
+//! SQL query cancellation and limits.
+//!
+//! Every query dispatched by the router gets a request id, which is sent
+//! to the storages along with the query fragments. Cancelling a query marks
+//! it on the router (the router stops waiting for the storages reading data
+//! and doesn't dispatch the rest of the fragments) and on every instance of
+//! the cluster (storages reject the fragments of the cancelled query).
+//!
+//! Storages register the fragments they execute, see [`start_fragment`].
+//! A running fragment checks whether it was interrupted via
+//! [`check_fragment`] while applying DML and right before the commit, so
+//! the changes of an interrupted fragment are rolled back. The router waits
+//! for the DML fragments to finish, so a client receiving the cancellation
+//! error can be sure the storages have not committed the changes.
+//!
+//! Note that the cancellation request is handled by another fiber, so it
+//! reaches a fragment only when the fragment yields: e.g. while the
+//! tuples of `INSERT .. SELECT` are read or while vinyl DML is applied.
+//! Memtx DML doesn't yield, so once started it is interrupted only by
+//! the expired statement timeout, which is checked without yielding.
+//!
+//! A query whose statement timeout expires is cancelled the same way,
+//! except that the storages learn about it from the time left until the
+//! timeout, which is sent with every fragment.
+//!
+//! The number of queries a user executes concurrently on a router is
+//! limited by `sql_user_query_max`.
+//!
+//! Limitations:
+//! - a single local SQL statement executed by the storage can't be
+//!   interrupted, its run time is bounded by `sql_vdbe_opcode_max`.
+
+use crate::schema::ADMIN_ID;
+use crate::sql::router::DEFAULT_QUERY_TIMEOUT;
+use crate::sql::session;
+use crate::sql::transaction::SessionKey;
+use crate::tlog;
+use crate::traft::error::Error;
+use crate::traft::{self, node};
+use crate::util::effective_user_id;
+use sbroad::executor::protocol::RequiredData;
+use smol_str::{SmolStr, ToSmolStr};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+use tarantool::fiber::{self, FiberId};
+use tarantool::session::{with_su, UserId};
+use tarantool::time::Instant;
+use tarantool::tlua;
+use tarantool::uuid::Uuid;
+
+/// Storages remember cancelled requests for this period,
+/// fragments of a query can't arrive after its timeout.
+const CANCELLED_REQUEST_TTL: Duration = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+
+/// Timeout of a cancellation request sent to another instance.
+const CANCEL_RPC_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+struct RunningQuery {
+    request_id: SmolStr,
+    session: SessionKey,
+    user_id: UserId,
+    started: Instant,
+    /// Moment the statement timeout expires.
+    deadline: Option<Instant>,
+    cancelled: bool,
+}
+
+#[derive(Debug)]
+struct RunningFragment {
+    request_id: Option<SmolStr>,
+    /// Moment the statement timeout expires on this storage.
+    deadline: Option<Instant>,
+}
+
+thread_local! {
+    /// Queries dispatched by the router, keyed by the fiber executing them.
+    static RUNNING_QUERIES: RefCell<HashMap<FiberId, RunningQuery>> = RefCell::new(HashMap::new());
+    /// Query fragments executed by the storage, keyed by the fiber executing them.
+    static RUNNING_FRAGMENTS: RefCell<HashMap<FiberId, RunningFragment>> = RefCell::new(HashMap::new());
+    /// Cancelled requests with the time of cancellation.
+    static CANCELLED_REQUESTS: RefCell<HashMap<SmolStr, Instant>> = RefCell::new(HashMap::new());
+}
+
+/// Unregisters the query executed by the current fiber on drop.
+#[must_use]
+pub struct QueryGuard {
+    request_id: SmolStr,
+    deadline: Option<Instant>,
+    /// Not set for the nested queries.
+    fiber_id: Option<FiberId>,
+}
+
+impl QueryGuard {
+    #[inline(always)]
+    pub fn request_id(&self) -> &SmolStr {
+        &self.request_id
+    }
+
+    /// Time left until the statement timeout expires, `None` if there is no timeout.
+    pub fn time_left(&self) -> Option<Duration> {
+        self.deadline
+            .map(|deadline| deadline.duration_since(Instant::now_fiber()))
+    }
+}
+
+impl Drop for QueryGuard {
+    fn drop(&mut self) {
+        if let Some(fiber_id) = self.fiber_id {
+            RUNNING_QUERIES.with(|q| q.borrow_mut().remove(&fiber_id));
+        }
+    }
+}
+
+/// Registers the query executed by the current fiber.
+///
+/// Queries dispatched while executing another query in the same fiber
+/// (e.g. `CREATE TABLE AS`) share the request id and the statement timeout
+/// of the outer one.
+///
+/// # Errors
+/// - The user already executes `sql_user_query_max` queries on this instance.
+pub fn start_query() -> traft::Result<QueryGuard> {
+    let fiber_id = fiber::id();
+    let outer = RUNNING_QUERIES.with(|q| {
+        q.borrow()
+            .get(&fiber_id)
+            .map(|query| (query.request_id.clone(), query.deadline))
+    });
+    if let Some((request_id, deadline)) = outer {
+        return Ok(QueryGuard {
+            request_id,
+            deadline,
+            fiber_id: None,
+        });
+    }
+
+    let node = node::global()?;
+    let session = SessionKey::current();
+    let user_id = effective_user_id();
+    let user_query_max = node.storage.db_config.sql_user_query_max()?;
+    // The session value overrides the user's default, which in turn
+    // overrides the system one. Zero disables the timeout.
+    let timeout = match session::statement_timeout(session) {
+        Some(timeout) => Some(timeout),
+        None => with_su(ADMIN_ID, || node.storage.users.by_id(user_id))??
+            .and_then(|user| user.statement_timeout)
+            .map(Duration::from_millis),
+    };
+    let timeout = match timeout {
+        Some(timeout) if timeout.is_zero() => None,
+        Some(timeout) => Some(timeout),
+        None => node.storage.db_config.sql_statement_timeout()?,
+    };
+
+    let started = Instant::now_fiber();
+    let deadline = timeout.map(|timeout| started.saturating_add(timeout));
+    let request_id = Uuid::random().to_smolstr();
+    RUNNING_QUERIES.with(|queries| {
+        let mut queries = queries.borrow_mut();
+        if user_id != ADMIN_ID && user_query_max > 0 {
+            let user_queries = queries.values().filter(|q| q.user_id == user_id).count();
+            if user_queries as u64 >= user_query_max {
+                return Err(Error::other(format!(
+                    "too many concurrent queries of the user, \
+                     the limit is {user_query_max} (see sql_user_query_max)"
+                )));
+            }
+        }
+        let query = RunningQuery {
+            request_id: request_id.clone(),
+            session,
+            user_id,
+            started,
+            deadline,
+            cancelled: false,
+        };
+        queries.insert(fiber_id, query);
+        Ok(())
+    })?;
+
+    Ok(QueryGuard {
+        request_id,
+        deadline,
+        fiber_id: Some(fiber_id),
+    })
+}
+
+/// Returns an error if the query executed by the current fiber was cancelled
+/// or its statement timeout has expired.
+pub fn check_cancelled() -> traft::Result<()> {
+    RUNNING_QUERIES.with(|q| {
+        let queries = q.borrow();
+        let Some(query) = queries.get(&fiber::id()) else {
+            return Ok(());
+        };
+        if query.cancelled {
+            return Err(Error::QueryCanceled);
+        }
+        if query
+            .deadline
+            .is_some_and(|deadline| Instant::now_fiber() >= deadline)
+        {
+            return Err(Error::StatementTimeout);
+        }
+        Ok(())
+    })
+}
+
+/// Time left until the statement timeout of the query executed
+/// by the current fiber expires, `None` if there is no timeout.
+pub fn time_left() -> Option<Duration> {
+    let deadline = RUNNING_QUERIES.with(|q| q.borrow().get(&fiber::id())?.deadline)?;
+    Some(deadline.duration_since(Instant::now_fiber()))
+}
+
+/// Moment the statement timeout of the request received
+/// by the storage at `received` expires.
+pub fn request_deadline(required: &RequiredData, received: Instant) -> Option<Instant> {
+    required
+        .statement_timeout
+        .map(|timeout| received.saturating_add(timeout))
+}
+
+/// Returns an error if the request was cancelled or its statement timeout has expired.
+pub fn check_request(request_id: Option<&SmolStr>, deadline: Option<Instant>) -> traft::Result<()> {
+    // The cached fiber time is not updated while the fragment is executed.
+    if deadline.is_some_and(|deadline| Instant::now_accurate() >= deadline) {
+        return Err(Error::StatementTimeout);
+    }
+    let Some(request_id) = request_id else {
+        return Ok(());
+    };
+    if CANCELLED_REQUESTS.with(|r| r.borrow().contains_key(request_id)) {
+        return Err(Error::QueryCanceled);
+    }
+    Ok(())
+}
+
+/// Unregisters the fragment executed by the current fiber on drop.
+#[must_use]
+pub struct FragmentGuard {
+    fiber_id: FiberId,
+}
+
+impl Drop for FragmentGuard {
+    fn drop(&mut self) {
+        RUNNING_FRAGMENTS.with(|f| f.borrow_mut().remove(&self.fiber_id));
+    }
+}
+
+/// Registers the query fragment executed by the current fiber on the storage.
+pub fn start_fragment(request_id: Option<SmolStr>, deadline: Option<Instant>) -> FragmentGuard {
+    let fiber_id = fiber::id();
+    let fragment = RunningFragment {
+        request_id,
+        deadline,
+    };
+    RUNNING_FRAGMENTS.with(|f| f.borrow_mut().insert(fiber_id, fragment));
+    FragmentGuard { fiber_id }
+}
+
+/// Returns an error if the query fragment executed by the current fiber
+/// on the storage was cancelled or its statement timeout has expired.
+pub fn check_fragment() -> traft::Result<()> {
+    RUNNING_FRAGMENTS.with(|f| {
+        let fragments = f.borrow();
+        let Some(fragment) = fragments.get(&fiber::id()) else {
+            return Ok(());
+        };
+        check_request(fragment.request_id.as_ref(), fragment.deadline)
+    })
+}
+
+/// Cancels the request on the current instance.
+/// Returns true if the query was running on this instance as a router.
+pub fn cancel_locally(request_id: &SmolStr) -> bool {
+    let now = Instant::now_fiber();
+    CANCELLED_REQUESTS.with(|requests| {
+        let mut requests = requests.borrow_mut();
+        requests.retain(|_, at| now.duration_since(*at) < CANCELLED_REQUEST_TTL);
+        requests.insert(request_id.clone(), now);
+    });
+    RUNNING_QUERIES.with(|queries| {
+        let mut found = false;
+        for query in queries.borrow_mut().values_mut() {
+            if &query.request_id == request_id {
+                query.cancelled = true;
+                found = true;
+            }
+        }
+        found
+    })
+}
+
+/// Cancels the request on every instance of the cluster.
+/// Returns true if the query was found running on some router.
+///
+/// Failures to reach other instances are logged, but not returned.
+pub fn cancel_query(request_id: &SmolStr) -> traft::Result<bool> {
+    let mut found = cancel_locally(request_id);
+
+    let node = node::global()?;
+    let instances: Vec<_> = {
+        let topology = node.topology_cache.get();
+        let my_name = node.topology_cache.my_instance_name();
+        topology
+            .all_instances()
+            .filter(|i| i.may_respond() && i.name != my_name)
+            .map(|i| i.name.clone())
+            .collect()
+    };
+
+    let request = Request {
+        request_id: request_id.to_string(),
+    };
+    let mut futures = Vec::with_capacity(instances.len());
+    for instance in &instances {
+        match node.pool.call(
+            instance,
+            crate::proc_name!(proc_sql_cancel),
+            &request,
+            CANCEL_RPC_TIMEOUT,
+        ) {
+            Ok(future) => futures.push((instance, future)),
+            Err(e) => tlog!(
+                Warning,
+                "failed to cancel query {request_id} on {instance}: {e}"
+            ),
+        }
+    }
+    for (instance, future) in futures {
+        match fiber::block_on(future) {
+            Ok(response) => found |= response.found,
+            Err(e) => tlog!(
+                Warning,
+                "failed to cancel query {request_id} on {instance}: {e}"
+            ),
+        }
+    }
+    Ok(found)
+}
+
+/// Cancels the query executed by the session, if any.
+/// Returns true if there was a query to cancel.
+pub fn cancel_session(session: SessionKey) -> traft::Result<bool> {
+    let request_id = RUNNING_QUERIES.with(|queries| {
+        queries
+            .borrow()
+            .values()
+            .find(|query| query.session == session)
+            .map(|query| query.request_id.clone())
+    });
+    match request_id {
+        Some(request_id) => cancel_query(&request_id),
+        None => Ok(false),
+    }
+}
+
+/// Information about a query running on the current instance.
+#[derive(Clone, Debug, tlua::Push, tlua::PushInto)]
+pub struct QueryInfo {
+    pub request_id: String,
+    /// Either "iproto" or "pgproto".
+    pub session_kind: &'static str,
+    /// Tarantool session id or pgproto client id.
+    pub session_id: u64,
+    pub user_id: UserId,
+    /// Time since the query has started, in seconds.
+    pub duration: f64,
+    pub cancelled: bool,
+}
+
+/// Returns queries dispatched by the current instance.
+pub fn running_queries() -> Vec<QueryInfo> {
+    let now = Instant::now_fiber();
+    RUNNING_QUERIES.with(|queries| {
+        queries
+            .borrow()
+            .values()
+            .map(|query| {
+                let (session_kind, session_id) = match query.session {
+                    SessionKey::Iproto(id) => ("iproto", id),
+                    SessionKey::Pgproto(id) => ("pgproto", id),
+                };
+                QueryInfo {
+                    request_id: query.request_id.to_string(),
+                    session_kind,
+                    session_id,
+                    user_id: query.user_id,
+                    duration: now.duration_since(query.started).as_secs_f64(),
+                    cancelled: query.cancelled,
+                }
+            })
+            .collect()
+    })
+}
+
+crate::define_rpc_request! {
+    /// Cancels the SQL query on the current instance.
+    ///
+    /// Returns `found: true` if the query was running on this instance as a router.
+    fn proc_sql_cancel(req: Request) -> traft::Result<Response> {
+        let found = cancel_locally(&SmolStr::from(req.request_id));
+        Ok(Response { found })
+    }
+
+    /// Request to cancel an SQL query.
+    pub struct Request {
+        pub request_id: String,
+    }
+
+    /// Response to [`cancel::Request`].
+    ///
+    /// [`cancel::Request`]: Request
+    pub struct Response {
+        pub found: bool,
+    }
+}
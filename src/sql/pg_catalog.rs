@@ -0,0 +1,898 @@
+//! Emulation of the PostgreSQL system catalogs.
+//!
+//! Clients and tools (psql, JDBC and ODBC drivers, ORMs, BI tools) read the
+//! database metadata from the relations of the `pg_catalog` and
+//! `information_schema` schemas. Picodata provides the most used ones as
+//! read-only views built into the router: every time a query references a
+//! catalog relation, the relation query is generated from the system tables
+//! (`_pico_table`, `_pico_index`, `_pico_user`, `_pico_routine`, ...) as
+//! `SELECT * FROM (VALUES ...) AS "v" (<columns>)`. So the catalogs are always
+//! up to date and can be filtered, joined and aggregated like any other view.
+//!
+//! The relations of `pg_catalog` can also be referenced without the schema
+//! unless there is a table or a view with the same name.
+//!
+//! Only the commonly used columns are provided. Object ids (oids) are the ids
+//! of the objects in the system tables, except for the schemas, the views and
+//! the indexes, which don't have ids unique among the other objects of their
+//! catalog, see [`schema_oid`], [`view_oid`] and [`index_oid`].
+
+use crate::access_control::is_superuser;
+use crate::config::PicodataConfig;
+use crate::schema::{
+    split_schema_name, IndexDef, IndexOption, PrivilegeType, SchemaObjectType, TableConstraint,
+    TableDef, TablePartition, ADMIN_ID, UNIVERSE_ID,
+};
+use crate::sql::router::DEFAULT_BUCKET_COLUMN;
+use crate::storage::{Catalog, ToEntryIter};
+use crate::traft;
+use postgres_types::Type as PgType;
+use sbroad::executor::engine::helpers::DEFAULT_SCHEMA;
+use sbroad::ir::relation::Type;
+use std::collections::HashMap;
+use std::fmt::Write;
+use tarantool::session::{with_su, UserId};
+use tarantool::space::{Field, FieldType, SpaceId};
+use tarantool::util::NumOrStr;
+
+pub const PG_CATALOG_SCHEMA: &str = "pg_catalog";
+pub const INFORMATION_SCHEMA: &str = "information_schema";
+
+/// Oids of the builtin schemas, the same as in PostgreSQL.
+const PG_CATALOG_OID: i64 = 11;
+const PUBLIC_OID: i64 = 2200;
+const INFORMATION_SCHEMA_OID: i64 = 13000;
+
+/// PostgreSQL assigns oids starting from this one to the user objects.
+const FIRST_USER_OID: i64 = 16384;
+
+/// Oid of the `sql` language in `pg_language`.
+const SQL_LANGUAGE_OID: i64 = 14;
+
+#[inline]
+fn schema_oid(schema_id: u32) -> i64 {
+    FIRST_USER_OID + i64::from(schema_id)
+}
+
+/// Oids are unsigned 32-bit integers in PostgreSQL. Table ids are below
+/// [`VIEW_OID_MIN`], so the upper half of the range is reserved for the
+/// views and the indexes, which share `pg_class` with the tables.
+const VIEW_OID_MIN: i64 = 1 << 30;
+const INDEX_OID_MIN: i64 = 1 << 31;
+const OID_MAX: i64 = u32::MAX as i64;
+
+#[inline]
+fn view_oid(view_id: u32) -> i64 {
+    VIEW_OID_MIN + i64::from(view_id) % (INDEX_OID_MIN - VIEW_OID_MIN)
+}
+
+/// Indexes are identified by the table id and the index id (below 128).
+/// Oids of the indexes of tables with ids above 2^24 may collide.
+#[inline]
+fn index_oid(table_id: SpaceId, index_id: u32) -> i64 {
+    let id = (i64::from(table_id) << 7) + i64::from(index_id);
+    INDEX_OID_MIN + id % (OID_MAX - INDEX_OID_MIN + 1)
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ColumnType {
+    Int,
+    Text,
+    Bool,
+}
+
+impl ColumnType {
+    fn as_sql(self) -> &'static str {
+        match self {
+            ColumnType::Int => "integer",
+            ColumnType::Text => "text",
+            ColumnType::Bool => "boolean",
+        }
+    }
+}
+
+/// Value of a catalog relation column.
+#[derive(Clone, Debug)]
+enum Datum {
+    Int(i64),
+    Text(String),
+    Bool(bool),
+    Null,
+}
+
+impl From<i64> for Datum {
+    fn from(v: i64) -> Self {
+        Datum::Int(v)
+    }
+}
+
+impl From<i32> for Datum {
+    fn from(v: i32) -> Self {
+        Datum::Int(i64::from(v))
+    }
+}
+
+impl From<u32> for Datum {
+    fn from(v: u32) -> Self {
+        Datum::Int(i64::from(v))
+    }
+}
+
+impl From<usize> for Datum {
+    fn from(v: usize) -> Self {
+        Datum::Int(v as i64)
+    }
+}
+
+impl From<&str> for Datum {
+    fn from(v: &str) -> Self {
+        Datum::Text(v.into())
+    }
+}
+
+impl From<String> for Datum {
+    fn from(v: String) -> Self {
+        Datum::Text(v)
+    }
+}
+
+impl From<bool> for Datum {
+    fn from(v: bool) -> Self {
+        Datum::Bool(v)
+    }
+}
+
+impl<T: Into<Datum>> From<Option<T>> for Datum {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Datum::Null, Into::into)
+    }
+}
+
+macro_rules! row {
+    ($($value:expr),* $(,)?) => {
+        vec![$(Datum::from($value)),*]
+    };
+}
+
+type Row = Vec<Datum>;
+
+/// Relation of the emulated catalogs.
+struct Relation {
+    /// Name of the relation qualified with the schema.
+    name: &'static str,
+    columns: &'static [(&'static str, ColumnType)],
+    rows: fn(&Catalog) -> traft::Result<Vec<Row>>,
+}
+
+use ColumnType::{Bool, Int, Text};
+
+const RELATIONS: &[Relation] = &[
+    Relation {
+        name: "pg_catalog.pg_namespace",
+        columns: &[("oid", Int), ("nspname", Text), ("nspowner", Int)],
+        rows: pg_namespace,
+    },
+    Relation {
+        name: "pg_catalog.pg_class",
+        columns: &[
+            ("oid", Int),
+            ("relname", Text),
+            ("relnamespace", Int),
+            ("reltype", Int),
+            ("relowner", Int),
+            ("relam", Int),
+            ("relhasindex", Bool),
+            ("relisshared", Bool),
+            ("relpersistence", Text),
+            ("relkind", Text),
+            ("relnatts", Int),
+            ("relchecks", Int),
+            ("relhassubclass", Bool),
+            ("relispartition", Bool),
+        ],
+        rows: pg_class,
+    },
+    Relation {
+        name: "pg_catalog.pg_attribute",
+        columns: &[
+            ("attrelid", Int),
+            ("attname", Text),
+            ("atttypid", Int),
+            ("attlen", Int),
+            ("attnum", Int),
+            ("atttypmod", Int),
+            ("attnotnull", Bool),
+            ("atthasdef", Bool),
+            ("attisdropped", Bool),
+        ],
+        rows: pg_attribute,
+    },
+    Relation {
+        name: "pg_catalog.pg_type",
+        columns: &[
+            ("oid", Int),
+            ("typname", Text),
+            ("typnamespace", Int),
+            ("typowner", Int),
+            ("typlen", Int),
+            ("typbyval", Bool),
+            ("typtype", Text),
+            ("typcategory", Text),
+            ("typrelid", Int),
+            ("typelem", Int),
+            ("typarray", Int),
+            ("typnotnull", Bool),
+            ("typbasetype", Int),
+            ("typtypmod", Int),
+        ],
+        rows: pg_type,
+    },
+    Relation {
+        name: "pg_catalog.pg_index",
+        columns: &[
+            ("indexrelid", Int),
+            ("indrelid", Int),
+            ("indnatts", Int),
+            ("indnkeyatts", Int),
+            ("indisunique", Bool),
+            ("indisprimary", Bool),
+            ("indisvalid", Bool),
+            ("indkey", Text),
+        ],
+        rows: pg_index,
+    },
+    Relation {
+        name: "pg_catalog.pg_proc",
+        columns: &[
+            ("oid", Int),
+            ("proname", Text),
+            ("pronamespace", Int),
+            ("proowner", Int),
+            ("prolang", Int),
+            ("prokind", Text),
+            ("prosecdef", Bool),
+            ("pronargs", Int),
+            ("proargtypes", Text),
+            ("prosrc", Text),
+        ],
+        rows: pg_proc,
+    },
+    Relation {
+        name: "pg_catalog.pg_roles",
+        columns: &[
+            ("oid", Int),
+            ("rolname", Text),
+            ("rolsuper", Bool),
+            ("rolinherit", Bool),
+            ("rolcreaterole", Bool),
+            ("rolcreatedb", Bool),
+            ("rolcanlogin", Bool),
+            ("rolreplication", Bool),
+            ("rolconnlimit", Int),
+            ("rolpassword", Text),
+            ("rolbypassrls", Bool),
+        ],
+        rows: pg_roles,
+    },
+    Relation {
+        name: "information_schema.tables",
+        columns: &[
+            ("table_catalog", Text),
+            ("table_schema", Text),
+            ("table_name", Text),
+            ("table_type", Text),
+            ("is_insertable_into", Text),
+        ],
+        rows: information_schema_tables,
+    },
+    Relation {
+        name: "information_schema.columns",
+        columns: &[
+            ("table_catalog", Text),
+            ("table_schema", Text),
+            ("table_name", Text),
+            ("column_name", Text),
+            ("ordinal_position", Int),
+            ("column_default", Text),
+            ("is_nullable", Text),
+            ("data_type", Text),
+            ("udt_name", Text),
+        ],
+        rows: information_schema_columns,
+    },
+    Relation {
+        name: "information_schema.key_column_usage",
+        columns: &[
+            ("constraint_catalog", Text),
+            ("constraint_schema", Text),
+            ("constraint_name", Text),
+            ("table_catalog", Text),
+            ("table_schema", Text),
+            ("table_name", Text),
+            ("column_name", Text),
+            ("ordinal_position", Int),
+            ("position_in_unique_constraint", Int),
+        ],
+        rows: information_schema_key_column_usage,
+    },
+];
+
+/// Returns true if the schema is one of the emulated catalogs.
+/// Such schemas can't be created.
+#[inline]
+pub fn is_catalog_schema(name: &str) -> bool {
+    name == PG_CATALOG_SCHEMA || name == INFORMATION_SCHEMA
+}
+
+fn find_relation(name: &str) -> Option<&'static Relation> {
+    RELATIONS.iter().find(|relation| {
+        relation.name == name
+            || relation
+                .name
+                .strip_prefix("pg_catalog.")
+                .is_some_and(|unqualified| unqualified == name)
+    })
+}
+
+/// Returns true if the name is the name of a catalog relation
+/// (with or without the `pg_catalog` schema).
+#[inline]
+pub fn is_catalog_relation(name: &str) -> bool {
+    find_relation(name).is_some()
+}
+
+/// Returns the query of the catalog relation or `None` if there is
+/// no such relation. An unqualified name of a `pg_catalog` relation
+/// refers to it only if there is no table with this name.
+///
+/// The views are expected to be looked up before the catalog relations.
+pub fn relation_query(storage: &Catalog, name: &str) -> traft::Result<Option<String>> {
+    let Some(relation) = find_relation(name) else {
+        return Ok(None);
+    };
+    // The catalogs list all the objects like in PostgreSQL,
+    // regardless of the privileges of the user.
+    with_su(ADMIN_ID, || {
+        if relation.name != name && storage.tables.by_name(name)?.is_some() {
+            return Ok(None);
+        }
+        let rows = (relation.rows)(storage)?;
+        Ok(Some(relation_sql(relation, &rows)))
+    })?
+}
+
+fn relation_sql(relation: &Relation, rows: &[Row]) -> String {
+    let mut sql = String::from("SELECT * FROM (VALUES ");
+    if rows.is_empty() {
+        // VALUES can't be empty, the row is filtered out below.
+        let nulls: Row = relation.columns.iter().map(|_| Datum::Null).collect();
+        write_row(&mut sql, relation, &nulls);
+    }
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        write_row(&mut sql, relation, row);
+    }
+    sql.push_str(r#") AS "v" ("#);
+    for (i, (column, _)) in relation.columns.iter().enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        write!(sql, r#""{column}""#).expect("writing to a string");
+    }
+    sql.push(')');
+    if rows.is_empty() {
+        sql.push_str(" WHERE false");
+    }
+    sql
+}
+
+fn write_row(sql: &mut String, relation: &Relation, row: &Row) {
+    debug_assert_eq!(row.len(), relation.columns.len(), "{}", relation.name);
+    sql.push('(');
+    for (i, (datum, (_, ty))) in row.iter().zip(relation.columns).enumerate() {
+        if i > 0 {
+            sql.push_str(", ");
+        }
+        match datum {
+            Datum::Int(v) => write!(sql, "{v}").expect("writing to a string"),
+            Datum::Text(v) => {
+                write!(sql, "'{}'", v.replace('\'', "''")).expect("writing to a string")
+            }
+            Datum::Bool(v) => write!(sql, "{v}").expect("writing to a string"),
+            // Nulls are typed not to leave the column type unknown.
+            Datum::Null => {
+                write!(sql, "CAST(NULL AS {})", ty.as_sql()).expect("writing to a string")
+            }
+        }
+    }
+    sql.push(')');
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// helpers
+////////////////////////////////////////////////////////////////////////////////
+
+/// Name of the database in `information_schema`.
+fn catalog_name() -> &'static str {
+    PicodataConfig::get().cluster_name()
+}
+
+/// Schemas with their oids and owners.
+fn namespaces(storage: &Catalog) -> traft::Result<Vec<(i64, String, UserId)>> {
+    let mut namespaces = vec![
+        (PG_CATALOG_OID, PG_CATALOG_SCHEMA.into(), ADMIN_ID),
+        (PUBLIC_OID, DEFAULT_SCHEMA.into(), ADMIN_ID),
+        (INFORMATION_SCHEMA_OID, INFORMATION_SCHEMA.into(), ADMIN_ID),
+    ];
+    for schema in storage.schemas.iter()? {
+        if schema.operable {
+            namespaces.push((schema_oid(schema.id), schema.name, schema.owner));
+        }
+    }
+    Ok(namespaces)
+}
+
+fn namespace_oids(storage: &Catalog) -> traft::Result<HashMap<String, i64>> {
+    Ok(namespaces(storage)?
+        .into_iter()
+        .map(|(oid, name, _)| (name, oid))
+        .collect())
+}
+
+fn tables(storage: &Catalog) -> traft::Result<Vec<TableDef>> {
+    let mut tables = Vec::new();
+    for table in storage.tables.iter()? {
+        if table.operable {
+            tables.push(table);
+        }
+    }
+    Ok(tables)
+}
+
+/// Indexes of the tables, the primary ones go first.
+fn indexes(storage: &Catalog, table: &TableDef) -> traft::Result<Vec<IndexDef>> {
+    let mut indexes = Vec::new();
+    for index in storage.indexes.by_space_id(table.id)? {
+        if index.operable {
+            indexes.push(index);
+        }
+    }
+    indexes.sort_by_key(|index| index.id);
+    Ok(indexes)
+}
+
+/// Columns of the table visible to SQL with their numbers starting from 1.
+/// The sharding column is hidden like in `SELECT *`.
+fn columns(table: &TableDef) -> impl Iterator<Item = (usize, &Field)> {
+    table
+        .format
+        .iter()
+        .filter(|field| field.name != DEFAULT_BUCKET_COLUMN)
+        .enumerate()
+        .map(|(i, field)| (i + 1, field))
+}
+
+/// Number and name of the column of the table referenced by the index part,
+/// `None` for the hidden columns.
+fn part_column<'a>(table: &'a TableDef, field: &NumOrStr) -> Option<(usize, &'a str)> {
+    let (num, field) = columns(table).find(|(_, column)| match field {
+        NumOrStr::Str(name) => column.name == *name,
+        NumOrStr::Num(pos) => table
+            .format
+            .get(*pos as usize)
+            .is_some_and(|f| f.name == column.name),
+    })?;
+    Some((num, field.name.as_str()))
+}
+
+fn is_unique(index: &IndexDef) -> bool {
+    index.id == 0
+        || index
+            .opts
+            .iter()
+            .find_map(|opt| match opt {
+                IndexOption::Unique(unique) => Some(*unique),
+                _ => None,
+            })
+            // Tarantool indexes are unique by default.
+            .unwrap_or(true)
+}
+
+/// PostgreSQL type of a column.
+struct TypeInfo {
+    ty: PgType,
+    len: i64,
+    by_val: bool,
+    category: &'static str,
+    /// Name in `information_schema`.
+    sql_name: &'static str,
+}
+
+/// Types supported by pgproto.
+fn types() -> [TypeInfo; 13] {
+    let ty = |ty, len, by_val, category, sql_name| TypeInfo {
+        ty,
+        len,
+        by_val,
+        category,
+        sql_name,
+    };
+    [
+        ty(PgType::BOOL, 1, true, "B", "boolean"),
+        ty(PgType::BYTEA, -1, false, "U", "bytea"),
+        ty(PgType::INT8, 8, true, "N", "bigint"),
+        ty(PgType::INT2, 2, true, "N", "smallint"),
+        ty(PgType::INT4, 4, true, "N", "integer"),
+        ty(PgType::TEXT, -1, false, "S", "text"),
+        ty(PgType::JSON, -1, false, "U", "json"),
+        ty(PgType::FLOAT8, 8, true, "N", "double precision"),
+        ty(PgType::VARCHAR, -1, false, "S", "character varying"),
+        ty(
+            PgType::TIMESTAMPTZ,
+            8,
+            true,
+            "D",
+            "timestamp with time zone",
+        ),
+        ty(PgType::INTERVAL, 16, false, "T", "interval"),
+        ty(PgType::NUMERIC, -1, false, "N", "numeric"),
+        ty(PgType::UUID, 16, false, "U", "uuid"),
+    ]
+}
+
+/// Type of the column as it is sent by pgproto.
+fn column_type(field_type: &FieldType) -> TypeInfo {
+    let pg_type = match Type::new(field_type.as_str()).unwrap_or(Type::Any) {
+        Type::Boolean => PgType::BOOL,
+        Type::Decimal => PgType::NUMERIC,
+        Type::Double => PgType::FLOAT8,
+        Type::Integer | Type::Unsigned => PgType::INT8,
+        Type::String => PgType::TEXT,
+        Type::Uuid => PgType::UUID,
+        Type::Varbinary => PgType::BYTEA,
+        Type::Map | Type::Array | Type::Any => PgType::JSON,
+        Type::Datetime => PgType::TIMESTAMPTZ,
+        Type::Interval => PgType::INTERVAL,
+    };
+    types()
+        .into_iter()
+        .find(|info| info.ty == pg_type)
+        .expect("all column types are listed")
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// pg_catalog
+////////////////////////////////////////////////////////////////////////////////
+
+fn pg_namespace(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    Ok(namespaces(storage)?
+        .into_iter()
+        .map(|(oid, name, owner)| row![oid, name, owner])
+        .collect())
+}
+
+fn pg_class(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let namespaces = namespace_oids(storage)?;
+    let namespace = |name: &str| namespaces.get(split_schema_name(name).0).copied();
+    let mut rows = Vec::new();
+    for table in tables(storage)? {
+        let (_, name) = split_schema_name(&table.name);
+        let relnamespace = namespace(&table.name);
+        let (relkind, is_partitioned, is_partition) = match &table.partition {
+            Some(TablePartition::By { .. }) => ("p", true, false),
+            Some(TablePartition::Of { .. }) => ("r", false, true),
+            None => ("r", false, false),
+        };
+        let checks = table
+            .constraints
+            .iter()
+            .flatten()
+            .filter(|c| matches!(c, TableConstraint::Check { .. }))
+            .count();
+        let indexes = indexes(storage, &table)?;
+        rows.push(row![
+            table.id,
+            name,
+            relnamespace,
+            0,
+            table.owner,
+            0,
+            !indexes.is_empty(),
+            false,
+            "p",
+            relkind,
+            columns(&table).count(),
+            checks,
+            is_partitioned,
+            is_partition,
+        ]);
+        for index in indexes {
+            rows.push(row![
+                index_oid(table.id, index.id),
+                index.name,
+                relnamespace,
+                0,
+                table.owner,
+                0,
+                false,
+                false,
+                "p",
+                "i",
+                index.parts.len(),
+                0,
+                false,
+                false,
+            ]);
+        }
+    }
+    for view in storage.views.iter()? {
+        if !view.operable {
+            continue;
+        }
+        let (_, name) = split_schema_name(&view.name);
+        rows.push(row![
+            view_oid(view.id),
+            name,
+            namespace(&view.name),
+            0,
+            view.owner,
+            0,
+            false,
+            false,
+            "p",
+            "v",
+            0,
+            0,
+            false,
+            false,
+        ]);
+    }
+    Ok(rows)
+}
+
+fn pg_attribute(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let mut rows = Vec::new();
+    for table in tables(storage)? {
+        for (num, field) in columns(&table) {
+            let ty = column_type(&field.field_type);
+            let has_default = table
+                .defaults
+                .iter()
+                .flatten()
+                .any(|d| d.column == field.name);
+            rows.push(row![
+                table.id,
+                field.name.as_str(),
+                ty.ty.oid(),
+                ty.len,
+                num,
+                -1,
+                !field.is_nullable,
+                has_default,
+                false,
+            ]);
+        }
+    }
+    Ok(rows)
+}
+
+fn pg_type(_storage: &Catalog) -> traft::Result<Vec<Row>> {
+    Ok(types()
+        .into_iter()
+        .map(|info| {
+            row![
+                info.ty.oid(),
+                info.ty.name(),
+                PG_CATALOG_OID,
+                ADMIN_ID,
+                info.len,
+                info.by_val,
+                "b",
+                info.category,
+                0,
+                0,
+                0,
+                false,
+                0,
+                -1,
+            ]
+        })
+        .collect())
+}
+
+fn pg_index(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let mut rows = Vec::new();
+    for table in tables(storage)? {
+        for index in indexes(storage, &table)? {
+            // Like for expressions, the hidden columns are numbered 0.
+            let indkey: Vec<_> = index
+                .parts
+                .iter()
+                .map(|part| part_column(&table, &part.field).map_or(0, |(num, _)| num))
+                .map(|num| num.to_string())
+                .collect();
+            rows.push(row![
+                index_oid(table.id, index.id),
+                table.id,
+                index.parts.len(),
+                index.parts.len(),
+                is_unique(&index),
+                index.id == 0,
+                true,
+                indkey.join(" "),
+            ]);
+        }
+    }
+    Ok(rows)
+}
+
+fn pg_proc(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let mut rows = Vec::new();
+    for routine in storage.routines.iter()? {
+        if !routine.operable {
+            continue;
+        }
+        let argtypes: Vec<_> = routine
+            .params
+            .iter()
+            .map(|param| column_type(&param.r#type).ty.oid().to_string())
+            .collect();
+        rows.push(row![
+            routine.id,
+            routine.name,
+            PUBLIC_OID,
+            routine.owner,
+            SQL_LANGUAGE_OID,
+            "p",
+            false,
+            routine.params.len(),
+            argtypes.join(" "),
+            routine.body,
+        ]);
+    }
+    Ok(rows)
+}
+
+fn pg_roles(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let mut rows = Vec::new();
+    for user in storage.users.iter()? {
+        let is_super = is_superuser(user.id);
+        let can_login = storage
+            .privileges
+            .get(
+                user.id,
+                SchemaObjectType::Universe.as_str(),
+                UNIVERSE_ID,
+                PrivilegeType::Login.as_str(),
+            )?
+            .is_some();
+        rows.push(row![
+            user.id,
+            user.name.as_str(),
+            is_super,
+            true,
+            is_super,
+            false,
+            can_login,
+            false,
+            -1,
+            "********",
+            false,
+        ]);
+    }
+    Ok(rows)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// information_schema
+////////////////////////////////////////////////////////////////////////////////
+
+fn information_schema_tables(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let catalog = catalog_name();
+    let mut rows = Vec::new();
+    for table in tables(storage)? {
+        let (schema, name) = split_schema_name(&table.name);
+        let is_insertable = !crate::storage::SYSTEM_TABLES_ID_RANGE.contains(&table.id);
+        rows.push(row![
+            catalog,
+            schema,
+            name,
+            "BASE TABLE",
+            if is_insertable { "YES" } else { "NO" },
+        ]);
+    }
+    for view in storage.views.iter()? {
+        if !view.operable {
+            continue;
+        }
+        let (schema, name) = split_schema_name(&view.name);
+        rows.push(row![catalog, schema, name, "VIEW", "NO"]);
+    }
+    Ok(rows)
+}
+
+fn information_schema_columns(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let catalog = catalog_name();
+    let mut rows = Vec::new();
+    for table in tables(storage)? {
+        let (schema, name) = split_schema_name(&table.name);
+        for (num, field) in columns(&table) {
+            let ty = column_type(&field.field_type);
+            let default = table
+                .defaults
+                .iter()
+                .flatten()
+                .find(|d| d.column == field.name)
+                .map(|d| d.expr.as_str());
+            rows.push(row![
+                catalog,
+                schema,
+                name,
+                field.name.as_str(),
+                num,
+                default,
+                if field.is_nullable { "YES" } else { "NO" },
+                ty.sql_name,
+                ty.ty.name(),
+            ]);
+        }
+    }
+    Ok(rows)
+}
+
+fn information_schema_key_column_usage(storage: &Catalog) -> traft::Result<Vec<Row>> {
+    let catalog = catalog_name();
+    let mut rows = Vec::new();
+    for table in tables(storage)? {
+        let (schema, name) = split_schema_name(&table.name);
+        // Primary keys and unique indexes.
+        for index in indexes(storage, &table)? {
+            if !is_unique(&index) {
+                continue;
+            }
+            for (i, part) in index.parts.iter().enumerate() {
+                let Some((_, column)) = part_column(&table, &part.field) else {
+                    continue;
+                };
+                rows.push(row![
+                    catalog,
+                    schema,
+                    index.name.as_str(),
+                    catalog,
+                    schema,
+                    name,
+                    column,
+                    i + 1,
+                    None::<i64>,
+                ]);
+            }
+        }
+        // Foreign keys reference the primary keys of the global tables.
+        for constraint in table.constraints.iter().flatten() {
+            let TableConstraint::ForeignKey {
+                name: fk_name,
+                columns,
+                ..
+            } = constraint
+            else {
+                continue;
+            };
+            for (i, column) in columns.iter().enumerate() {
+                rows.push(row![
+                    catalog,
+                    schema,
+                    fk_name.as_str(),
+                    catalog,
+                    schema,
+                    name,
+                    column.as_str(),
+                    i + 1,
+                    i + 1,
+                ]);
+            }
+        }
+    }
+    Ok(rows)
+}
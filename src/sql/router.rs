@@ -2,6 +2,9 @@
 //! Implements infrastructure to build a distributed
 //! query plan and dispatch it to the storage nodes.
 
+use sbroad::cbo::analyze::decode_column_stats;
+use sbroad::cbo::histogram::Scalar;
+use sbroad::cbo::{ColumnStats, TableColumnPair, TableStats};
 use sbroad::errors::{Action, Entity, SbroadError};
 use sbroad::executor::bucket::Buckets;
 use sbroad::executor::engine::helpers::vshard::{get_random_bucket, impl_exec_ir_on_buckets};
@@ -10,7 +13,7 @@ use sbroad::executor::engine::helpers::{
 };
 use sbroad::executor::engine::helpers::{sharding_key_from_map, sharding_key_from_tuple};
 use sbroad::executor::engine::{
-    get_builtin_functions, DispatchReturnFormat, QueryCache, Router, Vshard,
+    get_builtin_functions, DispatchReturnFormat, QueryCache, Router, Statistics, Vshard,
 };
 use sbroad::executor::ir::ExecutionPlan;
 use sbroad::executor::lru::{Cache, EvictFn, LRUCache, DEFAULT_CAPACITY};
@@ -29,15 +32,20 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::rc::Rc;
 
-use crate::schema::{Distribution, ShardingFn, ADMIN_ID};
+use crate::schema::{Distribution, ShardingFn, TableDef, TablePartition, TableStatsDef, ADMIN_ID};
 use crate::storage::{self, Catalog};
 
 use sbroad::executor::engine::helpers::normalize_name_from_sql;
 use sbroad::executor::engine::Metadata;
 use sbroad::ir::function::Function;
-use sbroad::ir::relation::{space_pk_columns, Column, ColumnRole, DerivedType, Table, Type};
+use sbroad::ir::relation::{
+    space_pk_columns, Column, ColumnRole, DerivedType, Partition, Partitioning, Table, Type,
+};
 
+use crate::sql::pg_catalog;
+use crate::sql::session;
 use crate::sql::storage::StorageRuntime;
+use crate::sql::transaction::{self, SessionKey};
 use crate::traft::node;
 
 use ::tarantool::tuple::{KeyDef, Tuple};
@@ -49,6 +57,11 @@ thread_local! {
         Mutex::new(PicoRouterCache::new(DEFAULT_CAPACITY).unwrap()));
 }
 
+/// Stops executing the query cancelled by the user.
+fn check_cancelled() -> Result<(), SbroadError> {
+    crate::sql::cancel::check_cancelled().map_err(|e| SbroadError::Other(e.to_smolstr()))
+}
+
 pub fn get_tier_info(tier_name: &str) -> Result<Tier, SbroadError> {
     let node = node::global().map_err(|e| {
         SbroadError::FailedTo(Action::Get, None, format_smolstr!("raft node: {}", e))
@@ -225,6 +238,17 @@ impl Cache<SmolStr, Plan> for PicoRouterCache {
                 return Ok(None);
             }
         }
+        // check Plan's views are not replaced or dropped
+        let storage_views = &node.storage.views;
+        for (view_name, query) in &ir.views.queries {
+            let view_def = storage_views.by_name(view_name).map_err(|e| {
+                SbroadError::FailedTo(Action::Get, None, format_smolstr!("view_def: {}", e))
+            })?;
+            match view_def {
+                Some(view_def) if view_def.operable && view_def.query == *query => {}
+                _ => return Ok(None),
+            }
+        }
         Ok(Some(ir))
     }
 
@@ -278,6 +302,7 @@ impl Router for RouterRuntime {
         motion_node_id: &NodeId,
         buckets: &sbroad::executor::bucket::Buckets,
     ) -> Result<sbroad::executor::vtable::VirtualTable, SbroadError> {
+        check_cancelled()?;
         materialize_motion(self, plan, *motion_node_id, buckets)
     }
 
@@ -288,6 +313,7 @@ impl Router for RouterRuntime {
         buckets: &sbroad::executor::bucket::Buckets,
         return_format: DispatchReturnFormat,
     ) -> Result<Box<dyn std::any::Any>, SbroadError> {
+        check_cancelled()?;
         dispatch_impl(self, plan, top_id, buckets, return_format)
     }
 
@@ -333,6 +359,64 @@ impl Router for RouterRuntime {
     }
 }
 
+/// Get the definition and the statistics of the table gathered by `ANALYZE`.
+fn get_table_stats_def(table_name: &str) -> Result<Option<(TableDef, TableStatsDef)>, SbroadError> {
+    let storage = Catalog::try_get(false).expect("storage should be initialized");
+    with_su(ADMIN_ID, || {
+        let Some(table_def) = storage.tables.by_name(table_name)? else {
+            return Ok(None);
+        };
+        let stats = storage.table_stats.get(table_def.id)?;
+        Ok(stats.map(|stats| (table_def, stats)))
+    })?
+}
+
+impl Statistics for RouterRuntime {
+    fn get_table_stats(&self, table_name: &str) -> Result<Option<Rc<TableStats>>, SbroadError> {
+        let stats = get_table_stats_def(table_name)?;
+        Ok(stats.map(|(_, stats)| Rc::new(TableStats::new(stats.rows_number))))
+    }
+
+    fn get_column_stats(
+        &self,
+        table_column_pair: &TableColumnPair,
+    ) -> Result<Option<Rc<Box<dyn Any>>>, SbroadError> {
+        let Some((table_def, stats)) = get_table_stats_def(table_column_pair.table_name())? else {
+            return Ok(None);
+        };
+        let Some(field) = table_def.format.get(table_column_pair.column_id()) else {
+            return Ok(None);
+        };
+        let Some(column_stats) = stats.columns.get(&field.name) else {
+            return Ok(None);
+        };
+        let column_type = Type::new(field.field_type.as_str())?;
+        decode_column_stats(column_stats, &column_type)
+    }
+
+    fn update_table_stats(
+        &mut self,
+        _table_name: SmolStr,
+        _table_stats: TableStats,
+    ) -> Result<(), SbroadError> {
+        Err(SbroadError::Unsupported(
+            Entity::Statistics,
+            Some("table statistics are updated by ANALYZE".into()),
+        ))
+    }
+
+    fn update_column_stats<T: Scalar>(
+        &self,
+        _table_column_pair: TableColumnPair,
+        _column_stats: ColumnStats<T>,
+    ) -> Result<(), SbroadError> {
+        Err(SbroadError::Unsupported(
+            Entity::Statistics,
+            Some("column statistics are updated by ANALYZE".into()),
+        ))
+    }
+}
+
 pub(crate) fn calculate_bucket_id(tuple: &[&Value], bucket_count: u64) -> Result<u64, SbroadError> {
     let wrapped_tuple = tuple
         .iter()
@@ -373,6 +457,9 @@ impl Vshard for Tier {
         buckets: &Buckets,
         return_format: DispatchReturnFormat,
     ) -> Result<Box<dyn Any>, SbroadError> {
+        if sub_plan.get_ir_plan().txn_id.is_some() {
+            return transaction::exec_ir_on_buckets(&self.name, sub_plan, buckets, return_format);
+        }
         let tier_name = self.name();
         impl_exec_ir_on_buckets(
             self,
@@ -425,6 +512,9 @@ impl Vshard for &Tier {
         buckets: &Buckets,
         return_format: DispatchReturnFormat,
     ) -> Result<Box<dyn Any>, SbroadError> {
+        if sub_plan.get_ir_plan().txn_id.is_some() {
+            return transaction::exec_ir_on_buckets(&self.name, sub_plan, buckets, return_format);
+        }
         let tier_name = self.name();
         impl_exec_ir_on_buckets(
             *self,
@@ -513,11 +603,18 @@ impl Metadata for RouterMetadata {
             } else {
                 ColumnRole::User
             };
+            let default = table
+                .defaults
+                .iter()
+                .flatten()
+                .find(|d| d.column == *col_name);
             let column = Column {
                 name: col_name.to_smolstr(),
                 r#type: DerivedType::new(col_type),
                 role,
                 is_nullable,
+                default: default.map(|d| d.expr.to_smolstr()),
+                generated_always: default.is_some_and(|d| d.always),
             };
             columns.push(column);
         }
@@ -530,10 +627,39 @@ impl Metadata for RouterMetadata {
             return Table::new_system(&name, columns, pk_cols_str);
         }
 
+        let partitioning = match &table.partition {
+            Some(TablePartition::By { strategy, column }) => {
+                let position = columns
+                    .iter()
+                    .position(|c| c.name == column.as_str())
+                    .ok_or_else(|| {
+                        SbroadError::NotFound(Entity::Column, format_smolstr!("{column}"))
+                    })?;
+                let partitions = storage
+                    .tables
+                    .partitions_of(table.id)?
+                    .into_iter()
+                    .filter_map(|partition| match partition.partition {
+                        Some(TablePartition::Of { bound, .. }) => Some(Partition {
+                            name: partition.name.to_smolstr(),
+                            bound,
+                        }),
+                        _ => None,
+                    })
+                    .collect();
+                Some(Partitioning {
+                    strategy: *strategy,
+                    column: position,
+                    partitions,
+                })
+            }
+            _ => None,
+        };
+
         // Try to find the sharding columns of the space in "_pico_table".
         // If nothing found then the space is local and we can't query it with
         // distributed SQL.
-        match table.distribution {
+        let mut result = match table.distribution {
             Distribution::Global => Table::new_global(&name, columns, pk_cols_str),
             Distribution::ShardedImplicitly {
                 sharding_key,
@@ -571,7 +697,29 @@ impl Metadata for RouterMetadata {
                 Entity::Distribution,
                 format_smolstr!("explicitly by field '{field}'"),
             )),
+        }?;
+        result.partitioning = partitioning;
+        Ok(result)
+    }
+
+    fn view(&self, view_name: &str) -> Result<Option<SmolStr>, SbroadError> {
+        let storage = Catalog::try_get(false).expect("storage should be initialized");
+        let view = storage
+            .views
+            .by_name(view_name)
+            .map_err(|e| SbroadError::FailedTo(Action::Get, None, format_smolstr!("view: {e}")))?;
+        if let Some(view) = view {
+            // A view being dropped can't be used by new queries.
+            return Ok(view.operable.then(|| view.query.to_smolstr()));
         }
+        let query = pg_catalog::relation_query(storage, view_name).map_err(|e| {
+            SbroadError::FailedTo(Action::Get, None, format_smolstr!("catalog relation: {e}"))
+        })?;
+        Ok(query.map(SmolStr::from))
+    }
+
+    fn search_path(&self) -> Vec<SmolStr> {
+        session::search_path(SessionKey::current())
     }
 
     fn function(&self, fn_name: &str) -> Result<&Function, SbroadError> {
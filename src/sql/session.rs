@@ -0,0 +1,62 @@
+//! SQL settings of the client sessions.
+//!
+//! Settings changed by `SET` live as long as the client session:
+//! either an iproto session or a pgproto connection.
+
+use crate::sql::transaction::SessionKey;
+use sbroad::executor::engine::helpers::DEFAULT_SCHEMA;
+use smol_str::SmolStr;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+
+thread_local! {
+    /// Search paths changed by `SET search_path`, the sessions
+    /// with the default search path are not stored.
+    static SEARCH_PATHS: RefCell<HashMap<SessionKey, Vec<SmolStr>>> = RefCell::new(HashMap::new());
+    /// Statement timeouts changed by `SET statement_timeout`, the sessions
+    /// using the `sql_statement_timeout` system parameter are not stored.
+    static STATEMENT_TIMEOUTS: RefCell<HashMap<SessionKey, Duration>> = RefCell::new(HashMap::new());
+}
+
+/// Returns the schemas to look up unqualified table names in.
+pub fn search_path(session: SessionKey) -> Vec<SmolStr> {
+    SEARCH_PATHS
+        .with(|paths| paths.borrow().get(&session).cloned())
+        .unwrap_or_else(|| vec![SmolStr::from(DEFAULT_SCHEMA)])
+}
+
+/// Sets the search path of the session.
+pub fn set_search_path(session: SessionKey, schemas: Vec<SmolStr>) {
+    SEARCH_PATHS.with(|paths| {
+        let mut paths = paths.borrow_mut();
+        if matches!(schemas.as_slice(), [schema] if schema == DEFAULT_SCHEMA) {
+            paths.remove(&session);
+        } else {
+            paths.insert(session, schemas);
+        }
+    });
+}
+
+/// Returns the statement timeout set in the session, `None` if the session uses
+/// the default one. Zero timeout means that the timeout is disabled.
+pub fn statement_timeout(session: SessionKey) -> Option<Duration> {
+    STATEMENT_TIMEOUTS.with(|timeouts| timeouts.borrow().get(&session).copied())
+}
+
+/// Sets the statement timeout of the session, `None` resets it to the default.
+pub fn set_statement_timeout(session: SessionKey, timeout: Option<Duration>) {
+    STATEMENT_TIMEOUTS.with(|timeouts| {
+        let mut timeouts = timeouts.borrow_mut();
+        match timeout {
+            Some(timeout) => timeouts.insert(session, timeout),
+            None => timeouts.remove(&session),
+        }
+    });
+}
+
+/// Forgets the settings of the closed session.
+pub fn forget_session(session: SessionKey) {
+    SEARCH_PATHS.with(|paths| paths.borrow_mut().remove(&session));
+    STATEMENT_TIMEOUTS.with(|timeouts| timeouts.borrow_mut().remove(&session));
+}
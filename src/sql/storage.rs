@@ -6,20 +6,25 @@ use sbroad::backend::sql::ir::PatternWithParams;
 use sbroad::backend::sql::space::TableGuard;
 use sbroad::errors::{Action, Entity, SbroadError};
 use sbroad::executor::bucket::Buckets;
-use sbroad::executor::engine::helpers::storage::{unprepare, StorageReturnFormat};
+use sbroad::executor::engine::helpers::storage::{
+    attach_storage_stats, count_vdbe_opcodes, unprepare, StorageReturnFormat,
+};
 use sbroad::executor::engine::helpers::vshard::{get_random_bucket, CacheInfo};
 use sbroad::executor::engine::helpers::{
-    self, execute_first_cacheable_request, execute_second_cacheable_request, read_or_prepare,
-    EncodedQueryInfo, OptionalBytes, PlanInfo, RequiredPlanInfo,
+    self, compile_encoded_optional, execute_first_cacheable_request,
+    execute_second_cacheable_request, read_or_prepare, EncodedQueryInfo, OptionalBytes, PlanInfo,
+    RequiredPlanInfo,
 };
 use sbroad::executor::engine::{DispatchReturnFormat, QueryCache, StorageCache, Vshard};
 use sbroad::executor::ir::{ExecutionPlan, QueryType};
 use sbroad::executor::lru::{Cache, EvictFn, LRUCache};
 use sbroad::executor::protocol::{EncodedTables, RequiredData, SchemaInfo};
 use sbroad::executor::result::ProducerResult;
+use sbroad::ir::explain::execution_info::StorageStats;
 use sbroad::ir::value::Value;
 use tarantool::fiber::Mutex;
 use tarantool::sql::Statement;
+use tarantool::time::Instant;
 use tarantool::tuple::{Tuple, TupleBuffer};
 
 use super::{router::calculate_bucket_id, DEFAULT_BUCKET_COUNT};
@@ -29,7 +34,7 @@ use once_cell::sync::Lazy;
 use sbroad::backend::sql::tree::{OrderedSyntaxNodes, SyntaxData, SyntaxPlan};
 use sbroad::ir::node::NodeId;
 use sbroad::ir::tree::Snapshot;
-use smol_str::{format_smolstr, SmolStr};
+use smol_str::{format_smolstr, SmolStr, ToSmolStr};
 use std::collections::HashMap;
 use std::{any::Any, rc::Rc};
 use tarantool::msgpack;
@@ -302,6 +307,11 @@ impl Vshard for StorageRuntime {
             "storage runtime can't execute vshard queries".into(),
         ));
     }
+
+    fn check_interrupted(&self) -> Result<(), SbroadError> {
+        crate::error_injection!(block "BLOCK_SQL_FRAGMENT_EXECUTION");
+        crate::sql::cancel::check_fragment().map_err(|e| SbroadError::Other(e.to_smolstr()))
+    }
 }
 
 impl StorageRuntime {
@@ -339,16 +349,56 @@ impl StorageRuntime {
         match required.query_type {
             QueryType::DML => helpers::execute_dml(self, required, raw_optional.get_mut()?),
             QueryType::DQL => {
+                self.check_interrupted()?;
                 let mut info = EncodedQueryInfo::new(raw_optional, required);
-                match cache_info {
+                let result = match cache_info {
                     CacheInfo::CacheableFirstRequest => {
                         execute_first_cacheable_request(self, &mut info)
                     }
                     CacheInfo::CacheableSecondRequest => {
                         execute_second_cacheable_request(self, &mut info)
                     }
-                }
+                }?;
+                // Don't send the result of the interrupted query to the router.
+                self.check_interrupted()?;
+                Ok(result)
             }
         }
     }
+
+    /// Execute dispatched DQL plan and report the statistics of the local
+    /// execution for `EXPLAIN ANALYZE`: the statistics replace the cache
+    /// miss flag in the result. The router sends the whole plan, as it is
+    /// needed to count the opcodes of the VDBE program.
+    ///
+    /// # Errors
+    /// - Something went wrong while executing the plan.
+    pub fn execute_plan_with_stats(
+        &self,
+        required: &mut RequiredData,
+        mut raw_optional: OptionalBytes,
+        cache_info: CacheInfo,
+    ) -> Result<Tuple, SbroadError> {
+        let cache_hit = self.cache().lock().get(&required.plan_id)?.is_some();
+        let vdbe_opcodes = {
+            let mut optional = raw_optional.get_mut()?.clone();
+            let (pattern, _tables) = compile_encoded_optional(&mut optional, &required.plan_id)?;
+            count_vdbe_opcodes(
+                &pattern.pattern,
+                &pattern.params,
+                required.options.sql_vdbe_opcode_max,
+            )?
+        };
+
+        let started = Instant::now_accurate();
+        let result = self.execute_plan(required, raw_optional, cache_info)?;
+        let stats = StorageStats::new(cache_hit, started.elapsed(), vdbe_opcodes);
+        let tuple = result.downcast::<Tuple>().map_err(|e| {
+            SbroadError::Invalid(
+                Entity::Tuple,
+                Some(format_smolstr!("expected Tuple as result: {e:?}")),
+            )
+        })?;
+        attach_storage_stats(&tuple, &stats)
+    }
 }
@@ -0,0 +1,677 @@
+//! Interactive SQL transactions.
+//!
+//! A transaction is opened on the router by `BEGIN` and belongs to the
+//! client session: either an iproto session or a pgproto connection. The
+//! first statement touching sharded data pins the transaction to the master
+//! of the replicaset owning the buckets. All the following statements must
+//! be executed on the same replicaset, otherwise an error is returned.
+//!
+//! On the storage side each transaction is served by a dedicated fiber,
+//! because tarantool transactions are bound to the fiber that opened them.
+//! The fiber keeps the transaction open between statements, which requires
+//! memtx MVCC engine (see `instance.memtx.use_mvcc_engine`).
+//!
+//! When memtx MVCC engine is disabled, `BEGIN`, `COMMIT` and `ROLLBACK`
+//! are accepted for compatibility with the drivers, but the statements in
+//! between are autocommitted one by one and can't be rolled back.
+//!
+//! Limitations:
+//! - DDL, ACL and plugin operations can't be executed inside a transaction;
+//! - DML on global tables can't be executed inside a transaction;
+//! - queries moving data between instances (i.e. requiring temporary
+//!   tables on the storages) are not supported inside a transaction.
+
+use crate::sql::cancel;
+use crate::sql::router::DEFAULT_QUERY_TIMEOUT;
+use crate::sql::session;
+use crate::sql::{execute_plan_on_storage, proc_sql_execute};
+use crate::tlog;
+use crate::traft::error::Error;
+use crate::traft::{self, node};
+
+use crate::instance::InstanceName;
+use crate::schema::ADMIN_ID;
+use sbroad::errors::{Entity, SbroadError};
+use sbroad::executor::bucket::Buckets;
+use sbroad::executor::engine::helpers::vshard::CacheInfo;
+use sbroad::executor::engine::helpers::{
+    build_optional_binary, build_required_binary, empty_query_result, OptionalBytes,
+};
+use sbroad::executor::engine::{ConvertToDispatchResult, DispatchReturnFormat};
+use sbroad::executor::ir::{ExecutionPlan, QueryType};
+use sbroad::executor::protocol::RequiredData;
+use sbroad::executor::result::ProducerResult;
+use sbroad::ir::node::tcl::Tcl;
+use smol_str::{format_smolstr, SmolStr, ToSmolStr};
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+use tarantool::fiber::{self, channel::Channel, FiberId};
+use tarantool::session::with_su;
+use tarantool::time::Instant;
+use tarantool::tlua::{self, LuaFunction};
+use tarantool::transaction;
+use tarantool::tuple::{RawByteBuf, RawBytes, Tuple};
+use tarantool::uuid::Uuid;
+
+/// Storage transaction is rolled back if the router doesn't send
+/// any requests for it during this period.
+const TXN_IDLE_TIMEOUT: Duration = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+
+////////////////////////////////////////////////////////////////////////////////
+// Router
+////////////////////////////////////////////////////////////////////////////////
+
+/// Client session owning an interactive transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SessionKey {
+    /// Tarantool session id.
+    Iproto(u64),
+    /// Pgproto client id.
+    Pgproto(u64),
+}
+
+impl SessionKey {
+    /// Returns the session of the current fiber.
+    pub fn current() -> Self {
+        let fiber_id = fiber::id();
+        if let Some(client_id) = PGPROTO_FIBERS.with(|f| f.borrow().get(&fiber_id).copied()) {
+            return Self::Pgproto(client_id);
+        }
+        // SAFETY: always safe
+        let session_id = unsafe { tarantool::ffi::tarantool::box_session_id() };
+        Self::Iproto(session_id)
+    }
+}
+
+/// Transaction state reported to the clients.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TxnStatus {
+    /// There is no transaction in progress.
+    Idle,
+    /// Transaction is in progress.
+    InProgress,
+    /// Some statement of the transaction has failed,
+    /// only `ROLLBACK` (or `COMMIT`) is accepted.
+    Failed,
+}
+
+/// Replicaset master the transaction is pinned to.
+#[derive(Clone, Debug)]
+struct TxnTarget {
+    tier: SmolStr,
+    replicaset_uuid: String,
+    master: InstanceName,
+}
+
+#[derive(Debug)]
+struct RouterTxn {
+    id: SmolStr,
+    /// Set by the first statement touching sharded data.
+    target: Option<TxnTarget>,
+    failed: bool,
+    /// Memtx MVCC engine is disabled, the statements are autocommitted.
+    autocommit: bool,
+}
+
+thread_local! {
+    static ROUTER_TXNS: RefCell<HashMap<SessionKey, RouterTxn>> = RefCell::new(HashMap::new());
+    /// Fibers serving pgproto clients. Each pgproto client is served by
+    /// a single fiber, while iproto requests of the same session may be
+    /// served by different fibers.
+    static PGPROTO_FIBERS: RefCell<HashMap<FiberId, u64>> = RefCell::new(HashMap::new());
+}
+
+/// Binds pgproto client to the current fiber.
+pub fn register_pgproto_client(client_id: u64) {
+    PGPROTO_FIBERS.with(|f| f.borrow_mut().insert(fiber::id(), client_id));
+}
+
+/// Rolls back the transaction of the disconnected pgproto client.
+pub fn unregister_pgproto_client(client_id: u64) {
+    rollback_session(SessionKey::Pgproto(client_id));
+    session::forget_session(SessionKey::Pgproto(client_id));
+    PGPROTO_FIBERS.with(|f| f.borrow_mut().retain(|_, id| *id != client_id));
+}
+
+/// Sets up a trigger rolling back transactions of disconnected iproto sessions.
+pub fn set_on_disconnect_trigger() {
+    let lua = tarantool::lua_state();
+    lua.exec_with(
+        "box.session.on_disconnect(...)",
+        tlua::Function::new(|| {
+            let session = SessionKey::current();
+            rollback_session(session);
+            session::forget_session(session);
+        }),
+    )
+    .expect("setting on_disconnect trigger should never fail");
+}
+
+/// Rolls back the session transaction if any, errors are logged.
+pub fn rollback_session(session: SessionKey) {
+    let Some(txn) = ROUTER_TXNS.with(|txns| txns.borrow_mut().remove(&session)) else {
+        return;
+    };
+    if let Err(e) = finish_on_storage(&txn, false) {
+        tlog!(Warning, "failed to rollback transaction {}: {e}", txn.id);
+    }
+}
+
+/// Returns state of the session transaction.
+pub fn txn_status(session: SessionKey) -> TxnStatus {
+    ROUTER_TXNS.with(|txns| match txns.borrow().get(&session) {
+        None => TxnStatus::Idle,
+        Some(txn) if txn.failed => TxnStatus::Failed,
+        Some(_) => TxnStatus::InProgress,
+    })
+}
+
+/// Returns identifier of the session transaction, `None` in autocommit mode.
+///
+/// # Errors
+/// - Some statement of the transaction has already failed.
+pub fn active_txn_id(session: SessionKey) -> traft::Result<Option<SmolStr>> {
+    ROUTER_TXNS.with(|txns| match txns.borrow().get(&session) {
+        None => Ok(None),
+        Some(txn) if txn.failed => Err(Error::other(
+            "current transaction is aborted, commands ignored until end of transaction block",
+        )),
+        Some(txn) if txn.autocommit => Ok(None),
+        Some(txn) => Ok(Some(txn.id.clone())),
+    })
+}
+
+/// Handles `BEGIN`, `COMMIT` and `ROLLBACK` statements.
+pub fn execute_tcl(session: SessionKey, tcl: Tcl) -> traft::Result<()> {
+    match tcl {
+        Tcl::Begin => {
+            ROUTER_TXNS.with(|txns| {
+                let mut txns = txns.borrow_mut();
+                if txns.contains_key(&session) {
+                    tlog!(Warning, "there is already a transaction in progress");
+                    return;
+                }
+                let autocommit = !mvcc_enabled();
+                if autocommit {
+                    tlog!(
+                        Warning,
+                        "memtx MVCC engine is disabled, statements of the transaction \
+                         are autocommitted (set instance.memtx.use_mvcc_engine to true)"
+                    );
+                }
+                let txn = RouterTxn {
+                    id: Uuid::random().to_smolstr(),
+                    target: None,
+                    failed: false,
+                    autocommit,
+                };
+                txns.insert(session, txn);
+            });
+            Ok(())
+        }
+        Tcl::Commit | Tcl::Rollback => {
+            let Some(txn) = ROUTER_TXNS.with(|txns| txns.borrow_mut().remove(&session)) else {
+                tlog!(Warning, "there is no transaction in progress");
+                return Ok(());
+            };
+            if txn.autocommit {
+                if matches!(tcl, Tcl::Rollback) {
+                    tlog!(
+                        Warning,
+                        "nothing to rollback, statements of the transaction \
+                         have been autocommitted"
+                    );
+                }
+                return Ok(());
+            }
+            let commit = matches!(tcl, Tcl::Commit) && !txn.failed;
+            finish_on_storage(&txn, commit)?;
+            if matches!(tcl, Tcl::Commit) && txn.failed {
+                return Err(Error::other(
+                    "transaction has been rolled back due to an error in one of its statements",
+                ));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// The storage transaction is kept open between the statements, which
+/// is possible only with memtx MVCC engine: otherwise a yield aborts it.
+fn mvcc_enabled() -> bool {
+    crate::tarantool::cfg_field::<bool>("memtx_use_mvcc_engine").unwrap_or(false)
+}
+
+fn finish_on_storage(txn: &RouterTxn, commit: bool) -> traft::Result<()> {
+    let Some(target) = &txn.target else {
+        // Transaction has not touched any storage.
+        return Ok(());
+    };
+    let node = node::global()?;
+    let request = Request {
+        txn_id: txn.id.to_string(),
+        commit,
+    };
+    let future = node.pool.call(
+        &target.master,
+        crate::proc_name!(proc_sql_txn_finish),
+        &request,
+        Duration::from_secs(DEFAULT_QUERY_TIMEOUT),
+    )?;
+    fiber::block_on(future)?;
+    Ok(())
+}
+
+/// Aborts the session transaction if any, only `ROLLBACK` (or `COMMIT`
+/// rolling it back) is accepted afterwards. Transactions in autocommit
+/// mode are not affected, as their statements are already committed.
+pub fn mark_failed(session: SessionKey) {
+    ROUTER_TXNS.with(|txns| {
+        if let Some(txn) = txns.borrow_mut().get_mut(&session) {
+            if !txn.autocommit {
+                txn.failed = true;
+            }
+        }
+    });
+}
+
+/// Executes a sub-plan of an interactive transaction on the replicaset
+/// the transaction is pinned to. Any failure aborts the transaction.
+pub fn exec_ir_on_buckets(
+    tier: &str,
+    sub_plan: ExecutionPlan,
+    buckets: &Buckets,
+    return_format: DispatchReturnFormat,
+) -> Result<Box<dyn Any>, SbroadError> {
+    let session = SessionKey::current();
+    exec_on_pinned_replicaset(session, tier, sub_plan, buckets, return_format)
+        .inspect_err(|_| mark_failed(session))
+}
+
+fn exec_on_pinned_replicaset(
+    session: SessionKey,
+    tier: &str,
+    mut sub_plan: ExecutionPlan,
+    buckets: &Buckets,
+    return_format: DispatchReturnFormat,
+) -> Result<Box<dyn Any>, SbroadError> {
+    if let Buckets::Filtered(buckets_set) = buckets {
+        if buckets_set.is_empty() {
+            return empty_query_result(&sub_plan, return_format);
+        }
+    }
+
+    let query_type = sub_plan.query_type()?;
+    if query_type == QueryType::DQL && sub_plan.get_vtables().is_some_and(|v| !v.is_empty()) {
+        return Err(SbroadError::Unsupported(
+            Entity::Transaction,
+            Some(
+                "queries moving data between instances are not supported inside a transaction"
+                    .into(),
+            ),
+        ));
+    }
+
+    let replicasets = replicasets_by_buckets(buckets, tier)?;
+    let [replicaset_uuid] = replicasets.as_slice() else {
+        return Err(SbroadError::Unsupported(
+            Entity::Transaction,
+            Some("transaction can't span multiple replicasets".into()),
+        ));
+    };
+    let target = pin_replicaset(session, tier, replicaset_uuid)?;
+
+    let required = build_required_binary(&mut sub_plan)?;
+    let optional = build_optional_binary(sub_plan)?;
+    let mut args = Vec::with_capacity(required.data().len() + optional.data().len() + 16);
+    rmp::encode::write_array_len(&mut args, 3).map_err(|e| SbroadError::Other(e.to_smolstr()))?;
+    args.extend_from_slice(required.data());
+    args.extend_from_slice(optional.data());
+    rmp::encode::write_str(&mut args, CacheInfo::CacheableSecondRequest.as_str())
+        .map_err(|e| SbroadError::Other(e.to_smolstr()))?;
+
+    let output = call_storage(&target.master, &args).map_err(|e| {
+        SbroadError::DispatchError(format_smolstr!(
+            "failed to execute statement on {}: {e}",
+            target.master
+        ))
+    })?;
+
+    match query_type {
+        // Storage returns [{row_count = ...}] tuple.
+        QueryType::DML => Ok(Box::new(Tuple::try_from_slice(&output)?)),
+        // Storage returns [row count, cache miss flag, [producer result]].
+        QueryType::DQL => {
+            let mut data: &[u8] = &output;
+            let decode_err = |e: &dyn std::fmt::Display| {
+                SbroadError::Other(format_smolstr!("failed to decode storage result: {e}"))
+            };
+            rmp::decode::read_array_len(&mut data).map_err(|e| decode_err(&e))?;
+            let _row_count: u64 = rmp::decode::read_int(&mut data).map_err(|e| decode_err(&e))?;
+            let _cache_miss = rmp::decode::read_bool(&mut data).map_err(|e| decode_err(&e))?;
+            if return_format == DispatchReturnFormat::Tuple {
+                return Ok(Box::new(Tuple::try_from_slice(data)?));
+            }
+            let results: Vec<ProducerResult> =
+                tarantool::msgpack::decode(data).map_err(|e| decode_err(&e))?;
+            let result = results
+                .into_iter()
+                .next()
+                .ok_or_else(|| SbroadError::Other("expected non-empty array".into()))?;
+            result.convert(return_format)
+        }
+    }
+}
+
+/// Rust binding to Lua `get_replicasets_from_buckets` function.
+fn replicasets_by_buckets(buckets: &Buckets, tier: &str) -> Result<Vec<String>, SbroadError> {
+    let lua_buckets: Vec<u64> = match buckets {
+        Buckets::Filtered(list) => list.iter().copied().collect(),
+        Buckets::All => {
+            let node = node::global().map_err(|e| SbroadError::Other(e.to_smolstr()))?;
+            let topology = node.topology_cache.get();
+            return Ok(topology
+                .all_replicasets()
+                .filter(|r| r.tier == tier)
+                .map(|r| r.uuid.clone())
+                .collect());
+        }
+        Buckets::Any => {
+            return Err(SbroadError::Unsupported(
+                Entity::Buckets,
+                Some("grouping buckets is not supported for Buckets::Any".into()),
+            ))
+        }
+    };
+
+    let lua = tarantool::lua_state();
+    let func: LuaFunction<_> = lua.get("get_replicasets_from_buckets").ok_or_else(|| {
+        SbroadError::LuaError("Lua function `get_replicasets_from_buckets` not found".into())
+    })?;
+    func.call_with_args((lua_buckets, tier))
+        .map_err(|e| SbroadError::LuaError(format_smolstr!("{e:?}")))
+}
+
+fn pin_replicaset(
+    session: SessionKey,
+    tier: &str,
+    replicaset_uuid: &str,
+) -> Result<TxnTarget, SbroadError> {
+    let current = ROUTER_TXNS.with(|txns| {
+        txns.borrow()
+            .get(&session)
+            .map(|txn| txn.target.clone())
+            .ok_or_else(|| SbroadError::NotFound(Entity::Transaction, "for the session".into()))
+    })?;
+    if let Some(target) = current {
+        if target.tier != tier || target.replicaset_uuid != replicaset_uuid {
+            return Err(SbroadError::Unsupported(
+                Entity::Transaction,
+                Some("transaction can't span multiple replicasets".into()),
+            ));
+        }
+        return Ok(target);
+    }
+
+    let node = node::global().map_err(|e| SbroadError::Other(e.to_smolstr()))?;
+    let master = node
+        .topology_cache
+        .get()
+        .replicaset_by_uuid(replicaset_uuid)
+        .map_err(|e| SbroadError::Other(e.to_smolstr()))?
+        .current_master_name
+        .clone();
+    let target = TxnTarget {
+        tier: tier.into(),
+        replicaset_uuid: replicaset_uuid.into(),
+        master,
+    };
+    ROUTER_TXNS.with(|txns| {
+        if let Some(txn) = txns.borrow_mut().get_mut(&session) {
+            txn.target = Some(target.clone());
+        }
+    });
+    Ok(target)
+}
+
+fn call_storage(master: &InstanceName, args: &[u8]) -> traft::Result<RawByteBuf> {
+    let node = node::global()?;
+    let timeout = Duration::from_secs(DEFAULT_QUERY_TIMEOUT);
+    let future = node.pool.call_raw(
+        master,
+        crate::proc_name!(proc_sql_execute),
+        RawBytes::new(args),
+        cancel::time_left().map_or(timeout, |time_left| time_left.min(timeout)),
+    )?;
+    fiber::block_on(future)
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Storage
+////////////////////////////////////////////////////////////////////////////////
+
+enum TxnRequest {
+    Execute {
+        required: RequiredData,
+        optional: OptionalBytes,
+        cache_info: CacheInfo,
+        deadline: Option<Instant>,
+        reply: Channel<traft::Result<Tuple>>,
+    },
+    Finish {
+        commit: bool,
+        reply: Channel<traft::Result<()>>,
+    },
+}
+
+enum StorageTxn {
+    /// Transaction is served by a dedicated fiber reading requests from the channel.
+    Active(Channel<TxnRequest>),
+    /// Transaction has been rolled back before the router finished it.
+    Aborted(String),
+}
+
+thread_local! {
+    static STORAGE_TXNS: RefCell<HashMap<SmolStr, StorageTxn>> = RefCell::new(HashMap::new());
+}
+
+fn aborted_error(txn_id: &str, reason: &str) -> Error {
+    Error::other(format!(
+        "transaction {txn_id} has been rolled back: {reason}"
+    ))
+}
+
+fn inactive_error(txn_id: &str) -> Error {
+    Error::other(format!("transaction {txn_id} is no longer active"))
+}
+
+/// Executes a statement inside of the interactive transaction on the local
+/// storage. The transaction is started by its first statement.
+pub(crate) fn execute_on_storage(
+    required: RequiredData,
+    optional: OptionalBytes,
+    cache_info: CacheInfo,
+    deadline: Option<Instant>,
+) -> traft::Result<Tuple> {
+    let txn_id = required
+        .txn_id
+        .clone()
+        .expect("should be checked by the caller");
+
+    let existing = STORAGE_TXNS.with(|txns| match txns.borrow().get(&txn_id) {
+        Some(StorageTxn::Active(requests)) => Ok(Some(requests.clone())),
+        Some(StorageTxn::Aborted(reason)) => Err(aborted_error(&txn_id, reason)),
+        None => Ok(None),
+    })?;
+    let requests = match existing {
+        Some(requests) => requests,
+        None => start_txn_fiber(&txn_id)?,
+    };
+
+    let reply = Channel::new(1);
+    let request = TxnRequest::Execute {
+        required,
+        optional,
+        cache_info,
+        deadline,
+        reply: reply.clone(),
+    };
+    if requests.send(request).is_err() {
+        return Err(inactive_error(&txn_id));
+    }
+    reply.recv().ok_or_else(|| inactive_error(&txn_id))?
+}
+
+fn start_txn_fiber(txn_id: &SmolStr) -> traft::Result<Channel<TxnRequest>> {
+    check_mvcc_enabled()?;
+    let requests = Channel::new(1);
+    STORAGE_TXNS.with(|txns| {
+        txns.borrow_mut()
+            .insert(txn_id.clone(), StorageTxn::Active(requests.clone()))
+    });
+    let txn_id_owned = txn_id.clone();
+    let requests_owned = requests.clone();
+    fiber::Builder::new()
+        .name(format!("sql_txn_{txn_id}"))
+        .func(move || serve_txn(txn_id_owned, requests_owned))
+        .defer_non_joinable()
+        .inspect_err(|_| {
+            STORAGE_TXNS.with(|txns| txns.borrow_mut().remove(txn_id));
+        })?;
+    Ok(requests)
+}
+
+/// Main loop of the fiber serving an interactive transaction.
+fn serve_txn(txn_id: SmolStr, requests: Channel<TxnRequest>) {
+    let mut aborted: Option<String> = transaction::begin().err().map(|e| e.to_string());
+
+    loop {
+        let request = match requests.recv_timeout(TXN_IDLE_TIMEOUT) {
+            Ok(request) => request,
+            Err(_) => {
+                tlog!(
+                    Warning,
+                    "transaction {txn_id} rolled back due to inactivity"
+                );
+                aborted.get_or_insert_with(|| "idle timeout exceeded".into());
+                break;
+            }
+        };
+        match request {
+            TxnRequest::Execute {
+                mut required,
+                optional,
+                cache_info,
+                deadline,
+                reply,
+            } => {
+                let res = match &aborted {
+                    Some(reason) => Err(aborted_error(&txn_id, reason)),
+                    None => with_su(ADMIN_ID, || {
+                        execute_plan_on_storage(&mut required, optional, cache_info, deadline)
+                    })
+                    .map_err(Error::from)
+                    .and_then(|res| res),
+                };
+                let failed = res.as_ref().err().map(ToString::to_string);
+                let _ = reply.send(res);
+                if let Some(e) = failed {
+                    aborted.get_or_insert(e);
+                    break;
+                }
+            }
+            TxnRequest::Finish { commit, reply } => {
+                let res = match &aborted {
+                    Some(reason) if commit => Err(aborted_error(&txn_id, reason)),
+                    Some(_) => Ok(()),
+                    None if commit => transaction::commit().map_err(Error::from),
+                    None => transaction::rollback().map_err(Error::from),
+                };
+                if res.is_err() && transaction::is_in_transaction() {
+                    let _ = transaction::rollback();
+                }
+                STORAGE_TXNS.with(|txns| txns.borrow_mut().remove(&txn_id));
+                let _ = reply.send(res);
+                return;
+            }
+        }
+    }
+
+    if transaction::is_in_transaction() {
+        let _ = transaction::rollback();
+    }
+    let reason = aborted.unwrap_or_default();
+    STORAGE_TXNS.with(|txns| {
+        txns.borrow_mut()
+            .insert(txn_id.clone(), StorageTxn::Aborted(reason.clone()))
+    });
+    // Reply to the requests which are already in the queue.
+    while let Ok(request) = requests.recv_timeout(Duration::ZERO) {
+        match request {
+            TxnRequest::Execute { reply, .. } => {
+                let _ = reply.send(Err(aborted_error(&txn_id, &reason)));
+            }
+            TxnRequest::Finish { commit, reply } => {
+                STORAGE_TXNS.with(|txns| txns.borrow_mut().remove(&txn_id));
+                let res = if commit {
+                    Err(aborted_error(&txn_id, &reason))
+                } else {
+                    Ok(())
+                };
+                let _ = reply.send(res);
+            }
+        }
+    }
+}
+
+crate::define_rpc_request! {
+    /// Commits or rolls back an interactive SQL transaction on the storage.
+    ///
+    /// Returns errors in the following cases:
+    /// 1. Commit is requested for a transaction which has been rolled back
+    /// 2. Storage failure on commit
+    fn proc_sql_txn_finish(req: Request) -> traft::Result<Response> {
+        let txn_id = SmolStr::from(req.txn_id);
+        let txn = STORAGE_TXNS.with(|txns| match txns.borrow().get(&txn_id) {
+            Some(StorageTxn::Active(requests)) => Some(Ok(requests.clone())),
+            Some(StorageTxn::Aborted(reason)) => Some(Err(reason.clone())),
+            None => None,
+        });
+        match txn {
+            Some(Ok(requests)) => {
+                let reply = Channel::new(1);
+                let request = TxnRequest::Finish {
+                    commit: req.commit,
+                    reply: reply.clone(),
+                };
+                if requests.send(request).is_err() {
+                    return Err(inactive_error(&txn_id));
+                }
+                reply.recv().ok_or_else(|| inactive_error(&txn_id))??;
+            }
+            Some(Err(reason)) => {
+                STORAGE_TXNS.with(|txns| txns.borrow_mut().remove(&txn_id));
+                if req.commit {
+                    return Err(aborted_error(&txn_id, &reason));
+                }
+            }
+            None if req.commit => return Err(inactive_error(&txn_id)),
+            None => {}
+        }
+        Ok(Response {})
+    }
+
+    /// Request to finish an interactive SQL transaction.
+    pub struct Request {
+        pub txn_id: String,
+        /// Commit if true, otherwise rollback.
+        pub commit: bool,
+    }
+
+    /// Response to [`transaction::Request`].
+    ///
+    /// [`transaction::Request`]: Request
+    pub struct Response {}
+}
@@ -28,9 +28,10 @@ use crate::schema::SchemaObjectType;
 use crate::schema::ServiceDef;
 use crate::schema::ServiceRouteItem;
 use crate::schema::ServiceRouteKey;
-use crate::schema::{IndexDef, IndexOption, TableDef};
+use crate::schema::TableStatsDef;
+use crate::schema::{ColumnDefault, IndexDef, IndexOption, TableDef, TablePartition};
 use crate::schema::{PluginDef, INITIAL_SCHEMA_VERSION};
-use crate::schema::{PrivilegeDef, RoutineDef, UserDef};
+use crate::schema::{PrivilegeDef, RoutineDef, SchemaDef, SequenceDef, UserDef, ViewDef};
 use crate::static_ref;
 use crate::storage::snapshot::SnapshotCache;
 use crate::system_parameter_name;
@@ -43,7 +44,7 @@ use crate::traft::op::Ddl;
 use crate::traft::op::Dml;
 use crate::traft::RaftId;
 use crate::traft::Result;
-use crate::util::Uppercase;
+use crate::util::{duration_from_secs_f64_clamped, Uppercase};
 
 use rmpv::Utf8String;
 use std::borrow::Cow;
@@ -131,6 +132,10 @@ pub struct Catalog {
     pub plugin_migrations: PluginMigrations,
     pub plugin_config: PluginConfig,
     pub db_config: DbConfig,
+    pub sequences: Sequences,
+    pub views: Views,
+    pub schemas: Schemas,
+    pub table_stats: TableStatistics,
 }
 
 /// Id of system table `_bucket`. Note that we don't add in to `Clusterwide`
@@ -154,7 +159,7 @@ impl Catalog {
             WAS_CALLED = true;
         }
 
-        Ok(Self {
+        let storage = Self {
             tables: Tables::new()?,
             indexes: Indexes::new()?,
             peer_addresses: PeerAddresses::new()?,
@@ -171,9 +176,15 @@ impl Catalog {
             plugin_migrations: PluginMigrations::new()?,
             plugin_config: PluginConfig::new()?,
             db_config: DbConfig::new()?,
+            sequences: Sequences::new()?,
+            views: Views::new()?,
+            schemas: Schemas::new()?,
+            table_stats: TableStatistics::new()?,
             snapshot_cache: Default::default(),
             login_attempts: Default::default(),
-        })
+        };
+        upgrade_system_space_formats()?;
+        Ok(storage)
     }
 
     pub fn system_space_name_by_id(id: SpaceId) -> Option<&'static str> {
@@ -194,6 +205,10 @@ impl Catalog {
             PluginMigrations::TABLE_ID => Some(PluginMigrations::TABLE_NAME),
             PluginConfig::TABLE_ID => Some(PluginConfig::TABLE_NAME),
             DbConfig::TABLE_ID => Some(DbConfig::TABLE_NAME),
+            Sequences::TABLE_ID => Some(Sequences::TABLE_NAME),
+            Views::TABLE_ID => Some(Views::TABLE_NAME),
+            Schemas::TABLE_ID => Some(Schemas::TABLE_NAME),
+            TableStatistics::TABLE_ID => Some(TableStatistics::TABLE_NAME),
             _ => None,
         }
     }
@@ -279,6 +294,36 @@ impl Catalog {
     }
 }
 
+/// Appends the new fields to the formats of the system spaces created by an
+/// older version of picodata. The new fields are nullable, so the existing
+/// tuples don't need to be changed. The definitions in "_pico_table" are
+/// upgraded by the governor, see [`crate::schema::system_catalog_upgrade_ops`].
+/// Replicas get the new formats from the master.
+fn upgrade_system_space_formats() -> tarantool::Result<()> {
+    let is_read_only: bool = tarantool::lua_state().eval("return box.info.ro")?;
+    if is_read_only {
+        return Ok(());
+    }
+    let sys_space = Space::from(SystemSpace::Space);
+    for (table_def, _) in crate::schema::system_table_definitions() {
+        let Some(tuple) = sys_space.get(&[table_def.id])? else {
+            continue;
+        };
+        let space_def: tarantool::space::Metadata = tuple.decode()?;
+        if space_def.format.len() >= table_def.format.len() {
+            continue;
+        }
+        tlog!(Info, "upgrading format of system table {}", table_def.name);
+        let mut ops = UpdateOps::with_capacity(1);
+        ops.assign(
+            crate::column_name!(tarantool::space::Metadata, format),
+            crate::schema::fields_to_format(&table_def.format),
+        )?;
+        sys_space.update(&[table_def.id], ops)?;
+    }
+    Ok(())
+}
+
 /// Return a `KeyDef` to be used for comparing **tuples** of the corresponding global table.
 ///
 /// The return value is cached for system tables.
@@ -545,14 +590,43 @@ impl Tables {
         &self,
         id: SpaceId,
         format: &[tarantool::space::Field],
+        defaults: &[ColumnDefault],
     ) -> tarantool::Result<()> {
         // We can't use UpdateOps as we use custom encoding
         let mut table_def = self.get(id)?.expect("should exist");
         table_def.format = format.to_vec();
+        table_def.defaults = Some(defaults.to_vec());
+        self.put(&table_def)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn update_partition(
+        &self,
+        id: SpaceId,
+        partition: Option<TablePartition>,
+    ) -> tarantool::Result<()> {
+        // We can't use UpdateOps as we use custom encoding
+        let mut table_def = self.get(id)?.expect("should exist");
+        table_def.partition = partition;
         self.put(&table_def)?;
         Ok(())
     }
 
+    /// Partitions of the partitioned table.
+    pub fn partitions_of(&self, parent_id: SpaceId) -> tarantool::Result<Vec<TableDef>> {
+        let partitions = self
+            .iter()?
+            .filter(|table_def| {
+                matches!(
+                    table_def.partition,
+                    Some(TablePartition::Of { parent, .. }) if parent == parent_id
+                )
+            })
+            .collect();
+        Ok(partitions)
+    }
+
     #[inline]
     pub fn update_schema_version(&self, id: SpaceId, schema_version: u64) -> tarantool::Result<()> {
         let mut ops = UpdateOps::with_capacity(1);
@@ -1422,6 +1496,11 @@ impl Properties {
             .expect("ClusterVersion should be initialized");
         Ok(res)
     }
+
+    #[inline]
+    pub fn system_catalog_version(&self) -> tarantool::Result<Option<String>> {
+        self.get(PropertyName::SystemCatalogVersion.as_str())
+    }
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -1784,9 +1863,27 @@ impl Users {
     }
 
     #[inline]
-    pub fn update_auth(&self, user_id: UserId, auth: &AuthDef) -> tarantool::Result<()> {
-        let mut ops = UpdateOps::with_capacity(1);
+    pub fn update_auth(
+        &self,
+        user_id: UserId,
+        auth: &AuthDef,
+        scram_verifier: Option<&str>,
+    ) -> tarantool::Result<()> {
+        let mut ops = UpdateOps::with_capacity(2);
         ops.assign(column_name!(UserDef, auth), auth)?;
+        ops.assign(column_name!(UserDef, scram_verifier), scram_verifier)?;
+        self.space.update(&[user_id], ops)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn update_statement_timeout(
+        &self,
+        user_id: UserId,
+        statement_timeout: Option<u64>,
+    ) -> tarantool::Result<()> {
+        let mut ops = UpdateOps::with_capacity(1);
+        ops.assign(column_name!(UserDef, statement_timeout), statement_timeout)?;
         self.space.update(&[user_id], ops)?;
         Ok(())
     }
@@ -3224,6 +3321,14 @@ impl DbConfig {
         )
     }
 
+    #[inline]
+    pub fn sql_recursion_row_max(&self) -> tarantool::Result<u64> {
+        self.get_or_default(
+            system_parameter_name!(sql_recursion_row_max),
+            Self::GLOBAL_SCOPE,
+        )
+    }
+
     #[inline]
     pub fn sql_vdbe_opcode_max(&self) -> tarantool::Result<u64> {
         self.get_or_default(
@@ -3232,6 +3337,25 @@ impl DbConfig {
         )
     }
 
+    /// Returns `None` if the timeout is disabled.
+    #[inline]
+    pub fn sql_statement_timeout(&self) -> tarantool::Result<Option<Duration>> {
+        #[rustfmt::skip]
+        let res: f64 = self.get_or_default(system_parameter_name!(sql_statement_timeout), Self::GLOBAL_SCOPE)?;
+        if res <= 0.0 {
+            return Ok(None);
+        }
+        Ok(Some(duration_from_secs_f64_clamped(res)))
+    }
+
+    #[inline]
+    pub fn sql_user_query_max(&self) -> tarantool::Result<u64> {
+        self.get_or_default(
+            system_parameter_name!(sql_user_query_max),
+            Self::GLOBAL_SCOPE,
+        )
+    }
+
     /// `tier` argument should be from set of existing tiers.
     pub fn sql_storage_cache_count_max(&self, tier: &str) -> tarantool::Result<usize> {
         self.get_or_default(system_parameter_name!(sql_storage_cache_count_max), tier)
@@ -3300,6 +3424,507 @@ impl ToEntryIter<MP_SERDE> for DbConfig {
     }
 }
 
+////////////////////////////////////////////////////////////////////////////////
+// Sequences
+////////////////////////////////////////////////////////////////////////////////
+
+/// A struct for accessing info of all the user-defined sequences.
+#[derive(Debug, Clone)]
+pub struct Sequences {
+    pub space: Space,
+    pub index_id: Index,
+    pub index_name: Index,
+}
+
+impl SystemTable for Sequences {
+    const TABLE_NAME: &'static str = "_pico_sequence";
+    const TABLE_ID: SpaceId = 533;
+
+    fn format() -> Vec<tarantool::space::Field> {
+        SequenceDef::format()
+    }
+
+    fn index_definitions() -> Vec<IndexDef> {
+        vec![
+            IndexDef {
+                table_id: Self::TABLE_ID,
+                // Primary index
+                id: 0,
+                name: "_pico_sequence_id".into(),
+                ty: IndexType::Tree,
+                opts: vec![IndexOption::Unique(true)],
+                parts: vec![Part::from(("id", IndexFieldType::Unsigned)).is_nullable(false)],
+                operable: true,
+                // This means the local schema is already up to date and main loop doesn't need to do anything
+                schema_version: INITIAL_SCHEMA_VERSION,
+            },
+            IndexDef {
+                table_id: Self::TABLE_ID,
+                id: 1,
+                name: "_pico_sequence_name".into(),
+                ty: IndexType::Tree,
+                opts: vec![IndexOption::Unique(true)],
+                parts: vec![Part::from(("name", IndexFieldType::String)).is_nullable(false)],
+                operable: true,
+                // This means the local schema is already up to date and main loop doesn't need to do anything
+                schema_version: INITIAL_SCHEMA_VERSION,
+            },
+        ]
+    }
+}
+
+impl Sequences {
+    pub fn new() -> tarantool::Result<Self> {
+        let space = Space::builder(Self::TABLE_NAME)
+            .id(Self::TABLE_ID)
+            .space_type(SpaceType::DataLocal)
+            .format(Self::format())
+            .if_not_exists(true)
+            .create()?;
+
+        let index_id = space
+            .index_builder("_pico_sequence_id")
+            .unique(true)
+            .part("id")
+            .if_not_exists(true)
+            .create()?;
+
+        let index_name = space
+            .index_builder("_pico_sequence_name")
+            .unique(true)
+            .part("name")
+            .if_not_exists(true)
+            .create()?;
+
+        Ok(Self {
+            space,
+            index_id,
+            index_name,
+        })
+    }
+
+    #[inline]
+    pub fn by_name(&self, sequence_name: &str) -> tarantool::Result<Option<SequenceDef>> {
+        let tuple = self.index_name.get(&[sequence_name])?;
+        tuple.as_ref().map(Tuple::decode).transpose()
+    }
+
+    #[inline]
+    pub fn by_id(&self, sequence_id: SequenceId) -> tarantool::Result<Option<SequenceDef>> {
+        let tuple = self.space.get(&[sequence_id])?;
+        tuple.as_ref().map(Tuple::decode).transpose()
+    }
+
+    #[inline]
+    pub fn put(&self, sequence: &SequenceDef) -> tarantool::Result<()> {
+        self.space.replace(sequence)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn insert(&self, sequence: &SequenceDef) -> tarantool::Result<()> {
+        self.space.insert(sequence)?;
+        Ok(())
+    }
+
+    /// Sequences of the `SERIAL` and identity columns of the table.
+    #[inline]
+    pub fn owned_by(&self, table_id: SpaceId) -> tarantool::Result<Vec<SequenceDef>> {
+        let sequences = self
+            .iter()?
+            .filter(|sequence| sequence.table_id == Some(table_id))
+            .collect();
+        Ok(sequences)
+    }
+
+    #[inline]
+    pub fn delete(&self, sequence_id: SequenceId) -> tarantool::Result<()> {
+        self.space.delete(&[sequence_id])?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn update_operable(
+        &self,
+        sequence_id: SequenceId,
+        operable: bool,
+    ) -> tarantool::Result<()> {
+        let mut ops = UpdateOps::with_capacity(1);
+        ops.assign(column_name!(SequenceDef, operable), operable)?;
+        self.space.update(&[sequence_id], ops)?;
+        Ok(())
+    }
+
+    /// Sequence ids don't intersect with ids of tables or routines,
+    /// so the next id is just the maximum one plus one.
+    #[inline]
+    pub fn max_sequence_id(&self) -> tarantool::Result<Option<SequenceId>> {
+        match self.index_id.max(&())? {
+            Some(sequence) => Ok(sequence.get(0).unwrap()),
+            None => Ok(None),
+        }
+    }
+}
+
+impl ToEntryIter<MP_SERDE> for Sequences {
+    type Entry = SequenceDef;
+
+    #[inline(always)]
+    fn index_iter(&self) -> tarantool::Result<IndexIterator> {
+        self.space.select(IteratorType::All, &())
+    }
+}
+
+pub type SequenceId = u32;
+
+////////////////////////////////////////////////////////////////////////////////
+// Views
+////////////////////////////////////////////////////////////////////////////////
+
+/// A struct for accessing info of all the user-defined views.
+#[derive(Debug, Clone)]
+pub struct Views {
+    pub space: Space,
+    pub index_id: Index,
+    pub index_name: Index,
+}
+
+impl SystemTable for Views {
+    const TABLE_NAME: &'static str = "_pico_view";
+    const TABLE_ID: SpaceId = 534;
+
+    fn format() -> Vec<tarantool::space::Field> {
+        ViewDef::format()
+    }
+
+    fn index_definitions() -> Vec<IndexDef> {
+        vec![
+            IndexDef {
+                table_id: Self::TABLE_ID,
+                // Primary index
+                id: 0,
+                name: "_pico_view_id".into(),
+                ty: IndexType::Tree,
+                opts: vec![IndexOption::Unique(true)],
+                parts: vec![Part::from(("id", IndexFieldType::Unsigned)).is_nullable(false)],
+                operable: true,
+                // This means the local schema is already up to date and main loop doesn't need to do anything
+                schema_version: INITIAL_SCHEMA_VERSION,
+            },
+            IndexDef {
+                table_id: Self::TABLE_ID,
+                id: 1,
+                name: "_pico_view_name".into(),
+                ty: IndexType::Tree,
+                opts: vec![IndexOption::Unique(true)],
+                parts: vec![Part::from(("name", IndexFieldType::String)).is_nullable(false)],
+                operable: true,
+                // This means the local schema is already up to date and main loop doesn't need to do anything
+                schema_version: INITIAL_SCHEMA_VERSION,
+            },
+        ]
+    }
+}
+
+impl Views {
+    pub fn new() -> tarantool::Result<Self> {
+        let space = Space::builder(Self::TABLE_NAME)
+            .id(Self::TABLE_ID)
+            .space_type(SpaceType::DataLocal)
+            .format(Self::format())
+            .if_not_exists(true)
+            .create()?;
+
+        let index_id = space
+            .index_builder("_pico_view_id")
+            .unique(true)
+            .part("id")
+            .if_not_exists(true)
+            .create()?;
+
+        let index_name = space
+            .index_builder("_pico_view_name")
+            .unique(true)
+            .part("name")
+            .if_not_exists(true)
+            .create()?;
+
+        Ok(Self {
+            space,
+            index_id,
+            index_name,
+        })
+    }
+
+    #[inline]
+    pub fn by_name(&self, view_name: &str) -> tarantool::Result<Option<ViewDef>> {
+        let tuple = self.index_name.get(&[view_name])?;
+        tuple.as_ref().map(Tuple::decode).transpose()
+    }
+
+    #[inline]
+    pub fn by_id(&self, view_id: ViewId) -> tarantool::Result<Option<ViewDef>> {
+        let tuple = self.space.get(&[view_id])?;
+        tuple.as_ref().map(Tuple::decode).transpose()
+    }
+
+    #[inline]
+    pub fn put(&self, view: &ViewDef) -> tarantool::Result<()> {
+        self.space.replace(view)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn delete(&self, view_id: ViewId) -> tarantool::Result<()> {
+        self.space.delete(&[view_id])?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn update_operable(&self, view_id: ViewId, operable: bool) -> tarantool::Result<()> {
+        let mut ops = UpdateOps::with_capacity(1);
+        ops.assign(column_name!(ViewDef, operable), operable)?;
+        self.space.update(&[view_id], ops)?;
+        Ok(())
+    }
+
+    /// View ids don't intersect with ids of other objects,
+    /// so the next id is just the maximum one plus one.
+    #[inline]
+    pub fn max_view_id(&self) -> tarantool::Result<Option<ViewId>> {
+        match self.index_id.max(&())? {
+            Some(view) => Ok(view.get(0).unwrap()),
+            None => Ok(None),
+        }
+    }
+}
+
+impl ToEntryIter<MP_SERDE> for Views {
+    type Entry = ViewDef;
+
+    #[inline(always)]
+    fn index_iter(&self) -> tarantool::Result<IndexIterator> {
+        self.space.select(IteratorType::All, &())
+    }
+}
+
+pub type ViewId = u32;
+
+////////////////////////////////////////////////////////////////////////////////
+// Schemas
+////////////////////////////////////////////////////////////////////////////////
+
+/// A struct for accessing info of all the user-defined SQL schemas.
+#[derive(Debug, Clone)]
+pub struct Schemas {
+    pub space: Space,
+    pub index_id: Index,
+    pub index_name: Index,
+}
+
+impl SystemTable for Schemas {
+    const TABLE_NAME: &'static str = "_pico_schema";
+    const TABLE_ID: SpaceId = 535;
+
+    fn format() -> Vec<tarantool::space::Field> {
+        SchemaDef::format()
+    }
+
+    fn index_definitions() -> Vec<IndexDef> {
+        vec![
+            IndexDef {
+                table_id: Self::TABLE_ID,
+                // Primary index
+                id: 0,
+                name: "_pico_schema_id".into(),
+                ty: IndexType::Tree,
+                opts: vec![IndexOption::Unique(true)],
+                parts: vec![Part::from(("id", IndexFieldType::Unsigned)).is_nullable(false)],
+                operable: true,
+                // This means the local schema is already up to date and main loop doesn't need to do anything
+                schema_version: INITIAL_SCHEMA_VERSION,
+            },
+            IndexDef {
+                table_id: Self::TABLE_ID,
+                id: 1,
+                name: "_pico_schema_name".into(),
+                ty: IndexType::Tree,
+                opts: vec![IndexOption::Unique(true)],
+                parts: vec![Part::from(("name", IndexFieldType::String)).is_nullable(false)],
+                operable: true,
+                // This means the local schema is already up to date and main loop doesn't need to do anything
+                schema_version: INITIAL_SCHEMA_VERSION,
+            },
+        ]
+    }
+}
+
+impl Schemas {
+    pub fn new() -> tarantool::Result<Self> {
+        let space = Space::builder(Self::TABLE_NAME)
+            .id(Self::TABLE_ID)
+            .space_type(SpaceType::DataLocal)
+            .format(Self::format())
+            .if_not_exists(true)
+            .create()?;
+
+        let index_id = space
+            .index_builder("_pico_schema_id")
+            .unique(true)
+            .part("id")
+            .if_not_exists(true)
+            .create()?;
+
+        let index_name = space
+            .index_builder("_pico_schema_name")
+            .unique(true)
+            .part("name")
+            .if_not_exists(true)
+            .create()?;
+
+        Ok(Self {
+            space,
+            index_id,
+            index_name,
+        })
+    }
+
+    #[inline]
+    pub fn by_name(&self, schema_name: &str) -> tarantool::Result<Option<SchemaDef>> {
+        let tuple = self.index_name.get(&[schema_name])?;
+        tuple.as_ref().map(Tuple::decode).transpose()
+    }
+
+    #[inline]
+    pub fn by_id(&self, schema_id: SchemaId) -> tarantool::Result<Option<SchemaDef>> {
+        let tuple = self.space.get(&[schema_id])?;
+        tuple.as_ref().map(Tuple::decode).transpose()
+    }
+
+    #[inline]
+    pub fn put(&self, schema: &SchemaDef) -> tarantool::Result<()> {
+        self.space.replace(schema)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn delete(&self, schema_id: SchemaId) -> tarantool::Result<()> {
+        self.space.delete(&[schema_id])?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn update_operable(&self, schema_id: SchemaId, operable: bool) -> tarantool::Result<()> {
+        let mut ops = UpdateOps::with_capacity(1);
+        ops.assign(column_name!(SchemaDef, operable), operable)?;
+        self.space.update(&[schema_id], ops)?;
+        Ok(())
+    }
+
+    /// Schema ids don't intersect with ids of other objects,
+    /// so the next id is just the maximum one plus one.
+    #[inline]
+    pub fn max_schema_id(&self) -> tarantool::Result<Option<SchemaId>> {
+        match self.index_id.max(&())? {
+            Some(schema) => Ok(schema.get(0).unwrap()),
+            None => Ok(None),
+        }
+    }
+}
+
+impl ToEntryIter<MP_SERDE> for Schemas {
+    type Entry = SchemaDef;
+
+    #[inline(always)]
+    fn index_iter(&self) -> tarantool::Result<IndexIterator> {
+        self.space.select(IteratorType::All, &())
+    }
+}
+
+pub type SchemaId = u32;
+
+////////////////////////////////////////////////////////////////////////////////
+// TableStatistics
+////////////////////////////////////////////////////////////////////////////////
+
+/// A struct for accessing the statistics of the user tables gathered by `ANALYZE`.
+#[derive(Debug, Clone)]
+pub struct TableStatistics {
+    pub space: Space,
+    pub index: Index,
+}
+
+impl SystemTable for TableStatistics {
+    const TABLE_NAME: &'static str = "_pico_table_stats";
+    const TABLE_ID: SpaceId = 536;
+
+    fn format() -> Vec<tarantool::space::Field> {
+        TableStatsDef::format()
+    }
+
+    fn index_definitions() -> Vec<IndexDef> {
+        vec![IndexDef {
+            table_id: Self::TABLE_ID,
+            // Primary index
+            id: 0,
+            name: "_pico_table_stats_table_id".into(),
+            ty: IndexType::Tree,
+            opts: vec![IndexOption::Unique(true)],
+            parts: vec![Part::from(("table_id", IndexFieldType::Unsigned)).is_nullable(false)],
+            operable: true,
+            // This means the local schema is already up to date and main loop doesn't need to do anything
+            schema_version: INITIAL_SCHEMA_VERSION,
+        }]
+    }
+}
+
+impl TableStatistics {
+    pub fn new() -> tarantool::Result<Self> {
+        let space = Space::builder(Self::TABLE_NAME)
+            .id(Self::TABLE_ID)
+            .space_type(SpaceType::DataLocal)
+            .format(Self::format())
+            .if_not_exists(true)
+            .create()?;
+
+        let index = space
+            .index_builder("_pico_table_stats_table_id")
+            .unique(true)
+            .part("table_id")
+            .if_not_exists(true)
+            .create()?;
+
+        Ok(Self { space, index })
+    }
+
+    #[inline]
+    pub fn get(&self, table_id: SpaceId) -> tarantool::Result<Option<TableStatsDef>> {
+        let tuple = self.space.get(&[table_id])?;
+        tuple.as_ref().map(Tuple::decode).transpose()
+    }
+
+    #[inline]
+    pub fn put(&self, stats: &TableStatsDef) -> tarantool::Result<()> {
+        self.space.replace(stats)?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn delete(&self, table_id: SpaceId) -> tarantool::Result<()> {
+        self.space.delete(&[table_id])?;
+        Ok(())
+    }
+}
+
+impl ToEntryIter<MP_SERDE> for TableStatistics {
+    type Entry = TableStatsDef;
+
+    #[inline(always)]
+    fn index_iter(&self) -> tarantool::Result<IndexIterator> {
+        self.space.select(IteratorType::All, &())
+    }
+}
+
 /// Ignore specific tarantool error.
 /// E.g. if `res` contains an `ignored` error, it will be
 /// transformed to ok instead.
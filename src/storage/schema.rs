@@ -1,3 +1,4 @@
+use crate::schema::{constraint_func_prefix, TableConstraint};
 use crate::schema::{fields_to_format, Distribution, PrivilegeType, SchemaObjectType};
 use crate::schema::{IndexDef, IndexOption};
 use crate::schema::{PrivilegeDef, RoutineDef, UserDef};
@@ -9,6 +10,7 @@ use crate::traft::error::Error;
 use crate::traft::op::Ddl;
 use crate::traft::Result;
 use crate::{column_name, traft};
+use sbroad::ir::value::Value as IrValue;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::ffi::CString;
@@ -21,12 +23,13 @@ use tarantool::index::FieldType as IndexFieldType;
 use tarantool::index::Metadata as IndexMetadata;
 use tarantool::index::{Index, IndexId, IndexType, IteratorType};
 use tarantool::index::{IndexOptions, Part};
+use tarantool::msgpack;
 use tarantool::schema::index::{create_index, drop_index};
 use tarantool::session::UserId;
 use tarantool::space::UpdateOps;
 use tarantool::space::{Space, SpaceId, SystemSpace};
 use tarantool::tlua::{self, LuaError};
-use tarantool::tuple::Encode;
+use tarantool::tuple::{Encode, RawBytes};
 
 ////////////////////////////////////////////////////////////////////////////////
 // ddl meta
@@ -62,6 +65,7 @@ pub fn ddl_meta_drop_space(storage: &Catalog, space_id: SpaceId) -> traft::Resul
     for index in iter {
         storage.indexes.delete(index.table_id, index.id)?;
     }
+    storage.table_stats.delete(space_id)?;
     storage.tables.delete(space_id)?;
     Ok(())
 }
@@ -90,6 +94,7 @@ pub fn ddl_abort_on_master(storage: &Catalog, ddl: &Ddl, version: u64) -> traft:
             sys_index.delete(&[id, 1])?;
             sys_index.delete(&[id, 0])?;
             sys_space.delete(&[id])?;
+            drop_table_constraints(id)?;
             set_local_schema_version(version)?;
         }
 
@@ -137,12 +142,41 @@ pub fn ddl_abort_on_master(storage: &Catalog, ddl: &Ddl, version: u64) -> traft:
             // Actual drop happens only on commit, so there's nothing to abort.
         }
 
+        Ddl::CreateSequence { .. } => {
+            set_local_schema_version(version)?;
+        }
+
+        Ddl::DropSequence { .. } => {
+            // Actual drop happens only on commit, so there's nothing to abort.
+        }
+
+        Ddl::CreateView { .. } => {
+            set_local_schema_version(version)?;
+        }
+
+        Ddl::DropView { .. } => {
+            // Actual drop happens only on commit, so there's nothing to abort.
+        }
+
+        Ddl::CreateSchema { .. } => {
+            set_local_schema_version(version)?;
+        }
+
+        Ddl::DropSchema { .. } => {
+            // Actual drop happens only on commit, so there's nothing to abort.
+        }
+
+        Ddl::DetachPartition { .. } => {
+            // Partition is detached only on commit, so there's nothing to abort.
+        }
+
         Ddl::ChangeFormat {
             table_id,
             ref old_format,
             ..
         } => {
             ddl_change_format_on_master(table_id, old_format)?;
+            ddl_remove_fields_on_master(table_id, old_format.len())?;
             set_local_schema_version(version)?;
         }
     }
@@ -419,6 +453,14 @@ pub fn ddl_create_space_on_master(
         if let Some(def) = bucket_id_def {
             sys_index.insert(&def.to_index_metadata(&pico_space_def))?;
         }
+        if let Some(constraints) = &pico_space_def.constraints {
+            create_table_constraints(
+                space_id,
+                &pico_space_def.format,
+                matches!(pico_space_def.distribution, Distribution::Global),
+                constraints,
+            )?;
+        }
 
         Ok(())
     })();
@@ -481,12 +523,137 @@ pub fn ddl_drop_space_on_master(space_id: SpaceId) -> traft::Result<Option<TntEr
         sys_index.delete(&(space_id, 0))?;
         sys_truncate.delete(&[space_id])?;
         sys_space.delete(&[space_id])?;
+        drop_table_constraints(space_id)?;
 
         Ok(())
     })();
     Ok(res.err())
 }
 
+/// Create the tarantool functions checking the constraints of the table
+/// and attach them to the space as tuple constraints, so the constraints
+/// are checked on every insert and update of the space.
+pub fn create_table_constraints(
+    table_id: SpaceId,
+    format: &[tarantool::space::Field],
+    is_global: bool,
+    constraints: &[TableConstraint],
+) -> tarantool::Result<()> {
+    if constraints.is_empty() {
+        return Ok(());
+    }
+
+    let lua = ::tarantool::lua_state();
+    let mut funcs = HashMap::with_capacity(constraints.len());
+    for constraint in constraints {
+        // The foreign key check depends on the contents of the referenced
+        // table, so unlike the check of the expression it is not deterministic.
+        let (language, body, is_deterministic) = match constraint {
+            // Like in SQL, the constraint is satisfied if the expression is NULL.
+            TableConstraint::Check { expr, .. } => {
+                ("SQL_EXPR", format!("COALESCE(({expr}), TRUE)"), true)
+            }
+            TableConstraint::ForeignKey {
+                columns, parent, ..
+            } => (
+                "LUA",
+                foreign_key_func_body(format, columns, *parent, is_global)?,
+                false,
+            ),
+        };
+        let func_name = constraint.func_name(table_id);
+        lua.exec_with(
+            "box.schema.func.create(...)",
+            (
+                &func_name,
+                tlua::AsTable((
+                    ("language", language),
+                    ("body", body),
+                    ("is_deterministic", is_deterministic),
+                )),
+            ),
+        )
+        .map_err(LuaError::from)?;
+        funcs.insert(constraint.name(), func_name);
+    }
+    lua.exec_with(
+        "local space_id, constraint = ...
+        box.space[space_id]:alter({constraint = constraint})",
+        (table_id, funcs),
+    )
+    .map_err(LuaError::from)?;
+
+    Ok(())
+}
+
+/// Body of the lua function checking the foreign key: the values of the
+/// columns must be the primary key of a row of the referenced global table.
+///
+/// The tuples of a sharded table are checked only on the master, the replicas
+/// receive them before the global table may be updated on them. The master
+/// catches up with the raft log before the DML (see `wait_foreign_key_parents`
+/// in [`crate::sql`]). The tuples of a global table are checked on every
+/// instance applying the raft log.
+fn foreign_key_func_body(
+    format: &[tarantool::space::Field],
+    columns: &[String],
+    parent: SpaceId,
+    is_global: bool,
+) -> tarantool::Result<String> {
+    let mut key = Vec::with_capacity(columns.len());
+    for column in columns {
+        let Some(index) = format.iter().position(|f| f.name == *column) else {
+            return Err(BoxError::new(
+                TntErrorCode::IllegalParams,
+                format!("foreign key column {column} not found"),
+            )
+            .into());
+        };
+        // Lua tables are 1-based.
+        key.push(format!("tuple[{}]", index + 1));
+    }
+    let skip_on_replica = if is_global { "false" } else { "box.info.ro" };
+
+    Ok(format!(
+        "function(tuple)
+            if {skip_on_replica} or not box.ctl.is_recovery_finished() then
+                return true
+            end
+            local key = {{{key}}}
+            for i = 1, {len} do
+                if key[i] == nil then
+                    return true
+                end
+            end
+            return box.space[{parent}]:get(key) ~= nil
+        end",
+        key = key.join(", "),
+        len = columns.len(),
+    ))
+}
+
+/// Drop the tarantool functions checking the constraints of the dropped table.
+pub fn drop_table_constraints(table_id: SpaceId) -> tarantool::Result<()> {
+    let lua = ::tarantool::lua_state();
+    lua.exec_with(
+        "local prefix = ...
+        local names = {}
+        for _, func in box.space._func.index.name:pairs(prefix, {iterator = 'GE'}) do
+            if func.name:sub(1, #prefix) ~= prefix then
+                break
+            end
+            table.insert(names, func.name)
+        end
+        for _, name in ipairs(names) do
+            box.schema.func.drop(name)
+        end",
+        constraint_func_prefix(table_id),
+    )
+    .map_err(LuaError::from)?;
+
+    Ok(())
+}
+
 /// Truncate tarantool space.
 ///
 /// Return values:
@@ -531,6 +698,63 @@ pub fn ddl_change_format_on_master(
     Ok(())
 }
 
+/// Append `values` of the added columns to every tuple of the space.
+/// Must be called before the new format is applied, so the new columns
+/// can be `NOT NULL`.
+///
+/// Return values:
+/// * `Ok(())` in case of success.
+/// * `Err(e)` in case of error which should result in a ddl abort.
+pub fn ddl_add_fields_on_master(space_id: SpaceId, values: &[IrValue]) -> Result<(), TntError> {
+    debug_assert!(unsafe { tarantool::ffi::tarantool::box_txn() });
+    if values.is_empty() {
+        return Ok(());
+    }
+
+    let mut fields = Vec::new();
+    for value in values {
+        fields.extend(msgpack::encode(value));
+    }
+    let space = space_by_id_unchecked(space_id);
+    for tuple in space.select(IteratorType::All, &())? {
+        let data = tuple.to_vec();
+        let mut rest = data.as_slice();
+        let count = rmp::decode::read_array_len(&mut rest)?;
+        let mut buf = Vec::with_capacity(data.len() + fields.len());
+        rmp::encode::write_array_len(&mut buf, count + values.len() as u32)?;
+        buf.extend_from_slice(rest);
+        buf.extend_from_slice(&fields);
+        space.replace(RawBytes::new(&buf))?;
+    }
+    Ok(())
+}
+
+/// Cut the tuples of the space back to `field_count` fields, reverting
+/// [`ddl_add_fields_on_master`] when the ddl is aborted.
+pub fn ddl_remove_fields_on_master(space_id: SpaceId, field_count: usize) -> Result<(), TntError> {
+    debug_assert!(unsafe { tarantool::ffi::tarantool::box_txn() });
+
+    let space = space_by_id_unchecked(space_id);
+    for tuple in space.select(IteratorType::All, &())? {
+        if tuple.len() as usize <= field_count {
+            continue;
+        }
+        let data = tuple.to_vec();
+        let mut cursor = std::io::Cursor::new(data.as_slice());
+        rmp::decode::read_array_len(&mut cursor)?;
+        let start = cursor.position() as usize;
+        for _ in 0..field_count {
+            msgpack::skip_value(&mut cursor)?;
+        }
+        let end = cursor.position() as usize;
+        let mut buf = Vec::with_capacity(end);
+        rmp::encode::write_array_len(&mut buf, field_count as u32)?;
+        buf.extend_from_slice(&data[start..end]);
+        space.replace(RawBytes::new(&buf))?;
+    }
+    Ok(())
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // acl
 ////////////////////////////////////////////////////////////////////////////////
@@ -611,11 +835,21 @@ pub mod acl {
         let owner_def = user_by_id(user_def.owner)?;
 
         let user = &user_def.name;
+        let auth_type = if user_def.scram_verifier.is_some() {
+            "scram-sha-256"
+        } else {
+            user_def
+                .auth
+                .as_ref()
+                .expect("user always should have non empty auth")
+                .method
+                .as_str()
+        };
         crate::audit!(
             message: "created user `{user}`",
             title: "create_user",
             severity: High,
-            auth_type: user_def.auth.as_ref().expect("user always should have non empty auth").method.as_str(),
+            auth_type: auth_type,
             user: user,
             initiator: owner_def.name,
         );
@@ -632,20 +866,26 @@ pub mod acl {
         storage: &Catalog,
         user_id: UserId,
         auth: &AuthDef,
+        scram_verifier: Option<&str>,
         initiator: UserId,
     ) -> tarantool::Result<()> {
-        storage.users.update_auth(user_id, auth)?;
+        storage.users.update_auth(user_id, auth, scram_verifier)?;
 
         let user_def = storage.users.by_id(user_id)?.expect("failed to get user");
         let user = &user_def.name;
 
         let initiator_def = user_by_id(initiator)?;
+        let auth_type = if scram_verifier.is_some() {
+            "scram-sha-256"
+        } else {
+            auth.method.as_str()
+        };
 
         crate::audit!(
             message: "password of user `{user}` was changed",
             title: "change_password",
             severity: High,
-            auth_type: auth.method.as_str(),
+            auth_type: auth_type,
             user: user,
             initiator: initiator_def.name,
         );
@@ -990,6 +1230,14 @@ pub mod acl {
     /// Grant a tarantool user or role the privilege defined by `priv_def`.
     /// Is idempotent: will not return an error even if the privilege is already granted.
     pub fn on_master_grant_privilege(priv_def: &PrivilegeDef) -> tarantool::Result<()> {
+        if matches!(
+            priv_def.object_type(),
+            SchemaObjectType::View | SchemaObjectType::Schema
+        ) {
+            // Views and schemas exist only in picodata's system tables.
+            return Ok(());
+        }
+
         let lua = ::tarantool::lua_state();
         lua.exec_with(
             "local grantee_id, privilege, object_type, object_id = ...
@@ -1014,6 +1262,13 @@ pub mod acl {
     /// Revoke a privilege from a tarantool user or role.
     /// Is idempotent: will not return an error even if the privilege was not granted.
     pub fn on_master_revoke_privilege(priv_def: &PrivilegeDef) -> tarantool::Result<()> {
+        if matches!(
+            priv_def.object_type(),
+            SchemaObjectType::View | SchemaObjectType::Schema
+        ) {
+            return Ok(());
+        }
+
         let lua = ::tarantool::lua_state();
         lua.exec_with(
             "local grantee_id, privilege, object_type, object_id = ...
@@ -350,6 +350,7 @@ impl Cfg {
             ("log_format",                  config_parameter_path!(instance.log.format)),
             ("wal_dir",                     config_parameter_path!(instance.instance_dir)),
             ("memtx_dir",                   config_parameter_path!(instance.instance_dir)),
+            ("memtx_use_mvcc_engine",       config_parameter_path!(instance.memtx.use_mvcc_engine)),
             ("vinyl_dir",                   config_parameter_path!(instance.instance_dir)),
             ("vinyl_bloom_fpr",             config_parameter_path!(instance.vinyl.bloom_fpr)),
             ("vinyl_run_count_per_level",   config_parameter_path!(instance.vinyl.run_count_per_level)),
@@ -399,7 +400,6 @@ pub fn set_cfg(cfg: &Cfg) -> Result<(), Error> {
     Ok(())
 }
 
-#[allow(dead_code)]
 pub fn cfg_field<T>(field: &str) -> Option<T>
 where
     T: LuaRead<PushGuard<LuaTable<PushGuard<LuaTable<PushGuard<LuaThread>>>>>>,
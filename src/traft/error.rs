@@ -43,6 +43,12 @@ pub enum AlreadyExists {
     Index(SmolStr),
     #[error("procedure {0} already exists")]
     Procedure(SmolStr),
+    #[error("sequence {0} already exists")]
+    Sequence(SmolStr),
+    #[error("view {0} already exists")]
+    View(SmolStr),
+    #[error("schema {0} already exists")]
+    Schema(SmolStr),
     #[error("user {0} already exists")]
     User(SmolStr),
     #[error("role {0} already exists")]
@@ -57,6 +63,12 @@ pub enum DoesNotExist {
     Index(SmolStr),
     #[error("procedure {0} does not exist")]
     Procedure(SmolStr),
+    #[error("sequence {0} does not exist")]
+    Sequence(SmolStr),
+    #[error("view {0} does not exist")]
+    View(SmolStr),
+    #[error("schema {0} does not exist")]
+    Schema(SmolStr),
     #[error("user {0} does not exist")]
     User(SmolStr),
     #[error("role {0} does not exist")]
@@ -69,6 +81,10 @@ pub enum Error {
     Uninitialized,
     #[error("timeout")]
     Timeout,
+    #[error("canceling statement due to user request")]
+    QueryCanceled,
+    #[error("canceling statement due to statement timeout")]
+    StatementTimeout,
     #[error("current instance is expelled from the cluster")]
     Expelled,
     #[error("{0}")]
@@ -26,7 +26,10 @@ use crate::rpc::snapshot::proc_raft_snapshot_next_chunk;
 use crate::schema::RoutineDef;
 use crate::schema::RoutineKind;
 use crate::schema::SchemaObjectType;
-use crate::schema::{Distribution, IndexDef, IndexOption, TableDef};
+use crate::schema::SequenceDef;
+use crate::schema::TableSequence;
+use crate::schema::{Distribution, IndexDef, IndexOption, TableDef, TablePartition};
+use crate::schema::{SchemaDef, ViewDef};
 use crate::sentinel;
 use crate::static_ref;
 use crate::storage::cached_key_def;
@@ -1045,10 +1048,29 @@ impl NodeImpl {
                 // Update pico metadata.
                 match ddl {
                     Ddl::CreateTable {
-                        id, name, owner, ..
+                        id,
+                        name,
+                        owner,
+                        partition,
+                        sequences,
+                        ..
                     } => {
                         ddl_meta_space_update_operable(&self.storage, id, true)
                             .expect("storage shouldn't fail");
+                        for sequence in sequences {
+                            self.storage
+                                .sequences
+                                .update_operable(sequence.id, true)
+                                .expect("storage shouldn't fail");
+                        }
+                        // Cached plans of the queries over the partitioned
+                        // table must see the new partition.
+                        if let Some(TablePartition::Of { parent, .. }) = partition {
+                            self.storage
+                                .tables
+                                .update_schema_version(parent, v_pending)
+                                .expect("storage shouldn't fail");
+                        }
 
                         let initiator_def = user_by_id(owner).expect("user must exist");
 
@@ -1065,6 +1087,23 @@ impl NodeImpl {
                         let space_raw = self.storage.tables.get(id);
                         let space = space_raw.ok().flatten().expect("failed to get space");
                         ddl_meta_drop_space(&self.storage, id).expect("storage shouldn't fail");
+                        for sequence in self
+                            .storage
+                            .sequences
+                            .owned_by(id)
+                            .expect("storage shouldn't fail")
+                        {
+                            self.storage
+                                .sequences
+                                .delete(sequence.id)
+                                .expect("storage shouldn't fail");
+                        }
+                        if let Some(TablePartition::Of { parent, .. }) = space.partition {
+                            self.storage
+                                .tables
+                                .update_schema_version(parent, v_pending)
+                                .expect("storage shouldn't fail");
+                        }
 
                         let initiator_def = user_by_id(initiator).expect("user must exist");
 
@@ -1249,6 +1288,179 @@ impl NodeImpl {
                             initiator: initiator_def.name,
                         );
                     }
+
+                    Ddl::CreateSequence {
+                        id, name, owner, ..
+                    } => {
+                        self.storage
+                            .sequences
+                            .update_operable(id, true)
+                            .expect("storage shouldn't fail");
+
+                        let initiator_def = user_by_id(owner).expect("user must exist");
+
+                        crate::audit!(
+                            message: "created sequence `{name}`",
+                            title: "create_sequence",
+                            severity: Medium,
+                            name: &name,
+                            initiator: initiator_def.name,
+                        );
+                    }
+
+                    Ddl::DropSequence { id, initiator } => {
+                        let sequence = self.storage.sequences.by_id(id);
+                        let sequence = sequence.ok().flatten().expect("sequence must exist");
+                        self.storage
+                            .sequences
+                            .delete(id)
+                            .expect("storage shouldn't fail");
+
+                        let initiator_def = user_by_id(initiator).expect("user must exist");
+
+                        let name = &sequence.name;
+                        crate::audit!(
+                            message: "dropped sequence `{name}`",
+                            title: "drop_sequence",
+                            severity: Medium,
+                            name: &name,
+                            initiator: initiator_def.name,
+                        );
+                    }
+
+                    Ddl::CreateView {
+                        id,
+                        name,
+                        query,
+                        owner,
+                        replace,
+                    } => {
+                        let view_def = ViewDef {
+                            id,
+                            name: name.clone(),
+                            query,
+                            operable: true,
+                            schema_version: v_pending,
+                            owner,
+                        };
+                        self.storage
+                            .views
+                            .put(&view_def)
+                            .expect("storage shouldn't fail");
+
+                        let initiator_def = user_by_id(owner).expect("user must exist");
+
+                        let action = if replace { "replaced" } else { "created" };
+                        crate::audit!(
+                            message: "{action} view `{name}`",
+                            title: "create_view",
+                            severity: Medium,
+                            name: &name,
+                            initiator: initiator_def.name,
+                        );
+                    }
+
+                    Ddl::DropView { id, initiator } => {
+                        let view = self.storage.views.by_id(id);
+                        let view = view.ok().flatten().expect("view must exist");
+                        self.storage
+                            .privileges
+                            .delete_all_by_object(SchemaObjectType::View, id.into())
+                            .expect("storage shouldn't fail");
+                        self.storage
+                            .views
+                            .delete(id)
+                            .expect("storage shouldn't fail");
+
+                        let initiator_def = user_by_id(initiator).expect("user must exist");
+
+                        let name = &view.name;
+                        crate::audit!(
+                            message: "dropped view `{name}`",
+                            title: "drop_view",
+                            severity: Medium,
+                            name: &name,
+                            initiator: initiator_def.name,
+                        );
+                    }
+
+                    Ddl::CreateSchema { id, name, owner } => {
+                        let schema_def = SchemaDef {
+                            id,
+                            name: name.clone(),
+                            operable: true,
+                            schema_version: v_pending,
+                            owner,
+                        };
+                        self.storage
+                            .schemas
+                            .put(&schema_def)
+                            .expect("storage shouldn't fail");
+
+                        let initiator_def = user_by_id(owner).expect("user must exist");
+
+                        crate::audit!(
+                            message: "created schema `{name}`",
+                            title: "create_schema",
+                            severity: Medium,
+                            name: &name,
+                            initiator: initiator_def.name,
+                        );
+                    }
+
+                    Ddl::DropSchema { id, initiator } => {
+                        let schema = self.storage.schemas.by_id(id);
+                        let schema = schema.ok().flatten().expect("schema must exist");
+                        self.storage
+                            .privileges
+                            .delete_all_by_object(SchemaObjectType::Schema, id.into())
+                            .expect("storage shouldn't fail");
+                        self.storage
+                            .schemas
+                            .delete(id)
+                            .expect("storage shouldn't fail");
+
+                        let initiator_def = user_by_id(initiator).expect("user must exist");
+
+                        let name = &schema.name;
+                        crate::audit!(
+                            message: "dropped schema `{name}`",
+                            title: "drop_schema",
+                            severity: Medium,
+                            name: &name,
+                            initiator: initiator_def.name,
+                        );
+                    }
+
+                    Ddl::DetachPartition {
+                        table_id,
+                        parent_id,
+                        initiator,
+                    } => {
+                        self.storage
+                            .tables
+                            .update_partition(table_id, None)
+                            .expect("storage shouldn't fail");
+                        for id in [table_id, parent_id] {
+                            self.storage
+                                .tables
+                                .update_schema_version(id, v_pending)
+                                .expect("storage shouldn't fail");
+                        }
+
+                        let initiator_def = user_by_id(initiator).expect("user must exist");
+
+                        let space_raw = self.storage.tables.get(table_id);
+                        let space = space_raw.ok().flatten().expect("failed to get space");
+                        let name = &space.name;
+                        crate::audit!(
+                            message: "detached partition `{name}`",
+                            title: "detach_partition",
+                            severity: Medium,
+                            name: &name,
+                            initiator: initiator_def.name,
+                        );
+                    }
                 }
 
                 storage_properties
@@ -1288,13 +1500,30 @@ impl NodeImpl {
 
                 // Update pico metadata.
                 match ddl {
-                    Ddl::CreateTable { id, .. } => {
+                    Ddl::CreateTable { id, sequences, .. } => {
                         ddl_meta_drop_space(&self.storage, id).expect("storage shouldn't fail");
+                        for sequence in sequences {
+                            self.storage
+                                .sequences
+                                .delete(sequence.id)
+                                .expect("storage shouldn't fail");
+                        }
                     }
 
                     Ddl::DropTable { id, .. } => {
                         ddl_meta_space_update_operable(&self.storage, id, true)
                             .expect("storage shouldn't fail");
+                        for sequence in self
+                            .storage
+                            .sequences
+                            .owned_by(id)
+                            .expect("storage shouldn't fail")
+                        {
+                            self.storage
+                                .sequences
+                                .update_operable(sequence.id, true)
+                                .expect("storage shouldn't fail");
+                        }
                     }
 
                     Ddl::RenameTable {
@@ -1316,13 +1545,14 @@ impl NodeImpl {
                     Ddl::ChangeFormat {
                         table_id,
                         ref old_format,
+                        ref old_defaults,
                         ..
                     } => {
                         ddl_meta_space_update_operable(&self.storage, table_id, true)
                             .expect("storage shouldn't fail");
                         self.storage
                             .tables
-                            .update_format(table_id, old_format)
+                            .update_format(table_id, old_format, old_defaults)
                             .expect("storage shouldn't fail");
                     }
                     Ddl::CreateProcedure { id, .. } => {
@@ -1374,6 +1604,55 @@ impl NodeImpl {
                             .update_operable(space_id, index_id, true)
                             .expect("storage shouldn't fail");
                     }
+
+                    Ddl::CreateSequence { id, .. } => {
+                        self.storage
+                            .sequences
+                            .delete(id)
+                            .expect("storage shouldn't fail");
+                    }
+
+                    Ddl::DropSequence { id, .. } => {
+                        self.storage
+                            .sequences
+                            .update_operable(id, true)
+                            .expect("storage shouldn't fail");
+                    }
+
+                    Ddl::CreateView { id, replace, .. } => {
+                        // Replaced view is left intact until commit.
+                        if !replace {
+                            self.storage
+                                .views
+                                .delete(id)
+                                .expect("storage shouldn't fail");
+                        }
+                    }
+
+                    Ddl::DropView { id, .. } => {
+                        self.storage
+                            .views
+                            .update_operable(id, true)
+                            .expect("storage shouldn't fail");
+                    }
+
+                    Ddl::CreateSchema { id, .. } => {
+                        self.storage
+                            .schemas
+                            .delete(id)
+                            .expect("storage shouldn't fail");
+                    }
+
+                    Ddl::DropSchema { id, .. } => {
+                        self.storage
+                            .schemas
+                            .update_operable(id, true)
+                            .expect("storage shouldn't fail");
+                    }
+
+                    Ddl::DetachPartition { .. } => {
+                        // Partition is detached only on commit.
+                    }
                 }
 
                 storage_properties
@@ -1570,6 +1849,9 @@ impl NodeImpl {
                                 acl::on_master_change_user_auth(*user_id, auth)
                                     .expect("changing user auth shouldn't fail");
                             }
+                            Acl::ChangeStatementTimeout { .. } => {
+                                // Stored in _pico_user only, tarantool knows nothing about it.
+                            }
                             Acl::DropUser { user_id, .. } => {
                                 acl::on_master_drop_user(*user_id)
                                     .expect("droping user shouldn't fail");
@@ -1613,9 +1895,26 @@ impl NodeImpl {
                         user_id,
                         auth,
                         initiator,
+                        scram_verifier,
                         ..
                     } => {
-                        acl::global_change_user_auth(&self.storage, *user_id, auth, *initiator)
+                        acl::global_change_user_auth(
+                            &self.storage,
+                            *user_id,
+                            auth,
+                            scram_verifier.as_deref(),
+                            *initiator,
+                        )
+                        .expect("changing user definition shouldn't fail");
+                    }
+                    Acl::ChangeStatementTimeout {
+                        user_id,
+                        statement_timeout,
+                        ..
+                    } => {
+                        self.storage
+                            .users
+                            .update_statement_timeout(*user_id, *statement_timeout)
                             .expect("changing user definition shouldn't fail");
                     }
                     Acl::DropUser {
@@ -1680,6 +1979,10 @@ impl NodeImpl {
                 distribution,
                 engine,
                 owner,
+                defaults,
+                partition,
+                constraints,
+                sequences,
             } => {
                 use ::tarantool::util::NumOrStr::*;
 
@@ -1788,12 +2091,42 @@ impl NodeImpl {
                     owner,
                     // TODO: add description field into Ddl::CreateTable
                     description: "".into(),
+                    defaults: Some(defaults),
+                    partition,
+                    constraints: Some(constraints),
                 };
                 let res = self.storage.tables.insert(&table_def);
                 if let Err(e) = res {
                     // Ignore the error for now, let governor deal with it.
                     tlog!(Warning, "failed creating table '{}': {e}", table_def.name);
                 }
+
+                for TableSequence {
+                    id: sequence_id,
+                    name,
+                } in sequences
+                {
+                    let sequence_def = SequenceDef {
+                        id: sequence_id,
+                        name,
+                        start: 1,
+                        increment: 1,
+                        allocated: 0,
+                        operable: false,
+                        schema_version,
+                        owner,
+                        table_id: Some(id),
+                    };
+                    let res = self.storage.sequences.insert(&sequence_def);
+                    if let Err(e) = res {
+                        // Ignore the error for now, let governor deal with it.
+                        tlog!(
+                            Warning,
+                            "failed creating sequence '{}': {e}",
+                            sequence_def.name
+                        );
+                    }
+                }
             }
             Ddl::CreateIndex {
                 space_id,
@@ -1822,6 +2155,17 @@ impl NodeImpl {
             Ddl::DropTable { id, .. } => {
                 ddl_meta_space_update_operable(&self.storage, id, false)
                     .expect("storage shouldn't fail");
+                for sequence in self
+                    .storage
+                    .sequences
+                    .owned_by(id)
+                    .expect("storage shouldn't fail")
+                {
+                    self.storage
+                        .sequences
+                        .update_operable(sequence.id, false)
+                        .expect("storage shouldn't fail");
+                }
             }
             Ddl::RenameTable {
                 table_id, new_name, ..
@@ -1899,17 +2243,99 @@ impl NodeImpl {
             Ddl::ChangeFormat {
                 table_id,
                 new_format,
+                new_defaults,
                 ..
             } => {
                 self.storage
                     .tables
-                    .update_format(table_id, &new_format)
+                    .update_format(table_id, &new_format, &new_defaults)
                     .expect("storage shouldn't fail");
                 self.storage
                     .tables
                     .update_operable(table_id, false)
                     .expect("storage shouldn't fail");
             }
+            Ddl::CreateSequence {
+                id,
+                name,
+                start,
+                increment,
+                owner,
+            } => {
+                let sequence_def = SequenceDef {
+                    id,
+                    name,
+                    start,
+                    increment,
+                    allocated: 0,
+                    operable: false,
+                    schema_version,
+                    owner,
+                    table_id: None,
+                };
+                self.storage
+                    .sequences
+                    .put(&sequence_def)
+                    .expect("storage shouldn't fail");
+            }
+            Ddl::DropSequence { id, .. } => {
+                self.storage
+                    .sequences
+                    .update_operable(id, false)
+                    .expect("storage shouldn't fail");
+            }
+            Ddl::CreateView {
+                id,
+                name,
+                query,
+                owner,
+                replace,
+            } => {
+                // Replaced view keeps being used with the old query until
+                // the new one is committed.
+                if !replace {
+                    let view_def = ViewDef {
+                        id,
+                        name,
+                        query,
+                        operable: false,
+                        schema_version,
+                        owner,
+                    };
+                    self.storage
+                        .views
+                        .put(&view_def)
+                        .expect("storage shouldn't fail");
+                }
+            }
+            Ddl::DropView { id, .. } => {
+                self.storage
+                    .views
+                    .update_operable(id, false)
+                    .expect("storage shouldn't fail");
+            }
+            Ddl::CreateSchema { id, name, owner } => {
+                let schema_def = SchemaDef {
+                    id,
+                    name,
+                    operable: false,
+                    schema_version,
+                    owner,
+                };
+                self.storage
+                    .schemas
+                    .put(&schema_def)
+                    .expect("storage shouldn't fail");
+            }
+            Ddl::DropSchema { id, .. } => {
+                self.storage
+                    .schemas
+                    .update_operable(id, false)
+                    .expect("storage shouldn't fail");
+            }
+            Ddl::DetachPartition { .. } => {
+                // Partition is detached on commit.
+            }
         }
 
         self.storage
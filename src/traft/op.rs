@@ -1,10 +1,11 @@
 use crate::plugin::PluginIdentifier;
 use crate::schema::{
-    Distribution, IndexOption, PrivilegeDef, RoutineLanguage, RoutineParams, RoutineSecurity,
-    UserDef, ADMIN_ID, GUEST_ID, PICO_SERVICE_ID, PUBLIC_ID, ROLE_REPLICATION_ID, SUPER_ID,
+    ColumnDefault, Distribution, IndexOption, PrivilegeDef, RoutineLanguage, RoutineParams,
+    RoutineSecurity, TableConstraint, TablePartition, TableSequence, UserDef, ADMIN_ID, GUEST_ID,
+    PICO_SERVICE_ID, PUBLIC_ID, ROLE_REPLICATION_ID, SUPER_ID,
 };
 use crate::storage::{self, Catalog};
-use crate::storage::{space_by_name, RoutineId};
+use crate::storage::{space_by_name, RoutineId, SchemaId, SequenceId, ViewId};
 use crate::traft::error::Error as TRaftError;
 use crate::traft::error::ErrorInfo;
 use ::tarantool::auth::AuthDef;
@@ -12,6 +13,7 @@ use ::tarantool::index::{IndexId, Part};
 use ::tarantool::space::{Field, SpaceId};
 use ::tarantool::tlua;
 use ::tarantool::tuple::{ToTupleBuffer, TupleBuffer};
+use sbroad::ir::value::Value as IrValue;
 use serde::{Deserialize, Serialize};
 use tarantool::error::{TarantoolError, TarantoolErrorCode};
 use tarantool::index::IndexType;
@@ -139,6 +141,20 @@ impl std::fmt::Display for Op {
                     "DdlPrepare({schema_version}, RenameTable({table_id}, {old_name} -> {new_name}))"
                 )
             }
+            Self::DdlPrepare {
+                schema_version,
+                ddl:
+                    Ddl::DetachPartition {
+                        table_id,
+                        parent_id,
+                        ..
+                    },
+            } => {
+                write!(
+                    f,
+                    "DdlPrepare({schema_version}, DetachPartition({table_id}, {parent_id}))"
+                )
+            }
             Self::DdlPrepare {
                 schema_version,
                 ddl: Ddl::TruncateTable { id, .. },
@@ -188,6 +204,48 @@ impl std::fmt::Display for Op {
             } => {
                 write!(f, "DdlPrepare({schema_version}, DropProcedure({id}))")
             }
+            Self::DdlPrepare {
+                schema_version,
+                ddl: Ddl::CreateSequence { id, name, .. },
+            } => {
+                write!(
+                    f,
+                    "DdlPrepare({schema_version}, CreateSequence({id}, {name}))"
+                )
+            }
+            Self::DdlPrepare {
+                schema_version,
+                ddl: Ddl::DropSequence { id, .. },
+            } => {
+                write!(f, "DdlPrepare({schema_version}, DropSequence({id}))")
+            }
+            Self::DdlPrepare {
+                schema_version,
+                ddl: Ddl::CreateView { id, name, .. },
+            } => {
+                write!(f, "DdlPrepare({schema_version}, CreateView({id}, {name}))")
+            }
+            Self::DdlPrepare {
+                schema_version,
+                ddl: Ddl::DropView { id, .. },
+            } => {
+                write!(f, "DdlPrepare({schema_version}, DropView({id}))")
+            }
+            Self::DdlPrepare {
+                schema_version,
+                ddl: Ddl::CreateSchema { id, name, .. },
+            } => {
+                write!(
+                    f,
+                    "DdlPrepare({schema_version}, CreateSchema({id}, {name}))"
+                )
+            }
+            Self::DdlPrepare {
+                schema_version,
+                ddl: Ddl::DropSchema { id, .. },
+            } => {
+                write!(f, "DdlPrepare({schema_version}, DropSchema({id}))")
+            }
             Self::DdlPrepare {
                 schema_version,
                 ddl:
@@ -230,6 +288,17 @@ impl std::fmt::Display for Op {
             }) => {
                 write!(f, "ChangeAuth({schema_version}, {user_id}, {initiator})")
             }
+            Self::Acl(Acl::ChangeStatementTimeout {
+                user_id,
+                statement_timeout,
+                schema_version,
+                ..
+            }) => {
+                write!(
+                    f,
+                    "ChangeStatementTimeout({schema_version}, {user_id}, {statement_timeout:?})"
+                )
+            }
             Self::Acl(Acl::DropUser {
                 user_id,
                 initiator,
@@ -585,6 +654,20 @@ impl Dml {
         Ok(res)
     }
 
+    #[inline(always)]
+    pub fn replace_raw(
+        space: impl Into<SpaceId>,
+        tuple: Vec<u8>,
+        initiator: UserId,
+    ) -> tarantool::Result<Self> {
+        let res = Self::Replace {
+            table: space.into(),
+            tuple: TupleBuffer::try_from_vec(tuple)?,
+            initiator,
+        };
+        Ok(res)
+    }
+
     /// Serializes `key` and returns an [`Dml::Update`] in case of success.
     #[inline(always)]
     pub fn update(
@@ -719,6 +802,16 @@ pub enum Ddl {
         distribution: Distribution,
         engine: SpaceEngineType,
         owner: UserId,
+        #[serde(default)]
+        defaults: Vec<ColumnDefault>,
+        #[serde(default)]
+        partition: Option<TablePartition>,
+        #[serde(default)]
+        constraints: Vec<TableConstraint>,
+        /// Sequences of the `SERIAL` and identity columns, created
+        /// and dropped along with the table.
+        #[serde(default)]
+        sequences: Vec<TableSequence>,
     },
     DropTable {
         id: SpaceId,
@@ -776,6 +869,53 @@ pub enum Ddl {
         new_format: Vec<Field>,
         old_format: Vec<Field>,
         initiator_id: UserId,
+        #[serde(default)]
+        new_defaults: Vec<ColumnDefault>,
+        #[serde(default)]
+        old_defaults: Vec<ColumnDefault>,
+        /// Values of the added columns appended to the existing tuples
+        /// before the new format is applied.
+        #[serde(default)]
+        added_values: Vec<IrValue>,
+    },
+    CreateSequence {
+        id: SequenceId,
+        name: String,
+        start: i64,
+        increment: i64,
+        owner: UserId,
+    },
+    DropSequence {
+        id: SequenceId,
+        initiator: UserId,
+    },
+    CreateView {
+        id: ViewId,
+        name: String,
+        query: String,
+        owner: UserId,
+        /// The view already exists and its query is replaced.
+        replace: bool,
+    },
+    DropView {
+        id: ViewId,
+        initiator: UserId,
+    },
+    CreateSchema {
+        id: SchemaId,
+        name: String,
+        owner: UserId,
+    },
+    /// The schema must not contain tables or views.
+    DropSchema {
+        id: SchemaId,
+        initiator: UserId,
+    },
+    /// The partition becomes a standalone table.
+    DetachPartition {
+        table_id: SpaceId,
+        parent_id: SpaceId,
+        initiator: UserId,
     },
 }
 
@@ -839,6 +979,18 @@ pub enum Acl {
         auth: AuthDef,
         initiator: UserId,
         schema_version: u64,
+        /// SCRAM-SHA-256 verifier of the new password, see [`UserDef::scram_verifier`].
+        #[serde(default)]
+        scram_verifier: Option<String>,
+    },
+
+    /// Update the default statement timeout of the user's sessions.
+    ChangeStatementTimeout {
+        user_id: UserId,
+        /// In milliseconds, see [`UserDef::statement_timeout`].
+        statement_timeout: Option<u64>,
+        initiator: UserId,
+        schema_version: u64,
     },
 
     /// Drop a tarantool user and any entities owned by it.
@@ -874,6 +1026,7 @@ impl Acl {
             Self::CreateUser { user_def } => user_def.schema_version,
             Self::RenameUser { schema_version, .. } => *schema_version,
             Self::ChangeAuth { schema_version, .. } => *schema_version,
+            Self::ChangeStatementTimeout { schema_version, .. } => *schema_version,
             Self::DropUser { schema_version, .. } => *schema_version,
             Self::CreateRole { role_def, .. } => role_def.schema_version,
             Self::DropRole { schema_version, .. } => *schema_version,
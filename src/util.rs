@@ -849,7 +849,7 @@ pub fn check_tuple_matches_format(tuple: &[u8], format: &[Field], what_to_fix: &
             FieldType::Double => field.is_f32() || field.is_f64(),
             FieldType::Integer => field.is_i64(),
             FieldType::Boolean => field.is_bool(),
-            FieldType::Varbinary => todo!(),
+            FieldType::Varbinary => field.is_bin(),
             FieldType::Decimal | FieldType::Uuid | FieldType::Datetime | FieldType::Interval => {
                 field.is_ext()
             }
@@ -888,7 +888,9 @@ pub fn check_msgpack_matches_type(
         }
         SbroadType::String => is_str(marker) || is_bin(marker),
         SbroadType::Uuid => is_ext(msgpack, tarantool::ffi::uuid::MP_UUID),
+        SbroadType::Varbinary => is_bin(marker),
         SbroadType::Datetime => is_ext(msgpack, tarantool::ffi::datetime::MP_DATETIME),
+        SbroadType::Interval => is_ext(msgpack, sbroad::ir::value::interval::MP_INTERVAL),
         SbroadType::Array => is_array(marker),
         SbroadType::Map => is_map(marker),
     };
@@ -52,6 +52,18 @@ def test_upgrade_minor(compat_cluster: Cluster):
     inst.start()
     inst.wait_online()
 
+    # System tables and fields added since the previous minor version are
+    # created by the governor once the cluster version is updated.
+    def check_system_catalog_upgraded():
+        cluster_version = inst.call("box.space._pico_property:get", "cluster_version")[1]
+        catalog_version = inst.call("box.space._pico_property:get", "system_catalog_version")[1]
+        assert catalog_version.split(".")[:2] == cluster_version.split(".")[:2]
+
+    Retriable(timeout=10, rps=5).call(check_system_catalog_upgraded)
+
+    data = inst.sql(""" SELECT "name" FROM "_pico_table" WHERE "name" = '_pico_sequence' """)
+    assert data == [["_pico_sequence"]]
+
 
 def test_instances_of_incompatible_versions(cluster: Cluster):
     error_injection = "UPDATE_PICODATA_VERSION"
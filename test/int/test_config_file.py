@@ -107,6 +107,7 @@ instance:
             memtx=dict(
                 memory=dict(value="64M", source="default"),
                 max_tuple_size=dict(value="1M", source="default"),
+                use_mvcc_engine=dict(value=False, source="default"),
             ),
             vinyl=dict(
                 memory=dict(value="128M", source="default"),
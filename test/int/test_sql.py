@@ -1,3 +1,4 @@
+import json
 import pytest
 import re
 import uuid
@@ -477,8 +478,11 @@ def test_read_from_system_tables(cluster: Cluster):
         "raft_wal_size_max",
         "shredding",
         "sql_motion_row_max",
+        "sql_recursion_row_max",
+        "sql_statement_timeout",
         "sql_storage_cache_count_max",
         "sql_storage_cache_size_max",
+        "sql_user_query_max",
         "sql_vdbe_opcode_max",
     ]
 
@@ -1225,6 +1229,23 @@ def test_join_with_global_tbls(cluster: Cluster):
     assert sorted(data, key=lambda e: e[0]) == [[3, 3, 3]]
 
 
+def test_full_join_with_null_rows(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1 = cluster.instances[0]
+
+    ddl = i1.sql("create table t (a int primary key, b int) distributed by (a)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table u (c int primary key, d int) distributed by (c)")
+    assert ddl["row_count"] == 1
+    i1.sql("insert into t values (1, 1), (2, null)")
+    i1.sql("insert into u values (1, 1), (2, null), (3, 5)")
+
+    # The inner row of NULLs has no match and is not confused
+    # with the padding of the outer row without a match.
+    data = i1.sql("select a, d from t full join (select d from u) as q on b = d")
+    assert sorted(data, key=str) == sorted([[1, 1], [2, None], [None, None], [None, 5]], key=str)
+
+
 def test_union_all_on_global_tbls(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
@@ -1919,112 +1940,944 @@ def test_except_on_global_tbls(cluster: Cluster):
     )
     assert dml["row_count"] == 5
 
-    ddl = i1.sql(
-        """
-        create table s (c int, d int not null, primary key (c))
-        using memtx
-        distributed by (c)
-        option (timeout = 3)
-        """
-    )
+    ddl = i1.sql(
+        """
+        create table s (c int, d int not null, primary key (c))
+        using memtx
+        distributed by (c)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    try:
+        data = i1.retriable_sql(
+            """insert into s values (3, 2), (4, 3), (5, 4), (6, 5), (7, 6);""",
+            fatal_predicate=r"Duplicate key exists in unique index",
+        )
+        assert data["row_count"] == 5
+    except TarantoolError as e:
+        assert re.search(r"Duplicate key exists in unique index", str(e))
+
+    data = i1.retriable_sql(
+        """
+        select a from g
+        except
+        select a - 1 from g
+        """,
+        timeout=2,
+    )
+    assert data == [[5]]
+
+    data = i1.retriable_sql(
+        """
+        select a from g
+        except
+        select c from s
+        """,
+        timeout=2,
+    )
+    assert sorted(data, key=lambda x: x) == [[1], [2]]
+
+    data = i1.retriable_sql(
+        """
+        select b from g
+        except
+        select d from s
+        """,
+        timeout=2,
+    )
+    assert sorted(data, key=lambda x: x[0]) == [[1]]
+
+    data = i1.retriable_sql(
+        """
+        select b from g
+        where b = 1 or b = 2
+        except
+        select sum(d) from s
+        where d = 3
+        """,
+        timeout=2,
+    )
+    assert sorted(data, key=lambda x: x[0]) == [[1], [2]]
+
+    data = i1.retriable_sql(
+        """
+        select sum(d) from s
+        where d = 3 or d = 2
+        except
+        select b from g
+        where b = 1 or b = 2
+        """,
+        timeout=2,
+    )
+    assert sorted(data, key=lambda x: x[0]) == [[5]]
+
+    data = i1.retriable_sql(
+        """
+        select c from s
+        except
+        select a from g
+        """,
+        timeout=2,
+    )
+    assert sorted(data, key=lambda x: x[0]) == [[6], [7]]
+
+    data = i1.retriable_sql(
+        """
+        select d from s
+        except
+        select b from g
+        """,
+        timeout=2,
+    )
+    assert sorted(data, key=lambda x: x[0]) == [[6]]
+
+    data = i1.retriable_sql(
+        """
+        select a + 5 from g
+        where a = 1 or a = 2
+        except select * from (
+        select d from s
+        except
+        select b from g
+        )
+        """,
+        timeout=2,
+    )
+    assert sorted(data, key=lambda x: x[0]) == [[7]]
+
+
+def test_intersect_and_except_all(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table g (a int primary key, b int not null)
+        distributed globally
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into g values (1, 1), (2, 1), (3, 2), (4, 3)")
+    assert dml["row_count"] == 4
+
+    ddl = i1.sql(
+        """
+        create table s (c int primary key, d int not null)
+        distributed by (c)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into s values (1, 1), (2, 1), (3, 1), (4, 3), (5, 4)")
+    assert dml["row_count"] == 5
+
+    data = i2.retriable_sql("select b from g intersect select d from s")
+    assert sorted(data) == [[1], [3]]
+
+    data = i2.retriable_sql("select d from s intersect all select b from g")
+    assert sorted(data) == [[1], [1], [3]]
+
+    data = i1.retriable_sql("select d from s except all select b from g")
+    assert sorted(data) == [[1], [4]]
+
+    data = i1.retriable_sql("select b from g except all select d from s")
+    assert sorted(data) == [[2]]
+
+    # INTERSECT binds tighter than UNION ALL.
+    data = i2.retriable_sql(
+        """
+        select b from g
+        union all
+        select d from s
+        intersect
+        select b from g
+        """
+    )
+    assert sorted(data) == [[1], [1], [1], [2], [3], [3]]
+
+
+def test_recursive_cte(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table tree (id int primary key, parent int)
+        distributed globally
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into tree values (1, null), (2, 1), (3, 1), (4, 2), (5, 4), (6, 3)")
+    assert dml["row_count"] == 6
+
+    data = i2.retriable_sql(
+        """
+        with recursive cte (n) as (
+            select 1
+            union all
+            select n + 1 from cte where n < 5
+        )
+        select * from cte
+        """
+    )
+    assert data == [[1], [2], [3], [4], [5]]
+
+    # Descendants of the node 2.
+    data = i2.retriable_sql(
+        """
+        with recursive sub (id) as (
+            select id from tree where id = 2
+            union
+            select tree.id from tree join sub on tree.parent = sub.id
+        )
+        select id from sub
+        """
+    )
+    assert sorted(data) == [[2], [4], [5]]
+
+    # Infinite recursion fails on exceeding sql_recursion_row_max.
+    infinite = """
+        with recursive cte (n) as (
+            select 1
+            union all
+            select n + 1 from cte
+        )
+        select count(*) from cte
+    """
+    with pytest.raises(TarantoolError, match=r"Exceeded maximum number of rows \(100\) in recursive CTE"):
+        i1.sql(infinite + " option (sql_recursion_row_max = 100)")
+    with pytest.raises(TarantoolError, match=r"Exceeded maximum number of rows \(5000\) in recursive CTE"):
+        i1.sql(infinite)
+
+    # Reaching the limit exactly is not an error.
+    query = """
+        with recursive cte (n) as (
+            select 1
+            union all
+            select n + 1 from cte where n < 100
+        )
+        select count(*) from cte
+        option (sql_recursion_row_max = 100)
+    """
+    assert i1.sql(query) == [[100]]
+
+    # The default is set with ALTER SYSTEM.
+    i1.sql("alter system set sql_recursion_row_max = 10")
+    with pytest.raises(TarantoolError, match=r"Exceeded maximum number of rows \(10\) in recursive CTE"):
+        i1.sql(infinite)
+    i1.sql("alter system reset sql_recursion_row_max")
+
+    ddl = i1.sql(
+        """
+        create table s (a int primary key)
+        distributed by (a)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    with pytest.raises(TarantoolError, match="recursive CTE is supported only over global tables"):
+        i1.sql(
+            """
+            with recursive cte (n) as (
+                select a from s
+                union all
+                select n + 1 from cte where n < 5
+            )
+            select * from cte
+            """
+        )
+
+
+def test_dml_returning(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table t (a int primary key, b text not null)
+        distributed by (a)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    data = i1.sql("insert into t values (1, 'a'), (2, 'b'), (3, 'c') returning a, b || '!' as c")
+    assert sorted(data) == [[1, "a!"], [2, "b!"], [3, "c!"]]
+
+    data = i2.retriable_sql("update t set b = 'x' where a > 1 returning *")
+    assert sorted(data) == [[2, "x"], [3, "x"]]
+
+    data = i1.sql("delete from t where a = 2 returning a")
+    assert data == [[2]]
+
+    data = i2.retriable_sql("delete from t returning b")
+    assert sorted(data) == [["a"], ["x"]]
+
+    ddl = i1.sql(
+        """
+        create table g (a int primary key)
+        distributed globally
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    with pytest.raises(
+        TarantoolError,
+        match="RETURNING clause is not supported for global tables",
+    ):
+        i1.sql("insert into g values (1) returning a")
+
+    with pytest.raises(
+        TarantoolError,
+        match="aggregate functions are not allowed in RETURNING clause",
+    ):
+        i1.sql("insert into t values (4, 'd') returning count(a)")
+
+
+def test_insert_on_conflict_do_update(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table counters (id int primary key, name text not null, hits int not null)
+        distributed by (id)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    data = i1.sql("insert into counters values (1, 'a', 1), (2, 'b', 1)")
+    assert data["row_count"] == 2
+
+    upsert = """
+        insert into counters values (?, ?, 1)
+        on conflict (id) do update set hits = counters.hits + excluded.hits, name = excluded.name
+    """
+    data = i1.sql(upsert, 1, "x")
+    assert data["row_count"] == 1
+    data = i2.retriable_sql(upsert, 3, "c")
+    assert data["row_count"] == 1
+    data = i1.sql(
+        """
+        insert into counters values (1, 'y', 5), (2, 'z', 5), (4, 'd', 1)
+        on conflict do update set hits = counters.hits + excluded.hits
+        where excluded.hits > counters.hits + 5
+        """
+    )
+    assert data["row_count"] == 1
+
+    data = i2.retriable_sql("select * from counters")
+    assert sorted(data) == [[1, "x", 2], [2, "b", 1], [3, "c", 1], [4, "d", 1]]
+
+    data = i1.sql(
+        """
+        insert into counters values (2, 'b', 10)
+        on conflict do update set hits = counters.hits + excluded.hits
+        returning hits
+        """
+    )
+    assert data == [[11]]
+
+    with pytest.raises(
+        TarantoolError,
+        match="it is illegal to update primary key column",
+    ):
+        i1.sql("insert into counters values (1, 'a', 1) on conflict do update set id = 5")
+
+    with pytest.raises(
+        TarantoolError,
+        match="ON CONFLICT target must be the primary key of table",
+    ):
+        i1.sql("insert into counters values (1, 'a', 1) on conflict (name) do update set hits = 0")
+
+    # A tuple can't be affected twice by the same command.
+    for values in ["(1, 'a', 1), (1, 'b', 1)", "(5, 'e', 1), (5, 'f', 1)"]:
+        with pytest.raises(
+            TarantoolError,
+            match="ON CONFLICT DO UPDATE command cannot affect row a second time",
+        ):
+            i1.sql(f"insert into counters values {values} on conflict do update set hits = counters.hits + 1")
+    data = i2.retriable_sql("select * from counters")
+    assert sorted(data) == [[1, "x", 2], [2, "b", 11], [3, "c", 1], [4, "d", 1]]
+
+
+def test_column_defaults(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table orders (
+            id int primary key,
+            status text default 'new' not null,
+            amount int default 10 * 10,
+            created datetime default localtimestamp
+        )
+        distributed by (id)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    data = i1.sql("insert into orders (id) values (1), (2)")
+    assert data["row_count"] == 2
+    data = i2.retriable_sql("insert into orders (id, status, amount) values (?, ?, ?)", 3, "paid", None)
+    assert data["row_count"] == 1
+
+    data = i1.sql("select id, status, amount from orders")
+    assert sorted(data) == [[1, "new", 100], [2, "new", 100], [3, "paid", None]]
+    data = i1.sql("select count(*) from orders where created is not null")
+    assert data == [[3]]
+
+    # Defaults are applied to every omitted column, including the ones
+    # skipped by INSERT .. SELECT.
+    data = i1.sql("insert into orders (id, amount) select id + 10, amount from orders where id = 1")
+    assert data["row_count"] == 1
+    data = i1.sql("select status, amount from orders where id = 11")
+    assert data == [["new", 100]]
+
+    # Existing rows get the default along with the new column.
+    ddl = i1.sql("alter table orders add column note text default 'none' option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    data = i1.sql("insert into orders (id) values (4)")
+    assert data["row_count"] == 1
+    data = i1.sql("insert into orders (id, note) values (5, null)")
+    assert data["row_count"] == 1
+    data = i2.retriable_sql("select id, note from orders where id in (1, 4, 5)")
+    assert sorted(data) == [[1, "none"], [4, "none"], [5, None]]
+
+    # NOT NULL columns can be added to a non-empty table only with a default.
+    ddl = i1.sql("alter table orders add column priority int default 1 + 1 not null option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    data = i2.retriable_sql("select distinct priority from orders")
+    assert data == [[2]]
+    with pytest.raises(TarantoolError, match="ddl operation was aborted"):
+        i1.sql("alter table orders add column code int not null option (timeout = 3)")
+    data = i2.retriable_sql("select * from orders where id = 1")
+    assert len(data[0]) == 6
+
+    with pytest.raises(
+        TarantoolError,
+        match="column references in column DEFAULT expression",
+    ):
+        i1.sql("create table bad (a int primary key, b int default a)")
+
+
+def test_generated_columns(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table items (
+            id int primary key,
+            price int not null,
+            qty int,
+            total int generated always as (price * qty) stored
+        )
+        distributed by (id)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    data = i1.sql("insert into items (id, price, qty) values (1, 10, 2), (2, 5, 3)")
+    assert data["row_count"] == 2
+    # The omitted column is NULL in the generated expression.
+    data = i1.sql("insert into items (id, price) values (3, 7)")
+    assert data["row_count"] == 1
+    data = i2.retriable_sql("select id, total from items")
+    assert sorted(data) == [[1, 20], [2, 15], [3, None]]
+
+    # The column is recalculated when the columns it refers to are updated.
+    data = i1.sql("update items set qty = qty + 1 where id = 1")
+    assert data["row_count"] == 1
+    data = i1.sql("update items set price = ?, qty = 1 where id = 3", 4)
+    assert data["row_count"] == 1
+    data = i1.sql("select id, total from items")
+    assert sorted(data) == [[1, 30], [2, 15], [3, 4]]
+
+    data = i1.sql("insert into items (id, price, qty) values (2, 6, 3) on conflict do update set price = excluded.price")
+    assert data["row_count"] == 1
+    data = i1.sql("select total from items where id = 2")
+    assert data == [[18]]
+
+    with pytest.raises(TarantoolError, match="column total is GENERATED ALWAYS"):
+        i1.sql("insert into items (id, price, total) values (4, 1, 1)")
+    with pytest.raises(TarantoolError, match="column total is GENERATED ALWAYS"):
+        i1.sql("update items set total = 1")
+
+    with pytest.raises(TarantoolError, match="column c referenced by generated column b not found"):
+        i1.sql("create table bad (a int primary key, b int generated always as (c + 1))")
+    with pytest.raises(TarantoolError, match="generated column c can't refer to generated column b"):
+        i1.sql(
+            """
+            create table bad (
+                a int primary key,
+                b int generated always as (a + 1),
+                c int generated always as (b + 1)
+            )
+            """
+        )
+    with pytest.raises(TarantoolError, match="subqueries in generated column expression"):
+        i1.sql("create table bad (a int primary key, b int generated always as ((select 1)))")
+    with pytest.raises(TarantoolError, match="generated columns in ALTER TABLE ADD COLUMN"):
+        i1.sql("alter table items add column c int generated always as (price + 1)")
+
+
+def test_sequences(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql("create sequence s start with 10 increment by 5 option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create sequence if not exists s")
+    assert ddl["row_count"] == 0
+    with pytest.raises(TarantoolError, match="sequence s already exists"):
+        i1.sql("create sequence s")
+
+    data = i1.sql("select nextval('s')")
+    assert data == [[10]]
+    data = i1.sql("select nextval('S')")
+    assert data == [[15]]
+
+    # Every instance allocates its own range of values.
+    data = i2.retriable_sql("select nextval('s')")
+    assert data == [[10 + 5 * 100]]
+
+    # SERIAL column is backed by an implicitly created sequence.
+    ddl = i1.sql(
+        """
+        create table orders (id serial primary key, item text)
+        distributed by (id)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    data = i1.sql(""" select "name", "start", "increment" from "_pico_sequence" order by "id" """)
+    assert data == [["s", 10, 5], ["orders_id_seq", 1, 1]]
+
+    data = i1.sql("insert into orders (item) values ('a'), ('b')")
+    assert data["row_count"] == 2
+    data = i1.sql("select id, item from orders")
+    assert sorted(data) == [[1, "a"], [2, "b"]]
+
+    with pytest.raises(TarantoolError, match="both default and sequence specified for column id"):
+        i1.sql("create table bad (id serial default 1 primary key)")
+
+    # Implicit sequence name must be free, the table is not created otherwise.
+    ddl = i1.sql("create sequence bad_id_seq")
+    assert ddl["row_count"] == 1
+    with pytest.raises(TarantoolError, match="sequence bad_id_seq already exists"):
+        i1.sql("create table bad (id serial primary key)")
+    data = i1.sql(""" select "name" from "_pico_table" where "name" = 'bad' """)
+    assert data == []
+    ddl = i1.sql("drop sequence bad_id_seq")
+    assert ddl["row_count"] == 1
+
+    # Implicit sequence belongs to the table.
+    with pytest.raises(TarantoolError, match="cannot drop sequence orders_id_seq because table orders requires it"):
+        i1.sql("drop sequence orders_id_seq")
+
+    # GENERATED ALWAYS column can't be specified explicitly.
+    ddl = i1.sql("create table events (id int generated always as identity primary key, name text)")
+    assert ddl["row_count"] == 1
+    data = i1.sql("insert into events (name) values ('a')")
+    assert data["row_count"] == 1
+    with pytest.raises(TarantoolError, match="column id is GENERATED ALWAYS"):
+        i1.sql("insert into events (id, name) values (5, 'b')")
+    with pytest.raises(TarantoolError, match="column id is GENERATED ALWAYS"):
+        i1.sql("insert into events values (5, 'b')")
+    with pytest.raises(TarantoolError, match="column id is GENERATED ALWAYS"):
+        i1.sql("update events set id = 5")
+    data = i1.sql("select id, name from events")
+    assert data == [[1, "a"]]
+
+    # Sequence of the table is available to those who can write to the table.
+    acl = i1.sql("create user alice with password 'P@ssw0rd' using md5")
+    assert acl["row_count"] == 1
+    with pytest.raises(TarantoolError, match="Write access to space 'orders' is denied for user 'alice'"):
+        i1.sql("select nextval('orders_id_seq')", user="alice", password="P@ssw0rd")
+    with pytest.raises(TarantoolError, match="Usage access to sequence 's' is denied for user 'alice'"):
+        i1.sql("select nextval('s')", user="alice", password="P@ssw0rd")
+    acl = i1.sql("grant write on table orders to alice")
+    assert acl["row_count"] == 1
+    data = i1.sql("select nextval('orders_id_seq')", user="alice", password="P@ssw0rd")
+    assert data == [[3]]
+
+    # Implicit sequences are dropped along with the table.
+    ddl = i1.sql("drop table orders option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("drop table events option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    data = i1.sql(""" select "name" from "_pico_sequence" """)
+    assert data == [["s"]]
+
+    ddl = i1.sql("drop sequence s option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    with pytest.raises(TarantoolError, match="sequence s does not exist"):
+        i1.sql("select nextval('s')")
+
+
+def test_views(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table t (a int primary key, b int)
+        distributed by (a)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    data = i1.sql("insert into t values (1, 10), (2, 20), (3, 30)")
+    assert data["row_count"] == 3
+
+    ddl = i1.sql("create view v as select a, b from t where b > 10")
+    assert ddl["row_count"] == 1
+    with pytest.raises(TarantoolError, match="view v already exists"):
+        i1.sql("create view v as select a from t")
+    with pytest.raises(TarantoolError, match="table t already exists"):
+        i1.sql("create view t as select a from t")
+    with pytest.raises(TarantoolError, match="view v already exists"):
+        i1.sql("create table v (a int primary key) distributed by (a)")
+
+    data = i1.sql("select a from v order by a")
+    assert data == [[2], [3]]
+    data = i2.retriable_sql("select v.b from v join t on v.a = t.a where t.a = 3")
+    assert data == [[30]]
+
+    # Views referencing other views are expanded recursively.
+    ddl = i1.sql("create view vv as select sum(b) from v")
+    assert ddl["row_count"] == 1
+    data = i1.sql("select * from vv")
+    assert data == [[50]]
+
+    ddl = i1.sql("create or replace view v as select a, b from t where b < 30")
+    assert ddl["row_count"] == 1
+    data = i1.sql("select * from vv")
+    assert data == [[30]]
+
+    # Only the read privilege on the view itself is required.
+    user = "view_reader"
+    password = "PaSSW0RD"
+    acl = i1.sql(f"create user {user} with password '{password}' using chap-sha1")
+    assert acl["row_count"] == 1
+    with pytest.raises(
+        TarantoolError,
+        match=rf"Read access to view 'v' is denied for user '{user}'",
+    ):
+        i1.sql("select * from v", user=user, password=password)
+    acl = i1.sql(f"grant read on table v to {user}", sudo=True)
+    assert acl["row_count"] == 1
+    data = i1.sql("select a from v order by a", user=user, password=password)
+    assert data == [[1], [2]]
+    with pytest.raises(
+        TarantoolError,
+        match=rf"Read access to space 't' is denied for user '{user}'",
+    ):
+        i1.sql("select * from t", user=user, password=password)
+    with pytest.raises(TarantoolError, match="Drop access to view 'v' is denied"):
+        i1.sql("drop view v", user=user, password=password)
+
+    # The tables are read with the privileges of the view owner,
+    # which are checked every time the view is queried.
+    owner = "view_owner"
+    acl = i1.sql(f"create user {owner} with password '{password}' using chap-sha1")
+    assert acl["row_count"] == 1
+    i1.sql(f"grant create table to {owner}", sudo=True)
+    i1.sql(f"grant read on table t to {owner}", sudo=True)
+    ddl = i1.sql("create view v_owned as select a from t", user=owner, password=password)
+    assert ddl["row_count"] == 1
+    i1.sql(f"grant read on table v_owned to {user}", user=owner, password=password)
+    data = i1.sql("select a from v_owned order by a", user=user, password=password)
+    assert data == [[1], [2], [3]]
+    i1.sql(f"revoke read on table t from {owner}", sudo=True)
+    with pytest.raises(
+        TarantoolError,
+        match=rf"Read access to space 't' is denied for user '{owner}'",
+    ):
+        i1.sql("select a from v_owned", user=user, password=password)
+    ddl = i1.sql("drop view v_owned")
+    assert ddl["row_count"] == 1
+
+    # A view can't be replaced by a query referencing itself endlessly.
+    ddl = i1.sql("create or replace view vv as select * from vv")
+    assert ddl["row_count"] == 1
+    with pytest.raises(TarantoolError, match="views are nested deeper than 32 levels"):
+        i1.sql("select * from vv")
+
+    ddl = i1.sql("drop view vv option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("drop view v option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("drop view if exists v")
+    assert ddl["row_count"] == 0
+    with pytest.raises(TarantoolError, match="view v does not exist"):
+        i1.sql("drop view v")
+    data = i1.sql(""" select count(*) from "_pico_privilege" where "object_type" = 'view' """)
+    assert data == [[0]]
+
+
+def test_partitions(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table t (a int, b int, primary key (a, b))
+        distributed by (a)
+        partition by range (b)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table t_low partition of t for values from (minvalue) to (10)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table t_high partition of t for values from (10) to (20)")
+    assert ddl["row_count"] == 1
+    with pytest.raises(TarantoolError, match="partition t_mid would overlap partition t_high"):
+        i1.sql("create table t_mid partition of t for values from (15) to (30)")
+    with pytest.raises(TarantoolError, match="invalid bound specification for a range partition"):
+        i1.sql("create table t_list partition of t for values in (1, 2)")
+
+    # Tuples are routed to the matching partitions.
+    data = i1.sql("insert into t values (1, 5), (2, 15), (3, 9)")
+    assert data["row_count"] == 3
+    with pytest.raises(TarantoolError, match="partition for the partition key value 25 not found"):
+        i1.sql("insert into t values (4, 25)")
+    ddl = i1.sql("create table t_rest partition of t default")
+    assert ddl["row_count"] == 1
+    data = i1.sql("insert into t values (4, 25)")
+    assert data["row_count"] == 1
+
+    data = i1.sql("select a from t_low order by a")
+    assert data == [[1], [3]]
+    data = i2.retriable_sql("select * from t order by a")
+    assert data == [[1, 5], [2, 15], [3, 9], [4, 25]]
+    data = i1.sql("select a from t where b >= 10 and b < 20")
+    assert data == [[2]]
+    data = i1.sql("select a from t where b in (?, ?) order by a", 9, 25)
+    assert data == [[3], [4]]
+
+    data = i1.sql("update t set a = 5 where b = 5")
+    assert data["row_count"] == 1
+    data = i1.sql("delete from t where b = 15")
+    assert data["row_count"] == 1
+    data = i1.sql("select * from t order by a")
+    assert data == [[3, 9], [4, 25], [5, 5]]
+
+    with pytest.raises(TarantoolError, match="TRUNCATE of a partitioned table"):
+        i1.sql("truncate t")
+
+    # A detached partition becomes a standalone table.
+    ddl = i1.sql("alter table t detach partition t_rest")
+    assert ddl["row_count"] == 1
+    data = i1.sql("select * from t order by a")
+    assert data == [[3, 9], [5, 5]]
+    data = i1.sql("select * from t_rest")
+    assert data == [[4, 25]]
+    with pytest.raises(TarantoolError, match="table t_rest is not a partition of table t"):
+        i1.sql("alter table t detach partition t_rest")
+
+    ddl = i1.sql("drop table t_high")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("drop table t option (timeout = 3)")
+    assert ddl["row_count"] == 1
+    data = i1.sql(""" select "name" from "_pico_table" where "name" like 't%' """)
+    assert data == [["t_rest"]]
+
+
+def test_hash_partitions(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table h (a int, b int, primary key (a, b))
+        distributed by (a)
+        partition by hash (b)
+        option (timeout = 3)
+        """
+    )
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table h_0 partition of h for values with (modulus 2, remainder 0)")
+    assert ddl["row_count"] == 1
+    with pytest.raises(TarantoolError, match="partition h_2 would overlap partition h_0"):
+        i1.sql("create table h_2 partition of h for values with (modulus 4, remainder 2)")
+    with pytest.raises(TarantoolError, match="remainder for hash partition must be less than modulus"):
+        i1.sql("create table h_5 partition of h for values with (modulus 4, remainder 5)")
+    with pytest.raises(TarantoolError, match="a hash-partitioned table may not have a default partition"):
+        i1.sql("create table h_rest partition of h default")
+    ddl = i1.sql("create table h_1 partition of h for values with (modulus 4, remainder 1)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table h_3 partition of h for values with (modulus 4, remainder 3)")
+    assert ddl["row_count"] == 1
+
+    # Every tuple is routed to exactly one partition.
+    data = i1.sql("insert into h values (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)")
+    assert data["row_count"] == 5
+    counts = [i1.sql(f"select count(*) from {p}")[0][0] for p in ["h_0", "h_1", "h_3"]]
+    assert sum(counts) == 5
+
+    data = i2.retriable_sql("select * from h order by a")
+    assert data == [[1, 1], [2, 2], [3, 3], [4, 4], [5, 5]]
+    data = i1.sql("select a from h where b = 3")
+    assert data == [[3]]
+    data = i1.sql("select a from h where b in (?, ?) order by a", 2, 5)
+    assert data == [[2], [5]]
+
+    ddl = i1.sql("drop table h option (timeout = 3)")
+    assert ddl["row_count"] == 1
+
+
+def test_schemas(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql("create schema s")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create schema if not exists s")
+    assert ddl["row_count"] == 0
+    with pytest.raises(TarantoolError, match="schema s already exists"):
+        i1.sql("create schema s")
+    with pytest.raises(TarantoolError, match="schema public already exists"):
+        i1.sql("create schema public")
+    with pytest.raises(TarantoolError, match="schema missing does not exist"):
+        i1.sql("create table missing.t (a int primary key) distributed by (a)")
+
+    # Tables of different schemas may have the same names.
+    ddl = i1.sql("create table t (a int primary key) distributed by (a)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table s.t (a int primary key, b int) distributed by (a)")
+    assert ddl["row_count"] == 1
+    data = i1.sql("insert into t values (1)")
+    assert data["row_count"] == 1
+    data = i1.sql("insert into s.t values (2, 20), (3, 30)")
+    assert data["row_count"] == 2
+    data = i2.retriable_sql("select a from s.t order by a")
+    assert data == [[2], [3]]
+    data = i1.sql("select s.a from public.t join s.t as s on true order by s.a")
+    assert data == [[2], [3]]
+    data = i1.sql(""" select "name" from "_pico_schema" """)
+    assert data == [["s"]]
+
+    # Unqualified names are looked up in the schemas of the search path.
+    with i1.connect(timeout=5) as conn:
+        conn.sql("set search_path to s, public")
+        data = conn.sql("select a, b from t order by a")
+        assert data["rows"] == [[2, 20], [3, 30]]
+        conn.sql("create view v as select a from t where b > 20")
+        data = conn.sql("select * from v")
+        assert data["rows"] == [[3]]
+        conn.sql("set search_path to default")
+        data = conn.sql("select * from t")
+        assert data["rows"] == [[1]]
+    # The view keeps the names resolved when it was created.
+    data = i1.sql("select * from s.v")
+    assert data == [[3]]
+
+    user = "schema_user"
+    password = "PaSSW0RD"
+    acl = i1.sql(f"create user {user} with password '{password}' using chap-sha1")
+    assert acl["row_count"] == 1
+    acl = i1.sql(f"grant read on table s.t to {user}", sudo=True)
+    assert acl["row_count"] == 1
+    with pytest.raises(
+        TarantoolError,
+        match=rf"Usage access to schema 's' is denied for user '{user}'",
+    ):
+        i1.sql("select * from s.t", user=user, password=password)
+    acl = i1.sql(f"grant usage on schema s to {user}", sudo=True)
+    assert acl["row_count"] == 1
+    data = i1.sql("select a from s.t order by a", user=user, password=password)
+    assert data == [[2], [3]]
+    with pytest.raises(
+        TarantoolError,
+        match=rf"Create access to schema 'u' is denied for user '{user}'",
+    ):
+        i1.sql("create schema u", user=user, password=password)
+
+    with pytest.raises(TarantoolError, match="moving table to schema"):
+        i1.sql("alter table s.t rename to public.t2")
+    with pytest.raises(TarantoolError, match="schema s is not empty"):
+        i1.sql("drop schema s")
+    ddl = i1.sql("drop schema s cascade option (timeout = 3)")
     assert ddl["row_count"] == 1
+    data = i1.sql(""" select "name" from "_pico_table" where "name" like 's.%' """)
+    assert data == []
+    data = i1.sql(""" select count(*) from "_pico_view" """)
+    assert data == [[0]]
+    data = i1.sql(""" select count(*) from "_pico_privilege" where "object_type" = 'schema' """)
+    assert data == [[0]]
+    ddl = i1.sql("drop schema if exists s")
+    assert ddl["row_count"] == 0
+    with pytest.raises(TarantoolError, match="dropping schema public"):
+        i1.sql("drop schema public")
 
-    try:
-        data = i1.retriable_sql(
-            """insert into s values (3, 2), (4, 3), (5, 4), (6, 5), (7, 6);""",
-            fatal_predicate=r"Duplicate key exists in unique index",
-        )
-        assert data["row_count"] == 5
-    except TarantoolError as e:
-        assert re.search(r"Duplicate key exists in unique index", str(e))
-
-    data = i1.retriable_sql(
-        """
-        select a from g
-        except
-        select a - 1 from g
-        """,
-        timeout=2,
-    )
-    assert data == [[5]]
 
-    data = i1.retriable_sql(
-        """
-        select a from g
-        except
-        select c from s
-        """,
-        timeout=2,
-    )
-    assert sorted(data, key=lambda x: x) == [[1], [2]]
+def test_analyze(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
 
-    data = i1.retriable_sql(
-        """
-        select b from g
-        except
-        select d from s
-        """,
-        timeout=2,
-    )
-    assert sorted(data, key=lambda x: x[0]) == [[1]]
+    ddl = i1.sql("create table big (a int primary key, b text) distributed by (a)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table small (a int primary key) distributed by (a)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table g (a int primary key) distributed globally")
+    assert ddl["row_count"] == 1
+    values = ", ".join(f"({i}, 'b{i % 10}')" for i in range(100))
+    data = i1.sql(f"insert into big values {values}")
+    assert data["row_count"] == 100
+    data = i1.sql("insert into small values (1), (2)")
+    assert data["row_count"] == 2
+    data = i1.sql("insert into g values (1), (2), (3)")
+    assert data["row_count"] == 3
 
-    data = i1.retriable_sql(
-        """
-        select b from g
-        where b = 1 or b = 2
-        except
-        select sum(d) from s
-        where d = 3
-        """,
-        timeout=2,
-    )
-    assert sorted(data, key=lambda x: x[0]) == [[1], [2]]
+    ddl = i1.sql("analyze big")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("analyze option (timeout = 10)")
+    assert ddl["row_count"] == 3
+    query = """
+        select t."name", s."rows_number" from "_pico_table_stats" s
+        join "_pico_table" t on s."table_id" = t."id"
+        order by t."name"
+    """
+    data = i2.retriable_sql(query)
+    assert data == [["big", 100], ["g", 3], ["small", 2]]
 
-    data = i1.retriable_sql(
-        """
-        select sum(d) from s
-        where d = 3 or d = 2
-        except
-        select b from g
-        where b = 1 or b = 2
-        """,
-        timeout=2,
-    )
-    assert sorted(data, key=lambda x: x[0]) == [[5]]
+    # The smaller outer child of the inner join is moved instead of the inner one.
+    data = i1.sql("select count(*) from small join big on big.b = 'b1' and small.a = 1")
+    assert data == [[10]]
 
-    data = i1.retriable_sql(
-        """
-        select c from s
-        except
-        select a from g
-        """,
-        timeout=2,
-    )
-    assert sorted(data, key=lambda x: x[0]) == [[6], [7]]
+    with pytest.raises(TarantoolError, match='table with name "missing" not found'):
+        i1.sql("analyze missing")
 
-    data = i1.retriable_sql(
-        """
-        select d from s
-        except
-        select b from g
-        """,
-        timeout=2,
-    )
-    assert sorted(data, key=lambda x: x[0]) == [[6]]
+    user = "analyze_user"
+    password = "PaSSW0RD"
+    acl = i1.sql(f"create user {user} with password '{password}' using chap-sha1")
+    assert acl["row_count"] == 1
+    with pytest.raises(
+        TarantoolError,
+        match=rf"Analyze access to table 'big' is denied for user '{user}'",
+    ):
+        i1.sql("analyze big", user=user, password=password)
+    # Only the owned tables are analyzed.
+    ddl = i1.sql("analyze", user=user, password=password)
+    assert ddl["row_count"] == 0
 
-    data = i1.retriable_sql(
-        """
-        select a + 5 from g
-        where a = 1 or a = 2
-        except select * from (
-        select d from s
-        except
-        select b from g
-        )
-        """,
-        timeout=2,
-    )
-    assert sorted(data, key=lambda x: x[0]) == [[7]]
+    # Statistics are dropped along with the table.
+    ddl = i1.sql("drop table big")
+    assert ddl["row_count"] == 1
+    data = i1.sql(query)
+    assert data == [["g", 3], ["small", 2]]
 
 
 def test_except_parsing_panic_gl_1339(cluster: Cluster):
@@ -5325,6 +6178,34 @@ def test_limit(cluster: Cluster):
     # Verify the order.
     assert data == [[1], [2], [3], [4], [5]]
 
+    # LIMIT + OFFSET + ORDER BY.
+    data = i3.retriable_sql(
+        """
+        SELECT "id" FROM "t"
+        ORDER BY "id"
+        LIMIT 2 OFFSET 3
+        """
+    )
+    assert data == [[4], [5]]
+
+    # FETCH FIRST is the same as LIMIT.
+    data = i1.retriable_sql(
+        """
+        SELECT "id" FROM "t"
+        ORDER BY "id"
+        OFFSET 5 ROWS FETCH FIRST 10 ROWS ONLY
+        """
+    )
+    assert data == [[6], [7]]
+
+    # OFFSET without LIMIT.
+    data = i2.retriable_sql(""" SELECT * FROM "t" OFFSET 4 """)
+    assert len(data) == 3
+
+    # LIMIT + OFFSET over a sharded table.
+    data = i3.retriable_sql(""" SELECT * FROM "t" LIMIT 3 OFFSET 5 """)
+    assert len(data) == 2
+
     # LIMIT + COUNT.
     data = i3.retriable_sql(
         """
@@ -6299,6 +7180,323 @@ buckets = any"""
     assert "\n".join(lines) == expected_explain
 
 
+def test_explain_analyze(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    cluster.wait_until_instance_has_this_many_active_buckets(i1, 1500)
+    cluster.wait_until_instance_has_this_many_active_buckets(i2, 1500)
+
+    ddl = i1.sql("create table t (a int primary key, b int)")
+    assert ddl["row_count"] == 1
+    ddl = i1.sql("create table g (a int primary key, b int) distributed globally")
+    assert ddl["row_count"] == 1
+
+    # EXPLAIN ANALYZE of DML executes the statement.
+    lines = i1.sql("explain analyze insert into t values (1, 1), (2, 2), (3, 3)")
+    assert lines[0].startswith('insert "t" on conflict: fail (actual rows=3')
+    assert "execution statistics:" in lines
+    assert i1.sql("select count(*) from t") == [[3]]
+
+    lines = i1.sql("explain analyze select a from t")
+    assert lines[0].startswith('projection ("t"."a"::integer -> "a") (actual rows=3')
+    assert any(line.strip().startswith("final dispatch: rows=3") for line in lines)
+    replicasets = [line for line in lines if line.strip().startswith("replicaset")]
+    assert len(replicasets) == 2
+    for line in replicasets:
+        assert "storage time=" in line
+        assert "vdbe opcodes=" in line
+
+    # Motion materialization is reported separately.
+    lines = i1.sql("explain analyze select b from t where a in (select b from t)")
+    motions = [line for line in lines if line.strip().startswith("motion")]
+    assert len(motions) == 2
+    assert "(actual rows=3" in motions[0]
+
+    # Explain without ANALYZE is not executed.
+    lines = i1.sql("explain (format json) select a from t where a = 1")
+    assert len(lines) == 1
+    explain = json.loads(lines[0])
+    assert explain["plan"]["node"] == 'projection ("t"."a"::integer -> "a")'
+    assert explain["buckets"]["exact"]
+    assert "execution" not in explain
+
+    lines = i1.sql("explain (analyze, format json) select a from t")
+    explain = json.loads(lines[0])
+    assert explain["plan"]["actual"]["rows"] == 3
+    assert explain["execution"]["rows"] == 3
+    assert explain["execution"]["dispatches"][-1]["node"] == "final dispatch"
+
+    with pytest.raises(
+        TarantoolError,
+        match="unsupported query: EXPLAIN ANALYZE for DML on global tables",
+    ):
+        i1.sql("explain analyze insert into g values (1, 1)")
+    assert i1.sql("select count(*) from g") == [[0]]
+
+
+def test_create_table_as(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql("create table t (a int primary key, b text, c double)")
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into t values (1, 'one', 1.5), (2, 'two', 2.5), (3, 'three', null)")
+    assert dml["row_count"] == 3
+
+    # Columns and their types are derived from the query.
+    dml = i1.sql("create table t2 as select b, a, c from t where a > 1")
+    assert dml["row_count"] == 2
+    data = i2.sql("select * from t2 order by a", strip_metadata=False)
+    assert data == {
+        "metadata": [
+            {"name": "b", "type": "string"},
+            {"name": "a", "type": "integer"},
+            {"name": "c", "type": "double"},
+        ],
+        "rows": [["two", 2, 2.5], ["three", 3, None]],
+    }
+    # The first column is the primary key by default.
+    with pytest.raises(TarantoolError, match="Duplicate key exists"):
+        i1.sql("insert into t2 values ('two', 4, null)")
+
+    # Explicit primary key and distribution.
+    dml = i1.sql(
+        """
+        create table g (primary key (a)) distributed globally
+        as select a, b || '!' as s from t
+        """
+    )
+    assert dml["row_count"] == 3
+    data = i2.sql("select * from g order by a")
+    assert data == [[1, "one!"], [2, "two!"], [3, "three!"]]
+
+    # Existing table is not refilled with IF NOT EXISTS.
+    dml = i1.sql("create table if not exists g as select a, b from t")
+    assert dml["row_count"] == 0
+    with pytest.raises(TarantoolError, match="table g already exists"):
+        i1.sql("create table g as select a, b from t")
+
+    with pytest.raises(TarantoolError, match="can't derive type of column n"):
+        i1.sql("create table t3 as select a, null as n from t")
+
+    # The table is dropped if it can't be filled: `c` is the primary
+    # key by default and contains null.
+    with pytest.raises(TarantoolError):
+        i1.sql("create table t3 as select c, a from t")
+    for i in cluster.instances:
+        assert i.eval("return box.space.t3") is None
+    # Neither is the table left under its temporary name.
+    tables = i1.sql("select name from _pico_table where name like 't3%'")
+    assert tables == []
+
+    # Parameters of the query are passed through.
+    dml = i1.sql("create table t4 as select a, b from t where a >= ?", 2)
+    assert dml["row_count"] == 2
+    data = i2.sql("select * from t4 order by a")
+    assert data == [[2, "two"], [3, "three"]]
+
+    # SELECT INTO is a shorthand for CREATE TABLE AS.
+    dml = i1.sql("select a, c into table t5 from t where c is not null")
+    assert dml["row_count"] == 2
+    data = i2.sql("select * from t5 order by a")
+    assert data == [[1, 1.5], [2, 2.5]]
+    with pytest.raises(TarantoolError, match="table t5 already exists"):
+        i1.sql("select a into t5 from t")
+
+
+def test_table_constraints(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table warehouse (id int primary key, name text)
+        distributed globally
+        """
+    )
+    assert ddl["row_count"] == 1
+    dml = i1.sql("insert into warehouse values (1, 'north'), (2, 'south')")
+    assert dml["row_count"] == 2
+
+    ddl = i1.sql(
+        """
+        create table orders (
+            id int primary key check (id < 1000),
+            amount int not null,
+            warehouse_id int references warehouse,
+            constraint positive_amount check (amount > 0)
+        ) distributed by (id)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    dml = i1.sql("insert into orders values (1, 10, 1), (2, 20, null)")
+    assert dml["row_count"] == 2
+
+    with pytest.raises(TarantoolError, match="Check constraint 'positive_amount' failed"):
+        i1.sql("insert into orders values (3, 0, 1)")
+    with pytest.raises(TarantoolError, match="Check constraint 'orders_id_check' failed"):
+        i2.sql("insert into orders values (1000, 1, 1)")
+    with pytest.raises(TarantoolError, match="Check constraint 'orders_warehouse_id_fkey' failed"):
+        i1.sql("insert into orders values (3, 30, 3)")
+    with pytest.raises(TarantoolError, match="Check constraint 'orders_warehouse_id_fkey' failed"):
+        i2.sql("update orders set warehouse_id = 5 where id = 1")
+    assert i2.sql("select * from orders order by id") == [[1, 10, 1], [2, 20, None]]
+
+    # Referenced rows of the global table can't be deleted or get another key.
+    referenced = "cannot delete or update a row of table warehouse: foreign key orders_warehouse_id_fkey"
+    with pytest.raises(TarantoolError, match=referenced):
+        i2.sql("delete from warehouse where id = 1")
+    with pytest.raises(TarantoolError, match=referenced):
+        i1.sql("update warehouse set id = 3 where id = 1")
+    dml = i1.sql("update warehouse set name = 'east' where id = 1")
+    assert dml["row_count"] == 1
+    dml = i2.sql("delete from warehouse where id = 2")
+    assert dml["row_count"] == 1
+    assert i1.sql("select * from warehouse") == [[1, "east"]]
+
+    with pytest.raises(TarantoolError, match="foreign key orders_warehouse_id_fkey of table orders references it"):
+        i1.sql("drop table warehouse")
+
+    with pytest.raises(TarantoolError, match="references table orders which is not global"):
+        i1.sql("create table t (a int primary key references orders)")
+
+    # Constraint functions are dropped together with the table.
+    ddl = i1.sql("drop table orders")
+    assert ddl["row_count"] == 1
+    for i in cluster.instances:
+        funcs = i.eval("return box.space._func.index.name:select('pico_constraint_', {iterator = 'GE'})")
+        assert not [f for f in funcs if f[2].startswith("pico_constraint_")]
+    ddl = i1.sql("drop table warehouse")
+    assert ddl["row_count"] == 1
+
+
+def test_extended_aggregates(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table scores (id int primary key, name text, score int, passed bool)
+        distributed by (id)
+        """
+    )
+    assert ddl["row_count"] == 1
+    dml = i1.sql(
+        """
+        insert into scores values
+            (1, 'a', 10, true), (2, 'b', 20, true), (3, 'c', 30, false), (4, 'd', 40, true)
+        """
+    )
+    assert dml["row_count"] == 4
+
+    data = i1.sql("select string_agg(name, ',' order by score desc) from scores")
+    assert data == [["d,c,b,a"]]
+    data = i2.sql("select array_agg(id order by score) from scores")
+    assert data == [[[1, 2, 3, 4]]]
+    data = i1.sql("select bool_and(passed), bool_or(passed), every(passed) from scores")
+    assert data == [[False, True, False]]
+    data = i2.sql("select var_samp(score), var_pop(score), stddev_pop(score) from scores")
+    assert data == [[pytest.approx(166.666666), 125.0, pytest.approx(11.180339)]]
+    data = i1.sql("select array_agg(score) from scores")
+    assert sorted(data[0][0]) == [10, 20, 30, 40]
+    data = i2.sql(
+        """
+        select passed, string_agg(name, '-' order by score desc), array_agg(id order by id)
+        from scores group by passed order by passed
+        """
+    )
+    assert data == [[False, "c", [3]], [True, "d-b-a", [1, 2, 4]]]
+
+    # Variance is merged from per-storage means, so large values
+    # don't lose precision.
+    dml = i1.sql(
+        """
+        insert into scores values
+            (5, 'e', 1000000004, true), (6, 'f', 1000000007, true),
+            (7, 'g', 1000000013, true), (8, 'h', 1000000016, true)
+        """
+    )
+    assert dml["row_count"] == 4
+    data = i2.sql("select var_samp(score) from scores where id > 4")
+    assert data == [[pytest.approx(30.0)]]
+    dml = i1.sql("delete from scores where id > 4")
+    assert dml["row_count"] == 4
+    data = i1.sql(
+        """
+        select percentile_cont(0.5) within group (order by score),
+               percentile_disc(0.5) within group (order by score)
+        from scores
+        """
+    )
+    assert data == [[25.0, 20]]
+    data = i2.sql("select count(distinct name, passed) from scores")
+    assert data == [[4]]
+
+    with pytest.raises(TarantoolError, match="requires WITHIN GROUP"):
+        i1.sql("select percentile_cont(0.5) from scores")
+    with pytest.raises(TarantoolError, match="percentile value 2 is not between 0 and 1"):
+        i1.sql("select percentile_cont(2) within group (order by score) from scores")
+
+    ddl = i1.sql("drop table scores")
+    assert ddl["row_count"] == 1
+
+
+def test_json_operators(cluster: Cluster):
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql("create table docs (id int primary key, doc json) distributed by (id)")
+    assert ddl["row_count"] == 1
+    docs = [
+        (1, {"name": "a", "tags": ["x", "y"], "info": {"age": 10}}),
+        (2, {"name": "b", "tags": [], "info": {"age": 20}}),
+    ]
+    for doc_id, doc in docs:
+        dml = i1.sql("insert into docs values (?, ?)", doc_id, doc)
+        assert dml["row_count"] == 1
+
+    data = i1.sql("select doc ->> 'name' || '!' from docs order by id")
+    assert data == [["a!"], ["b!"]]
+    data = i2.sql("select id from docs where (doc #>> '{info,age}')::int > 15")
+    assert data == [[2]]
+    data = i1.sql("select doc -> 'tags' ->> 0, json_array_length(doc -> 'tags') from docs order by id")
+    assert data == [["x", 2], [None, 0]]
+    data = i2.sql("""select id from docs where doc @> '{"info": {"age": 10}}'""")
+    assert data == [[1]]
+    data = i1.sql(
+        """
+        select json_typeof(doc -> 'info'), json_extract_path_text(doc, 'info', 'age')
+        from docs where id = 1
+        """
+    )
+    assert data == [["object", "10"]]
+    data = i2.sql("select json_build_object('id', id, 'name', doc ->> 'name') from docs where id = 2")
+    assert data == [[{"id": 2, "name": "b"}]]
+    data = i1.sql("select json_build_array(1, 'a')")
+    assert data == [[[1, "a"]]]
+    data = i1.sql("select json_build_object(), json_build_array()")
+    assert data == [[{}, []]]
+
+    data = i2.sql("select id, jsonb_array_elements(doc -> 'tags') as tag from docs")
+    assert sorted(data) == [[1, "x"], [1, "y"]]
+    data = i1.sql("""select json_array_elements('[1, {"a": 2}, [3]]')""")
+    assert data == [[1], ['{"a":2}'], ["[3]"]]
+
+    with pytest.raises(TarantoolError, match="even number of arguments"):
+        i1.sql("select json_build_object('a') from docs")
+    with pytest.raises(TarantoolError, match="cannot extract elements from a non-array"):
+        i1.sql("select json_array_elements(doc) from docs")
+    with pytest.raises(TarantoolError, match="set-returning functions are allowed only"):
+        i1.sql("select json_array_elements(doc -> 'tags') from docs order by id")
+    with pytest.raises(TarantoolError, match="could not resolve operator overload"):
+        i1.sql("select id from docs where doc -> 'name' = 1")
+
+    ddl = i1.sql("drop table docs")
+    assert ddl["row_count"] == 1
+
+
 def test_extreme_integer_values(cluster: Cluster):
     cluster.deploy(instance_count=1)
     i1 = cluster.instances[0]
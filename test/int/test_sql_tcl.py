@@ -1,9 +1,50 @@
+import pytest
+
 from conftest import (
     Cluster,
+    TarantoolError,
 )
 
 
+# Interactive transactions require memtx MVCC engine.
+MVCC_CONFIG = """
+cluster:
+    name: test
+    tier:
+        default:
+instance:
+    memtx:
+        use_mvcc_engine: true
+"""
+
+
+def test_transaction_without_mvcc(cluster: Cluster):
+    cluster.deploy(instance_count=1)
+    i1 = cluster.instances[0]
+
+    i1.sql(
+        """
+        create table t (a int not null, primary key (a))
+        using memtx distributed by (a)
+        """
+    )
+
+    # Statements are autocommitted, transaction control is a no-op.
+    with i1.connect(timeout=5) as conn:
+        conn.sql("BEGIN")
+        conn.sql("insert into t values (1)")
+        assert i1.sql("select * from t") == [[1]]
+        # Errors don't abort the transaction.
+        with pytest.raises(TarantoolError, match="Duplicate key exists"):
+            conn.sql("insert into t values (1)")
+        conn.sql("insert into t values (2)")
+        conn.sql("ROLLBACK")
+
+    assert i1.sql("select * from t order by a") == [[1], [2]]
+
+
 def test_tcl(cluster: Cluster):
+    cluster.set_config_file(yaml=MVCC_CONFIG)
     cluster.deploy(instance_count=2)
     i1 = cluster.instances[0]
 
@@ -19,3 +60,100 @@ def test_tcl(cluster: Cluster):
 
     tcl = i1.sql("""ROLLBACK""")
     assert tcl["row_count"] == 0
+
+
+def test_transaction_commit_and_rollback(cluster: Cluster):
+    cluster.set_config_file(yaml=MVCC_CONFIG)
+    cluster.deploy(instance_count=2)
+    i1, i2 = cluster.instances
+
+    ddl = i1.sql(
+        """
+        create table t (a int not null, b int, primary key (a))
+        using memtx distributed by (a)
+        """
+    )
+    assert ddl["row_count"] == 1
+
+    with i1.connect(timeout=5) as conn:
+        conn.sql("BEGIN")
+        dml = conn.sql("insert into t values (1, 1)")
+        assert dml["row_count"] == 1
+        dml = conn.sql("update t set b = 2 where a = 1")
+        assert dml["row_count"] == 1
+
+        # Changes are visible inside the transaction.
+        data = conn.sql("select * from t where a = 1")
+        assert data["rows"] == [[1, 2]]
+
+        # But not outside of it until the transaction is committed.
+        assert i1.sql("select * from t where a = 1") == []
+        assert i2.sql("select * from t where a = 1") == []
+
+        conn.sql("COMMIT")
+
+    assert i1.sql("select * from t where a = 1") == [[1, 2]]
+    assert i2.sql("select * from t where a = 1") == [[1, 2]]
+
+    with i1.connect(timeout=5) as conn:
+        conn.sql("BEGIN")
+        conn.sql("delete from t where a = 1")
+        assert conn.sql("select * from t where a = 1")["rows"] == []
+        conn.sql("ROLLBACK")
+
+    assert i1.sql("select * from t where a = 1") == [[1, 2]]
+
+    # Transaction is rolled back when the session is closed.
+    with i1.connect(timeout=5) as conn:
+        conn.sql("BEGIN")
+        conn.sql("insert into t values (2, 2)")
+
+    assert i1.sql("select * from t where a = 2") == []
+
+
+def test_transaction_errors(cluster: Cluster):
+    cluster.set_config_file(yaml=MVCC_CONFIG)
+    cluster.deploy(instance_count=2)
+    i1 = cluster.instances[0]
+
+    i1.sql(
+        """
+        create table t (a int not null, primary key (a))
+        using memtx distributed by (a)
+        """
+    )
+    i1.sql("create table g (a int not null, primary key (a)) distributed globally")
+
+    with i1.connect(timeout=5) as conn:
+        conn.sql("BEGIN")
+        with pytest.raises(TarantoolError, match="DDL, ACL and plugin operations are not supported"):
+            conn.sql("create table t2 (a int primary key)")
+        with pytest.raises(TarantoolError, match="DML on global tables is not supported"):
+            conn.sql("insert into g values (1)")
+        conn.sql("ROLLBACK")
+
+    with i1.connect(timeout=5) as conn:
+        conn.sql("BEGIN")
+        conn.sql("insert into t values (1)")
+        # With two replicasets a full scan touches both of them.
+        with pytest.raises(TarantoolError, match="transaction can't span multiple replicasets"):
+            conn.sql("select * from t")
+        with pytest.raises(TarantoolError, match="current transaction is aborted"):
+            conn.sql("insert into t values (2)")
+        with pytest.raises(TarantoolError, match="transaction has been rolled back"):
+            conn.sql("COMMIT")
+
+    assert i1.sql("select * from t") == []
+
+    # Errors raised on the router abort the transaction as well.
+    with i1.connect(timeout=5) as conn:
+        conn.sql("BEGIN")
+        conn.sql("insert into t values (1)")
+        with pytest.raises(TarantoolError, match="table with name .*missing.* not found"):
+            conn.sql("select * from missing")
+        with pytest.raises(TarantoolError, match="current transaction is aborted"):
+            conn.sql("insert into t values (2)")
+        with pytest.raises(TarantoolError, match="transaction has been rolled back"):
+            conn.sql("COMMIT")
+
+    assert i1.sql("select * from t") == []
@@ -1,6 +1,7 @@
 from pg8000 import DatabaseError  # type: ignore
 import pytest
 import pg8000.dbapi as pg  # type: ignore
+import psycopg
 from conftest import Postgres, Cluster, log_crawler
 from framework.ldap import LdapServer, is_glauth_available
 from framework.port_distributor import PortDistributor
@@ -127,6 +128,60 @@ def test_user_auth_failure_counter_resets_on_success(postgres: Postgres):
     pg.Connection(user, password=password, host=postgres.host, port=postgres.port)
 
 
+def test_auth_scram(postgres: Postgres):
+    i1 = postgres.instance
+
+    user = "scram-user"
+    password = "P@ssw0rd"
+    i1.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}' USING scram-sha-256")
+
+    # only the verifier is stored
+    user_def = i1.call("box.space._pico_user.index._pico_user_name:get", user)
+    assert user_def[6].startswith("SCRAM-SHA-256$4096:")
+    assert password not in user_def[6]
+
+    conn = pg.Connection(user, password=password, host=postgres.host, port=postgres.port)
+    conn.run("SELECT 1")
+    conn.close()
+
+    with pytest.raises(pg.DatabaseError, match=f"authentication failed for user '{user}'"):
+        pg.Connection(user, password="WrongPassword1", host=postgres.host, port=postgres.port)
+
+    # libpq supports SCRAM too
+    psycopg.connect(
+        f"user={user} password={password} host={postgres.host} port={postgres.port} sslmode=disable"
+    ).close()
+
+    # the verifier is updated along with the password
+    another_password = "An0therP@ssw0rd"
+    i1.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{another_password}' USING scram-sha-256")
+    with pytest.raises(pg.DatabaseError, match=f"authentication failed for user '{user}'"):
+        pg.Connection(user, password=password, host=postgres.host, port=postgres.port)
+    pg.Connection(user, password=another_password, host=postgres.host, port=postgres.port).close()
+
+    # and removed when the method is changed
+    i1.sql(f"ALTER USER \"{user}\" WITH PASSWORD '{another_password}' USING md5")
+    user_def = i1.call("box.space._pico_user.index._pico_user_name:get", user)
+    assert user_def[6] is None
+    pg.Connection(user, password=another_password, host=postgres.host, port=postgres.port).close()
+
+
+def test_auth_scram_blocked_user(postgres: Postgres):
+    i1 = postgres.instance
+
+    user = "scram-user"
+    password = "P@ssw0rd"
+    i1.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}' USING scram-sha-256")
+
+    # user is banned after 4 failures in a row
+    for _ in range(4):
+        with pytest.raises(pg.DatabaseError, match=f"authentication failed for user '{user}'"):
+            pg.Connection(user, password="WrongPassword1", host=postgres.host, port=postgres.port)
+
+    with pytest.raises(pg.DatabaseError, match=f"authentication failed for user '{user}'"):
+        pg.Connection(user, password=password, host=postgres.host, port=postgres.port)
+
+
 @pytest.mark.skipif(
     not is_glauth_available(),
     reason=("need installed glauth"),
@@ -0,0 +1,234 @@
+from conftest import Postgres, Retriable
+import psycopg
+import pytest
+import threading
+import time
+
+
+USER = "postgres"
+PASSWORD = "P@ssw0rd"
+
+
+def connect(postgres: Postgres) -> psycopg.Connection:
+    conn = psycopg.connect(
+        f"user = {USER} password={PASSWORD} host={postgres.host} port={postgres.port} sslmode=disable"
+    )
+    conn.autocommit = True
+    return conn
+
+
+def setup_connection(postgres: Postgres) -> psycopg.Connection:
+    postgres.instance.sql(f"CREATE USER \"{USER}\" WITH PASSWORD '{PASSWORD}'")
+    postgres.instance.sql(f'GRANT CREATE TABLE TO "{USER}"', sudo=True)
+
+    conn = connect(postgres)
+
+    conn.execute(
+        """
+        CREATE TABLE T (
+            ID INT NOT NULL,
+            PRIMARY KEY (ID)
+        )
+        USING MEMTX DISTRIBUTED BY (ID);
+        """
+    )
+    conn.execute("INSERT INTO T VALUES (1), (2), (3)")
+    return conn
+
+
+def test_cancel_request(postgres: Postgres):
+    i1 = postgres.instance
+    conn = setup_connection(postgres)
+    assert conn.info.backend_pid is not None
+
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", True)
+
+    def cancel():
+        time.sleep(1)
+        conn.cancel()
+
+    thread = threading.Thread(target=cancel)
+    thread.start()
+    with pytest.raises(psycopg.errors.QueryCanceled, match="canceling statement due to user request"):
+        conn.execute("SELECT * FROM T")
+    thread.join()
+
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", False)
+
+    # the session is still usable
+    assert sorted(conn.execute("SELECT * FROM T").fetchall()) == [(1,), (2,), (3,)]
+
+    # cancel request without a running query does nothing
+    conn.cancel()
+    assert sorted(conn.execute("SELECT * FROM T").fetchall()) == [(1,), (2,), (3,)]
+
+
+def test_cancel_query_lua_api(postgres: Postgres):
+    i1 = postgres.instance
+    conn = setup_connection(postgres)
+
+    assert i1.call("pico.sql_queries") == []
+    assert i1.call("pico.cancel_query", "no-such-query") is False
+
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", True)
+
+    errors = []
+
+    def run():
+        try:
+            conn.execute("SELECT * FROM T")
+        except psycopg.Error as e:
+            errors.append(e)
+
+    thread = threading.Thread(target=run)
+    thread.start()
+
+    def running_query():
+        queries = i1.call("pico.sql_queries")
+        assert len(queries) == 1
+        return queries[0]
+
+    query = Retriable(timeout=10, rps=5).call(running_query)
+    assert query["session_kind"] == "pgproto"
+    assert query["cancelled"] is False
+
+    assert i1.call("pico.cancel_query", query["request_id"]) is True
+    thread.join()
+    assert len(errors) == 1
+    assert isinstance(errors[0], psycopg.errors.QueryCanceled)
+
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", False)
+    assert i1.call("pico.sql_queries") == []
+
+
+def test_statement_timeout(postgres: Postgres):
+    i1 = postgres.instance
+    conn = setup_connection(postgres)
+
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", True)
+
+    # session timeout
+    conn.execute("SET statement_timeout = '500ms'")
+    with pytest.raises(psycopg.errors.QueryCanceled, match="canceling statement due to statement timeout"):
+        conn.execute("SELECT * FROM T")
+
+    # system default
+    conn.execute("SET statement_timeout TO DEFAULT")
+    i1.sql("ALTER SYSTEM SET sql_statement_timeout = 0.5")
+    with pytest.raises(psycopg.errors.QueryCanceled, match="canceling statement due to statement timeout"):
+        conn.execute("SELECT * FROM T")
+
+    with pytest.raises(psycopg.errors.InternalError, match="SET LOCAL statement_timeout"):
+        conn.execute("SET LOCAL statement_timeout = 1000")
+
+    # zero disables the system default in the session
+    conn.execute("SET statement_timeout = 0")
+
+    def unblock():
+        time.sleep(1)
+        i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", False)
+
+    thread = threading.Thread(target=unblock)
+    thread.start()
+    assert sorted(conn.execute("SELECT * FROM T").fetchall()) == [(1,), (2,), (3,)]
+    thread.join()
+
+
+def test_user_query_max(postgres: Postgres):
+    i1 = postgres.instance
+    conn = setup_connection(postgres)
+    other_conn = connect(postgres)
+
+    i1.sql("ALTER SYSTEM SET sql_user_query_max = 1")
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", True)
+
+    rows = []
+    thread = threading.Thread(target=lambda: rows.extend(conn.execute("SELECT * FROM T").fetchall()))
+    thread.start()
+
+    def query_is_running():
+        assert len(i1.call("pico.sql_queries")) == 1
+
+    Retriable(timeout=10, rps=5).call(query_is_running)
+
+    with pytest.raises(psycopg.errors.InternalError, match="too many concurrent queries of the user"):
+        other_conn.execute("SELECT * FROM T")
+
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", False)
+    thread.join()
+    assert sorted(rows) == [(1,), (2,), (3,)]
+
+    assert sorted(other_conn.execute("SELECT * FROM T").fetchall()) == [(1,), (2,), (3,)]
+
+
+def test_cancel_running_insert(postgres: Postgres):
+    i1 = postgres.instance
+    conn = setup_connection(postgres)
+
+    # The storage blocks right after the first tuple is inserted. Blocking
+    # yields, like vinyl DML does, so the cancellation reaches the fragment.
+    # Memtx DML doesn't yield and can be interrupted only by the timeout.
+    i1.call("pico._inject_error", "BLOCK_SQL_FRAGMENT_EXECUTION", True)
+
+    errors = []
+
+    def run():
+        try:
+            conn.execute("INSERT INTO T VALUES (4), (5), (6)")
+        except psycopg.Error as e:
+            errors.append(e)
+
+    thread = threading.Thread(target=run)
+    thread.start()
+
+    def running_query():
+        queries = i1.call("pico.sql_queries")
+        assert len(queries) == 1
+        return queries[0]
+
+    query = Retriable(timeout=10, rps=5).call(running_query)
+    assert i1.call("pico.cancel_query", query["request_id"]) is True
+
+    # The router waits for the storage to roll back the changes.
+    time.sleep(0.5)
+    assert thread.is_alive()
+
+    i1.call("pico._inject_error", "BLOCK_SQL_FRAGMENT_EXECUTION", False)
+    thread.join()
+    assert len(errors) == 1
+    assert isinstance(errors[0], psycopg.errors.QueryCanceled)
+
+    assert sorted(conn.execute("SELECT * FROM T").fetchall()) == [(1,), (2,), (3,)]
+
+
+def test_user_statement_timeout(postgres: Postgres):
+    i1 = postgres.instance
+    conn = setup_connection(postgres)
+
+    i1.sql(f"ALTER USER \"{USER}\" SET statement_timeout = '500ms'")
+
+    # The storage blocks right after the first tuple is inserted
+    # and detects the expired timeout once it is unblocked.
+    i1.call("pico._inject_error", "BLOCK_SQL_FRAGMENT_EXECUTION", True)
+    with pytest.raises(psycopg.errors.QueryCanceled, match="canceling statement due to statement timeout"):
+        conn.execute("INSERT INTO T VALUES (4), (5), (6)")
+    i1.call("pico._inject_error", "BLOCK_SQL_FRAGMENT_EXECUTION", False)
+
+    time.sleep(0.5)
+    assert sorted(conn.execute("SELECT * FROM T").fetchall()) == [(1,), (2,), (3,)]
+
+    # the session value overrides the user's default
+    conn.execute("SET statement_timeout = 0")
+    i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", True)
+
+    def unblock():
+        time.sleep(1)
+        i1.call("pico._inject_error", "BLOCK_SQL_EXECUTE_ON_STORAGE", False)
+
+    thread = threading.Thread(target=unblock)
+    thread.start()
+    assert sorted(conn.execute("SELECT * FROM T").fetchall()) == [(1,), (2,), (3,)]
+    thread.join()
+
+    i1.sql(f'ALTER USER "{USER}" SET statement_timeout TO DEFAULT')
+    assert i1.sql(f"SELECT statement_timeout FROM _pico_user WHERE name = '{USER}'") == [[None]]
@@ -0,0 +1,153 @@
+from conftest import Postgres
+import psycopg
+import pytest
+
+
+def setup_connection(postgres: Postgres) -> psycopg.Connection:
+    user = "postgres"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+
+    postgres.instance.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}'")
+    postgres.instance.sql(f'GRANT CREATE TABLE TO "{user}"', sudo=True)
+
+    conn = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    conn.execute(
+        """
+        CREATE TABLE warehouse (
+            id INT NOT NULL,
+            name TEXT,
+            PRIMARY KEY (id)
+        )
+        USING MEMTX DISTRIBUTED BY (id);
+        """
+    )
+    conn.execute("CREATE UNIQUE INDEX warehouse_name ON warehouse (name)")
+    postgres.instance.sql("CREATE VIEW warehouse_names AS SELECT name FROM warehouse", sudo=True)
+    return conn
+
+
+def test_pg_catalog(postgres: Postgres):
+    conn = setup_connection(postgres)
+    cur = conn.cursor()
+
+    cur.execute("SELECT nspname FROM pg_catalog.pg_namespace")
+    assert {"public", "pg_catalog", "information_schema"} <= {row[0] for row in cur.fetchall()}
+
+    cur.execute(
+        """
+        SELECT c.relname, c.relkind
+        FROM pg_catalog.pg_class c
+        JOIN pg_catalog.pg_namespace n ON c.relnamespace = n.oid
+        WHERE n.nspname = 'public' AND c.relname LIKE 'warehouse%'
+        """
+    )
+    assert sorted(cur.fetchall()) == [
+        ("warehouse", "r"),
+        ("warehouse_name", "i"),
+        ("warehouse_names", "v"),
+        ("warehouse_pkey", "i"),
+    ]
+
+    # pg_catalog relations are accessible without the schema name
+    cur.execute("SELECT relnatts FROM pg_class WHERE relname = 'warehouse'")
+    assert cur.fetchall() == [(2,)]
+
+    # the hidden bucket_id column is not listed
+    cur.execute(
+        """
+        SELECT a.attname, a.attnum, a.attnotnull, t.typname
+        FROM pg_catalog.pg_attribute a
+        JOIN pg_catalog.pg_class c ON a.attrelid = c.oid
+        JOIN pg_catalog.pg_type t ON a.atttypid = t.oid
+        WHERE c.relname = 'warehouse'
+        ORDER BY a.attnum
+        """
+    )
+    assert cur.fetchall() == [
+        ("id", 1, True, "int8"),
+        ("name", 2, False, "text"),
+    ]
+
+    cur.execute(
+        """
+        SELECT c.relname, i.indisunique, i.indisprimary, i.indkey
+        FROM pg_catalog.pg_index i
+        JOIN pg_catalog.pg_class c ON i.indexrelid = c.oid
+        WHERE c.relname LIKE 'warehouse%'
+        """
+    )
+    assert sorted(cur.fetchall()) == [
+        ("warehouse_name", True, False, "2"),
+        ("warehouse_pkey", True, True, "1"),
+    ]
+
+    # oids fit into the unsigned 32-bit PostgreSQL oid type
+    cur.execute("SELECT max(oid) FROM pg_catalog.pg_class")
+    assert cur.fetchall()[0][0] < 2**32
+
+    cur.execute("SELECT rolname, rolsuper FROM pg_catalog.pg_roles WHERE rolname IN ('admin', 'postgres')")
+    assert sorted(cur.fetchall()) == [("admin", True), ("postgres", False)]
+
+    # rolcanlogin reflects the login privilege
+    postgres.instance.sql("CREATE ROLE reader", sudo=True)
+    cur.execute("SELECT rolcanlogin FROM pg_catalog.pg_roles WHERE rolname IN ('postgres', 'reader') ORDER BY rolname")
+    assert cur.fetchall() == [(True,), (False,)]
+    postgres.instance.sql('ALTER USER "postgres" WITH NOLOGIN', sudo=True)
+    cur.execute("SELECT rolcanlogin FROM pg_catalog.pg_roles WHERE rolname = 'postgres'")
+    assert cur.fetchall() == [(False,)]
+
+    conn.close()
+
+
+def test_information_schema(postgres: Postgres):
+    conn = setup_connection(postgres)
+    cur = conn.cursor()
+
+    cur.execute(
+        """
+        SELECT table_name, table_type FROM information_schema.tables
+        WHERE table_schema = 'public' AND table_name LIKE 'warehouse%'
+        """
+    )
+    assert sorted(cur.fetchall()) == [
+        ("warehouse", "BASE TABLE"),
+        ("warehouse_names", "VIEW"),
+    ]
+
+    cur.execute(
+        """
+        SELECT column_name, ordinal_position, is_nullable
+        FROM information_schema.columns
+        WHERE table_name = 'warehouse'
+        ORDER BY ordinal_position
+        """
+    )
+    assert cur.fetchall() == [("id", 1, "NO"), ("name", 2, "YES")]
+
+    cur.execute(
+        """
+        SELECT constraint_name, column_name
+        FROM information_schema.key_column_usage
+        WHERE table_name = 'warehouse'
+        """
+    )
+    assert sorted(cur.fetchall()) == [
+        ("warehouse_name", "name"),
+        ("warehouse_pkey", "id"),
+    ]
+
+    # the catalog reflects the schema changes immediately
+    postgres.instance.sql("DROP VIEW warehouse_names", sudo=True)
+    cur.execute("SELECT table_name FROM information_schema.tables WHERE table_name LIKE 'warehouse%'")
+    assert cur.fetchall() == [("warehouse",)]
+
+    # the catalogs are emulated and can't be created
+    with pytest.raises(psycopg.InternalError, match="schema pg_catalog already exists"):
+        conn.execute("CREATE SCHEMA pg_catalog")
+    conn.execute("CREATE SCHEMA IF NOT EXISTS information_schema")
+
+    conn.close()
@@ -0,0 +1,133 @@
+from conftest import Postgres
+import psycopg
+import pytest
+
+
+def setup_connection(postgres: Postgres) -> psycopg.Connection:
+    user = "postgres"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+
+    postgres.instance.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}'")
+    postgres.instance.sql(f'GRANT CREATE TABLE TO "{user}"', sudo=True)
+
+    conn = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    conn.execute(
+        """
+        CREATE TABLE T (
+            ID INT NOT NULL,
+            NAME TEXT,
+            PRIMARY KEY (ID)
+        )
+        USING MEMTX DISTRIBUTED BY (ID);
+        """
+    )
+    return conn
+
+
+def test_copy_from_stdin(postgres: Postgres):
+    conn = setup_connection(postgres)
+    cur = conn.cursor()
+
+    # text format, rows are split between CopyData messages at arbitrary positions
+    with cur.copy("COPY T FROM STDIN") as copy:
+        copy.write("1\tone\n2\t")
+        copy.write("\\N\n3\tthree\\tthree\n")
+    assert cur.statusmessage == "COPY 3"
+
+    # csv format with a header and an explicit column list
+    with cur.copy("COPY T (NAME, ID) FROM STDIN WITH (FORMAT csv, HEADER true)") as copy:
+        copy.write('name,id\n"four, four",4\n,5\n"",6\n')
+    assert cur.statusmessage == "COPY 3"
+
+    # binary format
+    with cur.copy("COPY T (ID, NAME) FROM STDIN (FORMAT binary)") as copy:
+        copy.set_types(["int8", "text"])
+        for i in range(7, 1007):
+            copy.write_row((i, f"name{i}"))
+    assert cur.statusmessage == "COPY 1000"
+
+    cur.execute("SELECT ID, NAME FROM T WHERE ID < 10")
+    assert sorted(cur.fetchall()) == [
+        (1, "one"),
+        (2, None),
+        (3, "three\tthree"),
+        (4, "four, four"),
+        (5, None),
+        (6, ""),
+        (7, "name7"),
+        (8, "name8"),
+        (9, "name9"),
+    ]
+    cur.execute("SELECT count(*) FROM T")
+    assert cur.fetchall() == [(1006,)]
+
+    # batches are committed on their own, the error tells which rows were loaded
+    postgres.instance.sql("ALTER SYSTEM SET sql_motion_row_max = 100", sudo=True)
+    with pytest.raises(
+        psycopg.errors.InternalError,
+        match=r"expected 2 columns, got 1; rows 1-500 \(batches 1-5\) have been committed",
+    ):
+        with cur.copy("COPY T FROM STDIN") as copy:
+            for i in range(2000, 2500):
+                copy.write(f"{i}\tname{i}\n")
+            copy.write("3000\n")
+
+    # the session is still usable
+    cur.execute("SELECT count(*) FROM T")
+    assert cur.fetchall() == [(1506,)]
+
+    with pytest.raises(psycopg.errors.FeatureNotSupported, match="COPY to or from a file"):
+        cur.execute("COPY T FROM '/tmp/t.csv'")
+
+
+def test_copy_schema(postgres: Postgres):
+    conn = setup_connection(postgres)
+    cur = conn.cursor()
+    postgres.instance.sql("CREATE SCHEMA s", sudo=True)
+    postgres.instance.sql(
+        "CREATE TABLE s.t (id INT NOT NULL, note TEXT, PRIMARY KEY (id)) DISTRIBUTED BY (id)",
+        sudo=True,
+    )
+    postgres.instance.sql('GRANT USAGE ON SCHEMA s TO "postgres"', sudo=True)
+    postgres.instance.sql('GRANT READ ON TABLE s.t TO "postgres"', sudo=True)
+    postgres.instance.sql('GRANT WRITE ON TABLE s.t TO "postgres"', sudo=True)
+
+    # the qualified name refers to the table of the schema, not to public.t
+    with cur.copy("COPY s.t FROM STDIN") as copy:
+        copy.write("1\tin s\n")
+    with cur.copy("COPY s.t TO STDOUT") as copy:
+        assert b"".join(copy) == b"1\tin s\n"
+    cur.execute("SELECT count(*) FROM public.t")
+    assert cur.fetchall() == [(0,)]
+
+    # unqualified names are looked up in the schemas of the search path
+    cur.execute("SET search_path TO s, public")
+    with cur.copy("COPY t (id, note) FROM STDIN (FORMAT csv)") as copy:
+        copy.write("2,also in s\n")
+    with cur.copy("COPY t (id) TO STDOUT") as copy:
+        assert sorted(b"".join(copy).split()) == [b"1", b"2"]
+
+
+def test_copy_to_stdout(postgres: Postgres):
+    conn = setup_connection(postgres)
+    cur = conn.cursor()
+    cur.execute("INSERT INTO T VALUES (1, 'one'), (2, NULL), (3, 'a,b')")
+
+    with cur.copy("COPY (SELECT ID, NAME FROM T ORDER BY ID) TO STDOUT") as copy:
+        data = b"".join(copy)
+    assert data == b"1\tone\n2\t\\N\n3\ta,b\n"
+    assert cur.statusmessage == "COPY 3"
+
+    with cur.copy("COPY (SELECT ID, NAME FROM T ORDER BY ID) TO STDOUT WITH (FORMAT csv, HEADER true)") as copy:
+        data = b"".join(copy)
+    assert data == b'id,name\n1,one\n2,\n3,"a,b"\n'
+
+    with cur.copy("COPY T (ID, NAME) TO STDOUT (FORMAT binary)") as copy:
+        copy.set_types(["int8", "text"])
+        rows = list(copy.rows())
+    assert sorted(rows) == [(1, "one"), (2, None), (3, "a,b")]
+    assert cur.statusmessage == "COPY 3"
@@ -116,3 +116,40 @@ def test_repeating_options(postgres: Postgres):
         match=r"Exceeded maximum number of rows \(1\) in virtual table: 2",
     ):
         conn.execute("SELECT * FROM (VALUES (1), (2))")
+
+
+def test_sql_recursion_row_max_default(postgres: Postgres):
+    user = "postgres"
+    password = "Passw0rd"
+    host = postgres.host
+    port = postgres.port
+
+    postgres.instance.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}'")
+    postgres.instance.sql("ALTER SYSTEM SET sql_recursion_row_max = 10", sudo=True)
+
+    conn = psycopg.connect(
+        f"postgres://{user}:{password}@{host}:{port}",
+        autocommit=True,
+    )
+    infinite = """
+        WITH RECURSIVE cte (n) AS (
+            SELECT 1
+            UNION ALL
+            SELECT n + 1 FROM cte
+        )
+        SELECT count(*) FROM cte
+    """
+
+    # The default is set with ALTER SYSTEM.
+    with pytest.raises(
+        psycopg.InternalError,
+        match=r"Exceeded maximum number of rows \(10\) in recursive CTE",
+    ):
+        conn.execute(infinite)
+
+    # Specify "sql_recursion_row_max" in a query so the default is not used.
+    with pytest.raises(
+        psycopg.InternalError,
+        match=r"Exceeded maximum number of rows \(20\) in recursive CTE",
+    ):
+        conn.execute(infinite + " OPTION (sql_recursion_row_max = 20)")
@@ -68,6 +68,23 @@ def test_mtls_with_unknown_cert(postgres_with_mtls: Postgres):
         try_connect_psycopg(postgres_with_mtls, client_tls_pair_name="self-signed")
 
 
+def test_scram_channel_binding(postgres_with_tls: Postgres):
+    postgres_with_tls.instance.sql(f"CREATE USER \"{USER}\" WITH PASSWORD '{PASSWORD}' USING scram-sha-256")
+
+    # SCRAM-SHA-256-PLUS is offered over TLS only
+    try_connect_psycopg(postgres_with_tls, channel_binding="require")
+    try_connect_psycopg(postgres_with_tls, channel_binding="disable")
+    try_connect_pg8000(postgres_with_tls, sslmode="require")
+
+
+def test_scram_channel_binding_without_ssl(postgres: Postgres):
+    postgres.instance.sql(f"CREATE USER \"{USER}\" WITH PASSWORD '{PASSWORD}' USING scram-sha-256")
+
+    try_connect_psycopg(postgres, sslmode="disable", channel_binding="prefer")
+    with pytest.raises(psycopg.OperationalError, match="channel binding"):
+        try_connect_psycopg(postgres, sslmode="disable", channel_binding="require")
+
+
 USER = "user"
 PASSWORD = "P@ssw0rd"
 
@@ -89,7 +106,12 @@ def try_connect_pg8000(postgres: Postgres, sslmode: str = "require"):
     pg.Connection(USER, password=PASSWORD, host=postgres.host, port=postgres.port, ssl_context=ssl_context).close()
 
 
-def try_connect_psycopg(postgres: Postgres, client_tls_pair_name: str | None = None, sslmode: str = "require"):
+def try_connect_psycopg(
+    postgres: Postgres,
+    client_tls_pair_name: str | None = None,
+    sslmode: str = "require",
+    channel_binding: str | None = None,
+):
     host = postgres.host
     port = postgres.port
     connection_string = f"\
@@ -106,4 +128,7 @@ def try_connect_psycopg(postgres: Postgres, client_tls_pair_name: str | None = N
         connection_string += f" sslcert={client_cert_path} sslkey={client_key_path}"
         os.chmod(client_key_path, 0o600)
 
+    if channel_binding is not None:
+        connection_string += f" channel_binding={channel_binding}"
+
     psycopg.connect(connection_string).close()
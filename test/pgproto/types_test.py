@@ -172,6 +172,141 @@ def test_uuid(postgres: Postgres):
     assert sorted(cur.fetchall()) == [(id1,), (id2,)]
 
 
+def test_bytea(postgres: Postgres):
+    user = "postgres"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+
+    postgres.instance.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}'")
+    postgres.instance.sql(f'GRANT CREATE TABLE TO "{user}"', sudo=True)
+
+    conn = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    conn.execute(
+        """
+        CREATE TABLE T (
+            ID INT NOT NULL,
+            DATA BYTEA,
+            PRIMARY KEY (ID)
+        )
+        USING MEMTX DISTRIBUTED BY (ID);
+        """
+    )
+
+    data1 = b"\x00\x01binary\xff"
+    data2 = b"\xde\xad\xbe\xef"
+
+    # test text decoding
+    conn.execute(""" INSERT INTO T VALUES(1, %t); """, (data1,))
+
+    # test binary decoding
+    conn.execute(""" INSERT INTO T VALUES(2, %b); """, (data2,))
+
+    # test hex literal
+    conn.execute(""" INSERT INTO T VALUES(3, X'CAFE'); """)
+
+    expected = [(1, data1), (2, data2), (3, b"\xca\xfe")]
+
+    # test text encoding
+    cur = conn.execute(""" SELECT * FROM T; """, binary=False)
+    assert sorted(cur.fetchall()) == expected
+
+    # test binary encoding
+    cur = conn.execute(""" SELECT * FROM T; """, binary=True)
+    assert sorted(cur.fetchall()) == expected
+
+    cur = conn.execute(""" SELECT length(DATA), substring(DATA from 2 for 2) FROM T WHERE ID = 2; """)
+    assert cur.fetchall() == [(4, b"\xad\xbe")]
+
+
+def test_interval(postgres: Postgres):
+    user = "postgres"
+    password = "P@ssw0rd"
+    host = postgres.host
+    port = postgres.port
+
+    postgres.instance.sql(f"CREATE USER \"{user}\" WITH PASSWORD '{password}'")
+    postgres.instance.sql(f'GRANT CREATE TABLE TO "{user}"', sudo=True)
+
+    conn = psycopg.connect(f"user = {user} password={password} host={host} port={port} sslmode=disable")
+    conn.autocommit = True
+
+    conn.execute(
+        """
+        CREATE TABLE T (
+            ID INT NOT NULL,
+            TS DATETIME,
+            DURATION INTERVAL,
+            PRIMARY KEY (ID)
+        )
+        USING MEMTX DISTRIBUTED BY (ID);
+        """
+    )
+
+    ts = datetime.datetime(2024, 3, 15, 10, 30, 45, tzinfo=datetime.timezone.utc)
+    duration1 = datetime.timedelta(days=1, hours=2)
+    duration2 = datetime.timedelta(minutes=90, microseconds=5)
+
+    # test text decoding
+    conn.execute(""" INSERT INTO T VALUES(1, %t, %t); """, (ts, duration1))
+
+    # test binary decoding
+    conn.execute(""" INSERT INTO T VALUES(2, %b, %b); """, (ts, duration2))
+
+    # test interval literal
+    conn.execute(""" INSERT INTO T VALUES(3, %t, INTERVAL '1 week'); """, (ts,))
+
+    expected = [
+        (1, ts, duration1),
+        (2, ts, duration2),
+        (3, ts, datetime.timedelta(weeks=1)),
+    ]
+
+    # test text encoding
+    cur = conn.execute(""" SELECT * FROM T; """, binary=False)
+    assert sorted(cur.fetchall()) == expected
+
+    # test binary encoding
+    cur = conn.execute(""" SELECT * FROM T; """, binary=True)
+    assert sorted(cur.fetchall()) == expected
+
+    # test datetime arithmetic
+    cur = conn.execute(""" SELECT TS + DURATION, TS - INTERVAL '30 minutes' FROM T WHERE ID = 1; """)
+    assert cur.fetchall() == [
+        (
+            datetime.datetime(2024, 3, 16, 12, 30, 45, tzinfo=datetime.timezone.utc),
+            datetime.datetime(2024, 3, 15, 10, 0, 45, tzinfo=datetime.timezone.utc),
+        )
+    ]
+
+    # test date/time functions
+    cur = conn.execute(
+        """
+        SELECT date_trunc('hour', TS), date_trunc('week', TS), extract(year FROM TS),
+               date_part('dow', TS), extract(epoch FROM DURATION)
+        FROM T WHERE ID = 1;
+        """
+    )
+    assert cur.fetchall() == [
+        (
+            datetime.datetime(2024, 3, 15, 10, 0, 0, tzinfo=datetime.timezone.utc),
+            datetime.datetime(2024, 3, 11, 0, 0, 0, tzinfo=datetime.timezone.utc),
+            2024,
+            5,
+            93600,
+        )
+    ]
+
+    cur = conn.execute(""" SELECT age(TS + INTERVAL '2 days 3 hours', TS) FROM T WHERE ID = 1; """)
+    assert cur.fetchall() == [(datetime.timedelta(days=2, hours=3),)]
+
+    # bucketing by hour
+    cur = conn.execute(""" SELECT date_trunc('hour', TS) AS h, count(*) FROM T GROUP BY date_trunc('hour', TS); """)
+    assert cur.fetchall() == [(datetime.datetime(2024, 3, 15, 10, 0, 0, tzinfo=datetime.timezone.utc), 3)]
+
+
 def test_text_and_varchar(postgres: Postgres):
     user = "postgres"
     password = "P@ssw0rd"